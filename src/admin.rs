@@ -3,23 +3,75 @@
 //! 此模块包含所有管理员和紧急控制功能，包括：
 //! - 合约配置更新（铸造者、基础URI、外部合约等）
 //! - 合约暂停和恢复
-//! - 紧急资金提取
-//! - 待处理状态清理
+//! - 紧急资金提取（可选的守护者多签审批）
+//! - 销毁监听合约管理
+//! - 受信任操作员注册表与操作员紧急停用
+//! - 运营角色管理与合约托管库存空投分发
+//! - 待处理状态清理（分批存储清理）
+//! - 系列重命名（分批改写 NFT 的系列 ID）
+//! - 旧部署的二级索引升级（分批重建）
+//! - 合成价值上限（防止高阶类型供应失控）
+//! - 维护公告
+//! - 保留 NFT ID 区间（仅所有者可铸造的特殊藏品）
 
-use cosmwasm_std::{DepsMut, MessageInfo, Response, Coin};
+use alloc::collections::BTreeSet;
+
+use cosmwasm_std::{
+    to_json_binary, Deps, DepsMut, Env, MessageInfo, Response, Coin, Storage, Order, Addr, Uint128,
+    Binary, StdResult, Api,
+};
+use cw_storage_plus::Bound;
 
 use crate::error::ContractError;
-use crate::state::{CONFIG, CONTRACT_PAUSED};
-use crate::helpers::check_contract_paused;
+use crate::pagination::{clamp_limit, cursor_scope, decode_cursor, into_page, start_bound, MAX_LIMIT};
+use crate::msg::{WithdrawGuardiansResponse, PendingWithdrawalResponse, BurnListenersResponse,
+                BlockedRecipientsResponse, ProvenanceHashResponse,
+                MetaOracleResponse, MetaOracleNonceResponse, UriTemplate, UriTemplatesResponse, PendingMinterResponse,
+                CollectionInfoResponse, StorageStatsResponse, TrustedOperatorsResponse,
+                DistributeItem, RoleMembersResponse, CraftingAllowanceResponse,
+                MaintenanceInfoResponse, ReservedIdRangeResponse, MinterSetLockResponse, EventVerbosityResponse, Cursor,
+                DeterministicTokenIdResponse};
+use crate::state::{
+    CONFIG, CONTRACT_PAUSED, GC_CURSOR, GcCursor, GcPhase, ALL_TOKENS, TOKEN_OWNERSHIP,
+    TOKEN_APPROVALS, TOKENS_BY_OWNER, SERIES_TOKEN_COUNT, SERIES_NEXT_SERIAL, BURN_GRACE_PERIOD,
+    WITHDRAW_GUARDIANS, GuardianConfig, PENDING_WITHDRAWALS, PendingWithdrawal, NEXT_WITHDRAWAL_ID,
+    PENDING_GUARDIAN_CHANGE, PendingGuardianChange, UNAPPROVED_WITHDRAW_WINDOW, UnapprovedWithdrawWindow,
+    BURN_LISTENERS, BLOCKED_RECIPIENTS, PROVENANCE_HASH,
+    META_ORACLE_PUBKEY, SPENT_ORACLE_NONCES, URI_TEMPLATES, TOKEN_META, SERIES_OWNERS,
+    PENDING_MINTER, MinterRotation, COLLECTION_INFO, CollectionInfo, TOTAL_SUPPLY,
+    TRUSTED_OPERATORS, TRUSTED_OPERATOR_OPT_INS, SUSPENDED_OPERATORS, ROLE_MEMBERS,
+    TOKENS_BY_SERIES, TOKENS_BY_SERIAL, TOKEN_INDEXES_READY, INDEX_UPGRADE, IndexUpgradeCursor, IndexUpgradePhase,
+    COUNTS_BY_OWNER_KIND, OWNER_KIND_COUNTS_READY, CRAFTING_LIMIT, CraftingLimit,
+    VOTING_POWER, VOTING_POWER_SINCE, DELEGATED_POWER,
+    MAINTENANCE_NOTICE, MaintenanceNotice, RESERVED_ID_RANGE, ReservedIdRange, MINTER_SET_LOCKED_AT,
+    EVENT_VERBOSITY, DETERMINISTIC_TOKEN_IDS, PENDING_BURNS,
+};
+use crate::helpers::{validate_base_uri, validate_provenance_hash, validate_uri_template, parse_token_id,
+                     validate_series_id, increase_series_token_count, decrease_series_token_count,
+                     validate_collection_info, track_storage_entry, storage_count, storage_counter,
+                     ensure_role, validate_recipient, move_token, ensure_not_session_locked, ensure_not_escrowed,
+                     token_indexed, update_token_index, current_crafted_window,
+                     diff_token_meta, next_auto_token_id, ensure_minter_set_unlocked, verbose_events,
+                     derive_token_id, deterministic_ids_enabled, next_series_serial,
+                     synthesis_series_id, SYNTHESIS_SERIES_PREFIX};
+use crate::context::ExecuteContext;
+use crate::treasury::record_treasury_withdrawal;
+use crate::deposits::ensure_withdrawable;
+use crate::events::{emit_update_base_uri_event, emit_minter_handover_event, emit_distribute_event,
+                    emit_index_upgrade_event, emit_meta_update_event};
+use crate::types::{EventVerbosity, Role};
 
 // ========== 管理员执行接口 ==========
 
 /// 更新铸造者地址
 /// 
-/// 更改合约的铸造者地址，只有合约所有者可以执行此操作
+/// 立即更改合约的铸造者地址，并取消待生效的铸造者轮换，只有合约所有者可以执行此操作。
+/// 需要平滑交接时应使用 `ProposeMinter`
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于记录交接高度
 /// - `info`: 消息信息，包含发送者
 /// - `new_minter`: 新的铸造者地址
 /// 
@@ -27,62 +79,331 @@ use crate::helpers::check_contract_paused;
 /// - `Result<Response, ContractError>`: 更新结果
 pub fn execute_update_minter(
     deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
     info: MessageInfo,
     new_minter: String,
 ) -> Result<Response, ContractError> {
     // 检查合约是否暂停
-    check_contract_paused(deps.storage)?;
+    ctx.ensure_not_paused()?;
     
     // 验证所有者权限
-    let mut config = CONFIG.load(deps.storage)?;
-    if config.owner != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+    ctx.ensure_owner(&info.sender)?;
+    ensure_minter_set_unlocked(deps.storage)?;
+    let mut config = ctx.config.clone();
+    let old_minter = config.minter.clone();
 
     // 验证新铸造者地址格式并更新配置
     config.minter = deps.api.addr_validate(&new_minter)?;
     CONFIG.save(deps.storage, &config)?;
+    PENDING_MINTER.remove(deps.storage);
 
     Ok(Response::new()
         .add_attribute("action", "update_minter")
-        .add_attribute("new_minter", new_minter))
+        .add_attribute("new_minter", new_minter)
+        .add_event(emit_minter_handover_event("activated", &old_minter, &config.minter, env.block.height)))
+}
+
+/// 提议铸造者轮换
+/// 
+/// 指定新铸造者和生效高度，生效前原铸造者仍然有效，避免在途的铸造交易失败。
+/// 重复提议会覆盖之前的提议，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于校验生效高度
+/// - `info`: 消息信息，包含发送者
+/// - `new_minter`: 新铸造者地址
+/// - `activation_height`: 生效区块高度
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 提议结果
+pub fn execute_propose_minter(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    new_minter: String,
+    activation_height: u64,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+    ensure_minter_set_unlocked(deps.storage)?;
+
+    if activation_height <= env.block.height {
+        return Err(ContractError::InvalidActivationHeight { height: activation_height });
+    }
+
+    let new_minter = deps.api.addr_validate(&new_minter)?;
+    PENDING_MINTER.save(deps.storage, &MinterRotation {
+        new_minter: new_minter.clone(),
+        activation_height,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_minter")
+        .add_event(emit_minter_handover_event("proposed", &ctx.config.minter, &new_minter, activation_height)))
+}
+
+/// 使铸造者轮换生效（无需权限）
+/// 
+/// 达到生效高度后任何人（通常是新铸造者）都可以调用，将主要铸造者切换为新铸造者
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于检查生效高度
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 生效结果
+pub fn execute_activate_minter(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+) -> Result<Response, ContractError> {
+    let rotation = PENDING_MINTER.may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingMinter {})?;
+    if env.block.height < rotation.activation_height {
+        return Err(ContractError::MinterRotationNotActive { activation_height: rotation.activation_height });
+    }
+
+    let mut config = ctx.config.clone();
+    let old_minter = core::mem::replace(&mut config.minter, rotation.new_minter.clone());
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_MINTER.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "activate_minter")
+        .add_event(emit_minter_handover_event("activated", &old_minter, &rotation.new_minter, rotation.activation_height)))
+}
+
+/// 查询待生效的铸造者轮换
+pub fn query_pending_minter(deps: Deps) -> StdResult<Binary> {
+    let rotation = PENDING_MINTER.may_load(deps.storage)?;
+    to_json_binary(&PendingMinterResponse { rotation })
+}
+
+/// 永久锁定铸造者集合
+/// 
+/// 锁定后不能再更换主要铸造者（`UpdateMinter`、`ProposeMinter`）或调整额外铸造者（`SetMinter`），
+/// 待生效的铸造者轮换同时被取消。此操作不可撤销，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于记录锁定高度
+/// - `info`: 消息信息，包含发送者
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 锁定结果
+pub fn execute_lock_minter_set(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+    ensure_minter_set_unlocked(deps.storage)?;
+
+    // 取消待生效的轮换，锁定后主要铸造者不再变化
+    let cancelled_rotation = PENDING_MINTER.exists(deps.storage);
+    PENDING_MINTER.remove(deps.storage);
+    MINTER_SET_LOCKED_AT.save(deps.storage, &env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "lock_minter_set")
+        .add_attribute("minter", ctx.config.minter.to_string())
+        .add_attribute("locked_at_height", env.block.height.to_string())
+        .add_attribute("cancelled_rotation", cancelled_rotation.to_string()))
+}
+
+/// 查询铸造者集合锁定状态
+pub fn query_minter_set_lock(deps: Deps) -> StdResult<Binary> {
+    let locked_at_height = MINTER_SET_LOCKED_AT.may_load(deps.storage)?;
+    to_json_binary(&MinterSetLockResponse { locked: locked_at_height.is_some(), locked_at_height })
+}
+
+/// 查询存储使用统计
+/// 
+/// 返回各主要存储映射的条目数量，数据来自写入时维护的计数器，查询开销固定
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 存储统计响应
+pub fn query_storage_stats(deps: Deps) -> StdResult<Binary> {
+    let storage = deps.storage;
+    to_json_binary(&StorageStatsResponse {
+        tokens: TOTAL_SUPPLY.may_load(storage)?.unwrap_or(0),
+        token_approvals: storage_count(storage, storage_counter::TOKEN_APPROVALS)?,
+        operator_approvals: storage_count(storage, storage_counter::OPERATOR_APPROVALS)?,
+        recipes: storage_count(storage, storage_counter::RECIPES)?,
+        synthesis_history: storage_count(storage, storage_counter::SYNTHESIS_HISTORY)?,
+        owner_index: storage_count(storage, storage_counter::OWNER_INDEX)?,
+    })
 }
 
 /// 更新基础 URI
 /// 
-/// 更改合约的基础 URI，用于构建 NFT 的完整 URI
+/// 更改或清除合约的基础 URI，用于构建 NFT 的完整 URI
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
 /// - `info`: 消息信息，包含发送者
-/// - `base_uri`: 新的基础 URI
+/// - `base_uri`: 新的基础 URI（None 表示清除）
 /// 
 /// # 返回值
 /// - `Result<Response, ContractError>`: 更新结果
 pub fn execute_update_base_uri(
     deps: DepsMut,
+    ctx: &ExecuteContext,
     info: MessageInfo,
-    base_uri: String,
+    base_uri: Option<String>,
 ) -> Result<Response, ContractError> {
     // 检查合约是否暂停
-    check_contract_paused(deps.storage)?;
+    ctx.ensure_not_paused()?;
     
     // 验证所有者权限
-    let mut config = CONFIG.load(deps.storage)?;
-    if config.owner != info.sender {
-        return Err(ContractError::Unauthorized {});
+    ctx.ensure_owner(&info.sender)?;
+    let mut config = ctx.config.clone();
+
+    // 验证新基础 URI 格式（如果提供）
+    if let Some(ref uri) = base_uri {
+        validate_base_uri(uri)?;
     }
 
-    // 更新基础 URI
-    config.base_uri = Some(base_uri.clone());
+    // 更新基础 URI，保留原值用于事件
+    let old_base_uri = core::mem::replace(&mut config.base_uri, base_uri);
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new()
         .add_attribute("action", "update_base_uri")
-        .add_attribute("base_uri", base_uri))
+        .add_attribute("old_base_uri", old_base_uri.clone().unwrap_or_default())
+        .add_attribute("new_base_uri", config.base_uri.clone().unwrap_or_default())
+        .add_event(emit_update_base_uri_event(old_base_uri.as_deref(), config.base_uri.as_deref())))
+}
+
+
+
+/// 设置 token URI 模板
+/// 
+/// 为指定的基础 URI 协议（如 `ipfs://`、`ar://`）配置 token URI 的渲染模板，
+/// 例如将 `ipfs://` 基础 URI 渲染为网关地址，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `scheme`: 基础 URI 协议前缀
+/// - `template`: URI 模板（None 表示移除，恢复默认格式）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_uri_template(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    scheme: String,
+    template: Option<String>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    match &template {
+        Some(template) => {
+            validate_uri_template(&scheme, template)?;
+            URI_TEMPLATES.save(deps.storage, scheme.clone(), template)?;
+        }
+        None => URI_TEMPLATES.remove(deps.storage, scheme.clone()),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_uri_template")
+        .add_attribute("scheme", scheme)
+        .add_attribute("template", template.unwrap_or_default()))
+}
+
+/// 查询所有 token URI 模板
+pub fn query_uri_templates(deps: Deps) -> StdResult<Binary> {
+    let templates = URI_TEMPLATES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(scheme, template)| UriTemplate { scheme, template }))
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&UriTemplatesResponse { templates })
+}
+
+/// 设置销毁宽限期
+/// 
+/// 设置销毁后可恢复的区块数，0 表示关闭宽限期（立即销毁），只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `blocks`: 宽限期区块数
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_burn_grace_period(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    blocks: u64,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    BURN_GRACE_PERIOD.save(deps.storage, &blocks)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_burn_grace_period")
+        .add_attribute("blocks", blocks.to_string()))
 }
 
+/// 设置事件详细程度
+/// 
+/// Minimal 时省略批量铸造的逐个铸造事件、合成输入列表和元数据变更明细，
+/// 只保留汇总事件和属性。只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `verbosity`: 事件详细程度
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_event_verbosity(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    verbosity: EventVerbosity,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    EVENT_VERBOSITY.save(deps.storage, &verbosity)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_event_verbosity")
+        .add_attribute("verbosity", alloc::format!("{:?}", verbosity)))
+}
 
+/// 查询事件详细程度
+pub fn query_event_verbosity(deps: Deps) -> StdResult<Binary> {
+    let verbosity = EVENT_VERBOSITY.may_load(deps.storage)?.unwrap_or(EventVerbosity::Verbose);
+    to_json_binary(&EventVerbosityResponse { verbosity })
+}
 
 /// 暂停合约
 /// 
@@ -90,19 +411,18 @@ pub fn execute_update_base_uri(
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
 /// - `info`: 消息信息，包含发送者
 /// 
 /// # 返回值
 /// - `Result<Response, ContractError>`: 暂停结果
 pub fn execute_pause(
     deps: DepsMut,
+    ctx: &ExecuteContext,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
     // 验证所有者权限
-    let config = CONFIG.load(deps.storage)?;
-    if config.owner != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+    ctx.ensure_owner(&info.sender)?;
 
     // 设置合约为暂停状态
     CONTRACT_PAUSED.save(deps.storage, &true)?;
@@ -117,19 +437,18 @@ pub fn execute_pause(
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
 /// - `info`: 消息信息，包含发送者
 /// 
 /// # 返回值
 /// - `Result<Response, ContractError>`: 恢复结果
 pub fn execute_unpause(
     deps: DepsMut,
+    ctx: &ExecuteContext,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
     // 验证所有者权限
-    let config = CONFIG.load(deps.storage)?;
-    if config.owner != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+    ctx.ensure_owner(&info.sender)?;
 
     // 设置合约为正常运行状态
     CONTRACT_PAUSED.save(deps.storage, &false)?;
@@ -138,12 +457,31 @@ pub fn execute_unpause(
         .add_attribute("action", "unpause"))
 }
 
+/// 免审批提取的累计窗口长度（秒）
+pub const GUARDIAN_WINDOW_SECONDS: u64 = 24 * 60 * 60;
+
+/// 待审批提取和守护者配置变更的有效期（秒），过期后不能再审批
+pub const GUARDIAN_PROPOSAL_TTL_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+/// 检查待审批提案是否仍在有效期内
+fn ensure_proposal_active(env: &Env, proposed_at: u64) -> Result<(), ContractError> {
+    if env.block.time.seconds() >= proposed_at.saturating_add(GUARDIAN_PROPOSAL_TTL_SECONDS) {
+        return Err(ContractError::ProposalExpired {});
+    }
+    Ok(())
+}
+
 /// 紧急提取资金
 /// 
-/// 紧急情况下提取合约中的资金，只有合约所有者可以执行
+/// 紧急情况下提取合约中的资金，只有合约所有者可以执行。
+/// 配置了守护者且本次金额加上当前窗口内已直接提取的金额超过阈值时，
+/// 只创建待审批提取，需收集足够审批后才会转账；拆分成多笔小额提取同样受阈值约束。
+/// 用户存款不属于合约收入，提取金额不能超过合约余额减去存款总额
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于计算累计窗口
 /// - `info`: 消息信息，包含发送者
 /// - `amount`: 要提取的资金列表
 /// 
@@ -151,27 +489,1597 @@ pub fn execute_unpause(
 /// - `Result<Response, ContractError>`: 提取结果
 pub fn execute_emergency_withdraw(
     deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
     info: MessageInfo,
     amount: Vec<Coin>,
 ) -> Result<Response, ContractError> {
     // 验证所有者权限
-    let config = CONFIG.load(deps.storage)?;
-    if config.owner != info.sender {
+    ctx.ensure_owner(&info.sender)?;
+
+    // 不能动用用户存款
+    ensure_withdrawable(deps.storage, &deps.querier, &env.contract.address, &amount)?;
+
+    // 累计超过阈值时需要守护者审批
+    if let Some(guardian_config) = WITHDRAW_GUARDIANS.may_load(deps.storage)? {
+        let mut window = current_withdraw_window(deps.storage, &env)?;
+        if requires_guardian_approval(&guardian_config, &window, &amount) {
+            let id = NEXT_WITHDRAWAL_ID.may_load(deps.storage)?.unwrap_or(1);
+            NEXT_WITHDRAWAL_ID.save(deps.storage, &(id.checked_add(1).ok_or(ContractError::Overflow {})?))?;
+            PENDING_WITHDRAWALS.save(deps.storage, id, &PendingWithdrawal {
+                id,
+                amount,
+                recipient: info.sender.clone(),
+                approvals: vec![],
+                proposed_at: env.block.time.seconds(),
+            })?;
+
+            return Ok(Response::new()
+                .add_attribute("action", "emergency_withdraw_proposed")
+                .add_attribute("withdrawal_id", id.to_string())
+                .add_attribute("required_approvals", guardian_config.required.to_string()));
+        }
+
+        // 记录窗口内直接提取的金额
+        for coin in amount.iter().filter(|coin| !coin.amount.is_zero()) {
+            match window.withdrawn.iter_mut().find(|used| used.denom == coin.denom) {
+                Some(used) => used.amount = used.amount.checked_add(coin.amount).map_err(|_| ContractError::Overflow {})?,
+                None => window.withdrawn.push(coin.clone()),
+            }
+        }
+        UNAPPROVED_WITHDRAW_WINDOW.save(deps.storage, &window)?;
+    }
+
+    record_treasury_withdrawal(deps.storage, &amount)?;
+    Ok(build_withdraw_response(&info.sender, amount)
+        .add_attribute("action", "emergency_withdraw"))
+}
+
+/// 设置紧急提取守护者
+/// 
+/// 配置 m-of-n 审批规则，传入空守护者列表表示关闭审批，只有合约所有者可以执行。
+/// 尚未配置守护者时立即生效；已配置时只登记变更提案，须经当前守护者审批后生效，
+/// 避免所有者一笔交易移除或替换守护者绕过审批。合约所有者不能担任守护者
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于记录提议时间
+/// - `info`: 消息信息，包含发送者
+/// - `guardians`: 守护者地址列表
+/// - `required`: 执行所需的最少审批数
+/// - `threshold`: 需要审批的金额阈值
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置或提议结果
+pub fn execute_set_withdraw_guardians(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    guardians: Vec<String>,
+    required: u32,
+    threshold: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    // 空列表表示关闭守护者审批
+    let config = if guardians.is_empty() {
+        None
+    } else {
+        // 验证守护者地址并去重
+        let mut guardian_addrs: Vec<Addr> = Vec::with_capacity(guardians.len());
+        for guardian in &guardians {
+            let addr = deps.api.addr_validate(guardian)?;
+            if addr == ctx.config.owner {
+                return Err(ContractError::InvalidGuardianConfig { reason: "owner cannot be a guardian".to_string() });
+            }
+            if guardian_addrs.contains(&addr) {
+                return Err(ContractError::InvalidGuardianConfig { reason: "duplicate guardian".to_string() });
+            }
+            guardian_addrs.push(addr);
+        }
+
+        // 审批数必须在 1..=守护者数量 之间
+        if required == 0 || required as usize > guardian_addrs.len() {
+            return Err(ContractError::InvalidGuardianConfig { reason: "required approvals out of range".to_string() });
+        }
+        Some(GuardianConfig { guardians: guardian_addrs, required, threshold })
+    };
+    let guardian_count = config.as_ref().map_or(0, |config| config.guardians.len());
+    let required = config.as_ref().map_or(0, |config| config.required);
+
+    // 已配置守护者时，变更须经当前守护者审批
+    if WITHDRAW_GUARDIANS.exists(deps.storage) {
+        PENDING_GUARDIAN_CHANGE.save(deps.storage, &PendingGuardianChange {
+            config,
+            proposed_at: env.block.time.seconds(),
+            approvals: vec![],
+        })?;
+        return Ok(Response::new()
+            .add_attribute("action", "propose_withdraw_guardians")
+            .add_attribute("guardians", guardian_count.to_string())
+            .add_attribute("required", required.to_string()));
+    }
+
+    apply_guardian_config(deps.storage, config)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_withdraw_guardians")
+        .add_attribute("guardians", guardian_count.to_string())
+        .add_attribute("required", required.to_string()))
+}
+
+/// 审批守护者配置变更
+/// 
+/// 当前守护者审批待生效的配置变更，审批数达到当前配置的要求时立即生效；
+/// 提议超过有效期后不能再审批
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，用于检查有效期
+/// - `info`: 消息信息，包含发送者
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 审批结果
+pub fn execute_approve_guardian_change(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // 验证守护者身份
+    let guardian_config = WITHDRAW_GUARDIANS.may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if !guardian_config.guardians.contains(&info.sender) {
         return Err(ContractError::Unauthorized {});
     }
 
-    // 构建银行转账消息
-    let mut response = Response::new()
-        .add_attribute("action", "emergency_withdraw");
+    let mut pending = PENDING_GUARDIAN_CHANGE.may_load(deps.storage)?
+        .ok_or_else(|| ContractError::InvalidGuardianConfig { reason: "no pending change".to_string() })?;
+    ensure_proposal_active(&env, pending.proposed_at)?;
+    if pending.approvals.contains(&info.sender) {
+        return Err(ContractError::AlreadyApproved {});
+    }
+    pending.approvals.push(info.sender.clone());
 
-    for coin in amount {
-        let bank_msg = cosmwasm_std::BankMsg::Send {
-            to_address: info.sender.to_string(),
-            amount: vec![coin.clone()],
-        };
-        response = response.add_message(bank_msg);
+    if pending.approvals.len() < guardian_config.required as usize {
+        PENDING_GUARDIAN_CHANGE.save(deps.storage, &pending)?;
+        return Ok(Response::new()
+            .add_attribute("action", "approve_guardian_change")
+            .add_attribute("guardian", info.sender.to_string())
+            .add_attribute("approvals", pending.approvals.len().to_string()));
     }
 
-    Ok(response)
+    // 审批数已满足，变更生效
+    let guardian_count = pending.config.as_ref().map_or(0, |config| config.guardians.len());
+    apply_guardian_config(deps.storage, pending.config)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_withdraw_guardians")
+        .add_attribute("guardian", info.sender.to_string())
+        .add_attribute("guardians", guardian_count.to_string()))
+}
+
+/// 取消待审批的守护者配置变更
+/// 
+/// 只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 取消结果
+pub fn execute_cancel_guardian_change(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    if !PENDING_GUARDIAN_CHANGE.exists(deps.storage) {
+        return Err(ContractError::InvalidGuardianConfig { reason: "no pending change".to_string() });
+    }
+    PENDING_GUARDIAN_CHANGE.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_guardian_change"))
+}
+
+/// 写入守护者配置并清除待审批的变更
+fn apply_guardian_config(storage: &mut dyn Storage, config: Option<GuardianConfig>) -> StdResult<()> {
+    match config {
+        Some(config) => WITHDRAW_GUARDIANS.save(storage, &config)?,
+        None => WITHDRAW_GUARDIANS.remove(storage),
+    }
+    PENDING_GUARDIAN_CHANGE.remove(storage);
+    Ok(())
+}
+
+/// 审批紧急提取
+/// 
+/// 守护者审批待执行的紧急提取，审批数达到要求时立即转账；提议超过有效期后不能再审批
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，用于检查有效期和可提取余额
+/// - `info`: 消息信息，包含发送者
+/// - `id`: 待审批提取 ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 审批结果
+pub fn execute_approve_withdrawal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    // 验证守护者身份
+    let guardian_config = WITHDRAW_GUARDIANS.may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if !guardian_config.guardians.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut pending = PENDING_WITHDRAWALS.may_load(deps.storage, id)?
+        .ok_or(ContractError::WithdrawalNotFound { id })?;
+    ensure_proposal_active(&env, pending.proposed_at)?;
+    if pending.approvals.contains(&info.sender) {
+        return Err(ContractError::AlreadyApproved {});
+    }
+    pending.approvals.push(info.sender.clone());
+
+    // 只统计当前仍是守护者的审批，避免守护者变更后旧审批继续生效
+    let valid_approvals = pending.approvals.iter()
+        .filter(|addr| guardian_config.guardians.contains(addr))
+        .count();
+
+    if valid_approvals < guardian_config.required as usize {
+        PENDING_WITHDRAWALS.save(deps.storage, id, &pending)?;
+        return Ok(Response::new()
+            .add_attribute("action", "approve_withdrawal")
+            .add_attribute("withdrawal_id", id.to_string())
+            .add_attribute("guardian", info.sender.to_string())
+            .add_attribute("approvals", valid_approvals.to_string()));
+    }
+
+    // 审批数已满足，执行转账；提案后存款可能增加，转账前重新检查
+    ensure_withdrawable(deps.storage, &deps.querier, &env.contract.address, &pending.amount)?;
+    PENDING_WITHDRAWALS.remove(deps.storage, id);
+    record_treasury_withdrawal(deps.storage, &pending.amount)?;
+    Ok(build_withdraw_response(&pending.recipient, pending.amount)
+        .add_attribute("action", "emergency_withdraw")
+        .add_attribute("withdrawal_id", id.to_string())
+        .add_attribute("approvals", valid_approvals.to_string()))
+}
+
+/// 取消待审批的紧急提取
+/// 
+/// 只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `id`: 待审批提取 ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 取消结果
+pub fn execute_cancel_withdrawal(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    if !PENDING_WITHDRAWALS.has(deps.storage, id) {
+        return Err(ContractError::WithdrawalNotFound { id });
+    }
+    PENDING_WITHDRAWALS.remove(deps.storage, id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_withdrawal")
+        .add_attribute("withdrawal_id", id.to_string()))
+}
+
+/// 销毁监听合约数量上限（每次销毁都会向所有监听合约发送消息）
+pub const MAX_BURN_LISTENERS: u32 = 10;
+
+/// 添加销毁监听合约
+/// 
+/// 注册后，NFT 最终销毁时（立即销毁、完成宽限期销毁或作为合成输入被消耗）
+/// 该合约会收到 `BurnNotification` 消息，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `address`: 监听合约地址
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 添加结果
+pub fn execute_add_burn_listener(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let listener = deps.api.addr_validate(&address)?;
+    if !BURN_LISTENERS.has(deps.storage, listener.clone()) {
+        // 限制监听合约数量，避免销毁操作的 gas 无限增长
+        let count = BURN_LISTENERS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .count() as u32;
+        if count >= MAX_BURN_LISTENERS {
+            return Err(ContractError::TooManyBurnListeners { max: MAX_BURN_LISTENERS });
+        }
+        BURN_LISTENERS.save(deps.storage, listener.clone(), &())?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "add_burn_listener")
+        .add_attribute("listener", listener.to_string()))
+}
+
+/// 移除销毁监听合约
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `address`: 监听合约地址
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 移除结果
+pub fn execute_remove_burn_listener(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let listener = deps.api.addr_validate(&address)?;
+    if !BURN_LISTENERS.has(deps.storage, listener.clone()) {
+        return Err(ContractError::BurnListenerNotFound {});
+    }
+    BURN_LISTENERS.remove(deps.storage, listener.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_burn_listener")
+        .add_attribute("listener", listener.to_string()))
+}
+
+/// 查询销毁监听合约列表
+pub fn query_burn_listeners(deps: Deps) -> StdResult<Binary> {
+    let listeners = BURN_LISTENERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|key| key.map(|addr| addr.to_string()))
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&BurnListenersResponse { listeners })
+}
+
+/// 受信任操作员数量上限
+pub const MAX_TRUSTED_OPERATORS: u32 = 20;
+
+/// 设置受信任操作员
+/// 
+/// 将市场等操作员加入或移出注册表。选择信任注册表的用户无需逐个授权，
+/// 注册操作员即可转移、销毁和合成其 NFT，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `operator`: 操作员地址
+/// - `trusted`: true 表示加入注册表，false 表示移出
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_trusted_operator(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    operator: String,
+    trusted: bool,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let operator_addr = deps.api.addr_validate(&operator)?;
+    if trusted {
+        if !TRUSTED_OPERATORS.has(deps.storage, operator_addr.clone()) {
+            let count = TRUSTED_OPERATORS
+                .keys(deps.storage, None, None, Order::Ascending)
+                .count() as u32;
+            if count >= MAX_TRUSTED_OPERATORS {
+                return Err(ContractError::TooManyTrustedOperators { max: MAX_TRUSTED_OPERATORS });
+            }
+            TRUSTED_OPERATORS.save(deps.storage, operator_addr.clone(), &())?;
+        }
+    } else {
+        TRUSTED_OPERATORS.remove(deps.storage, operator_addr.clone());
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_trusted_operator")
+        .add_attribute("operator", operator_addr.to_string())
+        .add_attribute("trusted", trusted.to_string()))
+}
+
+/// 全局停用操作员
+/// 
+/// 紧急开关：停用后该操作员对所有用户的授权（注册表信任和按类型授权）立即失效，
+/// 恢复后原有授权重新生效，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `operator`: 操作员地址
+/// - `suspended`: true 表示停用，false 表示恢复
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_suspend_operator(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    operator: String,
+    suspended: bool,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let operator_addr = deps.api.addr_validate(&operator)?;
+    if suspended {
+        SUSPENDED_OPERATORS.save(deps.storage, operator_addr.clone(), &())?;
+    } else {
+        SUSPENDED_OPERATORS.remove(deps.storage, operator_addr.clone());
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "suspend_operator")
+        .add_attribute("operator", operator_addr.to_string())
+        .add_attribute("suspended", suspended.to_string()))
+}
+
+/// 查询受信任操作员注册表
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `owner`: 用户地址（可选，提供时返回其是否选择信任注册表）
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 注册表、停用列表和用户选择状态
+pub fn query_trusted_operators(deps: Deps, owner: Option<String>) -> StdResult<Binary> {
+    let operators = TRUSTED_OPERATORS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|key| key.map(|addr| addr.to_string()))
+        .collect::<StdResult<Vec<_>>>()?;
+    let suspended = SUSPENDED_OPERATORS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(MAX_TRUSTED_OPERATORS as usize)
+        .map(|key| key.map(|addr| addr.to_string()))
+        .collect::<StdResult<Vec<_>>>()?;
+    let opted_in = owner
+        .map(|owner| deps.api.addr_validate(&owner))
+        .transpose()?
+        .map(|owner| TRUSTED_OPERATOR_OPT_INS.has(deps.storage, owner));
+
+    to_json_binary(&TrustedOperatorsResponse { operators, suspended, opted_in })
+}
+
+/// 单次空投分发的最大数量
+pub const MAX_DISTRIBUTE_ITEMS: usize = 100;
+
+/// 设置运营角色
+/// 
+/// 授予或撤销地址的运营角色，铸造者集合锁定后不可再变更 `mint_relay` 角色，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `role`: 运营角色
+/// - `address`: 目标地址
+/// - `granted`: true 表示授予，false 表示撤销
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_role(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+    granted: bool,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+    // 中继铸造角色属于铸造者集合，锁定后不可再变更
+    if role == Role::MintRelay {
+        ensure_minter_set_unlocked(deps.storage)?;
+    }
+
+    let addr = deps.api.addr_validate(&address)?;
+    let key = (role.as_str().to_string(), addr.clone());
+    if granted {
+        ROLE_MEMBERS.save(deps.storage, key, &())?;
+    } else {
+        ROLE_MEMBERS.remove(deps.storage, key);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", addr.to_string())
+        .add_attribute("granted", granted.to_string()))
+}
+
+/// 空投分发
+/// 
+/// 将预先铸造到合约地址的 NFT 批量转给接收者，每个 NFT 发出一个分发事件。
+/// 作为礼物托管的 NFT 不可分发，只有空投角色可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，包含合约地址和当前时间
+/// - `info`: 消息信息，包含发送者
+/// - `items`: 分发项目列表
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 分发结果
+pub fn execute_distribute(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    items: Vec<DistributeItem>,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停并验证空投角色
+    ctx.ensure_not_paused()?;
+    ensure_role(deps.storage, &Role::Airdrop, &info.sender)?;
+
+    if items.is_empty() {
+        return Err(ContractError::InvalidDistribution { reason: "no items".to_string() });
+    }
+    if items.len() > MAX_DISTRIBUTE_ITEMS {
+        return Err(ContractError::InvalidDistribution {
+            reason: alloc::format!("at most {} items per distribution", MAX_DISTRIBUTE_ITEMS),
+        });
+    }
+
+    let mut response = Response::new()
+        .add_attribute("action", "distribute")
+        .add_attribute("operator", info.sender.to_string())
+        .add_attribute("count", items.len().to_string());
+    for (index, item) in items.iter().enumerate() {
+        // 错误附带出错条目的位置，便于集成方修正单条数据
+        let recipient = distribute_item(deps.storage, deps.api, &env, item)
+            .map_err(|err| err.at_batch_item(index, item.token_id))?;
+        response = response.add_event(emit_distribute_event(item.token_id, &info.sender, &recipient));
+    }
+
+    Ok(response)
+}
+
+/// 分发单个空投条目
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `api`: 地址校验接口
+/// - `env`: 环境信息，包含合约地址和当前时间
+/// - `item`: 分发条目
+/// 
+/// # 返回值
+/// - `Result<Addr, ContractError>`: 接收者地址
+fn distribute_item(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    env: &Env,
+    item: &DistributeItem,
+) -> Result<Addr, ContractError> {
+    // 只能分发合约持有且未被托管的 NFT；重复的 token ID 在第二次出现时已不属于合约
+    let owner = TOKEN_OWNERSHIP.may_load(storage, item.token_id)?
+        .ok_or(ContractError::TokenNotFound {})?;
+    if owner != env.contract.address {
+        return Err(ContractError::InvalidDistribution {
+            reason: alloc::format!("token {} is not in contract inventory", item.token_id),
+        });
+    }
+    ensure_not_escrowed(storage, item.token_id)?;
+    let meta = TOKEN_META.load(storage, item.token_id)?;
+    if meta.is_expired(env.block.time.seconds()) {
+        return Err(ContractError::TokenExpired {});
+    }
+    ensure_not_session_locked(storage, env, item.token_id)?;
+
+    let recipient = api.addr_validate(&item.recipient)?;
+    validate_recipient(storage, &env.contract.address, &recipient)?;
+    move_token(storage, env, item.token_id, &env.contract.address, &recipient, &meta.kind)?;
+    Ok(recipient)
+}
+
+/// 查询运营角色成员
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `role`: 运营角色
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 角色成员列表
+pub fn query_role_members(deps: Deps, role: Role) -> StdResult<Binary> {
+    let members = ROLE_MEMBERS
+        .prefix(role.as_str().to_string())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|key| key.map(|addr| addr.to_string()))
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&RoleMembersResponse { role, members })
+}
+
+/// 设置合成价值上限
+/// 
+/// 限制每个所有者在时间窗口内通过合成产出的兑换价值总和（以四叶草数量计），
+/// None 表示取消限制，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `limit`: 上限配置
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_crafting_limit(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    limit: Option<CraftingLimit>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let response = Response::new().add_attribute("action", "set_crafting_limit");
+    match limit {
+        Some(limit) => {
+            if limit.window_seconds == 0 {
+                return Err(ContractError::InvalidCraftingLimit {
+                    reason: "window_seconds must be positive".to_string(),
+                });
+            }
+            CRAFTING_LIMIT.save(deps.storage, &limit)?;
+            Ok(response
+                .add_attribute("max_value", limit.max_value.to_string())
+                .add_attribute("window_seconds", limit.window_seconds.to_string()))
+        }
+        None => {
+            CRAFTING_LIMIT.remove(deps.storage);
+            Ok(response.add_attribute("max_value", "unlimited"))
+        }
+    }
+}
+
+/// 查询合成价值额度
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，用于判断窗口是否结束
+/// - `owner`: 所有者地址
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 上限配置、当前窗口已产出价值和剩余额度（未设置上限时为 None）
+pub fn query_crafting_allowance(deps: Deps, env: Env, owner: String) -> StdResult<Binary> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = CRAFTING_LIMIT.may_load(deps.storage)?;
+    let (used, remaining, window_ends_at) = match &limit {
+        Some(limit) => {
+            let window = current_crafted_window(deps.storage, &env, &owner, limit.window_seconds)?;
+            (
+                window.value,
+                Some(limit.max_value.saturating_sub(window.value)),
+                Some(window.window_start.saturating_add(limit.window_seconds)),
+            )
+        }
+        None => (0, None, None),
+    };
+    to_json_binary(&CraftingAllowanceResponse { limit, used, remaining, window_ends_at })
+}
+
+/// 设置元数据预言机公钥
+/// 
+/// 预言机只能通过签名更新有限的元数据字段，无需授予其管理员权限。
+/// 更换或移除公钥时不清除已使用的更新序号，避免旧签名被重放，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `pubkey`: 预言机 secp256k1 公钥（None 表示移除预言机）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_meta_oracle(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    pubkey: Option<Binary>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    match pubkey {
+        Some(pubkey) => {
+            // secp256k1 公钥为 33 字节（压缩）或 65 字节（非压缩）
+            if pubkey.len() != 33 && pubkey.len() != 65 {
+                return Err(ContractError::InvalidOraclePayload { reason: "invalid secp256k1 public key length".to_string() });
+            }
+            META_ORACLE_PUBKEY.save(deps.storage, &pubkey)?;
+        }
+        None => META_ORACLE_PUBKEY.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_meta_oracle"))
+}
+
+/// 查询元数据预言机配置
+pub fn query_meta_oracle(deps: Deps) -> StdResult<Binary> {
+    let pubkey = META_ORACLE_PUBKEY.may_load(deps.storage)?;
+    to_json_binary(&MetaOracleResponse { pubkey })
+}
+
+/// 查询预言机更新序号是否已使用
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `nonce`: 更新序号
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 序号状态
+pub fn query_meta_oracle_nonce(deps: Deps, nonce: u64) -> StdResult<Binary> {
+    let spent = SPENT_ORACLE_NONCES.has(deps.storage, nonce);
+    to_json_binary(&MetaOracleNonceResponse { nonce, spent })
+}
+
+/// 设置集合展示信息
+/// 
+/// 设置集合描述、封面图片、外部链接和社交链接，供前端渲染集合页面，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `collection_info`: 集合展示信息
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_collection_info(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    collection_info: CollectionInfo,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    validate_collection_info(&collection_info)?;
+    COLLECTION_INFO.save(deps.storage, &collection_info)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_collection_info"))
+}
+
+/// 维护公告内容的最大长度
+const MAX_MAINTENANCE_MESSAGE_LENGTH: usize = 280;
+
+/// 设置维护公告
+/// 
+/// 发布或清除只读维护公告，不影响合约的暂停状态，只有合约所有者可以执行（暂停期间也可设置）
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于校验预计结束时间
+/// - `info`: 消息信息，包含发送者
+/// - `notice`: 维护公告，None 表示清除
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_maintenance_notice(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    notice: Option<MaintenanceNotice>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let notice = match notice {
+        Some(notice) => notice,
+        None => {
+            MAINTENANCE_NOTICE.remove(deps.storage);
+            return Ok(Response::new()
+                .add_attribute("action", "set_maintenance_notice")
+                .add_attribute("cleared", "true"));
+        }
+    };
+
+    if notice.message.is_empty() || notice.message.len() > MAX_MAINTENANCE_MESSAGE_LENGTH {
+        return Err(ContractError::InvalidMaintenanceNotice {
+            reason: alloc::format!("message must be 1 to {} bytes", MAX_MAINTENANCE_MESSAGE_LENGTH),
+        });
+    }
+    if notice.ends_at.map_or(false, |ends_at| ends_at <= env.block.time.seconds()) {
+        return Err(ContractError::InvalidMaintenanceNotice { reason: "end time already passed".to_string() });
+    }
+    MAINTENANCE_NOTICE.save(deps.storage, &notice)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_maintenance_notice")
+        .add_attribute("ends_at", notice.ends_at.map(|t| t.to_string()).unwrap_or_default()))
+}
+
+/// 查询维护公告
+/// 
+/// 预计结束时间已过的公告视为失效，不再返回
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `env`: 环境信息，用于判断公告是否失效
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 维护公告和合约暂停状态
+pub fn query_maintenance_info(deps: Deps, env: Env) -> StdResult<Binary> {
+    let now = env.block.time.seconds();
+    let notice = MAINTENANCE_NOTICE
+        .may_load(deps.storage)?
+        .filter(|notice| notice.ends_at.map_or(true, |ends_at| ends_at > now));
+    let paused = CONTRACT_PAUSED.may_load(deps.storage)?.unwrap_or(false);
+
+    to_json_binary(&MaintenanceInfoResponse {
+        active: notice.is_some(),
+        message: notice.as_ref().map(|notice| notice.message.clone()),
+        ends_at: notice.and_then(|notice| notice.ends_at),
+        paused,
+    })
+}
+
+/// 查询集合展示信息
+/// 
+/// 未设置时返回空描述和空图片，创建者为合约所有者
+pub fn query_collection_info(deps: Deps) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let info = COLLECTION_INFO.may_load(deps.storage)?.unwrap_or(CollectionInfo {
+        description: String::new(),
+        image: String::new(),
+        external_link: None,
+        socials: vec![],
+    });
+
+    to_json_binary(&CollectionInfoResponse {
+        creator: config.owner.to_string(),
+        description: info.description,
+        image: info.image,
+        external_link: info.external_link,
+        socials: info.socials,
+    })
+}
+
+/// 设置集合来源证明哈希
+/// 
+/// 记录所有预提交元数据的哈希，供收藏者在揭示后验证元数据未被调换。
+/// 哈希只能设置一次，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `hash`: 十六进制编码的 SHA-256 哈希
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_provenance_hash(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    hash: String,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    // 来源证明哈希不可更改
+    if PROVENANCE_HASH.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::ProvenanceHashAlreadySet {});
+    }
+
+    // 统一使用小写存储，便于比较
+    let hash = hash.to_ascii_lowercase();
+    validate_provenance_hash(&hash)?;
+    PROVENANCE_HASH.save(deps.storage, &hash)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_provenance_hash")
+        .add_attribute("provenance_hash", hash))
+}
+
+/// 查询集合来源证明哈希
+pub fn query_provenance_hash(deps: Deps) -> StdResult<Binary> {
+    let hash = PROVENANCE_HASH.may_load(deps.storage)?;
+    to_json_binary(&ProvenanceHashResponse { hash })
+}
+
+/// 设置禁止接收 NFT 的地址
+/// 
+/// 被禁止的地址无法作为铸造、批量铸造或转移的接收者，
+/// 用于防止 NFT 被发送到已知的销毁地址而无法找回，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `address`: 接收地址
+/// - `blocked`: 是否禁止
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_blocked_recipient(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    address: String,
+    blocked: bool,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let addr = deps.api.addr_validate(&address)?;
+    if blocked {
+        BLOCKED_RECIPIENTS.save(deps.storage, addr.clone(), &())?;
+    } else {
+        BLOCKED_RECIPIENTS.remove(deps.storage, addr.clone());
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_blocked_recipient")
+        .add_attribute("address", addr.to_string())
+        .add_attribute("blocked", blocked.to_string()))
+}
+
+/// 查询禁止接收 NFT 的地址列表
+pub fn query_blocked_recipients(deps: Deps, start_after: Option<Cursor>, limit: Option<u32>) -> StdResult<Binary> {
+    let limit = clamp_limit(limit, MAX_LIMIT);
+    let start_after = decode_cursor::<String>(cursor_scope::BLOCKED_RECIPIENTS, start_after)?;
+    let start = start_bound(start_after.map(|addr| deps.api.addr_validate(&addr)).transpose()?);
+
+    let addresses = BLOCKED_RECIPIENTS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit + 1)
+        .map(|key| key.map(|addr| addr.to_string()))
+        .collect::<StdResult<Vec<_>>>()?;
+    let page = into_page(cursor_scope::BLOCKED_RECIPIENTS, addresses, limit, |addr| addr.clone());
+
+    to_json_binary(&BlockedRecipientsResponse {
+        addresses: page.items,
+        next_cursor: page.next_cursor,
+        has_more: page.has_more,
+    })
+}
+
+/// 设置保留 NFT ID 区间
+/// 
+/// 区间内的 ID 只能由合约所有者铸造，用于博物馆、展览等特殊藏品；
+/// 合成等自动分配 ID 的场景会跳过该区间。None 表示取消保留，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `range`: 保留区间（含两端）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_reserved_id_range(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    range: Option<ReservedIdRange>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let response = Response::new().add_attribute("action", "set_reserved_id_range");
+    match range {
+        Some(range) => {
+            if range.start > range.end {
+                return Err(ContractError::InvalidReservedIdRange {
+                    reason: "start must not exceed end".to_string(),
+                });
+            }
+            if range.end == u64::MAX {
+                return Err(ContractError::InvalidReservedIdRange {
+                    reason: "range must leave ids for automatic allocation".to_string(),
+                });
+            }
+            RESERVED_ID_RANGE.save(deps.storage, &range)?;
+            Ok(response
+                .add_attribute("start", range.start.to_string())
+                .add_attribute("end", range.end.to_string()))
+        }
+        None => {
+            RESERVED_ID_RANGE.remove(deps.storage);
+            Ok(response.add_attribute("start", "none"))
+        }
+    }
+}
+
+/// 查询保留 NFT ID 区间
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 保留区间（未设置时为 None）和下一个自动分配的 ID
+pub fn query_reserved_id_range(deps: Deps) -> StdResult<Binary> {
+    let range = RESERVED_ID_RANGE.may_load(deps.storage)?;
+    let next_auto_token_id = next_auto_token_id(deps.storage)
+        .map_err(|err| cosmwasm_std::StdError::generic_err(err.to_string()))?;
+    to_json_binary(&ReservedIdRangeResponse { range, next_auto_token_id })
+}
+
+/// 启用或停用确定性 NFT ID
+/// 
+/// 启用后铸造、合成、导入和凭证铸造的 ID 由系列 ID 和系列内序号的哈希推导，
+/// 各环境中相同系列序号的 ID 一致，盲盒合约无需自行协调 ID 计数器。
+/// 切换不影响已铸造的 NFT，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `enabled`: 是否启用
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_deterministic_token_ids(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    DETERMINISTIC_TOKEN_IDS.save(deps.storage, &enabled)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_deterministic_token_ids")
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+/// 查询确定性 NFT ID
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `series_id`: 系列 ID
+/// - `serial`: 系列内序号（None 时为下一个将分配的序号）
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 是否启用、序号、推导出的 ID 及其是否已被占用
+pub fn query_deterministic_token_id(deps: Deps, series_id: String, serial: Option<u64>) -> StdResult<Binary> {
+    let serial = match serial {
+        Some(serial) => serial,
+        None => next_series_serial(deps.storage, &series_id)
+            .map_err(|err| cosmwasm_std::StdError::generic_err(err.to_string()))?,
+    };
+    let token_id = derive_token_id(&series_id, serial);
+    to_json_binary(&DeterministicTokenIdResponse {
+        enabled: deterministic_ids_enabled(deps.storage)?,
+        series_id,
+        serial,
+        token_id,
+        taken: TOKEN_META.has(deps.storage, token_id),
+    })
+}
+
+/// 查询紧急提取守护者配置
+pub fn query_withdraw_guardians(deps: Deps) -> StdResult<Binary> {
+    let config = WITHDRAW_GUARDIANS.may_load(deps.storage)?;
+    let pending_change = PENDING_GUARDIAN_CHANGE.may_load(deps.storage)?;
+    to_json_binary(&WithdrawGuardiansResponse { config, pending_change })
+}
+
+/// 查询待审批的紧急提取
+pub fn query_pending_withdrawal(deps: Deps, id: u64) -> StdResult<Binary> {
+    let withdrawal = PENDING_WITHDRAWALS.may_load(deps.storage, id)?;
+    to_json_binary(&PendingWithdrawalResponse { withdrawal })
+}
+
+/// 判断提取是否需要守护者审批
+/// 
+/// 按币种将本次金额与窗口内已直接提取的金额累加后与阈值比较
+fn requires_guardian_approval(config: &GuardianConfig, window: &UnapprovedWithdrawWindow, amount: &[Coin]) -> bool {
+    if config.guardians.is_empty() {
+        return false;
+    }
+    match config.threshold {
+        Some(threshold) => amount.iter().any(|coin| {
+            let used = window.withdrawn.iter()
+                .find(|used| used.denom == coin.denom)
+                .map_or(Uint128::zero(), |used| used.amount);
+            used.saturating_add(coin.amount) > threshold
+        }),
+        None => true,
+    }
+}
+
+/// 读取当前的免审批提取窗口
+/// 
+/// 窗口已结束时返回从当前时间开始的新窗口
+fn current_withdraw_window(storage: &dyn Storage, env: &Env) -> StdResult<UnapprovedWithdrawWindow> {
+    let now = env.block.time.seconds();
+    Ok(match UNAPPROVED_WITHDRAW_WINDOW.may_load(storage)? {
+        Some(window) if now < window.window_start.saturating_add(GUARDIAN_WINDOW_SECONDS) => window,
+        _ => UnapprovedWithdrawWindow { window_start: now, withdrawn: vec![] },
+    })
+}
+
+/// 构建银行转账响应
+fn build_withdraw_response(recipient: &Addr, amount: Vec<Coin>) -> Response {
+    let mut response = Response::new();
+    for coin in amount {
+        let bank_msg = cosmwasm_std::BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![coin],
+        };
+        response = response.add_message(bank_msg);
+    }
+    response
+}
+
+// ========== 存储清理 ==========
+
+/// 单次清理默认扫描数量
+const DEFAULT_GC_LIMIT: u32 = 30;
+
+/// 单次系列重命名最大扫描数量
+const MAX_RETAG_LIMIT: u32 = 100;
+
+/// 分批重命名系列
+/// 
+/// 按 token ID 顺序扫描最多 `limit` 个 NFT，把 `from_series` 的 NFT 改为 `to_series`，
+/// 同步更新系列现存数量、系列序号计数器和系列所有权。NFT 的系列内序号保持不变，
+/// 因此目标系列必须是新系列。返回的 `next_cursor` 用作下一批的 `cursor`，
+/// 为空表示已扫描完毕，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `from_series`: 原系列 ID
+/// - `to_series`: 新系列 ID
+/// - `limit`: 本批最多扫描的 NFT 数量
+/// - `cursor`: 上一批返回的游标（首批为 None）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 重命名结果
+pub fn execute_retag_series(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    from_series: String,
+    to_series: String,
+    limit: Option<u32>,
+    cursor: Option<u64>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    validate_series_id(&to_series)?;
+    if from_series == to_series {
+        return Err(ContractError::InvalidSeriesRetag { reason: "source and target series are the same".to_string() });
+    }
+
+    // 首批要求目标系列为新系列，并迁移系列所有权和序号计数器
+    if cursor.is_none() {
+        if SERIES_TOKEN_COUNT.may_load(deps.storage, to_series.clone())?.unwrap_or(0) > 0
+            || SERIES_NEXT_SERIAL.has(deps.storage, to_series.clone())
+        {
+            return Err(ContractError::InvalidSeriesRetag { reason: "target series already exists".to_string() });
+        }
+        if let Some(next_serial) = SERIES_NEXT_SERIAL.may_load(deps.storage, from_series.clone())? {
+            SERIES_NEXT_SERIAL.save(deps.storage, to_series.clone(), &next_serial)?;
+        }
+        if let Some(owner) = SERIES_OWNERS.may_load(deps.storage, from_series.clone())? {
+            if !SERIES_OWNERS.has(deps.storage, to_series.clone()) {
+                SERIES_OWNERS.save(deps.storage, to_series.clone(), &owner)?;
+            }
+        }
+    }
+
+    let limit = limit.unwrap_or(30).clamp(1, MAX_RETAG_LIMIT) as usize;
+    let start = cursor.map(Bound::exclusive);
+    let batch = TOKEN_META
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut retagged = 0u64;
+    let mut max_serial = 0u64;
+    let mut response = Response::new();
+    let verbose = verbose_events(deps.storage)?;
+    for (token_id, old_meta) in batch.iter() {
+        if old_meta.series_id != from_series {
+            continue;
+        }
+        let token_id = *token_id;
+        let mut meta = old_meta.clone();
+        meta.series_id = to_series.clone();
+        if verbose {
+            response = response.add_event(emit_meta_update_event(token_id, &diff_token_meta(old_meta, &meta)));
+        }
+        max_serial = max_serial.max(meta.serial_in_series);
+        TOKEN_META.save(deps.storage, token_id, &meta)?;
+        if token_indexed(deps.storage, token_id)? {
+            TOKENS_BY_SERIES.remove(deps.storage, (from_series.clone(), token_id));
+            TOKENS_BY_SERIES.save(deps.storage, (to_series.clone(), token_id), &())?;
+            TOKENS_BY_SERIAL.remove(deps.storage, (from_series.clone(), meta.serial_in_series));
+            TOKENS_BY_SERIAL.save(deps.storage, (to_series.clone(), meta.serial_in_series), &token_id)?;
+        }
+        decrease_series_token_count(deps.storage, &from_series)?;
+        retagged += 1;
+    }
+
+    if retagged > 0 {
+        increase_series_token_count(deps.storage, &to_series, retagged)?;
+
+        // 目标系列的序号计数器不能小于已迁入的序号
+        let next_serial = SERIES_NEXT_SERIAL.may_load(deps.storage, to_series.clone())?.unwrap_or(0);
+        if max_serial > next_serial {
+            SERIES_NEXT_SERIAL.save(deps.storage, to_series.clone(), &max_serial)?;
+        }
+    }
+
+    // 本批未扫满说明已到末尾
+    let next_cursor = if batch.len() == limit {
+        batch.last().map(|(token_id, _)| token_id.to_string()).unwrap_or_default()
+    } else {
+        String::new()
+    };
+
+    Ok(response
+        .add_attribute("action", "retag_series")
+        .add_attribute("from_series", from_series)
+        .add_attribute("to_series", to_series)
+        .add_attribute("scanned", batch.len().to_string())
+        .add_attribute("retagged", retagged.to_string())
+        .add_attribute("next_cursor", next_cursor))
+}
+
+/// 索引升级默认每批处理数量
+pub const DEFAULT_INDEX_UPGRADE_LIMIT: u32 = 50;
+
+/// 索引升级单批最大处理数量
+const MAX_INDEX_UPGRADE_LIMIT: u32 = 200;
+
+/// 应用索引升级
+/// 
+/// 为旧部署分批重建类型、系列、集合组索引和所有者类型持有数量，进度保存在状态中，
+/// 重复调用直至事件中 `complete` 为 true。合约暂停期间也可执行，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于记录投票权历史
+/// - `info`: 消息信息，包含发送者
+/// - `limit`: 本批最多处理的条目数量
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 升级结果
+pub fn execute_apply_index_upgrade(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let limit = limit.unwrap_or(DEFAULT_INDEX_UPGRADE_LIMIT);
+    Ok(Response::new()
+        .add_attribute("action", "apply_index_upgrade")
+        .add_event(apply_index_upgrade_batch(deps.storage, env.block.height, limit)?))
+}
+
+/// 执行一批索引升级
+/// 
+/// 由 `ApplyIndexUpgrade` 和 `migrate` 共用。首批开始时停用持有数量预检，
+/// 先分批清空旧的持有数量和投票权，再按 token ID 升序重建索引，全部完成后标记索引可用，
+/// 并从当前高度起提供投票权快照
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `height`: 当前区块高度
+/// - `limit`: 本批最多处理的条目数量
+/// 
+/// # 返回值
+/// - `Result<Event, ContractError>`: 本批的进度事件
+pub fn apply_index_upgrade_batch(
+    storage: &mut dyn Storage,
+    height: u64,
+    limit: u32,
+) -> Result<cosmwasm_std::Event, ContractError> {
+    if TOKEN_INDEXES_READY.may_load(storage)?.unwrap_or(false) {
+        return Ok(emit_index_upgrade_event("done", 0, true));
+    }
+    let limit = limit.clamp(1, MAX_INDEX_UPGRADE_LIMIT) as usize;
+
+    let cursor = match INDEX_UPGRADE.may_load(storage)? {
+        Some(cursor) => cursor,
+        None => {
+            // 升级期间持有数量不完整，合成和可合成目标查询回退到逐个检查
+            OWNER_KIND_COUNTS_READY.save(storage, &false)?;
+            // 投票权历史需要重建，重建完成前不提供快照
+            VOTING_POWER_SINCE.remove(storage);
+            IndexUpgradeCursor::default()
+        }
+    };
+
+    match cursor.phase {
+        IndexUpgradePhase::ClearCounts => {
+            // 每批删除最前面的条目，无需记录位置
+            let keys = COUNTS_BY_OWNER_KIND
+                .keys(storage, None, None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for key in &keys {
+                COUNTS_BY_OWNER_KIND.remove(storage, key.clone());
+            }
+            let next = if keys.len() < limit {
+                IndexUpgradeCursor { phase: IndexUpgradePhase::ClearVotingPower, last_token_id: None }
+            } else {
+                cursor.clone()
+            };
+            INDEX_UPGRADE.save(storage, &next)?;
+            Ok(emit_index_upgrade_event(cursor.phase.as_str(), keys.len(), false))
+        }
+        IndexUpgradePhase::ClearVotingPower => {
+            // 与持有数量相同，每批删除最前面的条目；先清空投票权，剩余额度清空委托投票权
+            let owners = VOTING_POWER
+                .keys(storage, None, None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for owner in &owners {
+                VOTING_POWER.remove(storage, owner.clone(), height)?;
+            }
+            let delegates = DELEGATED_POWER
+                .keys(storage, None, None, Order::Ascending)
+                .take(limit - owners.len())
+                .collect::<StdResult<Vec<_>>>()?;
+            for delegate in &delegates {
+                DELEGATED_POWER.remove(storage, delegate.clone(), height)?;
+            }
+            let processed = owners.len() + delegates.len();
+            let next = if processed < limit {
+                IndexUpgradeCursor { phase: IndexUpgradePhase::Build, last_token_id: None }
+            } else {
+                cursor.clone()
+            };
+            INDEX_UPGRADE.save(storage, &next)?;
+            Ok(emit_index_upgrade_event(cursor.phase.as_str(), processed, false))
+        }
+        IndexUpgradePhase::Build => {
+            let start = cursor.last_token_id.map(Bound::exclusive);
+            let batch = TOKEN_META
+                .range(storage, start, None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (token_id, meta) in &batch {
+                let owner = TOKEN_OWNERSHIP.load(storage, *token_id)?;
+                update_token_index(storage, *token_id, &owner, meta, true, height)?;
+            }
+
+            // 扫描完毕则标记索引可用，否则推进游标
+            let complete = batch.len() < limit;
+            if complete {
+                INDEX_UPGRADE.remove(storage);
+                TOKEN_INDEXES_READY.save(storage, &true)?;
+                OWNER_KIND_COUNTS_READY.save(storage, &true)?;
+                VOTING_POWER_SINCE.save(storage, &height)?;
+            } else {
+                INDEX_UPGRADE.save(storage, &IndexUpgradeCursor {
+                    phase: IndexUpgradePhase::Build,
+                    last_token_id: batch.last().map(|(token_id, _)| *token_id),
+                })?;
+            }
+            Ok(emit_index_upgrade_event(cursor.phase.as_str(), batch.len(), complete))
+        }
+    }
+}
+
+/// 单次清理最大扫描数量
+const MAX_GC_LIMIT: u32 = 100;
+
+/// 存储清理（无需权限）
+/// 
+/// 按阶段分批扫描存储，删除不再被引用的条目，进度游标保存在状态中，
+/// 任何人都可以重复调用以逐步完成清理
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于识别当前区块的合成系列
+/// - `limit`: 本次最多扫描的条目数量
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 清理结果
+pub fn execute_garbage_collect(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    let limit = limit.unwrap_or(DEFAULT_GC_LIMIT).clamp(1, MAX_GC_LIMIT) as usize;
+    let cursor = GC_CURSOR.may_load(deps.storage)?.unwrap_or_default();
+    let start_after = cursor.start_after.as_deref();
+
+    // 执行当前阶段的清理
+    let (scanned, removed, last_key) = match cursor.phase {
+        GcPhase::AllTokens => gc_all_tokens(deps.storage, start_after, limit)?,
+        GcPhase::Approvals => gc_approvals(deps.storage, start_after, limit)?,
+        GcPhase::OwnerIndex => gc_owner_index(deps.storage, start_after, limit)?,
+        GcPhase::Series => gc_series(deps.storage, &env, start_after, limit)?,
+    };
+
+    // 本阶段扫描完毕则进入下一阶段，否则记录游标以便下次继续
+    let next_cursor = if scanned < limit {
+        GcCursor { phase: cursor.phase.next(), start_after: None }
+    } else {
+        GcCursor { phase: cursor.phase.clone(), start_after: last_key }
+    };
+    GC_CURSOR.save(deps.storage, &next_cursor)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "garbage_collect")
+        .add_attribute("phase", cursor.phase.as_str())
+        .add_attribute("scanned", scanned.to_string())
+        .add_attribute("removed", removed.to_string())
+        .add_attribute("next_phase", next_cursor.phase.as_str()))
+}
+
+/// 清理没有所有者的全局索引条目
+fn gc_all_tokens(
+    storage: &mut dyn Storage,
+    start_after: Option<&str>,
+    limit: usize,
+) -> Result<(usize, u64, Option<String>), ContractError> {
+    let start = start_after.map(parse_token_id).transpose()?.map(Bound::exclusive);
+    let token_ids: Vec<u64> = ALL_TOKENS
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut removed = 0u64;
+    for token_id in &token_ids {
+        if !TOKEN_OWNERSHIP.has(storage, *token_id) {
+            ALL_TOKENS.remove(storage, *token_id);
+            removed += 1;
+        }
+    }
+
+    Ok((token_ids.len(), removed, token_ids.last().map(|id| id.to_string())))
+}
+
+/// 清理已不存在 NFT 的批准信息
+fn gc_approvals(
+    storage: &mut dyn Storage,
+    start_after: Option<&str>,
+    limit: usize,
+) -> Result<(usize, u64, Option<String>), ContractError> {
+    let start = start_after.map(parse_token_id).transpose()?.map(Bound::exclusive);
+    let token_ids: Vec<u64> = TOKEN_APPROVALS
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut removed = 0u64;
+    for token_id in &token_ids {
+        if !TOKEN_OWNERSHIP.has(storage, *token_id) {
+            TOKEN_APPROVALS.remove(storage, *token_id);
+            track_storage_entry(storage, storage_counter::TOKEN_APPROVALS, true, false)?;
+            removed += 1;
+        }
+    }
+
+    Ok((token_ids.len(), removed, token_ids.last().map(|id| id.to_string())))
+}
+
+/// 清理所有者索引中已不属于该所有者的 NFT
+fn gc_owner_index(
+    storage: &mut dyn Storage,
+    start_after: Option<&str>,
+    limit: usize,
+) -> Result<(usize, u64, Option<String>), ContractError> {
+    let start = start_after.map(|addr| Bound::exclusive(Addr::unchecked(addr)));
+    let entries: Vec<(Addr, Vec<u64>)> = TOKENS_BY_OWNER
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut removed = 0u64;
+    for (owner, tokens) in &entries {
+        let mut kept = tokens.clone();
+        kept.retain(|token_id| {
+            TOKEN_OWNERSHIP.may_load(storage, *token_id).ok().flatten().as_ref() == Some(owner)
+        });
+        if kept.len() == tokens.len() {
+            continue;
+        }
+        removed += (tokens.len() - kept.len()) as u64;
+        if kept.is_empty() {
+            TOKENS_BY_OWNER.remove(storage, owner.clone());
+            track_storage_entry(storage, storage_counter::OWNER_INDEX, true, false)?;
+        } else {
+            TOKENS_BY_OWNER.save(storage, owner.clone(), &kept)?;
+        }
+    }
+
+    Ok((entries.len(), removed, entries.last().map(|(owner, _)| owner.to_string())))
+}
+
+/// 清理已无现存 NFT 的系列计数
+/// 
+/// 普通系列保留序号计数器，避免后续铸造复用已发放的序号；
+/// 合约自动生成的合成系列不会再有新的铸造，连同序号计数器一起删除。
+/// 当前区块的合成系列仍可能继续分配序号，宽限期内可恢复的 NFT 会让系列重新启用，均跳过
+fn gc_series(
+    storage: &mut dyn Storage,
+    env: &Env,
+    start_after: Option<&str>,
+    limit: usize,
+) -> Result<(usize, u64, Option<String>), ContractError> {
+    let start = start_after.map(|series_id| Bound::exclusive(series_id.to_string()));
+    let entries: Vec<(String, u64)> = SERIES_TOKEN_COUNT
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let current_synthesis_series = synthesis_series_id(env);
+    let mut pending_series: Option<BTreeSet<String>> = None;
+    let mut removed = 0u64;
+    for (series_id, count) in &entries {
+        if *count > 0 || *series_id == current_synthesis_series {
+            continue;
+        }
+        // 待销毁 NFT 的系列只在需要时读取一次
+        if pending_series.is_none() {
+            let series = PENDING_BURNS
+                .range(storage, None, None, Order::Ascending)
+                .map(|entry| entry.map(|(_, pending)| pending.meta.series_id))
+                .collect::<StdResult<BTreeSet<_>>>()?;
+            pending_series = Some(series);
+        }
+        if pending_series.as_ref().is_some_and(|series| series.contains(series_id)) {
+            continue;
+        }
+        SERIES_TOKEN_COUNT.remove(storage, series_id.clone());
+        removed += 1;
+        if series_id.starts_with(SYNTHESIS_SERIES_PREFIX) {
+            SERIES_NEXT_SERIAL.remove(storage, series_id.clone());
+            removed += 1;
+        }
+    }
+
+    Ok((entries.len(), removed, entries.last().map(|(series_id, _)| series_id.clone())))
 }
 
@@ -0,0 +1,286 @@
+//! NFT 组合包模块
+//! 
+//! 此模块实现将多个 NFT 打包为一个整体，包括：
+//! - 创建组合包：将多个 NFT 托管到合约，登记为新的组合包
+//! - 转移组合包：组合包作为整体转移，可交由市场合约整体出售
+//! - 拆包：持有者取回组合包中的全部 NFT
+//! - 按组合包 ID 和持有者查询组合包内容
+//! 
+//! 托管期间 NFT 由合约持有，不计入持有者的持有数量和投票权
+
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage};
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::helpers::{ensure_not_session_locked, escrowed_token_meta, move_token, validate_recipient};
+use crate::msg::{BundleInfo, BundleResponse, BundlesResponse, Cursor, TokenBundleResponse};
+use crate::pagination::{clamp_limit, cursor_scope, decode_cursor, into_page, start_bound, MAX_WIDE_LIMIT};
+use crate::state::{
+    Bundle, BUNDLED_TOKENS, BUNDLES, BUNDLES_BY_OWNER, NEXT_BUNDLE_ID, TOKEN_META, TOKEN_OWNERSHIP,
+};
+
+/// 组合包的最少 NFT 数量
+pub const MIN_BUNDLE_SIZE: usize = 2;
+
+/// 组合包的最多 NFT 数量
+pub const MAX_BUNDLE_SIZE: usize = 50;
+
+// ========== 组合包执行接口 ==========
+
+/// 创建组合包
+/// 
+/// 将发送者的多个 NFT 托管到合约，登记为由发送者持有的新组合包。
+/// 任一 NFT 校验失败时整批回滚，错误中附带条目位置
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，包含合约地址和当前时间
+/// - `info`: 消息信息，发送者为组合包持有者
+/// - `token_ids`: 打包的 NFT ID 列表
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 创建结果，包含新组合包 ID
+pub fn execute_create_bundle(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    token_ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    if token_ids.len() < MIN_BUNDLE_SIZE || token_ids.len() > MAX_BUNDLE_SIZE {
+        return Err(ContractError::InvalidBundle {
+            reason: alloc::format!("bundle must contain between {} and {} tokens", MIN_BUNDLE_SIZE, MAX_BUNDLE_SIZE),
+        });
+    }
+
+    let bundle_id = NEXT_BUNDLE_ID.may_load(deps.storage)?.unwrap_or(1);
+    let now = env.block.time.seconds();
+    for (index, token_id) in token_ids.iter().enumerate() {
+        let token_id = *token_id;
+        if token_ids[..index].contains(&token_id) {
+            return Err(ContractError::InvalidBundle { reason: "duplicate token".to_string() }
+                .at_batch_item(index, token_id));
+        }
+
+        // 验证所有者身份，过期和对局中锁定的 NFT 不可打包
+        let owner = TOKEN_OWNERSHIP.may_load(deps.storage, token_id)?
+            .ok_or_else(|| ContractError::TokenNotFound {}.at_batch_item(index, token_id))?;
+        if owner != info.sender {
+            return Err(ContractError::NotOwned {}.at_batch_item(index, token_id));
+        }
+        let meta = TOKEN_META.load(deps.storage, token_id)?;
+        if meta.is_expired(now) {
+            return Err(ContractError::TokenExpired {}.at_batch_item(index, token_id));
+        }
+        ensure_not_session_locked(deps.storage, &env, token_id).map_err(|err| err.at_batch_item(index, token_id))?;
+
+        // 托管到合约
+        move_token(deps.storage, &env, token_id, &info.sender, &env.contract.address, &meta.kind)?;
+        BUNDLED_TOKENS.save(deps.storage, token_id, &bundle_id)?;
+    }
+
+    BUNDLES.save(deps.storage, bundle_id, &Bundle {
+        owner: info.sender.clone(),
+        token_ids: token_ids.clone(),
+        created_at: now,
+    })?;
+    BUNDLES_BY_OWNER.save(deps.storage, (info.sender.clone(), bundle_id), &())?;
+    NEXT_BUNDLE_ID.save(deps.storage, &(bundle_id + 1))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_bundle")
+        .add_attribute("bundle_id", bundle_id.to_string())
+        .add_attribute("owner", info.sender.to_string())
+        .add_attribute("count", token_ids.len().to_string()))
+}
+
+/// 转移组合包
+/// 
+/// 组合包中的 NFT 仍托管在合约中，只变更组合包的持有者
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，包含合约地址
+/// - `info`: 消息信息，发送者必须是组合包持有者
+/// - `bundle_id`: 组合包 ID
+/// - `recipient`: 接收者地址
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 转移结果
+pub fn execute_transfer_bundle(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    bundle_id: u64,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    let mut bundle = BUNDLES.may_load(deps.storage, bundle_id)?.ok_or(ContractError::BundleNotFound {})?;
+    if bundle.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let recipient_addr = deps.api.addr_validate(&recipient)?;
+    validate_recipient(deps.storage, &env.contract.address, &recipient_addr)?;
+
+    BUNDLES_BY_OWNER.remove(deps.storage, (bundle.owner.clone(), bundle_id));
+    BUNDLES_BY_OWNER.save(deps.storage, (recipient_addr.clone(), bundle_id), &())?;
+    bundle.owner = recipient_addr.clone();
+    BUNDLES.save(deps.storage, bundle_id, &bundle)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_bundle")
+        .add_attribute("bundle_id", bundle_id.to_string())
+        .add_attribute("from", info.sender.to_string())
+        .add_attribute("to", recipient_addr.to_string()))
+}
+
+/// 拆包
+/// 
+/// 将组合包中的 NFT 归还持有者并删除组合包。托管期间已被回收的 NFT 跳过，
+/// 与取消礼物一致，合约暂停期间也可拆包
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，包含合约地址
+/// - `info`: 消息信息，发送者必须是组合包持有者
+/// - `bundle_id`: 组合包 ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 拆包结果
+pub fn execute_unbundle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    bundle_id: u64,
+) -> Result<Response, ContractError> {
+    let bundle = BUNDLES.may_load(deps.storage, bundle_id)?.ok_or(ContractError::BundleNotFound {})?;
+    if bundle.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    BUNDLES.remove(deps.storage, bundle_id);
+    BUNDLES_BY_OWNER.remove(deps.storage, (bundle.owner.clone(), bundle_id));
+
+    let mut returned = 0u32;
+    for token_id in &bundle.token_ids {
+        BUNDLED_TOKENS.remove(deps.storage, *token_id);
+        // 托管期间已被回收的 NFT 跳过，同一 ID 可能已重新铸造给他人
+        if let Some(meta) = escrowed_token_meta(deps.storage, &env, *token_id)? {
+            move_token(deps.storage, &env, *token_id, &env.contract.address, &bundle.owner, &meta.kind)?;
+            returned += 1;
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "unbundle")
+        .add_attribute("bundle_id", bundle_id.to_string())
+        .add_attribute("owner", bundle.owner.to_string())
+        .add_attribute("returned", returned.to_string()))
+}
+
+/// 将被删除的 NFT 移出所在的组合包
+/// 
+/// 托管中的 NFT 过期被回收时调用，组合包因此变空时一并删除
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `token_id`: 被删除的 NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<()>`: 处理结果（NFT 不在组合包中时不做任何修改）
+pub(crate) fn remove_from_bundle(storage: &mut dyn Storage, token_id: u64) -> StdResult<()> {
+    let Some(bundle_id) = BUNDLED_TOKENS.may_load(storage, token_id)? else {
+        return Ok(());
+    };
+    BUNDLED_TOKENS.remove(storage, token_id);
+
+    let Some(mut bundle) = BUNDLES.may_load(storage, bundle_id)? else {
+        return Ok(());
+    };
+    bundle.token_ids.retain(|id| *id != token_id);
+    if bundle.token_ids.is_empty() {
+        BUNDLES.remove(storage, bundle_id);
+        BUNDLES_BY_OWNER.remove(storage, (bundle.owner, bundle_id));
+    } else {
+        BUNDLES.save(storage, bundle_id, &bundle)?;
+    }
+    Ok(())
+}
+
+// ========== 组合包查询接口 ==========
+
+/// 查询组合包
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `bundle_id`: 组合包 ID
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 组合包（不存在或已拆包时为 None）
+pub fn query_bundle(deps: Deps, bundle_id: u64) -> StdResult<Binary> {
+    let bundle = BUNDLES.may_load(deps.storage, bundle_id)?;
+    to_json_binary(&BundleResponse { bundle_id, bundle })
+}
+
+/// 查询 NFT 所在的组合包
+/// 
+/// 组合包中的 NFT 所有者为合约地址，可据此找到组合包及其持有者
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 组合包 ID（未打包时为 None）
+pub fn query_token_bundle(deps: Deps, token_id: u64) -> StdResult<Binary> {
+    let bundle_id = BUNDLED_TOKENS.may_load(deps.storage, token_id)?;
+    to_json_binary(&TokenBundleResponse { token_id, bundle_id })
+}
+
+/// 按持有者分页列出组合包
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `owner`: 持有者地址
+/// - `start_after`: 上一页返回的 `next_cursor`
+/// - `limit`: 返回数量限制
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 组合包 ID、内容和创建时间列表
+pub fn query_bundles(
+    deps: Deps,
+    owner: String,
+    start_after: Option<Cursor>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let limit = clamp_limit(limit, MAX_WIDE_LIMIT);
+    let start = start_bound(decode_cursor::<u64>(cursor_scope::BUNDLES, start_after)?);
+
+    let bundles = BUNDLES_BY_OWNER
+        .prefix(owner_addr)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit + 1)
+        .map(|bundle_id| {
+            let bundle_id = bundle_id?;
+            let bundle = BUNDLES.load(deps.storage, bundle_id)?;
+            Ok(BundleInfo { bundle_id, token_ids: bundle.token_ids, created_at: bundle.created_at })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    let page = into_page(cursor_scope::BUNDLES, bundles, limit, |bundle| bundle.bundle_id);
+
+    to_json_binary(&BundlesResponse {
+        owner,
+        bundles: page.items,
+        next_cursor: page.next_cursor,
+        has_more: page.has_more,
+    })
+}
@@ -0,0 +1,266 @@
+//! 销毁冷却模块
+//! 
+//! 此模块为高价值 NFT 提供两步销毁流程，包括：
+//! - 销毁冷却设置：稀有度不低于门槛的类型不能直接销毁
+//! - 销毁申请：所有者或获授权的操作员发起申请，进入冷却期
+//! - 确认销毁：冷却期结束后确认，按普通销毁流程执行
+//! - 取消申请：冷却期内外均可由所有者取消
+//! 
+//! 冷却期让所有者有机会撤销被钓鱼签名触发的销毁，避免创世级资产被立即销毁
+
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage};
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::helpers::{authorize_token_operation, ensure_not_session_locked};
+use crate::luckee::burn_token;
+use crate::msg::{BurnCooldownResponse, BurnRequestResponse};
+use crate::state::{BurnCooldown, BurnRequest, BURN_COOLDOWN, BURN_REQUESTS, TOKEN_META, TOKEN_OWNERSHIP};
+use crate::types::NftKind;
+
+// ========== 销毁冷却执行接口 ==========
+
+/// 设置销毁冷却
+/// 
+/// `min_rarity_level` 为 None 时关闭冷却，已有的销毁申请仍可确认或取消。
+/// 只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `min_rarity_level`: 需要申请销毁的最低稀有度等级（0-8）
+/// - `delay`: 冷却时间（秒，启用时必须大于 0）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_burn_cooldown(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    min_rarity_level: Option<u8>,
+    delay: u64,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let response = Response::new().add_attribute("action", "set_burn_cooldown");
+    match min_rarity_level {
+        Some(min_rarity_level) => {
+            if min_rarity_level > NftKind::Genesis.rarity_level() {
+                return Err(ContractError::InvalidBurnRequest { reason: "rarity level out of range".to_string() });
+            }
+            if delay == 0 {
+                return Err(ContractError::InvalidBurnRequest { reason: "zero delay".to_string() });
+            }
+            BURN_COOLDOWN.save(deps.storage, &BurnCooldown { min_rarity_level, delay })?;
+            Ok(response
+                .add_attribute("min_rarity_level", min_rarity_level.to_string())
+                .add_attribute("delay", delay.to_string()))
+        }
+        None => {
+            BURN_COOLDOWN.remove(deps.storage);
+            Ok(response.add_attribute("min_rarity_level", "none"))
+        }
+    }
+}
+
+/// 申请销毁 NFT
+/// 
+/// 仅适用于受销毁冷却保护的类型；冷却期结束后可通过 ConfirmBurn 确认
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于计算可确认时间
+/// - `info`: 消息信息，包含发送者
+/// - `token_id`: 要销毁的 NFT ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 申请结果
+pub fn execute_request_burn(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    let meta = TOKEN_META.may_load(deps.storage, token_id)?
+        .ok_or(ContractError::TokenNotFound {})?;
+    let owner = TOKEN_OWNERSHIP.load(deps.storage, token_id)?;
+    authorize_token_operation(deps.storage, &env, &owner, &info.sender, &meta.kind)?;
+    ensure_not_session_locked(deps.storage, &env, token_id)?;
+
+    let cooldown = match BURN_COOLDOWN.may_load(deps.storage)? {
+        Some(cooldown) if meta.kind.rarity_level() >= cooldown.min_rarity_level => cooldown,
+        _ => return Err(ContractError::InvalidBurnRequest { reason: "kind can be burned directly".to_string() }),
+    };
+    // NFT 转移时申请随之删除，已有申请一定属于当前所有者
+    if BURN_REQUESTS.has(deps.storage, token_id) {
+        return Err(ContractError::InvalidBurnRequest { reason: "already requested".to_string() });
+    }
+
+    let confirm_after = env.block.time.seconds().checked_add(cooldown.delay)
+        .ok_or(ContractError::Overflow {})?;
+    BURN_REQUESTS.save(deps.storage, token_id, &BurnRequest {
+        owner: owner.clone(),
+        requested_by: info.sender.clone(),
+        confirm_after,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "request_burn")
+        .add_attribute("token_id", token_id.to_string())
+        .add_attribute("owner", owner.to_string())
+        .add_attribute("requested_by", info.sender.to_string())
+        .add_attribute("confirm_after", confirm_after.to_string()))
+}
+
+/// 确认销毁申请
+/// 
+/// 冷却期结束后执行销毁，调用者须为所有者或获授权的操作员。
+/// NFT 转移时申请随之删除，新所有者须重新申请
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于检查冷却期
+/// - `info`: 消息信息，包含发送者
+/// - `token_id`: 要销毁的 NFT ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 销毁结果
+pub fn execute_confirm_burn(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    let request = BURN_REQUESTS.may_load(deps.storage, token_id)?
+        .ok_or(ContractError::BurnRequestNotFound {})?;
+    if env.block.time.seconds() < request.confirm_after {
+        return Err(ContractError::InvalidBurnRequest { reason: "cooldown not elapsed".to_string() });
+    }
+
+    let meta = TOKEN_META.may_load(deps.storage, token_id)?
+        .ok_or(ContractError::TokenNotFound {})?;
+    let owner = TOKEN_OWNERSHIP.load(deps.storage, token_id)?;
+    authorize_token_operation(deps.storage, &env, &owner, &info.sender, &meta.kind)?;
+    ensure_not_session_locked(deps.storage, &env, token_id)?;
+
+    BURN_REQUESTS.remove(deps.storage, token_id);
+    burn_token(deps.storage, &env, &info.sender, token_id, owner, meta)
+}
+
+/// 取消销毁申请
+/// 
+/// 当前所有者可以取消（NFT 转移时申请已随之删除），暂停期间也可执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `info`: 消息信息，包含发送者
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 取消结果
+pub fn execute_cancel_burn_request(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: u64,
+) -> Result<Response, ContractError> {
+    let request = BURN_REQUESTS.may_load(deps.storage, token_id)?
+        .ok_or(ContractError::BurnRequestNotFound {})?;
+    if info.sender != request.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    BURN_REQUESTS.remove(deps.storage, token_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_burn_request")
+        .add_attribute("token_id", token_id.to_string())
+        .add_attribute("cancelled_by", info.sender.to_string()))
+}
+
+// ========== 直接销毁的检查 ==========
+
+/// 检查类型是否可以直接销毁
+/// 
+/// 稀有度不低于销毁冷却门槛的类型须通过销毁申请
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `kind`: NFT 类型
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 受保护时返回 `BurnRequestRequired`
+pub fn ensure_burn_request_not_required(storage: &dyn Storage, kind: &NftKind) -> Result<(), ContractError> {
+    match BURN_COOLDOWN.may_load(storage)? {
+        Some(cooldown) if kind.rarity_level() >= cooldown.min_rarity_level => {
+            Err(ContractError::BurnRequestRequired { kind: kind.to_key() })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// 检查合成输入是否可以被消耗
+/// 
+/// 受销毁冷却保护的类型须有冷却期已结束的销毁申请（NFT 转移时申请随之删除，
+/// 因此申请一定属于当前所有者），合成消耗输入即视为确认该申请
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `env`: 环境信息，用于检查冷却期
+/// - `token_id`: 输入 NFT ID
+/// - `kind`: 输入 NFT 类型
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 缺少已到期的申请时返回 `BurnRequestRequired`
+pub fn ensure_synthesis_input_burnable(
+    storage: &dyn Storage,
+    env: &Env,
+    token_id: u64,
+    kind: &NftKind,
+) -> Result<(), ContractError> {
+    if ensure_burn_request_not_required(storage, kind).is_ok() {
+        return Ok(());
+    }
+    match BURN_REQUESTS.may_load(storage, token_id)? {
+        Some(request) if env.block.time.seconds() >= request.confirm_after => Ok(()),
+        _ => Err(ContractError::BurnRequestRequired { kind: kind.to_key() }),
+    }
+}
+
+// ========== 销毁冷却查询接口 ==========
+
+/// 查询销毁冷却配置
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 销毁冷却配置（关闭时为 None）
+pub fn query_burn_cooldown(deps: Deps) -> StdResult<Binary> {
+    let cooldown = BURN_COOLDOWN.may_load(deps.storage)?;
+    to_json_binary(&BurnCooldownResponse { cooldown })
+}
+
+/// 查询 NFT 的销毁申请
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 销毁申请（不存在时为 None）
+pub fn query_burn_request(deps: Deps, token_id: u64) -> StdResult<Binary> {
+    let request = BURN_REQUESTS.may_load(deps.storage, token_id)?;
+    to_json_binary(&BurnRequestResponse { token_id, request })
+}
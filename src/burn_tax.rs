@@ -0,0 +1,191 @@
+//! 合成销毁税模块
+//! 
+//! 此模块实现按配方配置的通缩规则，包括：
+//! - 销毁税设置（额外消耗一个输入 NFT，或额外收取原生代币）
+//! - 合成时按销毁税调整配方要求并收取原生代币
+//! - 累计销毁统计，供代币经济报表使用
+//! 
+//! 原生代币销毁税发送至配置的 sink 地址，未设置 sink 时直接销毁
+
+use cosmwasm_std::{
+    to_json_binary, Addr, BankMsg, Binary, Coin, Deps, DepsMut, MessageInfo, Response, StdResult, Storage,
+};
+
+use crate::context::ExecuteContext;
+use crate::deposits::charge_fee;
+use crate::error::ContractError;
+use crate::msg::{BurnTaxResponse, BurnTaxStatsResponse};
+use crate::state::{BURN_TAXES, BURN_TAX_STATS, RECIPES};
+use crate::types::{BurnTax, NftKind, Recipe};
+
+// ========== 销毁税执行接口 ==========
+
+/// 设置合成销毁税
+/// 
+/// 为目标类型的配方设置销毁税，None 表示移除，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `target`: 目标 NFT 类型
+/// - `tax`: 销毁税规则
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_burn_tax(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    target: NftKind,
+    tax: Option<BurnTax>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let recipe = RECIPES.may_load(deps.storage, target.to_key())?
+        .ok_or(ContractError::RecipeNotFound {})?;
+
+    let response = Response::new()
+        .add_attribute("action", "set_burn_tax")
+        .add_attribute("target", alloc::format!("{:?}", target));
+    match tax {
+        Some(tax) => {
+            match &tax {
+                BurnTax::ExtraInput {} => {
+                    if recipe.inputs.is_empty() {
+                        return Err(ContractError::InvalidBurnTax { reason: "recipe has no inputs".to_string() });
+                    }
+                }
+                BurnTax::NativeFee { amount, sink } => {
+                    if amount.amount.is_zero() {
+                        return Err(ContractError::InvalidBurnTax { reason: "zero fee".to_string() });
+                    }
+                    if let Some(sink) = sink {
+                        deps.api.addr_validate(sink)?;
+                    }
+                }
+            }
+            let description = describe_burn_tax(&tax, &recipe);
+            BURN_TAXES.save(deps.storage, target.to_key(), &tax)?;
+            Ok(response.add_attribute("burn_tax", description))
+        }
+        None => {
+            BURN_TAXES.remove(deps.storage, target.to_key());
+            Ok(response.add_attribute("burn_tax", "none"))
+        }
+    }
+}
+
+// ========== 合成时的销毁税处理 ==========
+
+/// 按销毁税调整配方要求
+/// 
+/// `ExtraInput` 时第一项输入的数量加一，其余规则不改变输入要求
+/// 
+/// # 参数
+/// - `recipe`: 合成配方
+/// - `tax`: 销毁税规则（未设置时为 None）
+/// 
+/// # 返回值
+/// - `Recipe`: 合成时实际校验的配方
+pub fn taxed_recipe(recipe: &Recipe, tax: Option<&BurnTax>) -> Recipe {
+    let mut recipe = recipe.clone();
+    if let (Some(BurnTax::ExtraInput {}), Some(first)) = (tax, recipe.inputs.first_mut()) {
+        first.count = first.count.saturating_add(1);
+    }
+    recipe
+}
+
+/// 收取合成销毁税并累计统计
+/// 
+/// 额外消耗的输入 NFT 随其他输入一起销毁，此处只记录统计；
+/// 原生代币销毁税从付费者存款中扣除，并返回发送至 sink 或直接销毁的消息
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `payer`: 付费地址
+/// - `recipe`: 合成配方（未按销毁税调整）
+/// - `tax`: 销毁税规则
+/// 
+/// # 返回值
+/// - `Result<(Option<BankMsg>, String), ContractError>`: 需要发出的银行消息和用于事件属性的描述
+pub fn collect_burn_tax(
+    storage: &mut dyn Storage,
+    payer: &Addr,
+    recipe: &Recipe,
+    tax: &BurnTax,
+) -> Result<(Option<BankMsg>, String), ContractError> {
+    let mut stats = BURN_TAX_STATS.may_load(storage)?.unwrap_or_default();
+    let msg = match tax {
+        BurnTax::ExtraInput {} => {
+            // 配方被改为无输入时不再要求额外输入
+            if let Some(input) = recipe.inputs.first() {
+                let value = input.nft_kind.exchange_value() as u64;
+                stats.tokens_burned = stats.tokens_burned.checked_add(1).ok_or(ContractError::Overflow {})?;
+                stats.value_burned = stats.value_burned.checked_add(value).ok_or(ContractError::Overflow {})?;
+            }
+            None
+        }
+        BurnTax::NativeFee { amount, sink } => {
+            charge_fee(storage, payer, &[], Some(amount))?;
+            add_coin(&mut stats.fees_burned, amount)?;
+            Some(match sink {
+                Some(sink) => BankMsg::Send { to_address: sink.clone(), amount: vec![amount.clone()] },
+                None => BankMsg::Burn { amount: vec![amount.clone()] },
+            })
+        }
+    };
+    BURN_TAX_STATS.save(storage, &stats)?;
+    Ok((msg, describe_burn_tax(tax, recipe)))
+}
+
+/// 生成销毁税的事件属性描述
+fn describe_burn_tax(tax: &BurnTax, recipe: &Recipe) -> String {
+    match tax {
+        BurnTax::ExtraInput {} => match recipe.inputs.first() {
+            Some(input) => alloc::format!("extra_input:{:?}", input.nft_kind),
+            None => "extra_input".to_string(),
+        },
+        BurnTax::NativeFee { amount, .. } => amount.to_string(),
+    }
+}
+
+/// 按代币累加金额
+pub fn add_coin(coins: &mut Vec<Coin>, amount: &Coin) -> Result<(), ContractError> {
+    match coins.iter_mut().find(|coin| coin.denom == amount.denom) {
+        Some(coin) => {
+            coin.amount = coin.amount.checked_add(amount.amount).map_err(|_| ContractError::Overflow {})?;
+        }
+        None => coins.push(amount.clone()),
+    }
+    Ok(())
+}
+
+// ========== 销毁税查询接口 ==========
+
+/// 查询目标类型配方的销毁税
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `target`: 目标 NFT 类型
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 销毁税规则（未设置时为 None）
+pub fn query_burn_tax(deps: Deps, target: NftKind) -> StdResult<Binary> {
+    let tax = BURN_TAXES.may_load(deps.storage, target.to_key())?;
+    to_json_binary(&BurnTaxResponse { target, tax })
+}
+
+/// 查询累计销毁统计
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 额外消耗的 NFT 数量、兑换价值和收取的原生代币
+pub fn query_burn_tax_stats(deps: Deps) -> StdResult<Binary> {
+    let stats = BURN_TAX_STATS.may_load(deps.storage)?.unwrap_or_default();
+    to_json_binary(&BurnTaxStatsResponse {
+        tokens_burned: stats.tokens_burned,
+        value_burned: stats.value_burned,
+        fees_burned: stats.fees_burned,
+    })
+}
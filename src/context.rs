@@ -0,0 +1,60 @@
+//! 执行上下文模块
+//!
+//! 此模块定义了每次执行入口加载一次的上下文结构，
+//! 包含合约配置和暂停状态，由各个处理函数共享，避免重复读取存储
+
+use cosmwasm_std::{Addr, Storage};
+
+use crate::error::ContractError;
+use crate::state::{Config, CONFIG, CONTRACT_PAUSED};
+
+/// 执行上下文
+///
+/// 在 execute 入口处加载一次，并以引用方式传递给各个处理函数
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecuteContext {
+    /// 合约配置
+    pub config: Config,
+    /// 合约是否暂停
+    pub paused: bool,
+}
+
+impl ExecuteContext {
+    /// 从存储加载执行上下文
+    ///
+    /// # 参数
+    /// - `storage`: 存储接口
+    ///
+    /// # 返回值
+    /// - `Result<Self, ContractError>`: 加载结果
+    pub fn load(storage: &dyn Storage) -> Result<Self, ContractError> {
+        let config = CONFIG.load(storage)?;
+        let paused = CONTRACT_PAUSED.may_load(storage)?.unwrap_or(false);
+        Ok(Self { config, paused })
+    }
+
+    /// 检查合约是否暂停
+    ///
+    /// # 返回值
+    /// - `Result<(), ContractError>`: 合约已暂停时返回错误
+    pub fn ensure_not_paused(&self) -> Result<(), ContractError> {
+        if self.paused {
+            return Err(ContractError::ContractPaused {});
+        }
+        Ok(())
+    }
+
+    /// 验证发送者是否为合约所有者
+    ///
+    /// # 参数
+    /// - `sender`: 发送者地址
+    ///
+    /// # 返回值
+    /// - `Result<(), ContractError>`: 非所有者时返回错误
+    pub fn ensure_owner(&self, sender: &Addr) -> Result<(), ContractError> {
+        if self.config.owner != *sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        Ok(())
+    }
+}
@@ -4,16 +4,20 @@
 //! 以及主调度逻辑。具体的功能实现都委托到各个子模块中。
 
 use cosmwasm_std::{
-    entry_point, to_json_binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
-    Binary,
+    entry_point, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    Binary, Order, Reply, Storage,
 };
 use cw2::{set_contract_version, get_contract_version};
+use cw_utils::nonpayable;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::context::ExecuteContext;
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::state::{
     Config, CONFIG, TOTAL_SUPPLY, STORAGE_VERSION, CONTRACT_PAUSED,
-    CONTRACT_INFO, ContractInfo, TOKEN_META, NEXT_TOKEN_ID,
+    CONTRACT_INFO, ContractInfo, NEXT_TOKEN_ID, OWNER_KIND_COUNTS_READY, TOKEN_INDEXES_READY,
+    TOKENS_BY_SERIAL, VOTING_POWER_SINCE, INDEX_UPGRADE, RECIPES,
+    DEPOSITS, TOTAL_DEPOSITS,
 };
 
 // 导入各个功能模块
@@ -21,6 +25,30 @@ use crate::cw721::*;      // 标准 CW721 接口实现
 use crate::luckee::*;     // Luckee 扩展功能（合成、铸造等）
 use crate::admin::*;      // 管理员功能（暂停、紧急提取等）
 use crate::recipes::*;    // 配方管理
+use crate::deposits::*;   // 预付费用存款
+use crate::pricing::*;    // 合成费用定价
+use crate::swaps::*;      // 类型互换
+use crate::gifts::*;      // 礼物领取码
+use crate::sessions::*;   // 游戏会话锁定
+use crate::burn_tax::*;   // 合成销毁税
+use crate::relay::*;      // 中继铸造
+use crate::sweeper::*;    // 过期报价和礼物清扫
+use crate::royalty::*;    // 合成版税
+use crate::custody::*;    // 托管证明
+use crate::voting::*;     // 投票权
+use crate::bundles::*;    // NFT 组合包
+use crate::htlc::*;       // 条件转移
+use crate::partners::*;   // 合作集合
+use crate::burn_queue::*; // 销毁冷却
+use crate::legacy::*;     // 旧版合约导入
+use crate::session_keys::*; // 会话密钥
+use crate::treasury::*;   // 资金统计
+use crate::factory::*;    // 集合工厂
+use crate::cross_collection::*; // 跨集合合成
+use crate::pools::*;      // 盲盒奖池
+use crate::events::emit_migrate_event;
+use crate::helpers::{validate_instantiate_fields, ensure_not_locked, guard_external_calls, release_execution_lock,
+                     REENTRANCY_GUARD_REPLY_ID};
 
 // 合约基本信息
 const CONTRACT_NAME: &str = "crates.io:luckee_nft";
@@ -33,7 +61,7 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
-/// - `_env`: 环境信息（未使用）
+/// - `env`: 环境信息，用于记录投票权历史起始高度
 /// - `info`: 消息信息，包含发送者等
 /// - `msg`: 初始化消息，包含合约配置
 /// 
@@ -42,20 +70,28 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     // 设置合约版本信息
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    // 验证名称、符号和基础 URI 格式
+    validate_instantiate_fields(&msg.name, &msg.symbol, msg.base_uri.as_deref())?;
+    let minter = deps.api.addr_validate(&msg.minter).map_err(|err| ContractError::InvalidInstantiateField {
+        field: "minter".to_string(),
+        reason: err.to_string(),
+    })?;
+
     // 创建合约配置
     let config = Config {
         name: msg.name.clone(),
         symbol: msg.symbol.clone(),
-        minter: deps.api.addr_validate(&msg.minter)?,
+        minter,
         base_uri: msg.base_uri.clone(),
         owner: info.sender.clone(),
+        accepted_denoms: vec![],
     };
 
     // 保存配置和初始状态
@@ -79,6 +115,14 @@ pub fn instantiate(
     // 初始化默认合成配方
     initialize_default_recipes(deps.storage)?;
 
+    // 从部署起完整维护各所有者的类型持有数量和二级索引
+    OWNER_KIND_COUNTS_READY.save(deps.storage, &true)?;
+    TOKEN_INDEXES_READY.save(deps.storage, &true)?;
+    VOTING_POWER_SINCE.save(deps.storage, &env.block.height)?;
+
+    // 自检：确认后续入口点依赖的存储项均已初始化
+    verify_instantiated_state(deps.storage)?;
+
     // 返回初始化成功的响应
     Ok(Response::new()
         .add_attribute("method", "instantiate")
@@ -87,9 +131,39 @@ pub fn instantiate(
         .add_attribute("minter", msg.minter))
 }
 
+/// 实例化自检
+/// 
+/// execute、query 和 migrate 直接 `load` 这些存储项，缺失时会在首次调用时才失败；
+/// 在实例化时检查可让部署直接失败并指出缺失的项
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 缺失时返回 `InstantiateSelfCheckFailed`
+fn verify_instantiated_state(storage: &dyn Storage) -> Result<(), ContractError> {
+    let checks = [
+        ("contract_version", get_contract_version(storage).is_ok()),
+        ("config", CONFIG.exists(storage)),
+        ("total_supply", TOTAL_SUPPLY.exists(storage)),
+        ("next_token_id", NEXT_TOKEN_ID.exists(storage)),
+        ("storage_version", STORAGE_VERSION.exists(storage)),
+        ("contract_paused", CONTRACT_PAUSED.exists(storage)),
+        ("contract_info", CONTRACT_INFO.exists(storage)),
+        ("recipes", RECIPES.keys(storage, None, None, Order::Ascending).next().is_some()),
+        ("owner_kind_counts_ready", OWNER_KIND_COUNTS_READY.exists(storage)),
+        ("token_indexes_ready", TOKEN_INDEXES_READY.exists(storage)),
+        ("voting_power_since", VOTING_POWER_SINCE.exists(storage)),
+    ];
+    match checks.iter().find(|(_, initialized)| !initialized) {
+        Some((item, _)) => Err(ContractError::InstantiateSelfCheckFailed { item: item.to_string() }),
+        None => Ok(()),
+    }
+}
+
 /// 合约执行入口点
 /// 
-/// 处理所有执行消息，根据消息类型路由到相应的处理函数
+/// 处理所有执行消息：检查执行锁，调度到相应的处理函数，并为外部合约调用加锁
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
@@ -101,91 +175,494 @@ pub fn instantiate(
 /// - `Result<Response, ContractError>`: 执行结果
 #[entry_point]
 pub fn execute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    // 外部合约调用尚未回复时拒绝重入
+    ensure_not_locked(deps.storage)?;
+
+    let response = dispatch_execute(deps.branch(), env, info, msg)?;
+
+    // 对响应中的外部合约调用加锁，直到对应的 reply 释放
+    guard_external_calls(deps.storage, response)
+}
+
+/// 执行消息调度
+/// 
+/// 根据消息类型路由到相应的处理函数
+fn dispatch_execute(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    // 每个入口只加载一次配置和暂停状态，并传递给各个处理函数
+    let ctx = ExecuteContext::load(deps.storage)?;
+
+    // 只有存款和合成（预付费用）会使用附带的资金，且须为接受的币种；
+    // 其余消息拒绝附带资金，避免资金滞留在合约中
+    if matches!(msg, ExecuteMsg::Deposit {} | ExecuteMsg::Synthesize { .. } | ExecuteMsg::SynthesizeWithPartners { .. }) {
+        validate_payment(&ctx.config.accepted_denoms, &info.funds)?;
+    } else {
+        nonpayable(&info)?;
+    }
+
     match msg {
         // ========== 标准 CW721 接口 ==========
         ExecuteMsg::TransferNft { recipient, token_id } => {
             // 转移 NFT 所有权
-            execute_transfer_nft(deps, env, info, recipient, token_id)
+            execute_transfer_nft(deps, &ctx, env, info, recipient, token_id)
         }
         ExecuteMsg::Approve { spender, token_id, expires } => {
             // 批准特定地址操作特定 NFT
-            execute_approve(deps, info, spender, token_id, expires)
+            execute_approve(deps, &ctx, env, info, spender, token_id, expires)
         }
         ExecuteMsg::Revoke { spender, token_id } => {
             // 撤销特定地址对特定 NFT 的批准
-            execute_revoke(deps, info, spender, token_id)
+            execute_revoke(deps, &ctx, env, info, spender, token_id)
         }
-        ExecuteMsg::ApproveAll { operator, expires } => {
+        ExecuteMsg::ApproveAll { operator, expires, max_transfers } => {
             // 批准操作员管理所有 NFT
-            execute_approve_all(deps, info, operator, expires)
+            execute_approve_all(deps, &ctx, info, operator, expires, max_transfers)
         }
         ExecuteMsg::RevokeAll { operator } => {
             // 撤销操作员对所有 NFT 的管理权限
-            execute_revoke_all(deps, info, operator)
+            execute_revoke_all(deps, &ctx, info, operator)
+        }
+        ExecuteMsg::ApproveKind { operator, kind, expires } => {
+            // 按类型授权操作员
+            execute_approve_kind(deps, &ctx, info, operator, kind, expires)
+        }
+        ExecuteMsg::RevokeKind { operator, kind } => {
+            // 撤销按类型授权
+            execute_revoke_kind(deps, &ctx, info, operator, kind)
+        }
+        ExecuteMsg::RevokeAllMyApprovals {} => {
+            // 撤销发送者授予的所有批准
+            execute_revoke_all_my_approvals(deps, info)
+        }
+        ExecuteMsg::CreateSwapOffer { offered_token_id, wanted_kind } => {
+            // 创建类型互换报价
+            execute_create_swap_offer(deps, &ctx, env, info, offered_token_id, wanted_kind)
+        }
+        ExecuteMsg::AcceptSwap { offer_id, token_id } => {
+            // 接受互换报价
+            execute_accept_swap(deps, &ctx, env, info, offer_id, token_id)
+        }
+        ExecuteMsg::CancelSwapOffer { offer_id } => {
+            // 取消互换报价
+            execute_cancel_swap_offer(deps, info, offer_id)
+        }
+        ExecuteMsg::CreateGift { token_id, claim_hash, expires } => {
+            // 创建礼物
+            execute_create_gift(deps, &ctx, env, info, token_id, claim_hash, expires)
+        }
+        ExecuteMsg::ClaimGift { preimage } => {
+            // 领取礼物
+            execute_claim_gift(deps, &ctx, env, info, preimage)
+        }
+        ExecuteMsg::CancelGift { claim_hash } => {
+            // 取回过期礼物
+            execute_cancel_gift(deps, env, info, claim_hash)
+        }
+        ExecuteMsg::TagCustody { tags } => {
+            // 标记托管 NFT 的链下账户引用
+            execute_tag_custody(deps, &ctx, env, info, tags)
+        }
+        ExecuteMsg::Delegate { to } => {
+            // 委托投票权
+            execute_delegate(deps, &ctx, env, info, to)
+        }
+        ExecuteMsg::Undelegate {} => {
+            // 取消投票权委托
+            execute_undelegate(deps, env, info)
+        }
+        ExecuteMsg::CreateBundle { token_ids } => {
+            // 创建组合包
+            execute_create_bundle(deps, &ctx, env, info, token_ids)
+        }
+        ExecuteMsg::TransferBundle { bundle_id, recipient } => {
+            // 转移组合包
+            execute_transfer_bundle(deps, &ctx, env, info, bundle_id, recipient)
+        }
+        ExecuteMsg::Unbundle { bundle_id } => {
+            // 拆包
+            execute_unbundle(deps, env, info, bundle_id)
+        }
+        ExecuteMsg::TransferWithCondition { recipient, token_id, hashlock, timeout } => {
+            // 创建条件转移
+            execute_transfer_with_condition(deps, &ctx, env, info, recipient, token_id, hashlock, timeout)
+        }
+        ExecuteMsg::ClaimConditionalTransfer { token_id, preimage } => {
+            // 领取条件转移
+            execute_claim_conditional_transfer(deps, env, info, token_id, preimage)
+        }
+        ExecuteMsg::ReclaimConditionalTransfer { token_id } => {
+            // 超时后取回条件转移
+            execute_reclaim_conditional_transfer(deps, env, info, token_id)
+        }
+        ExecuteMsg::OptInTrustedOperators {} => {
+            // 选择信任受信任操作员注册表
+            execute_set_trusted_operator_opt_in(deps, &ctx, info, true)
+        }
+        ExecuteMsg::OptOutTrustedOperators {} => {
+            // 取消信任受信任操作员注册表
+            execute_set_trusted_operator_opt_in(deps, &ctx, info, false)
         }
 
         // ========== Luckee 扩展接口 ==========
-        ExecuteMsg::Mint { token_id, owner, extension } => {
+        ExecuteMsg::Mint { token_id, owner, extension, idempotency_key } => {
             // 铸造新的 NFT
-            execute_mint(deps, info, token_id, owner, extension)
+            execute_mint(deps, &ctx, env, info, token_id, owner, extension, idempotency_key)
         }
         ExecuteMsg::Burn { token_id } => {
             // 销毁 NFT
-            execute_burn(deps, info, token_id)
+            execute_burn(deps, &ctx, env, info, token_id)
+        }
+        ExecuteMsg::BurnAllOfKind { kind, max } => {
+            // 销毁调用者持有的指定类型 NFT
+            execute_burn_all_of_kind(deps, &ctx, env, info, kind, max)
+        }
+        ExecuteMsg::RestoreBurned { token_id } => {
+            // 宽限期内恢复待销毁的 NFT
+            execute_restore_burned(deps, &ctx, env, info, token_id)
+        }
+        ExecuteMsg::FinalizeBurn { token_id } => {
+            // 宽限期结束后完成销毁
+            execute_finalize_burn(deps, &ctx, env, token_id)
+        }
+        ExecuteMsg::RequestBurn { token_id } => {
+            // 申请销毁高价值 NFT
+            execute_request_burn(deps, &ctx, env, info, token_id)
+        }
+        ExecuteMsg::ConfirmBurn { token_id } => {
+            // 冷却期结束后确认销毁
+            execute_confirm_burn(deps, &ctx, env, info, token_id)
+        }
+        ExecuteMsg::CancelBurnRequest { token_id } => {
+            // 取消销毁申请
+            execute_cancel_burn_request(deps, info, token_id)
+        }
+        ExecuteMsg::Expire { token_id } => {
+            // 回收已过期的 NFT
+            execute_expire(deps, &ctx, env, token_id)
         }
 
         // ========== 管理员接口 ==========
         ExecuteMsg::UpdateMinter { new_minter } => {
             // 更新铸造者地址
-            execute_update_minter(deps, info, new_minter)
+            execute_update_minter(deps, &ctx, env, info, new_minter)
+        }
+        ExecuteMsg::ProposeMinter { new_minter, activation_height } => {
+            // 提议铸造者轮换
+            execute_propose_minter(deps, &ctx, env, info, new_minter, activation_height)
+        }
+        ExecuteMsg::ActivateMinter {} => {
+            // 使铸造者轮换生效
+            execute_activate_minter(deps, &ctx, env)
+        }
+        ExecuteMsg::LockMinterSet {} => {
+            // 永久锁定铸造者集合
+            execute_lock_minter_set(deps, &ctx, env, info)
         }
         ExecuteMsg::UpdateBaseUri { base_uri } => {
             // 更新基础 URI
-            execute_update_base_uri(deps, info, base_uri)
+            execute_update_base_uri(deps, &ctx, info, base_uri)
+        }
+        ExecuteMsg::SetUriTemplate { scheme, template } => {
+            // 设置 token URI 模板
+            execute_set_uri_template(deps, &ctx, info, scheme, template)
+        }
+        ExecuteMsg::SetBurnGracePeriod { blocks } => {
+            // 设置销毁宽限期
+            execute_set_burn_grace_period(deps, &ctx, info, blocks)
+        }
+        ExecuteMsg::SetBurnCooldown { min_rarity_level, delay } => {
+            // 设置销毁冷却
+            execute_set_burn_cooldown(deps, &ctx, info, min_rarity_level, delay)
+        }
+        ExecuteMsg::SetEventVerbosity { verbosity } => {
+            // 设置事件详细程度
+            execute_set_event_verbosity(deps, &ctx, info, verbosity)
         }
 
         // ========== 合成相关接口 ==========
         ExecuteMsg::SetRecipe { target, recipe } => {
             // 设置合成配方
-            execute_set_recipe(deps, info, target, recipe)
+            execute_set_recipe(deps, &ctx, info, target, recipe)
         }
         ExecuteMsg::RemoveRecipe { target } => {
             // 删除合成配方
-            execute_remove_recipe(deps, info, target)
+            execute_remove_recipe(deps, &ctx, info, target)
+        }
+        ExecuteMsg::ResetRecipes { install_defaults } => {
+            // 紧急清空合成配方
+            execute_reset_recipes(deps, &ctx, info, install_defaults)
+        }
+        ExecuteMsg::SetRecipeRegistry { registry, cache_ttl } => {
+            // 设置共享配方注册表
+            execute_set_recipe_registry(deps, &ctx, env, info, registry, cache_ttl)
         }
         ExecuteMsg::Synthesize { inputs, target } => {
             // 执行合成操作
-            execute_synthesize(deps, env, info, inputs, target)
+            execute_synthesize(deps, &ctx, env, info, inputs, target, vec![])
+        }
+        ExecuteMsg::SynthesizeWithPartners { inputs, partner_inputs, target } => {
+            // 使用合作集合 NFT 执行合成
+            execute_synthesize(deps, &ctx, env, info, inputs, target, partner_inputs)
+        }
+        ExecuteMsg::SetPartnerCollection { collection, kind } => {
+            // 设置合作集合
+            execute_set_partner_collection(deps, &ctx, info, collection, kind)
+        }
+        ExecuteMsg::ReceiveNft(msg) => {
+            // 接收旧版合约 NFT 并导入，或接收合作集合 NFT
+            if is_legacy_contract(deps.storage, &info.sender)? {
+                execute_import_legacy(deps, &ctx, env, info, msg)
+            } else {
+                execute_receive_nft(deps, &ctx, info, msg)
+            }
+        }
+        ExecuteMsg::WithdrawPartnerNft { collection, token_id } => {
+            // 取回合作集合 NFT
+            execute_withdraw_partner_nft(deps, info, collection, token_id)
         }
 
         // ========== 批量操作接口 ==========
-        ExecuteMsg::BatchMint { mints } => {
+        ExecuteMsg::BatchMint { mints, idempotency_key } => {
             // 批量铸造 NFT
-            execute_batch_mint(deps, info, mints)
+            execute_batch_mint(deps, &ctx, env, info, mints, idempotency_key)
         }
         ExecuteMsg::SetMinter { minter, allowed } => {
             // 设置铸造者权限
-            execute_set_minter(deps, info, minter, allowed)
+            execute_set_minter(deps, &ctx, info, minter, allowed)
+        }
+        ExecuteMsg::SetTrustedOperator { operator, trusted } => {
+            // 加入或移出受信任操作员注册表
+            execute_set_trusted_operator(deps, &ctx, info, operator, trusted)
+        }
+        ExecuteMsg::SetRole { role, address, granted } => {
+            // 授予或撤销运营角色
+            execute_set_role(deps, &ctx, info, role, address, granted)
+        }
+        ExecuteMsg::Distribute { items } => {
+            // 从合约托管库存批量分发
+            execute_distribute(deps, &ctx, env, info, items)
+        }
+        ExecuteMsg::LockForSession { token_ids, session_id, until } => {
+            // 锁定 NFT 到游戏会话
+            execute_lock_for_session(deps, &ctx, env, info, token_ids, session_id, until)
+        }
+        ExecuteMsg::UnlockSession { session_id } => {
+            // 解锁游戏会话
+            execute_unlock_session(deps, &ctx, info, session_id)
+        }
+        ExecuteMsg::GrantSessionKey { key, scopes, duration } => {
+            // 授予会话密钥
+            execute_grant_session_key(deps, &ctx, env, info, key, scopes, duration)
+        }
+        ExecuteMsg::RevokeSessionKey { key } => {
+            // 撤销会话密钥（暂停期间仍可撤销）
+            execute_revoke_session_key(deps, info, key)
+        }
+        ExecuteMsg::SuspendOperator { operator, suspended } => {
+            // 全局停用或恢复操作员
+            execute_suspend_operator(deps, &ctx, info, operator, suspended)
+        }
+        ExecuteMsg::SetPriceOracle { oracle } => {
+            // 设置价格预言机
+            execute_set_price_oracle(deps, &ctx, info, oracle)
+        }
+        ExecuteMsg::SetLegacyImport { import } => {
+            // 设置旧版合约导入
+            execute_set_legacy_import(deps, &ctx, info, import)
+        }
+        ExecuteMsg::SetRecipeUsdCost { target, usd_cost } => {
+            // 设置配方美元费用
+            execute_set_recipe_usd_cost(deps, &ctx, info, target, usd_cost)
+        }
+        ExecuteMsg::SetBurnTax { target, tax } => {
+            // 设置配方的合成销毁税
+            execute_set_burn_tax(deps, &ctx, info, target, tax)
+        }
+        ExecuteMsg::SetRecipeOutputCollection { target, collection } => {
+            // 设置配方的产出集合
+            execute_set_recipe_output_collection(deps, &ctx, env, info, target, collection)
+        }
+        ExecuteMsg::SetSynthesisSource { collection, series_id } => {
+            // 登记跨集合合成的来源集合
+            execute_set_synthesis_source(deps, &ctx, env, info, collection, series_id)
+        }
+        ExecuteMsg::MintSynthesisOutput { owner, kind } => {
+            // 为来源集合铸造跨集合合成的产出
+            execute_mint_synthesis_output(deps, &ctx, env, info, owner, kind)
+        }
+        ExecuteMsg::CreateMintPool { pool_id, series_id, entries, size } => {
+            // 创建盲盒奖池
+            execute_create_mint_pool(deps, &ctx, info, pool_id, series_id, entries, size)
+        }
+        ExecuteMsg::CloseMintPool { pool_id } => {
+            // 关闭盲盒奖池
+            execute_close_mint_pool(deps, &ctx, info, pool_id)
+        }
+        ExecuteMsg::PoolMint { pool_id, owner } => {
+            // 从奖池抽取并铸造
+            execute_pool_mint(deps, &ctx, env, info, pool_id, owner)
+        }
+        ExecuteMsg::SetCraftingRoyalty { royalty } => {
+            // 设置高稀有度合成的创作者版税
+            execute_set_crafting_royalty(deps, &ctx, info, royalty)
+        }
+        ExecuteMsg::SetMetaOracle { pubkey } => {
+            // 设置元数据预言机公钥
+            execute_set_meta_oracle(deps, &ctx, info, pubkey)
+        }
+        ExecuteMsg::UpdateMetaSigned { token_id, payload, signature } => {
+            // 提交预言机签名的元数据更新
+            execute_update_meta_signed(deps, &ctx, env, token_id, payload, signature)
+        }
+        ExecuteMsg::SetMintVoucherSigner { pubkey } => {
+            // 设置铸造凭证签名者公钥
+            execute_set_mint_voucher_signer(deps, &ctx, info, pubkey)
+        }
+        ExecuteMsg::SetRelayQuota { relayer, quota } => {
+            // 设置中继者的铸造额度
+            execute_set_relay_quota(deps, &ctx, info, relayer, quota)
+        }
+        ExecuteMsg::RelayMint { payload, signature } => {
+            // 中继者提交签名的铸造凭证
+            execute_relay_mint(deps, &ctx, env, info, payload, signature)
+        }
+        ExecuteMsg::SetCollectionInfo { collection_info } => {
+            // 设置集合展示信息
+            execute_set_collection_info(deps, &ctx, info, collection_info)
+        }
+        ExecuteMsg::SetMaintenanceNotice { notice } => {
+            // 发布或清除维护公告
+            execute_set_maintenance_notice(deps, &ctx, env, info, notice)
+        }
+        ExecuteMsg::SetProvenanceHash { hash } => {
+            // 设置集合来源证明哈希
+            execute_set_provenance_hash(deps, &ctx, info, hash)
+        }
+        ExecuteMsg::RegisterSeries { series_id } => {
+            // 注册系列所有权
+            execute_register_series(deps, &ctx, info, series_id)
+        }
+        ExecuteMsg::GrantSeriesMinter { series_id, minter } => {
+            // 授权其他铸造者向系列铸造
+            execute_set_series_grant(deps, &ctx, info, series_id, minter, true)
+        }
+        ExecuteMsg::RevokeSeriesMinter { series_id, minter } => {
+            // 撤销其他铸造者向系列铸造的授权
+            execute_set_series_grant(deps, &ctx, info, series_id, minter, false)
+        }
+        ExecuteMsg::SetKindMintStatus { kind, status } => {
+            // 设置指定类型的铸造状态
+            execute_set_kind_mint_status(deps, &ctx, info, kind, status)
+        }
+        ExecuteMsg::SetBlockedRecipient { address, blocked } => {
+            // 设置禁止接收 NFT 的地址
+            execute_set_blocked_recipient(deps, &ctx, info, address, blocked)
+        }
+        ExecuteMsg::SetReservedIdRange { range } => {
+            // 设置仅合约所有者可铸造的保留 ID 区间
+            execute_set_reserved_id_range(deps, &ctx, info, range)
+        }
+        ExecuteMsg::SetDeterministicTokenIds { enabled } => {
+            // 启用或停用确定性 NFT ID
+            execute_set_deterministic_token_ids(deps, &ctx, info, enabled)
         }
         
         
         // ========== 访问控制和紧急机制 ==========
         ExecuteMsg::Pause {} => {
             // 暂停合约
-            execute_pause(deps, info)
+            execute_pause(deps, &ctx, info)
         }
         ExecuteMsg::Unpause {} => {
             // 恢复合约
-            execute_unpause(deps, info)
+            execute_unpause(deps, &ctx, info)
         }
         ExecuteMsg::EmergencyWithdraw { amount } => {
             // 紧急提取资金
-            execute_emergency_withdraw(deps, info, amount)
+            execute_emergency_withdraw(deps, &ctx, env, info, amount)
+        }
+        ExecuteMsg::SetWithdrawGuardians { guardians, required, threshold } => {
+            // 设置紧急提取守护者
+            execute_set_withdraw_guardians(deps, &ctx, env, info, guardians, required, threshold)
+        }
+        ExecuteMsg::ApproveWithdrawal { id } => {
+            // 守护者审批紧急提取
+            execute_approve_withdrawal(deps, env, info, id)
+        }
+        ExecuteMsg::ApproveGuardianChange {} => {
+            // 守护者审批守护者配置变更
+            execute_approve_guardian_change(deps, env, info)
+        }
+        ExecuteMsg::CancelWithdrawal { id } => {
+            // 取消待审批的紧急提取
+            execute_cancel_withdrawal(deps, &ctx, info, id)
+        }
+        ExecuteMsg::CancelGuardianChange {} => {
+            // 取消待审批的守护者配置变更
+            execute_cancel_guardian_change(deps, &ctx, info)
+        }
+
+        // ========== 销毁监听 ==========
+        ExecuteMsg::AddBurnListener { address } => {
+            // 添加销毁监听合约
+            execute_add_burn_listener(deps, &ctx, info, address)
+        }
+        ExecuteMsg::RemoveBurnListener { address } => {
+            // 移除销毁监听合约
+            execute_remove_burn_listener(deps, &ctx, info, address)
+        }
+
+        // ========== 预付费用存款 ==========
+        ExecuteMsg::Deposit {} => {
+            // 存入预付费用
+            execute_deposit(deps, &ctx, info)
+        }
+        ExecuteMsg::Withdraw { amount } => {
+            // 提取存款（暂停期间仍可提取）
+            execute_withdraw(deps, info, amount)
+        }
+        ExecuteMsg::SetAcceptedDenoms { denoms } => {
+            // 设置接受的支付币种
+            execute_set_accepted_denoms(deps, &ctx, info, denoms)
+        }
+
+        // ========== 存储维护 ==========
+        ExecuteMsg::RetagSeries { from_series, to_series, limit, cursor } => {
+            // 分批重命名系列
+            execute_retag_series(deps, &ctx, info, from_series, to_series, limit, cursor)
+        }
+        ExecuteMsg::GarbageCollect { limit } => {
+            // 分批清理不再被引用的存储条目
+            execute_garbage_collect(deps, &ctx, env, limit)
+        }
+        ExecuteMsg::SweepExpired { limit } => {
+            // 分批关闭失效的互换报价并退回过期礼物
+            execute_sweep_expired(deps, env, limit)
+        }
+        ExecuteMsg::SetCraftingLimit { limit } => {
+            // 设置合成价值上限
+            execute_set_crafting_limit(deps, &ctx, info, limit)
+        }
+        ExecuteMsg::ApplyIndexUpgrade { limit } => {
+            // 分批重建旧部署的二级索引
+            execute_apply_index_upgrade(deps, &ctx, env, info, limit)
+        }
+        ExecuteMsg::CreateCollection { name, symbol, base_uri } => {
+            // 创建子集合
+            execute_create_collection(deps, &ctx, env, info, name, symbol, base_uri)
+        }
+        ExecuteMsg::ExecuteOnCollection { collection, msg } => {
+            // 向子集合转发执行消息
+            execute_execute_on_collection(deps, &ctx, info, collection, msg)
         }
         
     }
@@ -234,12 +711,15 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             // 查询指定用户拥有的 NFT 列表
             query_tokens(deps, env, owner, start_after, limit)
         }
+        QueryMsg::Minter { address } => {
+            // 查询主要铸造者及指定地址的铸造权限
+            query_minter(deps, address)
+        }
 
         // ========== Luckee 扩展查询 ==========
         QueryMsg::TokenMeta { token_id } => {
             // 查询 NFT 的扩展元数据
-            let meta = TOKEN_META.load(deps.storage, token_id)?;
-            to_json_binary(&crate::msg::TokenMetaResponse { meta })
+            query_token_meta(deps, token_id)
         }
         QueryMsg::TokensByKind { kind, start_after, limit } => {
             // 按类型查询 NFT 列表
@@ -253,24 +733,351 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             // 按组查询 NFT 列表
             query_tokens_by_group(deps, group_id, start_after, limit)
         }
+        QueryMsg::TokensWithMeta { owner, start_after, limit } => {
+            // 查询用户拥有的 NFT 及其元数据和 URI
+            query_tokens_with_meta(deps, owner, start_after, limit)
+        }
+        QueryMsg::TokensByExpiry { expired, start_after, limit } => {
+            // 按是否过期查询 NFT 列表
+            query_tokens_by_expiry(deps, env, expired, start_after, limit)
+        }
+        QueryMsg::RecentActivity { limit } => {
+            // 查询最近的合约活动
+            query_recent_activity(deps, limit)
+        }
         QueryMsg::LuckeeContractInfo {} => {
             // 查询 Luckee 合约信息
             query_contract_info(deps)
         }
+        QueryMsg::PendingMinter {} => {
+            // 查询待生效的铸造者轮换
+            query_pending_minter(deps)
+        }
+        QueryMsg::EventVerbosity {} => {
+            // 查询事件详细程度
+            query_event_verbosity(deps)
+        }
+        QueryMsg::MinterSetLock {} => {
+            // 查询铸造者集合锁定状态
+            query_minter_set_lock(deps)
+        }
+        QueryMsg::StorageStats {} => {
+            // 查询存储使用统计
+            query_storage_stats(deps)
+        }
+        QueryMsg::UriTemplates {} => {
+            // 查询所有 token URI 模板
+            query_uri_templates(deps)
+        }
+        QueryMsg::VersionInfo {} => {
+            // 查询合约版本与启用的能力
+            query_version_info(deps)
+        }
+        QueryMsg::PendingBurn { token_id } => {
+            // 查询待销毁 NFT 信息
+            query_pending_burn(deps, token_id)
+        }
+        QueryMsg::BurnCooldown {} => {
+            // 查询销毁冷却配置
+            query_burn_cooldown(deps)
+        }
+        QueryMsg::BurnRequest { token_id } => {
+            // 查询销毁申请
+            query_burn_request(deps, token_id)
+        }
+        QueryMsg::LegacyImport {} => {
+            // 查询旧版合约导入配置
+            query_legacy_import(deps)
+        }
+        QueryMsg::LegacyToken { legacy_contract, legacy_token_id } => {
+            // 查询旧版 NFT 导入后的 NFT ID
+            query_legacy_token(deps, legacy_contract, legacy_token_id)
+        }
+        QueryMsg::WithdrawGuardians {} => {
+            // 查询紧急提取守护者配置
+            query_withdraw_guardians(deps)
+        }
+        QueryMsg::PendingWithdrawal { id } => {
+            // 查询待审批的紧急提取
+            query_pending_withdrawal(deps, id)
+        }
+        QueryMsg::PriceOracle {} => {
+            // 查询价格预言机配置
+            query_price_oracle(deps)
+        }
+        QueryMsg::RecipeFee { target } => {
+            // 查询配方当前费用报价
+            query_recipe_fee(deps, env, target)
+        }
+        QueryMsg::MetaOracle {} => {
+            // 查询元数据预言机配置
+            query_meta_oracle(deps)
+        }
+        QueryMsg::MetaOracleNonce { nonce } => {
+            // 查询预言机更新序号是否已使用
+            query_meta_oracle_nonce(deps, nonce)
+        }
+        QueryMsg::RelayQuota { relayer } => {
+            // 查询中继者的剩余铸造额度
+            query_relay_quota(deps, relayer)
+        }
+        QueryMsg::MintVoucherStatus { nonce } => {
+            // 查询铸造凭证序号是否已使用
+            query_mint_voucher_status(deps, nonce)
+        }
+        QueryMsg::CollectionInfo {} => {
+            // 查询集合展示信息
+            query_collection_info(deps)
+        }
+        QueryMsg::MaintenanceInfo {} => {
+            // 查询维护公告
+            query_maintenance_info(deps, env)
+        }
+        QueryMsg::ProvenanceHash {} => {
+            // 查询集合来源证明哈希
+            query_provenance_hash(deps)
+        }
+        QueryMsg::SeriesOwner { series_id } => {
+            // 查询系列所有者和被授权的铸造者
+            query_series_owner(deps, series_id)
+        }
+        QueryMsg::SerialOwner { series_id, serial } => {
+            // 按系列序号查询所有者
+            query_serial_owner(deps, series_id, serial)
+        }
+        QueryMsg::TokenBySerial { series_id, serial } => {
+            // 按系列序号查询 NFT
+            query_token_by_serial(deps, series_id, serial)
+        }
+        QueryMsg::OwnedAt { token_id } => {
+            // 查询所有权证明
+            query_owned_at(deps, token_id)
+        }
+        QueryMsg::TokenDebug { token_id } => {
+            // 查询 NFT 完整记录
+            query_token_debug(deps, token_id)
+        }
+        QueryMsg::VerifyOwnership { owner, token_ids } => {
+            // 批量验证所有权
+            query_verify_ownership(deps, owner, token_ids)
+        }
+        QueryMsg::ValidateMint { token_id, owner, extension } => {
+            // 铸造预检
+            query_validate_mint(deps, env, token_id, owner, extension)
+        }
+        QueryMsg::ValidateBatchMint { mints } => {
+            // 批量铸造预检
+            query_validate_batch_mint(deps, env, mints)
+        }
+        QueryMsg::MinterStats { minter } => {
+            // 查询铸造者统计
+            query_minter_stats(deps, minter)
+        }
+        QueryMsg::KindMintStatus { kind } => {
+            // 查询指定类型的铸造状态
+            query_kind_mint_status(deps, kind)
+        }
+        QueryMsg::KindInfo { kind } => {
+            // 查询类型信息
+            query_kind_info(kind)
+        }
+        QueryMsg::KindList {} => {
+            // 查询全部类型信息
+            query_kind_list()
+        }
+        QueryMsg::ScaleInfo {} => {
+            // 查询盲盒规模及头奖类型
+            query_scale_info()
+        }
+        QueryMsg::BlockedRecipients { start_after, limit } => {
+            // 查询禁止接收 NFT 的地址列表
+            query_blocked_recipients(deps, start_after, limit)
+        }
+        QueryMsg::ReservedIdRange {} => {
+            // 查询保留 ID 区间
+            query_reserved_id_range(deps)
+        }
+        QueryMsg::DeterministicTokenId { series_id, serial } => {
+            // 查询确定性 NFT ID
+            query_deterministic_token_id(deps, series_id, serial)
+        }
+        QueryMsg::BurnListeners {} => {
+            // 查询销毁监听合约列表
+            query_burn_listeners(deps)
+        }
+        QueryMsg::DepositBalance { address, denom } => {
+            // 查询用户存款余额
+            query_deposit_balance(deps, address, denom)
+        }
+        QueryMsg::AcceptedDenoms {} => {
+            // 查询接受的支付币种
+            query_accepted_denoms(deps)
+        }
 
         // ========== 合成相关查询 ==========
         QueryMsg::Recipe { target } => {
             // 查询指定目标的合成配方
-            let recipe = crate::state::RECIPES.may_load(deps.storage, target.to_key())?;
-            to_json_binary(&crate::msg::RecipeResponse { recipe })
+            query_recipe(deps, env, target)
         }
         QueryMsg::AllRecipes { start_after, limit } => {
             // 查询所有合成配方
             query_all_recipes(deps, start_after, limit)
         }
+        QueryMsg::RecipeGraph {} => {
+            // 查询完整配方图
+            query_recipe_graph(deps)
+        }
         QueryMsg::SynthesisPreview { inputs, target } => {
             // 预览合成操作的结果
-            query_synthesis_preview(deps, inputs, target)
+            query_synthesis_preview(deps, env, inputs, target)
+        }
+        QueryMsg::CraftingAllowance { owner } => {
+            // 查询合成价值额度
+            query_crafting_allowance(deps, env, owner)
+        }
+        QueryMsg::SimulateRecipe { recipe, inventory } => {
+            // 以假设库存模拟配方
+            query_simulate_recipe(recipe, inventory)
+        }
+        QueryMsg::CraftingCost { target, from_kind } => {
+            // 按配方链估算合成成本
+            query_crafting_cost(deps, target, from_kind)
+        }
+        QueryMsg::BurnTax { target } => {
+            // 查询配方的合成销毁税
+            query_burn_tax(deps, target)
+        }
+        QueryMsg::BurnTaxStats {} => {
+            // 查询累计销毁统计
+            query_burn_tax_stats(deps)
+        }
+        QueryMsg::Treasury {} => {
+            // 查询合约资金
+            query_treasury(deps, env)
+        }
+        QueryMsg::CraftingRoyalty {} => {
+            // 查询合成版税配置
+            query_crafting_royalty(deps)
+        }
+        QueryMsg::SeriesRoyalties { series_id } => {
+            // 查询系列累计版税
+            query_series_royalties(deps, series_id)
+        }
+        QueryMsg::OperatorBudget { owner, operator } => {
+            // 查询操作员批准的剩余转移次数
+            query_operator_budget(deps, owner, operator)
+        }
+        QueryMsg::GrantsByOwner { owner, start_after, limit } => {
+            // 查询所有者授予的批准
+            query_grants_by_owner(deps, env, owner, start_after, limit)
+        }
+        QueryMsg::ApprovedTokens { owner, spender, start_after, limit } => {
+            // 查询已批准给指定被批准者的 NFT
+            query_approved_tokens(deps, env, owner, spender, start_after, limit)
+        }
+        QueryMsg::TrustedOperators { owner } => {
+            // 查询受信任操作员注册表
+            query_trusted_operators(deps, owner)
+        }
+        QueryMsg::RoleMembers { role } => {
+            // 查询运营角色成员
+            query_role_members(deps, role)
+        }
+        QueryMsg::SessionLock { token_id } => {
+            // 查询 NFT 的会话锁定状态
+            query_session_lock(deps, env, token_id)
+        }
+        QueryMsg::SessionTokens { session_id } => {
+            // 查询会话内锁定中的 NFT
+            query_session_tokens(deps, env, session_id)
+        }
+        QueryMsg::SessionKey { owner, key } => {
+            // 查询会话密钥
+            query_session_key(deps, env, owner, key)
+        }
+        QueryMsg::SwapOffers { wanted_kind, start_after, limit } => {
+            // 按期望类型查询开放的互换报价
+            query_swap_offers(deps, wanted_kind, start_after, limit)
+        }
+        QueryMsg::Gift { claim_hash } => {
+            // 按领取码哈希查询礼物
+            query_gift(deps, claim_hash)
+        }
+        QueryMsg::CustodyTag { token_id } => {
+            // 查询托管标记
+            query_custody_tag(deps, token_id)
+        }
+        QueryMsg::CustodyTokens { custodian, start_after, limit } => {
+            // 按托管方列出已标记的 NFT
+            query_custody_tokens(deps, custodian, start_after, limit)
+        }
+        QueryMsg::VotingPower { owner, at_snapshot } => {
+            // 查询投票权
+            query_voting_power(deps, env, owner, at_snapshot)
+        }
+        QueryMsg::DelegatedPower { delegate, at_snapshot } => {
+            // 查询委托投票权
+            query_delegated_power(deps, env, delegate, at_snapshot)
+        }
+        QueryMsg::Delegation { delegator } => {
+            // 查询委托关系
+            query_delegation(deps, delegator)
+        }
+        QueryMsg::Bundle { bundle_id } => {
+            // 查询组合包
+            query_bundle(deps, bundle_id)
+        }
+        QueryMsg::Bundles { owner, start_after, limit } => {
+            // 按持有者列出组合包
+            query_bundles(deps, owner, start_after, limit)
+        }
+        QueryMsg::TokenBundle { token_id } => {
+            // 查询 NFT 所在的组合包
+            query_token_bundle(deps, token_id)
+        }
+        QueryMsg::ConditionalTransfer { token_id } => {
+            // 查询条件转移
+            query_conditional_transfer(deps, token_id)
+        }
+        QueryMsg::MintPool { pool_id } => {
+            // 查询盲盒奖池
+            query_mint_pool(deps, pool_id)
+        }
+        QueryMsg::RecipeOutputCollection { target } => {
+            // 查询配方的产出集合
+            query_recipe_output_collection(deps, target)
+        }
+        QueryMsg::SynthesisSource { collection } => {
+            // 查询跨集合合成的来源集合登记
+            query_synthesis_source(deps, collection)
+        }
+        QueryMsg::RecipeRegistry {} => {
+            // 查询共享配方注册表配置和缓存
+            query_recipe_registry(deps)
+        }
+        QueryMsg::Collections { start_after, limit } => {
+            // 列出子集合
+            query_collections(deps, start_after, limit)
+        }
+        QueryMsg::Collection { address } => {
+            // 查询子集合记录
+            query_collection(deps, address)
+        }
+        QueryMsg::PartnerCollections {} => {
+            // 查询合作集合白名单
+            query_partner_collections(deps)
+        }
+        QueryMsg::PartnerDeposit { collection, token_id } => {
+            // 查询合作集合 NFT 的存入者
+            query_partner_deposit(deps, collection, token_id)
+        }
+        QueryMsg::KindApproval { owner, operator, kind } => {
+            // 查询按类型授权
+            query_kind_approval(deps, env, owner, operator, kind)
+        }
+        QueryMsg::SynthesizableTargets { owner } => {
+            // 查询用户当前可合成的目标类型
+            query_synthesizable_targets(deps, owner)
         }
         
         // ========== CW721 集成查询 ==========
@@ -288,36 +1095,168 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 }
 
 
+/// 子消息回复入口点
+/// 
+/// 外部合约调用成功后释放对应的执行锁；子集合实例化成功后登记子集合；
+/// 关联集合铸造合成产出后记录合成历史
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `_env`: 环境信息（未使用）
+/// - `msg`: 子消息回复
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 处理结果
+#[entry_point]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        REENTRANCY_GUARD_REPLY_ID => {
+            release_execution_lock(deps.storage)?;
+            Ok(Response::new())
+        }
+        CHILD_COLLECTION_REPLY_ID => reply_child_collection(deps, msg),
+        REMOTE_SYNTHESIS_REPLY_ID => reply_remote_synthesis(deps, msg),
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
+
 /// 合约迁移入口点
 /// 
 /// 处理合约升级时的数据迁移和版本更新
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
-/// - `_env`: 环境信息（未使用）
-/// - `_msg`: 迁移消息（未使用）
+/// - `env`: 环境信息，用于推进索引升级
+/// - `msg`: 迁移消息，包含是否允许降级
 /// 
 /// # 返回值
 /// - `Result<Response, ContractError>`: 迁移结果
 #[entry_point]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: cosmwasm_std::Empty) -> Result<Response, ContractError> {
-    // 获取当前合约版本
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    // 获取当前合约版本，拒绝来自其他合约的迁移和未经允许的降级
     let current_version = get_contract_version(deps.storage)?;
+    ensure_migration_allowed(&current_version.contract, &current_version.version, msg.allow_downgrade)?;
     
     // 更新合约版本信息
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     
     // 更新存储版本
     STORAGE_VERSION.save(deps.storage, &CONTRACT_VERSION.to_string())?;
+
+    // 记录本次执行的数据转换，随迁移事件发出
+    let mut transforms = vec![];
     
     // 确保暂停状态被正确初始化（向后兼容性处理）
     if CONTRACT_PAUSED.may_load(deps.storage)?.is_none() {
         CONTRACT_PAUSED.save(deps.storage, &false)?;
+        transforms.push("init_paused");
+    }
+
+    // 序号索引为新增的二级索引：已有 NFT 但索引为空时重新执行索引升级以回填
+    if TOKEN_INDEXES_READY.may_load(deps.storage)?.unwrap_or(false)
+        && TOTAL_SUPPLY.may_load(deps.storage)?.unwrap_or(0) > 0
+        && TOKENS_BY_SERIAL.keys(deps.storage, None, None, Order::Ascending).next().is_none()
+    {
+        TOKEN_INDEXES_READY.save(deps.storage, &false)?;
+        transforms.push("backfill_serial_index");
+    }
+
+    // 投票权为新增的持有统计：尚未开始记录时同样重新执行索引升级以回填
+    if TOKEN_INDEXES_READY.may_load(deps.storage)?.unwrap_or(false)
+        && VOTING_POWER_SINCE.may_load(deps.storage)?.is_none()
+    {
+        TOKEN_INDEXES_READY.save(deps.storage, &false)?;
+        transforms.push("backfill_voting_power");
+    }
+
+    // 旧版 ID 记录改为按旧版合约区分
+    if crate::legacy::migrate_legacy_ids(deps.storage)? {
+        transforms.push("rekey_legacy_ids");
+    }
+
+    // 存款总额为新增的汇总：已有存款但尚无汇总时按存款重建
+    if TOTAL_DEPOSITS.is_empty(deps.storage)
+        && DEPOSITS.keys(deps.storage, None, None, Order::Ascending).next().is_some()
+    {
+        rebuild_total_deposits(deps.storage)?;
+        transforms.push("backfill_total_deposits");
+    }
+
+    // 旧部署尚无完整的二级索引时推进一批升级，可重复迁移或使用 ApplyIndexUpgrade 继续
+    let mut response = Response::new();
+    if !TOKEN_INDEXES_READY.may_load(deps.storage)?.unwrap_or(false) {
+        response = response.add_event(apply_index_upgrade_batch(deps.storage, env.block.height, DEFAULT_INDEX_UPGRADE_LIMIT)?);
+        transforms.push("index_upgrade_batch");
     }
 
+    // 迁移事件附带批次结束后的索引升级进度
+    let index_ready = TOKEN_INDEXES_READY.may_load(deps.storage)?.unwrap_or(false);
+    let index_cursor = INDEX_UPGRADE.may_load(deps.storage)?;
+    let index_phase = match &index_cursor {
+        Some(cursor) => cursor.phase.as_str(),
+        None if index_ready => "done",
+        None => "pending",
+    };
+    response = response.add_event(emit_migrate_event(
+        &current_version.version,
+        CONTRACT_VERSION,
+        &transforms,
+        index_phase,
+        index_cursor.and_then(|cursor| cursor.last_token_id),
+        index_ready,
+    ));
+
     // 返回迁移成功的响应
-    Ok(Response::new()
+    Ok(response
         .add_attribute("method", "migrate")
         .add_attribute("previous_version", current_version.version)
         .add_attribute("new_version", CONTRACT_VERSION))
+}
+
+/// 检查迁移是否兼容
+/// 
+/// 已存储的合约名称必须与本合约一致；已存储的版本比本合约新时，
+/// 只有显式设置 `allow_downgrade` 才允许迁移
+/// 
+/// # 参数
+/// - `stored_contract`: 已存储的合约名称
+/// - `stored_version`: 已存储的合约版本
+/// - `allow_downgrade`: 是否允许降级
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 不兼容时返回错误
+fn ensure_migration_allowed(
+    stored_contract: &str,
+    stored_version: &str,
+    allow_downgrade: bool,
+) -> Result<(), ContractError> {
+    if stored_contract != CONTRACT_NAME {
+        return Err(ContractError::MigrationContractMismatch {
+            stored: stored_contract.to_string(),
+            target: CONTRACT_NAME.to_string(),
+        });
+    }
+    if !allow_downgrade && parse_version(stored_version)? > parse_version(CONTRACT_VERSION)? {
+        return Err(ContractError::MigrationDowngrade {
+            stored: stored_version.to_string(),
+            target: CONTRACT_VERSION.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// 解析 `major.minor.patch` 版本号，忽略预发布和构建标识
+fn parse_version(version: &str) -> Result<(u64, u64, u64), ContractError> {
+    let invalid = || ContractError::InvalidContractVersion { version: version.to_string() };
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().map_err(|_| invalid()));
+    let parsed = (
+        parts.next().ok_or_else(invalid)??,
+        parts.next().ok_or_else(invalid)??,
+        parts.next().ok_or_else(invalid)??,
+    );
+    if parts.next().is_some() {
+        return Err(invalid());
+    }
+    Ok(parsed)
 }
\ No newline at end of file
@@ -0,0 +1,345 @@
+//! 跨集合合成模块
+//! 
+//! 此模块实现在关联集合中铸造合成产出，包括：
+//! - 产出集合设置：合约所有者为配方指定关联的 Luckee NFT 集合
+//! - 远程铸造：合成时在本集合销毁输入，通过子消息请求关联集合铸造产出
+//! - 来源登记：关联集合的所有者登记来源集合及产出所在系列，与铸造者列表相互独立
+//! - 产出接收：关联集合为已登记的来源集合铸造产出 NFT
+//! - 回复处理：从关联集合的响应中读取产出 NFT ID，记录合成历史并发出合成事件
+//! 
+//! 例如以第一季的材料合成第二季的物品。来源集合须在关联集合中登记
+
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult, SubMsg,
+    WasmMsg,
+};
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::events::{emit_mint_event, emit_synthesize_event, event_attributes};
+use crate::helpers::{
+    allocate_token_id, assign_series_serial, authorize_series_mint, ensure_kind_mintable, record_activity,
+    record_minter_stats, release_execution_lock, storage_counter, store_minted_token, track_storage_entry,
+    validate_mint_owner, validate_series_id, verbose_events,
+};
+use crate::msg::{ExecuteMsg, RecipeOutputCollectionResponse, SynthesisSourceResponse, SynthesizeData};
+use crate::state::{
+    PendingRemoteSynthesis, SynthesisRecord, PENDING_BURNS, PENDING_REMOTE_SYNTHESIS, RECIPE_OUTPUT_COLLECTIONS,
+    SYNTHESIS_HISTORY, SYNTHESIS_SOURCES, TOKEN_META, TOTAL_SUPPLY,
+};
+use crate::types::{NftKind, NftMeta, Scale};
+
+/// 跨集合产出铸造回复 ID
+pub const REMOTE_SYNTHESIS_REPLY_ID: u64 = 3;
+
+/// wasm 事件类型
+const WASM_EVENT: &str = "wasm";
+
+/// wasm 事件中表示发出合约的属性名
+const CONTRACT_ADDRESS_ATTR: &str = "_contract_address";
+
+/// 关联集合响应中表示产出 NFT ID 的属性名
+const OUTPUT_TOKEN_ID_ATTR: &str = "output_token_id";
+
+// ========== 跨集合合成执行接口 ==========
+
+/// 设置配方的产出集合
+/// 
+/// `collection` 为 None 时产出恢复在本集合铸造。只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于拒绝将本合约设为产出集合
+/// - `info`: 消息信息，包含发送者
+/// - `target`: 目标 NFT 类型
+/// - `collection`: 关联集合地址
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_recipe_output_collection(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    target: NftKind,
+    collection: Option<String>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let response = Response::new()
+        .add_attribute("action", "set_recipe_output_collection")
+        .add_attribute("target", target.to_key());
+    match collection {
+        Some(collection) => {
+            let collection = deps.api.addr_validate(&collection)?;
+            if collection == env.contract.address {
+                return Err(ContractError::InvalidCrossCollectionSynthesis {
+                    reason: "output collection is this contract".to_string(),
+                });
+            }
+            RECIPE_OUTPUT_COLLECTIONS.save(deps.storage, target.to_key(), &collection)?;
+            Ok(response.add_attribute("collection", collection.to_string()))
+        }
+        None => {
+            RECIPE_OUTPUT_COLLECTIONS.remove(deps.storage, target.to_key());
+            Ok(response.add_attribute("collection", "none"))
+        }
+    }
+}
+
+/// 登记跨集合合成的来源集合
+/// 
+/// 登记后来源集合可以请求铸造合成产出，产出铸造在 `series_id` 系列中并受该系列的铸造权限约束。
+/// `series_id` 为 None 时移除登记。只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于拒绝登记本合约
+/// - `info`: 消息信息，包含发送者
+/// - `collection`: 来源集合地址
+/// - `series_id`: 产出所在系列 ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 登记结果
+pub fn execute_set_synthesis_source(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    series_id: Option<String>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let collection = deps.api.addr_validate(&collection)?;
+    let response = Response::new()
+        .add_attribute("action", "set_synthesis_source")
+        .add_attribute("collection", collection.to_string());
+    match series_id {
+        Some(series_id) => {
+            if collection == env.contract.address {
+                return Err(ContractError::InvalidCrossCollectionSynthesis {
+                    reason: "source collection is this contract".to_string(),
+                });
+            }
+            validate_series_id(&series_id)?;
+            SYNTHESIS_SOURCES.save(deps.storage, collection, &series_id)?;
+            Ok(response.add_attribute("series_id", series_id))
+        }
+        None => {
+            SYNTHESIS_SOURCES.remove(deps.storage, collection);
+            Ok(response.add_attribute("series_id", "none"))
+        }
+    }
+}
+
+/// 构造在关联集合中铸造产出的子消息
+/// 
+/// 由合成处理函数在销毁输入后调用，产出 ID 和合成历史在回复中确定
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `env`: 环境信息，用于记录合成时间
+/// - `collection`: 关联集合地址
+/// - `owner`: 产出 NFT 的所有者
+/// - `target`: 合成目标类型
+/// - `inputs`: 已销毁的输入 NFT ID
+/// 
+/// # 返回值
+/// - `Result<SubMsg, ContractError>`: 请求关联集合铸造产出的子消息
+pub fn remote_synthesis_output(
+    deps: DepsMut,
+    env: &Env,
+    collection: &Addr,
+    owner: &Addr,
+    target: &NftKind,
+    inputs: &[u64],
+) -> Result<SubMsg, ContractError> {
+    if PENDING_REMOTE_SYNTHESIS.exists(deps.storage) {
+        return Err(ContractError::InvalidCrossCollectionSynthesis { reason: "output already pending".to_string() });
+    }
+    PENDING_REMOTE_SYNTHESIS.save(deps.storage, &PendingRemoteSynthesis {
+        record: SynthesisRecord {
+            user: owner.clone(),
+            inputs: inputs.to_vec(),
+            output: 0,
+            timestamp: env.block.time.seconds(),
+            output_collection: Some(collection.clone()),
+        },
+        target: target.clone(),
+    })?;
+
+    let mint = WasmMsg::Execute {
+        contract_addr: collection.to_string(),
+        msg: to_json_binary(&ExecuteMsg::MintSynthesisOutput {
+            owner: owner.to_string(),
+            kind: target.clone(),
+        })?,
+        funds: vec![],
+    };
+    Ok(SubMsg::reply_on_success(mint, REMOTE_SYNTHESIS_REPLY_ID))
+}
+
+/// 铸造来源集合的合成产出
+/// 
+/// 由已登记的来源集合在合成时调用，产出铸造在登记的系列中，
+/// 按本集合的 ID 分配规则分配 ID（跳过保留区间）。该调用是来源集合合成交易内的子消息，
+/// 失败时整笔合成回滚，不需要幂等键
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息
+/// - `info`: 消息信息，发送者为来源集合
+/// - `owner`: 产出 NFT 的所有者
+/// - `kind`: 产出类型
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 铸造结果，`output_token_id` 属性为产出 NFT ID
+pub fn execute_mint_synthesis_output(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    kind: NftKind,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    // 只接受已登记的来源集合，铸造者身份不能代替登记
+    let series_id = SYNTHESIS_SOURCES.may_load(deps.storage, info.sender.clone())?
+        .ok_or(ContractError::Unauthorized {})?;
+    ensure_kind_mintable(deps.storage, &kind)?;
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    validate_mint_owner(deps.storage, &env.contract.address, &owner_addr)?;
+
+    // 检查来源集合对登记系列的铸造权限（未注册的系列由来源集合认领）
+    authorize_series_mint(deps.storage, &series_id, &info.sender)?;
+
+    let serial_in_series = assign_series_serial(deps.storage, &series_id)?;
+    let token_id = allocate_token_id(deps.storage, &series_id, serial_in_series)?;
+    if TOKEN_META.has(deps.storage, token_id) || PENDING_BURNS.has(deps.storage, token_id) {
+        return Err(ContractError::TokenAlreadyExists {});
+    }
+    let meta = NftMeta {
+        kind,
+        scale_origin: Scale::Tiny,
+        physical_sku: None,
+        crafted_from: None, // 输入属于来源集合，不在本集合记录
+        series_id,
+        collection_group_id: None,
+        serial_in_series,
+        expires_at: None,
+    };
+    let new_supply = store_minted_token(deps.storage, &env, token_id, &owner_addr, &meta)?;
+
+    record_activity(deps.storage, &env, "mint_synthesis_output", Some(token_id), &info.sender)?;
+    record_minter_stats(deps.storage, &env, &info.sender, 1)?;
+
+    let kind = alloc::format!("{:?}", meta.kind);
+    Ok(Response::new()
+        .add_attribute("action", "mint_synthesis_output")
+        .add_attribute(OUTPUT_TOKEN_ID_ATTR, token_id.to_string())
+        .add_attribute("source_collection", info.sender.to_string())
+        .add_attribute("owner", owner_addr.to_string())
+        .add_attribute("kind", kind.clone())
+        .add_event(emit_mint_event(token_id, owner_addr.as_str(), &kind, new_supply)))
+}
+
+/// 处理跨集合产出铸造回复
+/// 
+/// 从关联集合的 wasm 事件中读取产出 NFT ID，记录合成历史，
+/// 发出与本地合成相同的合成事件，并将合成结果写入 Response.data。
+/// 事件中的总供应量为本集合销毁输入后的数量，产出计入关联集合
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `msg`: 子消息回复
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 记录结果
+pub fn reply_remote_synthesis(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    // 产出铸造子消息计入了执行锁
+    release_execution_lock(deps.storage)?;
+
+    let PendingRemoteSynthesis { mut record, target } = PENDING_REMOTE_SYNTHESIS.may_load(deps.storage)?
+        .ok_or_else(|| ContractError::InvalidCrossCollectionSynthesis { reason: "no pending output".to_string() })?;
+    let collection = record.output_collection.clone()
+        .ok_or_else(|| ContractError::InvalidCrossCollectionSynthesis { reason: "no output collection".to_string() })?;
+    let result = msg.result.into_result().map_err(StdError::generic_err)?;
+    let output_token_id = result.events.iter()
+        .filter(|event| event.ty == WASM_EVENT)
+        .filter(|event| event.attributes.iter()
+            .any(|attr| attr.key == CONTRACT_ADDRESS_ATTR && attr.value == collection.as_str()))
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == OUTPUT_TOKEN_ID_ATTR)
+        .and_then(|attr| attr.value.parse::<u64>().ok())
+        .ok_or_else(|| ContractError::InvalidCrossCollectionSynthesis { reason: "missing output token id".to_string() })?;
+
+    PENDING_REMOTE_SYNTHESIS.remove(deps.storage);
+    record.output = output_token_id;
+    let history_key = (record.user.clone(), record.timestamp);
+    let existed = SYNTHESIS_HISTORY.has(deps.storage, history_key.clone());
+    SYNTHESIS_HISTORY.save(deps.storage, history_key, &record)?;
+    track_storage_entry(deps.storage, storage_counter::SYNTHESIS_HISTORY, existed, true)?;
+
+    // 事件详细程度为 Verbose 时附带输入列表
+    let total_supply = TOTAL_SUPPLY.load(deps.storage)?;
+    let mut synthesize_event = emit_synthesize_event(
+        output_token_id, &alloc::format!("{:?}", target), record.inputs.len(), &record.user, total_supply,
+    ).add_attribute("output_collection", collection.to_string());
+    if verbose_events(deps.storage)? {
+        let input_ids = record.inputs.iter().map(|token_id| token_id.to_string()).collect::<Vec<_>>().join(",");
+        synthesize_event = synthesize_event.add_attribute(event_attributes::INPUTS, input_ids);
+    }
+
+    let data = to_json_binary(&SynthesizeData {
+        output_token_id,
+        owner: record.user.to_string(),
+        target,
+        burned_inputs: record.inputs,
+        output_collection: Some(collection.to_string()),
+    })?;
+
+    Ok(Response::new()
+        .set_data(data)
+        .add_attribute("action", "record_remote_synthesis")
+        .add_attribute("output_collection", collection.to_string())
+        .add_attribute(OUTPUT_TOKEN_ID_ATTR, output_token_id.to_string())
+        .add_event(synthesize_event))
+}
+
+// ========== 跨集合合成查询接口 ==========
+
+/// 查询配方的产出集合
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `target`: 目标 NFT 类型
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 关联集合（在本集合铸造时为 None）
+pub fn query_recipe_output_collection(deps: Deps, target: NftKind) -> StdResult<Binary> {
+    let collection = RECIPE_OUTPUT_COLLECTIONS.may_load(deps.storage, target.to_key())?
+        .map(|collection| collection.to_string());
+    to_json_binary(&RecipeOutputCollectionResponse { target, collection })
+}
+
+/// 查询跨集合合成的来源集合登记
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `collection`: 来源集合地址
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 产出所在系列 ID（未登记时为 None）
+pub fn query_synthesis_source(deps: Deps, collection: String) -> StdResult<Binary> {
+    let addr = deps.api.addr_validate(&collection)?;
+    let series_id = SYNTHESIS_SOURCES.may_load(deps.storage, addr)?;
+    to_json_binary(&SynthesisSourceResponse { collection, series_id })
+}
@@ -0,0 +1,154 @@
+//! 托管证明模块
+//! 
+//! 此模块实现可选的托管登记，包括：
+//! - 托管方为其持有的 NFT 标注链下账户引用的哈希
+//! - 按 NFT 查询托管标记
+//! - 按托管方分页列出已标记的 NFT，供交易所与链上状态对账
+//! 
+//! 标记只对标记时的所有者有效，NFT 转出或销毁时自动清除
+
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult};
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::helpers::clear_custody_tag;
+use crate::msg::{Cursor, CustodyTagItem, CustodyTagResponse, CustodyTokenInfo, CustodyTokensResponse};
+use crate::pagination::{clamp_limit, cursor_scope, decode_cursor, into_page, start_bound, MAX_WIDE_LIMIT};
+use crate::state::{CustodyTag, CUSTODY_TAGS, CUSTODY_TOKENS, TOKEN_OWNERSHIP};
+
+/// 单次标记的最大 NFT 数量
+pub const MAX_CUSTODY_TAGS: usize = 100;
+
+/// 账户引用哈希长度（SHA-256 十六进制）
+const ACCOUNT_HASH_LENGTH: usize = 64;
+
+// ========== 托管执行接口 ==========
+
+/// 标记或清除托管 NFT 的账户引用
+/// 
+/// 发送者必须是每个 NFT 的当前所有者，`account_hash` 为 None 时清除标记。
+/// 任一条目校验失败时整批回滚，错误中附带条目位置
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于记录标记时间
+/// - `info`: 消息信息，发送者为托管方
+/// - `tags`: 标记条目列表
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 标记结果
+pub fn execute_tag_custody(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    tags: Vec<CustodyTagItem>,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    if tags.is_empty() || tags.len() > MAX_CUSTODY_TAGS {
+        return Err(ContractError::InvalidCustodyTag {
+            reason: alloc::format!("tag count must be between 1 and {}", MAX_CUSTODY_TAGS),
+        });
+    }
+
+    let mut tagged = 0u32;
+    let mut cleared = 0u32;
+    for (index, item) in tags.into_iter().enumerate() {
+        let owner = TOKEN_OWNERSHIP.may_load(deps.storage, item.token_id)?
+            .ok_or_else(|| ContractError::TokenNotFound {}.at_batch_item(index, item.token_id))?;
+        if owner != info.sender {
+            return Err(ContractError::NotOwned {}.at_batch_item(index, item.token_id));
+        }
+
+        // 先清除旧标记，再按需写入新标记
+        clear_custody_tag(deps.storage, item.token_id)?;
+        match item.account_hash {
+            Some(account_hash) => {
+                let account_hash = validate_account_hash(&account_hash)
+                    .map_err(|err| err.at_batch_item(index, item.token_id))?;
+                CUSTODY_TAGS.save(deps.storage, item.token_id, &CustodyTag {
+                    custodian: info.sender.clone(),
+                    account_hash,
+                    tagged_at: env.block.time.seconds(),
+                })?;
+                CUSTODY_TOKENS.save(deps.storage, (info.sender.clone(), item.token_id), &())?;
+                tagged += 1;
+            }
+            None => cleared += 1,
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "tag_custody")
+        .add_attribute("custodian", info.sender.to_string())
+        .add_attribute("tagged", tagged.to_string())
+        .add_attribute("cleared", cleared.to_string()))
+}
+
+// ========== 托管查询接口 ==========
+
+/// 查询 NFT 的托管标记
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 托管标记（未标记时为 None）
+pub fn query_custody_tag(deps: Deps, token_id: u64) -> StdResult<Binary> {
+    let tag = CUSTODY_TAGS.may_load(deps.storage, token_id)?;
+    to_json_binary(&CustodyTagResponse { token_id, tag })
+}
+
+/// 按托管方分页列出已标记的 NFT
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `custodian`: 托管方地址
+/// - `start_after`: 上一页返回的 `next_cursor`
+/// - `limit`: 返回数量限制
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: NFT ID、账户引用哈希和标记时间列表
+pub fn query_custody_tokens(
+    deps: Deps,
+    custodian: String,
+    start_after: Option<Cursor>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let custodian = deps.api.addr_validate(&custodian)?;
+    let limit = clamp_limit(limit, MAX_WIDE_LIMIT);
+    let start = start_bound(decode_cursor::<u64>(cursor_scope::CUSTODY_TOKENS, start_after)?);
+
+    let tokens = CUSTODY_TOKENS
+        .prefix(custodian.clone())
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit + 1)
+        .map(|token_id| {
+            let token_id = token_id?;
+            let tag = CUSTODY_TAGS.load(deps.storage, token_id)?;
+            Ok(CustodyTokenInfo { token_id, account_hash: tag.account_hash, tagged_at: tag.tagged_at })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    let page = into_page(cursor_scope::CUSTODY_TOKENS, tokens, limit, |token| token.token_id);
+
+    to_json_binary(&CustodyTokensResponse {
+        custodian: custodian.to_string(),
+        tokens: page.items,
+        next_cursor: page.next_cursor,
+        has_more: page.has_more,
+    })
+}
+
+// ========== 内部辅助函数 ==========
+
+/// 验证账户引用哈希格式（64 位十六进制），统一为小写
+fn validate_account_hash(account_hash: &str) -> Result<String, ContractError> {
+    if account_hash.len() != ACCOUNT_HASH_LENGTH || !account_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ContractError::InvalidCustodyTag { reason: "account hash must be 64 hex characters".to_string() });
+    }
+    Ok(account_hash.to_ascii_lowercase())
+}
@@ -4,11 +4,12 @@
 //! - 转移 NFT 所有权 (TransferNft)
 //! - 批准和撤销批准 (Approve/Revoke)
 //! - 操作员管理 (ApproveAll/RevokeAll)
+//! - 一次撤销所有批准 (RevokeAllMyApprovals)
 //! - 所有权和批准查询
 //! - Token 枚举查询
 
 use cosmwasm_std::{
-    to_json_binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    to_json_binary, Addr, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
     Binary, Order,
 };
 use cw721::{OwnerOfResponse, NftInfoResponse, ApprovalsResponse, 
@@ -18,10 +19,17 @@ use cw721::{OwnerOfResponse, NftInfoResponse, ApprovalsResponse,
 use crate::error::ContractError;
 use crate::state::{
     TOKEN_OWNERSHIP, TOKEN_APPROVALS, OPERATOR_APPROVALS, TOKENS_BY_OWNER, 
-    ALL_TOKENS, CONTRACT_INFO, CONFIG, Expiration
+    ALL_TOKENS, CONTRACT_INFO, CONFIG, Expiration, TOKEN_META, KIND_APPROVALS,
+    TRUSTED_OPERATOR_OPT_INS, OPERATOR_BUDGETS, APPROVED_TOKENS_BY_OWNER, SESSION_KEYS,
 };
-use crate::types::NftMeta;
-use crate::helpers::{check_contract_paused, update_owner_tokens};
+use crate::types::{NftKind, NftMeta, SessionKeyScope};
+use crate::pagination::{clamp_limit, cursor_scope, decode_cursor, into_page, scan_page, start_bound, page_after, MAX_LIMIT};
+use crate::helpers::{move_token, is_authorized_minter, validate_recipient, build_token_uri,
+                     record_activity, track_storage_entry, storage_counter, authorize_token_operation,
+                     ensure_not_session_locked, has_session_key_scope};
+use crate::msg::{MinterResponse, KindApprovalResponse, OperatorBudgetResponse, GrantsByOwnerResponse,
+                TokenApprovalGrant, OperatorGrant, ApprovedTokensResponse, Cursor};
+use crate::context::ExecuteContext;
 use crate::events::{
     emit_transfer_event, emit_approval_event, emit_revoke_event,
     emit_approve_all_event, emit_revoke_all_event
@@ -29,13 +37,17 @@ use crate::events::{
 
 // ========== 标准 CW721 执行接口 ==========
 
+/// RevokeAllMyApprovals 每次最多清除的条目数
+pub const REVOKE_ALL_BATCH_LIMIT: usize = 100;
+
 /// 转移 NFT 所有权
 /// 
 /// 将指定 NFT 的所有权从当前所有者转移给接收者
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
-/// - `_env`: 环境信息（未使用）
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于检查过期时间和拒绝转移给合约自身
 /// - `info`: 消息信息，包含发送者
 /// - `recipient`: 接收者地址
 /// - `token_id`: 要转移的 NFT ID
@@ -44,31 +56,39 @@ use crate::events::{
 /// - `Result<Response, ContractError>`: 转移结果
 pub fn execute_transfer_nft(
     deps: DepsMut,
-    _env: Env,
+    ctx: &ExecuteContext,
+    env: Env,
     info: MessageInfo,
     recipient: String,
     token_id: u64,
 ) -> Result<Response, ContractError> {
     // 检查合约是否暂停
-    check_contract_paused(deps.storage)?;
+    ctx.ensure_not_paused()?;
     
-    // 验证当前所有者
+    // 验证当前所有者（或按类型授权的操作员、持有转移授权的会话密钥）
     let owner = TOKEN_OWNERSHIP.load(deps.storage, token_id)?;
-    if owner != info.sender {
-        return Err(ContractError::NotOwned {});
+    let meta = TOKEN_META.load(deps.storage, token_id)?;
+    if !has_session_key_scope(deps.storage, &env, &owner, &info.sender, &SessionKeyScope::TransferNft)? {
+        authorize_token_operation(deps.storage, &env, &owner, &info.sender, &meta.kind)?;
     }
     
-    // 验证接收者地址格式
-    let recipient_addr = deps.api.addr_validate(&recipient)?;
+    // 已过期的 NFT 不可转移
+    if meta.is_expired(env.block.time.seconds()) {
+        return Err(ContractError::TokenExpired {});
+    }
     
-    // 更新 NFT 所有权
-    TOKEN_OWNERSHIP.save(deps.storage, token_id, &recipient_addr)?;
+    // 对局中锁定的 NFT 不可转移
+    ensure_not_session_locked(deps.storage, &env, token_id)?;
     
-    // 清理转移前的批准信息（安全措施）
-    crate::helpers::clear_token_approvals(deps.storage, token_id)?;
+    // 验证接收者地址格式，并拒绝合约自身和被禁止的接收地址
+    let recipient_addr = deps.api.addr_validate(&recipient)?;
+    validate_recipient(deps.storage, &env.contract.address, &recipient_addr)?;
     
-    // 更新所有者索引
-    update_owner_tokens(deps.storage, &owner, &recipient_addr, token_id)?;
+    // 更新所有权、批准信息、所有者索引和持有数量
+    move_token(deps.storage, &env, token_id, &owner, &recipient_addr, &meta.kind)?;
+
+    // 记录到活动流
+    record_activity(deps.storage, &env, "transfer", Some(token_id), &info.sender)?;
     
     // 返回成功响应并发出转移事件
     Ok(Response::new()
@@ -85,6 +105,8 @@ pub fn execute_transfer_nft(
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于检查会话密钥是否过期
 /// - `info`: 消息信息，包含发送者
 /// - `spender`: 被批准的地址
 /// - `token_id`: NFT ID
@@ -94,17 +116,21 @@ pub fn execute_transfer_nft(
 /// - `Result<Response, ContractError>`: 批准结果
 pub fn execute_approve(
     deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
     info: MessageInfo,
     spender: String,
     token_id: u64,
     expires: Option<Expiration>,
 ) -> Result<Response, ContractError> {
     // 检查合约是否暂停
-    check_contract_paused(deps.storage)?;
+    ctx.ensure_not_paused()?;
     
-    // 验证所有者身份
+    // 验证所有者身份（或持有批准授权的会话密钥）
     let owner = TOKEN_OWNERSHIP.load(deps.storage, token_id)?;
-    if owner != info.sender {
+    if owner != info.sender
+        && !has_session_key_scope(deps.storage, &env, &owner, &info.sender, &SessionKeyScope::Approve)?
+    {
         return Err(ContractError::NotOwned {});
     }
     
@@ -112,6 +138,7 @@ pub fn execute_approve(
     let spender_addr = deps.api.addr_validate(&spender)?;
     
     // 获取现有的批准列表
+    let existed = TOKEN_APPROVALS.has(deps.storage, token_id);
     let mut approvals = TOKEN_APPROVALS.may_load(deps.storage, token_id)?.unwrap_or_default();
     
     // 移除现有的批准（如果存在），避免重复
@@ -125,6 +152,8 @@ pub fn execute_approve(
     
     // 保存更新后的批准列表
     TOKEN_APPROVALS.save(deps.storage, token_id, &approvals)?;
+    APPROVED_TOKENS_BY_OWNER.save(deps.storage, (owner.clone(), token_id), &())?;
+    track_storage_entry(deps.storage, storage_counter::TOKEN_APPROVALS, existed, true)?;
     
     // 返回成功响应并发出批准事件
     Ok(Response::new()
@@ -141,6 +170,8 @@ pub fn execute_approve(
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于检查会话密钥是否过期
 /// - `info`: 消息信息，包含发送者
 /// - `spender`: 要撤销批准的地址
 /// - `token_id`: NFT ID
@@ -149,16 +180,20 @@ pub fn execute_approve(
 /// - `Result<Response, ContractError>`: 撤销结果
 pub fn execute_revoke(
     deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
     info: MessageInfo,
     spender: String,
     token_id: u64,
 ) -> Result<Response, ContractError> {
     // 检查合约是否暂停
-    check_contract_paused(deps.storage)?;
+    ctx.ensure_not_paused()?;
     
-    // 验证所有者身份
+    // 验证所有者身份（或持有撤销授权的会话密钥）
     let owner = TOKEN_OWNERSHIP.load(deps.storage, token_id)?;
-    if owner != info.sender {
+    if owner != info.sender
+        && !has_session_key_scope(deps.storage, &env, &owner, &info.sender, &SessionKeyScope::Revoke)?
+    {
         return Err(ContractError::NotOwned {});
     }
     
@@ -166,6 +201,7 @@ pub fn execute_revoke(
     let spender_addr = deps.api.addr_validate(&spender)?;
     
     // 获取现有的批准列表
+    let existed = TOKEN_APPROVALS.has(deps.storage, token_id);
     let mut approvals = TOKEN_APPROVALS.may_load(deps.storage, token_id)?.unwrap_or_default();
     
     // 移除指定地址的批准
@@ -174,9 +210,11 @@ pub fn execute_revoke(
     // 如果批准列表为空，则删除整个条目；否则保存更新后的列表
     if approvals.is_empty() {
         TOKEN_APPROVALS.remove(deps.storage, token_id);
+        APPROVED_TOKENS_BY_OWNER.remove(deps.storage, (owner.clone(), token_id));
     } else {
         TOKEN_APPROVALS.save(deps.storage, token_id, &approvals)?;
     }
+    track_storage_entry(deps.storage, storage_counter::TOKEN_APPROVALS, existed, !approvals.is_empty())?;
     
     // 返回成功响应并发出撤销事件
     Ok(Response::new()
@@ -193,35 +231,50 @@ pub fn execute_revoke(
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
 /// - `info`: 消息信息，包含发送者
 /// - `operator`: 操作员地址
 /// - `expires`: 批准过期时间（可选）
+/// - `max_transfers`: 允许的转移和销毁总次数（可选，用尽时自动撤销批准）
 /// 
 /// # 返回值
 /// - `Result<Response, ContractError>`: 批准结果
 pub fn execute_approve_all(
     deps: DepsMut,
+    ctx: &ExecuteContext,
     info: MessageInfo,
     operator: String,
     expires: Option<Expiration>,
+    max_transfers: Option<u32>,
 ) -> Result<Response, ContractError> {
     // 检查合约是否暂停
-    check_contract_paused(deps.storage)?;
+    ctx.ensure_not_paused()?;
     
     // 验证操作员地址格式
     let operator_addr = deps.api.addr_validate(&operator)?;
     
     // 设置操作员批准（如果没有指定过期时间，则设置为永不过期）
+    let existed = OPERATOR_APPROVALS.has(deps.storage, (info.sender.clone(), operator_addr.clone()));
     OPERATOR_APPROVALS.save(deps.storage, (info.sender.clone(), operator_addr.clone()), &expires.unwrap_or(Expiration {
         at_height: None,
         at_time: None,
     }))?;
+    track_storage_entry(deps.storage, storage_counter::OPERATOR_APPROVALS, existed, true)?;
+    
+    // 设置或清除转移次数限制（重新批准会覆盖之前的剩余次数）
+    let budget_key = (info.sender.clone(), operator_addr.clone());
+    match max_transfers {
+        Some(0) => return Err(ContractError::InvalidApprovalBudget {}),
+        Some(max) => OPERATOR_BUDGETS.save(deps.storage, budget_key, &max)?,
+        None => OPERATOR_BUDGETS.remove(deps.storage, budget_key),
+    }
     
     // 返回成功响应并发出批准事件
     Ok(Response::new()
         .add_attribute("action", "approve_all")
         .add_attribute("owner", info.sender.to_string())
         .add_attribute("operator", operator)
+        .add_attribute("max_transfers", max_transfers.map(|max| max.to_string()).unwrap_or_default())
         .add_event(emit_approve_all_event(&info.sender, &operator_addr)))
 }
 
@@ -231,6 +284,7 @@ pub fn execute_approve_all(
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
 /// - `info`: 消息信息，包含发送者
 /// - `operator`: 要撤销权限的操作员地址
 /// 
@@ -238,17 +292,21 @@ pub fn execute_approve_all(
 /// - `Result<Response, ContractError>`: 撤销结果
 pub fn execute_revoke_all(
     deps: DepsMut,
+    ctx: &ExecuteContext,
     info: MessageInfo,
     operator: String,
 ) -> Result<Response, ContractError> {
     // 检查合约是否暂停
-    check_contract_paused(deps.storage)?;
+    ctx.ensure_not_paused()?;
     
     // 验证操作员地址格式
     let operator_addr = deps.api.addr_validate(&operator)?;
     
     // 移除操作员批准
+    let existed = OPERATOR_APPROVALS.has(deps.storage, (info.sender.clone(), operator_addr.clone()));
     OPERATOR_APPROVALS.remove(deps.storage, (info.sender.clone(), operator_addr.clone()));
+    OPERATOR_BUDGETS.remove(deps.storage, (info.sender.clone(), operator_addr.clone()));
+    track_storage_entry(deps.storage, storage_counter::OPERATOR_APPROVALS, existed, false)?;
     
     // 返回成功响应并发出撤销事件
     Ok(Response::new()
@@ -258,6 +316,400 @@ pub fn execute_revoke_all(
         .add_event(emit_revoke_all_event(&info.sender, &operator_addr)))
 }
 
+// ========== 按类型授权 ==========
+
+/// 按类型授权操作员
+/// 
+/// 授权操作员转移、销毁和合成发送者持有的指定类型的所有 NFT，
+/// 例如只允许合成合约操作四叶草
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `operator`: 操作员地址
+/// - `kind`: 授权的 NFT 类型
+/// - `expires`: 过期时间（可选，默认永不过期）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 授权结果
+pub fn execute_approve_kind(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    operator: String,
+    kind: NftKind,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+    
+    // 验证操作员地址格式
+    let operator_addr = deps.api.addr_validate(&operator)?;
+    
+    KIND_APPROVALS.save(
+        deps.storage,
+        (info.sender.clone(), operator_addr, kind.to_key()),
+        &expires.unwrap_or(Expiration { at_height: None, at_time: None }),
+    )?;
+    
+    Ok(Response::new()
+        .add_attribute("action", "approve_kind")
+        .add_attribute("owner", info.sender.to_string())
+        .add_attribute("operator", operator)
+        .add_attribute("kind", kind.to_key()))
+}
+
+/// 撤销按类型授权
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `operator`: 操作员地址
+/// - `kind`: NFT 类型
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 撤销结果
+pub fn execute_revoke_kind(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    operator: String,
+    kind: NftKind,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+    
+    // 验证操作员地址格式
+    let operator_addr = deps.api.addr_validate(&operator)?;
+    
+    KIND_APPROVALS.remove(deps.storage, (info.sender.clone(), operator_addr, kind.to_key()));
+    
+    Ok(Response::new()
+        .add_attribute("action", "revoke_kind")
+        .add_attribute("owner", info.sender.to_string())
+        .add_attribute("operator", operator)
+        .add_attribute("kind", kind.to_key()))
+}
+
+/// 一次撤销发送者授予的所有批准
+/// 
+/// 钱包疑似泄露时使用，依次清除单个 NFT 批准、操作员批准（含转移次数限制）、
+/// 按类型授权和会话密钥，并取消信任受信任操作员注册表。每次最多处理
+/// `REVOKE_ALL_BATCH_LIMIT` 项，未清除完时响应中 `has_more` 为 true，
+/// 重复调用即从剩余条目继续。暂停期间也可执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `info`: 消息信息，发送者为授予批准的所有者
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 撤销结果
+pub fn execute_revoke_all_my_approvals(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let owner = info.sender;
+    let mut budget = REVOKE_ALL_BATCH_LIMIT;
+
+    // 单个 NFT 批准（已转出的 NFT 只清理索引，批准已随转移清除）
+    let token_ids: Vec<u64> = APPROVED_TOKENS_BY_OWNER
+        .prefix(owner.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(budget + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut has_more = token_ids.len() > budget;
+    let token_ids = &token_ids[..token_ids.len().min(budget)];
+    for token_id in token_ids {
+        APPROVED_TOKENS_BY_OWNER.remove(deps.storage, (owner.clone(), *token_id));
+        if TOKEN_OWNERSHIP.may_load(deps.storage, *token_id)?.as_ref() == Some(&owner) {
+            let existed = TOKEN_APPROVALS.has(deps.storage, *token_id);
+            TOKEN_APPROVALS.remove(deps.storage, *token_id);
+            track_storage_entry(deps.storage, storage_counter::TOKEN_APPROVALS, existed, false)?;
+        }
+    }
+    budget -= token_ids.len();
+
+    // 操作员批准及其转移次数限制
+    let operators: Vec<Addr> = OPERATOR_APPROVALS
+        .prefix(owner.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(budget + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    has_more |= operators.len() > budget;
+    let operators = &operators[..operators.len().min(budget)];
+    for operator in operators {
+        OPERATOR_APPROVALS.remove(deps.storage, (owner.clone(), operator.clone()));
+        OPERATOR_BUDGETS.remove(deps.storage, (owner.clone(), operator.clone()));
+        track_storage_entry(deps.storage, storage_counter::OPERATOR_APPROVALS, true, false)?;
+    }
+    budget -= operators.len();
+
+    // 按类型授权
+    let kind_approvals: Vec<(Addr, String)> = KIND_APPROVALS
+        .sub_prefix(owner.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(budget + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    has_more |= kind_approvals.len() > budget;
+    let kind_approvals = &kind_approvals[..kind_approvals.len().min(budget)];
+    for (operator, kind) in kind_approvals {
+        KIND_APPROVALS.remove(deps.storage, (owner.clone(), operator.clone(), kind.clone()));
+    }
+    budget -= kind_approvals.len();
+
+    // 会话密钥
+    let session_keys: Vec<Addr> = SESSION_KEYS
+        .prefix(owner.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(budget + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    has_more |= session_keys.len() > budget;
+    let session_keys = &session_keys[..session_keys.len().min(budget)];
+    for key in session_keys {
+        SESSION_KEYS.remove(deps.storage, (owner.clone(), key.clone()));
+    }
+
+    TRUSTED_OPERATOR_OPT_INS.remove(deps.storage, owner.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_all_my_approvals")
+        .add_attribute("owner", owner.to_string())
+        .add_attribute("token_approvals", token_ids.len().to_string())
+        .add_attribute("operators", operators.len().to_string())
+        .add_attribute("kind_approvals", kind_approvals.len().to_string())
+        .add_attribute("session_keys", session_keys.len().to_string())
+        .add_attribute("has_more", has_more.to_string()))
+}
+
+/// 选择信任或取消信任受信任操作员注册表
+/// 
+/// 选择信任后，注册表中的所有操作员（未被停用时）都可转移、销毁和合成发送者的 NFT
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `opt_in`: true 表示选择信任，false 表示取消
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_trusted_operator_opt_in(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    opt_in: bool,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+    
+    if opt_in {
+        TRUSTED_OPERATOR_OPT_INS.save(deps.storage, info.sender.clone(), &())?;
+    } else {
+        TRUSTED_OPERATOR_OPT_INS.remove(deps.storage, info.sender.clone());
+    }
+    
+    Ok(Response::new()
+        .add_attribute("action", if opt_in { "opt_in_trusted_operators" } else { "opt_out_trusted_operators" })
+        .add_attribute("owner", info.sender.to_string()))
+}
+
+/// 查询操作员批准的剩余转移次数
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `owner`: 所有者地址
+/// - `operator`: 操作员地址
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 剩余次数（未限制时为 None）
+pub fn query_operator_budget(deps: Deps, owner: String, operator: String) -> StdResult<Binary> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let operator_addr = deps.api.addr_validate(&operator)?;
+    let remaining = OPERATOR_BUDGETS.may_load(deps.storage, (owner_addr, operator_addr))?;
+    to_json_binary(&OperatorBudgetResponse { remaining })
+}
+
+/// 查询按类型授权
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，用于检查授权是否过期
+/// - `owner`: 所有者地址
+/// - `operator`: 操作员地址
+/// - `kind`: NFT 类型
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 授权状态
+pub fn query_kind_approval(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    operator: String,
+    kind: NftKind,
+) -> StdResult<Binary> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let operator_addr = deps.api.addr_validate(&operator)?;
+    
+    // 已过期的授权视为不存在
+    let expires = KIND_APPROVALS
+        .may_load(deps.storage, (owner_addr, operator_addr, kind.to_key()))?
+        .filter(|expires| !expires.is_expired(&env));
+    
+    to_json_binary(&KindApprovalResponse {
+        approved: expires.is_some(),
+        expires,
+    })
+}
+
+/// 按所有者查询其授予的批准
+/// 
+/// 按 NFT ID 分页列出所有者授予的单个 NFT 批准；第一页（`start_after` 为空）同时列出操作员批准。
+/// 已过期的批准不返回
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，用于检查批准是否过期
+/// - `owner`: 所有者地址
+/// - `start_after`: 上一页返回的 `next_cursor`
+/// - `limit`: 返回的 NFT 数量限制（默认 30，最大 30）
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 单个 NFT 批准和操作员批准列表
+pub fn query_grants_by_owner(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    start_after: Option<Cursor>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let limit = clamp_limit(limit, MAX_LIMIT);
+    let start_after = decode_cursor::<u64>(cursor_scope::GRANTS_BY_OWNER, start_after)?;
+
+    let token_ids: Vec<u64> = APPROVED_TOKENS_BY_OWNER
+        .prefix(owner_addr.clone())
+        .keys(deps.storage, start_bound(start_after), None, Order::Ascending)
+        .take(limit + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    let page = into_page(cursor_scope::GRANTS_BY_OWNER, token_ids, limit, |token_id| *token_id);
+
+    let mut token_approvals = vec![];
+    for token_id in &page.items {
+        // 跳过已不属于该所有者的 NFT（旧数据清理前可能残留）
+        if TOKEN_OWNERSHIP.may_load(deps.storage, *token_id)?.as_ref() != Some(&owner_addr) {
+            continue;
+        }
+        let approvals = TOKEN_APPROVALS.may_load(deps.storage, *token_id)?.unwrap_or_default();
+        for approval in approvals {
+            if approval.expires.as_ref().map_or(false, |exp| exp.is_expired(&env)) {
+                continue;
+            }
+            token_approvals.push(TokenApprovalGrant {
+                token_id: *token_id,
+                spender: approval.spender.to_string(),
+                expires: approval.expires,
+            });
+        }
+    }
+
+    // 操作员批准只在第一页返回
+    let mut operators = vec![];
+    if start_after.is_none() {
+        let grants = OPERATOR_APPROVALS
+            .prefix(owner_addr.clone())
+            .range(deps.storage, None, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (operator, expires) in grants {
+            if expires.is_expired(&env) {
+                continue;
+            }
+            let remaining = OPERATOR_BUDGETS.may_load(deps.storage, (owner_addr.clone(), operator.clone()))?;
+            operators.push(OperatorGrant {
+                operator: operator.to_string(),
+                expires,
+                remaining,
+            });
+        }
+    }
+
+    to_json_binary(&GrantsByOwnerResponse {
+        owner,
+        token_approvals,
+        operators,
+        next_cursor: page.next_cursor,
+        has_more: page.has_more,
+    })
+}
+
+/// 查询所有者批准给指定被批准者的 NFT
+/// 
+/// 按 NFT ID 分页遍历所有者授予批准的反向索引，只返回仍属于该所有者且对 `spender` 的批准未过期的 NFT。
+/// 受扫描上限约束，可能返回部分结果
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，用于检查批准是否过期
+/// - `owner`: 所有者地址
+/// - `spender`: 被批准者地址
+/// - `start_after`: 上一页返回的 `next_cursor`
+/// - `limit`: 返回的 NFT 数量限制（默认 30，最大 30）
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 已批准给 `spender` 的 NFT 及批准过期时间
+pub fn query_approved_tokens(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    spender: String,
+    start_after: Option<Cursor>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    let limit = clamp_limit(limit, MAX_LIMIT);
+    let start_after = decode_cursor::<u64>(cursor_scope::APPROVED_TOKENS, start_after)?;
+
+    // 读取每个索引条目对 spender 仍有效的批准，跳过已不属于该所有者的 NFT
+    let entries = APPROVED_TOKENS_BY_OWNER
+        .prefix(owner_addr.clone())
+        .keys(deps.storage, start_bound(start_after), None, Order::Ascending)
+        .map(|token_id| -> StdResult<_> {
+            let token_id = token_id?;
+            if TOKEN_OWNERSHIP.may_load(deps.storage, token_id)?.as_ref() != Some(&owner_addr) {
+                return Ok((token_id, None));
+            }
+            let approval = TOKEN_APPROVALS.may_load(deps.storage, token_id)?
+                .unwrap_or_default()
+                .into_iter()
+                .find(|approval| approval.spender == spender_addr
+                    && !approval.expires.as_ref().map_or(false, |exp| exp.is_expired(&env)));
+            Ok((token_id, approval))
+        });
+    let page = scan_page(cursor_scope::APPROVED_TOKENS, entries, limit, |approval| approval.is_some())?;
+
+    let mut tokens = vec![];
+    for token_id in page.items {
+        let expires = TOKEN_APPROVALS.may_load(deps.storage, token_id)?
+            .unwrap_or_default()
+            .into_iter()
+            .find(|approval| approval.spender == spender_addr)
+            .and_then(|approval| approval.expires);
+        tokens.push(TokenApprovalGrant { token_id, spender: spender.clone(), expires });
+    }
+
+    to_json_binary(&ApprovedTokensResponse {
+        owner,
+        spender,
+        tokens,
+        next_cursor: page.next_cursor,
+        has_more: page.has_more,
+        scan_truncated: page.scan_truncated,
+    })
+}
+
 // ========== 标准 CW721 查询接口 ==========
 /// 查询 NFT 的所有者信息
 /// 
@@ -322,9 +774,9 @@ pub fn query_nft_info(deps: Deps, token_id: u64) -> StdResult<Binary> {
     let _owner = TOKEN_OWNERSHIP.load(deps.storage, token_id)?;
     let meta = crate::state::TOKEN_META.load(deps.storage, token_id)?;
     
-    // 构建 token URI（基于基础 URI 和 token ID）
+    // 构建 token URI（基于基础 URI、URI 模板和 NFT 字段）
     let config = CONFIG.load(deps.storage)?;
-    let token_uri = config.base_uri.map(|base| alloc::format!("{}/{}", base, token_id));
+    let token_uri = build_token_uri(deps.storage, config.base_uri.as_deref(), token_id, &meta)?;
     
     // 返回 NFT 信息响应
     to_json_binary(&NftInfoResponse::<NftMeta> {
@@ -445,7 +897,7 @@ pub fn query_token_uri(deps: Deps, token_id: u64) -> StdResult<Binary> {
     
     // 构建 token URI
     let config = CONFIG.load(deps.storage)?;
-    let token_uri = config.base_uri.map(|base| alloc::format!("{}/{}", base, token_id));
+    let token_uri = build_token_uri(deps.storage, config.base_uri.as_deref(), token_id, &meta)?;
     
     to_json_binary(&NftInfoResponse::<NftMeta> {
         token_uri,
@@ -466,14 +918,11 @@ pub fn query_token_uri(deps: Deps, token_id: u64) -> StdResult<Binary> {
 /// # 返回值
 /// - `StdResult<Binary>`: NFT ID 列表
 pub fn query_all_tokens(deps: Deps, _env: Env, start_after: Option<u64>, limit: Option<u32>) -> StdResult<Binary> {
-    let limit = limit.unwrap_or(30).min(30) as usize;
-    
-    // 使用Bound实现标准分页逻辑
-    let start_bound = start_after.map(|id| Bound::exclusive(id));
+    let limit = clamp_limit(limit, MAX_LIMIT);
     
     // 获取所有 NFT ID，支持分页
     let tokens: Vec<u64> = ALL_TOKENS
-        .keys(deps.storage, start_bound, None, Order::Ascending)
+        .keys(deps.storage, start_bound(start_after), None, Order::Ascending)
         .take(limit)
         .collect::<Result<Vec<_>, _>>()?;
 
@@ -497,7 +946,7 @@ pub fn query_all_tokens(deps: Deps, _env: Env, start_after: Option<u64>, limit:
 /// - `StdResult<Binary>`: 用户拥有的 NFT ID 列表
 pub fn query_tokens(deps: Deps, _env: Env, owner: String, start_after: Option<u64>, limit: Option<u32>) -> StdResult<Binary> {
     let owner_addr = deps.api.addr_validate(&owner)?;
-    let limit = limit.unwrap_or(30).min(30) as usize;
+    let limit = clamp_limit(limit, MAX_LIMIT);
     
     // 获取用户拥有的 NFT 列表
     let all_tokens = TOKENS_BY_OWNER
@@ -505,16 +954,7 @@ pub fn query_tokens(deps: Deps, _env: Env, owner: String, start_after: Option<u6
         .unwrap_or_default();
     
     // 使用标准分页逻辑
-    let tokens: Vec<u64> = if let Some(start) = start_after {
-        all_tokens.into_iter()
-            .filter(|&token_id| token_id > start)
-            .take(limit)
-            .collect()
-    } else {
-        all_tokens.into_iter()
-            .take(limit)
-            .collect()
-    };
+    let tokens = page_after(all_tokens, start_after, limit);
 
     to_json_binary(&TokensResponse { 
         tokens: tokens.into_iter().map(|id| id.to_string()).collect() 
@@ -537,3 +977,32 @@ pub fn query_cw721_contract_info(deps: Deps) -> StdResult<Binary> {
         symbol: contract_info.symbol,
     })
 }
+
+/// 查询铸造者信息
+/// 
+/// 按照 cw721-base 约定返回主要铸造者地址，
+/// 指定地址时额外返回该地址是否有铸造权限（主要铸造者或允许列表中的铸造者）
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `address`: 要检查铸造权限的地址（可选）
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 铸造者信息
+pub fn query_minter(deps: Deps, address: Option<String>) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let is_allowed = match address {
+        Some(address) => {
+            let addr = deps.api.addr_validate(&address)?;
+            Some(is_authorized_minter(deps, &addr, &config).unwrap_or(false))
+        }
+        None => None,
+    };
+
+    to_json_binary(&MinterResponse {
+        minter: Some(config.minter.to_string()),
+        is_allowed,
+    })
+}
+
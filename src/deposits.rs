@@ -0,0 +1,330 @@
+//! 预付费用存款模块
+//! 
+//! 此模块实现用户原生代币存款账本，包括：
+//! - 存入资金 (Deposit)
+//! - 提取资金 (Withdraw)
+//! - 从存款中扣除合成等操作的费用
+//! - 存款余额查询
+//! - 存款总额：按币种汇总，紧急提取只能动用合约余额中超出存款总额的部分
+//! - 接受的支付币种：按部署链配置币种和单次最低数量，所有附带资金的消息统一检查
+
+use cosmwasm_std::{
+    to_json_binary, Addr, BankMsg, Binary, Coin, Deps, DepsMut, MessageInfo, Order, QuerierWrapper,
+    Response, StdResult, Storage, Uint128,
+};
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::events::{emit_deposit_event, emit_withdraw_event};
+use crate::msg::{AcceptedDenomsResponse, DepositBalanceResponse};
+use crate::state::{CONFIG, DEPOSITS, TOTAL_DEPOSITS};
+use crate::types::AcceptedDenom;
+
+// ========== 存款执行接口 ==========
+
+/// 存入资金
+/// 
+/// 将随消息附带的原生代币记入发送者的存款余额
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者和资金
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 存入结果
+pub fn execute_deposit(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    if info.funds.iter().all(|coin| coin.amount.is_zero()) {
+        return Err(ContractError::InvalidFunds { reason: "no funds attached".to_string() });
+    }
+
+    credit_funds(deps.storage, &info.sender, &info.funds)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "deposit")
+        .add_attribute("owner", info.sender.to_string());
+    for coin in info.funds.iter().filter(|coin| !coin.amount.is_zero()) {
+        let balance = DEPOSITS.load(deps.storage, (info.sender.clone(), coin.denom.clone()))?;
+        response = response.add_event(emit_deposit_event(&info.sender, coin, balance));
+    }
+
+    Ok(response)
+}
+
+/// 提取资金
+/// 
+/// 从发送者的存款余额中提取指定资金。合约暂停时仍允许提取，确保用户资金不被锁定
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `info`: 消息信息，包含发送者
+/// - `amount`: 要提取的资金
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 提取结果
+pub fn execute_withdraw(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Coin,
+) -> Result<Response, ContractError> {
+    if amount.amount.is_zero() {
+        return Err(ContractError::InvalidFunds { reason: "zero amount".to_string() });
+    }
+
+    let balance = debit_deposit(deps.storage, &info.sender, &amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "withdraw")
+        .add_attribute("owner", info.sender.to_string())
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![amount.clone()],
+        })
+        .add_event(emit_withdraw_event(&info.sender, &amount, balance)))
+}
+
+/// 设置接受的支付币种
+/// 
+/// 空列表表示不限制币种。已有存款不受影响，仍可按原币种提取或抵扣费用。
+/// 只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `denoms`: 接受的币种及单次最低数量
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_accepted_denoms(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    denoms: Vec<AcceptedDenom>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let mut seen = alloc::collections::BTreeSet::new();
+    for accepted in &denoms {
+        if accepted.denom.is_empty() {
+            return Err(ContractError::InvalidFunds { reason: "empty denom".to_string() });
+        }
+        if !seen.insert(accepted.denom.as_str()) {
+            return Err(ContractError::InvalidFunds {
+                reason: alloc::format!("duplicate denom {}", accepted.denom),
+            });
+        }
+    }
+
+    let listed = denoms.iter().map(|accepted| accepted.denom.as_str()).collect::<Vec<_>>().join(",");
+    let mut config = ctx.config.clone();
+    config.accepted_denoms = denoms;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_accepted_denoms")
+        .add_attribute("denoms", if listed.is_empty() { "any".to_string() } else { listed }))
+}
+
+// ========== 费用扣除 ==========
+
+/// 检查随消息附带的资金
+/// 
+/// 配置了接受的币种时，每个非零币种都必须在列表中且不低于单次最低数量；
+/// 未配置时不限制币种
+/// 
+/// # 参数
+/// - `accepted`: 接受的币种列表
+/// - `funds`: 随消息附带的资金
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 币种不接受或低于最低数量时返回 `InvalidFunds`
+pub fn validate_payment(accepted: &[AcceptedDenom], funds: &[Coin]) -> Result<(), ContractError> {
+    if accepted.is_empty() {
+        return Ok(());
+    }
+    for coin in funds.iter().filter(|coin| !coin.amount.is_zero()) {
+        let rule = accepted.iter()
+            .find(|accepted| accepted.denom == coin.denom)
+            .ok_or_else(|| ContractError::InvalidFunds {
+                reason: alloc::format!("denom {} not accepted", coin.denom),
+            })?;
+        if coin.amount < rule.min_amount {
+            return Err(ContractError::InvalidFunds {
+                reason: alloc::format!("{} below minimum {}{}", coin, rule.min_amount, rule.denom),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 收取操作费用
+/// 
+/// 先将随消息附带的资金记入存款，再从存款中扣除费用，
+/// 多付的部分保留在存款中，可随时提取
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `payer`: 付费地址
+/// - `funds`: 随消息附带的资金
+/// - `fee`: 需要收取的费用（None 表示免费，仅记入附带资金）
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 扣费结果
+pub fn charge_fee(
+    storage: &mut dyn Storage,
+    payer: &Addr,
+    funds: &[Coin],
+    fee: Option<&Coin>,
+) -> Result<(), ContractError> {
+    credit_funds(storage, payer, funds)?;
+    if let Some(fee) = fee.filter(|fee| !fee.amount.is_zero()) {
+        debit_deposit(storage, payer, fee)?;
+    }
+    Ok(())
+}
+
+/// 将资金记入存款余额
+fn credit_funds(storage: &mut dyn Storage, owner: &Addr, funds: &[Coin]) -> Result<(), ContractError> {
+    for coin in funds.iter().filter(|coin| !coin.amount.is_zero()) {
+        let key = (owner.clone(), coin.denom.clone());
+        let balance = DEPOSITS.may_load(storage, key.clone())?.unwrap_or_default();
+        let new_balance = balance.checked_add(coin.amount).map_err(|_| ContractError::Overflow {})?;
+        DEPOSITS.save(storage, key, &new_balance)?;
+
+        let total = TOTAL_DEPOSITS.may_load(storage, coin.denom.clone())?.unwrap_or_default();
+        let total = total.checked_add(coin.amount).map_err(|_| ContractError::Overflow {})?;
+        TOTAL_DEPOSITS.save(storage, coin.denom.clone(), &total)?;
+    }
+    Ok(())
+}
+
+/// 从存款余额中扣除资金，返回扣除后的余额
+fn debit_deposit(storage: &mut dyn Storage, owner: &Addr, amount: &Coin) -> Result<Uint128, ContractError> {
+    let key = (owner.clone(), amount.denom.clone());
+    let balance = DEPOSITS.may_load(storage, key.clone())?.unwrap_or_default();
+    let new_balance = balance.checked_sub(amount.amount).map_err(|_| ContractError::InsufficientDeposit {
+        denom: amount.denom.clone(),
+        required: amount.amount,
+        available: balance,
+    })?;
+
+    if new_balance.is_zero() {
+        DEPOSITS.remove(storage, key);
+    } else {
+        DEPOSITS.save(storage, key, &new_balance)?;
+    }
+
+    let total = TOTAL_DEPOSITS.may_load(storage, amount.denom.clone())?.unwrap_or_default();
+    let total = total.checked_sub(amount.amount).map_err(|_| ContractError::Overflow {})?;
+    if total.is_zero() {
+        TOTAL_DEPOSITS.remove(storage, amount.denom.clone());
+    } else {
+        TOTAL_DEPOSITS.save(storage, amount.denom.clone(), &total)?;
+    }
+    Ok(new_balance)
+}
+
+/// 检查紧急提取是否只动用存款以外的资金
+/// 
+/// 每个币种的提取金额不能超过合约余额减去该币种的存款总额
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `querier`: 查询接口，用于读取合约余额
+/// - `contract`: 合约地址
+/// - `amount`: 要提取的资金
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 超出可提取余额时返回 `WithdrawExceedsAvailable`
+pub fn ensure_withdrawable(
+    storage: &dyn Storage,
+    querier: &QuerierWrapper,
+    contract: &Addr,
+    amount: &[Coin],
+) -> Result<(), ContractError> {
+    for coin in amount.iter().filter(|coin| !coin.amount.is_zero()) {
+        let balance = querier.query_balance(contract, coin.denom.clone())?.amount;
+        let reserved = TOTAL_DEPOSITS.may_load(storage, coin.denom.clone())?.unwrap_or_default();
+        let available = balance.saturating_sub(reserved);
+        if coin.amount > available {
+            return Err(ContractError::WithdrawExceedsAvailable {
+                denom: coin.denom.clone(),
+                requested: coin.amount,
+                available,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 按用户存款重建各币种的存款总额
+/// 
+/// 用于存款总额记录之前部署的合约迁移，调用前须确保 TOTAL_DEPOSITS 为空
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 重建结果
+pub fn rebuild_total_deposits(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    let mut totals = alloc::collections::BTreeMap::<String, Uint128>::new();
+    for item in DEPOSITS.range(storage, None, None, Order::Ascending) {
+        let ((_, denom), amount) = item?;
+        let total = totals.entry(denom).or_default();
+        *total = total.checked_add(amount).map_err(|_| ContractError::Overflow {})?;
+    }
+    for (denom, total) in totals {
+        TOTAL_DEPOSITS.save(storage, denom, &total)?;
+    }
+    Ok(())
+}
+
+// ========== 存款查询接口 ==========
+
+/// 查询存款余额
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `address`: 用户地址
+/// - `denom`: 币种（可选，不指定时返回所有币种）
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 存款余额列表
+pub fn query_deposit_balance(deps: Deps, address: String, denom: Option<String>) -> StdResult<Binary> {
+    let owner = deps.api.addr_validate(&address)?;
+
+    let balances: Vec<Coin> = match denom {
+        Some(denom) => {
+            let amount = DEPOSITS.may_load(deps.storage, (owner, denom.clone()))?.unwrap_or_default();
+            vec![Coin { denom, amount }]
+        }
+        None => DEPOSITS
+            .prefix(owner)
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+            .collect::<StdResult<Vec<_>>>()?,
+    };
+
+    to_json_binary(&DepositBalanceResponse { address, balances })
+}
+
+/// 查询接受的支付币种
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 接受的币种及单次最低数量（为空时不限制币种）
+pub fn query_accepted_denoms(deps: Deps) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    to_json_binary(&AcceptedDenomsResponse { denoms: config.accepted_denoms })
+}
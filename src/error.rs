@@ -15,6 +15,10 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    /// 附带资金错误（消息不接受资金时附带了资金）
+    #[error("{0}")]
+    Payment(#[from] cw_utils::PaymentError),
+
     /// 未授权操作
     #[error("Unauthorized")]
     Unauthorized {},
@@ -130,4 +134,388 @@ pub enum ContractError {
     /// 合成输入数量过多
     #[error("Too many inputs for synthesis: {count}")]
     TooManyInputs { count: usize },
+
+    /// 无效的基础 URI
+    #[error("Invalid base URI: {reason}")]
+    InvalidBaseUri { reason: String },
+
+    /// 待销毁记录不存在
+    #[error("Pending burn not found")]
+    PendingBurnNotFound {},
+
+    /// 销毁宽限期尚未结束
+    #[error("Burn grace period has not ended")]
+    BurnGracePeriodActive {},
+
+    /// 销毁宽限期已结束，无法恢复
+    #[error("Burn grace period has expired")]
+    BurnGracePeriodExpired {},
+
+    /// 无效的守护者配置
+    #[error("Invalid guardian config: {reason}")]
+    InvalidGuardianConfig { reason: String },
+
+    /// 待审批提取不存在
+    #[error("Withdrawal not found: {id}")]
+    WithdrawalNotFound { id: u64 },
+
+    /// 守护者已审批过该提取
+    #[error("Withdrawal already approved by sender")]
+    AlreadyApproved {},
+
+    /// 待审批提案已过期
+    #[error("Proposal expired")]
+    ProposalExpired {},
+
+    /// 无效的资金
+    #[error("Invalid funds: {reason}")]
+    InvalidFunds { reason: String },
+
+    /// 存款余额不足
+    #[error("Insufficient deposit for {denom}: required {required}, available {available}")]
+    InsufficientDeposit { denom: String, required: cosmwasm_std::Uint128, available: cosmwasm_std::Uint128 },
+
+    /// 提取金额超过扣除用户存款后的可提取余额
+    #[error("Withdraw exceeds available {denom}: requested {requested}, available {available}")]
+    WithdrawExceedsAvailable { denom: String, requested: cosmwasm_std::Uint128, available: cosmwasm_std::Uint128 },
+
+    /// 销毁监听合约数量超过上限
+    #[error("Too many burn listeners (max {max})")]
+    TooManyBurnListeners { max: u32 },
+
+    /// 销毁监听合约未注册
+    #[error("Burn listener not found")]
+    BurnListenerNotFound {},
+
+    /// 无效的 NFT 接收者
+    #[error("Invalid recipient: {reason}")]
+    InvalidRecipient { reason: String },
+
+    /// 该类型的铸造已暂停或冻结
+    #[error("Minting is disabled for kind {kind}")]
+    KindMintDisabled { kind: String },
+
+    /// 该类型的铸造已永久冻结，无法更改
+    #[error("Minting is permanently frozen for kind {kind}")]
+    KindMintFrozen { kind: String },
+
+    /// 来源证明哈希已设置，不可更改
+    #[error("Provenance hash already set")]
+    ProvenanceHashAlreadySet {},
+
+    /// 无效的来源证明哈希
+    #[error("Invalid provenance hash: {reason}")]
+    InvalidProvenanceHash { reason: String },
+
+    /// 未配置元数据预言机
+    #[error("Metadata oracle not configured")]
+    OracleNotConfigured {},
+
+    /// 预言机签名无效
+    #[error("Invalid oracle signature")]
+    InvalidOracleSignature {},
+
+    /// 预言机更新序号已被使用
+    #[error("Oracle nonce {nonce} already spent")]
+    OracleNonceSpent { nonce: u64 },
+
+    /// 预言机负载无效
+    #[error("Invalid oracle payload: {reason}")]
+    InvalidOraclePayload { reason: String },
+
+    /// NFT 已过期
+    #[error("Token has expired")]
+    TokenExpired {},
+
+    /// NFT 尚未过期
+    #[error("Token has not expired")]
+    TokenNotExpired {},
+
+    /// 无权向该系列铸造
+    #[error("Not authorized to mint into series {series_id}")]
+    SeriesNotAuthorized { series_id: String },
+
+    /// 系列已被注册
+    #[error("Series already registered: {series_id}")]
+    SeriesAlreadyRegistered { series_id: String },
+
+    /// 系列尚未注册
+    #[error("Series not registered: {series_id}")]
+    SeriesNotRegistered { series_id: String },
+
+    /// 无效的 URI 模板
+    #[error("Invalid URI template: {reason}")]
+    InvalidUriTemplate { reason: String },
+
+    /// 无效的系列重命名
+    #[error("Invalid series retag: {reason}")]
+    InvalidSeriesRetag { reason: String },
+
+    /// 无效的生效高度
+    #[error("Invalid activation height: {height}")]
+    InvalidActivationHeight { height: u64 },
+
+    /// 没有待生效的铸造者轮换
+    #[error("No pending minter rotation")]
+    NoPendingMinter {},
+
+    /// 铸造者轮换尚未到达生效高度
+    #[error("Minter rotation not active until height {activation_height}")]
+    MinterRotationNotActive { activation_height: u64 },
+
+    /// 无效的集合展示信息
+    #[error("Invalid collection info: {reason}")]
+    InvalidCollectionInfo { reason: String },
+
+    /// 合成输入中存在重复的 NFT
+    #[error("Duplicate input token: {token_id}")]
+    DuplicateInputToken { token_id: u64 },
+
+    /// 外部调用未完成时的重入调用
+    #[error("Reentrant call rejected while external calls are pending")]
+    ReentrantCall {},
+
+    /// 价格预言机未配置
+    #[error("Price oracle not configured")]
+    PriceOracleNotConfigured {},
+
+    /// 无效的预言机价格或配置
+    #[error("Invalid oracle price: {reason}")]
+    InvalidOraclePrice { reason: String },
+
+    /// 预言机价格已过期
+    #[error("Stale oracle price: updated at {updated_at}, max age {max_age_seconds}s")]
+    StaleOraclePrice { updated_at: u64, max_age_seconds: u64 },
+
+    /// 预言机价格偏离超出容忍度
+    #[error("Oracle price deviation too large: last {last}, current {current}")]
+    OraclePriceDeviation { last: String, current: String },
+
+    /// 受信任操作员数量超出上限
+    #[error("Too many trusted operators (max {max})")]
+    TooManyTrustedOperators { max: u32 },
+
+    /// 无效的操作员批准次数限制
+    #[error("Approval transfer budget must be positive")]
+    InvalidApprovalBudget {},
+
+    /// 互换报价不存在
+    #[error("Swap offer {offer_id} not found")]
+    SwapOfferNotFound { offer_id: u64 },
+
+    /// 无效的互换操作
+    #[error("Invalid swap: {reason}")]
+    InvalidSwap { reason: String },
+
+    /// 礼物不存在
+    #[error("Gift not found")]
+    GiftNotFound {},
+
+    /// 无效的礼物操作
+    #[error("Invalid gift: {reason}")]
+    InvalidGift { reason: String },
+
+    /// 缺少运营角色
+    #[error("Missing role: {role}")]
+    MissingRole { role: String },
+
+    /// 无效的空投分发
+    #[error("Invalid distribution: {reason}")]
+    InvalidDistribution { reason: String },
+
+    /// NFT 被游戏会话锁定
+    #[error("Token {token_id} is locked for a game session until {until}")]
+    TokenSessionLocked { token_id: u64, until: u64 },
+
+    /// 无效的会话锁定操作
+    #[error("Invalid session lock: {reason}")]
+    InvalidSessionLock { reason: String },
+
+    /// 超出合成价值上限
+    #[error("Crafting limit exceeded: {used} of {max_value} used in current window, requested {requested}")]
+    CraftingLimitExceeded { max_value: u64, used: u64, requested: u64 },
+
+    /// 无效的合成价值上限配置
+    #[error("Invalid crafting limit: {reason}")]
+    InvalidCraftingLimit { reason: String },
+
+    /// 批量操作中的某一项失败
+    #[error("Batch item {index} (token {token_id}) failed: {source}")]
+    BatchItemFailed {
+        index: usize,
+        token_id: u64,
+        source: alloc::boxed::Box<ContractError>,
+    },
+
+    /// 无效的维护公告
+    #[error("Invalid maintenance notice: {reason}")]
+    InvalidMaintenanceNotice { reason: String },
+
+    /// NFT ID 位于保留区间内，只能由合约所有者铸造
+    #[error("Token id {token_id} is reserved ({start}-{end}) and can only be minted by the owner")]
+    ReservedTokenId { token_id: u64, start: u64, end: u64 },
+
+    /// 无效的保留 ID 区间
+    #[error("Invalid reserved id range: {reason}")]
+    InvalidReservedIdRange { reason: String },
+
+    /// 无效的合成销毁税配置
+    #[error("Invalid burn tax: {reason}")]
+    InvalidBurnTax { reason: String },
+
+    /// 无效的铸造凭证
+    #[error("Invalid mint voucher: {reason}")]
+    InvalidMintVoucher { reason: String },
+
+    /// 铸造凭证序号已被使用
+    #[error("Mint voucher nonce {nonce} already spent")]
+    VoucherNonceSpent { nonce: u64 },
+
+    /// 中继者铸造额度已用尽
+    #[error("Relay quota exhausted for {relayer}")]
+    RelayQuotaExhausted { relayer: String },
+
+    /// 无效的合成版税配置
+    #[error("Invalid crafting royalty: {reason}")]
+    InvalidCraftingRoyalty { reason: String },
+
+    /// 铸造者集合已永久锁定
+    #[error("Minter set is locked")]
+    MinterSetLocked {},
+
+    /// 无效的托管标记
+    #[error("Invalid custody tag: {reason}")]
+    InvalidCustodyTag { reason: String },
+
+    /// 无效的投票权委托
+    #[error("Invalid delegation: {reason}")]
+    InvalidDelegation { reason: String },
+
+    /// 组合包不存在
+    #[error("Bundle not found")]
+    BundleNotFound {},
+
+    /// 无效的组合包操作
+    #[error("Invalid bundle: {reason}")]
+    InvalidBundle { reason: String },
+
+    /// NFT 由合约托管中（礼物、组合包等）
+    #[error("Token {token_id} is held in escrow")]
+    TokenEscrowed { token_id: u64 },
+
+    /// 条件转移不存在
+    #[error("Conditional transfer not found")]
+    ConditionalTransferNotFound {},
+
+    /// 无效的条件转移操作
+    #[error("Invalid conditional transfer: {reason}")]
+    InvalidConditionalTransfer { reason: String },
+
+    /// 无效的合作集合 NFT 操作
+    #[error("Invalid partner NFT: {reason}")]
+    InvalidPartnerNft { reason: String },
+
+    /// 幂等键无效
+    #[error("Invalid idempotency key: {reason}")]
+    InvalidIdempotencyKey { reason: String },
+
+    /// 迁移目标不是本合约
+    #[error("Cannot migrate from contract {stored} to {target}")]
+    MigrationContractMismatch { stored: String, target: String },
+
+    /// 迁移会降低合约版本
+    #[error("Cannot migrate from version {stored} to older version {target} without allow_downgrade")]
+    MigrationDowngrade { stored: String, target: String },
+
+    /// 无法解析的合约版本号
+    #[error("Invalid contract version: {version}")]
+    InvalidContractVersion { version: String },
+
+    /// 该类型 NFT 须先申请销毁并等待冷却期
+    #[error("Burning {kind} requires a burn request and cooldown")]
+    BurnRequestRequired { kind: String },
+
+    /// 销毁申请不存在
+    #[error("Burn request not found")]
+    BurnRequestNotFound {},
+
+    /// 无效的销毁申请操作
+    #[error("Invalid burn request: {reason}")]
+    InvalidBurnRequest { reason: String },
+
+    /// 无效的旧版 NFT 导入
+    #[error("Invalid legacy import: {reason}")]
+    InvalidLegacyImport { reason: String },
+
+    /// 无效的会话密钥操作
+    #[error("Invalid session key: {reason}")]
+    InvalidSessionKey { reason: String },
+
+    /// 确定性 ID 与已有 NFT 冲突
+    #[error("Deterministic token id {token_id} for series {series_id} serial {serial} is already taken")]
+    TokenIdCollision { token_id: u64, series_id: String, serial: u64 },
+
+    /// 确定性 ID 模式下指定的 ID 与推导结果不一致
+    #[error("Token id {provided} does not match the deterministic id {expected}")]
+    DeterministicTokenIdMismatch { expected: u64, provided: u64 },
+
+    /// 实例化参数无效
+    #[error("Invalid instantiate field {field}: {reason}")]
+    InvalidInstantiateField { field: String, reason: String },
+
+    /// 实例化后的自检发现必需的存储项未初始化
+    #[error("Instantiate self-check failed: {item} is not initialized")]
+    InstantiateSelfCheckFailed { item: String },
+
+    /// 无效的子集合操作
+    #[error("Invalid child collection: {reason}")]
+    InvalidChildCollection { reason: String },
+
+    /// 无效的共享配方注册表
+    #[error("Invalid recipe registry: {reason}")]
+    InvalidRecipeRegistry { reason: String },
+
+    /// 无效的跨集合合成
+    #[error("Invalid cross-collection synthesis: {reason}")]
+    InvalidCrossCollectionSynthesis { reason: String },
+
+    /// 无效的奖池
+    #[error("Invalid mint pool: {reason}")]
+    InvalidMintPool { reason: String },
+
+    /// 奖池已抽完
+    #[error("Mint pool {pool_id} is exhausted")]
+    MintPoolExhausted { pool_id: String },
+}
+
+impl ContractError {
+    /// 为批量操作中的错误附加条目位置
+    /// 
+    /// # 参数
+    /// - `index`: 出错条目在批量中的下标（从 0 开始）
+    /// - `token_id`: 出错条目的 NFT ID
+    /// 
+    /// # 返回值
+    /// - `ContractError`: 包装后的 `BatchItemFailed` 错误
+    pub fn at_batch_item(self, index: usize, token_id: u64) -> Self {
+        ContractError::BatchItemFailed {
+            index,
+            token_id,
+            source: alloc::boxed::Box::new(self),
+        }
+    }
+}
+
+impl From<crate::logic::SynthesisError> for ContractError {
+    fn from(err: crate::logic::SynthesisError) -> Self {
+        match err {
+            crate::logic::SynthesisError::DuplicateInput { token_id } => ContractError::DuplicateInputToken { token_id },
+            crate::logic::SynthesisError::NoInputs | crate::logic::SynthesisError::InsufficientInputs => {
+                ContractError::InsufficientInputTokens {}
+            }
+            crate::logic::SynthesisError::RecipeNotFound => ContractError::RecipeNotFound {},
+            crate::logic::SynthesisError::RecipeCycle => ContractError::InvalidRecipe {},
+            crate::logic::SynthesisError::Overflow => ContractError::Overflow {},
+        }
+    }
 }
@@ -3,7 +3,7 @@
 //! 此模块包含所有标准 CW721 事件和 Luckee 扩展事件的生成函数
 //! 用于在区块链上发出可索引的事件，方便外部应用监听和查询
 
-use cosmwasm_std::{Addr, Event};
+use cosmwasm_std::{Addr, Coin, Event, Uint128};
 
 // ========== 事件属性常量 ==========
 
@@ -36,10 +36,46 @@ pub mod event_attributes {
     pub const TOTAL_SUPPLY: &str = "total_supply";
     /// 输入数量属性键
     pub const INPUTS_COUNT: &str = "inputs_count";
+    /// 输入 NFT ID 列表属性键（逗号分隔）
+    pub const INPUTS: &str = "inputs";
     /// 输出 NFT ID 属性键
     pub const OUTPUT_TOKEN_ID: &str = "output_token_id";
     /// 目标类型属性键
     pub const TARGET: &str = "target";
+    /// 原基础 URI 属性键
+    pub const OLD_BASE_URI: &str = "old_base_uri";
+    /// 新基础 URI 属性键
+    pub const NEW_BASE_URI: &str = "new_base_uri";
+    /// 交接阶段属性键
+    pub const PHASE: &str = "phase";
+    /// 原铸造者属性键
+    pub const OLD_MINTER: &str = "old_minter";
+    /// 新铸造者属性键
+    pub const NEW_MINTER: &str = "new_minter";
+    /// 生效高度属性键
+    pub const ACTIVATION_HEIGHT: &str = "activation_height";
+    /// 金额属性键
+    pub const AMOUNT: &str = "amount";
+    /// 余额属性键
+    pub const BALANCE: &str = "balance";
+    /// 本批处理数量属性键
+    pub const PROCESSED: &str = "processed";
+    /// 是否完成属性键
+    pub const COMPLETE: &str = "complete";
+    /// 变更字段列表属性键
+    pub const CHANGED_FIELDS: &str = "changed_fields";
+    /// 迁移前版本属性键
+    pub const PREVIOUS_VERSION: &str = "previous_version";
+    /// 迁移后版本属性键
+    pub const NEW_VERSION: &str = "new_version";
+    /// 已执行的迁移转换列表属性键（逗号分隔）
+    pub const TRANSFORMS: &str = "transforms";
+    /// 索引升级阶段属性键
+    pub const INDEX_PHASE: &str = "index_phase";
+    /// 索引已重建到的 NFT ID 属性键
+    pub const INDEX_LAST_TOKEN_ID: &str = "index_last_token_id";
+    /// 索引是否可用属性键
+    pub const INDEX_READY: &str = "index_ready";
 }
 
 /// 操作类型常量，统一管理所有操作类型
@@ -48,6 +84,8 @@ pub mod action_types {
     pub const MINT: &str = "mint";
     /// 销毁操作
     pub const BURN: &str = "burn";
+    /// 恢复已销毁 NFT 操作
+    pub const RESTORE: &str = "restore";
     /// 转移操作
     pub const TRANSFER: &str = "transfer";
     /// 批准操作
@@ -62,6 +100,22 @@ pub mod action_types {
     pub const SYNTHESIZE: &str = "synthesize";
     /// 批量铸造操作
     pub const BATCH_MINT: &str = "batch_mint";
+    /// 更新基础 URI 操作
+    pub const UPDATE_BASE_URI: &str = "update_base_uri";
+    /// 铸造者交接操作
+    pub const MINTER_HANDOVER: &str = "minter_handover";
+    /// 存款操作
+    pub const DEPOSIT: &str = "deposit";
+    /// 提取存款操作
+    pub const WITHDRAW: &str = "withdraw";
+    /// 空投分发操作
+    pub const DISTRIBUTE: &str = "distribute";
+    /// 索引升级操作
+    pub const INDEX_UPGRADE: &str = "index_upgrade";
+    /// 元数据更新操作
+    pub const META_UPDATE: &str = "meta_update";
+    /// 合约迁移操作
+    pub const MIGRATE: &str = "migrate";
 }
 
 // ========== 标准 CW721 事件 ==========
@@ -74,15 +128,17 @@ pub mod action_types {
 /// - `token_id`: NFT ID
 /// - `owner`: 所有者地址
 /// - `kind`: NFT 类型
+/// - `total_supply`: 铸造后的总供应量
 /// 
 /// # 返回值
 /// - `Event`: 铸造事件
-pub fn emit_mint_event(token_id: u64, owner: &str, kind: &str) -> Event {
+pub fn emit_mint_event(token_id: u64, owner: &str, kind: &str, total_supply: u64) -> Event {
     Event::new("wasm")
         .add_attribute(event_attributes::ACTION, action_types::MINT)
         .add_attribute(event_attributes::TOKEN_ID, token_id.to_string())
         .add_attribute(event_attributes::OWNER, owner)
         .add_attribute(event_attributes::KIND, kind)
+        .add_attribute(event_attributes::TOTAL_SUPPLY, total_supply.to_string())
 }
 
 /// 生成销毁事件
@@ -92,14 +148,35 @@ pub fn emit_mint_event(token_id: u64, owner: &str, kind: &str) -> Event {
 /// # 参数
 /// - `token_id`: NFT ID
 /// - `owner`: 所有者地址
+/// - `total_supply`: 销毁后的总供应量
 /// 
 /// # 返回值
 /// - `Event`: 销毁事件
-pub fn emit_burn_event(token_id: u64, owner: &Addr) -> Event {
+pub fn emit_burn_event(token_id: u64, owner: &Addr, total_supply: u64) -> Event {
     Event::new("wasm")
         .add_attribute(event_attributes::ACTION, action_types::BURN)
         .add_attribute(event_attributes::TOKEN_ID, token_id.to_string())
         .add_attribute(event_attributes::OWNER, owner.to_string())
+        .add_attribute(event_attributes::TOTAL_SUPPLY, total_supply.to_string())
+}
+
+/// 生成恢复事件
+/// 
+/// 当宽限期内的待销毁 NFT 被恢复时发出此事件
+/// 
+/// # 参数
+/// - `token_id`: NFT ID
+/// - `owner`: 所有者地址
+/// - `total_supply`: 恢复后的总供应量
+/// 
+/// # 返回值
+/// - `Event`: 恢复事件
+pub fn emit_restore_event(token_id: u64, owner: &Addr, total_supply: u64) -> Event {
+    Event::new("wasm")
+        .add_attribute(event_attributes::ACTION, action_types::RESTORE)
+        .add_attribute(event_attributes::TOKEN_ID, token_id.to_string())
+        .add_attribute(event_attributes::OWNER, owner.to_string())
+        .add_attribute(event_attributes::TOTAL_SUPPLY, total_supply.to_string())
 }
 
 /// 生成转移事件
@@ -204,16 +281,18 @@ pub fn emit_revoke_all_event(owner: &Addr, operator: &Addr) -> Event {
 /// - `target`: 目标 NFT 类型
 /// - `inputs_count`: 输入 NFT 数量
 /// - `user`: 执行合成的用户地址
+/// - `total_supply`: 合成后的总供应量
 /// 
 /// # 返回值
 /// - `Event`: 合成事件
-pub fn emit_synthesize_event(output_token_id: u64, target: &str, inputs_count: usize, user: &Addr) -> Event {
+pub fn emit_synthesize_event(output_token_id: u64, target: &str, inputs_count: usize, user: &Addr, total_supply: u64) -> Event {
     Event::new("wasm")
         .add_attribute(event_attributes::ACTION, action_types::SYNTHESIZE)
         .add_attribute(event_attributes::OUTPUT_TOKEN_ID, output_token_id.to_string())
         .add_attribute(event_attributes::TARGET, target)
         .add_attribute(event_attributes::INPUTS_COUNT, inputs_count.to_string())
         .add_attribute(event_attributes::OWNER, user.to_string())
+        .add_attribute(event_attributes::TOTAL_SUPPLY, total_supply.to_string())
 }
 
 /// 生成批量铸造事件
@@ -223,12 +302,199 @@ pub fn emit_synthesize_event(output_token_id: u64, target: &str, inputs_count: u
 /// # 参数
 /// - `count`: 铸造的 NFT 数量
 /// - `minter`: 铸造者地址
+/// - `total_supply`: 批量铸造后的总供应量
 /// 
 /// # 返回值
 /// - `Event`: 批量铸造事件
-pub fn emit_batch_mint_event(count: usize, minter: &Addr) -> Event {
+pub fn emit_batch_mint_event(count: usize, minter: &Addr, total_supply: u64) -> Event {
     Event::new("wasm")
         .add_attribute(event_attributes::ACTION, action_types::BATCH_MINT)
         .add_attribute(event_attributes::INPUTS_COUNT, count.to_string())
         .add_attribute(event_attributes::OWNER, minter.to_string())
+        .add_attribute(event_attributes::TOTAL_SUPPLY, total_supply.to_string())
+}
+
+// ========== 管理员事件 ==========
+
+/// 生成更新基础 URI 事件
+/// 
+/// 当基础 URI 被更新或清除时发出此事件，未设置的一侧以空字符串表示
+/// 
+/// # 参数
+/// - `old_base_uri`: 原基础 URI
+/// - `new_base_uri`: 新基础 URI
+/// 
+/// # 返回值
+/// - `Event`: 更新基础 URI 事件
+pub fn emit_update_base_uri_event(old_base_uri: Option<&str>, new_base_uri: Option<&str>) -> Event {
+    Event::new("wasm")
+        .add_attribute(event_attributes::ACTION, action_types::UPDATE_BASE_URI)
+        .add_attribute(event_attributes::OLD_BASE_URI, old_base_uri.unwrap_or_default())
+        .add_attribute(event_attributes::NEW_BASE_URI, new_base_uri.unwrap_or_default())
+}
+
+/// 生成铸造者交接事件
+/// 
+/// 铸造者轮换在提议和生效时各发出一次此事件，立即更换铸造者时发出一次
+/// 
+/// # 参数
+/// - `phase`: 交接阶段（proposed、activated）
+/// - `old_minter`: 原铸造者地址
+/// - `new_minter`: 新铸造者地址
+/// - `activation_height`: 生效高度
+/// 
+/// # 返回值
+/// - `Event`: 铸造者交接事件
+pub fn emit_minter_handover_event(
+    phase: &str,
+    old_minter: &Addr,
+    new_minter: &Addr,
+    activation_height: u64,
+) -> Event {
+    Event::new("wasm")
+        .add_attribute(event_attributes::ACTION, action_types::MINTER_HANDOVER)
+        .add_attribute(event_attributes::PHASE, phase)
+        .add_attribute(event_attributes::OLD_MINTER, old_minter.to_string())
+        .add_attribute(event_attributes::NEW_MINTER, new_minter.to_string())
+        .add_attribute(event_attributes::ACTIVATION_HEIGHT, activation_height.to_string())
+}
+
+// ========== 存款事件 ==========
+
+/// 生成存款事件
+/// 
+/// 当用户存入资金时发出此事件，每个币种一个事件
+/// 
+/// # 参数
+/// - `owner`: 存款用户地址
+/// - `amount`: 存入的资金
+/// - `balance`: 存入后的余额
+/// 
+/// # 返回值
+/// - `Event`: 存款事件
+pub fn emit_deposit_event(owner: &Addr, amount: &Coin, balance: Uint128) -> Event {
+    Event::new("wasm")
+        .add_attribute(event_attributes::ACTION, action_types::DEPOSIT)
+        .add_attribute(event_attributes::OWNER, owner.to_string())
+        .add_attribute(event_attributes::AMOUNT, amount.to_string())
+        .add_attribute(event_attributes::BALANCE, balance.to_string())
+}
+
+/// 生成提取存款事件
+/// 
+/// 当用户提取存款时发出此事件
+/// 
+/// # 参数
+/// - `owner`: 存款用户地址
+/// - `amount`: 提取的资金
+/// - `balance`: 提取后的余额
+/// 
+/// # 返回值
+/// - `Event`: 提取存款事件
+pub fn emit_withdraw_event(owner: &Addr, amount: &Coin, balance: Uint128) -> Event {
+    Event::new("wasm")
+        .add_attribute(event_attributes::ACTION, action_types::WITHDRAW)
+        .add_attribute(event_attributes::OWNER, owner.to_string())
+        .add_attribute(event_attributes::AMOUNT, amount.to_string())
+        .add_attribute(event_attributes::BALANCE, balance.to_string())
+}
+
+/// 生成空投分发事件
+/// 
+/// 从合约托管库存分发 NFT 时发出此事件，每个 NFT 一个事件
+/// 
+/// # 参数
+/// - `token_id`: NFT ID
+/// - `operator`: 执行分发的地址
+/// - `recipient`: 接收者地址
+/// 
+/// # 返回值
+/// - `Event`: 空投分发事件
+pub fn emit_distribute_event(token_id: u64, operator: &Addr, recipient: &Addr) -> Event {
+    Event::new("wasm")
+        .add_attribute(event_attributes::ACTION, action_types::DISTRIBUTE)
+        .add_attribute(event_attributes::TOKEN_ID, token_id.to_string())
+        .add_attribute(event_attributes::OPERATOR, operator.to_string())
+        .add_attribute(event_attributes::RECIPIENT, recipient.to_string())
+}
+
+/// 生成索引升级进度事件
+/// 
+/// 每批索引升级发出一个事件，便于运维跟踪进度
+/// 
+/// # 参数
+/// - `phase`: 本批所处的升级阶段
+/// - `processed`: 本批处理的条目数量
+/// - `complete`: 升级是否已全部完成
+/// 
+/// # 返回值
+/// - `Event`: 索引升级事件
+pub fn emit_index_upgrade_event(phase: &str, processed: usize, complete: bool) -> Event {
+    Event::new("wasm")
+        .add_attribute(event_attributes::ACTION, action_types::INDEX_UPGRADE)
+        .add_attribute(event_attributes::PHASE, phase)
+        .add_attribute(event_attributes::PROCESSED, processed.to_string())
+        .add_attribute(event_attributes::COMPLETE, complete.to_string())
+}
+
+/// 生成合约迁移事件
+/// 
+/// 每次迁移发出一个事件，运维工具可据此确认各链上的升级结果和索引重建进度。
+/// 没有执行任何转换时 `transforms` 为 `none`；索引可用时 `index_phase` 为 `done`
+/// 
+/// # 参数
+/// - `previous_version`: 迁移前的合约版本
+/// - `new_version`: 迁移后的合约版本
+/// - `transforms`: 本次迁移执行的数据转换
+/// - `index_phase`: 迁移结束时的索引升级阶段
+/// - `index_last_token_id`: 索引已重建到的最后一个 NFT ID
+/// - `index_ready`: 二级索引是否已可用
+/// 
+/// # 返回值
+/// - `Event`: 合约迁移事件
+pub fn emit_migrate_event(
+    previous_version: &str,
+    new_version: &str,
+    transforms: &[&str],
+    index_phase: &str,
+    index_last_token_id: Option<u64>,
+    index_ready: bool,
+) -> Event {
+    let transforms = if transforms.is_empty() { "none".into() } else { transforms.join(",") };
+    Event::new("wasm")
+        .add_attribute(event_attributes::ACTION, action_types::MIGRATE)
+        .add_attribute(event_attributes::PREVIOUS_VERSION, previous_version)
+        .add_attribute(event_attributes::NEW_VERSION, new_version)
+        .add_attribute(event_attributes::TRANSFORMS, transforms)
+        .add_attribute(event_attributes::INDEX_PHASE, index_phase)
+        .add_attribute(
+            event_attributes::INDEX_LAST_TOKEN_ID,
+            index_last_token_id.map_or("none".into(), |token_id| token_id.to_string()),
+        )
+        .add_attribute(event_attributes::INDEX_READY, index_ready.to_string())
+}
+
+/// 生成元数据变更事件
+/// 
+/// NFT 元数据被修改时发出此事件，每个变更字段附带 `<字段>_before` 和 `<字段>_after` 属性，
+/// 便于索引器直接还原审计记录
+/// 
+/// # 参数
+/// - `token_id`: NFT ID
+/// - `changes`: 变更字段列表（字段名、修改前的值、修改后的值）
+/// 
+/// # 返回值
+/// - `Event`: 元数据变更事件
+pub fn emit_meta_update_event(token_id: u64, changes: &[(&str, String, String)]) -> Event {
+    let fields = changes.iter().map(|(field, _, _)| *field).collect::<alloc::vec::Vec<_>>().join(",");
+    let mut event = Event::new("wasm")
+        .add_attribute(event_attributes::ACTION, action_types::META_UPDATE)
+        .add_attribute(event_attributes::TOKEN_ID, token_id.to_string())
+        .add_attribute(event_attributes::CHANGED_FIELDS, fields);
+    for (field, before, after) in changes {
+        event = event
+            .add_attribute(alloc::format!("{}_before", field), before.clone())
+            .add_attribute(alloc::format!("{}_after", field), after.clone());
+    }
+    event
 }
@@ -0,0 +1,209 @@
+//! 集合工厂模块
+//! 
+//! 此模块实现由本合约创建季节性子集合的工厂模式，包括：
+//! - 创建子集合：合约所有者以本合约的代码实例化新的 Luckee NFT 集合，继承铸造者和基础 URI
+//! - 子集合注册：实例化成功的回复中将子集合登记到注册表
+//! - 子集合管理：子集合的合约所有者是本合约，合约所有者通过本合约向子集合转发执行消息
+//! - 查询：分页列出子集合和查询单个子集合记录
+//! 
+//! 子集合的 wasm 管理员（迁移权限）设为本合约的所有者，可直接迁移子集合
+
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdError, StdResult,
+    SubMsg, WasmMsg,
+};
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::helpers::{release_execution_lock, validate_instantiate_fields};
+use crate::msg::{CollectionEntry, CollectionResponse, CollectionsResponse, Cursor, InstantiateMsg};
+use crate::pagination::{clamp_limit, cursor_scope, decode_cursor, into_page, start_bound, MAX_LIMIT};
+use crate::state::{ChildCollection, COLLECTIONS, PENDING_COLLECTION};
+
+/// 子集合实例化回复 ID
+pub const CHILD_COLLECTION_REPLY_ID: u64 = 2;
+
+/// 实例化事件类型
+const INSTANTIATE_EVENT: &str = "instantiate";
+
+/// 实例化事件中表示新合约地址的属性名
+const CONTRACT_ADDRESS_ATTR: &str = "_contract_address";
+
+// ========== 工厂执行接口 ==========
+
+/// 创建子集合
+/// 
+/// 以本合约的代码 ID 实例化新集合，铸造者沿用本合约的配置，
+/// `base_uri` 为 None 时沿用本合约的基础 URI。只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储、API 和查询访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于查询本合约的代码 ID
+/// - `info`: 消息信息，包含发送者
+/// - `name`: 子集合名称
+/// - `symbol`: 子集合符号
+/// - `base_uri`: 子集合基础 URI（可选）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 包含实例化子消息的响应
+pub fn execute_create_collection(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    symbol: String,
+    base_uri: Option<String>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let base_uri = base_uri.or_else(|| ctx.config.base_uri.clone());
+    validate_instantiate_fields(&name, &symbol, base_uri.as_deref())?;
+    if PENDING_COLLECTION.exists(deps.storage) {
+        return Err(ContractError::InvalidChildCollection { reason: "creation already pending".to_string() });
+    }
+
+    let code_id = deps.querier.query_wasm_contract_info(env.contract.address.to_string())?.code_id;
+    PENDING_COLLECTION.save(deps.storage, &ChildCollection {
+        name: name.clone(),
+        symbol: symbol.clone(),
+        code_id,
+        created_at: env.block.time.seconds(),
+    })?;
+
+    let instantiate = WasmMsg::Instantiate {
+        admin: Some(ctx.config.owner.to_string()),
+        code_id,
+        msg: to_json_binary(&InstantiateMsg {
+            name: name.clone(),
+            symbol: symbol.clone(),
+            minter: ctx.config.minter.to_string(),
+            base_uri,
+        })?,
+        funds: vec![],
+        label: name.clone(),
+    };
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(instantiate, CHILD_COLLECTION_REPLY_ID))
+        .add_attribute("action", "create_collection")
+        .add_attribute("name", name)
+        .add_attribute("symbol", symbol)
+        .add_attribute("code_id", code_id.to_string()))
+}
+
+/// 向子集合转发执行消息
+/// 
+/// 子集合的合约所有者是本合约，所有者专属的操作（暂停、设置配方等）须经此转发。
+/// 只转发给注册表中的子集合。只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `collection`: 子集合地址
+/// - `msg`: 子集合的执行消息（JSON）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 包含转发消息的响应
+pub fn execute_execute_on_collection(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    collection: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let collection_addr = deps.api.addr_validate(&collection)?;
+    if !COLLECTIONS.has(deps.storage, collection_addr.clone()) {
+        return Err(ContractError::InvalidChildCollection { reason: "not a child collection".to_string() });
+    }
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute { contract_addr: collection_addr.to_string(), msg, funds: vec![] })
+        .add_attribute("action", "execute_on_collection")
+        .add_attribute("collection", collection_addr.to_string()))
+}
+
+/// 处理子集合实例化回复
+/// 
+/// 从实例化事件中读取新合约地址，将待回复的子集合登记到注册表
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `msg`: 子消息回复
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 登记结果
+pub fn reply_child_collection(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    // 子集合实例化计入了执行锁
+    release_execution_lock(deps.storage)?;
+
+    let pending = PENDING_COLLECTION.may_load(deps.storage)?
+        .ok_or_else(|| ContractError::InvalidChildCollection { reason: "no pending creation".to_string() })?;
+    let result = msg.result.into_result().map_err(StdError::generic_err)?;
+    let address = result.events.iter()
+        .filter(|event| event.ty == INSTANTIATE_EVENT)
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == CONTRACT_ADDRESS_ATTR)
+        .map(|attr| attr.value.clone())
+        .ok_or_else(|| ContractError::InvalidChildCollection { reason: "missing contract address".to_string() })?;
+    let address = deps.api.addr_validate(&address)?;
+
+    PENDING_COLLECTION.remove(deps.storage);
+    COLLECTIONS.save(deps.storage, address.clone(), &pending)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_collection")
+        .add_attribute("collection", address.to_string())
+        .add_attribute("name", pending.name)
+        .add_attribute("symbol", pending.symbol))
+}
+
+// ========== 工厂查询接口 ==========
+
+/// 查询子集合列表
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `start_after`: 上一页响应的 `next_cursor`（不包含）
+/// - `limit`: 每页数量
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 按地址升序的子集合
+pub fn query_collections(deps: Deps, start_after: Option<Cursor>, limit: Option<u32>) -> StdResult<Binary> {
+    let limit = clamp_limit(limit, MAX_LIMIT);
+    let start_after = decode_cursor::<String>(cursor_scope::COLLECTIONS, start_after)?;
+    let start = start_bound(start_after.map(Addr::unchecked));
+
+    let collections = COLLECTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit + 1)
+        .map(|item| item.map(|(address, collection)| CollectionEntry { address: address.to_string(), collection }))
+        .collect::<StdResult<Vec<_>>>()?;
+    let page = into_page(cursor_scope::COLLECTIONS, collections, limit, |entry| entry.address.clone());
+
+    to_json_binary(&CollectionsResponse {
+        collections: page.items,
+        next_cursor: page.next_cursor,
+        has_more: page.has_more,
+    })
+}
+
+/// 查询子集合记录
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `address`: 子集合地址
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 子集合记录（不是本合约创建的子集合时为 None）
+pub fn query_collection(deps: Deps, address: String) -> StdResult<Binary> {
+    let addr = deps.api.addr_validate(&address)?;
+    let collection = COLLECTIONS.may_load(deps.storage, addr)?;
+    to_json_binary(&CollectionResponse { address, collection })
+}
@@ -0,0 +1,208 @@
+//! 礼物领取码模块
+//! 
+//! 此模块实现通过领取码赠送 NFT，包括：
+//! - 创建礼物：将 NFT 托管到合约，并登记领取码的 SHA-256 哈希
+//! - 领取礼物：任何人提交领取码原文即可领取
+//! - 取消礼物：过期后由赠送者取回
+//! 
+//! 适用于赠送给尚未拥有地址的用户
+
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use sha2::{Digest, Sha256};
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::helpers::{escrowed_token_meta, move_token, record_activity, validate_recipient, ensure_not_session_locked};
+use crate::msg::GiftResponse;
+use crate::state::{Gift, GIFTS, GIFTED_TOKENS, TOKEN_META, TOKEN_OWNERSHIP};
+
+/// 领取码哈希长度（SHA-256 十六进制）
+pub const CLAIM_HASH_LENGTH: usize = 64;
+
+// ========== 礼物执行接口 ==========
+
+/// 创建礼物
+/// 
+/// 将发送者的 NFT 托管到合约，持有领取码原文的任何人可在过期前领取
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，包含合约地址和当前时间
+/// - `info`: 消息信息，包含发送者
+/// - `token_id`: 赠送的 NFT ID
+/// - `claim_hash`: 领取码的 SHA-256 哈希（十六进制）
+/// - `expires`: 礼物过期时间（秒）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 创建结果
+pub fn execute_create_gift(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+    claim_hash: String,
+    expires: u64,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    // 验证领取码哈希格式，统一为小写
+    if claim_hash.len() != CLAIM_HASH_LENGTH || !claim_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ContractError::InvalidGift { reason: "claim hash must be 64 hex characters".to_string() });
+    }
+    let claim_hash = claim_hash.to_ascii_lowercase();
+    if GIFTS.has(deps.storage, claim_hash.clone()) {
+        return Err(ContractError::InvalidGift { reason: "claim hash already in use".to_string() });
+    }
+    if expires <= env.block.time.seconds() {
+        return Err(ContractError::InvalidGift { reason: "expiration must be in the future".to_string() });
+    }
+
+    // 验证所有者身份
+    let owner = TOKEN_OWNERSHIP.may_load(deps.storage, token_id)?
+        .ok_or(ContractError::TokenNotFound {})?;
+    if owner != info.sender {
+        return Err(ContractError::NotOwned {});
+    }
+    let meta = TOKEN_META.load(deps.storage, token_id)?;
+    if meta.is_expired(env.block.time.seconds()) {
+        return Err(ContractError::TokenExpired {});
+    }
+    ensure_not_session_locked(deps.storage, &env, token_id)?;
+
+    // 托管到合约
+    move_token(deps.storage, &env, token_id, &info.sender, &env.contract.address, &meta.kind)?;
+    GIFTS.save(deps.storage, claim_hash.clone(), &Gift {
+        sender: info.sender.clone(),
+        token_id,
+        expires,
+    })?;
+    GIFTED_TOKENS.save(deps.storage, token_id, &claim_hash)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_gift")
+        .add_attribute("token_id", token_id.to_string())
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("claim_hash", claim_hash)
+        .add_attribute("expires", expires.to_string()))
+}
+
+/// 领取礼物
+/// 
+/// 提交领取码原文，哈希匹配且礼物未过期时将 NFT 转给发送者
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，包含合约地址和当前时间
+/// - `info`: 消息信息，包含发送者
+/// - `preimage`: 领取码原文
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 领取结果
+pub fn execute_claim_gift(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    preimage: String,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    let claim_hash = hash_claim_code(&preimage);
+    let gift = GIFTS.may_load(deps.storage, claim_hash.clone())?
+        .ok_or(ContractError::GiftNotFound {})?;
+    if env.block.time.seconds() >= gift.expires {
+        return Err(ContractError::InvalidGift { reason: "gift expired".to_string() });
+    }
+    validate_recipient(deps.storage, &env.contract.address, &info.sender)?;
+
+    // 托管期间 NFT 可能已过期被回收，同一 ID 也可能已重新铸造给他人
+    let meta = escrowed_token_meta(deps.storage, &env, gift.token_id)?
+        .ok_or(ContractError::TokenNotFound {})?;
+
+    GIFTS.remove(deps.storage, claim_hash);
+    GIFTED_TOKENS.remove(deps.storage, gift.token_id);
+    move_token(deps.storage, &env, gift.token_id, &env.contract.address, &info.sender, &meta.kind)?;
+
+    // 记录到活动流
+    record_activity(deps.storage, &env, "claim_gift", Some(gift.token_id), &info.sender)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_gift")
+        .add_attribute("token_id", gift.token_id.to_string())
+        .add_attribute("sender", gift.sender.to_string())
+        .add_attribute("recipient", info.sender.to_string()))
+}
+
+/// 取消礼物
+/// 
+/// 礼物过期后，赠送者可取回托管的 NFT，合约暂停期间也可取消
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，包含合约地址和当前时间
+/// - `info`: 消息信息，包含发送者
+/// - `claim_hash`: 领取码哈希
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 取消结果
+pub fn execute_cancel_gift(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    claim_hash: String,
+) -> Result<Response, ContractError> {
+    let claim_hash = claim_hash.to_ascii_lowercase();
+    let gift = GIFTS.may_load(deps.storage, claim_hash.clone())?
+        .ok_or(ContractError::GiftNotFound {})?;
+    if gift.sender != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if env.block.time.seconds() < gift.expires {
+        return Err(ContractError::InvalidGift { reason: "gift not expired yet".to_string() });
+    }
+
+    GIFTS.remove(deps.storage, claim_hash);
+    GIFTED_TOKENS.remove(deps.storage, gift.token_id);
+
+    // NFT 仍由合约托管时归还赠送者
+    if let Some(meta) = escrowed_token_meta(deps.storage, &env, gift.token_id)? {
+        move_token(deps.storage, &env, gift.token_id, &env.contract.address, &gift.sender, &meta.kind)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_gift")
+        .add_attribute("token_id", gift.token_id.to_string())
+        .add_attribute("sender", gift.sender.to_string()))
+}
+
+// ========== 礼物查询接口 ==========
+
+/// 查询礼物
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `claim_hash`: 领取码哈希
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 礼物信息（不存在时为 None）
+pub fn query_gift(deps: Deps, claim_hash: String) -> StdResult<Binary> {
+    let gift = GIFTS.may_load(deps.storage, claim_hash.to_ascii_lowercase())?;
+    to_json_binary(&GiftResponse { gift })
+}
+
+// ========== 内部辅助函数 ==========
+
+/// 计算领取码的 SHA-256 哈希（小写十六进制）
+/// 
+/// 条件转移的哈希锁使用相同的计算方式
+pub fn hash_claim_code(preimage: &str) -> String {
+    Sha256::digest(preimage.as_bytes())
+        .iter()
+        .map(|byte| alloc::format!("{:02x}", byte))
+        .collect()
+}
@@ -6,11 +6,29 @@
 //! - 数据验证
 //! - 索引维护
 
-use cosmwasm_std::{Addr, Deps, Storage};
+use cosmwasm_std::{to_json_binary, Addr, Coin, CosmosMsg, Deps, Env, Event, Order, ReplyOn, Response, StdResult,
+                   Storage, SubMsg, WasmMsg};
 use crate::error::ContractError;
-use crate::state::TOKEN_APPROVALS;
-use crate::state::{Config, CONTRACT_PAUSED, ALLOWED_MINTERS, TOKEN_META, TOKEN_OWNERSHIP, TOKENS_BY_OWNER};
-use crate::types::Recipe;
+use crate::state::{TOKEN_APPROVALS, SERIES_TOKEN_COUNT, BURN_LISTENERS, BLOCKED_RECIPIENTS, KIND_MINT_STATUS,
+                   ACTIVITY_LOG, NEXT_ACTIVITY_SEQ, ActivityEntry, SERIES_OWNERS, SERIES_GRANTS,
+                   URI_TEMPLATES, CollectionInfo, STORAGE_COUNTERS, EXECUTION_LOCK,
+                   COUNTS_BY_OWNER_KIND, OWNER_KIND_COUNTS_READY, KIND_APPROVALS, VOTING_POWER,
+                   DELEGATIONS, DELEGATED_POWER,
+                   TRUSTED_OPERATORS, TRUSTED_OPERATOR_OPT_INS, SUSPENDED_OPERATORS,
+                   OPERATOR_APPROVALS, OPERATOR_BUDGETS, ROLE_MEMBERS, SESSION_LOCKS, SessionLock,
+                   TOKENS_BY_KIND, TOKENS_BY_SERIES, TOKENS_BY_SERIAL, TOKENS_BY_GROUP, TOKEN_INDEXES_READY, INDEX_UPGRADE,
+                   IndexUpgradeCursor, IndexUpgradePhase, CRAFTING_LIMIT, CRAFTED_VALUE, CraftedValueWindow,
+                   MINTER_STATS, MinterStats, APPROVED_TOKENS_BY_OWNER, SERIES_NEXT_SERIAL,
+                   NEXT_TOKEN_ID, RESERVED_ID_RANGE, ReservedIdRange, ALL_TOKENS, TOTAL_SUPPLY,
+                   TOKEN_ACQUIRED, Acquisition, CUSTODY_TAGS, CUSTODY_TOKENS, MINT_RECEIPTS, MintReceipt,
+                   EVENT_VERBOSITY, SESSION_KEYS, DETERMINISTIC_TOKEN_IDS, PENDING_BURNS, GIFTED_TOKENS,
+                   BUNDLED_TOKENS, CONDITIONAL_TRANSFERS, BURN_REQUESTS};
+use crate::events::{action_types, event_attributes, emit_batch_mint_event, emit_mint_event};
+use crate::msg::{BatchMintItem, BurnHookMsg, ExecuteMsg};
+use crate::state::{Config, CONTRACT_PAUSED, ALLOWED_MINTERS, TOKEN_META, TOKEN_OWNERSHIP, TOKENS_BY_OWNER,
+                   MINTER_SET_LOCKED_AT};
+use crate::types::{NftKind, NftMeta, Recipe, Role, SessionKeyScope};
+use sha2::{Digest, Sha256};
 
 // ========== 状态检查函数 ==========
 
@@ -33,6 +51,38 @@ pub fn check_contract_paused(storage: &dyn Storage) -> Result<(), ContractError>
 
 // ========== 权限验证函数 ==========
 
+/// 验证运营角色
+/// 
+/// 检查地址是否被授予指定的运营角色
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `role`: 运营角色
+/// - `addr`: 待检查的地址
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 未持有角色时返回 `MissingRole`
+pub fn ensure_role(storage: &dyn Storage, role: &Role, addr: &Addr) -> Result<(), ContractError> {
+    if !ROLE_MEMBERS.has(storage, (role.as_str().to_string(), addr.clone())) {
+        return Err(ContractError::MissingRole { role: role.as_str().to_string() });
+    }
+    Ok(())
+}
+
+/// 验证铸造者集合未被锁定
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 已执行 `LockMinterSet` 时返回 `MinterSetLocked`
+pub fn ensure_minter_set_unlocked(storage: &dyn Storage) -> Result<(), ContractError> {
+    if MINTER_SET_LOCKED_AT.exists(storage) {
+        return Err(ContractError::MinterSetLocked {});
+    }
+    Ok(())
+}
+
 /// 验证铸造权限
 /// 
 /// 检查指定地址是否有铸造 NFT 的权限
@@ -58,6 +108,99 @@ pub fn is_authorized_minter(deps: Deps, sender: &Addr, config: &Config) -> Resul
     Ok(false)
 }
 
+/// 验证铸造者对系列的铸造权限
+/// 
+/// 系列尚无所有者时，由本次铸造者认领；已有所有者时，
+/// 只有系列所有者或被授权的铸造者可以向该系列铸造
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `series_id`: 系列 ID
+/// - `minter`: 铸造者地址
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 无权向该系列铸造时返回错误
+pub fn authorize_series_mint(
+    storage: &mut dyn Storage,
+    series_id: &str,
+    minter: &Addr,
+) -> Result<(), ContractError> {
+    match SERIES_OWNERS.may_load(storage, series_id.to_string())? {
+        None => {
+            SERIES_OWNERS.save(storage, series_id.to_string(), minter)?;
+            Ok(())
+        }
+        Some(owner) if &owner == minter => Ok(()),
+        Some(_) if SERIES_GRANTS.has(storage, (series_id.to_string(), minter.clone())) => Ok(()),
+        Some(_) => Err(ContractError::SeriesNotAuthorized { series_id: series_id.to_string() }),
+    }
+}
+
+/// 检查指定类型是否允许铸造
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `kind`: NFT 类型
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 该类型铸造被暂停或冻结时返回错误
+pub fn ensure_kind_mintable(storage: &dyn Storage, kind: &NftKind) -> Result<(), ContractError> {
+    if let Some(status) = KIND_MINT_STATUS.may_load(storage, kind.to_key())? {
+        if !status.is_mintable() {
+            return Err(ContractError::KindMintDisabled { kind: kind.to_key() });
+        }
+    }
+    Ok(())
+}
+
+/// 验证 NFT 接收者
+/// 
+/// 拒绝将 NFT 发送到合约自身地址（无法找回）或被禁止的接收地址
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `contract_addr`: 本合约地址
+/// - `recipient`: 接收者地址
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 接收者无效时返回错误
+pub fn validate_recipient(
+    storage: &dyn Storage,
+    contract_addr: &Addr,
+    recipient: &Addr,
+) -> Result<(), ContractError> {
+    if recipient == contract_addr {
+        return Err(ContractError::InvalidRecipient { reason: "recipient is the contract itself".to_string() });
+    }
+    if BLOCKED_RECIPIENTS.has(storage, recipient.clone()) {
+        return Err(ContractError::InvalidRecipient { reason: "recipient is blocked".to_string() });
+    }
+    Ok(())
+}
+
+/// 验证铸造所有者
+/// 
+/// 允许铸造到合约自身作为空投库存（由空投角色通过 `Distribute` 分发），
+/// 其他所有者按 `validate_recipient` 校验
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `contract_addr`: 本合约地址
+/// - `owner`: 铸造所有者地址
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 所有者无效时返回错误
+pub fn validate_mint_owner(
+    storage: &dyn Storage,
+    contract_addr: &Addr,
+    owner: &Addr,
+) -> Result<(), ContractError> {
+    if owner == contract_addr {
+        return Ok(());
+    }
+    validate_recipient(storage, contract_addr, owner)
+}
+
 /// 验证 NFT 所有权
 /// 
 /// 检查指定地址是否拥有指定的 NFT
@@ -83,64 +226,330 @@ pub fn verify_nft_ownership(
 
 /// 验证合成输入
 /// 
-/// 验证合成操作的输入 NFT 是否有效且符合配方要求
+/// 验证合成操作的输入 NFT 是否有效且符合配方要求。
+/// 所有输入必须属于同一所有者，发送者须为该所有者、持有各输入类型的按类型授权，
+/// 或持有包含合成操作的会话密钥授权。
+/// 每个输入的所有权和元数据只读取一次，遇到第一个无效输入立即返回错误
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，用于检查按类型授权是否过期
 /// - `sender`: 发送者地址
 /// - `inputs`: 输入 NFT ID 列表
 /// - `recipe`: 合成配方
 /// 
 /// # 返回值
-/// - `Result<(), ContractError>`: 验证结果
+/// - `Result<(Addr, Vec<NftMeta>), ContractError>`: 输入所有者，以及与 `inputs` 顺序一致的输入元数据
 pub fn validate_synthesis_inputs(
     deps: Deps,
+    env: &Env,
     sender: &Addr,
     inputs: &[u64],
     recipe: &Recipe,
-) -> Result<(), ContractError> {
+) -> Result<(Addr, Vec<NftMeta>), ContractError> {
     // 检查输入数量
-    if inputs.is_empty() {
-        return Err(ContractError::InsufficientInputTokens {});
+    let first = *inputs.first().ok_or(ContractError::InsufficientInputTokens {})?;
+
+    // 第一个输入的所有者即为合成的所有者（不存在的 NFT 没有所有者记录）
+    let owner = TOKEN_OWNERSHIP.may_load(deps.storage, first)?
+        .ok_or(ContractError::TokenNotFound {})?;
+
+    // 持有数量计数可用时，先以 O(1) 检查所有者是否持有足够数量的各类型 NFT
+    if OWNER_KIND_COUNTS_READY.may_load(deps.storage)?.unwrap_or(false) {
+        for recipe_input in &recipe.inputs {
+            if owner_kind_count(deps.storage, &owner, &recipe_input.nft_kind)? < recipe_input.count as u64 {
+                return Err(ContractError::InsufficientInputTokens {});
+            }
+        }
     }
 
-    // 预先加载所有输入NFT的元数据，避免重复读取
-    let mut input_metas = alloc::collections::BTreeMap::new();
+    // 会话密钥授权对全部输入生效
+    let session_key = owner != *sender
+        && has_session_key_scope(deps.storage, env, &owner, sender, &SessionKeyScope::Synthesize)?;
+
+    // 单次遍历：检查重复、所有权和元数据
+    let mut seen = alloc::collections::BTreeSet::new();
+    let mut input_metas = Vec::with_capacity(inputs.len());
     for token_id in inputs {
-        let meta = TOKEN_META.may_load(deps.storage, *token_id)?;
-        if meta.is_none() {
-            return Err(ContractError::TokenNotFound {});
+        if !seen.insert(*token_id) {
+            return Err(ContractError::DuplicateInputToken { token_id: *token_id });
         }
-        
-        // 验证 CW721 所有权
-        if !verify_nft_ownership(deps, *token_id, sender)? {
-            return Err(ContractError::NotOwned {});
+
+        // 所有输入必须属于同一所有者
+        if *token_id != first {
+            match TOKEN_OWNERSHIP.may_load(deps.storage, *token_id)? {
+                None => return Err(ContractError::TokenNotFound {}),
+                Some(token_owner) if token_owner != owner => return Err(ContractError::NotOwned {}),
+                Some(_) => {}
+            }
         }
-        
-        // 缓存元数据供后续使用
-        if let Some(meta) = meta {
-            input_metas.insert(*token_id, meta);
+
+        let meta = TOKEN_META.may_load(deps.storage, *token_id)?
+            .ok_or(ContractError::TokenNotFound {})?;
+
+        // 对局中锁定的 NFT 不可作为合成输入
+        ensure_not_session_locked(deps.storage, env, *token_id)?;
+
+        // 受销毁冷却保护的输入须先完成销毁申请
+        crate::burn_queue::ensure_synthesis_input_burnable(deps.storage, env, *token_id, &meta.kind)?;
+
+        // 发送者须为所有者或持有该类型的授权
+        if !session_key && !can_operate_kind(deps.storage, env, &owner, sender, &meta.kind)? {
+            return Err(ContractError::NotOwned {});
         }
+
+        input_metas.push(meta);
+    }
+
+    // 验证配方要求（与链下模拟共用同一校验逻辑）
+    let typed_inputs: Vec<(u64, NftKind)> = inputs.iter().copied()
+        .zip(input_metas.iter().map(|meta| meta.kind.clone()))
+        .collect();
+    crate::logic::check_synthesis_inputs(&typed_inputs, &recipe.inputs)?;
+
+    Ok((owner, input_metas))
+}
+
+/// 读取所有者当前窗口的合成产出
+/// 
+/// 窗口已结束时返回从当前时间开始的新窗口
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `env`: 环境信息，包含当前时间
+/// - `owner`: 所有者地址
+/// - `window_seconds`: 窗口长度（秒）
+/// 
+/// # 返回值
+/// - `StdResult<CraftedValueWindow>`: 当前窗口
+pub fn current_crafted_window(
+    storage: &dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    window_seconds: u64,
+) -> StdResult<CraftedValueWindow> {
+    let now = env.block.time.seconds();
+    Ok(match CRAFTED_VALUE.may_load(storage, owner.clone())? {
+        Some(window) if now < window.window_start.saturating_add(window_seconds) => window,
+        _ => CraftedValueWindow { window_start: now, value: 0 },
+    })
+}
+
+/// 扣减合成价值额度
+/// 
+/// 设置了合成价值上限时，检查所有者当前窗口的产出加上本次产出不超过上限并记录
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `env`: 环境信息，包含当前时间
+/// - `owner`: 合成产出的所有者
+/// - `value`: 本次产出的兑换价值
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 超出上限时返回 `CraftingLimitExceeded`
+pub fn consume_crafting_allowance(
+    storage: &mut dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    value: u64,
+) -> Result<(), ContractError> {
+    let limit = match CRAFTING_LIMIT.may_load(storage)? {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+    let mut window = current_crafted_window(storage, env, owner, limit.window_seconds)?;
+    let used = window.value.saturating_add(value);
+    if used > limit.max_value {
+        return Err(ContractError::CraftingLimitExceeded {
+            max_value: limit.max_value,
+            used: window.value,
+            requested: value,
+        });
+    }
+    window.value = used;
+    CRAFTED_VALUE.save(storage, owner.clone(), &window)?;
+    Ok(())
+}
+
+/// 检查地址是否可操作所有者的某类型 NFT
+/// 
+/// 所有者本人始终可操作；其他地址未被全局停用时，若为所有者选择信任的注册操作员，
+/// 或持有该类型未过期的按类型授权，也可操作
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `env`: 环境信息，用于检查授权是否过期
+/// - `owner`: NFT 所有者
+/// - `operator`: 操作地址
+/// - `kind`: NFT 类型
+/// 
+/// # 返回值
+/// - `StdResult<bool>`: 是否可操作
+pub fn can_operate_kind(
+    storage: &dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    operator: &Addr,
+    kind: &NftKind,
+) -> StdResult<bool> {
+    if owner == operator {
+        return Ok(true);
+    }
+    if SUSPENDED_OPERATORS.has(storage, operator.clone()) {
+        return Ok(false);
+    }
+    if TRUSTED_OPERATORS.has(storage, operator.clone())
+        && TRUSTED_OPERATOR_OPT_INS.has(storage, owner.clone())
+    {
+        return Ok(true);
+    }
+    Ok(KIND_APPROVALS
+        .may_load(storage, (owner.clone(), operator.clone(), kind.to_key()))?
+        .map(|expires| !expires.is_expired(env))
+        .unwrap_or(false))
+}
+
+/// 检查会话密钥是否可代所有者执行某项操作
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `env`: 环境信息，用于检查授权是否过期
+/// - `owner`: NFT 所有者
+/// - `key`: 会话密钥地址
+/// - `scope`: 要执行的操作
+/// 
+/// # 返回值
+/// - `StdResult<bool>`: 是否持有包含该操作且未过期的会话密钥授权
+pub fn has_session_key_scope(
+    storage: &dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    key: &Addr,
+    scope: &SessionKeyScope,
+) -> StdResult<bool> {
+    Ok(SESSION_KEYS
+        .may_load(storage, (owner.clone(), key.clone()))?
+        .map_or(false, |grant| env.block.time.seconds() < grant.expires_at && grant.scopes.contains(scope)))
+}
+
+/// 授权单个 NFT 的转移或销毁
+/// 
+/// 除 `can_operate_kind` 的授权外，还接受未过期的操作员批准（ApproveAll）。
+/// 操作员批准设置了次数限制时每次使用扣减一次，用尽时自动撤销批准
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `env`: 环境信息，用于检查批准是否过期
+/// - `owner`: NFT 所有者
+/// - `operator`: 操作地址
+/// - `kind`: NFT 类型
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 未获授权时返回 `NotOwned`
+pub fn authorize_token_operation(
+    storage: &mut dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    operator: &Addr,
+    kind: &NftKind,
+) -> Result<(), ContractError> {
+    if can_operate_kind(storage, env, owner, operator, kind)? {
+        return Ok(());
+    }
+    if SUSPENDED_OPERATORS.has(storage, operator.clone()) {
+        return Err(ContractError::NotOwned {});
     }
 
-    // 验证配方要求（使用缓存的元数据）
-    for recipe_input in &recipe.inputs {
-        let count = inputs.iter()
-            .filter(|&&token_id| {
-                input_metas.get(&token_id)
-                    .map(|meta| meta.kind == recipe_input.nft_kind)
-                    .unwrap_or(false)
-            })
-            .count();
+    let key = (owner.clone(), operator.clone());
+    match OPERATOR_APPROVALS.may_load(storage, key.clone())? {
+        Some(expires) if !expires.is_expired(env) => {}
+        _ => return Err(ContractError::NotOwned {}),
+    }
 
-        if count < recipe_input.count as usize {
-            return Err(ContractError::InsufficientInputTokens {});
+    // 扣减次数限制，用尽时撤销批准
+    if let Some(remaining) = OPERATOR_BUDGETS.may_load(storage, key.clone())? {
+        if remaining <= 1 {
+            OPERATOR_BUDGETS.remove(storage, key.clone());
+            OPERATOR_APPROVALS.remove(storage, key);
+            track_storage_entry(storage, storage_counter::OPERATOR_APPROVALS, true, false)?;
+        } else {
+            OPERATOR_BUDGETS.save(storage, key, &(remaining - 1))?;
         }
     }
+    Ok(())
+}
+
+/// 读取生效中的会话锁定
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `env`: 环境信息，用于判断锁定是否到期
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<Option<SessionLock>>`: 未锁定或锁定已到期时为 None
+pub fn active_session_lock(storage: &dyn Storage, env: &Env, token_id: u64) -> StdResult<Option<SessionLock>> {
+    Ok(SESSION_LOCKS
+        .may_load(storage, token_id)?
+        .filter(|lock| env.block.time.seconds() < lock.until))
+}
+
+/// 检查 NFT 未被游戏会话锁定
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `env`: 环境信息，用于判断锁定是否到期
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 锁定生效中时返回 `TokenSessionLocked`
+pub fn ensure_not_session_locked(storage: &dyn Storage, env: &Env, token_id: u64) -> Result<(), ContractError> {
+    if let Some(lock) = active_session_lock(storage, env, token_id)? {
+        return Err(ContractError::TokenSessionLocked { token_id, until: lock.until });
+    }
+    Ok(())
+}
 
+/// 检查合约持有的 NFT 不属于任何托管
+/// 
+/// 礼物、组合包和条件转移托管的 NFT 同样由合约持有，但归属于托管记录，
+/// 不能作为合约库存分发，否则领取、拆包或取回时归还的 NFT 已不在合约中
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 托管中时返回 `TokenEscrowed`
+pub fn ensure_not_escrowed(storage: &dyn Storage, token_id: u64) -> Result<(), ContractError> {
+    if GIFTED_TOKENS.has(storage, token_id)
+        || BUNDLED_TOKENS.has(storage, token_id)
+        || CONDITIONAL_TRANSFERS.has(storage, token_id)
+    {
+        return Err(ContractError::TokenEscrowed { token_id });
+    }
     Ok(())
 }
 
+/// 读取仍由合约托管的 NFT 元数据
+/// 
+/// 托管期间 NFT 可能过期被回收，同一 ID 随后又可能铸造给他人；
+/// 只有 NFT 仍存在且由合约持有时才能按托管记录归还
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `env`: 环境信息，包含合约地址
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<Option<NftMeta>>`: NFT 已不在合约中时为 None
+pub fn escrowed_token_meta(storage: &dyn Storage, env: &Env, token_id: u64) -> StdResult<Option<NftMeta>> {
+    if TOKEN_OWNERSHIP.may_load(storage, token_id)?.as_ref() != Some(&env.contract.address) {
+        return Ok(None);
+    }
+    TOKEN_META.may_load(storage, token_id)
+}
+
 // ========== 数据验证函数 ==========
 
 /// 验证系列ID格式
@@ -168,9 +577,32 @@ pub fn validate_series_id(series_id: &str) -> Result<(), ContractError> {
         return Err(ContractError::Std(cosmwasm_std::StdError::generic_err("Series ID contains invalid characters")));
     }
     
+    // 合成系列前缀保留给合约自动生成的系列
+    if series_id.starts_with(SYNTHESIS_SERIES_PREFIX) {
+        return Err(ContractError::Std(cosmwasm_std::StdError::generic_err("Series ID prefix is reserved")));
+    }
+    
     Ok(())
 }
 
+/// 合成产出 NFT 使用的系列前缀
+/// 
+/// 这类系列由合约按区块时间自动生成，外部指定的系列 ID 不能使用该前缀
+pub const SYNTHESIS_SERIES_PREFIX: &str = "synthesis_";
+
+/// 当前区块合成产出 NFT 所在的系列 ID
+/// 
+/// 同一区块内的多次合成共用系列，由计数器分配序号
+/// 
+/// # 参数
+/// - `env`: 环境信息，提供区块时间
+/// 
+/// # 返回值
+/// - `String`: 系列 ID
+pub fn synthesis_series_id(env: &Env) -> String {
+    alloc::format!("{}{}", SYNTHESIS_SERIES_PREFIX, env.block.time.seconds())
+}
+
 /// 验证集合组ID格式
 /// 
 /// 检查集合组ID是否符合格式要求
@@ -199,93 +631,558 @@ pub fn validate_collection_group_id(group_id: &str) -> Result<(), ContractError>
     Ok(())
 }
 
-// ========== 类型转换函数 ==========
+/// 基础 URI 的最大长度
+pub const MAX_BASE_URI_LENGTH: usize = 256;
 
-/// 将字符串 token_id 转换为 u64
-/// 
-/// 用于外部接口接收字符串格式的 token_id 时进行转换
-/// 
+/// 基础 URI 允许的协议前缀
+pub const ALLOWED_BASE_URI_SCHEMES: [&str; 4] = ["https://", "http://", "ipfs://", "ar://"];
+
+/// 验证基础URI格式
+///
+/// 检查基础URI的长度、协议前缀和字符集
+///
 /// # 参数
-/// - `token_id_str`: 字符串格式的 token_id
-/// 
+/// - `base_uri`: 要验证的基础URI
+///
 /// # 返回值
-/// - `Result<u64, ContractError>`: 转换结果
-pub fn parse_token_id(token_id_str: &str) -> Result<u64, ContractError> {
-    token_id_str.parse::<u64>()
-        .map_err(|_| ContractError::Std(cosmwasm_std::StdError::parse_err("u64", token_id_str)))
+/// - `Result<(), ContractError>`: 验证结果
+pub fn validate_base_uri(base_uri: &str) -> Result<(), ContractError> {
+    // 检查长度限制
+    if base_uri.len() > MAX_BASE_URI_LENGTH {
+        return Err(ContractError::InvalidBaseUri { reason: "too long".to_string() });
+    }
+
+    // 检查协议前缀，且前缀之后必须有内容
+    let rest = ALLOWED_BASE_URI_SCHEMES
+        .iter()
+        .find_map(|scheme| base_uri.strip_prefix(scheme))
+        .ok_or_else(|| ContractError::InvalidBaseUri { reason: "unsupported scheme".to_string() })?;
+    if rest.is_empty() {
+        return Err(ContractError::InvalidBaseUri { reason: "missing host or path".to_string() });
+    }
+
+    // 检查字符集（不允许空白和控制字符）
+    if base_uri.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(ContractError::InvalidBaseUri { reason: "contains invalid characters".to_string() });
+    }
+
+    Ok(())
 }
 
-/// 将 u64 token_id 转换为字符串
+/// 合约名称的最大长度（字节）
+pub const MAX_NAME_LENGTH: usize = 64;
+
+/// 合约符号的最大长度（字节）
+pub const MAX_SYMBOL_LENGTH: usize = 16;
+
+/// 验证实例化参数
 /// 
-/// 用于响应中返回字符串格式的 token_id
+/// 名称不能为空且不超过 64 字节，不含控制字符；符号不能为空且不超过 16 字节，
+/// 不含空白和控制字符；基础 URI 按 `validate_base_uri` 校验。错误附带出错的字段名
 /// 
 /// # 参数
-/// - `token_id`: u64 格式的 token_id
+/// - `name`: 合约名称
+/// - `symbol`: 合约符号
+/// - `base_uri`: 基础 URI（可选）
 /// 
 /// # 返回值
-/// - `String`: 字符串格式的 token_id
-pub fn format_token_id(token_id: u64) -> String {
-    token_id.to_string()
+/// - `Result<(), ContractError>`: 无效时返回 `InvalidInstantiateField`
+pub fn validate_instantiate_fields(name: &str, symbol: &str, base_uri: Option<&str>) -> Result<(), ContractError> {
+    let invalid = |field: &str, reason: String| ContractError::InvalidInstantiateField {
+        field: field.to_string(),
+        reason,
+    };
+
+    if name.trim().is_empty() {
+        return Err(invalid("name", "empty".to_string()));
+    }
+    if name.len() > MAX_NAME_LENGTH {
+        return Err(invalid("name", alloc::format!("longer than {} bytes", MAX_NAME_LENGTH)));
+    }
+    if name.chars().any(|c| c.is_control()) {
+        return Err(invalid("name", "contains control characters".to_string()));
+    }
+
+    if symbol.is_empty() {
+        return Err(invalid("symbol", "empty".to_string()));
+    }
+    if symbol.len() > MAX_SYMBOL_LENGTH {
+        return Err(invalid("symbol", alloc::format!("longer than {} bytes", MAX_SYMBOL_LENGTH)));
+    }
+    if symbol.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(invalid("symbol", "contains whitespace or control characters".to_string()));
+    }
+
+    if let Some(base_uri) = base_uri {
+        validate_base_uri(base_uri).map_err(|err| match err {
+            ContractError::InvalidBaseUri { reason } => invalid("base_uri", reason),
+            err => err,
+        })?;
+    }
+
+    Ok(())
 }
 
-// ========== 索引维护函数 ==========
+/// 来源证明哈希的长度（十六进制编码的 SHA-256）
+pub const PROVENANCE_HASH_LENGTH: usize = 64;
 
-/// 清理指定 NFT 的所有批准信息
+/// 验证来源证明哈希格式
 /// 
-/// 在 NFT 转移或销毁时调用，确保不会遗留无效的批准信息
+/// 哈希必须是 64 位十六进制字符串（SHA-256）
 /// 
 /// # 参数
-/// - `storage`: 存储接口
-/// - `token_id`: 要清理批准的 NFT ID
+/// - `hash`: 要验证的哈希
 /// 
 /// # 返回值
-/// - `Result<(), ContractError>`: 清理结果
-pub fn clear_token_approvals(
-    storage: &mut dyn Storage,
-    token_id: u64,
-) -> Result<(), ContractError> {
-    // 移除该 NFT 的所有批准信息
-    TOKEN_APPROVALS.remove(storage, token_id);
+/// - `Result<(), ContractError>`: 验证结果
+pub fn validate_provenance_hash(hash: &str) -> Result<(), ContractError> {
+    if hash.len() != PROVENANCE_HASH_LENGTH {
+        return Err(ContractError::InvalidProvenanceHash { reason: "expected 64 hex characters".to_string() });
+    }
+    if !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ContractError::InvalidProvenanceHash { reason: "not a hex string".to_string() });
+    }
     Ok(())
 }
 
-/// 更新所有者索引
+/// 构建 NFT 的 token URI
 /// 
-/// 在 NFT 所有权转移时更新所有者索引
+/// 根据基础 URI 的协议前缀查找对应的 URI 模板并渲染；未配置模板时
+/// 使用默认格式 `{基础 URI}/{token ID}`。未设置基础 URI 时返回 None
+/// 
+/// 模板支持以下占位符：
+/// - `{base}`: 去掉协议前缀后的基础 URI
+/// - `{id}`: NFT ID
+/// - `{series}`: 系列 ID
+/// - `{kind}`: NFT 类型键
 /// 
 /// # 参数
 /// - `storage`: 存储接口
-/// - `from`: 原所有者地址
-/// - `to`: 新所有者地址
+/// - `base_uri`: 基础 URI（可选）
 /// - `token_id`: NFT ID
+/// - `meta`: NFT 元数据
 /// 
 /// # 返回值
-/// - `Result<(), ContractError>`: 更新结果
-pub fn update_owner_tokens(
-    storage: &mut dyn Storage,
-    from: &Addr,
-    to: &Addr,
+/// - `StdResult<Option<String>>`: token URI
+pub fn build_token_uri(
+    storage: &dyn Storage,
+    base_uri: Option<&str>,
     token_id: u64,
-) -> Result<(), ContractError> {
-    // 从原所有者的索引中移除
+    meta: &NftMeta,
+) -> StdResult<Option<String>> {
+    let base_uri = match base_uri {
+        Some(base_uri) => base_uri,
+        None => return Ok(None),
+    };
+
+    // 查找基础 URI 协议对应的模板
+    let scheme = ALLOWED_BASE_URI_SCHEMES.iter().find(|scheme| base_uri.starts_with(**scheme));
+    let template = match scheme {
+        Some(scheme) => URI_TEMPLATES.may_load(storage, scheme.to_string())?.map(|template| (scheme, template)),
+        None => None,
+    };
+
+    let uri = match template {
+        Some((scheme, template)) => template
+            .replace("{base}", &base_uri[scheme.len()..])
+            .replace("{id}", &token_id.to_string())
+            .replace("{series}", &meta.series_id)
+            .replace("{kind}", &meta.kind.to_key()),
+        None => alloc::format!("{}/{}", base_uri, token_id),
+    };
+    Ok(Some(uri))
+}
+
+/// 验证 URI 模板
+/// 
+/// 协议必须是允许的基础 URI 协议之一，模板必须是允许协议的 URI 且包含 `{id}` 占位符
+/// 
+/// # 参数
+/// - `scheme`: 模板适用的基础 URI 协议前缀（如 `ipfs://`）
+/// - `template`: URI 模板
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 验证结果
+pub fn validate_uri_template(scheme: &str, template: &str) -> Result<(), ContractError> {
+    if !ALLOWED_BASE_URI_SCHEMES.contains(&scheme) {
+        return Err(ContractError::InvalidUriTemplate { reason: "unsupported scheme".to_string() });
+    }
+    if !template.contains("{id}") {
+        return Err(ContractError::InvalidUriTemplate { reason: "missing {id} placeholder".to_string() });
+    }
+    validate_base_uri(template)
+        .map_err(|_| ContractError::InvalidUriTemplate { reason: "template is not a valid URI".to_string() })
+}
+
+/// 集合描述的最大长度
+pub const MAX_COLLECTION_DESCRIPTION_LENGTH: usize = 512;
+
+/// 集合社交链接的最大数量
+pub const MAX_COLLECTION_SOCIALS: usize = 10;
+
+/// 验证集合展示信息
+/// 
+/// # 参数
+/// - `info`: 集合展示信息
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 验证结果
+pub fn validate_collection_info(info: &CollectionInfo) -> Result<(), ContractError> {
+    let invalid = |reason: &str| ContractError::InvalidCollectionInfo { reason: reason.to_string() };
+
+    if info.description.len() > MAX_COLLECTION_DESCRIPTION_LENGTH {
+        return Err(invalid("description too long"));
+    }
+    validate_base_uri(&info.image).map_err(|_| invalid("invalid image URI"))?;
+    if let Some(link) = &info.external_link {
+        validate_base_uri(link).map_err(|_| invalid("invalid external link"))?;
+    }
+    if info.socials.len() > MAX_COLLECTION_SOCIALS {
+        return Err(invalid("too many social links"));
+    }
+    for social in &info.socials {
+        if social.platform.is_empty() {
+            return Err(invalid("empty social platform"));
+        }
+        validate_base_uri(&social.url).map_err(|_| invalid("invalid social link"))?;
+    }
+    Ok(())
+}
+
+// ========== 存储计数函数 ==========
+
+/// 存储计数器名称
+pub mod storage_counter {
+    /// 单个 NFT 的批准列表条目
+    pub const TOKEN_APPROVALS: &str = "token_approvals";
+    /// 操作员批准条目
+    pub const OPERATOR_APPROVALS: &str = "operator_approvals";
+    /// 合成配方条目
+    pub const RECIPES: &str = "recipes";
+    /// 合成历史条目
+    pub const SYNTHESIS_HISTORY: &str = "synthesis_history";
+    /// 所有者索引条目（持有者数量）
+    pub const OWNER_INDEX: &str = "owner_index";
+}
+
+/// 同步存储计数器
+/// 
+/// 根据条目写入前后的存在状态调整计数：新建时 +1，删除时 -1，覆盖写入不变
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `counter`: 计数器名称（见 `storage_counter`）
+/// - `existed`: 写入前条目是否存在
+/// - `exists`: 写入后条目是否存在
+/// 
+/// # 返回值
+/// - `StdResult<()>`: 更新结果
+pub fn track_storage_entry(
+    storage: &mut dyn Storage,
+    counter: &str,
+    existed: bool,
+    exists: bool,
+) -> StdResult<()> {
+    if existed == exists {
+        return Ok(());
+    }
+    let count = STORAGE_COUNTERS.may_load(storage, counter.to_string())?.unwrap_or(0);
+    let count = if exists { count.saturating_add(1) } else { count.saturating_sub(1) };
+    STORAGE_COUNTERS.save(storage, counter.to_string(), &count)
+}
+
+/// 读取存储计数器
+pub fn storage_count(storage: &dyn Storage, counter: &str) -> StdResult<u64> {
+    Ok(STORAGE_COUNTERS.may_load(storage, counter.to_string())?.unwrap_or(0))
+}
+
+// ========== 类型转换函数 ==========
+
+/// 将字符串 token_id 转换为 u64
+/// 
+/// 用于外部接口接收字符串格式的 token_id 时进行转换
+/// 
+/// # 参数
+/// - `token_id_str`: 字符串格式的 token_id
+/// 
+/// # 返回值
+/// - `Result<u64, ContractError>`: 转换结果
+pub fn parse_token_id(token_id_str: &str) -> Result<u64, ContractError> {
+    token_id_str.parse::<u64>()
+        .map_err(|_| ContractError::Std(cosmwasm_std::StdError::parse_err("u64", token_id_str)))
+}
+
+/// 将 u64 token_id 转换为字符串
+/// 
+/// 用于响应中返回字符串格式的 token_id
+/// 
+/// # 参数
+/// - `token_id`: u64 格式的 token_id
+/// 
+/// # 返回值
+/// - `String`: 字符串格式的 token_id
+pub fn format_token_id(token_id: u64) -> String {
+    token_id.to_string()
+}
+
+/// 比较两份元数据，列出发生变化的字段
+/// 
+/// 用于生成元数据变更事件，未设置的可选字段以空字符串表示
+/// 
+/// # 参数
+/// - `before`: 修改前的元数据
+/// - `after`: 修改后的元数据
+/// 
+/// # 返回值
+/// - `Vec<(&'static str, String, String)>`: 变更字段列表（字段名、修改前的值、修改后的值）
+pub fn diff_token_meta(before: &NftMeta, after: &NftMeta) -> Vec<(&'static str, String, String)> {
+    let crafted_from = |meta: &NftMeta| {
+        meta.crafted_from
+            .as_ref()
+            .map(|ids| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(","))
+            .unwrap_or_default()
+    };
+    let fields = [
+        ("kind", alloc::format!("{:?}", before.kind), alloc::format!("{:?}", after.kind)),
+        ("scale_origin", alloc::format!("{:?}", before.scale_origin), alloc::format!("{:?}", after.scale_origin)),
+        ("physical_sku", before.physical_sku.clone().unwrap_or_default(), after.physical_sku.clone().unwrap_or_default()),
+        ("crafted_from", crafted_from(before), crafted_from(after)),
+        ("series_id", before.series_id.clone(), after.series_id.clone()),
+        ("collection_group_id", before.collection_group_id.clone().unwrap_or_default(),
+            after.collection_group_id.clone().unwrap_or_default()),
+        ("serial_in_series", before.serial_in_series.to_string(), after.serial_in_series.to_string()),
+        ("expires_at", before.expires_at.map(|t| t.to_string()).unwrap_or_default(),
+            after.expires_at.map(|t| t.to_string()).unwrap_or_default()),
+    ];
+    fields.into_iter().filter(|(_, old, new)| old != new).collect()
+}
+
+/// 反序列化 token_id，兼容字符串和数字
+/// 
+/// 通用 CW721 工具以字符串发送 token_id（如 `"42"`），Luckee 原生接口使用数字。
+/// 用于标准 CW721 消息和查询的 `#[serde(deserialize_with = "...")]`，字符串经 `parse_token_id` 转换
+/// 
+/// # 参数
+/// - `deserializer`: serde 反序列化器
+/// 
+/// # 返回值
+/// - `Result<u64, D::Error>`: 转换结果
+pub fn deserialize_token_id<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserializer.deserialize_any(TokenIdVisitor)
+}
+
+/// 反序列化可选 token_id（如分页的 `start_after`），兼容字符串和数字
+/// 
+/// 需要与 `#[serde(default)]` 一起使用，字段缺省时为 None
+/// 
+/// # 参数
+/// - `deserializer`: serde 反序列化器
+/// 
+/// # 返回值
+/// - `Result<Option<u64>, D::Error>`: 转换结果
+pub fn deserialize_optional_token_id<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserializer.deserialize_option(OptionalTokenIdVisitor)
+}
+
+/// 字符串或数字 token_id 访问器
+struct TokenIdVisitor;
+
+impl<'de> serde::de::Visitor<'de> for TokenIdVisitor {
+    type Value = u64;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("a u64 token id as number or string")
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<u64, E> {
+        Ok(value)
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<u64, E> {
+        u64::try_from(value).map_err(|_| E::invalid_value(serde::de::Unexpected::Signed(value), &self))
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<u64, E> {
+        parse_token_id(value).map_err(|_| E::invalid_value(serde::de::Unexpected::Str(value), &self))
+    }
+}
+
+/// 可选 token_id 访问器
+struct OptionalTokenIdVisitor;
+
+impl<'de> serde::de::Visitor<'de> for OptionalTokenIdVisitor {
+    type Value = Option<u64>;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("an optional u64 token id as number or string")
+    }
+
+    fn visit_none<E: serde::de::Error>(self) -> Result<Option<u64>, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<Option<u64>, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<Option<u64>, D::Error> {
+        deserialize_token_id(deserializer).map(Some)
+    }
+}
+
+// ========== 索引维护函数 ==========
+
+/// 清理指定 NFT 的所有批准信息
+/// 
+/// 在 NFT 转移或销毁时调用，确保不会遗留无效的批准信息
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `token_id`: 要清理批准的 NFT ID
+/// - `owner`: 授予批准的所有者
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 清理结果
+pub fn clear_token_approvals(
+    storage: &mut dyn Storage,
+    token_id: u64,
+    owner: &Addr,
+) -> Result<(), ContractError> {
+    // 移除该 NFT 的所有批准信息及按所有者的反向索引
+    let existed = TOKEN_APPROVALS.has(storage, token_id);
+    TOKEN_APPROVALS.remove(storage, token_id);
+    APPROVED_TOKENS_BY_OWNER.remove(storage, (owner.clone(), token_id));
+    track_storage_entry(storage, storage_counter::TOKEN_APPROVALS, existed, false)?;
+    Ok(())
+}
+
+/// 更新所有者索引
+/// 
+/// 在 NFT 所有权转移时更新所有者索引
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `from`: 原所有者地址
+/// - `to`: 新所有者地址
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 更新结果
+pub fn update_owner_tokens(
+    storage: &mut dyn Storage,
+    from: &Addr,
+    to: &Addr,
+    token_id: u64,
+) -> Result<(), ContractError> {
+    // 从原所有者的索引中移除
     if let Some(mut tokens) = TOKENS_BY_OWNER.may_load(storage, from.clone())? {
         tokens.retain(|&id| id != token_id);
         if tokens.is_empty() {
             TOKENS_BY_OWNER.remove(storage, from.clone());
+            track_storage_entry(storage, storage_counter::OWNER_INDEX, true, false)?;
         } else {
             TOKENS_BY_OWNER.save(storage, from.clone(), &tokens)?;
         }
     }
 
     // 添加到新所有者的索引中
-    let mut tokens = TOKENS_BY_OWNER.may_load(storage, to.clone())?.unwrap_or_default();
-    tokens.push(token_id);
-    tokens.sort(); // 保持有序以便分页查询
-    TOKENS_BY_OWNER.save(storage, to.clone(), &tokens)?;
+    add_token_to_owner(storage, to, token_id)?;
+
+    Ok(())
+}
+
+/// 转移 NFT 所有权状态
+/// 
+/// 更新所有权、清理批准信息和原所有者的销毁申请，并同步所有者索引和持有数量。
+/// 调用方负责权限、过期和接收地址检查；NFT 当前不属于 `from` 时返回 `NotOwned`，
+/// 避免按过期的托管记录移动他人的 NFT
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `env`: 环境信息，用于记录取得时间
+/// - `token_id`: NFT ID
+/// - `from`: 原所有者地址
+/// - `to`: 新所有者地址
+/// - `kind`: NFT 类型
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 转移结果
+pub fn move_token(
+    storage: &mut dyn Storage,
+    env: &Env,
+    token_id: u64,
+    from: &Addr,
+    to: &Addr,
+    kind: &NftKind,
+) -> Result<(), ContractError> {
+    if TOKEN_OWNERSHIP.may_load(storage, token_id)?.as_ref() != Some(from) {
+        return Err(ContractError::NotOwned {});
+    }
+
+    // 更新 NFT 所有权，所有者变化时记录取得时间，清除托管标记和原所有者的销毁申请
+    TOKEN_OWNERSHIP.save(storage, token_id, to)?;
+    if from != to {
+        record_acquisition(storage, env, token_id)?;
+        clear_custody_tag(storage, token_id)?;
+        BURN_REQUESTS.remove(storage, token_id);
+    }
+    
+    // 清理转移前的批准信息（安全措施）
+    clear_token_approvals(storage, token_id, from)?;
+    
+    // 更新所有者索引、持有数量和投票权
+    update_owner_tokens(storage, from, to, token_id)?;
+    if token_indexed(storage, token_id)? {
+        adjust_owner_kind_count(storage, from, kind, false)?;
+        adjust_owner_kind_count(storage, to, kind, true)?;
+        adjust_voting_power(storage, from, kind, false, env.block.height)?;
+        adjust_voting_power(storage, to, kind, true, env.block.height)?;
+    }
+    Ok(())
+}
 
+/// 清除 NFT 的托管标记
+/// 
+/// 托管标记只对标记时的所有者有效，NFT 转出或销毁时调用
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<()>`: 清除结果
+pub fn clear_custody_tag(storage: &mut dyn Storage, token_id: u64) -> StdResult<()> {
+    if let Some(tag) = CUSTODY_TAGS.may_load(storage, token_id)? {
+        CUSTODY_TAGS.remove(storage, token_id);
+        CUSTODY_TOKENS.remove(storage, (tag.custodian, token_id));
+    }
     Ok(())
 }
 
+/// 记录当前所有者取得 NFT 的区块高度和时间
+/// 
+/// 铸造、转移、合成产出和恢复时调用，供 `OwnedAt` 查询证明持有起始时间
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `env`: 环境信息，提供区块高度和时间
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<()>`: 记录结果
+pub fn record_acquisition(storage: &mut dyn Storage, env: &Env, token_id: u64) -> StdResult<()> {
+    TOKEN_ACQUIRED.save(storage, token_id, &Acquisition {
+        height: env.block.height,
+        time: env.block.time.seconds(),
+    })
+}
+
 /// 添加 NFT 到所有者索引
 /// 
 /// 将新铸造的 NFT 添加到指定所有者的索引中
@@ -303,10 +1200,13 @@ pub fn add_token_to_owner(
     token_id: u64,
 ) -> Result<(), ContractError> {
     // 获取现有索引或创建新索引
-    let mut tokens = TOKENS_BY_OWNER.may_load(storage, owner.clone())?.unwrap_or_default();
+    let existing = TOKENS_BY_OWNER.may_load(storage, owner.clone())?;
+    let existed = existing.is_some();
+    let mut tokens = existing.unwrap_or_default();
     tokens.push(token_id);
     tokens.sort(); // 保持有序以便分页查询
     TOKENS_BY_OWNER.save(storage, owner.clone(), &tokens)?;
+    track_storage_entry(storage, storage_counter::OWNER_INDEX, existed, true)?;
     Ok(())
 }
 
@@ -332,6 +1232,7 @@ pub fn remove_token_from_owner(
         if tokens.is_empty() {
             // 如果索引为空，则删除整个条目
             TOKENS_BY_OWNER.remove(storage, owner.clone());
+            track_storage_entry(storage, storage_counter::OWNER_INDEX, true, false)?;
         } else {
             // 否则保存更新后的索引
             TOKENS_BY_OWNER.save(storage, owner.clone(), &tokens)?;
@@ -339,3 +1240,875 @@ pub fn remove_token_from_owner(
     }
     Ok(())
 }
+
+/// 判断 NFT 的变更是否需要实时维护二级索引
+/// 
+/// 索引完整时始终需要；旧部署升级期间，只有已被重建扫描过的 NFT 需要，
+/// 尚未扫描到的 NFT 会在重建时按当时的状态纳入索引
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<bool>`: 是否需要维护
+pub fn token_indexed(storage: &dyn Storage, token_id: u64) -> StdResult<bool> {
+    if TOKEN_INDEXES_READY.may_load(storage)?.unwrap_or(false) {
+        return Ok(true);
+    }
+    Ok(match INDEX_UPGRADE.may_load(storage)? {
+        Some(IndexUpgradeCursor { phase: IndexUpgradePhase::Build, last_token_id: Some(last) }) => token_id <= last,
+        _ => false,
+    })
+}
+
+/// 写入或删除 NFT 的二级索引条目
+/// 
+/// 包括类型、系列、集合组索引、所有者类型持有数量和投票权，不检查升级进度
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `token_id`: NFT ID
+/// - `owner`: 所有者地址
+/// - `meta`: NFT 元数据
+/// - `add`: true 表示写入，false 表示删除
+/// - `height`: 当前区块高度，用于记录投票权历史
+/// 
+/// # 返回值
+/// - `StdResult<()>`: 更新结果
+pub fn update_token_index(
+    storage: &mut dyn Storage,
+    token_id: u64,
+    owner: &Addr,
+    meta: &NftMeta,
+    add: bool,
+    height: u64,
+) -> StdResult<()> {
+    if add {
+        TOKENS_BY_KIND.save(storage, (meta.kind.to_key(), token_id), &())?;
+        TOKENS_BY_SERIES.save(storage, (meta.series_id.clone(), token_id), &())?;
+        TOKENS_BY_SERIAL.save(storage, (meta.series_id.clone(), meta.serial_in_series), &token_id)?;
+        if let Some(group_id) = &meta.collection_group_id {
+            TOKENS_BY_GROUP.save(storage, (group_id.clone(), token_id), &())?;
+        }
+    } else {
+        TOKENS_BY_KIND.remove(storage, (meta.kind.to_key(), token_id));
+        TOKENS_BY_SERIES.remove(storage, (meta.series_id.clone(), token_id));
+        TOKENS_BY_SERIAL.remove(storage, (meta.series_id.clone(), meta.serial_in_series));
+        if let Some(group_id) = &meta.collection_group_id {
+            TOKENS_BY_GROUP.remove(storage, (group_id.clone(), token_id));
+        }
+    }
+    adjust_owner_kind_count(storage, owner, &meta.kind, add)?;
+    adjust_voting_power(storage, owner, &meta.kind, add, height)
+}
+
+/// 将新增的 NFT 纳入二级索引（升级期间按进度决定）
+pub fn index_token(storage: &mut dyn Storage, token_id: u64, owner: &Addr, meta: &NftMeta, height: u64) -> StdResult<()> {
+    if token_indexed(storage, token_id)? {
+        update_token_index(storage, token_id, owner, meta, true, height)?;
+    }
+    Ok(())
+}
+
+/// 将删除的 NFT 移出二级索引（升级期间按进度决定）
+pub fn unindex_token(storage: &mut dyn Storage, token_id: u64, owner: &Addr, meta: &NftMeta, height: u64) -> StdResult<()> {
+    if token_indexed(storage, token_id)? {
+        update_token_index(storage, token_id, owner, meta, false, height)?;
+    }
+    Ok(())
+}
+
+/// 调整所有者某类型 NFT 的持有数量
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `owner`: 所有者地址
+/// - `kind`: NFT 类型
+/// - `increase`: true 表示 +1，false 表示 -1
+/// 
+/// # 返回值
+/// - `StdResult<()>`: 更新结果
+pub fn adjust_owner_kind_count(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    kind: &NftKind,
+    increase: bool,
+) -> StdResult<()> {
+    let key = (owner.clone(), kind.to_key());
+    let count = COUNTS_BY_OWNER_KIND.may_load(storage, key.clone())?.unwrap_or(0);
+    let count = if increase { count.saturating_add(1) } else { count.saturating_sub(1) };
+    if count == 0 {
+        COUNTS_BY_OWNER_KIND.remove(storage, key);
+        Ok(())
+    } else {
+        COUNTS_BY_OWNER_KIND.save(storage, key, &count)
+    }
+}
+
+/// 调整所有者的投票权
+/// 
+/// 投票权为持有 NFT 的兑换价值之和，按区块高度记录历史，为 0 时删除条目。
+/// 所有者已委托时同步调整受托人的委托投票权
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `owner`: 所有者地址
+/// - `kind`: NFT 类型
+/// - `increase`: true 表示增加该类型的兑换价值，false 表示减少
+/// - `height`: 当前区块高度
+/// 
+/// # 返回值
+/// - `StdResult<()>`: 更新结果
+pub fn adjust_voting_power(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    kind: &NftKind,
+    increase: bool,
+    height: u64,
+) -> StdResult<()> {
+    let value = kind.exchange_value() as u64;
+    let power = VOTING_POWER.may_load(storage, owner.clone())?.unwrap_or(0);
+    let power = if increase { power.saturating_add(value) } else { power.saturating_sub(value) };
+    if power == 0 {
+        VOTING_POWER.remove(storage, owner.clone(), height)?;
+    } else {
+        VOTING_POWER.save(storage, owner.clone(), &power, height)?;
+    }
+
+    if let Some(delegate) = DELEGATIONS.may_load(storage, owner.clone())? {
+        adjust_delegated_power(storage, &delegate, value, increase, height)?;
+    }
+    Ok(())
+}
+
+/// 调整受托人的委托投票权
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `delegate`: 受托人地址
+/// - `amount`: 调整的投票权数量
+/// - `increase`: true 表示增加，false 表示减少
+/// - `height`: 当前区块高度
+/// 
+/// # 返回值
+/// - `StdResult<()>`: 更新结果
+pub fn adjust_delegated_power(
+    storage: &mut dyn Storage,
+    delegate: &Addr,
+    amount: u64,
+    increase: bool,
+    height: u64,
+) -> StdResult<()> {
+    let power = DELEGATED_POWER.may_load(storage, delegate.clone())?.unwrap_or(0);
+    let power = if increase { power.saturating_add(amount) } else { power.saturating_sub(amount) };
+    if power == 0 {
+        DELEGATED_POWER.remove(storage, delegate.clone(), height)
+    } else {
+        DELEGATED_POWER.save(storage, delegate.clone(), &power, height)
+    }
+}
+
+/// 读取所有者某类型 NFT 的持有数量
+pub fn owner_kind_count(storage: &dyn Storage, owner: &Addr, kind: &NftKind) -> StdResult<u64> {
+    Ok(COUNTS_BY_OWNER_KIND.may_load(storage, (owner.clone(), kind.to_key()))?.unwrap_or(0))
+}
+
+/// 分配系列内序号
+/// 
+/// 铸造和合成产出时由系列计数器分配序号，调用方传入的序号不会被采用，保证系列内序号唯一
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `series_id`: 系列 ID
+/// 
+/// # 返回值
+/// - `Result<u64, ContractError>`: 分配的序号（从 1 开始）
+pub fn assign_series_serial(storage: &mut dyn Storage, series_id: &str) -> Result<u64, ContractError> {
+    let serial = next_series_serial(storage, series_id)?;
+    SERIES_NEXT_SERIAL.save(storage, series_id.to_string(), &serial)?;
+    Ok(serial)
+}
+
+/// 查看系列下一个将分配的序号（不分配）
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `series_id`: 系列 ID
+/// 
+/// # 返回值
+/// - `Result<u64, ContractError>`: 下一个序号（从 1 开始）
+pub fn next_series_serial(storage: &dyn Storage, series_id: &str) -> Result<u64, ContractError> {
+    SERIES_NEXT_SERIAL.may_load(storage, series_id.to_string())?
+        .unwrap_or(0)
+        .checked_add(1)
+        .ok_or(ContractError::Overflow {})
+}
+
+/// 检查铸造指定 ID 的权限
+/// 
+/// 保留区间内的 ID 只能由合约所有者铸造，其余 ID 需要铸造权限
+/// 
+/// # 参数
+/// - `reserved`: 当前的保留 ID 区间（未设置时为 None）
+/// - `token_id`: 要铸造的 NFT ID
+/// - `is_owner`: 发送者是否为合约所有者
+/// - `is_minter`: 发送者是否为授权铸造者
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 无权铸造该 ID 时返回错误
+pub fn check_mint_id_permission(
+    reserved: Option<&ReservedIdRange>,
+    token_id: u64,
+    is_owner: bool,
+    is_minter: bool,
+) -> Result<(), ContractError> {
+    match reserved {
+        Some(range) if range.contains(token_id) => {
+            if !is_owner {
+                return Err(ContractError::ReservedTokenId { token_id, start: range.start, end: range.end });
+            }
+        }
+        _ => {
+            if !is_minter {
+                return Err(ContractError::MinterNotAuthorized {});
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 计算下一个自动分配的 NFT ID
+/// 
+/// 计数器位于保留区间内时跳到区间之后
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// 
+/// # 返回值
+/// - `Result<u64, ContractError>`: 下一个自动分配的 ID
+pub fn next_auto_token_id(storage: &dyn Storage) -> Result<u64, ContractError> {
+    let token_id = NEXT_TOKEN_ID.load(storage)?;
+    match RESERVED_ID_RANGE.may_load(storage)? {
+        Some(range) if range.contains(token_id) => range.end.checked_add(1).ok_or(ContractError::Overflow {}),
+        _ => Ok(token_id),
+    }
+}
+
+/// 自动分配新的 NFT ID
+/// 
+/// 合成产出等无需调用方指定 ID 的场景使用。启用确定性 ID 时按系列和序号推导，
+/// 否则使用计数器并跳过保留区间
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `series_id`: 新 NFT 的系列 ID
+/// - `serial`: 新 NFT 的系列内序号（已分配）
+/// 
+/// # 返回值
+/// - `Result<u64, ContractError>`: 分配的 ID
+pub fn allocate_token_id(storage: &mut dyn Storage, series_id: &str, serial: u64) -> Result<u64, ContractError> {
+    if let Some(token_id) = deterministic_token_id(storage, series_id, serial)? {
+        return Ok(token_id);
+    }
+    let token_id = next_auto_token_id(storage)?;
+    NEXT_TOKEN_ID.save(storage, &(token_id.checked_add(1).ok_or(ContractError::Overflow {})?))?;
+    Ok(token_id)
+}
+
+// ========== 确定性 NFT ID ==========
+
+/// 由系列 ID 和系列内序号推导 NFT ID
+/// 
+/// 取 sha256(系列 ID || 0x00 || 序号大端字节) 的前 8 字节（大端），
+/// 相同的系列和序号在任何部署中得到相同的 ID
+/// 
+/// # 参数
+/// - `series_id`: 系列 ID
+/// - `serial`: 系列内序号
+/// 
+/// # 返回值
+/// - `u64`: 推导出的 NFT ID
+pub fn derive_token_id(series_id: &str, serial: u64) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(series_id.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(serial.to_be_bytes());
+    let digest = hasher.finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(bytes)
+}
+
+/// 检查是否启用确定性 NFT ID
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// 
+/// # 返回值
+/// - `StdResult<bool>`: 是否启用
+pub fn deterministic_ids_enabled(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(DETERMINISTIC_TOKEN_IDS.may_load(storage)?.unwrap_or(false))
+}
+
+/// 按确定性模式推导 NFT ID 并检查冲突
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `series_id`: 系列 ID
+/// - `serial`: 系列内序号
+/// 
+/// # 返回值
+/// - `Result<Option<u64>, ContractError>`: 推导出的 ID（未启用时为 None），
+///   已被占用（含宽限期内待销毁的 NFT）时返回 `TokenIdCollision`
+pub fn deterministic_token_id(storage: &dyn Storage, series_id: &str, serial: u64) -> Result<Option<u64>, ContractError> {
+    if !deterministic_ids_enabled(storage)? {
+        return Ok(None);
+    }
+    let token_id = derive_token_id(series_id, serial);
+    if TOKEN_META.has(storage, token_id) || PENDING_BURNS.has(storage, token_id) {
+        return Err(ContractError::TokenIdCollision { token_id, series_id: series_id.to_string(), serial });
+    }
+    Ok(Some(token_id))
+}
+
+/// 确定铸造使用的 NFT ID
+/// 
+/// 未启用确定性 ID 时使用调用方指定的 ID；启用时按系列和序号推导，
+/// 调用方须传入 0 或与推导结果一致的 ID
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `requested`: 调用方指定的 ID
+/// - `series_id`: 系列 ID
+/// - `serial`: 即将分配的系列内序号
+/// 
+/// # 返回值
+/// - `Result<u64, ContractError>`: 实际铸造的 ID
+pub fn resolve_mint_token_id(
+    storage: &dyn Storage,
+    requested: u64,
+    series_id: &str,
+    serial: u64,
+) -> Result<u64, ContractError> {
+    match deterministic_token_id(storage, series_id, serial)? {
+        Some(expected) if requested != 0 && requested != expected => {
+            Err(ContractError::DeterministicTokenIdMismatch { expected, provided: requested })
+        }
+        Some(expected) => Ok(expected),
+        None => Ok(requested),
+    }
+}
+
+/// 保存新铸造的 NFT
+/// 
+/// 写入元数据和所有权，更新所有者索引、二级索引、全局索引、系列数量和总供应量。
+/// 调用方负责权限、ID 占用和元数据校验
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `env`: 环境信息，用于记录取得时间
+/// - `token_id`: 新 NFT 的 ID
+/// - `owner`: 所有者地址
+/// - `meta`: 元数据（系列内序号已分配）
+/// 
+/// # 返回值
+/// - `Result<u64, ContractError>`: 铸造后的总供应量
+pub fn store_minted_token(
+    storage: &mut dyn Storage,
+    env: &Env,
+    token_id: u64,
+    owner: &Addr,
+    meta: &NftMeta,
+) -> Result<u64, ContractError> {
+    TOKEN_META.save(storage, token_id, meta)?;
+    TOKEN_OWNERSHIP.save(storage, token_id, owner)?;
+    record_acquisition(storage, env, token_id)?;
+
+    // 更新所有者索引、二级索引和全局索引
+    add_token_to_owner(storage, owner, token_id)?;
+    index_token(storage, token_id, owner, meta, env.block.height)?;
+    ALL_TOKENS.save(storage, token_id, &())?;
+
+    increase_series_token_count(storage, &meta.series_id, 1)?;
+
+    // 更新总供应量（使用 checked_add 防止溢出）
+    let new_supply = TOTAL_SUPPLY.load(storage)?
+        .checked_add(1)
+        .ok_or(ContractError::Overflow {})?;
+    TOTAL_SUPPLY.save(storage, &new_supply)?;
+    Ok(new_supply)
+}
+
+/// 增加系列现存 NFT 数量
+/// 
+/// 在铸造或合成产出 NFT 时调用
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `series_id`: 系列 ID
+/// - `amount`: 增加数量
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 更新结果
+pub fn increase_series_token_count(
+    storage: &mut dyn Storage,
+    series_id: &str,
+    amount: u64,
+) -> Result<(), ContractError> {
+    let count = SERIES_TOKEN_COUNT.may_load(storage, series_id.to_string())?.unwrap_or(0);
+    let new_count = count.checked_add(amount).ok_or(ContractError::Overflow {})?;
+    SERIES_TOKEN_COUNT.save(storage, series_id.to_string(), &new_count)?;
+    Ok(())
+}
+
+/// 减少系列现存 NFT 数量
+/// 
+/// 在销毁或合成消耗 NFT 时调用。未被跟踪的旧系列保持不变，
+/// 避免把无法确认是否仍被引用的系列标记为可清理
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `series_id`: 系列 ID
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 更新结果
+pub fn decrease_series_token_count(
+    storage: &mut dyn Storage,
+    series_id: &str,
+) -> Result<(), ContractError> {
+    if let Some(count) = SERIES_TOKEN_COUNT.may_load(storage, series_id.to_string())? {
+        SERIES_TOKEN_COUNT.save(storage, series_id.to_string(), &count.saturating_sub(1))?;
+    }
+    Ok(())
+}
+
+// ========== 销毁通知 ==========
+
+/// 构建销毁通知消息
+/// 
+/// 为每个已注册的销毁监听合约生成一条 `BurnNotification` 子消息。
+/// 子消息不处理回复，监听合约执行失败会使整个销毁操作回滚
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `token_id`: 被销毁的 NFT ID
+/// - `owner`: 销毁前的最后所有者
+/// 
+/// # 返回值
+/// - `Result<Vec<SubMsg>, ContractError>`: 通知子消息列表
+pub fn build_burn_notifications(
+    storage: &dyn Storage,
+    token_id: u64,
+    owner: &Addr,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let msg = to_json_binary(&BurnHookMsg::BurnNotification {
+        token_id,
+        owner: owner.to_string(),
+    })?;
+
+    BURN_LISTENERS
+        .keys(storage, None, None, Order::Ascending)
+        .map(|listener| {
+            Ok(SubMsg::new(WasmMsg::Execute {
+                contract_addr: listener?.to_string(),
+                msg: msg.clone(),
+                funds: vec![],
+            }))
+        })
+        .collect()
+}
+
+// ========== 重入保护 ==========
+
+/// 执行锁释放回复 ID
+pub const REENTRANCY_GUARD_REPLY_ID: u64 = 1;
+
+/// 检查执行锁
+/// 
+/// 外部子消息尚未全部回复时，拒绝新的执行消息
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 存在未完成的外部调用时返回错误
+pub fn ensure_not_locked(storage: &dyn Storage) -> Result<(), ContractError> {
+    if EXECUTION_LOCK.may_load(storage)?.unwrap_or(0) > 0 {
+        return Err(ContractError::ReentrantCall {});
+    }
+    Ok(())
+}
+
+/// 为外部合约调用加锁
+/// 
+/// 将响应中的外部合约子消息改为成功后回复，并记录待回复数量；
+/// 已自带回复的子消息（子集合实例化、跨集合产出铸造）同样计入，由各自的回复处理释放。
+/// 子消息失败时整个交易回滚，锁也随之回滚
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `response`: 处理函数返回的响应
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 加锁后的响应
+pub fn guard_external_calls(
+    storage: &mut dyn Storage,
+    mut response: Response,
+) -> Result<Response, ContractError> {
+    let mut pending = 0u32;
+    for sub_msg in response.messages.iter_mut() {
+        if !matches!(sub_msg.msg, CosmosMsg::Wasm(_)) {
+            continue;
+        }
+        if sub_msg.reply_on == ReplyOn::Never {
+            sub_msg.id = REENTRANCY_GUARD_REPLY_ID;
+            sub_msg.reply_on = ReplyOn::Success;
+        }
+        pending = pending.checked_add(1).ok_or(ContractError::Overflow {})?;
+    }
+    if pending > 0 {
+        EXECUTION_LOCK.save(storage, &pending)?;
+    }
+    Ok(response)
+}
+
+/// 释放一个外部调用的执行锁
+/// 
+/// 每个外部子消息回复时调用一次，全部回复后解除锁定
+pub fn release_execution_lock(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    let pending = EXECUTION_LOCK.may_load(storage)?.unwrap_or(0);
+    if pending <= 1 {
+        EXECUTION_LOCK.remove(storage);
+    } else {
+        EXECUTION_LOCK.save(storage, &(pending - 1))?;
+    }
+    Ok(())
+}
+
+// ========== 活动流 ==========
+
+/// 活动流环形缓冲区容量
+pub const ACTIVITY_LOG_SIZE: u64 = 100;
+
+/// 记录一条合约活动
+/// 
+/// 写入环形缓冲区，超过容量时覆盖最旧的记录
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `env`: 环境信息，提供区块高度和时间
+/// - `action`: 操作类型
+/// - `token_id`: 相关的 NFT ID（可选）
+/// - `actor`: 执行操作的地址
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 记录结果
+pub fn record_activity(
+    storage: &mut dyn Storage,
+    env: &Env,
+    action: &str,
+    token_id: Option<u64>,
+    actor: &Addr,
+) -> Result<(), ContractError> {
+    let seq = NEXT_ACTIVITY_SEQ.may_load(storage)?.unwrap_or(0);
+    ACTIVITY_LOG.save(storage, seq % ACTIVITY_LOG_SIZE, &ActivityEntry {
+        seq,
+        action: action.to_string(),
+        token_id,
+        actor: actor.clone(),
+        height: env.block.height,
+        timestamp: env.block.time.seconds(),
+    })?;
+    NEXT_ACTIVITY_SEQ.save(storage, &(seq.checked_add(1).ok_or(ContractError::Overflow {})?))?;
+    Ok(())
+}
+
+/// 累加铸造者统计
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `env`: 环境信息，提供区块高度和时间
+/// - `minter`: 执行铸造的地址
+/// - `count`: 本次铸造的数量
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 记录结果
+pub fn record_minter_stats(
+    storage: &mut dyn Storage,
+    env: &Env,
+    minter: &Addr,
+    count: u64,
+) -> Result<(), ContractError> {
+    let total_minted = MINTER_STATS
+        .may_load(storage, minter.clone())?
+        .map(|stats| stats.total_minted)
+        .unwrap_or(0)
+        .checked_add(count)
+        .ok_or(ContractError::Overflow {})?;
+    MINTER_STATS.save(storage, minter.clone(), &MinterStats {
+        total_minted,
+        last_mint_height: env.block.height,
+        last_mint_time: env.block.time.seconds(),
+    })?;
+    Ok(())
+}
+
+// ========== 事件详细程度 ==========
+
+/// 是否发出逐条明细事件
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// 
+/// # 返回值
+/// - `StdResult<bool>`: 事件详细程度为 Verbose（默认）时为 true
+pub fn verbose_events(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(EVENT_VERBOSITY.may_load(storage)?.map_or(true, |verbosity| verbosity.is_verbose()))
+}
+
+// ========== 铸造幂等 ==========
+
+/// 幂等键的最大长度
+pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 128;
+
+/// 查找重复提交的铸造
+/// 
+/// 校验幂等键格式；该铸造者已使用过此幂等键时，返回与首次铸造形式相同的成功响应
+/// （属性和事件与首次铸造一致，另附 `replayed` 标记），调用方应直接返回该响应而不再铸造。
+/// 幂等键只能用于首次使用时的操作类型，单个铸造与批量铸造之间不能互相重放。未提供幂等键时返回 None
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `minter`: 执行铸造的地址
+/// - `idempotency_key`: 幂等键（可选）
+/// - `action`: 响应中的操作类型（"mint" 或 "batch_mint"）
+/// 
+/// # 返回值
+/// - `Result<Option<Response>, ContractError>`: 重复提交时为首次铸造的结果
+pub fn replay_mint(
+    storage: &dyn Storage,
+    minter: &Addr,
+    idempotency_key: Option<&str>,
+    action: &str,
+) -> Result<Option<Response>, ContractError> {
+    let Some(key) = idempotency_key else {
+        return Ok(None);
+    };
+    if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LENGTH {
+        return Err(ContractError::InvalidIdempotencyKey {
+            reason: alloc::format!("length must be between 1 and {}", MAX_IDEMPOTENCY_KEY_LENGTH),
+        });
+    }
+    let Some(receipt) = MINT_RECEIPTS.may_load(storage, (minter.clone(), key.to_string()))? else {
+        return Ok(None);
+    };
+    // 旧记录没有操作类型，按当前操作重放
+    if !receipt.action.is_empty() && receipt.action != action {
+        return Err(ContractError::InvalidIdempotencyKey {
+            reason: alloc::format!("key already used by {}", receipt.action),
+        });
+    }
+
+    let token_ids = receipt.token_ids.iter().map(|token_id| token_id.to_string()).collect::<Vec<_>>().join(",");
+    let mut response = Response::new().add_attribute("action", action);
+    let mut event = None;
+    match (receipt.token_ids.as_slice(), &receipt.owner, &receipt.kind) {
+        ([token_id], Some(owner), Some(kind)) if action == "mint" => {
+            response = response
+                .add_attribute("token_id", token_id.to_string())
+                .add_attribute("owner", owner.to_string())
+                .add_attribute("kind", kind);
+            event = Some(emit_mint_event(*token_id, owner.as_str(), kind, receipt.total_supply));
+        }
+        _ if action == "batch_mint" && !receipt.action.is_empty() => {
+            response = response.add_attribute("count", receipt.token_ids.len().to_string());
+            event = Some(emit_batch_mint_event(receipt.token_ids.len(), minter, receipt.total_supply));
+        }
+        _ => {}
+    }
+    response = response
+        .add_attribute("idempotency_key", key)
+        .add_attribute("replayed", "true")
+        .add_attribute("token_ids", token_ids)
+        .add_attribute("minted_at_height", receipt.height.to_string());
+    // 事件附带重放标记，索引器据此跳过重复的铸造事件
+    if let Some(event) = event {
+        response = response.add_event(event.add_attribute("replayed", "true"));
+    }
+    Ok(Some(response))
+}
+
+/// 记录带幂等键的铸造结果
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `minter`: 执行铸造的地址
+/// - `idempotency_key`: 幂等键（未提供时不记录）
+/// - `receipt`: 本次铸造的结果
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 记录结果
+pub fn record_mint_receipt(
+    storage: &mut dyn Storage,
+    minter: &Addr,
+    idempotency_key: Option<String>,
+    receipt: &MintReceipt,
+) -> Result<(), ContractError> {
+    if let Some(key) = idempotency_key {
+        MINT_RECEIPTS.save(storage, (minter.clone(), key), receipt)?;
+    }
+    Ok(())
+}
+
+// ========== 消息构造与响应解析 ==========
+
+/// 构造调用本合约的执行消息
+/// 
+/// 供其他合约和机器人直接构造 `CosmosMsg`，无需手写 JSON
+/// 
+/// # 参数
+/// - `contract_addr`: Luckee NFT 合约地址
+/// - `msg`: 执行消息
+/// - `funds`: 附带的资金（仅 `Deposit`、`Synthesize` 接受资金）
+/// 
+/// # 返回值
+/// - `StdResult<CosmosMsg>`: Wasm 执行消息
+pub fn build_execute_msg(contract_addr: impl Into<String>, msg: &ExecuteMsg, funds: Vec<Coin>) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract_addr.into(),
+        msg: to_json_binary(msg)?,
+        funds,
+    }))
+}
+
+/// 构造铸造消息
+/// 
+/// # 参数
+/// - `contract_addr`: Luckee NFT 合约地址
+/// - `token_id`: 要铸造的 NFT ID
+/// - `owner`: 新 NFT 的所有者地址
+/// - `extension`: NFT 元数据
+/// 
+/// # 返回值
+/// - `StdResult<CosmosMsg>`: Wasm 执行消息
+pub fn build_mint_msg(
+    contract_addr: impl Into<String>,
+    token_id: u64,
+    owner: impl Into<String>,
+    extension: NftMeta,
+) -> StdResult<CosmosMsg> {
+    build_execute_msg(contract_addr, &ExecuteMsg::Mint { token_id, owner: owner.into(), extension, idempotency_key: None }, vec![])
+}
+
+/// 构造批量铸造消息
+/// 
+/// # 参数
+/// - `contract_addr`: Luckee NFT 合约地址
+/// - `mints`: 批量铸造条目
+/// 
+/// # 返回值
+/// - `StdResult<CosmosMsg>`: Wasm 执行消息
+pub fn build_batch_mint_msg(contract_addr: impl Into<String>, mints: Vec<BatchMintItem>) -> StdResult<CosmosMsg> {
+    build_execute_msg(contract_addr, &ExecuteMsg::BatchMint { mints, idempotency_key: None }, vec![])
+}
+
+/// 构造转移消息
+/// 
+/// # 参数
+/// - `contract_addr`: Luckee NFT 合约地址
+/// - `recipient`: 接收者地址
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<CosmosMsg>`: Wasm 执行消息
+pub fn build_transfer_msg(contract_addr: impl Into<String>, recipient: impl Into<String>, token_id: u64) -> StdResult<CosmosMsg> {
+    build_execute_msg(contract_addr, &ExecuteMsg::TransferNft { recipient: recipient.into(), token_id }, vec![])
+}
+
+/// 构造销毁消息
+/// 
+/// # 参数
+/// - `contract_addr`: Luckee NFT 合约地址
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<CosmosMsg>`: Wasm 执行消息
+pub fn build_burn_msg(contract_addr: impl Into<String>, token_id: u64) -> StdResult<CosmosMsg> {
+    build_execute_msg(contract_addr, &ExecuteMsg::Burn { token_id }, vec![])
+}
+
+/// 构造合成消息
+/// 
+/// # 参数
+/// - `contract_addr`: Luckee NFT 合约地址
+/// - `inputs`: 输入 NFT ID 列表
+/// - `target`: 目标类型
+/// - `funds`: 支付的合成费用（配方无费用时传空）
+/// 
+/// # 返回值
+/// - `StdResult<CosmosMsg>`: Wasm 执行消息
+pub fn build_synthesize_msg(
+    contract_addr: impl Into<String>,
+    inputs: Vec<u64>,
+    target: NftKind,
+    funds: Vec<Coin>,
+) -> StdResult<CosmosMsg> {
+    build_execute_msg(contract_addr, &ExecuteMsg::Synthesize { inputs, target }, funds)
+}
+
+/// 查找指定操作事件的属性值
+/// 
+/// 在 `action` 属性等于指定操作的 wasm 事件中查找属性，
+/// 同时兼容链上的 `wasm-` 前缀事件类型
+/// 
+/// # 参数
+/// - `events`: 事件列表（如 `Response::events`、`SubMsgResponse::events` 或交易结果中的事件）
+/// - `action`: 操作类型（见 `action_types`）
+/// - `key`: 属性键（见 `event_attributes`）
+/// 
+/// # 返回值
+/// - `Option<&str>`: 第一个匹配事件中的属性值
+pub fn find_event_attribute<'a>(events: &'a [Event], action: &str, key: &str) -> Option<&'a str> {
+    events
+        .iter()
+        .filter(|event| event.ty.starts_with("wasm"))
+        .filter(|event| {
+            event.attributes.iter().any(|attr| attr.key == event_attributes::ACTION && attr.value == action)
+        })
+        .find_map(|event| event.attributes.iter().find(|attr| attr.key == key))
+        .map(|attr| attr.value.as_str())
+}
+
+/// 从事件中解析铸造的 NFT ID
+/// 
+/// # 参数
+/// - `events`: 铸造交易或子消息返回的事件
+/// 
+/// # 返回值
+/// - `Option<u64>`: 铸造的 NFT ID，没有铸造事件时返回 None
+pub fn parse_minted_token_id(events: &[Event]) -> Option<u64> {
+    find_event_attribute(events, action_types::MINT, event_attributes::TOKEN_ID)?.parse().ok()
+}
+
+/// 从事件中解析合成产出的 NFT ID
+/// 
+/// # 参数
+/// - `events`: 合成交易或子消息返回的事件
+/// 
+/// # 返回值
+/// - `Option<u64>`: 产出的 NFT ID，没有合成事件时返回 None
+pub fn parse_synthesized_token_id(events: &[Event]) -> Option<u64> {
+    find_event_attribute(events, action_types::SYNTHESIZE, event_attributes::OUTPUT_TOKEN_ID)?.parse().ok()
+}
+
+/// 从事件中解析销毁的 NFT ID
+/// 
+/// # 参数
+/// - `events`: 销毁交易或子消息返回的事件
+/// 
+/// # 返回值
+/// - `Option<u64>`: 销毁的 NFT ID，没有销毁事件时返回 None
+pub fn parse_burned_token_id(events: &[Event]) -> Option<u64> {
+    find_event_attribute(events, action_types::BURN, event_attributes::TOKEN_ID)?.parse().ok()
+}
@@ -0,0 +1,204 @@
+//! 条件转移模块
+//! 
+//! 此模块实现哈希时间锁（HTLC）式的条件转移，包括：
+//! - 创建条件转移：将 NFT 托管到合约，登记接收者、哈希锁和超时时间
+//! - 领取：接收者在超时前提交哈希锁原文领取 NFT，原文公开在事件中
+//! - 取回：超时后由发送者取回 NFT
+//! 
+//! 用于与其他链或合约上的付款进行原子交换：付款方使用同一哈希锁锁定付款，
+//! NFT 接收者领取时公开的原文即可用于领取付款
+
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::gifts::{hash_claim_code, CLAIM_HASH_LENGTH};
+use crate::helpers::{ensure_not_session_locked, escrowed_token_meta, move_token, record_activity, validate_recipient};
+use crate::msg::ConditionalTransferResponse;
+use crate::state::{ConditionalTransfer, CONDITIONAL_TRANSFERS, TOKEN_META, TOKEN_OWNERSHIP};
+
+// ========== 条件转移执行接口 ==========
+
+/// 创建条件转移
+/// 
+/// 将发送者的 NFT 托管到合约，接收者在超时前提交哈希锁原文即可领取
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，包含合约地址和当前时间
+/// - `info`: 消息信息，包含发送者
+/// - `recipient`: 接收者地址
+/// - `token_id`: NFT ID
+/// - `hashlock`: 原文的 SHA-256 哈希（十六进制）
+/// - `timeout`: 超时时间（秒）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 创建结果
+#[allow(clippy::too_many_arguments)]
+pub fn execute_transfer_with_condition(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    token_id: u64,
+    hashlock: String,
+    timeout: u64,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    // 验证哈希锁格式，统一为小写
+    if hashlock.len() != CLAIM_HASH_LENGTH || !hashlock.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ContractError::InvalidConditionalTransfer {
+            reason: "hashlock must be 64 hex characters".to_string(),
+        });
+    }
+    let hashlock = hashlock.to_ascii_lowercase();
+    if timeout <= env.block.time.seconds() {
+        return Err(ContractError::InvalidConditionalTransfer { reason: "timeout must be in the future".to_string() });
+    }
+
+    // 验证接收者
+    let recipient_addr = deps.api.addr_validate(&recipient)?;
+    if recipient_addr == info.sender {
+        return Err(ContractError::InvalidConditionalTransfer { reason: "recipient is the sender".to_string() });
+    }
+    validate_recipient(deps.storage, &env.contract.address, &recipient_addr)?;
+
+    // 验证所有者身份
+    let owner = TOKEN_OWNERSHIP.may_load(deps.storage, token_id)?
+        .ok_or(ContractError::TokenNotFound {})?;
+    if owner != info.sender {
+        return Err(ContractError::NotOwned {});
+    }
+    let meta = TOKEN_META.load(deps.storage, token_id)?;
+    if meta.is_expired(env.block.time.seconds()) {
+        return Err(ContractError::TokenExpired {});
+    }
+    ensure_not_session_locked(deps.storage, &env, token_id)?;
+
+    // 托管到合约
+    move_token(deps.storage, &env, token_id, &info.sender, &env.contract.address, &meta.kind)?;
+    CONDITIONAL_TRANSFERS.save(deps.storage, token_id, &ConditionalTransfer {
+        sender: info.sender.clone(),
+        recipient: recipient_addr.clone(),
+        hashlock: hashlock.clone(),
+        timeout,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_with_condition")
+        .add_attribute("token_id", token_id.to_string())
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("recipient", recipient_addr.to_string())
+        .add_attribute("hashlock", hashlock)
+        .add_attribute("timeout", timeout.to_string()))
+}
+
+/// 领取条件转移
+/// 
+/// 接收者在超时前提交哈希锁原文领取 NFT，原文写入事件属性供交换对手使用。
+/// 合约暂停期间也可领取，避免暂停导致原子交换一方失去领取机会
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，包含合约地址和当前时间
+/// - `info`: 消息信息，发送者必须是接收者
+/// - `token_id`: NFT ID
+/// - `preimage`: 哈希锁原文
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 领取结果
+pub fn execute_claim_conditional_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+    preimage: String,
+) -> Result<Response, ContractError> {
+    let transfer = CONDITIONAL_TRANSFERS.may_load(deps.storage, token_id)?
+        .ok_or(ContractError::ConditionalTransferNotFound {})?;
+    if transfer.recipient != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if env.block.time.seconds() >= transfer.timeout {
+        return Err(ContractError::InvalidConditionalTransfer { reason: "transfer timed out".to_string() });
+    }
+    if hash_claim_code(&preimage) != transfer.hashlock {
+        return Err(ContractError::InvalidConditionalTransfer { reason: "preimage does not match hashlock".to_string() });
+    }
+
+    // 托管期间 NFT 可能已过期被回收，同一 ID 也可能已重新铸造给他人
+    let meta = escrowed_token_meta(deps.storage, &env, token_id)?
+        .ok_or(ContractError::TokenNotFound {})?;
+
+    CONDITIONAL_TRANSFERS.remove(deps.storage, token_id);
+    move_token(deps.storage, &env, token_id, &env.contract.address, &transfer.recipient, &meta.kind)?;
+
+    // 记录到活动流
+    record_activity(deps.storage, &env, "claim_conditional_transfer", Some(token_id), &info.sender)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_conditional_transfer")
+        .add_attribute("token_id", token_id.to_string())
+        .add_attribute("sender", transfer.sender.to_string())
+        .add_attribute("recipient", transfer.recipient.to_string())
+        .add_attribute("preimage", preimage))
+}
+
+/// 取回条件转移
+/// 
+/// 超时后发送者取回托管的 NFT，合约暂停期间也可取回
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，包含合约地址和当前时间
+/// - `info`: 消息信息，发送者必须是条件转移的发送者
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 取回结果
+pub fn execute_reclaim_conditional_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+) -> Result<Response, ContractError> {
+    let transfer = CONDITIONAL_TRANSFERS.may_load(deps.storage, token_id)?
+        .ok_or(ContractError::ConditionalTransferNotFound {})?;
+    if transfer.sender != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if env.block.time.seconds() < transfer.timeout {
+        return Err(ContractError::InvalidConditionalTransfer { reason: "transfer not timed out yet".to_string() });
+    }
+
+    CONDITIONAL_TRANSFERS.remove(deps.storage, token_id);
+
+    // NFT 仍由合约托管时归还发送者
+    if let Some(meta) = escrowed_token_meta(deps.storage, &env, token_id)? {
+        move_token(deps.storage, &env, token_id, &env.contract.address, &transfer.sender, &meta.kind)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "reclaim_conditional_transfer")
+        .add_attribute("token_id", token_id.to_string())
+        .add_attribute("sender", transfer.sender.to_string()))
+}
+
+// ========== 条件转移查询接口 ==========
+
+/// 查询 NFT 的条件转移
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 条件转移（不存在时为 None）
+pub fn query_conditional_transfer(deps: Deps, token_id: u64) -> StdResult<Binary> {
+    let transfer = CONDITIONAL_TRANSFERS.may_load(deps.storage, token_id)?;
+    to_json_binary(&ConditionalTransferResponse { token_id, transfer })
+}
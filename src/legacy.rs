@@ -0,0 +1,275 @@
+//! 旧版合约导入模块
+//! 
+//! 此模块实现将旧版 NFT 合约的 NFT 导入本合约，包括：
+//! - 导入配置：管理员登记旧版合约、导入系列和类型映射
+//! - 导入：用户通过旧版合约的 SendNft 将 NFT 发送到本合约，按映射后的元数据重新铸造
+//! - 旧版 ID 记录：按旧版合约保存旧版 token ID 到新 NFT ID 的对应关系，供查询
+//! 
+//! 旧版 NFT 由本合约永久持有；元数据以旧版合约的 `nft_info` 为准，
+//! 读取链上属性中的 `kind`、`scale` 和 `physical_sku`
+
+use cosmwasm_std::{to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage};
+use cw_storage_plus::Map;
+use cw721::{Cw721QueryMsg, Cw721ReceiveMsg, NftInfoResponse};
+use serde::Deserialize;
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::events::emit_mint_event;
+use crate::helpers::{
+    allocate_token_id, assign_series_serial, ensure_kind_mintable, record_activity, store_minted_token,
+    validate_mint_owner, validate_series_id,
+};
+use crate::msg::{LegacyImportMsg, LegacyImportResponse, LegacyTokenResponse};
+use crate::state::{LegacyImportConfig, LEGACY_IDS, LEGACY_IMPORT};
+use crate::types::{NftKind, NftMeta, Scale};
+
+/// 按旧版 token ID 单独记录的旧版导入（迁移前的格式）
+const LEGACY_IDS_V1: Map<String, u64> = Map::new("legacy_ids");
+
+/// 旧版元数据中表示类型的属性名
+const KIND_TRAIT: &str = "kind";
+
+/// 旧版元数据中表示盲盒规模的属性名
+const SCALE_TRAIT: &str = "scale";
+
+/// 旧版元数据中表示实物商品编号的属性名
+const PHYSICAL_SKU_TRAIT: &str = "physical_sku";
+
+/// 旧版合约的链上元数据
+/// 
+/// 只解析属性列表，忽略其余字段
+#[derive(Deserialize)]
+struct LegacyMetadata {
+    #[serde(default)]
+    attributes: Option<Vec<LegacyTrait>>,
+}
+
+/// 旧版元数据属性
+#[derive(Deserialize)]
+struct LegacyTrait {
+    trait_type: String,
+    value: String,
+}
+
+// ========== 导入执行接口 ==========
+
+/// 设置旧版合约导入
+/// 
+/// `import` 为 None 时停止导入，已导入的 NFT 及其旧版 ID 记录保留。
+/// 只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `import`: 导入配置
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_legacy_import(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    import: Option<LegacyImportMsg>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let response = Response::new().add_attribute("action", "set_legacy_import");
+    match import {
+        Some(import) => {
+            validate_series_id(&import.series_id)?;
+            if import.kind_map.is_empty() {
+                return Err(ContractError::InvalidLegacyImport { reason: "empty kind map".to_string() });
+            }
+            let mut seen = alloc::collections::BTreeSet::new();
+            for mapping in &import.kind_map {
+                if !seen.insert(mapping.legacy_kind.as_str()) {
+                    return Err(ContractError::InvalidLegacyImport {
+                        reason: alloc::format!("duplicate legacy kind {}", mapping.legacy_kind),
+                    });
+                }
+            }
+            let config = LegacyImportConfig {
+                contract: deps.api.addr_validate(&import.contract)?,
+                series_id: import.series_id,
+                kind_map: import.kind_map,
+            };
+            LEGACY_IMPORT.save(deps.storage, &config)?;
+            Ok(response
+                .add_attribute("contract", config.contract.to_string())
+                .add_attribute("series_id", config.series_id))
+        }
+        None => {
+            LEGACY_IMPORT.remove(deps.storage);
+            Ok(response.add_attribute("contract", "none"))
+        }
+    }
+}
+
+/// 导入旧版 NFT
+/// 
+/// 由旧版合约在 SendNft 时回调，按旧版元数据映射后为原所有者铸造新的 NFT，
+/// 并记录旧版 token ID。拒绝时旧版合约的转移一并回滚
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储、API 和查询访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息
+/// - `info`: 消息信息，发送者为旧版合约
+/// - `msg`: CW721 回调消息
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 导入结果
+pub fn execute_import_legacy(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    let config = LEGACY_IMPORT.may_load(deps.storage)?
+        .filter(|config| config.contract == info.sender)
+        .ok_or_else(|| ContractError::InvalidLegacyImport { reason: "sender is not the legacy contract".to_string() })?;
+    let legacy_key = (config.contract.clone(), msg.token_id.clone());
+    if LEGACY_IDS.has(deps.storage, legacy_key.clone()) {
+        return Err(ContractError::InvalidLegacyImport { reason: "token already imported".to_string() });
+    }
+
+    let owner = deps.api.addr_validate(&msg.sender)?;
+    validate_mint_owner(deps.storage, &env.contract.address, &owner)?;
+
+    // 读取旧版元数据并映射
+    let legacy: NftInfoResponse<Option<LegacyMetadata>> = deps.querier.query_wasm_smart(
+        config.contract.to_string(),
+        &Cw721QueryMsg::NftInfo { token_id: msg.token_id.clone() },
+    )?;
+    let traits = legacy.extension.and_then(|meta| meta.attributes).unwrap_or_default();
+    let mut meta = map_legacy_meta(&config, &traits)?;
+    ensure_kind_mintable(deps.storage, &meta.kind)?;
+
+    // 分配 ID 和序号并保存
+    meta.serial_in_series = assign_series_serial(deps.storage, &meta.series_id)?;
+    let token_id = allocate_token_id(deps.storage, &meta.series_id, meta.serial_in_series)?;
+    let new_supply = store_minted_token(deps.storage, &env, token_id, &owner, &meta)?;
+    LEGACY_IDS.save(deps.storage, legacy_key, &token_id)?;
+
+    record_activity(deps.storage, &env, "import_legacy", Some(token_id), &owner)?;
+
+    let kind = alloc::format!("{:?}", meta.kind);
+    Ok(Response::new()
+        .add_attribute("action", "import_legacy")
+        .add_attribute("legacy_contract", config.contract.to_string())
+        .add_attribute("legacy_token_id", msg.token_id)
+        .add_attribute("token_id", token_id.to_string())
+        .add_attribute("owner", owner.to_string())
+        .add_attribute("kind", kind.clone())
+        .add_event(emit_mint_event(token_id, owner.as_str(), &kind, new_supply)))
+}
+
+/// 检查地址是否为旧版合约
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `sender`: 回调发送者
+/// 
+/// # 返回值
+/// - `StdResult<bool>`: 是否为已配置的旧版合约
+pub fn is_legacy_contract(storage: &dyn Storage, sender: &Addr) -> StdResult<bool> {
+    Ok(LEGACY_IMPORT.may_load(storage)?.map_or(false, |config| config.contract == *sender))
+}
+
+/// 将迁移前的旧版 ID 记录改为按旧版合约记录
+/// 
+/// 迁移前的记录不含旧版合约地址，归属于当前配置的旧版合约；
+/// 未配置导入时保留原记录，待重新配置后再次迁移
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// 
+/// # 返回值
+/// - `StdResult<bool>`: 是否迁移了记录
+pub fn migrate_legacy_ids(storage: &mut dyn Storage) -> StdResult<bool> {
+    let Some(config) = LEGACY_IMPORT.may_load(storage)? else {
+        return Ok(false);
+    };
+    let records = LEGACY_IDS_V1
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (legacy_token_id, token_id) in &records {
+        LEGACY_IDS.save(storage, (config.contract.clone(), legacy_token_id.clone()), token_id)?;
+        LEGACY_IDS_V1.remove(storage, legacy_token_id.clone());
+    }
+    Ok(!records.is_empty())
+}
+
+/// 将旧版属性映射为 NFT 元数据
+/// 
+/// `kind` 属性按配置映射且必须存在；`scale` 缺失时为 Tiny；序号由调用方分配
+fn map_legacy_meta(config: &LegacyImportConfig, traits: &[LegacyTrait]) -> Result<NftMeta, ContractError> {
+    let find = |trait_type: &str| traits.iter()
+        .find(|attr| attr.trait_type == trait_type)
+        .map(|attr| attr.value.as_str());
+
+    let legacy_kind = find(KIND_TRAIT)
+        .ok_or_else(|| ContractError::InvalidLegacyImport { reason: "missing kind attribute".to_string() })?;
+    let kind: NftKind = config.kind_map.iter()
+        .find(|mapping| mapping.legacy_kind == legacy_kind)
+        .map(|mapping| mapping.kind.clone())
+        .ok_or_else(|| ContractError::InvalidLegacyImport {
+            reason: alloc::format!("unmapped legacy kind {}", legacy_kind),
+        })?;
+    let scale_origin = match find(SCALE_TRAIT) {
+        Some(value) => Scale::ALL.iter()
+            .find(|scale| alloc::format!("{:?}", scale).eq_ignore_ascii_case(value))
+            .cloned()
+            .ok_or_else(|| ContractError::InvalidLegacyImport {
+                reason: alloc::format!("unknown scale {}", value),
+            })?,
+        None => Scale::Tiny,
+    };
+
+    Ok(NftMeta {
+        kind,
+        scale_origin,
+        physical_sku: find(PHYSICAL_SKU_TRAIT).map(|value| value.to_string()),
+        crafted_from: None,
+        series_id: config.series_id.clone(),
+        collection_group_id: None,
+        serial_in_series: 0,
+        expires_at: None,
+    })
+}
+
+// ========== 导入查询接口 ==========
+
+/// 查询旧版合约导入配置
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 导入配置（未设置时为 None）
+pub fn query_legacy_import(deps: Deps) -> StdResult<Binary> {
+    let import = LEGACY_IMPORT.may_load(deps.storage)?;
+    to_json_binary(&LegacyImportResponse { import })
+}
+
+/// 查询旧版 NFT 导入后的 NFT ID
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `legacy_contract`: 旧版合约地址
+/// - `legacy_token_id`: 旧版合约中的 token ID
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 导入后的 NFT ID（未导入时为 None）
+pub fn query_legacy_token(deps: Deps, legacy_contract: String, legacy_token_id: String) -> StdResult<Binary> {
+    let contract = deps.api.addr_validate(&legacy_contract)?;
+    let token_id = LEGACY_IDS.may_load(deps.storage, (contract, legacy_token_id.clone()))?;
+    to_json_binary(&LegacyTokenResponse { legacy_contract, legacy_token_id, token_id })
+}
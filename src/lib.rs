@@ -12,6 +12,7 @@
 //! - NFT synthesis system
 //! - Recipe-based crafting
 //! - no_std compatibility for embedded environments
+//! - Storage-agnostic synthesis logic ([`logic`]) for off-chain simulation
 //!
 //! ## Usage
 //!
@@ -22,23 +23,89 @@
 extern crate alloc;
 
 // Core modules
+#[cfg(feature = "cosmwasm")]
 pub mod contract;
+#[cfg(feature = "cosmwasm")]
+pub mod context;
+#[cfg(feature = "cosmwasm")]
 pub mod error;
+#[cfg(feature = "cosmwasm")]
 pub mod msg;
+#[cfg(feature = "cosmwasm")]
 pub mod state;
-pub mod types;
+#[cfg(feature = "cosmwasm")]
 pub mod cw721;
+#[cfg(feature = "cosmwasm")]
 pub mod luckee;
+#[cfg(feature = "cosmwasm")]
 pub mod admin;
+#[cfg(feature = "cosmwasm")]
 pub mod events;
+#[cfg(feature = "cosmwasm")]
 pub mod helpers;
+#[cfg(feature = "cosmwasm")]
+pub mod pagination;
+#[cfg(feature = "cosmwasm")]
 pub mod recipes;
+#[cfg(feature = "cosmwasm")]
+pub mod deposits;
+#[cfg(feature = "cosmwasm")]
+pub mod pricing;
+#[cfg(feature = "cosmwasm")]
+pub mod swaps;
+#[cfg(feature = "cosmwasm")]
+pub mod gifts;
+#[cfg(feature = "cosmwasm")]
+pub mod sessions;
+#[cfg(feature = "cosmwasm")]
+pub mod burn_tax;
+#[cfg(feature = "cosmwasm")]
+pub mod relay;
+#[cfg(feature = "cosmwasm")]
+pub mod sweeper;
+#[cfg(feature = "cosmwasm")]
+pub mod royalty;
+#[cfg(feature = "cosmwasm")]
+pub mod custody;
+#[cfg(feature = "cosmwasm")]
+pub mod voting;
+#[cfg(feature = "cosmwasm")]
+pub mod bundles;
+#[cfg(feature = "cosmwasm")]
+pub mod htlc;
+#[cfg(feature = "cosmwasm")]
+pub mod partners;
+#[cfg(feature = "cosmwasm")]
+pub mod burn_queue;
+#[cfg(feature = "cosmwasm")]
+pub mod legacy;
+#[cfg(feature = "cosmwasm")]
+pub mod session_keys;
+#[cfg(feature = "cosmwasm")]
+pub mod treasury;
+#[cfg(feature = "cosmwasm")]
+pub mod factory;
+#[cfg(feature = "cosmwasm")]
+pub mod cross_collection;
+#[cfg(feature = "cosmwasm")]
+pub mod pools;
+
+// Integration test fixtures for downstream contracts
+#[cfg(feature = "test-utils")]
+pub mod testing;
+
+// Storage-agnostic modules, usable without cosmwasm_std
+pub mod types;
+pub mod logic;
 
 // Re-export main functionality
+#[cfg(feature = "cosmwasm")]
 pub use crate::error::ContractError;
+#[cfg(feature = "cosmwasm")]
 pub use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
 
 // Common result type
+#[cfg(feature = "cosmwasm")]
 pub type Result<T> = core::result::Result<T, ContractError>;
 
 // Unit tests for no_std compatibility
@@ -0,0 +1,233 @@
+//! 合成核心逻辑模块
+//!
+//! 此模块包含与存储无关的纯合成逻辑，不依赖 cosmwasm_std，包括：
+//! - 配方输入匹配与可合成次数计算
+//! - 兑换价值计算
+//! - 合成输入校验（重复输入、数量要求）
+//! - 链式合成成本估算
+//!
+//! 链上合约与链下服务（如匹配服务器模拟合成结果）共用同一套逻辑，
+//! 调用方只需提供库存或输入类型，无需访问合约存储
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::types::{NftKind, RecipeInput};
+
+// ========== 错误定义 ==========
+
+/// 合成校验错误
+///
+/// 链上调用时转换为对应的 `ContractError`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SynthesisError {
+    /// 没有提供任何输入
+    NoInputs,
+    /// 输入中出现重复的 NFT
+    DuplicateInput { token_id: u64 },
+    /// 输入不满足配方要求
+    InsufficientInputs,
+    /// 目标类型没有合成配方
+    RecipeNotFound,
+    /// 配方链存在循环
+    RecipeCycle,
+    /// 数量计算溢出
+    Overflow,
+}
+
+impl core::fmt::Display for SynthesisError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SynthesisError::NoInputs => write!(f, "No synthesis inputs"),
+            SynthesisError::DuplicateInput { token_id } => write!(f, "Duplicate input token: {}", token_id),
+            SynthesisError::InsufficientInputs => write!(f, "Insufficient input tokens"),
+            SynthesisError::RecipeNotFound => write!(f, "Recipe not found"),
+            SynthesisError::RecipeCycle => write!(f, "Recipe chain contains a cycle"),
+            SynthesisError::Overflow => write!(f, "Overflow"),
+        }
+    }
+}
+
+// ========== 库存与价值计算 ==========
+
+/// 汇总库存
+///
+/// # 参数
+/// - `inventory`: 类型与数量列表，重复类型累加
+///
+/// # 返回值
+/// - `BTreeMap<String, u64>`: 按类型键汇总的数量
+pub fn tally_inventory<'a>(inventory: impl IntoIterator<Item = (&'a NftKind, u64)>) -> BTreeMap<String, u64> {
+    let mut available = BTreeMap::new();
+    for (kind, count) in inventory {
+        let entry = available.entry(kind.to_key()).or_insert(0u64);
+        *entry = entry.saturating_add(count);
+    }
+    available
+}
+
+/// 计算配方输入的兑换价值总和（以四叶草数量计）
+///
+/// # 参数
+/// - `inputs`: 配方输入
+///
+/// # 返回值
+/// - `u64`: 单次合成消耗的兑换价值
+pub fn inputs_value(inputs: &[RecipeInput]) -> u64 {
+    inputs.iter().fold(0u64, |total, input| {
+        total.saturating_add(input.nft_kind.exchange_value() as u64 * input.count as u64)
+    })
+}
+
+/// 计算单次合成的兑换价值变化
+///
+/// # 参数
+/// - `inputs`: 配方输入
+/// - `output`: 输出类型
+///
+/// # 返回值
+/// - `i64`: 产出减消耗，负数表示合成会损失价值
+pub fn value_delta(inputs: &[RecipeInput], output: &NftKind) -> i64 {
+    output.exchange_value() as i64 - inputs_value(inputs) as i64
+}
+
+// ========== 配方匹配 ==========
+
+/// 计算最多可合成次数
+///
+/// 取各输入的持有数量 / 需要数量的最小值，数量为 0 的输入不参与计算；
+/// 没有有效输入的配方不能合成，返回 0
+///
+/// # 参数
+/// - `inputs`: 配方输入
+/// - `have`: 返回指定类型持有数量的函数
+///
+/// # 返回值
+/// - `u64`: 最多可合成次数
+pub fn max_synthesis_times(inputs: &[RecipeInput], have: impl Fn(&NftKind) -> u64) -> u64 {
+    let mut max_times = u64::MAX;
+    for input in inputs.iter().filter(|input| input.count > 0) {
+        max_times = max_times.min(have(&input.nft_kind) / input.count as u64);
+    }
+    if max_times == u64::MAX {
+        0
+    } else {
+        max_times
+    }
+}
+
+/// 列出合成一次还缺少的输入
+///
+/// # 参数
+/// - `inputs`: 配方输入
+/// - `have`: 返回指定类型持有数量的函数
+///
+/// # 返回值
+/// - `Vec<RecipeInput>`: 缺少的类型与数量
+pub fn missing_inputs(inputs: &[RecipeInput], have: impl Fn(&NftKind) -> u64) -> Vec<RecipeInput> {
+    inputs
+        .iter()
+        .filter_map(|input| {
+            let have = have(&input.nft_kind);
+            (have < input.count as u64).then(|| RecipeInput {
+                nft_kind: input.nft_kind.clone(),
+                count: input.count - have as u32,
+            })
+        })
+        .collect()
+}
+
+/// 校验合成输入
+///
+/// 检查输入非空、没有重复的 NFT，且各类型数量满足配方要求
+///
+/// # 参数
+/// - `inputs`: 输入 NFT 的 ID 与类型，按提交顺序
+/// - `recipe_inputs`: 配方输入要求
+///
+/// # 返回值
+/// - `Result<(), SynthesisError>`: 校验结果，遇到第一个问题即返回
+pub fn check_synthesis_inputs(inputs: &[(u64, NftKind)], recipe_inputs: &[RecipeInput]) -> Result<(), SynthesisError> {
+    if inputs.is_empty() {
+        return Err(SynthesisError::NoInputs);
+    }
+
+    let mut seen = BTreeSet::new();
+    for (token_id, _) in inputs {
+        if !seen.insert(*token_id) {
+            return Err(SynthesisError::DuplicateInput { token_id: *token_id });
+        }
+    }
+
+    let counts = tally_inventory(inputs.iter().map(|(_, kind)| (kind, 1)));
+    let satisfied = recipe_inputs.iter().all(|input| {
+        counts.get(&input.nft_kind.to_key()).copied().unwrap_or(0) >= input.count as u64
+    });
+    if !satisfied {
+        return Err(SynthesisError::InsufficientInputs);
+    }
+    Ok(())
+}
+
+// ========== 链式合成成本 ==========
+
+/// 配方链的最大深度，超过时视为配方存在循环
+const MAX_RECIPE_DEPTH: u32 = 32;
+
+/// 链式合成所需的基础输入
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CraftingCost {
+    /// 所需的基础类型数量
+    pub required: u64,
+    /// 需要执行的合成次数（包括中间产物）
+    pub syntheses: u64,
+    /// 无法由基础类型合成、需要另外持有的输入（按类型键排序）
+    pub other_inputs: Vec<(NftKind, u64)>,
+}
+
+/// 计算链式合成目标所需的基础类型数量
+///
+/// 从目标开始沿配方逐层展开输入，直到基础类型或没有配方的类型为止
+///
+/// # 参数
+/// - `target`: 目标类型
+/// - `from_kind`: 基础类型
+/// - `recipe_of`: 返回指定类型配方输入的函数，没有配方时返回 None
+///
+/// # 返回值
+/// - `Result<CraftingCost, SynthesisError>`: 所需基础类型数量、合成次数和其他输入
+pub fn crafting_cost<'a>(
+    target: &NftKind,
+    from_kind: &NftKind,
+    recipe_of: impl Fn(&NftKind) -> Option<&'a [RecipeInput]>,
+) -> Result<CraftingCost, SynthesisError> {
+    if target != from_kind && recipe_of(target).is_none() {
+        return Err(SynthesisError::RecipeNotFound);
+    }
+
+    let mut cost = CraftingCost::default();
+    let mut other: BTreeMap<String, (NftKind, u64)> = BTreeMap::new();
+    let mut pending = alloc::vec![(target.clone(), 1u64, 0u32)];
+    while let Some((kind, times, depth)) = pending.pop() {
+        if kind == *from_kind {
+            cost.required = cost.required.checked_add(times).ok_or(SynthesisError::Overflow)?;
+            continue;
+        }
+        let Some(inputs) = recipe_of(&kind) else {
+            let entry = other.entry(kind.to_key()).or_insert((kind, 0));
+            entry.1 = entry.1.checked_add(times).ok_or(SynthesisError::Overflow)?;
+            continue;
+        };
+        if depth >= MAX_RECIPE_DEPTH {
+            return Err(SynthesisError::RecipeCycle);
+        }
+        cost.syntheses = cost.syntheses.checked_add(times).ok_or(SynthesisError::Overflow)?;
+        for input in inputs.iter().filter(|input| input.count > 0) {
+            let needed = times.checked_mul(input.count as u64).ok_or(SynthesisError::Overflow)?;
+            pending.push((input.nft_kind.clone(), needed, depth + 1));
+        }
+    }
+    cost.other_inputs = other.into_values().collect();
+    Ok(cost)
+}
@@ -9,27 +9,73 @@
 
 #[cfg(feature = "cosmwasm")]
 use cosmwasm_std::{
-    to_json_binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    to_json_binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
     Binary, Order,
 };
 #[cfg(feature = "cosmwasm")]
-use cw_storage_plus::Bound;
+use crate::pagination::{clamp_limit, cursor_scope, decode_cursor, into_page, scan_page, start_bound, page_after, MAX_LIMIT};
 
 use crate::error::ContractError;
+use crate::context::ExecuteContext;
+#[cfg(feature = "cosmwasm")]
+use crate::deposits::charge_fee;
+#[cfg(feature = "cosmwasm")]
+use crate::treasury::{fee_source, record_fee_collected};
+#[cfg(feature = "cosmwasm")]
+use crate::pricing::resolve_recipe_fee;
+#[cfg(feature = "cosmwasm")]
+use crate::burn_tax::{collect_burn_tax, taxed_recipe};
+#[cfg(feature = "cosmwasm")]
+use crate::royalty::collect_crafting_royalty;
+#[cfg(feature = "cosmwasm")]
+use crate::partners::{consume_partner_inputs, partner_adjusted_recipe, validate_partner_inputs};
+#[cfg(feature = "cosmwasm")]
+use crate::burn_queue::ensure_burn_request_not_required;
+#[cfg(feature = "cosmwasm")]
+use crate::recipes::{lookup_recipe, resolve_recipe};
+#[cfg(feature = "cosmwasm")]
+use crate::cross_collection::remote_synthesis_output;
 #[cfg(feature = "cosmwasm")]
 use crate::state::{
     TOKEN_META, TOKEN_OWNERSHIP, SERIES_NEXT_SERIAL, TOTAL_SUPPLY, RECIPES, 
-    SYNTHESIS_HISTORY, SynthesisRecord, ALL_TOKENS, NEXT_TOKEN_ID
+    SYNTHESIS_HISTORY, SynthesisRecord, ALL_TOKENS, NEXT_TOKEN_ID,
+    BURN_GRACE_PERIOD, PENDING_BURNS, PendingBurn, KIND_MINT_STATUS,
+    META_ORACLE_PUBKEY, SPENT_ORACLE_NONCES, TOKENS_BY_OWNER, ACTIVITY_LOG, NEXT_ACTIVITY_SEQ,
+    SERIES_OWNERS, SERIES_GRANTS, OWNER_KIND_COUNTS_READY, MINTER_STATS,
+    TOKENS_BY_KIND, TOKENS_BY_SERIES, TOKENS_BY_SERIAL, TOKENS_BY_GROUP, TOKEN_INDEXES_READY,
+    RESERVED_ID_RANGE, BURN_TAXES, TOKEN_ACQUIRED, BURN_REQUESTS, SERIES_TOKEN_COUNT,
+    TOKEN_APPROVALS, SESSION_LOCKS, CUSTODY_TAGS, GIFTED_TOKENS, CONDITIONAL_TRANSFERS, BUNDLED_TOKENS,
+    RECIPE_OUTPUT_COLLECTIONS, GIFTS, MintReceipt,
 };
-use crate::types::{NftKind, NftMeta, Recipe, Scale};
+#[cfg(feature = "cosmwasm")]
+use sha2::{Digest, Sha256};
+use crate::logic;
+use crate::types::{NftKind, NftMeta, Recipe, Scale, KindMintStatus};
 use crate::msg::{BatchMintItem, TokensByKindResponse, TokensBySeriesResponse, 
                 TokensByGroupResponse, LuckeeContractInfoResponse, AllRecipesResponse, 
-                SynthesisPreviewResponse, NftContractResponse};
+                SynthesisPreviewResponse, NftContractResponse, VersionInfoResponse,
+                PendingBurnResponse, KindMintStatusResponse, OracleMetaPayload,
+                TokensByExpiryResponse, TokenWithMeta, TokensWithMetaResponse,
+                RecentActivityResponse, SeriesOwnerResponse, SerialOwnerResponse, TokenBySerialResponse, OwnedAtResponse, VerifyOwnershipResponse, SynthesizeData,
+                SynthesizableTarget, SynthesizableTargetsResponse, SimulateRecipeResponse, MinterStatsResponse, CraftingCostResponse,
+                RecipeGraphEdge, RecipeGraphNode, RecipeGraphResponse, PartnerInput, MintValidationResponse, ValidateBatchMintResponse,
+                KindInfoResponse, KindListResponse, ScaleInfo, ScaleInfoResponse, TokenMetaResponse, RecipeResponse, Cursor,
+                TokenDebugResponse, TokenIndexPresence};
 #[cfg(feature = "cosmwasm")]
-use crate::helpers::{check_contract_paused, is_authorized_minter, validate_synthesis_inputs, 
-                    add_token_to_owner, validate_series_id, validate_collection_group_id};
+use crate::helpers::{is_authorized_minter, validate_synthesis_inputs, 
+                    add_token_to_owner, validate_series_id, validate_collection_group_id,
+                    increase_series_token_count, decrease_series_token_count, build_burn_notifications,
+                    validate_mint_owner, ensure_kind_mintable, build_token_uri,
+                    record_activity, record_minter_stats, ACTIVITY_LOG_SIZE, authorize_series_mint,
+                    track_storage_entry, storage_counter, index_token, unindex_token, owner_kind_count,
+                    consume_crafting_allowance, diff_token_meta, assign_series_serial,
+                    authorize_token_operation, ensure_not_session_locked, active_session_lock,
+                    check_mint_id_permission, allocate_token_id, store_minted_token, record_acquisition,
+                    deterministic_ids_enabled, next_series_serial, resolve_mint_token_id,
+                    replay_mint, record_mint_receipt, verbose_events, synthesis_series_id};
 #[cfg(feature = "cosmwasm")]
-use crate::events::{emit_mint_event, emit_burn_event, emit_synthesize_event, emit_batch_mint_event};
+use crate::events::{emit_mint_event, emit_burn_event, emit_restore_event, emit_synthesize_event, emit_batch_mint_event,
+                    emit_meta_update_event, event_attributes};
 
 // ========== 常量定义 ==========
 
@@ -39,7 +85,62 @@ const MAX_SYNTHESIS_INPUTS: usize = 50;
 /// 批量铸造的最大数量限制
 const MAX_BATCH_MINT: usize = 100;
 
+/// 单次按类型批量销毁的最大数量
+const MAX_BURN_ALL_OF_KIND: u32 = 100;
 
+/// 单次批量验证所有权的最大 NFT 数量
+const MAX_VERIFY_OWNERSHIP: usize = 100;
+
+/// 当前部署启用的能力标识
+/// 
+/// 集成方通过 VersionInfo 查询进行功能探测，新增功能时在此登记。
+/// `marketplace`、`ibc`、`randomness`、`soulbound` 为约定的标识，只在对应功能实现后登记
+pub const CAPABILITIES: &[&str] = &[
+    "synthesis",
+    "batch_mint",
+    "pausable",
+    "burn_grace_period",
+    "withdraw_guardians",
+    "deposits",
+    "burn_hooks",
+    "kind_mint_status",
+    "provenance",
+    "meta_oracle",
+    "expiration",
+    "series_registry",
+    "minter_rotation",
+    "usd_pricing",
+    "kind_approvals",
+    "trusted_operators",
+    "operator_budgets",
+    "swaps",
+    "gifts",
+    "airdrop",
+    "game_sessions",
+    "crafting_limits",
+    "reserved_ids",
+    "burn_tax",
+    "relay_mint",
+    "crafting_royalty",
+    "minter_set_lock",
+    "custody",
+    "voting",
+    "delegation",
+    "bundles",
+    "conditional_transfers",
+    "partners",
+    "idempotency",
+    "event_verbosity",
+    "burn_cooldown",
+    "legacy_import",
+    "payment_denoms",
+    "session_keys",
+    "deterministic_ids",
+    "factory",
+    "shared_recipes",
+    "cross_collection_output",
+    "mint_pools",
+];
 
 // ========== Luckee 扩展执行接口 ==========
 
@@ -49,75 +150,78 @@ const MAX_BATCH_MINT: usize = 100;
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于拒绝铸造给合约自身
 /// - `info`: 消息信息，包含发送者
 /// - `token_id`: 要铸造的 NFT ID
 /// - `owner`: 新 NFT 的所有者地址
 /// - `extension`: NFT 的扩展元数据
+/// - `idempotency_key`: 幂等键（可选），重复提交时返回首次铸造的结果
 /// 
 /// # 返回值
 /// - `Result<Response, ContractError>`: 铸造结果
 #[cfg(feature = "cosmwasm")]
+#[allow(clippy::too_many_arguments)]
 pub fn execute_mint(
     deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
     info: MessageInfo,
     token_id: u64,
     owner: String,
     extension: NftMeta,
+    idempotency_key: Option<String>,
 ) -> Result<Response, ContractError> {
     // 检查合约是否暂停
-    check_contract_paused(deps.storage)?;
-    
-    // 加载合约配置
-    let config = crate::state::CONFIG.load(deps.storage)?;
-    
-    // 验证铸造者权限
-    if !is_authorized_minter(deps.as_ref(), &info.sender, &config)? {
-        return Err(ContractError::MinterNotAuthorized {});
+    ctx.ensure_not_paused()?;
+
+    // 重复提交的幂等键直接返回首次铸造的结果
+    if let Some(response) = replay_mint(deps.storage, &info.sender, idempotency_key.as_deref(), "mint")? {
+        return Ok(response);
     }
+    
+    // 确定性 ID 模式下按系列和即将分配的序号推导 ID
+    let deterministic = deterministic_ids_enabled(deps.storage)?;
+    let serial = next_series_serial(deps.storage, &extension.series_id)?;
+    let token_id = resolve_mint_token_id(deps.storage, token_id, &extension.series_id, serial)?;
 
-    // 验证所有者地址格式
-    let owner_addr = deps.api.addr_validate(&owner)?;
+    // 验证铸造权限：保留区间内的 ID 只能由合约所有者铸造，其余 ID 需要铸造者权限
+    let reserved = RESERVED_ID_RANGE.may_load(deps.storage)?;
+    let is_minter = is_authorized_minter(deps.as_ref(), &info.sender, &ctx.config)?;
+    check_mint_id_permission(reserved.as_ref(), token_id, info.sender == ctx.config.owner, is_minter)?;
 
-    // 验证系列ID格式
-    validate_series_id(&extension.series_id)?;
-    
-    // 验证集合组ID格式（如果提供）
-    if let Some(ref group_id) = extension.collection_group_id {
-        validate_collection_group_id(group_id)?;
-    }
+    // 校验 ID 占用、所有者地址、类型铸造状态、系列ID、过期时间和集合组ID
+    let owner_addr = check_mint_item(deps.storage, deps.api, &env, token_id, &owner, &extension)?;
 
-    // 检查 NFT 是否已存在
-    if TOKEN_META.has(deps.storage, token_id) {
-        return Err(ContractError::TokenAlreadyExists {});
-    }
+    // 检查对该系列的铸造权限（未注册的系列由本次铸造者认领）
+    authorize_series_mint(deps.storage, &extension.series_id, &info.sender)?;
 
-    // 更新NEXT_TOKEN_ID计数器，确保后续生成的ID不会冲突
+    // 更新NEXT_TOKEN_ID计数器，确保后续生成的ID不会冲突（推导出的 ID 不占用计数器）
     let current_next_id = NEXT_TOKEN_ID.load(deps.storage)?;
-    if token_id >= current_next_id {
+    if !deterministic && token_id >= current_next_id {
         NEXT_TOKEN_ID.save(deps.storage, &(token_id.checked_add(1).ok_or(ContractError::Overflow {})?))?;
     }
 
+    // 系列内序号由系列计数器分配，保证系列内唯一
+    let mut extension = extension;
+    extension.serial_in_series = assign_series_serial(deps.storage, &extension.series_id)?;
+
     // ========== 本地 CW721 模式 ==========
-    // 直接保存元数据和所有权到本地存储
-    
-    TOKEN_META.save(deps.storage, token_id, &extension)?;
-    TOKEN_OWNERSHIP.save(deps.storage, token_id, &owner_addr)?;
-    
-    // 更新所有者索引和全局索引
-    add_token_to_owner(deps.storage, &owner_addr, token_id)?;
-    ALL_TOKENS.save(deps.storage, token_id, &())?;
-    
-    // 更新系列序号（使用 checked_add 防止溢出）
-    let next_serial = SERIES_NEXT_SERIAL.may_load(deps.storage, extension.series_id.clone())?.unwrap_or(0);
-    let new_serial = next_serial.checked_add(1)
-        .ok_or(ContractError::Overflow {})?;
-    SERIES_NEXT_SERIAL.save(deps.storage, extension.series_id.clone(), &new_serial)?;
+    // 直接保存元数据和所有权到本地存储，并更新索引和总供应量
+    let new_supply = store_minted_token(deps.storage, &env, token_id, &owner_addr, &extension)?;
 
-    // 更新总供应量（使用 checked_add 防止溢出）
-    let total_supply = TOTAL_SUPPLY.load(deps.storage)?;
-    let new_supply = total_supply.checked_add(1)
-        .ok_or(ContractError::Overflow {})?;
-    TOTAL_SUPPLY.save(deps.storage, &new_supply)?;
+    // 记录到活动流
+    record_activity(deps.storage, &env, "mint", Some(token_id), &info.sender)?;
+    record_minter_stats(deps.storage, &env, &info.sender, 1)?;
+    let kind = alloc::format!("{:?}", extension.kind);
+    record_mint_receipt(deps.storage, &info.sender, idempotency_key, &MintReceipt {
+        token_ids: vec![token_id],
+        height: env.block.height,
+        action: "mint".to_string(),
+        owner: Some(owner_addr),
+        kind: Some(kind),
+        total_supply: new_supply,
+    })?;
     
     let owner_str = owner.clone();
     Ok(Response::new()
@@ -125,7 +229,7 @@ pub fn execute_mint(
         .add_attribute("token_id", token_id.to_string())
         .add_attribute("owner", owner)
         .add_attribute("kind", alloc::format!("{:?}", extension.kind))
-        .add_event(emit_mint_event(token_id, &owner_str, &alloc::format!("{:?}", extension.kind))))
+        .add_event(emit_mint_event(token_id, &owner_str, &alloc::format!("{:?}", extension.kind), new_supply)))
 }
 
 /// 销毁 NFT
@@ -134,6 +238,8 @@ pub fn execute_mint(
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于计算宽限期截止高度
 /// - `info`: 消息信息，包含发送者
 /// - `token_id`: 要销毁的 NFT ID
 /// 
@@ -142,51 +248,371 @@ pub fn execute_mint(
 #[cfg(feature = "cosmwasm")]
 pub fn execute_burn(
     deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
     info: MessageInfo,
     token_id: u64,
 ) -> Result<Response, ContractError> {
     // 检查合约是否暂停
-    check_contract_paused(deps.storage)?;
+    ctx.ensure_not_paused()?;
     
     // 验证 NFT 是否存在
-    let meta = TOKEN_META.may_load(deps.storage, token_id)?;
-    if meta.is_none() {
-        return Err(ContractError::TokenNotFound {});
-    }
+    let meta = TOKEN_META.may_load(deps.storage, token_id)?
+        .ok_or(ContractError::TokenNotFound {})?;
 
-    // 验证所有者身份
+    // 验证所有者身份（或获得授权的操作员）
     let owner = TOKEN_OWNERSHIP.load(deps.storage, token_id)?;
-    if owner != info.sender {
-        return Err(ContractError::NotOwned {});
-    }
+    authorize_token_operation(deps.storage, &env, &owner, &info.sender, &meta.kind)?;
+
+    // 对局中锁定的 NFT 不可销毁
+    ensure_not_session_locked(deps.storage, &env, token_id)?;
+
+    // 高价值类型须通过销毁申请并等待冷却期
+    ensure_burn_request_not_required(deps.storage, &meta.kind)?;
+
+    burn_token(deps.storage, &env, &info.sender, token_id, owner, meta)
+}
 
+/// 销毁单个 NFT
+/// 
+/// 删除元数据、所有权和索引；启用宽限期时进入待销毁状态，否则通知销毁监听合约。
+/// 调用方负责权限、对局锁定和销毁申请的检查
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `env`: 环境信息，用于计算宽限期截止高度
+/// - `actor`: 执行销毁的地址
+/// - `token_id`: 要销毁的 NFT ID
+/// - `owner`: NFT 所有者
+/// - `meta`: NFT 元数据
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 销毁结果
+#[cfg(feature = "cosmwasm")]
+pub(crate) fn burn_token(
+    storage: &mut dyn cosmwasm_std::Storage,
+    env: &Env,
+    actor: &cosmwasm_std::Addr,
+    token_id: u64,
+    owner: cosmwasm_std::Addr,
+    meta: NftMeta,
+) -> Result<Response, ContractError> {
     // ========== 本地 CW721 模式 ==========
-    // 直接删除本地元数据和所有权
+    // 直接删除本地元数据、所有权和索引
+    let new_supply = remove_token_state(storage, env.block.height, token_id, &owner, &meta)?;
+
+    // 记录到活动流
+    record_activity(storage, env, "burn", Some(token_id), actor)?;
     
-    // 删除 NFT 元数据
-    TOKEN_META.remove(deps.storage, token_id);
-    TOKEN_OWNERSHIP.remove(deps.storage, token_id);
+    // 启用宽限期时，NFT 进入待销毁状态，宽限期内可恢复
+    let grace_period = BURN_GRACE_PERIOD.may_load(storage)?.unwrap_or(0);
+    if grace_period > 0 {
+        let restore_until_height = env.block.height.checked_add(grace_period)
+            .ok_or(ContractError::Overflow {})?;
+        PENDING_BURNS.save(storage, token_id, &PendingBurn {
+            owner: owner.clone(),
+            meta,
+            restore_until_height,
+        })?;
+
+        return Ok(Response::new()
+            .add_attribute("action", "burn_pending")
+            .add_attribute("token_id", token_id.to_string())
+            .add_attribute("owner", owner.to_string())
+            .add_attribute("restore_until_height", restore_until_height.to_string()));
+    }
+    
+    // 通知已注册的销毁监听合约
+    let notifications = build_burn_notifications(storage, token_id, &owner)?;
+    
+    Ok(Response::new()
+        .add_submessages(notifications)
+        .add_attribute("action", "burn")
+        .add_attribute("token_id", token_id.to_string())
+        .add_attribute("owner", owner.to_string())
+        .add_event(emit_burn_event(token_id, &owner, new_supply)))
+}
+
+/// 恢复待销毁的 NFT
+/// 
+/// 宽限期内由原所有者或合约所有者恢复误销毁的 NFT
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于检查宽限期
+/// - `info`: 消息信息，包含发送者
+/// - `token_id`: 要恢复的 NFT ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 恢复结果
+#[cfg(feature = "cosmwasm")]
+pub fn execute_restore_burned(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    token_id: u64,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    // 加载待销毁记录
+    let pending = PENDING_BURNS.may_load(deps.storage, token_id)?
+        .ok_or(ContractError::PendingBurnNotFound {})?;
+
+    // 只有原所有者或合约所有者可以恢复
+    if info.sender != pending.owner && info.sender != ctx.config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // 检查是否仍在宽限期内
+    if env.block.height >= pending.restore_until_height {
+        return Err(ContractError::BurnGracePeriodExpired {});
+    }
+
+    // 按铸造流程恢复元数据、所有权、索引和总供应量
+    PENDING_BURNS.remove(deps.storage, token_id);
+    let new_supply = store_minted_token(deps.storage, &env, token_id, &pending.owner, &pending.meta)?;
+
+    // 记录到活动流
+    record_activity(deps.storage, &env, "restore", Some(token_id), &info.sender)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "restore_burned")
+        .add_attribute("token_id", token_id.to_string())
+        .add_attribute("owner", pending.owner.to_string())
+        .add_event(emit_restore_event(token_id, &pending.owner, new_supply)))
+}
+
+/// 完成待销毁 NFT 的销毁（无需权限）
+/// 
+/// 宽限期结束后任何人都可以调用，清除待销毁记录并发出销毁事件
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于检查宽限期
+/// - `token_id`: 要完成销毁的 NFT ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 销毁结果
+#[cfg(feature = "cosmwasm")]
+pub fn execute_finalize_burn(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    token_id: u64,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    // 加载待销毁记录
+    let pending = PENDING_BURNS.may_load(deps.storage, token_id)?
+        .ok_or(ContractError::PendingBurnNotFound {})?;
+
+    // 宽限期结束前不能完成销毁
+    if env.block.height < pending.restore_until_height {
+        return Err(ContractError::BurnGracePeriodActive {});
+    }
+
+    PENDING_BURNS.remove(deps.storage, token_id);
+
+    // 宽限期结束，销毁成为最终结果，通知已注册的销毁监听合约
+    let notifications = build_burn_notifications(deps.storage, token_id, &pending.owner)?;
+    // 供应量在进入宽限期时已扣减
+    let total_supply = TOTAL_SUPPLY.load(deps.storage)?;
+
+    Ok(Response::new()
+        .add_submessages(notifications)
+        .add_attribute("action", "burn")
+        .add_attribute("token_id", token_id.to_string())
+        .add_attribute("owner", pending.owner.to_string())
+        .add_event(emit_burn_event(token_id, &pending.owner, total_supply)))
+}
+
+/// 回收已过期的 NFT
+/// 
+/// 任何人都可以销毁已过过期时间的 NFT（如过期的兑换券），
+/// 不进入销毁宽限期，并通知已注册的销毁监听合约
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于检查过期时间
+/// - `token_id`: 要回收的 NFT ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 回收结果
+#[cfg(feature = "cosmwasm")]
+pub fn execute_expire(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    token_id: u64,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    // 验证 NFT 是否存在
+    let meta = TOKEN_META.may_load(deps.storage, token_id)?
+        .ok_or(ContractError::TokenNotFound {})?;
+
+    // 只能回收已过期的 NFT
+    if !meta.is_expired(env.block.time.seconds()) {
+        return Err(ContractError::TokenNotExpired {});
+    }
+
+    let owner = TOKEN_OWNERSHIP.load(deps.storage, token_id)?;
+    let new_supply = remove_token_state(deps.storage, env.block.height, token_id, &owner, &meta)?;
+    record_activity(deps.storage, &env, "expire", Some(token_id), &owner)?;
+
+    // 通知已注册的销毁监听合约
+    let notifications = build_burn_notifications(deps.storage, token_id, &owner)?;
+
+    Ok(Response::new()
+        .add_submessages(notifications)
+        .add_attribute("action", "expire")
+        .add_attribute("token_id", token_id.to_string())
+        .add_attribute("owner", owner.to_string())
+        .add_event(emit_burn_event(token_id, &owner, new_supply)))
+}
+
+/// 销毁调用者持有的指定类型 NFT
+/// 
+/// 一次销毁最多 `max` 个调用者持有的指定类型 NFT（单次上限 100），
+/// 便于清理大量普通 NFT。启用宽限期时这些 NFT 进入待销毁状态
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于计算宽限期
+/// - `info`: 消息信息，包含发送者
+/// - `kind`: 要销毁的 NFT 类型
+/// - `max`: 最多销毁的数量
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 销毁结果
+#[cfg(feature = "cosmwasm")]
+pub fn execute_burn_all_of_kind(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    kind: NftKind,
+    max: u32,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    // 高价值类型须逐个通过销毁申请
+    ensure_burn_request_not_required(deps.storage, &kind)?;
+
+    let max = max.min(MAX_BURN_ALL_OF_KIND) as usize;
+
+    // 先收集要销毁的 NFT，避免在遍历所有者索引时修改它
+    let owned = TOKENS_BY_OWNER
+        .may_load(deps.storage, info.sender.clone())?
+        .unwrap_or_default();
+    let mut targets = vec![];
+    for token_id in owned {
+        if targets.len() >= max {
+            break;
+        }
+        let meta = TOKEN_META.load(deps.storage, token_id)?;
+        // 跳过对局中锁定的 NFT
+        if meta.kind == kind && active_session_lock(deps.storage, &env, token_id)?.is_none() {
+            targets.push((token_id, meta));
+        }
+    }
+
+    let grace_period = BURN_GRACE_PERIOD.may_load(deps.storage)?.unwrap_or(0);
+    let restore_until_height = env.block.height.checked_add(grace_period)
+        .ok_or(ContractError::Overflow {})?;
+
+    let mut response = Response::new();
+    for (token_id, meta) in &targets {
+        let new_supply = remove_token_state(deps.storage, env.block.height, *token_id, &info.sender, meta)?;
+
+        if grace_period > 0 {
+            // 启用宽限期时进入待销毁状态
+            PENDING_BURNS.save(deps.storage, *token_id, &PendingBurn {
+                owner: info.sender.clone(),
+                meta: meta.clone(),
+                restore_until_height,
+            })?;
+        } else {
+            // 立即销毁，通知已注册的销毁监听合约
+            response = response
+                .add_submessages(build_burn_notifications(deps.storage, *token_id, &info.sender)?)
+                .add_event(emit_burn_event(*token_id, &info.sender, new_supply));
+        }
+    }
+
+    // 记录到活动流（整批记录为一条）
+    if !targets.is_empty() {
+        record_activity(deps.storage, &env, "burn_all_of_kind", None, &info.sender)?;
+    }
+
+    Ok(response
+        .add_attribute("action", "burn_all_of_kind")
+        .add_attribute("kind", kind.to_key())
+        .add_attribute("owner", info.sender.to_string())
+        .add_attribute("count", targets.len().to_string())
+        .add_attribute("pending", (grace_period > 0).to_string()))
+}
+
+/// 删除 NFT 的全部状态
+/// 
+/// 删除元数据、所有权、批准信息、托管记录和各类索引，并更新系列现存数量和总供应量，
+/// 返回更新后的总供应量
+#[cfg(feature = "cosmwasm")]
+fn remove_token_state(
+    storage: &mut dyn cosmwasm_std::Storage,
+    height: u64,
+    token_id: u64,
+    owner: &cosmwasm_std::Addr,
+    meta: &NftMeta,
+) -> Result<u64, ContractError> {
+    // 删除 NFT 元数据、所有权、取得时间、托管标记和销毁申请
+    TOKEN_META.remove(storage, token_id);
+    TOKEN_OWNERSHIP.remove(storage, token_id);
+    TOKEN_ACQUIRED.remove(storage, token_id);
+    BURN_REQUESTS.remove(storage, token_id);
+    crate::helpers::clear_custody_tag(storage, token_id)?;
+
+    // 托管中的礼物随 NFT 一同删除，避免同一 ID 重新铸造后被旧礼物记录引用
+    if let Some(claim_hash) = GIFTED_TOKENS.may_load(storage, token_id)? {
+        GIFTED_TOKENS.remove(storage, token_id);
+        GIFTS.remove(storage, claim_hash);
+    }
+
+    // 从所在的组合包中移出
+    crate::bundles::remove_from_bundle(storage, token_id)?;
+
+    // 条件转移随 NFT 一同删除，接收者不能再领取同一 ID 的 NFT
+    CONDITIONAL_TRANSFERS.remove(storage, token_id);
     
     // 清理销毁前的批准信息（安全措施）
-    crate::helpers::clear_token_approvals(deps.storage, token_id)?;
+    crate::helpers::clear_token_approvals(storage, token_id, owner)?;
     
-    // 从所有者索引中移除
-    crate::helpers::remove_token_from_owner(deps.storage, &owner, token_id)?;
+    // 从所有者索引和二级索引中移除
+    crate::helpers::remove_token_from_owner(storage, owner, token_id)?;
+    unindex_token(storage, token_id, owner, meta, height)?;
     
     // 从全局索引中移除
-    ALL_TOKENS.remove(deps.storage, token_id);
+    ALL_TOKENS.remove(storage, token_id);
+    
+    // 更新系列现存数量
+    decrease_series_token_count(storage, &meta.series_id)?;
     
     // 更新总供应量（使用 checked_sub 防止下溢）
-    let total_supply = TOTAL_SUPPLY.load(deps.storage)?;
+    let total_supply = TOTAL_SUPPLY.load(storage)?;
     let new_supply = total_supply.checked_sub(1)
         .ok_or(ContractError::Overflow {})?;
-    TOTAL_SUPPLY.save(deps.storage, &new_supply)?;
+    TOTAL_SUPPLY.save(storage, &new_supply)?;
     
-    Ok(Response::new()
-        .add_attribute("action", "burn")
-        .add_attribute("token_id", token_id.to_string())
-        .add_attribute("owner", owner.to_string())
-        .add_event(emit_burn_event(token_id, &owner)))
+    Ok(new_supply)
 }
 
 /// 合成 NFT
@@ -195,86 +621,163 @@ pub fn execute_burn(
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
 /// - `env`: 环境信息，包含区块高度和时间
 /// - `info`: 消息信息，包含发送者
 /// - `inputs`: 输入 NFT ID 列表
 /// - `target`: 目标 NFT 类型
+/// - `partner_inputs`: 已存入的合作集合 NFT，按映射类型抵扣配方输入
 /// 
 /// # 返回值
 /// - `Result<Response, ContractError>`: 合成结果
 #[cfg(feature = "cosmwasm")]
 pub fn execute_synthesize(
-    deps: DepsMut,
+    mut deps: DepsMut,
+    ctx: &ExecuteContext,
     env: Env,
     info: MessageInfo,
     inputs: Vec<u64>,
     target: NftKind,
+    partner_inputs: Vec<PartnerInput>,
 ) -> Result<Response, ContractError> {
     // 检查合约是否暂停
-    check_contract_paused(deps.storage)?;
+    ctx.ensure_not_paused()?;
     
     // 检查输入数量限制
-    if inputs.len() > MAX_SYNTHESIS_INPUTS {
-        return Err(ContractError::TooManyInputs { count: inputs.len() });
+    if inputs.len() + partner_inputs.len() > MAX_SYNTHESIS_INPUTS {
+        return Err(ContractError::TooManyInputs { count: inputs.len() + partner_inputs.len() });
     }
     
-    // 获取合成配方
-    let recipe = RECIPES.load(deps.storage, target.to_key())
-        .map_err(|_| ContractError::RecipeNotFound {})?;
+    // 获取合成配方（设置了共享注册表时优先使用注册表的配方）和销毁税
+    let recipe = resolve_recipe(deps.branch(), &env, &target)?
+        .ok_or(ContractError::RecipeNotFound {})?;
+    let burn_tax = BURN_TAXES.may_load(deps.storage, target.to_key())?;
 
-    // 验证输入 NFT 的所有权和有效性
-    validate_synthesis_inputs(deps.as_ref(), &info.sender, &inputs, &recipe)?;
+    // 检查目标类型是否允许通过合成产生
+    ensure_kind_mintable(deps.storage, &target)?;
 
-    // 生成新的 token ID（使用独立计数器确保唯一性）
-    let next_token_id = NEXT_TOKEN_ID.load(deps.storage)?;
-    let output_token_id = next_token_id;
-    NEXT_TOKEN_ID.save(deps.storage, &(next_token_id.checked_add(1).ok_or(ContractError::Overflow {})?))?;
+    // 合作集合输入须由发送者存入，按映射类型抵扣配方要求
+    let partner_kinds = validate_partner_inputs(deps.as_ref(), &info.sender, &partner_inputs)?;
+    let required = partner_adjusted_recipe(&taxed_recipe(&recipe, burn_tax.as_ref()), &partner_kinds)?;
 
-    // 创建输出 NFT 的元数据
-    let output_meta = NftMeta {
-        kind: target.clone(),
-        scale_origin: Scale::Tiny, // 合成获得的 NFT 使用默认规模
-        physical_sku: None,
-        crafted_from: Some(inputs.clone()), // 记录合成来源
-        series_id: alloc::format!("synthesis_{}", env.block.time.seconds()),
-        collection_group_id: None,
-        serial_in_series: 1,
+    // 验证输入 NFT 的所有权（或按类型授权）和有效性，并取得输入所有者和元数据；
+    // 销毁税要求额外输入时按调整后的配方校验
+    let (owner, input_metas) = validate_synthesis_inputs(
+        deps.as_ref(), &env, &info.sender, &inputs, &required,
+    )?;
+
+    // 使用合作集合输入时只能由所有者本人合成，产出不会归属于存入者以外的地址
+    if !partner_inputs.is_empty() && owner != info.sender {
+        return Err(ContractError::InvalidPartnerNft { reason: "partner inputs require the owner to synthesize".to_string() });
+    }
+
+    // 检查并扣减所有者在当前窗口内的合成价值额度
+    consume_crafting_allowance(deps.storage, &env, &owner, target.exchange_value() as u64)?;
+
+    // 确定合成费用：设置了美元费用时按喂价换算为原生代币
+    let cost = resolve_recipe_fee(deps.storage, &deps.querier, &env, &target, &recipe)?;
+
+    // 收取合成费用：附带资金先记入存款，再从存款中扣除配方费用
+    charge_fee(deps.storage, &info.sender, &info.funds, cost.as_ref())?;
+    if let Some(cost) = &cost {
+        record_fee_collected(deps.storage, fee_source::SYNTHESIS, cost)?;
+    }
+    let fee = cost.as_ref().map(|cost| cost.to_string()).unwrap_or_default();
+
+    // 收取销毁税：原生代币发送至 sink 或直接销毁，额外输入随其他输入一起销毁
+    let (burn_tax_msg, burn_tax) = match &burn_tax {
+        Some(tax) => collect_burn_tax(deps.storage, &info.sender, &recipe, tax)?,
+        None => (None, String::new()),
     };
 
+    // 收取合成版税：目标达到稀有度门槛时按输入分给各系列创建者
+    let (royalty_msgs, crafting_royalty) = collect_crafting_royalty(deps.storage, &info.sender, &target, &input_metas)?;
+
     // ========== 本地 CW721 模式 ==========
     // 直接完成合成操作，无需外部合约交互
     
     // 删除输入 NFT 的本地元数据
-    for token_id in &inputs {
+    let mut notifications = vec![];
+    for (token_id, input_meta) in inputs.iter().zip(&input_metas) {
+        notifications.extend(build_burn_notifications(deps.storage, *token_id, &owner)?);
+        decrease_series_token_count(deps.storage, &input_meta.series_id)?;
         TOKEN_META.remove(deps.storage, *token_id);
         TOKEN_OWNERSHIP.remove(deps.storage, *token_id);
+        TOKEN_ACQUIRED.remove(deps.storage, *token_id);
+        BURN_REQUESTS.remove(deps.storage, *token_id);
+        crate::helpers::clear_custody_tag(deps.storage, *token_id)?;
         
         // 清理销毁前的批准信息（安全措施）
-        crate::helpers::clear_token_approvals(deps.storage, *token_id)?;
+        crate::helpers::clear_token_approvals(deps.storage, *token_id, &owner)?;
         
-        // 从所有者索引中移除
-        crate::helpers::remove_token_from_owner(deps.storage, &info.sender, *token_id)?;
+        // 从所有者索引和二级索引中移除
+        crate::helpers::remove_token_from_owner(deps.storage, &owner, *token_id)?;
+        unindex_token(deps.storage, *token_id, &owner, input_meta, env.block.height)?;
         
         // 从全局索引中移除
         ALL_TOKENS.remove(deps.storage, *token_id);
     }
     
+    // 消耗合作集合输入，在外部集合中销毁
+    let partner_msgs = consume_partner_inputs(deps.storage, &partner_inputs)?;
+    
+    // 配方指定了产出集合时，由关联集合铸造产出，合成历史在回复中记录
+    if let Some(collection) = RECIPE_OUTPUT_COLLECTIONS.may_load(deps.storage, target.to_key())? {
+        let total_supply = TOTAL_SUPPLY.load(deps.storage)?;
+        let new_total_supply = total_supply.checked_sub(inputs.len() as u64)
+            .ok_or(ContractError::Overflow {})?;
+        TOTAL_SUPPLY.save(deps.storage, &new_total_supply)?;
+        record_activity(deps.storage, &env, "synthesize", None, &info.sender)?;
+        let remote_mint = remote_synthesis_output(deps.branch(), &env, &collection, &owner, &target, &inputs)?;
+
+        return Ok(Response::new()
+            .add_submessages(notifications)
+            .add_messages(burn_tax_msg)
+            .add_messages(royalty_msgs)
+            .add_messages(partner_msgs)
+            .add_submessage(remote_mint)
+            .add_attribute("action", "synthesize")
+            .add_attribute("output_collection", collection.to_string())
+            .add_attribute("target", alloc::format!("{:?}", target))
+            .add_attribute("inputs_count", inputs.len().to_string())
+            .add_attribute("partner_inputs_count", partner_inputs.len().to_string())
+            .add_attribute("fee", fee)
+            .add_attribute("burn_tax", burn_tax)
+            .add_attribute("crafting_royalty", crafting_royalty));
+    }
+
+    // 创建输出 NFT 的元数据（同一区块内的多次合成共用系列，由计数器分配序号）
+    let series_id = synthesis_series_id(&env);
+    let serial_in_series = assign_series_serial(deps.storage, &series_id)?;
+
+    // 生成新的 token ID（使用独立计数器确保唯一性，跳过保留区间；确定性模式下按系列和序号推导）
+    let output_token_id = allocate_token_id(deps.storage, &series_id, serial_in_series)?;
+    let output_meta = NftMeta {
+        kind: target.clone(),
+        scale_origin: Scale::Tiny, // 合成获得的 NFT 使用默认规模
+        physical_sku: None,
+        crafted_from: Some(inputs.clone()), // 记录合成来源
+        series_id,
+        collection_group_id: None,
+        serial_in_series,
+        expires_at: None,
+    };
+
     // 铸造输出 NFT
     TOKEN_META.save(deps.storage, output_token_id, &output_meta)?;
-    TOKEN_OWNERSHIP.save(deps.storage, output_token_id, &info.sender)?;
+    TOKEN_OWNERSHIP.save(deps.storage, output_token_id, &owner)?;
+    record_acquisition(deps.storage, &env, output_token_id)?;
     
-    // 更新所有者索引和全局索引
-    crate::helpers::add_token_to_owner(deps.storage, &info.sender, output_token_id)?;
+    // 更新所有者索引、二级索引和全局索引
+    crate::helpers::add_token_to_owner(deps.storage, &owner, output_token_id)?;
+    index_token(deps.storage, output_token_id, &owner, &output_meta, env.block.height)?;
     ALL_TOKENS.save(deps.storage, output_token_id, &())?;
     
-    // 更新系列序号（使用 checked_add 防止溢出）
-    let next_serial = SERIES_NEXT_SERIAL.may_load(deps.storage, output_meta.series_id.clone())?.unwrap_or(0);
-    let new_serial = next_serial.checked_add(1)
-        .ok_or(ContractError::Overflow {})?;
-    SERIES_NEXT_SERIAL.save(deps.storage, output_meta.series_id.clone(), &new_serial)?;
+    increase_series_token_count(deps.storage, &output_meta.series_id, 1)?;
     
     // 更新总供应量（输出 +1，输入 -inputs.len()）
     // 注意：TOTAL_SUPPLY只表示当前存在的NFT数量，不用于ID生成
+    let total_supply = TOTAL_SUPPLY.load(deps.storage)?;
     let new_total_supply = total_supply.checked_add(1)
         .and_then(|supply| supply.checked_sub(inputs.len() as u64))
         .ok_or(ContractError::Overflow {})?;
@@ -282,19 +785,51 @@ pub fn execute_synthesize(
 
     // 记录合成历史
     let synthesis_record = SynthesisRecord {
-        user: info.sender.clone(),
+        user: owner.clone(),
         inputs: inputs.clone(),
         output: output_token_id,
         timestamp: env.block.time.seconds(),
+        output_collection: None,
     };
-    SYNTHESIS_HISTORY.save(deps.storage, (info.sender.clone(), env.block.time.seconds()), &synthesis_record)?;
+    let history_key = (owner.clone(), env.block.time.seconds());
+    let existed = SYNTHESIS_HISTORY.has(deps.storage, history_key.clone());
+    SYNTHESIS_HISTORY.save(deps.storage, history_key, &synthesis_record)?;
+    track_storage_entry(deps.storage, storage_counter::SYNTHESIS_HISTORY, existed, true)?;
+
+    // 记录到活动流
+    record_activity(deps.storage, &env, "synthesize", Some(output_token_id), &info.sender)?;
+
+    // 合成结果写入 Response.data，供代用户调用的合约在 reply 中直接解析
+    let data = to_json_binary(&SynthesizeData {
+        output_token_id,
+        owner: owner.to_string(),
+        target: target.clone(),
+        burned_inputs: inputs.clone(),
+        output_collection: None,
+    })?;
+
+    // 事件详细程度为 Verbose 时附带输入列表
+    let mut synthesize_event = emit_synthesize_event(output_token_id, &alloc::format!("{:?}", target), inputs.len(), &owner, new_total_supply);
+    if verbose_events(deps.storage)? {
+        let input_ids = inputs.iter().map(|token_id| token_id.to_string()).collect::<Vec<_>>().join(",");
+        synthesize_event = synthesize_event.add_attribute(event_attributes::INPUTS, input_ids);
+    }
 
     Ok(Response::new()
+        .set_data(data)
+        .add_submessages(notifications)
+        .add_messages(burn_tax_msg)
+        .add_messages(royalty_msgs)
+        .add_messages(partner_msgs)
         .add_attribute("action", "synthesize")
         .add_attribute("output_token_id", output_token_id.to_string())
         .add_attribute("target", alloc::format!("{:?}", target))
         .add_attribute("inputs_count", inputs.len().to_string())
-        .add_event(emit_synthesize_event(output_token_id, &alloc::format!("{:?}", target), inputs.len(), &info.sender)))
+        .add_attribute("partner_inputs_count", partner_inputs.len().to_string())
+        .add_attribute("fee", fee)
+        .add_attribute("burn_tax", burn_tax)
+        .add_attribute("crafting_royalty", crafting_royalty)
+        .add_event(synthesize_event))
 }
 
 /// 设置合成配方
@@ -303,6 +838,7 @@ pub fn execute_synthesize(
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
 /// - `info`: 消息信息，包含发送者
 /// - `target`: 目标 NFT 类型
 /// - `recipe`: 合成配方
@@ -312,21 +848,21 @@ pub fn execute_synthesize(
 #[cfg(feature = "cosmwasm")]
 pub fn execute_set_recipe(
     deps: DepsMut,
+    ctx: &ExecuteContext,
     info: MessageInfo,
     target: NftKind,
     recipe: Recipe,
 ) -> Result<Response, ContractError> {
     // 检查合约是否暂停
-    check_contract_paused(deps.storage)?;
+    ctx.ensure_not_paused()?;
     
     // 验证所有者权限
-    let config = crate::state::CONFIG.load(deps.storage)?;
-    if config.owner != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+    ctx.ensure_owner(&info.sender)?;
 
     // 保存合成配方
+    let existed = RECIPES.has(deps.storage, target.to_key());
     RECIPES.save(deps.storage, target.to_key(), &recipe)?;
+    track_storage_entry(deps.storage, storage_counter::RECIPES, existed, true)?;
 
     Ok(Response::new()
         .add_attribute("action", "set_recipe")
@@ -339,6 +875,7 @@ pub fn execute_set_recipe(
 /// 
 /// # 参数
 /// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
 /// - `info`: 消息信息，包含发送者
 /// - `target`: 目标 NFT 类型
 /// 
@@ -346,20 +883,20 @@ pub fn execute_set_recipe(
 /// - `Result<Response, ContractError>`: 删除结果
 pub fn execute_remove_recipe(
     deps: DepsMut,
+    ctx: &ExecuteContext,
     info: MessageInfo,
     target: NftKind,
 ) -> Result<Response, ContractError> {
     // 检查合约是否暂停
-    check_contract_paused(deps.storage)?;
+    ctx.ensure_not_paused()?;
     
     // 验证所有者权限
-    let config = crate::state::CONFIG.load(deps.storage)?;
-    if config.owner != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+    ctx.ensure_owner(&info.sender)?;
 
     // 删除合成配方
+    let existed = RECIPES.has(deps.storage, target.to_key());
     RECIPES.remove(deps.storage, target.to_key());
+    track_storage_entry(deps.storage, storage_counter::RECIPES, existed, false)?;
 
     Ok(Response::new()
         .add_attribute("action", "remove_recipe")
@@ -370,16 +907,24 @@ pub fn execute_remove_recipe(
 #[cfg(feature = "cosmwasm")]
 pub fn execute_batch_mint(
     deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
     info: MessageInfo,
     mints: Vec<BatchMintItem>,
+    idempotency_key: Option<String>,
 ) -> Result<Response, ContractError> {
     // 检查合约是否暂停
-    check_contract_paused(deps.storage)?;
-    
-    let config = crate::state::CONFIG.load(deps.storage)?;
+    ctx.ensure_not_paused()?;
+
+    // 重复提交的幂等键直接返回首次铸造的结果
+    if let Some(response) = replay_mint(deps.storage, &info.sender, idempotency_key.as_deref(), "batch_mint")? {
+        return Ok(response);
+    }
     
-    // 检查铸造权限
-    if !is_authorized_minter(deps.as_ref(), &info.sender, &config)? {
+    // 检查铸造权限：合约所有者可以铸造保留区间内的 ID，逐条权限在下方校验
+    let is_owner = info.sender == ctx.config.owner;
+    let is_minter = is_authorized_minter(deps.as_ref(), &info.sender, &ctx.config)?;
+    if !is_owner && !is_minter {
         return Err(ContractError::MinterNotAuthorized {});
     }
 
@@ -389,93 +934,242 @@ pub fn execute_batch_mint(
     }
 
     let mint_count = mints.len();
-    let mut total_supply = TOTAL_SUPPLY.load(deps.storage)?;
     let mut response = Response::new()
         .add_attribute("action", "batch_mint")
         .add_attribute("count", mint_count.to_string());
 
-    // 预先检查重复的token_id
+    // 确定性 ID 模式下按各系列即将分配的序号依次推导 ID
+    let deterministic = deterministic_ids_enabled(deps.storage)?;
+    let mut mints = mints;
+    if deterministic {
+        let serials = batch_mint_serials(deps.storage, &mints)?;
+        for (index, (mint_item, serial)) in mints.iter_mut().zip(serials).enumerate() {
+            mint_item.token_id = resolve_mint_token_id(deps.storage, mint_item.token_id, &mint_item.extension.series_id, serial)
+                .map_err(|err| err.at_batch_item(index, mint_item.token_id))?;
+        }
+    }
+
+    // 预先检查重复的token_id，同时完成全部校验，避免部分写入；错误附带出错条目的位置
+    let reserved = RESERVED_ID_RANGE.may_load(deps.storage)?;
     let mut token_ids = alloc::collections::BTreeSet::new();
-    for mint_item in &mints {
+    let mut owner_addrs = Vec::with_capacity(mint_count);
+    for (index, mint_item) in mints.iter().enumerate() {
         if !token_ids.insert(mint_item.token_id) {
-            return Err(ContractError::TokenAlreadyExists {});
-        }
-        if TOKEN_META.has(deps.storage, mint_item.token_id) {
-            return Err(ContractError::TokenAlreadyExists {});
+            return Err(ContractError::TokenAlreadyExists {}.at_batch_item(index, mint_item.token_id));
         }
+        check_mint_id_permission(reserved.as_ref(), mint_item.token_id, is_owner, is_minter)
+            .map_err(|err| err.at_batch_item(index, mint_item.token_id))?;
+        let owner_addr = validate_batch_mint_item(deps.storage, deps.api, &env, &info.sender, mint_item)
+            .map_err(|err| err.at_batch_item(index, mint_item.token_id))?;
+        owner_addrs.push(owner_addr);
     }
 
-    for mint_item in mints {
-        // 校验所有者地址
-        let owner_addr = deps.api.addr_validate(&mint_item.owner)?;
-        
-        // 验证系列ID格式
-        validate_series_id(&mint_item.extension.series_id)?;
-        
-        // 验证集合组ID格式（如果提供）
-        if let Some(ref group_id) = mint_item.extension.collection_group_id {
-            validate_collection_group_id(group_id)?;
-        }
-        
-        // 更新NEXT_TOKEN_ID计数器，确保后续生成的ID不会冲突
-        let current_next_id = NEXT_TOKEN_ID.load(deps.storage)?;
-        if mint_item.token_id >= current_next_id {
-            NEXT_TOKEN_ID.save(deps.storage, &(mint_item.token_id.checked_add(1).ok_or(ContractError::Overflow {})?))?;
+    // 一次性计算计数器增量：最大 token ID 与各系列的铸造数量，并读取各系列当前的序号
+    let max_token_id = token_ids.iter().next_back().copied();
+    let mut series_counts: alloc::collections::BTreeMap<String, u64> = alloc::collections::BTreeMap::new();
+    let mut series_serials: alloc::collections::BTreeMap<String, u64> = alloc::collections::BTreeMap::new();
+    for mint_item in &mints {
+        let series_id = &mint_item.extension.series_id;
+        *series_counts.entry(series_id.clone()).or_insert(0) += 1;
+        if !series_serials.contains_key(series_id) {
+            let serial = SERIES_NEXT_SERIAL.may_load(deps.storage, series_id.clone())?.unwrap_or(0);
+            series_serials.insert(series_id.clone(), serial);
         }
-        
+    }
+
+    // 预先计算总供应量（仅加一次本批数量，使用checked_add），逐个铸造事件附带当时的供应量
+    let total_supply = TOTAL_SUPPLY.load(deps.storage)?;
+    let verbose = verbose_events(deps.storage)?;
+    let new_total_supply = total_supply.checked_add(mint_count as u64)
+        .ok_or(ContractError::Overflow {})?;
+
+    for (index, (mut mint_item, owner_addr)) in mints.into_iter().zip(owner_addrs).enumerate() {
+        // 按系列依次分配序号
+        let serial = series_serials.entry(mint_item.extension.series_id.clone()).or_insert(0);
+        *serial = serial.checked_add(1).ok_or(ContractError::Overflow {})?;
+        mint_item.extension.serial_in_series = *serial;
+
         // 保存元数据
         TOKEN_META.save(deps.storage, mint_item.token_id, &mint_item.extension)?;
         
         // 设置所有权
         TOKEN_OWNERSHIP.save(deps.storage, mint_item.token_id, &owner_addr)?;
+        record_acquisition(deps.storage, &env, mint_item.token_id)?;
         
-        // 更新所有者索引
+        // 更新所有者索引和二级索引
         add_token_to_owner(deps.storage, &owner_addr, mint_item.token_id)?;
+        index_token(deps.storage, mint_item.token_id, &owner_addr, &mint_item.extension, env.block.height)?;
         
         // 添加到全局索引
         ALL_TOKENS.save(deps.storage, mint_item.token_id, &())?;
         
-        // 更新系列序号（使用checked_add）
-        let next_serial = SERIES_NEXT_SERIAL.may_load(deps.storage, mint_item.extension.series_id.clone())?.unwrap_or(0);
-        let new_serial = next_serial.checked_add(1)
-            .ok_or(ContractError::Overflow {})?;
-        SERIES_NEXT_SERIAL.save(deps.storage, mint_item.extension.series_id.clone(), &new_serial)?;
-        
-        // 发出mint事件
-        response = response.add_event(emit_mint_event(
-            mint_item.token_id, 
-            &mint_item.owner, 
-            &alloc::format!("{:?}", mint_item.extension.kind)
-        ));
-        
-        total_supply += 1;
+        // 发出mint事件（事件详细程度为 Minimal 时只发出批量铸造事件）
+        if verbose {
+            response = response.add_event(emit_mint_event(
+                mint_item.token_id, 
+                &mint_item.owner, 
+                &alloc::format!("{:?}", mint_item.extension.kind),
+                total_supply + index as u64 + 1,
+            ));
+        }
     }
 
-    // 更新总供应量（使用checked_add）
-    let new_total_supply = total_supply.checked_add(mint_count as u64)
-        .ok_or(ContractError::Overflow {})?;
+    // 更新NEXT_TOKEN_ID计数器（仅写入一次），确保后续生成的ID不会冲突（推导出的 ID 不占用计数器）
+    if let Some(max_token_id) = max_token_id.filter(|_| !deterministic) {
+        let current_next_id = NEXT_TOKEN_ID.load(deps.storage)?;
+        if max_token_id >= current_next_id {
+            NEXT_TOKEN_ID.save(deps.storage, &(max_token_id.checked_add(1).ok_or(ContractError::Overflow {})?))?;
+        }
+    }
+
+    // 更新系列序号（每个系列仅写入一次）
+    for (series_id, serial) in series_serials {
+        SERIES_NEXT_SERIAL.save(deps.storage, series_id, &serial)?;
+    }
+    for (series_id, count) in series_counts {
+        increase_series_token_count(deps.storage, &series_id, count)?;
+    }
+
+    // 更新总供应量
     TOTAL_SUPPLY.save(deps.storage, &new_total_supply)?;
+
+    // 记录到活动流（整批记录为一条）
+    record_activity(deps.storage, &env, "batch_mint", None, &info.sender)?;
+    record_minter_stats(deps.storage, &env, &info.sender, mint_count as u64)?;
+    record_mint_receipt(deps.storage, &info.sender, idempotency_key, &MintReceipt {
+        token_ids: token_ids.into_iter().collect(),
+        height: env.block.height,
+        action: "batch_mint".to_string(),
+        owner: None,
+        kind: None,
+        total_supply: new_total_supply,
+    })?;
     
     // 发出批量铸造事件
-    response = response.add_event(emit_batch_mint_event(mint_count, &info.sender));
+    response = response.add_event(emit_batch_mint_event(mint_count, &info.sender, new_total_supply));
     
     Ok(response)
 }
 
+/// 计算批量铸造中各条目将分配的系列内序号
+/// 
+/// 同一系列的条目按出现顺序依次取下一个序号
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `mints`: 铸造条目
+/// 
+/// # 返回值
+/// - `Result<Vec<u64>, ContractError>`: 与输入顺序一致的序号
+#[cfg(feature = "cosmwasm")]
+fn batch_mint_serials(storage: &dyn cosmwasm_std::Storage, mints: &[BatchMintItem]) -> Result<Vec<u64>, ContractError> {
+    let mut next_serials: alloc::collections::BTreeMap<&str, u64> = alloc::collections::BTreeMap::new();
+    let mut serials = Vec::with_capacity(mints.len());
+    for mint_item in mints {
+        let series_id = mint_item.extension.series_id.as_str();
+        let serial = match next_serials.get(series_id) {
+            Some(serial) => serial.checked_add(1).ok_or(ContractError::Overflow {})?,
+            None => next_series_serial(storage, series_id)?,
+        };
+        next_serials.insert(series_id, serial);
+        serials.push(serial);
+    }
+    Ok(serials)
+}
+
+/// 校验批量铸造中的单个条目
+/// 
+/// # 参数
+/// - `storage`: 存储接口（认领未注册系列时写入）
+/// - `api`: 地址校验接口
+/// - `env`: 环境信息，用于检查过期时间
+/// - `minter`: 执行批量铸造的地址
+/// - `mint_item`: 铸造条目
+/// 
+/// # 返回值
+/// - `Result<Addr, ContractError>`: 校验通过的所有者地址
+#[cfg(feature = "cosmwasm")]
+fn validate_batch_mint_item(
+    storage: &mut dyn cosmwasm_std::Storage,
+    api: &dyn cosmwasm_std::Api,
+    env: &Env,
+    minter: &cosmwasm_std::Addr,
+    mint_item: &BatchMintItem,
+) -> Result<cosmwasm_std::Addr, ContractError> {
+    let owner_addr = check_mint_item(storage, api, env, mint_item.token_id, &mint_item.owner, &mint_item.extension)?;
+
+    // 检查对该系列的铸造权限
+    authorize_series_mint(storage, &mint_item.extension.series_id, minter)?;
+
+    Ok(owner_addr)
+}
+
+/// 只读校验单个铸造条目
+/// 
+/// 包含与铸造者无关的全部校验：ID 占用、所有者地址、类型铸造状态、系列ID格式、
+/// 过期时间和集合组ID格式。铸造权限和系列权限依赖发送者，由调用方另行校验。
+/// 铸造执行和铸造预检查询共用，保证两者结果一致
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `api`: 地址校验接口
+/// - `env`: 环境信息，用于检查过期时间
+/// - `token_id`: 要铸造的 NFT ID
+/// - `owner`: 所有者地址
+/// - `extension`: NFT 的扩展元数据
+/// 
+/// # 返回值
+/// - `Result<Addr, ContractError>`: 校验通过的所有者地址
+#[cfg(feature = "cosmwasm")]
+fn check_mint_item(
+    storage: &dyn cosmwasm_std::Storage,
+    api: &dyn cosmwasm_std::Api,
+    env: &Env,
+    token_id: u64,
+    owner: &str,
+    extension: &NftMeta,
+) -> Result<cosmwasm_std::Addr, ContractError> {
+    // 检查 NFT 是否已存在（包括宽限期内待销毁的 NFT）
+    if TOKEN_META.has(storage, token_id) || PENDING_BURNS.has(storage, token_id) {
+        return Err(ContractError::TokenAlreadyExists {});
+    }
+
+    // 校验所有者地址，并拒绝被禁止的接收地址（可铸造到合约自身作为空投库存）
+    let owner_addr = api.addr_validate(owner)?;
+    validate_mint_owner(storage, &env.contract.address, &owner_addr)?;
+
+    // 检查该类型是否允许铸造
+    ensure_kind_mintable(storage, &extension.kind)?;
+
+    // 验证系列ID格式
+    validate_series_id(&extension.series_id)?;
+
+    // 不能铸造已过期的 NFT
+    if extension.is_expired(env.block.time.seconds()) {
+        return Err(ContractError::TokenExpired {});
+    }
+
+    // 验证集合组ID格式（如果提供）
+    if let Some(ref group_id) = extension.collection_group_id {
+        validate_collection_group_id(group_id)?;
+    }
+
+    Ok(owner_addr)
+}
+
 /// 设置铸造者权限
 pub fn execute_set_minter(
     deps: DepsMut,
+    ctx: &ExecuteContext,
     info: MessageInfo,
     minter: String,
     allowed: bool,
 ) -> Result<Response, ContractError> {
     // 检查合约是否暂停
-    check_contract_paused(deps.storage)?;
+    ctx.ensure_not_paused()?;
     
-    let config = crate::state::CONFIG.load(deps.storage)?;
-    if config.owner != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+    ctx.ensure_owner(&info.sender)?;
+    crate::helpers::ensure_minter_set_unlocked(deps.storage)?;
 
     let minter_addr = deps.api.addr_validate(&minter)?;
     crate::state::ALLOWED_MINTERS.save(deps.storage, minter_addr, &allowed)?;
@@ -486,92 +1180,412 @@ pub fn execute_set_minter(
         .add_attribute("allowed", allowed.to_string()))
 }
 
+/// 注册系列所有权
+/// 
+/// 授权铸造者可以在首次铸造前显式认领一个系列，认领后其他铸造者需获得授权才能向该系列铸造
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `series_id`: 系列 ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 注册结果
+#[cfg(feature = "cosmwasm")]
+pub fn execute_register_series(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    series_id: String,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    // 验证铸造者权限
+    if !is_authorized_minter(deps.as_ref(), &info.sender, &ctx.config)? {
+        return Err(ContractError::MinterNotAuthorized {});
+    }
+
+    validate_series_id(&series_id)?;
+    if SERIES_OWNERS.has(deps.storage, series_id.clone()) {
+        return Err(ContractError::SeriesAlreadyRegistered { series_id });
+    }
+    SERIES_OWNERS.save(deps.storage, series_id.clone(), &info.sender)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_series")
+        .add_attribute("series_id", series_id)
+        .add_attribute("owner", info.sender.to_string()))
+}
+
+/// 授权或撤销铸造者向系列铸造
+/// 
+/// 只有系列所有者或合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `series_id`: 系列 ID
+/// - `minter`: 铸造者地址
+/// - `granted`: true 为授权，false 为撤销
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+#[cfg(feature = "cosmwasm")]
+pub fn execute_set_series_grant(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    series_id: String,
+    minter: String,
+    granted: bool,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    let series_owner = SERIES_OWNERS.may_load(deps.storage, series_id.clone())?
+        .ok_or_else(|| ContractError::SeriesNotRegistered { series_id: series_id.clone() })?;
+    if info.sender != series_owner && info.sender != ctx.config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let minter_addr = deps.api.addr_validate(&minter)?;
+    if granted {
+        SERIES_GRANTS.save(deps.storage, (series_id.clone(), minter_addr.clone()), &())?;
+    } else {
+        SERIES_GRANTS.remove(deps.storage, (series_id.clone(), minter_addr.clone()));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", if granted { "grant_series_minter" } else { "revoke_series_minter" })
+        .add_attribute("series_id", series_id)
+        .add_attribute("minter", minter_addr.to_string()))
+}
+
+/// 设置指定类型的铸造状态
+/// 
+/// 暂停或恢复某一 NFT 类型的铸造，或将其永久冻结（如上线后冻结 Genesis 铸造）。
+/// 冻结后该类型的状态不可再更改，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `kind`: NFT 类型
+/// - `status`: 新的铸造状态
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+#[cfg(feature = "cosmwasm")]
+pub fn execute_set_kind_mint_status(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    kind: NftKind,
+    status: KindMintStatus,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    // 已冻结的类型不可再更改
+    if let Some(KindMintStatus::Frozen) = KIND_MINT_STATUS.may_load(deps.storage, kind.to_key())? {
+        return Err(ContractError::KindMintFrozen { kind: kind.to_key() });
+    }
+
+    match status {
+        KindMintStatus::Enabled => KIND_MINT_STATUS.remove(deps.storage, kind.to_key()),
+        _ => KIND_MINT_STATUS.save(deps.storage, kind.to_key(), &status)?,
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_kind_mint_status")
+        .add_attribute("kind", kind.to_key())
+        .add_attribute("status", alloc::format!("{:?}", status)))
+}
+
+/// 提交预言机签名的元数据更新
+/// 
+/// 预言机对 `OracleMetaPayload` 的 JSON 编码进行 secp256k1 签名，任何人都可以代为提交。
+/// 预言机只能更新物理实物 SKU 等有限字段，无需持有管理员权限
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于校验负载中的合约地址和过期时间
+/// - `token_id`: 要更新的 NFT ID
+/// - `payload`: `OracleMetaPayload` 的 JSON 编码
+/// - `signature`: 预言机对 `sha256(payload)` 的签名（64 字节）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 更新结果
+#[cfg(feature = "cosmwasm")]
+pub fn execute_update_meta_signed(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    token_id: u64,
+    payload: Binary,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    // 加载预言机公钥
+    let pubkey = META_ORACLE_PUBKEY.may_load(deps.storage)?
+        .ok_or(ContractError::OracleNotConfigured {})?;
+
+    // 验证签名
+    let hash = Sha256::digest(payload.as_slice());
+    let valid = deps.api.secp256k1_verify(&hash, &signature, &pubkey)
+        .map_err(|_| ContractError::InvalidOracleSignature {})?;
+    if !valid {
+        return Err(ContractError::InvalidOracleSignature {});
+    }
+
+    // 解析并校验负载
+    let update: OracleMetaPayload = cosmwasm_std::from_json(&payload)
+        .map_err(|_| ContractError::InvalidOraclePayload { reason: "malformed payload".to_string() })?;
+    if update.contract != env.contract.address.as_str() {
+        return Err(ContractError::InvalidOraclePayload { reason: "contract mismatch".to_string() });
+    }
+    if update.token_id != token_id {
+        return Err(ContractError::InvalidOraclePayload { reason: "token_id mismatch".to_string() });
+    }
+    if env.block.time.seconds() >= update.expires_at {
+        return Err(ContractError::InvalidOraclePayload { reason: "payload expired".to_string() });
+    }
+
+    // 更新序号只能使用一次，防止重放；不要求连续，失败或未提交的更新不会阻塞后续更新
+    if SPENT_ORACLE_NONCES.has(deps.storage, update.nonce) {
+        return Err(ContractError::OracleNonceSpent { nonce: update.nonce });
+    }
+
+    // 更新元数据
+    let old_meta = TOKEN_META.may_load(deps.storage, token_id)?
+        .ok_or(ContractError::TokenNotFound {})?;
+    SPENT_ORACLE_NONCES.save(deps.storage, update.nonce, &())?;
+    let mut meta = old_meta.clone();
+    meta.physical_sku = update.physical_sku.clone();
+    TOKEN_META.save(deps.storage, token_id, &meta)?;
+
+    // 仅在字段确有变化时发出变更事件
+    let changes = diff_token_meta(&old_meta, &meta);
+    let mut response = Response::new()
+        .add_attribute("action", "update_meta_signed")
+        .add_attribute("token_id", token_id.to_string())
+        .add_attribute("nonce", update.nonce.to_string())
+        .add_attribute("old_physical_sku", old_meta.physical_sku.unwrap_or_default())
+        .add_attribute("new_physical_sku", update.physical_sku.unwrap_or_default());
+    if !changes.is_empty() && verbose_events(deps.storage)? {
+        response = response.add_event(emit_meta_update_event(token_id, &changes));
+    }
+    Ok(response)
+}
+
 // 查询函数实现
+
+/// 查询 NFT 的扩展元数据
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: NFT 元数据（不存在时返回错误）
+#[cfg(feature = "cosmwasm")]
+pub fn query_token_meta(deps: Deps, token_id: u64) -> StdResult<Binary> {
+    let meta = TOKEN_META.load(deps.storage, token_id)?;
+    to_json_binary(&TokenMetaResponse { meta })
+}
+
 #[cfg(feature = "cosmwasm")]
 pub fn query_tokens_by_kind(
     deps: Deps,
     kind: NftKind,
-    start_after: Option<u64>,
+    start_after: Option<Cursor>,
     limit: Option<u32>,
 ) -> StdResult<Binary> {
-    let limit = limit.unwrap_or(30).min(30) as usize;
-    
-    // 使用Bound实现标准分页逻辑
-    let start_bound = start_after.map(|id| Bound::exclusive(id));
-
-    let tokens: Vec<u64> = TOKEN_META
-        .range(deps.storage, start_bound, None, Order::Ascending)
-        .filter_map(|item| {
-            item.ok().and_then(|(token_id, meta)| {
-                if meta.kind == kind {
-                    Some(token_id)
-                } else {
-                    None
-                }
-            })
+    let limit = clamp_limit(limit, MAX_LIMIT);
+    let start = start_bound(decode_cursor::<u64>(cursor_scope::TOKENS_BY_KIND, start_after)?);
+
+    // 索引完整时直接按前缀读取
+    if TOKEN_INDEXES_READY.may_load(deps.storage)?.unwrap_or(false) {
+        let tokens = TOKENS_BY_KIND
+            .prefix(kind.to_key())
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit + 1)
+            .collect::<StdResult<Vec<u64>>>()?;
+        let page = into_page(cursor_scope::TOKENS_BY_KIND, tokens, limit, |token_id| *token_id);
+        return to_json_binary(&TokensByKindResponse {
+            tokens: page.items,
+            next_cursor: page.next_cursor,
+            has_more: page.has_more,
+            scan_truncated: false,
+            total: None,
+        });
+    }
+
+    // 旧部署升级完成前扫描全部元数据
+    let entries = TOKEN_META.range(deps.storage, start, None, Order::Ascending);
+    let page = scan_page(cursor_scope::TOKENS_BY_KIND, entries, limit, |meta| meta.kind == kind)?;
+    to_json_binary(&TokensByKindResponse {
+        tokens: page.items,
+        next_cursor: page.next_cursor,
+        has_more: page.has_more,
+        scan_truncated: page.scan_truncated,
+        total: None,
+    })
+}
+
+#[cfg(feature = "cosmwasm")]
+pub fn query_recent_activity(deps: Deps, limit: Option<u32>) -> StdResult<Binary> {
+    let limit = (limit.unwrap_or(10) as u64).min(ACTIVITY_LOG_SIZE);
+    let next_seq = NEXT_ACTIVITY_SEQ.may_load(deps.storage)?.unwrap_or(0);
+
+    // 从最新的序号开始倒序读取环形缓冲区
+    let activities = (next_seq.saturating_sub(limit)..next_seq)
+        .rev()
+        .map(|seq| ACTIVITY_LOG.load(deps.storage, seq % ACTIVITY_LOG_SIZE))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&RecentActivityResponse { activities })
+}
+
+#[cfg(feature = "cosmwasm")]
+pub fn query_tokens_with_meta(
+    deps: Deps,
+    owner: String,
+    start_after: Option<Cursor>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let limit = clamp_limit(limit, MAX_LIMIT);
+    let start_after = decode_cursor::<u64>(cursor_scope::TOKENS_WITH_META, start_after)?;
+    let config = crate::state::CONFIG.load(deps.storage)?;
+
+    // 获取用户拥有的 NFT 列表，并按分页条件截取
+    let owned = TOKENS_BY_OWNER
+        .may_load(deps.storage, owner_addr)?
+        .unwrap_or_default();
+
+    let page = into_page(cursor_scope::TOKENS_WITH_META, page_after(owned, start_after, limit + 1), limit, |token_id| *token_id);
+    let tokens = page.items
+        .into_iter()
+        .map(|token_id| {
+            let meta = TOKEN_META.load(deps.storage, token_id)?;
+            let token_uri = build_token_uri(deps.storage, config.base_uri.as_deref(), token_id, &meta)?;
+            Ok(TokenWithMeta { token_id, meta, token_uri })
         })
-        .take(limit)
-        .collect();
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&TokensWithMetaResponse {
+        tokens,
+        next_cursor: page.next_cursor,
+        has_more: page.has_more,
+    })
+}
+
+#[cfg(feature = "cosmwasm")]
+pub fn query_tokens_by_expiry(
+    deps: Deps,
+    env: Env,
+    expired: bool,
+    start_after: Option<Cursor>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = clamp_limit(limit, MAX_LIMIT);
+    let now = env.block.time.seconds();
+    let start = start_bound(decode_cursor::<u64>(cursor_scope::TOKENS_BY_EXPIRY, start_after)?);
 
-    to_json_binary(&TokensByKindResponse { tokens })
+    let entries = TOKEN_META.range(deps.storage, start, None, Order::Ascending);
+    let page = scan_page(cursor_scope::TOKENS_BY_EXPIRY, entries, limit, |meta| meta.is_expired(now) == expired)?;
+    to_json_binary(&TokensByExpiryResponse {
+        tokens: page.items,
+        next_cursor: page.next_cursor,
+        has_more: page.has_more,
+        scan_truncated: page.scan_truncated,
+    })
 }
 
 #[cfg(feature = "cosmwasm")]
 pub fn query_tokens_by_series(
     deps: Deps,
     series_id: String,
-    start_after: Option<u64>,
+    start_after: Option<Cursor>,
     limit: Option<u32>,
 ) -> StdResult<Binary> {
-    let limit = limit.unwrap_or(30).min(30) as usize;
-    
-    // 使用Bound实现标准分页逻辑
-    let start_bound = start_after.map(|id| Bound::exclusive(id));
-
-    let tokens: Vec<u64> = TOKEN_META
-        .range(deps.storage, start_bound, None, Order::Ascending)
-        .filter_map(|item| {
-            item.ok().and_then(|(token_id, meta)| {
-                if meta.series_id == series_id {
-                    Some(token_id)
-                } else {
-                    None
-                }
-            })
-        })
-        .take(limit)
-        .collect();
+    let limit = clamp_limit(limit, MAX_LIMIT);
+    let start = start_bound(decode_cursor::<u64>(cursor_scope::TOKENS_BY_SERIES, start_after)?);
+    let total = SERIES_TOKEN_COUNT.may_load(deps.storage, series_id.clone())?;
 
-    to_json_binary(&TokensBySeriesResponse { tokens })
+    // 索引完整时直接按前缀读取
+    if TOKEN_INDEXES_READY.may_load(deps.storage)?.unwrap_or(false) {
+        let tokens = TOKENS_BY_SERIES
+            .prefix(series_id)
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit + 1)
+            .collect::<StdResult<Vec<u64>>>()?;
+        let page = into_page(cursor_scope::TOKENS_BY_SERIES, tokens, limit, |token_id| *token_id);
+        return to_json_binary(&TokensBySeriesResponse {
+            tokens: page.items,
+            next_cursor: page.next_cursor,
+            has_more: page.has_more,
+            scan_truncated: false,
+            total,
+        });
+    }
+
+    // 旧部署升级完成前扫描全部元数据
+    let entries = TOKEN_META.range(deps.storage, start, None, Order::Ascending);
+    let page = scan_page(cursor_scope::TOKENS_BY_SERIES, entries, limit, |meta| meta.series_id == series_id)?;
+    to_json_binary(&TokensBySeriesResponse {
+        tokens: page.items,
+        next_cursor: page.next_cursor,
+        has_more: page.has_more,
+        scan_truncated: page.scan_truncated,
+        total,
+    })
 }
 
 #[cfg(feature = "cosmwasm")]
 pub fn query_tokens_by_group(
     deps: Deps,
     group_id: String,
-    start_after: Option<u64>,
+    start_after: Option<Cursor>,
     limit: Option<u32>,
 ) -> StdResult<Binary> {
-    let limit = limit.unwrap_or(30).min(30) as usize;
-    
-    // 使用Bound实现标准分页逻辑
-    let start_bound = start_after.map(|id| Bound::exclusive(id));
-
-    let tokens: Vec<u64> = TOKEN_META
-        .range(deps.storage, start_bound, None, Order::Ascending)
-        .filter_map(|item| {
-            item.ok().and_then(|(token_id, meta)| {
-                if meta.collection_group_id.as_ref() == Some(&group_id) {
-                    Some(token_id)
-                } else {
-                    None
-                }
-            })
-        })
-        .take(limit)
-        .collect();
+    let limit = clamp_limit(limit, MAX_LIMIT);
+    let start = start_bound(decode_cursor::<u64>(cursor_scope::TOKENS_BY_GROUP, start_after)?);
 
-    to_json_binary(&TokensByGroupResponse { tokens })
+    // 索引完整时直接按前缀读取
+    if TOKEN_INDEXES_READY.may_load(deps.storage)?.unwrap_or(false) {
+        let tokens = TOKENS_BY_GROUP
+            .prefix(group_id)
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit + 1)
+            .collect::<StdResult<Vec<u64>>>()?;
+        let page = into_page(cursor_scope::TOKENS_BY_GROUP, tokens, limit, |token_id| *token_id);
+        return to_json_binary(&TokensByGroupResponse {
+            tokens: page.items,
+            next_cursor: page.next_cursor,
+            has_more: page.has_more,
+            scan_truncated: false,
+            total: None,
+        });
+    }
+
+    // 旧部署升级完成前扫描全部元数据
+    let entries = TOKEN_META.range(deps.storage, start, None, Order::Ascending);
+    let page = scan_page(cursor_scope::TOKENS_BY_GROUP, entries, limit, |meta| meta.collection_group_id.as_ref() == Some(&group_id))?;
+    to_json_binary(&TokensByGroupResponse {
+        tokens: page.items,
+        next_cursor: page.next_cursor,
+        has_more: page.has_more,
+        scan_truncated: page.scan_truncated,
+        total: None,
+    })
 }
 
 #[cfg(feature = "cosmwasm")]
@@ -590,36 +1604,446 @@ pub fn query_contract_info(deps: Deps) -> StdResult<Binary> {
     to_json_binary(&info)
 }
 
+#[cfg(feature = "cosmwasm")]
+pub fn query_version_info(deps: Deps) -> StdResult<Binary> {
+    let version = cw2::get_contract_version(deps.storage)?;
+    let storage_version = crate::state::STORAGE_VERSION.may_load(deps.storage)?.unwrap_or_default();
+
+    to_json_binary(&VersionInfoResponse {
+        contract_name: version.contract,
+        contract_version: version.version,
+        storage_version,
+        capabilities: CAPABILITIES.iter().map(|c| c.to_string()).collect(),
+    })
+}
+
+#[cfg(feature = "cosmwasm")]
+pub fn query_pending_burn(deps: Deps, token_id: u64) -> StdResult<Binary> {
+    let pending = PENDING_BURNS.may_load(deps.storage, token_id)?;
+    let grace_period = BURN_GRACE_PERIOD.may_load(deps.storage)?.unwrap_or(0);
+    to_json_binary(&PendingBurnResponse { pending, grace_period })
+}
+
+#[cfg(feature = "cosmwasm")]
+pub fn query_minter_stats(deps: Deps, minter: String) -> StdResult<Binary> {
+    let minter_addr = deps.api.addr_validate(&minter)?;
+    let stats = MINTER_STATS.may_load(deps.storage, minter_addr)?;
+
+    to_json_binary(&MinterStatsResponse {
+        minter,
+        total_minted: stats.as_ref().map(|s| s.total_minted).unwrap_or(0),
+        last_mint_height: stats.as_ref().map(|s| s.last_mint_height),
+        last_mint_time: stats.map(|s| s.last_mint_time),
+    })
+}
+
+#[cfg(feature = "cosmwasm")]
+pub fn query_series_owner(deps: Deps, series_id: String) -> StdResult<Binary> {
+    let owner = SERIES_OWNERS.may_load(deps.storage, series_id.clone())?;
+    let grantees = SERIES_GRANTS
+        .prefix(series_id.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|key| key.map(|addr| addr.to_string()))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&SeriesOwnerResponse {
+        series_id,
+        owner: owner.map(|addr| addr.to_string()),
+        grantees,
+    })
+}
+
+/// 按系列和系列内序号查询 NFT 所有者
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `series_id`: 系列 ID
+/// - `serial`: 系列内序号
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: NFT ID 和所有者（序号不存在或已销毁时为 None）
+#[cfg(feature = "cosmwasm")]
+pub fn query_serial_owner(deps: Deps, series_id: String, serial: u64) -> StdResult<Binary> {
+    let token_id = TOKENS_BY_SERIAL.may_load(deps.storage, (series_id.clone(), serial))?;
+    let owner = match token_id {
+        Some(token_id) => TOKEN_OWNERSHIP.may_load(deps.storage, token_id)?.map(|addr| addr.to_string()),
+        None => None,
+    };
+
+    to_json_binary(&SerialOwnerResponse { series_id, serial, token_id, owner })
+}
+
+/// 按系列和系列内序号查询 NFT
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `series_id`: 系列 ID
+/// - `serial`: 系列内序号
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: NFT ID、所有者和元数据，序号不存在时返回 NotFound
+#[cfg(feature = "cosmwasm")]
+pub fn query_token_by_serial(deps: Deps, series_id: String, serial: u64) -> StdResult<Binary> {
+    let token_id = TOKENS_BY_SERIAL.load(deps.storage, (series_id, serial))?;
+    let owner = TOKEN_OWNERSHIP.load(deps.storage, token_id)?;
+    let meta = TOKEN_META.load(deps.storage, token_id)?;
+
+    to_json_binary(&TokenBySerialResponse { token_id, owner: owner.to_string(), meta })
+}
+
+/// 查询 NFT 所有权证明
+/// 
+/// 返回当前所有者及其取得该 NFT 的区块高度和时间，供空投快照等场景证明持有时长
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 所有者和取得时间，NFT 不存在时返回 NotFound
+#[cfg(feature = "cosmwasm")]
+pub fn query_owned_at(deps: Deps, token_id: u64) -> StdResult<Binary> {
+    let owner = TOKEN_OWNERSHIP.load(deps.storage, token_id)?;
+    let acquired = TOKEN_ACQUIRED.may_load(deps.storage, token_id)?;
+
+    to_json_binary(&OwnedAtResponse { token_id, owner: owner.to_string(), acquired })
+}
+
+/// 查询 NFT 的完整记录
+/// 
+/// 一次返回所有者、元数据、批准、锁定和托管状态以及各索引的存在情况，
+/// 供客服排查问题；NFT 不存在时各项为空，可用于发现残留的索引
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: NFT 完整记录
+#[cfg(feature = "cosmwasm")]
+pub fn query_token_debug(deps: Deps, token_id: u64) -> StdResult<Binary> {
+    let owner = TOKEN_OWNERSHIP.may_load(deps.storage, token_id)?;
+    let meta = TOKEN_META.may_load(deps.storage, token_id)?;
+
+    let owner_tokens = match &owner {
+        Some(owner) => TOKENS_BY_OWNER.may_load(deps.storage, owner.clone())?
+            .map_or(false, |tokens| tokens.contains(&token_id)),
+        None => false,
+    };
+    let indexes = match &meta {
+        Some(meta) => TokenIndexPresence {
+            all_tokens: ALL_TOKENS.has(deps.storage, token_id),
+            owner_tokens,
+            by_kind: TOKENS_BY_KIND.has(deps.storage, (meta.kind.to_key(), token_id)),
+            by_series: TOKENS_BY_SERIES.has(deps.storage, (meta.series_id.clone(), token_id)),
+            by_serial: TOKENS_BY_SERIAL.may_load(deps.storage, (meta.series_id.clone(), meta.serial_in_series))?
+                == Some(token_id),
+            by_group: meta.collection_group_id.as_ref()
+                .map(|group_id| TOKENS_BY_GROUP.has(deps.storage, (group_id.clone(), token_id))),
+        },
+        None => TokenIndexPresence {
+            all_tokens: ALL_TOKENS.has(deps.storage, token_id),
+            owner_tokens,
+            by_kind: false,
+            by_series: false,
+            by_serial: false,
+            by_group: None,
+        },
+    };
+
+    to_json_binary(&TokenDebugResponse {
+        token_id,
+        owner: owner.map(|addr| addr.to_string()),
+        meta,
+        acquired: TOKEN_ACQUIRED.may_load(deps.storage, token_id)?,
+        approvals: TOKEN_APPROVALS.may_load(deps.storage, token_id)?.unwrap_or_default(),
+        session_lock: SESSION_LOCKS.may_load(deps.storage, token_id)?,
+        pending_burn: PENDING_BURNS.may_load(deps.storage, token_id)?,
+        burn_request: BURN_REQUESTS.may_load(deps.storage, token_id)?,
+        custody_tag: CUSTODY_TAGS.may_load(deps.storage, token_id)?,
+        gift_claim_hash: GIFTED_TOKENS.may_load(deps.storage, token_id)?,
+        conditional_transfer: CONDITIONAL_TRANSFERS.may_load(deps.storage, token_id)?,
+        bundle_id: BUNDLED_TOKENS.may_load(deps.storage, token_id)?,
+        indexes,
+    })
+}
+
+/// 批量验证所有权
+/// 
+/// 供其他合约在一次查询中确认用户持有一组 NFT（如验证托管存入），
+/// 不存在或已销毁的 NFT 视为未持有
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `owner`: 待验证的所有者地址
+/// - `token_ids`: NFT ID 列表（最多 100 个）
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 与输入顺序一致的逐个结果和是否全部持有
+#[cfg(feature = "cosmwasm")]
+pub fn query_verify_ownership(deps: Deps, owner: String, token_ids: Vec<u64>) -> StdResult<Binary> {
+    if token_ids.len() > MAX_VERIFY_OWNERSHIP {
+        return Err(StdError::generic_err(alloc::format!(
+            "too many token ids: {} (max {})", token_ids.len(), MAX_VERIFY_OWNERSHIP
+        )));
+    }
+    let owner = deps.api.addr_validate(&owner)?;
+
+    let owned = token_ids
+        .iter()
+        .map(|token_id| Ok(TOKEN_OWNERSHIP.may_load(deps.storage, *token_id)?.as_ref() == Some(&owner)))
+        .collect::<StdResult<Vec<bool>>>()?;
+    let all_owned = owned.iter().all(|owned| *owned);
+
+    to_json_binary(&VerifyOwnershipResponse { owner: owner.to_string(), owned, all_owned })
+}
+
+/// 铸造预检
+/// 
+/// 以只读方式执行铸造的全部校验，供盲盒合约在提交大批量铸造前预检。
+/// 铸造权限和系列权限依赖发送者，查询无法确认，不在预检范围内
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，用于检查过期时间
+/// - `token_id`: 要铸造的 NFT ID
+/// - `owner`: 新 NFT 的所有者地址
+/// - `extension`: NFT 的扩展元数据
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 是否可以铸造及首个校验错误
+#[cfg(feature = "cosmwasm")]
+pub fn query_validate_mint(
+    deps: Deps,
+    env: Env,
+    token_id: u64,
+    owner: String,
+    extension: NftMeta,
+) -> StdResult<Binary> {
+    let paused = crate::state::CONTRACT_PAUSED.may_load(deps.storage)?.unwrap_or(false);
+    let serial = next_series_serial(deps.storage, &extension.series_id)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    to_json_binary(&mint_validation(deps, &env, paused, token_id, serial, &owner, &extension))
+}
+
+/// 批量铸造预检
+/// 
+/// 逐条执行铸造预检并返回每个条目的结果，批内重复的 ID 从第二次出现起报错，
+/// 运营方可一次修正空投清单中的全部问题
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，用于检查过期时间
+/// - `mints`: 铸造条目（数量上限与批量铸造相同）
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 与输入顺序一致的逐条结果和是否全部通过
+#[cfg(feature = "cosmwasm")]
+pub fn query_validate_batch_mint(deps: Deps, env: Env, mints: Vec<BatchMintItem>) -> StdResult<Binary> {
+    if mints.len() > MAX_BATCH_MINT {
+        return Err(StdError::generic_err(alloc::format!(
+            "too many mints: {} (max {})", mints.len(), MAX_BATCH_MINT
+        )));
+    }
+    let paused = crate::state::CONTRACT_PAUSED.may_load(deps.storage)?.unwrap_or(false);
+    let serials = batch_mint_serials(deps.storage, &mints)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    let mut token_ids = alloc::collections::BTreeSet::new();
+    let results: Vec<MintValidationResponse> = mints
+        .iter()
+        .zip(serials)
+        .map(|(mint_item, serial)| {
+            // 确定性 ID 模式下按推导出的 ID 检查批内重复
+            let token_id = resolve_mint_token_id(deps.storage, mint_item.token_id, &mint_item.extension.series_id, serial)
+                .unwrap_or(mint_item.token_id);
+            if !token_ids.insert(token_id) {
+                return MintValidationResponse {
+                    token_id,
+                    valid: false,
+                    error: Some(ContractError::TokenAlreadyExists {}.to_string()),
+                };
+            }
+            mint_validation(deps, &env, paused, mint_item.token_id, serial, &mint_item.owner, &mint_item.extension)
+        })
+        .collect();
+    let all_valid = results.iter().all(|result| result.valid);
+
+    to_json_binary(&ValidateBatchMintResponse { results, all_valid })
+}
+
+/// 执行铸造预检，合约暂停时同样视为无法铸造
+/// 
+/// 确定性 ID 模式下按 `serial` 推导 ID，响应中的 `token_id` 为实际将铸造的 ID
+#[cfg(feature = "cosmwasm")]
+fn mint_validation(
+    deps: Deps,
+    env: &Env,
+    paused: bool,
+    token_id: u64,
+    serial: u64,
+    owner: &str,
+    extension: &NftMeta,
+) -> MintValidationResponse {
+    let resolved = resolve_mint_token_id(deps.storage, token_id, &extension.series_id, serial);
+    let token_id = resolved.as_ref().copied().unwrap_or(token_id);
+    let result = if paused {
+        Err(ContractError::ContractPaused {})
+    } else {
+        resolved.and_then(|token_id| check_mint_item(deps.storage, deps.api, env, token_id, owner, extension))
+    };
+    MintValidationResponse {
+        token_id,
+        valid: result.is_ok(),
+        error: result.err().map(|err| err.to_string()),
+    }
+}
+
+/// 查询 NFT 类型信息
+/// 
+/// 返回类型的稀有度等级、稀有度名称和兑换价值，前端和合作合约无需复制类型表
+/// 
+/// # 参数
+/// - `kind`: NFT 类型
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 类型信息
+#[cfg(feature = "cosmwasm")]
+pub fn query_kind_info(kind: NftKind) -> StdResult<Binary> {
+    to_json_binary(&kind_info(kind))
+}
+
+/// 查询全部 NFT 类型信息
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 按稀有度从低到高排列的类型信息列表
+#[cfg(feature = "cosmwasm")]
+pub fn query_kind_list() -> StdResult<Binary> {
+    let kinds = NftKind::ALL.into_iter().map(kind_info).collect();
+    to_json_binary(&KindListResponse { kinds })
+}
+
+/// 查询盲盒规模及其头奖类型
+/// 
+/// 盲盒前端据此展示各规模的头奖，无需复制规模与头奖的对应表
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 按从小到大排列的规模及头奖类型
+#[cfg(feature = "cosmwasm")]
+pub fn query_scale_info() -> StdResult<Binary> {
+    let scales = Scale::ALL
+        .into_iter()
+        .map(|scale| ScaleInfo { first_prize: scale.first_prize_nft(), scale })
+        .collect();
+    to_json_binary(&ScaleInfoResponse { scales })
+}
+
+/// 汇总单个类型的稀有度和兑换价值
+#[cfg(feature = "cosmwasm")]
+fn kind_info(kind: NftKind) -> KindInfoResponse {
+    KindInfoResponse {
+        rarity_level: kind.rarity_level(),
+        rarity_name: kind.rarity_name().to_string(),
+        exchange_value: kind.exchange_value(),
+        kind,
+    }
+}
+
+#[cfg(feature = "cosmwasm")]
+pub fn query_kind_mint_status(deps: Deps, kind: NftKind) -> StdResult<Binary> {
+    let status = KIND_MINT_STATUS.may_load(deps.storage, kind.to_key())?
+        .unwrap_or(KindMintStatus::Enabled);
+    to_json_binary(&KindMintStatusResponse { kind, status })
+}
+
+/// 查询指定目标的合成配方
+/// 
+/// 设置了共享配方注册表时返回合成实际使用的配方
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和查询访问
+/// - `env`: 环境信息，用于判断注册表配方缓存是否过期
+/// - `target`: 目标 NFT 类型
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 合成配方（不存在时为 None）
+#[cfg(feature = "cosmwasm")]
+pub fn query_recipe(deps: Deps, env: Env, target: NftKind) -> StdResult<Binary> {
+    let (recipe, _) = lookup_recipe(deps, &env, &target)?;
+    to_json_binary(&RecipeResponse { recipe })
+}
+
 #[cfg(feature = "cosmwasm")]
 pub fn query_all_recipes(
     deps: Deps,
-    start_after: Option<NftKind>,
+    start_after: Option<Cursor>,
     limit: Option<u32>,
 ) -> StdResult<Binary> {
-    let limit = limit.unwrap_or(30).min(30) as usize;
+    let limit = clamp_limit(limit, MAX_LIMIT);
+    let start = start_bound(decode_cursor::<String>(cursor_scope::ALL_RECIPES, start_after)?);
 
     let recipes: Vec<(String, Recipe)> = RECIPES
-        .range(deps.storage, start_after.as_ref().map(|k| Bound::exclusive(k.to_key())), None, Order::Ascending)
-        .take(limit)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit + 1)
         .collect::<StdResult<Vec<_>>>()?;
+    let page = into_page(cursor_scope::ALL_RECIPES, recipes, limit, |(key, _)| key.clone());
+    // 配方以类型为键，数量不超过 NFT 类型数量
+    let total = RECIPES.keys(deps.storage, None, None, Order::Ascending).count() as u64;
 
     // 转换String键为NftKind
-    let recipes: Vec<(NftKind, Recipe)> = recipes.into_iter()
+    let recipes: Vec<(NftKind, Recipe)> = page.items.into_iter()
         .filter_map(|(key, recipe)| {
             NftKind::from_key(&key).ok().map(|kind| (kind, recipe))
         })
         .collect();
     
-    to_json_binary(&AllRecipesResponse { recipes })
+    to_json_binary(&AllRecipesResponse {
+        recipes,
+        next_cursor: page.next_cursor,
+        has_more: page.has_more,
+        total: Some(total),
+    })
+}
+
+/// 查询完整配方图
+/// 
+/// 将全部配方按输入类型组织为邻接表，前端一次查询即可渲染完整合成树。
+/// 配方以目标类型为键，数量不超过 NFT 类型数量，无需分页
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 输入类型 → 输出类型、数量和费用
+#[cfg(feature = "cosmwasm")]
+pub fn query_recipe_graph(deps: Deps) -> StdResult<Binary> {
+    let mut nodes: alloc::collections::BTreeMap<String, RecipeGraphNode> = alloc::collections::BTreeMap::new();
+    let mut recipe_count = 0u32;
+    for item in RECIPES.range(deps.storage, None, None, Order::Ascending) {
+        let (key, recipe) = item?;
+        let Ok(output) = NftKind::from_key(&key) else {
+            continue;
+        };
+        recipe_count += 1;
+        for input in &recipe.inputs {
+            nodes.entry(input.nft_kind.to_key())
+                .or_insert_with(|| RecipeGraphNode { input: input.nft_kind.clone(), outputs: vec![] })
+                .outputs
+                .push(RecipeGraphEdge { output: output.clone(), count: input.count, cost: recipe.cost.clone() });
+        }
+    }
+
+    to_json_binary(&RecipeGraphResponse { nodes: nodes.into_values().collect(), recipe_count })
 }
 
 #[cfg(feature = "cosmwasm")]
 pub fn query_synthesis_preview(
     deps: Deps,
+    env: Env,
     _inputs: Vec<u64>,
     target: NftKind,
 ) -> StdResult<Binary> {
-    let recipe = RECIPES.may_load(deps.storage, target.to_key())?;
+    let (recipe, _) = lookup_recipe(deps, &env, &target)?;
     
     let recipe = match recipe {
         Some(recipe) => recipe,
@@ -634,6 +2058,10 @@ pub fn query_synthesis_preview(
     };
     let output_value = target.exchange_value();
 
+    // 需要的输入包含销毁税要求的额外输入
+    let burn_tax = BURN_TAXES.may_load(deps.storage, target.to_key())?;
+    let recipe = taxed_recipe(&recipe, burn_tax.as_ref());
+
     to_json_binary(&SynthesisPreviewResponse {
         can_synthesize: true,
         required_inputs: recipe.inputs,
@@ -642,6 +2070,122 @@ pub fn query_synthesis_preview(
     })
 }
 
+/// 模拟合成配方
+/// 
+/// 以假设的库存评估尚未保存的配方，不读取任何持有状态，供管理员在 SetRecipe 前检查配方
+/// 
+/// # 参数
+/// - `recipe`: 待检查的配方
+/// - `inventory`: 假设的库存（类型与数量，重复类型累加）
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 是否匹配、可合成次数、缺少的输入和兑换价值变化
+pub fn query_simulate_recipe(recipe: Recipe, inventory: Vec<(NftKind, u32)>) -> StdResult<Binary> {
+    // 汇总库存
+    let available = logic::tally_inventory(inventory.iter().map(|(kind, count)| (kind, *count as u64)));
+    let have = |kind: &NftKind| available.get(&kind.to_key()).copied().unwrap_or(0);
+
+    // 统计配方所需的输入价值、缺少的输入和最多可合成次数
+    let max_times = logic::max_synthesis_times(&recipe.inputs, have);
+    let input_value = logic::inputs_value(&recipe.inputs);
+    let output_value = recipe.output.exchange_value() as u64;
+    to_json_binary(&SimulateRecipeResponse {
+        matches: max_times > 0,
+        max_times: max_times.min(u32::MAX as u64) as u32,
+        missing: logic::missing_inputs(&recipe.inputs, have),
+        input_value,
+        output_value,
+        value_delta: logic::value_delta(&recipe.inputs, &recipe.output),
+    })
+}
+
+/// 估算链式合成成本
+/// 
+/// 从目标开始沿链上配方逐层展开，计算合成一个目标最终需要的基础类型数量，
+/// 配方调整后前端无需更新硬编码的合成阶梯
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `target`: 目标类型
+/// - `from_kind`: 基础类型（默认四叶草）
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 所需基础类型数量、合成次数和其他输入
+#[cfg(feature = "cosmwasm")]
+pub fn query_crafting_cost(deps: Deps, target: NftKind, from_kind: Option<NftKind>) -> StdResult<Binary> {
+    let from_kind = from_kind.unwrap_or(NftKind::Clover);
+
+    // 配方数量很少，一次读取全部配方后在内存中展开
+    let recipes: alloc::collections::BTreeMap<String, Recipe> = RECIPES
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    let cost = logic::crafting_cost(&target, &from_kind, |kind| {
+        recipes.get(&kind.to_key()).map(|recipe| recipe.inputs.as_slice())
+    })
+    .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    to_json_binary(&CraftingCostResponse {
+        target,
+        from_kind,
+        required: cost.required,
+        syntheses: cost.syntheses,
+        other_inputs: cost.other_inputs,
+    })
+}
+
+/// 查询用户当前可合成的目标类型
+/// 
+/// 根据用户各类型 NFT 的持有数量检查每个配方，返回可合成的目标及最多可合成次数
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `owner`: 用户地址
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 可合成目标列表
+#[cfg(feature = "cosmwasm")]
+pub fn query_synthesizable_targets(deps: Deps, owner: String) -> StdResult<Binary> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+
+    // 持有数量计数不完整时（旧版本升级），遍历用户持有的 NFT 统计数量
+    let counts_ready = OWNER_KIND_COUNTS_READY.may_load(deps.storage)?.unwrap_or(false);
+    let mut scanned: alloc::collections::BTreeMap<String, u64> = alloc::collections::BTreeMap::new();
+    if !counts_ready {
+        for token_id in TOKENS_BY_OWNER.may_load(deps.storage, owner_addr.clone())?.unwrap_or_default() {
+            if let Some(meta) = TOKEN_META.may_load(deps.storage, token_id)? {
+                *scanned.entry(meta.kind.to_key()).or_insert(0) += 1;
+            }
+        }
+    }
+    let count_of = |kind: &NftKind| -> StdResult<u64> {
+        if counts_ready {
+            owner_kind_count(deps.storage, &owner_addr, kind)
+        } else {
+            Ok(scanned.get(&kind.to_key()).copied().unwrap_or(0))
+        }
+    };
+
+    let mut targets = vec![];
+    for item in RECIPES.range(deps.storage, None, None, Order::Ascending) {
+        let (key, recipe) = item?;
+        let Ok(target) = NftKind::from_key(&key) else {
+            continue;
+        };
+
+        // 先读取各输入的持有数量，再按链下模拟相同的逻辑计算最多可合成次数
+        let mut held = alloc::collections::BTreeMap::new();
+        for input in &recipe.inputs {
+            held.insert(input.nft_kind.to_key(), count_of(&input.nft_kind)?);
+        }
+        let max_times = logic::max_synthesis_times(&recipe.inputs, |kind| held.get(&kind.to_key()).copied().unwrap_or(0));
+        if max_times > 0 {
+            targets.push(SynthesizableTarget { target, max_times });
+        }
+    }
+
+    to_json_binary(&SynthesizableTargetsResponse { owner, targets })
+}
+
 #[cfg(feature = "cosmwasm")]
 pub fn query_nft_contract(_deps: Deps) -> StdResult<Binary> {
     // 本地 CW721 模式，不依赖外部合约
@@ -27,7 +27,7 @@ macro_rules! cw_serde {
 macro_rules! QueryResponses {
     () => {};
 }
-use crate::types::{NftKind, NftMeta, Recipe, RecipeInput};
+use crate::types::{AcceptedDenom, EventVerbosity, NftKind, NftMeta, PoolEntry, Recipe, RecipeInput, Role, Scale};
 use crate::state::Expiration;
 
 // ========== 初始化消息 ==========
@@ -47,6 +47,16 @@ pub struct InstantiateMsg {
     pub base_uri: Option<String>,
 }
 
+/// 合约迁移消息
+/// 
+/// 迁移时检查已存储的合约名称和版本，默认拒绝迁移到更旧的版本
+#[cw_serde]
+pub struct MigrateMsg {
+    /// 允许迁移到比已存储版本更旧的代码（用于紧急回滚）
+    #[serde(default)]
+    pub allow_downgrade: bool,
+}
+
 // ========== 执行消息 ==========
 
 /// 合约执行消息
@@ -55,46 +65,220 @@ pub struct InstantiateMsg {
 #[cw_serde]
 pub enum ExecuteMsg {
     // ========== 标准 CW721 接口 ==========
+    // 标准接口的 token_id 同时接受数字和字符串（兼容通用 CW721 工具）
     /// 转移 NFT 所有权
-    TransferNft { recipient: String, token_id: u64 },
+    TransferNft {
+        recipient: String,
+        #[serde(deserialize_with = "crate::helpers::deserialize_token_id")]
+        token_id: u64,
+    },
     /// 批准特定地址操作特定 NFT
-    Approve { spender: String, token_id: u64, expires: Option<Expiration> },
+    Approve {
+        spender: String,
+        #[serde(deserialize_with = "crate::helpers::deserialize_token_id")]
+        token_id: u64,
+        expires: Option<Expiration>,
+    },
     /// 撤销特定地址对特定 NFT 的批准
-    Revoke { spender: String, token_id: u64 },
-    /// 批准操作员管理所有 NFT
-    ApproveAll { operator: String, expires: Option<Expiration> },
+    Revoke {
+        spender: String,
+        #[serde(deserialize_with = "crate::helpers::deserialize_token_id")]
+        token_id: u64,
+    },
+    /// 批准操作员管理所有 NFT（可选限制转移和销毁的总次数）
+    ApproveAll { operator: String, expires: Option<Expiration>, max_transfers: Option<u32> },
     /// 撤销操作员对所有 NFT 的管理权限
     RevokeAll { operator: String },
+    /// 按类型授权操作员管理发送者持有的该类型所有 NFT
+    ApproveKind { operator: String, kind: NftKind, expires: Option<Expiration> },
+    /// 撤销按类型授权
+    RevokeKind { operator: String, kind: NftKind },
+    /// 一次撤销发送者授予的所有批准（单个 NFT 批准、操作员、按类型授权、会话密钥和受信任操作员选择），
+    /// 每次最多处理 100 项，响应中 has_more 为 true 时重复调用
+    RevokeAllMyApprovals {},
+    /// 创建类型互换报价：以持有的 NFT 换取任意一个指定类型的 NFT
+    CreateSwapOffer { offered_token_id: u64, wanted_kind: NftKind },
+    /// 接受互换报价，以持有的期望类型 NFT 原子交换
+    AcceptSwap { offer_id: u64, token_id: u64 },
+    /// 取消互换报价（仅报价创建者）
+    CancelSwapOffer { offer_id: u64 },
+    /// 创建礼物：托管 NFT，持有领取码原文者可在过期前领取
+    CreateGift { token_id: u64, claim_hash: String, expires: u64 },
+    /// 提交领取码原文领取礼物
+    ClaimGift { preimage: String },
+    /// 礼物过期后由赠送者取回
+    CancelGift { claim_hash: String },
+    /// 托管方为持有的 NFT 标记或清除链下账户引用哈希（最多 100 个）
+    TagCustody { tags: Vec<CustodyTagItem> },
+    /// 将投票权委托给其他地址（已有委托时改为新的受托人）
+    Delegate { to: String },
+    /// 取消投票权委托
+    Undelegate {},
+    /// 将多个 NFT 托管到合约并创建组合包（2 到 50 个）
+    CreateBundle { token_ids: Vec<u64> },
+    /// 将组合包整体转移给接收者
+    TransferBundle { bundle_id: u64, recipient: String },
+    /// 拆包，取回组合包中的 NFT
+    Unbundle { bundle_id: u64 },
+    /// 条件转移：NFT 托管到合约，接收者在超时前提交哈希锁原文领取，超时后发送者取回
+    TransferWithCondition { recipient: String, token_id: u64, hashlock: String, timeout: u64 },
+    /// 接收者提交哈希锁原文领取条件转移
+    ClaimConditionalTransfer { token_id: u64, preimage: String },
+    /// 超时后发送者取回条件转移
+    ReclaimConditionalTransfer { token_id: u64 },
+    /// 选择信任受信任操作员注册表中的所有操作员
+    OptInTrustedOperators {},
+    /// 取消信任受信任操作员注册表
+    OptOutTrustedOperators {},
     
     // ========== Luckee 扩展接口 ==========
-    /// 铸造新的 NFT
+    /// 铸造新的 NFT（提供幂等键时，同一铸造者重复提交该键返回首次铸造的结果而不再铸造）
     Mint { 
         token_id: u64, 
         owner: String, 
-        extension: NftMeta 
+        extension: NftMeta,
+        idempotency_key: Option<String>,
+    },
+    /// 销毁 NFT（启用宽限期时进入待销毁状态）
+    Burn {
+        #[serde(deserialize_with = "crate::helpers::deserialize_token_id")]
+        token_id: u64,
     },
-    /// 销毁 NFT
-    Burn { token_id: u64 },
+    /// 销毁调用者持有的指定类型 NFT（最多 max 个，单次上限 100）
+    BurnAllOfKind { kind: NftKind, max: u32 },
+    /// 宽限期内恢复待销毁的 NFT（原所有者或合约所有者）
+    RestoreBurned { token_id: u64 },
+    /// 宽限期结束后完成销毁（无需权限）
+    FinalizeBurn { token_id: u64 },
+    /// 申请销毁高价值 NFT，冷却期结束后方可确认（所有者或获授权的操作员）
+    RequestBurn { token_id: u64 },
+    /// 冷却期结束后确认销毁申请
+    ConfirmBurn { token_id: u64 },
+    /// 取消销毁申请（当前所有者或申请时的所有者，暂停期间也可执行）
+    CancelBurnRequest { token_id: u64 },
+    /// 回收已过期的 NFT（无需权限）
+    Expire { token_id: u64 },
     
     // ========== 管理员接口 ==========
     /// 更新铸造者地址
     UpdateMinter { new_minter: String },
-    /// 更新基础 URI
-    UpdateBaseUri { base_uri: String },
+    /// 提议铸造者轮换，到达生效高度前原铸造者仍然有效
+    ProposeMinter { new_minter: String, activation_height: u64 },
+    /// 到达生效高度后使铸造者轮换生效（无需权限）
+    ActivateMinter {},
+    /// 永久锁定铸造者集合，之后不能再更换或增加铸造者（仅合约所有者，不可撤销）
+    LockMinterSet {},
+    /// 更新基础 URI（传入 None 清除基础 URI）
+    UpdateBaseUri { base_uri: Option<String> },
+    /// 设置基础 URI 协议对应的 token URI 模板（None 表示移除）
+    SetUriTemplate { scheme: String, template: Option<String> },
+    /// 设置销毁宽限期（区块数，0 表示关闭）
+    SetBurnGracePeriod { blocks: u64 },
+    /// 设置销毁冷却（稀有度不低于 min_rarity_level 的 NFT 须申请销毁并等待 delay 秒，None 表示关闭）
+    SetBurnCooldown { min_rarity_level: Option<u8>, delay: u64 },
+    /// 设置事件详细程度（Minimal 时省略逐条明细事件，仅合约所有者）
+    SetEventVerbosity { verbosity: EventVerbosity },
     
     // ========== 合成相关接口 ==========
     /// 设置合成配方
     SetRecipe { target: NftKind, recipe: Recipe },
     /// 删除合成配方
     RemoveRecipe { target: NftKind },
+    /// 紧急分批清空合成配方，清空完成后可重新写入默认配方（仅合约所有者）
+    ResetRecipes { install_defaults: bool },
+    /// 设置共享配方注册表（仅合约所有者，None 表示只使用本地配方）
+    SetRecipeRegistry { registry: Option<String>, cache_ttl: u64 },
     /// 执行合成操作
     Synthesize { inputs: Vec<u64>, target: NftKind },
+    /// 使用已存入的合作集合 NFT 抵扣部分配方输入执行合成
+    SynthesizeWithPartners { inputs: Vec<u64>, partner_inputs: Vec<PartnerInput>, target: NftKind },
+    /// 登记或移除合作集合及其 NFT 在配方中视为的类型（仅合约所有者，kind 为 None 时移除）
+    SetPartnerCollection { collection: String, kind: Option<NftKind> },
+    /// 合作集合通过 SendNft 存入 NFT 时的回调（来自旧版合约时导入该 NFT）
+    ReceiveNft(cw721::Cw721ReceiveMsg),
+    /// 取回存入的合作集合 NFT（仅存入者）
+    WithdrawPartnerNft { collection: String, token_id: String },
     
     // ========== 批量操作接口 ==========
-    /// 批量铸造 NFT
-    BatchMint { mints: Vec<BatchMintItem> },
+    /// 批量铸造 NFT（幂等键规则与 Mint 相同）
+    BatchMint { mints: Vec<BatchMintItem>, idempotency_key: Option<String> },
     /// 设置铸造者权限
     SetMinter { minter: String, allowed: bool },
+    /// 加入或移出受信任操作员注册表（仅合约所有者）
+    SetTrustedOperator { operator: String, trusted: bool },
+    /// 全局停用或恢复操作员（仅合约所有者）
+    SuspendOperator { operator: String, suspended: bool },
+    /// 授予或撤销运营角色（仅合约所有者）
+    SetRole { role: Role, address: String, granted: bool },
+    /// 从合约托管库存批量分发 NFT（仅空投角色）
+    Distribute { items: Vec<DistributeItem> },
+    /// 对局期间将 NFT 锁定到游戏会话（仅游戏服务器角色）
+    LockForSession { token_ids: Vec<u64>, session_id: String, until: u64 },
+    /// 提前解锁游戏会话（锁定者或合约所有者）
+    UnlockSession { session_id: String },
+    /// 授予会话密钥，允许其在 duration 秒内代发送者执行 scopes 中的操作
+    GrantSessionKey { key: String, scopes: Vec<crate::types::SessionKeyScope>, duration: u64 },
+    /// 撤销发送者授予的会话密钥
+    RevokeSessionKey { key: String },
+    /// 设置价格预言机（仅合约所有者，None 表示移除）
+    SetPriceOracle { oracle: Option<PriceOracleMsg> },
+    /// 设置旧版合约导入（仅合约所有者，None 表示停止导入）
+    SetLegacyImport { import: Option<LegacyImportMsg> },
+    /// 设置配方美元费用（仅合约所有者，单位为微美元，None 表示移除）
+    SetRecipeUsdCost { target: NftKind, usd_cost: Option<cosmwasm_std::Uint128> },
+    /// 设置配方的合成销毁税（仅合约所有者，None 表示移除）
+    SetBurnTax { target: NftKind, tax: Option<crate::types::BurnTax> },
+    /// 设置配方的产出集合，产出在关联的 Luckee NFT 集合中铸造（仅合约所有者，None 表示在本集合铸造）
+    SetRecipeOutputCollection { target: NftKind, collection: Option<String> },
+    /// 登记跨集合合成的来源集合及其产出所在系列（仅合约所有者，None 表示移除）
+    SetSynthesisSource { collection: String, series_id: Option<String> },
+    /// 为来源集合铸造跨集合合成的产出（仅已登记的来源集合，在合成时调用）
+    MintSynthesisOutput { owner: String, kind: NftKind },
+    /// 按类型权重和总量创建盲盒奖池（仅合约所有者）
+    CreateMintPool { pool_id: String, series_id: String, entries: Vec<PoolEntry>, size: u64 },
+    /// 关闭盲盒奖池，未抽取的数量作废（仅合约所有者）
+    CloseMintPool { pool_id: String },
+    /// 从奖池按剩余数量加权抽取类型并铸造（仅铸造者）
+    PoolMint { pool_id: String, owner: String },
+    /// 设置高稀有度合成的创作者版税（仅合约所有者，None 表示停止收取）
+    SetCraftingRoyalty { royalty: Option<crate::state::CraftingRoyalty> },
+    /// 设置元数据预言机公钥（仅合约所有者，None 表示移除）
+    SetMetaOracle { pubkey: Option<cosmwasm_std::Binary> },
+    /// 提交预言机签名的元数据更新（任何人都可提交）
+    UpdateMetaSigned {
+        token_id: u64,
+        payload: cosmwasm_std::Binary,
+        signature: cosmwasm_std::Binary,
+    },
+    /// 设置铸造凭证签名者公钥（仅合约所有者，None 表示停用中继铸造）
+    SetMintVoucherSigner { pubkey: Option<cosmwasm_std::Binary> },
+    /// 设置中继者的剩余铸造额度（仅合约所有者，0 表示清除）
+    SetRelayQuota { relayer: String, quota: u32 },
+    /// 提交签名的铸造凭证（仅中继铸造角色，消耗一次额度）
+    RelayMint {
+        payload: cosmwasm_std::Binary,
+        signature: cosmwasm_std::Binary,
+    },
+    /// 设置集合展示信息（仅合约所有者）
+    SetCollectionInfo { collection_info: crate::state::CollectionInfo },
+    /// 发布或清除维护公告（仅合约所有者，None 表示清除）
+    SetMaintenanceNotice { notice: Option<crate::state::MaintenanceNotice> },
+    /// 设置集合来源证明哈希（仅合约所有者，只能设置一次）
+    SetProvenanceHash { hash: String },
+    /// 注册系列所有权（仅授权铸造者，系列必须尚未注册）
+    RegisterSeries { series_id: String },
+    /// 授权其他铸造者向系列铸造（系列所有者或合约所有者）
+    GrantSeriesMinter { series_id: String, minter: String },
+    /// 撤销其他铸造者向系列铸造的授权（系列所有者或合约所有者）
+    RevokeSeriesMinter { series_id: String, minter: String },
+    /// 设置指定类型的铸造状态（仅合约所有者，Frozen 不可撤销）
+    SetKindMintStatus { kind: NftKind, status: crate::types::KindMintStatus },
+    /// 设置禁止接收 NFT 的地址（仅合约所有者）
+    SetBlockedRecipient { address: String, blocked: bool },
+    /// 设置仅合约所有者可铸造的保留 ID 区间（仅合约所有者，None 表示取消保留）
+    SetReservedIdRange { range: Option<crate::state::ReservedIdRange> },
+    /// 启用或停用确定性 NFT ID：ID 由系列 ID 和系列内序号的哈希推导（仅合约所有者）
+    SetDeterministicTokenIds { enabled: bool },
     
     
     // ========== 访问控制和紧急机制 ==========
@@ -102,8 +286,59 @@ pub enum ExecuteMsg {
     Pause {},
     /// 恢复合约
     Unpause {},
-    /// 紧急提取资金
+    /// 紧急提取资金（超过阈值时需守护者审批）
     EmergencyWithdraw { amount: Vec<cosmwasm_std::Coin> },
+    /// 设置紧急提取守护者（空列表表示关闭审批；已配置守护者时须经其审批后生效）
+    SetWithdrawGuardians {
+        guardians: Vec<String>,
+        required: u32,
+        threshold: Option<cosmwasm_std::Uint128>,
+    },
+    /// 守护者审批紧急提取
+    ApproveWithdrawal { id: u64 },
+    /// 守护者审批待生效的守护者配置变更
+    ApproveGuardianChange {},
+    /// 取消待审批的紧急提取
+    CancelWithdrawal { id: u64 },
+    /// 取消待审批的守护者配置变更
+    CancelGuardianChange {},
+    
+    // ========== 销毁监听 ==========
+    /// 添加销毁监听合约（仅合约所有者）
+    AddBurnListener { address: String },
+    /// 移除销毁监听合约（仅合约所有者）
+    RemoveBurnListener { address: String },
+    
+    // ========== 预付费用存款 ==========
+    /// 存入随消息附带的资金，用于支付合成等操作的费用
+    Deposit {},
+    /// 从存款余额中提取资金
+    Withdraw { amount: cosmwasm_std::Coin },
+    /// 设置接受的支付币种及单次最低数量，空列表表示不限制（仅合约所有者）
+    SetAcceptedDenoms { denoms: Vec<AcceptedDenom> },
+    
+    // ========== 存储维护 ==========
+    /// 分批将系列 ID 从 from_series 重命名为 to_series（仅合约所有者）
+    RetagSeries {
+        from_series: String,
+        to_series: String,
+        limit: Option<u32>,
+        cursor: Option<u64>,
+    },
+    /// 分批清理不再被引用的存储条目（无需权限）
+    GarbageCollect { limit: Option<u32> },
+    /// 分批关闭失效的互换报价并退回过期礼物（无需权限）
+    SweepExpired { limit: Option<u32> },
+    /// 设置每个所有者在时间窗口内的合成价值上限（仅合约所有者，None 表示不限制）
+    SetCraftingLimit { limit: Option<crate::state::CraftingLimit> },
+    /// 为旧部署分批重建二级索引（仅合约所有者，重复调用直至完成）
+    ApplyIndexUpgrade { limit: Option<u32> },
+    
+    // ========== 工厂模式 ==========
+    /// 以本合约的代码实例化子集合，继承铸造者和基础 URI（仅合约所有者）
+    CreateCollection { name: String, symbol: String, base_uri: Option<String> },
+    /// 以子集合所有者（本合约）的身份向子集合转发执行消息（仅合约所有者）
+    ExecuteOnCollection { collection: String, msg: cosmwasm_std::Binary },
 }
 
 // ========== 查询消息 ==========
@@ -117,15 +352,26 @@ pub enum QueryMsg {
     // ========== 标准 CW721 查询 ==========
     /// 查询 NFT 所有者信息
     #[returns(cw721::OwnerOfResponse)]
-    OwnerOf { token_id: u64, include_expired: Option<bool> },
+    OwnerOf {
+        #[serde(deserialize_with = "crate::helpers::deserialize_token_id")]
+        token_id: u64,
+        include_expired: Option<bool>,
+    },
     
     /// 查询 NFT 详细信息
     #[returns(cw721::NftInfoResponse<NftMeta>)]
-    NftInfo { token_id: u64 },
+    NftInfo {
+        #[serde(deserialize_with = "crate::helpers::deserialize_token_id")]
+        token_id: u64,
+    },
     
     /// 查询 NFT 批准信息
     #[returns(cw721::ApprovalsResponse)]
-    Approvals { token_id: u64, include_expired: Option<bool> },
+    Approvals {
+        #[serde(deserialize_with = "crate::helpers::deserialize_token_id")]
+        token_id: u64,
+        include_expired: Option<bool>,
+    },
     
     /// 查询操作员批准状态
     #[returns(cw721::OperatorResponse)]
@@ -133,15 +379,31 @@ pub enum QueryMsg {
     
     /// 查询 NFT URI 信息
     #[returns(cw721::NftInfoResponse<NftMeta>)]
-    TokenUri { token_id: u64 },
+    TokenUri {
+        #[serde(deserialize_with = "crate::helpers::deserialize_token_id")]
+        token_id: u64,
+    },
     
     /// 查询所有 NFT 列表
     #[returns(cw721::TokensResponse)]
-    AllTokens { start_after: Option<u64>, limit: Option<u32> },
+    AllTokens {
+        #[serde(default, deserialize_with = "crate::helpers::deserialize_optional_token_id")]
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
     
     /// 查询用户拥有的 NFT 列表
     #[returns(cw721::TokensResponse)]
-    Tokens { owner: String, start_after: Option<u64>, limit: Option<u32> },
+    Tokens {
+        owner: String,
+        #[serde(default, deserialize_with = "crate::helpers::deserialize_optional_token_id")]
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    
+    /// 查询主要铸造者（兼容 cw721-base），可选检查指定地址是否可铸造
+    #[returns(MinterResponse)]
+    Minter { address: Option<String> },
     
     // ========== Luckee 扩展查询 ==========
     /// 查询 NFT 扩展元数据
@@ -150,20 +412,216 @@ pub enum QueryMsg {
     
     /// 按类型查询 NFT 列表
     #[returns(TokensByKindResponse)]
-    TokensByKind { kind: NftKind, start_after: Option<u64>, limit: Option<u32> },
+    TokensByKind { kind: NftKind, start_after: Option<Cursor>, limit: Option<u32> },
     
     /// 按系列查询 NFT 列表
     #[returns(TokensBySeriesResponse)]
-    TokensBySeries { series_id: String, start_after: Option<u64>, limit: Option<u32> },
+    TokensBySeries { series_id: String, start_after: Option<Cursor>, limit: Option<u32> },
     
     /// 按组查询 NFT 列表
     #[returns(TokensByGroupResponse)]
-    TokensByGroup { group_id: String, start_after: Option<u64>, limit: Option<u32> },
+    TokensByGroup { group_id: String, start_after: Option<Cursor>, limit: Option<u32> },
+    
+    /// 查询用户拥有的 NFT 及其元数据和 URI（一次返回，减少查询次数）
+    #[returns(TokensWithMetaResponse)]
+    TokensWithMeta { owner: String, start_after: Option<Cursor>, limit: Option<u32> },
+    
+    /// 按是否过期查询 NFT 列表
+    #[returns(TokensByExpiryResponse)]
+    TokensByExpiry { expired: bool, start_after: Option<Cursor>, limit: Option<u32> },
+    
+    /// 查询最近的合约活动（按时间倒序）
+    #[returns(RecentActivityResponse)]
+    RecentActivity { limit: Option<u32> },
     
     /// 查询 Luckee 合约信息
     #[returns(LuckeeContractInfoResponse)]
     LuckeeContractInfo {},
     
+    /// 查询待生效的铸造者轮换
+    #[returns(PendingMinterResponse)]
+    PendingMinter {},
+    
+    /// 查询铸造者集合是否已锁定
+    #[returns(MinterSetLockResponse)]
+    MinterSetLock {},
+    
+    /// 查询事件详细程度
+    #[returns(EventVerbosityResponse)]
+    EventVerbosity {},
+    
+    /// 查询存储使用统计
+    #[returns(StorageStatsResponse)]
+    StorageStats {},
+    
+    /// 查询所有 token URI 模板
+    #[returns(UriTemplatesResponse)]
+    UriTemplates {},
+    
+    /// 查询合约版本与启用的能力
+    #[returns(VersionInfoResponse)]
+    VersionInfo {},
+    
+    /// 查询待销毁 NFT 信息
+    #[returns(PendingBurnResponse)]
+    PendingBurn { token_id: u64 },
+    
+    /// 查询销毁冷却配置
+    #[returns(BurnCooldownResponse)]
+    BurnCooldown {},
+    
+    /// 查询 NFT 的销毁申请
+    #[returns(BurnRequestResponse)]
+    BurnRequest { token_id: u64 },
+    
+    /// 查询旧版合约导入配置
+    #[returns(LegacyImportResponse)]
+    LegacyImport {},
+    
+    /// 查询旧版 NFT 导入后的 NFT ID
+    #[returns(LegacyTokenResponse)]
+    LegacyToken { legacy_contract: String, legacy_token_id: String },
+    
+    /// 查询紧急提取守护者配置
+    #[returns(WithdrawGuardiansResponse)]
+    WithdrawGuardians {},
+    
+    /// 查询待审批的紧急提取
+    #[returns(PendingWithdrawalResponse)]
+    PendingWithdrawal { id: u64 },
+    
+    /// 查询元数据预言机配置
+    #[returns(MetaOracleResponse)]
+    MetaOracle {},
+    
+    /// 查询预言机更新序号是否已使用
+    #[returns(MetaOracleNonceResponse)]
+    MetaOracleNonce { nonce: u64 },
+    
+    /// 查询中继者的角色和剩余铸造额度
+    #[returns(RelayQuotaResponse)]
+    RelayQuota { relayer: String },
+    
+    /// 查询铸造凭证序号是否已使用
+    #[returns(MintVoucherStatusResponse)]
+    MintVoucherStatus { nonce: u64 },
+    
+    /// 查询价格预言机配置
+    #[returns(PriceOracleResponse)]
+    PriceOracle {},
+    
+    /// 查询配方当前费用报价
+    #[returns(RecipeFeeResponse)]
+    RecipeFee { target: NftKind },
+    
+    /// 查询配方的合成销毁税
+    #[returns(BurnTaxResponse)]
+    BurnTax { target: NftKind },
+    
+    /// 查询合成销毁税的累计销毁统计
+    #[returns(BurnTaxStatsResponse)]
+    BurnTaxStats {},
+    
+    /// 查询合约资金：当前余额、按来源的累计收入和累计提取
+    #[returns(TreasuryResponse)]
+    Treasury {},
+    
+    /// 查询合成版税配置
+    #[returns(CraftingRoyaltyResponse)]
+    CraftingRoyalty {},
+    
+    /// 查询系列创建者累计获得的合成版税
+    #[returns(SeriesRoyaltiesResponse)]
+    SeriesRoyalties { series_id: String },
+    
+    /// 查询集合展示信息（兼容 sg721 前端）
+    #[returns(CollectionInfoResponse)]
+    CollectionInfo {},
+    
+    /// 查询维护公告
+    #[returns(MaintenanceInfoResponse)]
+    MaintenanceInfo {},
+    
+    /// 查询集合来源证明哈希
+    #[returns(ProvenanceHashResponse)]
+    ProvenanceHash {},
+    
+    /// 查询系列所有者和被授权的铸造者
+    #[returns(SeriesOwnerResponse)]
+    SeriesOwner { series_id: String },
+    
+    /// 按系列和系列内序号查询 NFT 所有者
+    #[returns(SerialOwnerResponse)]
+    SerialOwner { series_id: String, serial: u64 },
+    
+    /// 按系列和系列内序号查询 NFT（如 "spring2025 #42"）
+    #[returns(TokenBySerialResponse)]
+    TokenBySerial { series_id: String, serial: u64 },
+    
+    /// 查询 NFT 当前所有者及其取得时的区块高度和时间
+    #[returns(OwnedAtResponse)]
+    OwnedAt { token_id: u64 },
+    
+    /// 查询 NFT 的完整记录（所有者、元数据、批准、锁定和托管状态、索引），供客服排查
+    #[returns(TokenDebugResponse)]
+    TokenDebug { token_id: u64 },
+    
+    /// 批量验证地址是否持有一组 NFT（最多 100 个）
+    #[returns(VerifyOwnershipResponse)]
+    VerifyOwnership { owner: String, token_ids: Vec<u64> },
+    
+    /// 铸造预检：只读执行铸造校验（不含依赖发送者的铸造权限和系列权限）
+    #[returns(MintValidationResponse)]
+    ValidateMint { token_id: u64, owner: String, extension: NftMeta },
+    
+    /// 批量铸造预检：逐条返回校验结果（数量上限与批量铸造相同）
+    #[returns(ValidateBatchMintResponse)]
+    ValidateBatchMint { mints: Vec<BatchMintItem> },
+    
+    /// 查询铸造者的累计铸造数量和最近铸造时间
+    #[returns(MinterStatsResponse)]
+    MinterStats { minter: String },
+    
+    /// 查询指定类型的铸造状态
+    #[returns(KindMintStatusResponse)]
+    KindMintStatus { kind: NftKind },
+    
+    /// 查询 NFT 类型的稀有度和兑换价值
+    #[returns(KindInfoResponse)]
+    KindInfo { kind: NftKind },
+    
+    /// 查询全部 NFT 类型的稀有度和兑换价值
+    #[returns(KindListResponse)]
+    KindList {},
+    
+    /// 查询盲盒规模及其头奖类型
+    #[returns(ScaleInfoResponse)]
+    ScaleInfo {},
+    
+    /// 查询禁止接收 NFT 的地址列表
+    #[returns(BlockedRecipientsResponse)]
+    BlockedRecipients { start_after: Option<Cursor>, limit: Option<u32> },
+    
+    /// 查询保留 ID 区间和下一个自动分配的 ID
+    #[returns(ReservedIdRangeResponse)]
+    ReservedIdRange {},
+    
+    /// 查询确定性 NFT ID 模式及指定系列序号对应的 ID（serial 缺省时为下一个将分配的序号）
+    #[returns(DeterministicTokenIdResponse)]
+    DeterministicTokenId { series_id: String, serial: Option<u64> },
+    
+    /// 查询销毁监听合约列表
+    #[returns(BurnListenersResponse)]
+    BurnListeners {},
+    
+    /// 查询用户存款余额
+    #[returns(DepositBalanceResponse)]
+    DepositBalance { address: String, denom: Option<String> },
+
+    /// 查询接受的支付币种
+    #[returns(AcceptedDenomsResponse)]
+    AcceptedDenoms {},
+    
     // ========== 合成相关查询 ==========
     /// 查询合成配方
     #[returns(RecipeResponse)]
@@ -171,12 +629,144 @@ pub enum QueryMsg {
     
     /// 查询所有合成配方
     #[returns(AllRecipesResponse)]
-    AllRecipes { start_after: Option<NftKind>, limit: Option<u32> },
+    AllRecipes { start_after: Option<Cursor>, limit: Option<u32> },
+    
+    /// 以邻接表形式一次返回全部配方（输入类型 → 可合成的输出）
+    #[returns(RecipeGraphResponse)]
+    RecipeGraph {},
     
     /// 预览合成操作结果
     #[returns(SynthesisPreviewResponse)]
     SynthesisPreview { inputs: Vec<u64>, target: NftKind },
     
+    /// 查询所有者当前窗口的合成价值额度
+    #[returns(CraftingAllowanceResponse)]
+    CraftingAllowance { owner: String },
+    
+    /// 以假设库存模拟尚未保存的配方
+    #[returns(SimulateRecipeResponse)]
+    SimulateRecipe { recipe: Recipe, inventory: Vec<(NftKind, u32)> },
+    
+    /// 按链上配方链估算合成目标所需的基础类型数量（默认四叶草）
+    #[returns(CraftingCostResponse)]
+    CraftingCost { target: NftKind, from_kind: Option<NftKind> },
+    
+    /// 查询操作员批准的剩余转移次数
+    #[returns(OperatorBudgetResponse)]
+    OperatorBudget { owner: String, operator: String },
+    
+    /// 查询所有者授予的单个 NFT 批准和操作员批准
+    #[returns(GrantsByOwnerResponse)]
+    GrantsByOwner { owner: String, start_after: Option<Cursor>, limit: Option<u32> },
+    
+    /// 查询所有者批准给指定被批准者且仍有效的 NFT
+    #[returns(ApprovedTokensResponse)]
+    ApprovedTokens { owner: String, spender: String, start_after: Option<Cursor>, limit: Option<u32> },
+    
+    /// 查询受信任操作员注册表
+    #[returns(TrustedOperatorsResponse)]
+    TrustedOperators { owner: Option<String> },
+    
+    /// 查询运营角色成员
+    #[returns(RoleMembersResponse)]
+    RoleMembers { role: Role },
+    
+    /// 查询 NFT 的游戏会话锁定状态
+    #[returns(SessionLockResponse)]
+    SessionLock { token_id: u64 },
+    
+    /// 查询游戏会话内锁定中的 NFT
+    #[returns(SessionTokensResponse)]
+    SessionTokens { session_id: String },
+
+    /// 查询所有者授予某地址的会话密钥
+    #[returns(SessionKeyResponse)]
+    SessionKey { owner: String, key: String },
+    
+    /// 按期望类型查询开放的互换报价
+    #[returns(SwapOffersResponse)]
+    SwapOffers { wanted_kind: NftKind, start_after: Option<Cursor>, limit: Option<u32> },
+    
+    /// 按领取码哈希查询礼物
+    #[returns(GiftResponse)]
+    Gift { claim_hash: String },
+    
+    /// 查询 NFT 的托管标记
+    #[returns(CustodyTagResponse)]
+    CustodyTag { token_id: u64 },
+    
+    /// 按托管方分页列出已标记的 NFT
+    #[returns(CustodyTokensResponse)]
+    CustodyTokens { custodian: String, start_after: Option<Cursor>, limit: Option<u32> },
+    
+    /// 查询所有者的投票权（当前或指定快照高度）
+    #[returns(VotingPowerResponse)]
+    VotingPower { owner: String, at_snapshot: Option<u64> },
+    
+    /// 查询受托人获得的委托投票权（当前或指定快照高度）
+    #[returns(DelegatedPowerResponse)]
+    DelegatedPower { delegate: String, at_snapshot: Option<u64> },
+    
+    /// 查询委托人的受托人
+    #[returns(DelegationResponse)]
+    Delegation { delegator: String },
+    
+    /// 查询组合包
+    #[returns(BundleResponse)]
+    Bundle { bundle_id: u64 },
+    
+    /// 按持有者分页列出组合包
+    #[returns(BundlesResponse)]
+    Bundles { owner: String, start_after: Option<Cursor>, limit: Option<u32> },
+    
+    /// 查询 NFT 所在的组合包
+    #[returns(TokenBundleResponse)]
+    TokenBundle { token_id: u64 },
+    
+    /// 查询 NFT 的条件转移
+    #[returns(ConditionalTransferResponse)]
+    ConditionalTransfer { token_id: u64 },
+    
+    /// 查询盲盒奖池的各类型剩余数量
+    #[returns(MintPoolResponse)]
+    MintPool { pool_id: String },
+    
+    /// 查询配方的产出集合
+    #[returns(RecipeOutputCollectionResponse)]
+    RecipeOutputCollection { target: NftKind },
+    
+    /// 查询跨集合合成的来源集合登记
+    #[returns(SynthesisSourceResponse)]
+    SynthesisSource { collection: String },
+    
+    /// 查询共享配方注册表配置和缓存
+    #[returns(RecipeRegistryResponse)]
+    RecipeRegistry {},
+    
+    /// 查询工厂模式创建的子集合（按地址升序分页）
+    #[returns(CollectionsResponse)]
+    Collections { start_after: Option<Cursor>, limit: Option<u32> },
+    
+    /// 查询子集合记录
+    #[returns(CollectionResponse)]
+    Collection { address: String },
+    
+    /// 查询合作集合白名单
+    #[returns(PartnerCollectionsResponse)]
+    PartnerCollections {},
+    
+    /// 查询合作集合 NFT 的存入者
+    #[returns(PartnerDepositResponse)]
+    PartnerDeposit { collection: String, token_id: String },
+    
+    /// 查询按类型授权
+    #[returns(KindApprovalResponse)]
+    KindApproval { owner: String, operator: String, kind: NftKind },
+    
+    /// 查询用户当前可合成的目标类型
+    #[returns(SynthesizableTargetsResponse)]
+    SynthesizableTargets { owner: String },
+    
     // ========== CW721 集成查询 ==========
     /// 查询外部 CW721 合约地址
     #[returns(NftContractResponse)]
@@ -190,6 +780,13 @@ pub enum QueryMsg {
 
 // ========== 查询响应类型 ==========
 
+/// 分页游标
+/// 
+/// 对客户端不透明的 base64 字符串：取自上一页响应的 `next_cursor`，原样作为下一页的 `start_after`。
+/// 游标只对发出它的查询有效
+#[cw_serde]
+pub struct Cursor(pub cosmwasm_std::Binary);
+
 /// NFT 元数据查询响应
 #[cw_serde]
 pub struct TokenMetaResponse {
@@ -202,6 +799,56 @@ pub struct TokenMetaResponse {
 pub struct TokensByKindResponse {
     /// NFT ID 列表
     pub tokens: Vec<u64>,
+    /// 下一页游标，作为下一次查询的 `start_after`（已到末尾时为 None）
+    pub next_cursor: Option<Cursor>,
+    /// 是否还有下一页
+    pub has_more: bool,
+    /// 是否因扫描上限提前停止（本页可能少于请求数量，`has_more` 为 true 时应继续查询）
+    pub scan_truncated: bool,
+    /// 该类型的 NFT 总数（未维护计数时为 None）
+    pub total: Option<u64>,
+}
+
+/// 最近活动查询响应
+#[cw_serde]
+pub struct RecentActivityResponse {
+    /// 活动记录列表（最新的在前）
+    pub activities: Vec<crate::state::ActivityEntry>,
+}
+
+/// 带元数据的 NFT 条目
+#[cw_serde]
+pub struct TokenWithMeta {
+    /// NFT ID
+    pub token_id: u64,
+    /// NFT 扩展元数据
+    pub meta: NftMeta,
+    /// NFT URI（未设置基础 URI 时为 None）
+    pub token_uri: Option<String>,
+}
+
+/// 带元数据的 NFT 列表查询响应
+#[cw_serde]
+pub struct TokensWithMetaResponse {
+    /// NFT 条目列表
+    pub tokens: Vec<TokenWithMeta>,
+    /// 下一页游标，作为下一次查询的 `start_after`（已到末尾时为 None）
+    pub next_cursor: Option<Cursor>,
+    /// 是否还有下一页
+    pub has_more: bool,
+}
+
+/// 按是否过期查询 NFT 响应
+#[cw_serde]
+pub struct TokensByExpiryResponse {
+    /// NFT ID 列表
+    pub tokens: Vec<u64>,
+    /// 下一页游标，作为下一次查询的 `start_after`（已到末尾时为 None）
+    pub next_cursor: Option<Cursor>,
+    /// 是否还有下一页
+    pub has_more: bool,
+    /// 是否因扫描上限提前停止（本页可能少于请求数量，`has_more` 为 true 时应继续查询）
+    pub scan_truncated: bool,
 }
 
 /// 按系列查询 NFT 响应
@@ -209,6 +856,14 @@ pub struct TokensByKindResponse {
 pub struct TokensBySeriesResponse {
     /// NFT ID 列表
     pub tokens: Vec<u64>,
+    /// 下一页游标，作为下一次查询的 `start_after`（已到末尾时为 None）
+    pub next_cursor: Option<Cursor>,
+    /// 是否还有下一页
+    pub has_more: bool,
+    /// 是否因扫描上限提前停止（本页可能少于请求数量，`has_more` 为 true 时应继续查询）
+    pub scan_truncated: bool,
+    /// 该系列现存 NFT 总数（未跟踪计数的旧系列为 None）
+    pub total: Option<u64>,
 }
 
 /// 按组查询 NFT 响应
@@ -216,6 +871,14 @@ pub struct TokensBySeriesResponse {
 pub struct TokensByGroupResponse {
     /// NFT ID 列表
     pub tokens: Vec<u64>,
+    /// 下一页游标，作为下一次查询的 `start_after`（已到末尾时为 None）
+    pub next_cursor: Option<Cursor>,
+    /// 是否还有下一页
+    pub has_more: bool,
+    /// 是否因扫描上限提前停止（本页可能少于请求数量，`has_more` 为 true 时应继续查询）
+    pub scan_truncated: bool,
+    /// 该组的 NFT 总数（未维护计数时为 None）
+    pub total: Option<u64>,
 }
 
 /// Luckee 合约信息查询响应
@@ -233,6 +896,638 @@ pub struct LuckeeContractInfoResponse {
     pub total_supply: u64,
 }
 
+/// 合约版本信息查询响应
+#[cw_serde]
+pub struct VersionInfoResponse {
+    /// cw2 合约名称
+    pub contract_name: String,
+    /// cw2 合约版本
+    pub contract_version: String,
+    /// 存储版本
+    pub storage_version: String,
+    /// 当前部署启用的能力列表
+    pub capabilities: Vec<String>,
+}
+
+/// 待销毁 NFT 查询响应
+#[cw_serde]
+pub struct PendingBurnResponse {
+    /// 待销毁记录（如果存在）
+    pub pending: Option<crate::state::PendingBurn>,
+    /// 当前销毁宽限期（区块数，0 表示关闭）
+    pub grace_period: u64,
+}
+
+/// 销毁冷却配置查询响应
+#[cw_serde]
+pub struct BurnCooldownResponse {
+    /// 销毁冷却配置（None 表示关闭）
+    pub cooldown: Option<crate::state::BurnCooldown>,
+}
+
+/// 销毁申请查询响应
+#[cw_serde]
+pub struct BurnRequestResponse {
+    /// NFT ID
+    pub token_id: u64,
+    /// 销毁申请（如果存在）
+    pub request: Option<crate::state::BurnRequest>,
+}
+
+/// 紧急提取守护者配置查询响应
+#[cw_serde]
+pub struct WithdrawGuardiansResponse {
+    /// 守护者配置（未设置时为 None）
+    pub config: Option<crate::state::GuardianConfig>,
+    /// 待当前守护者审批的配置变更
+    pub pending_change: Option<crate::state::PendingGuardianChange>,
+}
+
+/// 待审批紧急提取查询响应
+#[cw_serde]
+pub struct PendingWithdrawalResponse {
+    /// 待审批提取记录（如果存在）
+    pub withdrawal: Option<crate::state::PendingWithdrawal>,
+}
+
+/// 元数据预言机配置查询响应
+#[cw_serde]
+pub struct MetaOracleResponse {
+    /// 预言机公钥（未设置时为 None）
+    pub pubkey: Option<cosmwasm_std::Binary>,
+}
+
+/// 预言机更新序号状态查询响应
+#[cw_serde]
+pub struct MetaOracleNonceResponse {
+    /// 更新序号
+    pub nonce: u64,
+    /// 是否已使用
+    pub spent: bool,
+}
+
+/// 价格预言机配置参数
+#[cw_serde]
+pub struct PriceOracleMsg {
+    /// 喂价合约地址
+    pub feed: String,
+    /// 结算使用的原生代币
+    pub denom: String,
+    /// 价格最长有效时间（秒）
+    pub max_age_seconds: u64,
+    /// 相对上次使用价格允许的最大偏离（基点，0 表示不检查）
+    pub max_deviation_bps: u16,
+}
+
+/// 旧版合约导入配置参数
+#[cw_serde]
+pub struct LegacyImportMsg {
+    /// 旧版 CW721 合约地址
+    pub contract: String,
+    /// 导入的 NFT 所属系列
+    pub series_id: String,
+    /// 旧版 `kind` 属性取值到 NFT 类型的映射
+    pub kind_map: Vec<crate::types::LegacyKindMapping>,
+}
+
+/// 旧版合约导入配置查询响应
+#[cw_serde]
+pub struct LegacyImportResponse {
+    /// 导入配置（未设置时为 None）
+    pub import: Option<crate::state::LegacyImportConfig>,
+}
+
+/// 旧版 NFT 导入查询响应
+#[cw_serde]
+pub struct LegacyTokenResponse {
+    /// 旧版合约地址
+    pub legacy_contract: String,
+    /// 旧版合约中的 token ID
+    pub legacy_token_id: String,
+    /// 导入后的 NFT ID（未导入时为 None）
+    pub token_id: Option<u64>,
+}
+
+/// 价格预言机配置查询响应
+#[cw_serde]
+pub struct PriceOracleResponse {
+    /// 预言机配置（未设置时为 None）
+    pub oracle: Option<crate::state::PriceOracleConfig>,
+    /// 上一次合成使用的喂价
+    pub last_price: Option<cosmwasm_std::Decimal>,
+}
+
+/// 配方费用报价查询响应
+#[cw_serde]
+pub struct RecipeFeeResponse {
+    /// 目标 NFT 类型
+    pub target: NftKind,
+    /// 美元费用（微美元，未设置时为 None）
+    pub usd_cost: Option<cosmwasm_std::Uint128>,
+    /// 当前应收取的原生代币费用
+    pub fee: Option<cosmwasm_std::Coin>,
+}
+
+/// 合成销毁税查询响应
+#[cw_serde]
+pub struct BurnTaxResponse {
+    /// 目标 NFT 类型
+    pub target: NftKind,
+    /// 销毁税规则（未设置时为 None）
+    pub tax: Option<crate::types::BurnTax>,
+}
+
+/// 累计销毁统计查询响应
+#[cw_serde]
+pub struct BurnTaxStatsResponse {
+    /// 作为销毁税额外消耗的 NFT 数量
+    pub tokens_burned: u64,
+    /// 额外消耗的 NFT 的兑换价值总和（四叶草数量）
+    pub value_burned: u64,
+    /// 作为销毁税收取的原生代币（按代币累计）
+    pub fees_burned: Vec<cosmwasm_std::Coin>,
+}
+
+/// 单个来源的累计收入
+#[cw_serde]
+pub struct FeeSourceTotal {
+    /// 费用来源（如 "synthesis"）
+    pub source: String,
+    /// 按币种累计的金额
+    pub amount: Vec<cosmwasm_std::Coin>,
+}
+
+/// 合约资金查询响应
+#[cw_serde]
+pub struct TreasuryResponse {
+    /// 合约当前余额（含用户预付存款）
+    pub balances: Vec<cosmwasm_std::Coin>,
+    /// 余额中属于用户存款、不可紧急提取的部分
+    pub reserved: Vec<cosmwasm_std::Coin>,
+    /// 按来源的累计收入
+    pub fees_collected: Vec<FeeSourceTotal>,
+    /// 累计通过紧急提取转出的资金
+    pub withdrawn: Vec<cosmwasm_std::Coin>,
+}
+
+/// 合成版税配置查询响应
+#[cw_serde]
+pub struct CraftingRoyaltyResponse {
+    /// 版税配置（未设置时为 None）
+    pub royalty: Option<crate::state::CraftingRoyalty>,
+}
+
+/// 系列累计版税查询响应
+#[cw_serde]
+pub struct SeriesRoyaltiesResponse {
+    /// 系列 ID
+    pub series_id: String,
+    /// 系列创建者（未注册时为 None）
+    pub creator: Option<String>,
+    /// 按代币累计获得的版税
+    pub earned: Vec<cosmwasm_std::Coin>,
+}
+
+/// 喂价合约查询消息
+#[cw_serde]
+pub enum PriceFeedQueryMsg {
+    /// 查询指定代币的价格
+    Price { denom: String },
+}
+
+/// 喂价合约查询响应
+#[cw_serde]
+pub struct PriceFeedResponse {
+    /// 每个最小单位代币对应的微美元数量
+    pub price: cosmwasm_std::Decimal,
+    /// 价格更新时间（秒）
+    pub updated_at: u64,
+}
+
+/// 预言机签名的元数据更新内容
+/// 
+/// `UpdateMetaSigned` 的 `payload` 为此结构的 JSON 编码，
+/// 预言机对 `sha256(payload)` 进行 secp256k1 签名
+#[cw_serde]
+pub struct OracleMetaPayload {
+    /// 本合约地址（防止跨合约重放）
+    pub contract: String,
+    /// 要更新的 NFT ID
+    pub token_id: u64,
+    /// 更新序号，每个序号只能使用一次，不要求连续
+    pub nonce: u64,
+    /// 过期时间（秒），到达后不能再提交
+    pub expires_at: u64,
+    /// 新的物理实物 SKU（如发货状态）
+    pub physical_sku: Option<String>,
+}
+
+/// 铸造凭证内容
+/// 
+/// `RelayMint` 的 `payload` 为此结构的 JSON 编码，
+/// 凭证签名者对 `sha256(payload)` 进行 secp256k1 签名
+#[cw_serde]
+pub struct MintVoucher {
+    /// 本合约地址（防止跨合约重放）
+    pub contract: String,
+    /// 接收 NFT 的用户地址
+    pub recipient: String,
+    /// 铸造的 NFT 类型
+    pub kind: NftKind,
+    /// 系列 ID
+    pub series_id: String,
+    /// 凭证序号，每个序号只能使用一次
+    pub nonce: u64,
+    /// 凭证失效时间（Unix 秒）
+    pub expires_at: u64,
+}
+
+/// 中继者额度查询响应
+#[cw_serde]
+pub struct RelayQuotaResponse {
+    /// 中继者地址
+    pub relayer: String,
+    /// 是否持有中继铸造角色
+    pub is_relayer: bool,
+    /// 剩余铸造额度
+    pub remaining: u32,
+}
+
+/// 铸造凭证使用状态查询响应
+#[cw_serde]
+pub struct MintVoucherStatusResponse {
+    /// 凭证序号
+    pub nonce: u64,
+    /// 是否已使用
+    pub spent: bool,
+}
+
+/// 待生效铸造者轮换查询响应
+#[cw_serde]
+pub struct PendingMinterResponse {
+    /// 待生效的轮换（不存在时为 None）
+    pub rotation: Option<crate::state::MinterRotation>,
+}
+
+/// 事件详细程度查询响应
+#[cw_serde]
+pub struct EventVerbosityResponse {
+    /// 事件详细程度
+    pub verbosity: EventVerbosity,
+}
+
+/// 铸造者集合锁定状态查询响应
+#[cw_serde]
+pub struct MinterSetLockResponse {
+    /// 是否已锁定
+    pub locked: bool,
+    /// 锁定时的区块高度（未锁定时为 None）
+    pub locked_at_height: Option<u64>,
+}
+
+/// 存储使用统计查询响应
+#[cw_serde]
+pub struct StorageStatsResponse {
+    /// 现存 NFT 数量
+    pub tokens: u64,
+    /// 持有批准列表的 NFT 数量
+    pub token_approvals: u64,
+    /// 操作员批准条目数量
+    pub operator_approvals: u64,
+    /// 合成配方数量
+    pub recipes: u64,
+    /// 合成历史记录数量
+    pub synthesis_history: u64,
+    /// 所有者索引条目数量（持有者数量）
+    pub owner_index: u64,
+}
+
+/// token URI 模板
+#[cw_serde]
+pub struct UriTemplate {
+    /// 基础 URI 协议前缀（如 `ipfs://`）
+    pub scheme: String,
+    /// URI 模板
+    pub template: String,
+}
+
+/// token URI 模板查询响应
+#[cw_serde]
+pub struct UriTemplatesResponse {
+    /// 已配置的模板列表
+    pub templates: Vec<UriTemplate>,
+}
+
+/// 维护公告查询响应
+#[cw_serde]
+pub struct MaintenanceInfoResponse {
+    /// 是否有生效中的维护公告
+    pub active: bool,
+    /// 公告内容（无公告时为 None）
+    pub message: Option<String>,
+    /// 预计结束时间（Unix 秒，可选）
+    pub ends_at: Option<u64>,
+    /// 合约当前是否已暂停
+    pub paused: bool,
+}
+
+/// 集合展示信息查询响应
+#[cw_serde]
+pub struct CollectionInfoResponse {
+    /// 集合创建者（合约所有者）
+    pub creator: String,
+    /// 集合描述
+    pub description: String,
+    /// 集合封面图片 URI
+    pub image: String,
+    /// 外部链接
+    pub external_link: Option<String>,
+    /// 社交链接列表
+    pub socials: Vec<crate::state::SocialLink>,
+}
+
+/// 来源证明哈希查询响应
+#[cw_serde]
+pub struct ProvenanceHashResponse {
+    /// 来源证明哈希（未设置时为 None）
+    pub hash: Option<String>,
+}
+
+/// 系列所有者查询响应
+#[cw_serde]
+pub struct SeriesOwnerResponse {
+    /// 系列 ID
+    pub series_id: String,
+    /// 系列所有者（未注册时为 None）
+    pub owner: Option<String>,
+    /// 被授权向该系列铸造的铸造者
+    pub grantees: Vec<String>,
+}
+
+/// 系列序号所有者查询响应
+#[cw_serde]
+pub struct SerialOwnerResponse {
+    /// 系列 ID
+    pub series_id: String,
+    /// 系列内序号
+    pub serial: u64,
+    /// 持有该序号的 NFT ID（不存在时为 None）
+    pub token_id: Option<u64>,
+    /// NFT 所有者（不存在时为 None）
+    pub owner: Option<String>,
+}
+
+/// 按系列序号查询 NFT 响应
+#[cw_serde]
+pub struct TokenBySerialResponse {
+    /// NFT ID
+    pub token_id: u64,
+    /// 所有者地址
+    pub owner: String,
+    /// NFT 元数据
+    pub meta: NftMeta,
+}
+
+/// 所有权证明查询响应
+#[cw_serde]
+pub struct OwnedAtResponse {
+    /// NFT ID
+    pub token_id: u64,
+    /// 当前所有者地址
+    pub owner: String,
+    /// 当前所有者取得该 NFT 的区块高度和时间（升级前取得的 NFT 为 None）
+    pub acquired: Option<crate::state::Acquisition>,
+}
+
+/// NFT 完整记录查询响应
+/// 
+/// 汇总各模块中与该 NFT 相关的存储，不存在的记录为 None；
+/// 合成来源见 `meta.crafted_from`
+#[cw_serde]
+pub struct TokenDebugResponse {
+    /// NFT ID
+    pub token_id: u64,
+    /// 当前所有者地址（不存在或已销毁时为 None）
+    pub owner: Option<String>,
+    /// NFT 元数据
+    pub meta: Option<NftMeta>,
+    /// 当前所有者取得该 NFT 的区块高度和时间
+    pub acquired: Option<crate::state::Acquisition>,
+    /// 单个 NFT 批准（含已过期的批准及其过期时间）
+    pub approvals: Vec<crate::state::Approval>,
+    /// 游戏会话锁定
+    pub session_lock: Option<crate::state::SessionLock>,
+    /// 销毁宽限期中的待销毁记录
+    pub pending_burn: Option<crate::state::PendingBurn>,
+    /// 销毁冷却中的销毁申请
+    pub burn_request: Option<crate::state::BurnRequest>,
+    /// 托管方的链下账户标记
+    pub custody_tag: Option<crate::state::CustodyTag>,
+    /// 作为礼物托管时的领取码哈希
+    pub gift_claim_hash: Option<String>,
+    /// 条件转移
+    pub conditional_transfer: Option<crate::state::ConditionalTransfer>,
+    /// 所在组合包 ID
+    pub bundle_id: Option<u64>,
+    /// 各索引中是否存在该 NFT
+    pub indexes: TokenIndexPresence,
+}
+
+/// NFT 在各索引中的存在情况
+/// 
+/// 与元数据和所有者一致时均为 true；未设置集合组时 `by_group` 为 None
+#[cw_serde]
+pub struct TokenIndexPresence {
+    /// 全部 NFT 列表
+    pub all_tokens: bool,
+    /// 所有者的 NFT 列表
+    pub owner_tokens: bool,
+    /// 按类型索引
+    pub by_kind: bool,
+    /// 按系列索引
+    pub by_series: bool,
+    /// 按系列序号索引（序号指向该 NFT）
+    pub by_serial: bool,
+    /// 按集合组索引
+    pub by_group: Option<bool>,
+}
+
+/// 合成结果数据
+/// 
+/// 合成成功时写入 Response.data，代用户调用合成的合约可在 reply 中直接解析，无需读取事件属性
+#[cw_serde]
+pub struct SynthesizeData {
+    /// 产出的 NFT ID
+    pub output_token_id: u64,
+    /// 产出 NFT 的所有者（输入 NFT 的所有者）
+    pub owner: String,
+    /// 合成目标类型
+    pub target: NftKind,
+    /// 被销毁的输入 NFT ID（与请求顺序一致）
+    pub burned_inputs: Vec<u64>,
+    /// 产出所在的关联集合（在本集合铸造时为 None）
+    #[serde(default)]
+    pub output_collection: Option<String>,
+}
+
+/// 批量所有权验证查询响应
+#[cw_serde]
+pub struct VerifyOwnershipResponse {
+    /// 被验证的所有者地址
+    pub owner: String,
+    /// 与请求顺序一致的逐个验证结果
+    pub owned: Vec<bool>,
+    /// 是否持有全部 NFT（请求为空时为 true）
+    pub all_owned: bool,
+}
+
+/// 铸造预检查询响应
+#[cw_serde]
+pub struct MintValidationResponse {
+    /// NFT ID
+    pub token_id: u64,
+    /// 是否通过全部校验
+    pub valid: bool,
+    /// 首个校验错误（通过时为 None）
+    pub error: Option<String>,
+}
+
+/// 批量铸造预检查询响应
+#[cw_serde]
+pub struct ValidateBatchMintResponse {
+    /// 与请求顺序一致的逐条结果
+    pub results: Vec<MintValidationResponse>,
+    /// 是否全部通过（请求为空时为 true）
+    pub all_valid: bool,
+}
+
+/// 铸造者统计查询响应
+#[cw_serde]
+pub struct MinterStatsResponse {
+    /// 铸造者地址
+    pub minter: String,
+    /// 累计铸造数量
+    pub total_minted: u64,
+    /// 最近一次铸造的区块高度（从未铸造时为 None）
+    pub last_mint_height: Option<u64>,
+    /// 最近一次铸造的区块时间戳（秒，从未铸造时为 None）
+    pub last_mint_time: Option<u64>,
+}
+
+/// NFT 类型信息查询响应
+#[cw_serde]
+pub struct KindInfoResponse {
+    /// NFT 类型
+    pub kind: NftKind,
+    /// 稀有度等级（0-8，越大越稀有）
+    pub rarity_level: u8,
+    /// 稀有度名称
+    pub rarity_name: String,
+    /// 兑换价值（四叶草数量）
+    pub exchange_value: u32,
+}
+
+/// NFT 类型列表查询响应
+#[cw_serde]
+pub struct KindListResponse {
+    /// 按稀有度从低到高排列的类型信息
+    pub kinds: Vec<KindInfoResponse>,
+}
+
+/// 盲盒规模信息
+#[cw_serde]
+pub struct ScaleInfo {
+    /// 盲盒规模
+    pub scale: Scale,
+    /// 头奖 NFT 类型
+    pub first_prize: NftKind,
+}
+
+/// 盲盒规模查询响应
+#[cw_serde]
+pub struct ScaleInfoResponse {
+    /// 按从小到大排列的规模信息
+    pub scales: Vec<ScaleInfo>,
+}
+
+/// 类型铸造状态查询响应
+#[cw_serde]
+pub struct KindMintStatusResponse {
+    /// NFT 类型
+    pub kind: NftKind,
+    /// 铸造状态
+    pub status: crate::types::KindMintStatus,
+}
+
+/// 保留 ID 区间查询响应
+#[cw_serde]
+pub struct ReservedIdRangeResponse {
+    /// 保留区间（未设置时为 None）
+    pub range: Option<crate::state::ReservedIdRange>,
+    /// 下一个自动分配的 NFT ID（已跳过保留区间）
+    pub next_auto_token_id: u64,
+}
+
+/// 确定性 NFT ID 查询响应
+#[cw_serde]
+pub struct DeterministicTokenIdResponse {
+    /// 是否启用确定性 ID
+    pub enabled: bool,
+    /// 系列 ID
+    pub series_id: String,
+    /// 系列内序号
+    pub serial: u64,
+    /// 由系列 ID 和序号推导出的 NFT ID（未启用时仅供参考）
+    pub token_id: u64,
+    /// 该 ID 是否已被占用
+    pub taken: bool,
+}
+
+/// 禁止接收地址列表查询响应
+#[cw_serde]
+pub struct BlockedRecipientsResponse {
+    /// 被禁止的接收地址列表
+    pub addresses: Vec<String>,
+    /// 下一页游标，作为下一次查询的 `start_after`（已到末尾时为 None）
+    pub next_cursor: Option<Cursor>,
+    /// 是否还有下一页
+    pub has_more: bool,
+}
+
+/// 销毁监听合约列表查询响应
+#[cw_serde]
+pub struct BurnListenersResponse {
+    /// 监听合约地址列表
+    pub listeners: Vec<String>,
+}
+
+/// 铸造者查询响应
+/// 
+/// 字段与 cw721-base 的 `MinterResponse` 保持兼容
+#[cw_serde]
+pub struct MinterResponse {
+    /// 主要铸造者地址
+    pub minter: Option<String>,
+    /// 查询地址是否有铸造权限（仅在查询时指定地址时返回）
+    pub is_allowed: Option<bool>,
+}
+
+/// 存款余额查询响应
+#[cw_serde]
+pub struct DepositBalanceResponse {
+    /// 用户地址
+    pub address: String,
+    /// 各币种存款余额
+    pub balances: Vec<cosmwasm_std::Coin>,
+}
+
+/// 接受的支付币种查询响应
+#[cw_serde]
+pub struct AcceptedDenomsResponse {
+    /// 接受的币种及单次最低数量（为空时不限制币种）
+    pub denoms: Vec<AcceptedDenom>,
+}
+
 /// 批量铸造项目
 #[cw_serde]
 pub struct BatchMintItem {
@@ -244,6 +1539,15 @@ pub struct BatchMintItem {
     pub extension: NftMeta,
 }
 
+/// 空投分发项目
+#[cw_serde]
+pub struct DistributeItem {
+    /// 接收者地址
+    pub recipient: String,
+    /// 合约托管的 NFT ID
+    pub token_id: u64,
+}
+
 /// 合成配方查询响应
 #[cw_serde]
 pub struct RecipeResponse {
@@ -256,6 +1560,41 @@ pub struct RecipeResponse {
 pub struct AllRecipesResponse {
     /// 配方列表（目标类型，配方）
     pub recipes: Vec<(NftKind, Recipe)>,
+    /// 下一页游标，作为下一次查询的 `start_after`（已到末尾时为 None）
+    pub next_cursor: Option<Cursor>,
+    /// 是否还有下一页
+    pub has_more: bool,
+    /// 配方总数
+    pub total: Option<u64>,
+}
+
+/// 配方图中的一条边（输入类型 → 输出类型）
+#[cw_serde]
+pub struct RecipeGraphEdge {
+    /// 输出类型
+    pub output: NftKind,
+    /// 单次合成需要的该输入数量
+    pub count: u32,
+    /// 配方的合成费用（可选）
+    pub cost: Option<cosmwasm_std::Coin>,
+}
+
+/// 配方图中的一个输入节点
+#[cw_serde]
+pub struct RecipeGraphNode {
+    /// 输入类型
+    pub input: NftKind,
+    /// 以该类型为输入的配方
+    pub outputs: Vec<RecipeGraphEdge>,
+}
+
+/// 配方图查询响应
+#[cw_serde]
+pub struct RecipeGraphResponse {
+    /// 按输入类型组织的邻接表
+    pub nodes: Vec<RecipeGraphNode>,
+    /// 配方总数
+    pub recipe_count: u32,
 }
 
 /// 合成预览查询响应
@@ -271,9 +1610,460 @@ pub struct SynthesisPreviewResponse {
     pub cost: Option<cosmwasm_std::Coin>,
 }
 
+/// 合成价值额度查询响应
+#[cw_serde]
+pub struct CraftingAllowanceResponse {
+    /// 上限配置（未设置时为 None）
+    pub limit: Option<crate::state::CraftingLimit>,
+    /// 当前窗口已产出的兑换价值
+    pub used: u64,
+    /// 当前窗口剩余额度（未设置上限时为 None）
+    pub remaining: Option<u64>,
+    /// 当前窗口结束时间（秒）
+    pub window_ends_at: Option<u64>,
+}
+
+/// 配方模拟查询响应
+#[cw_serde]
+pub struct SimulateRecipeResponse {
+    /// 库存是否满足配方（至少可合成一次）
+    pub matches: bool,
+    /// 库存最多可合成的次数
+    pub max_times: u32,
+    /// 合成一次还缺少的输入
+    pub missing: Vec<RecipeInput>,
+    /// 单次合成消耗的兑换价值（四叶草数量）
+    pub input_value: u64,
+    /// 单次合成产出的兑换价值（四叶草数量）
+    pub output_value: u64,
+    /// 单次合成的兑换价值变化（产出减消耗，负数表示合成亏损价值）
+    pub value_delta: i64,
+}
+
+/// 链式合成成本查询响应
+#[cw_serde]
+pub struct CraftingCostResponse {
+    /// 目标类型
+    pub target: NftKind,
+    /// 基础类型
+    pub from_kind: NftKind,
+    /// 合成一个目标所需的基础类型数量
+    pub required: u64,
+    /// 需要执行的合成次数（包括中间产物）
+    pub syntheses: u64,
+    /// 无法由基础类型合成、需要另外持有的输入
+    pub other_inputs: Vec<(NftKind, u64)>,
+}
+
+/// 操作员批准剩余次数查询响应
+#[cw_serde]
+pub struct OperatorBudgetResponse {
+    /// 剩余转移次数（未限制次数时为 None）
+    pub remaining: Option<u32>,
+}
+
+/// 单个 NFT 批准
+#[cw_serde]
+pub struct TokenApprovalGrant {
+    /// NFT ID
+    pub token_id: u64,
+    /// 被批准者地址
+    pub spender: String,
+    /// 批准过期时间（None 表示永不过期）
+    pub expires: Option<crate::state::Expiration>,
+}
+
+/// 操作员批准
+#[cw_serde]
+pub struct OperatorGrant {
+    /// 操作员地址
+    pub operator: String,
+    /// 批准过期时间
+    pub expires: crate::state::Expiration,
+    /// 剩余转移次数（未限制次数时为 None）
+    pub remaining: Option<u32>,
+}
+
+/// 按所有者查询批准的响应
+#[cw_serde]
+pub struct GrantsByOwnerResponse {
+    /// 所有者地址
+    pub owner: String,
+    /// 本页的单个 NFT 批准
+    pub token_approvals: Vec<TokenApprovalGrant>,
+    /// 操作员批准（仅第一页返回）
+    pub operators: Vec<OperatorGrant>,
+    /// 下一页游标，作为下一次查询的 `start_after`（已到末尾时为 None）
+    pub next_cursor: Option<Cursor>,
+    /// 是否还有下一页
+    pub has_more: bool,
+}
+
+/// 按被批准者查询已批准 NFT 的响应
+#[cw_serde]
+pub struct ApprovedTokensResponse {
+    /// 所有者地址
+    pub owner: String,
+    /// 被批准者地址
+    pub spender: String,
+    /// 本页已批准给被批准者的 NFT
+    pub tokens: Vec<TokenApprovalGrant>,
+    /// 下一页游标，作为下一次查询的 `start_after`（已到末尾时为 None）
+    pub next_cursor: Option<Cursor>,
+    /// 是否还有下一页
+    pub has_more: bool,
+    /// 是否因扫描上限提前停止（本页可能少于请求数量，`has_more` 为 true 时应继续查询）
+    pub scan_truncated: bool,
+}
+
+/// 受信任操作员注册表查询响应
+#[cw_serde]
+pub struct TrustedOperatorsResponse {
+    /// 注册表中的操作员
+    pub operators: Vec<String>,
+    /// 被全局停用的操作员
+    pub suspended: Vec<String>,
+    /// 指定用户是否选择信任注册表（未指定用户时为 None）
+    pub opted_in: Option<bool>,
+}
+
+/// 互换报价查询响应
+#[cw_serde]
+pub struct SwapOffersResponse {
+    /// 报价列表
+    pub offers: Vec<crate::state::SwapOffer>,
+    /// 下一页游标，作为下一次查询的 `start_after`（已到末尾时为 None）
+    pub next_cursor: Option<Cursor>,
+    /// 是否还有下一页
+    pub has_more: bool,
+}
+
+/// 运营角色成员查询响应
+#[cw_serde]
+pub struct RoleMembersResponse {
+    /// 角色
+    pub role: Role,
+    /// 成员地址列表
+    pub members: Vec<String>,
+}
+
+/// 会话锁定查询响应
+#[cw_serde]
+pub struct SessionLockResponse {
+    /// 生效中的锁定（未锁定或已到期时为 None）
+    pub lock: Option<crate::state::SessionLock>,
+}
+
+/// 会话内 NFT 查询响应
+#[cw_serde]
+pub struct SessionTokensResponse {
+    /// 会话 ID
+    pub session_id: String,
+    /// 锁定中的 NFT ID 列表
+    pub token_ids: Vec<u64>,
+}
+
+/// 会话密钥查询响应
+#[cw_serde]
+pub struct SessionKeyResponse {
+    /// 所有者地址
+    pub owner: String,
+    /// 会话密钥地址
+    pub key: String,
+    /// 生效中的授权（不存在或已过期时为 None）
+    pub grant: Option<crate::state::SessionKeyGrant>,
+}
+
+/// 礼物查询响应
+#[cw_serde]
+pub struct GiftResponse {
+    /// 礼物信息（不存在时为 None）
+    pub gift: Option<crate::state::Gift>,
+}
+
+/// 托管标记条目
+#[cw_serde]
+pub struct CustodyTagItem {
+    /// NFT ID
+    pub token_id: u64,
+    /// 链下账户引用的 SHA-256 哈希（十六进制，None 表示清除标记）
+    pub account_hash: Option<String>,
+}
+
+/// 托管标记查询响应
+#[cw_serde]
+pub struct CustodyTagResponse {
+    /// NFT ID
+    pub token_id: u64,
+    /// 托管标记（未标记时为 None）
+    pub tag: Option<crate::state::CustodyTag>,
+}
+
+/// 托管方已标记的 NFT
+#[cw_serde]
+pub struct CustodyTokenInfo {
+    /// NFT ID
+    pub token_id: u64,
+    /// 链下账户引用的 SHA-256 哈希
+    pub account_hash: String,
+    /// 标记时的区块时间（秒）
+    pub tagged_at: u64,
+}
+
+/// 托管方已标记 NFT 列表查询响应
+#[cw_serde]
+pub struct CustodyTokensResponse {
+    /// 托管方地址
+    pub custodian: String,
+    /// 已标记的 NFT（按 ID 升序）
+    pub tokens: Vec<CustodyTokenInfo>,
+    /// 下一页游标，作为下一次查询的 `start_after`（已到末尾时为 None）
+    pub next_cursor: Option<Cursor>,
+    /// 是否还有下一页
+    pub has_more: bool,
+}
+
+/// 投票权查询响应
+#[cw_serde]
+pub struct VotingPowerResponse {
+    /// 所有者地址
+    pub owner: String,
+    /// 投票权（持有 NFT 的兑换价值之和）
+    pub power: u64,
+    /// 快照高度（查询当前投票权时为 None）
+    pub height: Option<u64>,
+}
+
+/// 委托投票权查询响应
+#[cw_serde]
+pub struct DelegatedPowerResponse {
+    /// 受托人地址
+    pub delegate: String,
+    /// 委托投票权（各委托人投票权之和）
+    pub power: u64,
+    /// 快照高度（查询当前委托投票权时为 None）
+    pub height: Option<u64>,
+}
+
+/// 委托关系查询响应
+#[cw_serde]
+pub struct DelegationResponse {
+    /// 委托人地址
+    pub delegator: String,
+    /// 受托人地址（未委托时为 None）
+    pub delegate: Option<String>,
+}
+
+/// 条件转移查询响应
+#[cw_serde]
+pub struct ConditionalTransferResponse {
+    /// NFT ID
+    pub token_id: u64,
+    /// 条件转移（不存在时为 None）
+    pub transfer: Option<crate::state::ConditionalTransfer>,
+}
+
+/// 合作集合输入
+#[cw_serde]
+pub struct PartnerInput {
+    /// 外部 CW721 合约地址
+    pub collection: String,
+    /// 外部集合中的 NFT ID
+    pub token_id: String,
+}
+
+/// 合作集合信息
+#[cw_serde]
+pub struct PartnerCollectionInfo {
+    /// 外部 CW721 合约地址
+    pub collection: String,
+    /// 该集合 NFT 在配方中视为的类型
+    pub kind: NftKind,
+}
+
+/// 合作集合白名单查询响应
+#[cw_serde]
+pub struct PartnerCollectionsResponse {
+    /// 合作集合列表
+    pub collections: Vec<PartnerCollectionInfo>,
+}
+
+/// 合作集合存入查询响应
+#[cw_serde]
+pub struct PartnerDepositResponse {
+    /// 外部 CW721 合约地址
+    pub collection: String,
+    /// 外部集合中的 NFT ID
+    pub token_id: String,
+    /// 存入者（未存入时为 None）
+    pub depositor: Option<String>,
+}
+
+/// 组合包查询响应
+#[cw_serde]
+pub struct BundleResponse {
+    /// 组合包 ID
+    pub bundle_id: u64,
+    /// 组合包（不存在或已拆包时为 None）
+    pub bundle: Option<crate::state::Bundle>,
+}
+
+/// 持有者的组合包
+#[cw_serde]
+pub struct BundleInfo {
+    /// 组合包 ID
+    pub bundle_id: u64,
+    /// 托管的 NFT ID
+    pub token_ids: Vec<u64>,
+    /// 创建时的区块时间（秒）
+    pub created_at: u64,
+}
+
+/// 持有者组合包列表查询响应
+#[cw_serde]
+pub struct BundlesResponse {
+    /// 持有者地址
+    pub owner: String,
+    /// 组合包（按 ID 升序）
+    pub bundles: Vec<BundleInfo>,
+    /// 下一页游标，作为下一次查询的 `start_after`（已到末尾时为 None）
+    pub next_cursor: Option<Cursor>,
+    /// 是否还有下一页
+    pub has_more: bool,
+}
+
+/// NFT 所在组合包查询响应
+#[cw_serde]
+pub struct TokenBundleResponse {
+    /// NFT ID
+    pub token_id: u64,
+    /// 组合包 ID（未打包时为 None）
+    pub bundle_id: Option<u64>,
+}
+
+/// 按类型授权查询响应
+#[cw_serde]
+pub struct KindApprovalResponse {
+    /// 是否存在有效授权
+    pub approved: bool,
+    /// 授权过期时间（无有效授权时为 None）
+    pub expires: Option<Expiration>,
+}
+
+/// 可合成目标
+#[cw_serde]
+pub struct SynthesizableTarget {
+    /// 目标 NFT 类型
+    pub target: NftKind,
+    /// 按当前持有数量最多可合成的次数
+    pub max_times: u64,
+}
+
+/// 可合成目标查询响应
+#[cw_serde]
+pub struct SynthesizableTargetsResponse {
+    /// 用户地址
+    pub owner: String,
+    /// 可合成的目标列表
+    pub targets: Vec<SynthesizableTarget>,
+}
+
+/// 盲盒奖池查询响应
+#[cw_serde]
+pub struct MintPoolResponse {
+    /// 奖池 ID
+    pub pool_id: String,
+    /// 奖池（不存在时为 None）
+    pub pool: Option<crate::state::MintPool>,
+}
+
+/// 配方产出集合查询响应
+#[cw_serde]
+pub struct RecipeOutputCollectionResponse {
+    /// 目标类型
+    pub target: NftKind,
+    /// 关联集合（在本集合铸造时为 None）
+    pub collection: Option<String>,
+}
+
+/// 跨集合合成来源集合查询响应
+#[cw_serde]
+pub struct SynthesisSourceResponse {
+    /// 来源集合地址
+    pub collection: String,
+    /// 产出所在系列 ID（未登记时为 None）
+    pub series_id: Option<String>,
+}
+
+/// 缓存的注册表配方条目
+#[cw_serde]
+pub struct CachedRecipeEntry {
+    /// 目标类型
+    pub target: NftKind,
+    /// 缓存的配方
+    pub recipe: Recipe,
+    /// 读取时间（秒）
+    pub fetched_at: u64,
+}
+
+/// 共享配方注册表查询响应
+#[cw_serde]
+pub struct RecipeRegistryResponse {
+    /// 注册表配置（未设置时为 None）
+    pub registry: Option<crate::state::RecipeRegistryConfig>,
+    /// 缓存的注册表配方
+    pub cached: Vec<CachedRecipeEntry>,
+}
+
+/// 子集合条目
+#[cw_serde]
+pub struct CollectionEntry {
+    /// 子集合合约地址
+    pub address: String,
+    /// 子集合记录
+    pub collection: crate::state::ChildCollection,
+}
+
+/// 子集合列表查询响应
+#[cw_serde]
+pub struct CollectionsResponse {
+    /// 子集合（按地址升序）
+    pub collections: Vec<CollectionEntry>,
+    /// 下一页游标，作为下一次查询的 `start_after`（已到末尾时为 None）
+    pub next_cursor: Option<Cursor>,
+    /// 是否还有下一页
+    pub has_more: bool,
+}
+
+/// 子集合查询响应
+#[cw_serde]
+pub struct CollectionResponse {
+    /// 子集合合约地址
+    pub address: String,
+    /// 子集合记录（不是本合约创建的子集合时为 None）
+    pub collection: Option<crate::state::ChildCollection>,
+}
+
 /// 外部 NFT 合约查询响应
 #[cw_serde]
 pub struct NftContractResponse {
     /// 外部合约地址（如果设置）
     pub contract_addr: Option<String>,
 }
+
+// ========== 外部回调消息 ==========
+
+/// 销毁通知消息
+/// 
+/// NFT 最终销毁时发送给已注册的监听合约，监听合约需在其 ExecuteMsg 中实现此变体
+#[cw_serde]
+pub enum BurnHookMsg {
+    /// NFT 已被销毁
+    BurnNotification {
+        /// 被销毁的 NFT ID
+        token_id: u64,
+        /// 销毁前的最后所有者
+        owner: String,
+    },
+}
+
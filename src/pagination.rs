@@ -0,0 +1,241 @@
+//! 分页工具模块
+//! 
+//! 此模块提供各查询接口共用的分页逻辑，包括：
+//! - 数量限制：缺省时使用默认值，超出上限时截断
+//! - 起始边界：将 `start_after` 转换为不包含起点的存储边界
+//! - 内存列表分页：按 `start_after` 跳过并截取已排序的 ID 列表
+//! - 分页结果：按 `limit + 1` 读取，判断是否还有下一页并生成下一页游标
+//! - 扫描上限：过滤扫描每次最多访问固定数量的键，未扫完时返回部分结果和续扫游标
+//! - 游标编码：游标对客户端不透明（base64），内含查询标识和上一页最后一项的键
+
+use cosmwasm_std::{Binary, StdError, StdResult};
+use cw_storage_plus::{Bound, PrimaryKey};
+
+use crate::msg::Cursor;
+
+/// 默认每页数量
+pub const DEFAULT_LIMIT: u32 = 30;
+
+/// 每页数量上限
+pub const MAX_LIMIT: u32 = 30;
+
+/// 条目较小的列表（托管、组合包）的每页数量上限
+pub const MAX_WIDE_LIMIT: u32 = 100;
+
+/// 过滤扫描每次查询最多访问的键数量
+/// 
+/// 过滤条件很少命中时（如按类型扫描全部元数据），
+/// 限制单次查询的读取量，避免公共 RPC 节点被高开销查询拖垮
+pub const MAX_SCAN_KEYS: usize = 300;
+
+/// 计算每页数量
+/// 
+/// 至少为 1，避免空页无法给出下一页游标
+/// 
+/// # 参数
+/// - `limit`: 调用方请求的数量（None 时使用默认值）
+/// - `max`: 该查询允许的上限
+/// 
+/// # 返回值
+/// - `usize`: 实际每页数量
+pub fn clamp_limit(limit: Option<u32>, max: u32) -> usize {
+    limit.unwrap_or(DEFAULT_LIMIT).clamp(1, max) as usize
+}
+
+/// 构造分页起始边界
+/// 
+/// # 参数
+/// - `start_after`: 上一页最后一项的键（不包含）
+/// 
+/// # 返回值
+/// - `Option<Bound<K>>`: 存储范围的下界
+pub fn start_bound<'a, K: PrimaryKey<'a>>(start_after: Option<K>) -> Option<Bound<'a, K>> {
+    start_after.map(Bound::exclusive)
+}
+
+/// 对内存中的 ID 列表分页
+/// 
+/// 跳过不大于 `start_after` 的 ID，再截取 `limit` 个
+/// 
+/// # 参数
+/// - `ids`: 已排序的 ID 列表
+/// - `start_after`: 上一页最后一个 ID（不包含）
+/// - `limit`: 每页数量
+/// 
+/// # 返回值
+/// - `Vec<u64>`: 本页的 ID
+pub fn page_after(ids: Vec<u64>, start_after: Option<u64>, limit: usize) -> Vec<u64> {
+    ids.into_iter()
+        .filter(|id| start_after.map_or(true, |start| *id > start))
+        .take(limit)
+        .collect()
+}
+
+/// 将多读取一项的条目截断为一页
+/// 
+/// 调用方按 `limit + 1` 读取；多出的一项说明还有下一页，
+/// 此时以本页最后一项的键生成下一页游标
+/// 
+/// # 参数
+/// - `scope`: 查询的游标标识
+/// - `items`: 按 `limit + 1` 读取的条目
+/// - `limit`: 每页数量
+/// - `key`: 从条目提取分页键
+/// 
+/// # 返回值
+/// - `Page<T>`: 本页条目、下一页游标和是否还有下一页
+pub fn into_page<T, K: CursorKey>(scope: &str, mut items: Vec<T>, limit: usize, key: impl Fn(&T) -> K) -> Page<T> {
+    let has_more = items.len() > limit;
+    items.truncate(limit);
+    let next_cursor = if has_more {
+        items.last().map(|item| encode_cursor(scope, &key(item)))
+    } else {
+        None
+    };
+    Page { items, next_cursor, has_more, scan_truncated: false }
+}
+
+/// 带扫描上限的过滤分页
+/// 
+/// 按顺序访问条目并保留满足条件的 ID，找到 `limit + 1` 个时按 [`into_page`] 截断。
+/// 访问满 [`MAX_SCAN_KEYS`] 个键仍未找到足够条目且还有未访问的键时停止扫描，
+/// 以最后访问的键生成下一页游标，并标记为部分结果
+/// 
+/// # 参数
+/// - `scope`: 查询的游标标识
+/// - `entries`: 按 ID 升序的存储条目
+/// - `limit`: 每页数量
+/// - `matches`: 过滤条件
+/// 
+/// # 返回值
+/// - `StdResult<Page<u64>>`: 本页 ID；扫描被截断时 `scan_truncated` 为 true
+pub fn scan_page<V>(
+    scope: &str,
+    entries: impl Iterator<Item = StdResult<(u64, V)>>,
+    limit: usize,
+    matches: impl Fn(&V) -> bool,
+) -> StdResult<Page<u64>> {
+    let mut entries = entries.peekable();
+    let mut ids = vec![];
+    let mut last_scanned = None;
+    for entry in entries.by_ref().take(MAX_SCAN_KEYS) {
+        let (token_id, value) = entry?;
+        last_scanned = Some(token_id);
+        if matches(&value) {
+            ids.push(token_id);
+            if ids.len() > limit {
+                return Ok(into_page(scope, ids, limit, |token_id| *token_id));
+            }
+        }
+    }
+
+    if entries.peek().is_none() {
+        return Ok(into_page(scope, ids, limit, |token_id| *token_id));
+    }
+    Ok(Page {
+        items: ids,
+        next_cursor: last_scanned.map(|token_id| encode_cursor(scope, &token_id)),
+        has_more: true,
+        scan_truncated: true,
+    })
+}
+
+// ========== 游标编码 ==========
+
+/// 游标中查询标识与键之间的分隔字节
+const CURSOR_SEPARATOR: u8 = 0;
+
+/// 各分页查询的游标标识
+/// 
+/// 游标以发出它的查询标识开头，传给其他查询时解码失败
+pub mod cursor_scope {
+    pub const TOKENS_BY_KIND: &str = "tokens_by_kind";
+    pub const TOKENS_BY_SERIES: &str = "tokens_by_series";
+    pub const TOKENS_BY_GROUP: &str = "tokens_by_group";
+    pub const TOKENS_WITH_META: &str = "tokens_with_meta";
+    pub const TOKENS_BY_EXPIRY: &str = "tokens_by_expiry";
+    pub const BLOCKED_RECIPIENTS: &str = "blocked_recipients";
+    pub const ALL_RECIPES: &str = "all_recipes";
+    pub const GRANTS_BY_OWNER: &str = "grants_by_owner";
+    pub const SWAP_OFFERS: &str = "swap_offers";
+    pub const CUSTODY_TOKENS: &str = "custody_tokens";
+    pub const BUNDLES: &str = "bundles";
+    pub const COLLECTIONS: &str = "collections";
+    pub const APPROVED_TOKENS: &str = "approved_tokens";
+}
+
+/// 一页查询结果
+pub struct Page<T> {
+    /// 本页条目
+    pub items: Vec<T>,
+    /// 下一页游标（已到末尾时为 None）
+    pub next_cursor: Option<Cursor>,
+    /// 是否还有下一页
+    pub has_more: bool,
+    /// 是否因扫描上限提前停止（本页可能少于请求数量，应继续用游标查询）
+    pub scan_truncated: bool,
+}
+
+/// 可编码进游标的分页键
+pub trait CursorKey: Sized {
+    /// 编码为字节
+    fn to_cursor_bytes(&self) -> Vec<u8>;
+    /// 从字节解码，格式不符时返回 None
+    fn from_cursor_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+impl CursorKey for u64 {
+    fn to_cursor_bytes(&self) -> Vec<u8> {
+        self.to_be_bytes().to_vec()
+    }
+
+    fn from_cursor_bytes(bytes: &[u8]) -> Option<Self> {
+        bytes.try_into().ok().map(u64::from_be_bytes)
+    }
+}
+
+impl CursorKey for String {
+    fn to_cursor_bytes(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn from_cursor_bytes(bytes: &[u8]) -> Option<Self> {
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+/// 生成游标
+/// 
+/// # 参数
+/// - `scope`: 查询的游标标识
+/// - `key`: 本页最后一项的键
+/// 
+/// # 返回值
+/// - `Cursor`: 不透明游标
+pub fn encode_cursor<K: CursorKey>(scope: &str, key: &K) -> Cursor {
+    let mut bytes = scope.as_bytes().to_vec();
+    bytes.push(CURSOR_SEPARATOR);
+    bytes.extend(key.to_cursor_bytes());
+    Cursor(Binary::from(bytes))
+}
+
+/// 解码游标
+/// 
+/// # 参数
+/// - `scope`: 查询的游标标识
+/// - `cursor`: 调用方传入的 `start_after`
+/// 
+/// # 返回值
+/// - `StdResult<Option<K>>`: 上一页最后一项的键；游标格式错误或属于其他查询时返回错误
+pub fn decode_cursor<K: CursorKey>(scope: &str, cursor: Option<Cursor>) -> StdResult<Option<K>> {
+    let cursor = match cursor {
+        Some(cursor) => cursor,
+        None => return Ok(None),
+    };
+    let bytes = cursor.0.as_slice();
+    let key = bytes.strip_prefix(scope.as_bytes())
+        .and_then(|rest| rest.strip_prefix(&[CURSOR_SEPARATOR]))
+        .and_then(K::from_cursor_bytes)
+        .ok_or_else(|| StdError::generic_err(alloc::format!("Invalid cursor for {}", scope)))?;
+    Ok(Some(key))
+}
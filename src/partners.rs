@@ -0,0 +1,260 @@
+//! 合作集合模块
+//! 
+//! 此模块实现跨集合合成配方，包括：
+//! - 合作集合白名单：管理员登记外部 CW721 合约，并指定其 NFT 在配方中视为的类型
+//! - 存入：用户通过外部集合的 SendNft 将 NFT 存入本合约
+//! - 合成：存入的 NFT 按映射类型抵扣配方输入，合成时在外部集合中销毁
+//! - 取回：未使用的 NFT 由存入者取回
+//! 
+//! 存入记录以外部集合的回调为准，只接受白名单中的合约发来的 ReceiveNft
+
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, MessageInfo, Order, Response, StdResult, Storage, WasmMsg,
+};
+use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::msg::{PartnerCollectionInfo, PartnerCollectionsResponse, PartnerDepositResponse, PartnerInput};
+use crate::state::{PARTNER_COLLECTIONS, PARTNER_DEPOSITS};
+use crate::types::{NftKind, Recipe};
+
+// ========== 合作集合执行接口 ==========
+
+/// 设置合作集合
+/// 
+/// `kind` 为 None 时移出白名单；已存入的 NFT 仍可由存入者取回，但不能再用于合成。
+/// 只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `collection`: 外部 CW721 合约地址
+/// - `kind`: 该集合 NFT 在配方中视为的类型
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_partner_collection(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    collection: String,
+    kind: Option<NftKind>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let collection = deps.api.addr_validate(&collection)?;
+    let response = Response::new()
+        .add_attribute("action", "set_partner_collection")
+        .add_attribute("collection", collection.to_string());
+    match kind {
+        Some(kind) => {
+            PARTNER_COLLECTIONS.save(deps.storage, collection, &kind)?;
+            Ok(response.add_attribute("kind", kind.to_key()))
+        }
+        None => {
+            PARTNER_COLLECTIONS.remove(deps.storage, collection);
+            Ok(response.add_attribute("kind", "none"))
+        }
+    }
+}
+
+/// 接收合作集合 NFT
+/// 
+/// 由外部集合在 SendNft 时回调，发送者必须是白名单中的合约，
+/// 存入者为回调消息中的原所有者。拒绝时外部集合的转移一并回滚
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，发送者为外部集合合约
+/// - `msg`: CW721 回调消息
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 存入结果
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    let kind = PARTNER_COLLECTIONS.may_load(deps.storage, info.sender.clone())?
+        .ok_or_else(|| ContractError::InvalidPartnerNft { reason: "collection is not a partner".to_string() })?;
+    let depositor = deps.api.addr_validate(&msg.sender)?;
+    PARTNER_DEPOSITS.save(deps.storage, (info.sender.clone(), msg.token_id.clone()), &depositor)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "receive_partner_nft")
+        .add_attribute("collection", info.sender.to_string())
+        .add_attribute("token_id", msg.token_id)
+        .add_attribute("depositor", depositor.to_string())
+        .add_attribute("kind", kind.to_key()))
+}
+
+/// 取回存入的合作集合 NFT
+/// 
+/// 只有存入者可以取回，合约暂停期间也可取回
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `info`: 消息信息，发送者必须是存入者
+/// - `collection`: 外部 CW721 合约地址
+/// - `token_id`: 外部集合中的 NFT ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 取回结果，包含转回 NFT 的外部调用
+pub fn execute_withdraw_partner_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let key = (collection.clone(), token_id.clone());
+    let depositor = PARTNER_DEPOSITS.may_load(deps.storage, key.clone())?
+        .ok_or_else(|| ContractError::InvalidPartnerNft { reason: "token not deposited".to_string() })?;
+    if depositor != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    PARTNER_DEPOSITS.remove(deps.storage, key);
+
+    let transfer = WasmMsg::Execute {
+        contract_addr: collection.to_string(),
+        msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: depositor.to_string(),
+            token_id: token_id.clone(),
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(transfer)
+        .add_attribute("action", "withdraw_partner_nft")
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("token_id", token_id)
+        .add_attribute("depositor", depositor.to_string()))
+}
+
+// ========== 合成时的合作集合输入处理 ==========
+
+/// 验证合作集合输入
+/// 
+/// 每个输入必须来自白名单中的集合、已由所有者存入且不重复
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `owner`: 合成的所有者
+/// - `partner_inputs`: 合作集合输入
+/// 
+/// # 返回值
+/// - `Result<Vec<NftKind>, ContractError>`: 各输入映射的类型
+pub fn validate_partner_inputs(
+    deps: Deps,
+    owner: &Addr,
+    partner_inputs: &[PartnerInput],
+) -> Result<Vec<NftKind>, ContractError> {
+    let mut seen = alloc::collections::BTreeSet::new();
+    let mut kinds = Vec::with_capacity(partner_inputs.len());
+    for input in partner_inputs {
+        let collection = deps.api.addr_validate(&input.collection)?;
+        if !seen.insert((collection.clone(), input.token_id.clone())) {
+            return Err(ContractError::InvalidPartnerNft { reason: "duplicate partner input".to_string() });
+        }
+        let kind = PARTNER_COLLECTIONS.may_load(deps.storage, collection.clone())?
+            .ok_or_else(|| ContractError::InvalidPartnerNft { reason: "collection is not a partner".to_string() })?;
+        match PARTNER_DEPOSITS.may_load(deps.storage, (collection, input.token_id.clone()))? {
+            Some(depositor) if depositor == *owner => kinds.push(kind),
+            _ => return Err(ContractError::InvalidPartnerNft { reason: "token not deposited by owner".to_string() }),
+        }
+    }
+    Ok(kinds)
+}
+
+/// 按合作集合输入抵扣配方要求
+/// 
+/// 每个合作集合输入抵扣一个对应类型的输入要求，配方不需要的类型会被拒绝，
+/// 避免多余的外部 NFT 被无故销毁
+/// 
+/// # 参数
+/// - `recipe`: 合成配方（已按销毁税调整）
+/// - `partner_kinds`: 合作集合输入映射的类型
+/// 
+/// # 返回值
+/// - `Result<Recipe, ContractError>`: 本合约输入需要满足的配方
+pub fn partner_adjusted_recipe(recipe: &Recipe, partner_kinds: &[NftKind]) -> Result<Recipe, ContractError> {
+    let mut recipe = recipe.clone();
+    for kind in partner_kinds {
+        let input = recipe.inputs.iter_mut()
+            .find(|input| input.nft_kind == *kind && input.count > 0)
+            .ok_or_else(|| ContractError::InvalidPartnerNft {
+                reason: alloc::format!("recipe does not need more {}", kind.to_key()),
+            })?;
+        input.count -= 1;
+    }
+    recipe.inputs.retain(|input| input.count > 0);
+    Ok(recipe)
+}
+
+/// 消耗合作集合输入
+/// 
+/// 删除存入记录，并返回在外部集合中销毁这些 NFT 的调用
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `partner_inputs`: 已验证的合作集合输入
+/// 
+/// # 返回值
+/// - `Result<Vec<WasmMsg>, ContractError>`: 销毁外部 NFT 的调用
+pub fn consume_partner_inputs(
+    storage: &mut dyn Storage,
+    partner_inputs: &[PartnerInput],
+) -> Result<Vec<WasmMsg>, ContractError> {
+    let mut msgs = Vec::with_capacity(partner_inputs.len());
+    for input in partner_inputs {
+        PARTNER_DEPOSITS.remove(storage, (Addr::unchecked(input.collection.clone()), input.token_id.clone()));
+        msgs.push(WasmMsg::Execute {
+            contract_addr: input.collection.clone(),
+            msg: to_json_binary(&Cw721ExecuteMsg::Burn { token_id: input.token_id.clone() })?,
+            funds: vec![],
+        });
+    }
+    Ok(msgs)
+}
+
+// ========== 合作集合查询接口 ==========
+
+/// 查询合作集合白名单
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 合作集合地址及其映射类型列表
+pub fn query_partner_collections(deps: Deps) -> StdResult<Binary> {
+    let collections = PARTNER_COLLECTIONS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (collection, kind) = item?;
+            Ok(PartnerCollectionInfo { collection: collection.to_string(), kind })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&PartnerCollectionsResponse { collections })
+}
+
+/// 查询合作集合 NFT 的存入者
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `collection`: 外部 CW721 合约地址
+/// - `token_id`: 外部集合中的 NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 存入者（未存入时为 None）
+pub fn query_partner_deposit(deps: Deps, collection: String, token_id: String) -> StdResult<Binary> {
+    let collection_addr = deps.api.addr_validate(&collection)?;
+    let depositor = PARTNER_DEPOSITS.may_load(deps.storage, (collection_addr, token_id.clone()))?
+        .map(|addr| addr.to_string());
+    to_json_binary(&PartnerDepositResponse { collection, token_id, depositor })
+}
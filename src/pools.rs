@@ -0,0 +1,254 @@
+//! 盲盒奖池模块
+//! 
+//! 此模块实现链上执行的盲盒概率，包括：
+//! - 奖池定义：合约所有者按类型权重和奖池总量创建奖池
+//! - 奖池铸造：铸造者为用户抽取类型并铸造，扣减剩余数量
+//! - 关闭奖池：合约所有者删除奖池，未抽取的数量作废
+//! - 查询：奖池的各类型剩余数量
+//! 
+//! 抽取为不放回抽样：奖池总量按权重分配给各类型，每次按剩余数量加权抽取，
+//! 抽完时实际产出与公示的概率完全一致。随机数由区块高度、时间、交易序号和奖池状态哈希得到，
+//! 调用者可以在提交前模拟结果，因此奖池铸造只开放给受信任的铸造者（盲盒合约）。
+//! 
+//! 信任假设：铸造者能够决定每次抽取落到哪个接收者，合约只约束奖池的总体产出，
+//! 不防范铸造者挑选结果；需要对铸造者也不可预测时，应由盲盒合约接入随机信标
+
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use sha2::{Digest, Sha256};
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::events::emit_mint_event;
+use crate::helpers::{
+    allocate_token_id, assign_series_serial, authorize_series_mint, ensure_kind_mintable, is_authorized_minter,
+    record_activity, record_minter_stats, store_minted_token, validate_mint_owner, validate_series_id,
+};
+use crate::msg::MintPoolResponse;
+use crate::state::{MintPool, PoolSlot, MINT_POOLS};
+use crate::types::{NftMeta, PoolEntry, Scale};
+
+// ========== 奖池执行接口 ==========
+
+/// 创建奖池
+/// 
+/// 各类型的数量为 `size * weight / 权重之和`，须能整除，保证概率与公示一致。
+/// 奖池 ID 不能重复。只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `pool_id`: 奖池 ID
+/// - `series_id`: 铸造的 NFT 所属系列
+/// - `entries`: 各类型的权重
+/// - `size`: 奖池总量
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 创建结果
+pub fn execute_create_mint_pool(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    pool_id: String,
+    series_id: String,
+    entries: Vec<PoolEntry>,
+    size: u64,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    validate_series_id(&pool_id)?;
+    validate_series_id(&series_id)?;
+    if MINT_POOLS.has(deps.storage, pool_id.clone()) {
+        return Err(ContractError::InvalidMintPool { reason: "pool already exists".to_string() });
+    }
+    if entries.is_empty() || size == 0 {
+        return Err(ContractError::InvalidMintPool { reason: "empty pool".to_string() });
+    }
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.weight == 0 {
+            return Err(ContractError::InvalidMintPool { reason: alloc::format!("zero weight for {}", entry.kind.to_key()) });
+        }
+        if entries[..index].iter().any(|other| other.kind == entry.kind) {
+            return Err(ContractError::InvalidMintPool { reason: alloc::format!("duplicate kind {}", entry.kind.to_key()) });
+        }
+    }
+
+    // 按权重分配数量，不能整除时拒绝
+    let total_weight: u128 = entries.iter().map(|entry| entry.weight as u128).sum();
+    let mut slots = vec![];
+    for entry in entries {
+        let scaled = size as u128 * entry.weight as u128;
+        if scaled % total_weight != 0 {
+            return Err(ContractError::InvalidMintPool {
+                reason: alloc::format!("size does not split exactly by weight for {}", entry.kind.to_key()),
+            });
+        }
+        slots.push(PoolSlot { kind: entry.kind, weight: entry.weight, remaining: (scaled / total_weight) as u64 });
+    }
+
+    MINT_POOLS.save(deps.storage, pool_id.clone(), &MintPool { series_id: series_id.clone(), slots, size, remaining: size })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_mint_pool")
+        .add_attribute("pool_id", pool_id)
+        .add_attribute("series_id", series_id)
+        .add_attribute("size", size.to_string()))
+}
+
+/// 关闭奖池
+/// 
+/// 删除奖池，未抽取的数量作废。只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `pool_id`: 奖池 ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 关闭结果
+pub fn execute_close_mint_pool(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    pool_id: String,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let pool = MINT_POOLS.may_load(deps.storage, pool_id.clone())?
+        .ok_or_else(|| ContractError::InvalidMintPool { reason: "pool not found".to_string() })?;
+    MINT_POOLS.remove(deps.storage, pool_id.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "close_mint_pool")
+        .add_attribute("pool_id", pool_id)
+        .add_attribute("discarded", pool.remaining.to_string()))
+}
+
+/// 从奖池抽取并铸造
+/// 
+/// 按剩余数量加权抽取一个类型，扣减数量后铸造给 `owner`，ID 自动分配。
+/// 只有铸造者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于生成随机数
+/// - `info`: 消息信息，包含发送者
+/// - `pool_id`: 奖池 ID
+/// - `owner`: 新 NFT 的所有者地址
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 铸造结果
+pub fn execute_pool_mint(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    pool_id: String,
+    owner: String,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    if !is_authorized_minter(deps.as_ref(), &info.sender, &ctx.config)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    let mut pool = MINT_POOLS.may_load(deps.storage, pool_id.clone())?
+        .ok_or_else(|| ContractError::InvalidMintPool { reason: "pool not found".to_string() })?;
+    if pool.remaining == 0 {
+        return Err(ContractError::MintPoolExhausted { pool_id });
+    }
+
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    validate_mint_owner(deps.storage, &env.contract.address, &owner_addr)?;
+    authorize_series_mint(deps.storage, &pool.series_id, &info.sender)?;
+
+    // 按剩余数量加权抽取
+    let mut draw = pool_random(&env, &pool_id, &pool, &owner) % pool.remaining;
+    let slot = pool.slots.iter_mut()
+        .find(|slot| {
+            if draw < slot.remaining {
+                return true;
+            }
+            draw -= slot.remaining;
+            false
+        })
+        .ok_or(ContractError::Overflow {})?;
+    slot.remaining -= 1;
+    let kind = slot.kind.clone();
+    pool.remaining -= 1;
+    ensure_kind_mintable(deps.storage, &kind)?;
+    MINT_POOLS.save(deps.storage, pool_id.clone(), &pool)?;
+
+    // 分配 ID 和序号并保存
+    let serial_in_series = assign_series_serial(deps.storage, &pool.series_id)?;
+    let token_id = allocate_token_id(deps.storage, &pool.series_id, serial_in_series)?;
+    let meta = NftMeta {
+        kind,
+        scale_origin: Scale::Tiny,
+        physical_sku: None,
+        crafted_from: None,
+        series_id: pool.series_id.clone(),
+        collection_group_id: None,
+        serial_in_series,
+        expires_at: None,
+    };
+    let new_supply = store_minted_token(deps.storage, &env, token_id, &owner_addr, &meta)?;
+
+    record_activity(deps.storage, &env, "mint", Some(token_id), &info.sender)?;
+    record_minter_stats(deps.storage, &env, &info.sender, 1)?;
+
+    let kind = alloc::format!("{:?}", meta.kind);
+    Ok(Response::new()
+        .add_attribute("action", "pool_mint")
+        .add_attribute("pool_id", pool_id)
+        .add_attribute("token_id", token_id.to_string())
+        .add_attribute("owner", owner_addr.to_string())
+        .add_attribute("kind", kind.clone())
+        .add_attribute("pool_remaining", pool.remaining.to_string())
+        // 抽取结果在提交前可被模拟，供索引器和审计方区分于不可预测的随机铸造
+        .add_attribute("draw_predictable", "true")
+        .add_event(emit_mint_event(token_id, owner_addr.as_str(), &kind, new_supply)))
+}
+
+/// 生成奖池抽取的随机数
+/// 
+/// 哈希区块高度、时间、交易序号、合约地址、奖池 ID、剩余总量和接收者，
+/// 同一交易内的连续抽取因剩余总量不同而得到不同结果。
+/// 
+/// 这些输入在提交前均可预知，不是不可预测的随机源：铸造者可以模拟结果，
+/// 并通过选择提交时机、接收者或放弃提交来影响单次抽取的类型。
+/// 不放回抽样只保证奖池抽完时的总体分布，单次抽取的公平性依赖铸造者可信
+fn pool_random(env: &Env, pool_id: &str, pool: &MintPool, owner: &str) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(env.block.height.to_be_bytes());
+    hasher.update(env.block.time.nanos().to_be_bytes());
+    hasher.update(env.transaction.as_ref().map_or(0, |tx| tx.index).to_be_bytes());
+    hasher.update(env.contract.address.as_bytes());
+    hasher.update(pool_id.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(pool.remaining.to_be_bytes());
+    hasher.update(owner.as_bytes());
+    let digest = hasher.finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(bytes)
+}
+
+// ========== 奖池查询接口 ==========
+
+/// 查询奖池
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `pool_id`: 奖池 ID
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 奖池（不存在时为 None）
+pub fn query_mint_pool(deps: Deps, pool_id: String) -> StdResult<Binary> {
+    let pool = MINT_POOLS.may_load(deps.storage, pool_id.clone())?;
+    to_json_binary(&MintPoolResponse { pool_id, pool })
+}
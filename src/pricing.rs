@@ -0,0 +1,251 @@
+//! 合成费用定价模块
+//! 
+//! 此模块实现以美元计价的合成费用，包括：
+//! - 价格预言机配置（喂价合约地址、结算代币、容忍参数）
+//! - 配方美元费用设置
+//! - 执行时通过喂价合约将美元费用换算为原生代币
+//! - 费用报价查询
+
+use cosmwasm_std::{
+    to_json_binary, Binary, Coin, Decimal, Deps, DepsMut, Env, MessageInfo, QuerierWrapper, Response,
+    StdResult, Storage, Uint128,
+};
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::msg::{PriceFeedQueryMsg, PriceFeedResponse, PriceOracleMsg, PriceOracleResponse, RecipeFeeResponse};
+use crate::recipes::lookup_recipe;
+use crate::state::{PriceOracleConfig, LAST_ORACLE_PRICE, PRICE_ORACLE, RECIPES, RECIPE_USD_COSTS};
+use crate::types::{NftKind, Recipe};
+
+/// 价格偏离容忍度的最大值（100%）
+pub const MAX_DEVIATION_BPS: u16 = 10_000;
+
+// ========== 定价执行接口 ==========
+
+/// 设置价格预言机
+/// 
+/// 配置用于换算美元费用的喂价合约，None 表示移除。
+/// 每次设置都会清除上一次使用的价格，偏离检查从新价格重新开始
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `oracle`: 预言机配置
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_price_oracle(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    oracle: Option<PriceOracleMsg>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    LAST_ORACLE_PRICE.remove(deps.storage);
+
+    let feed = match oracle {
+        Some(oracle) => {
+            if oracle.denom.is_empty() {
+                return Err(ContractError::InvalidOraclePrice { reason: "empty denom".to_string() });
+            }
+            if oracle.max_age_seconds == 0 {
+                return Err(ContractError::InvalidOraclePrice { reason: "max age must be positive".to_string() });
+            }
+            if oracle.max_deviation_bps > MAX_DEVIATION_BPS {
+                return Err(ContractError::InvalidOraclePrice { reason: "deviation tolerance above 100%".to_string() });
+            }
+            let config = PriceOracleConfig {
+                feed: deps.api.addr_validate(&oracle.feed)?,
+                denom: oracle.denom,
+                max_age_seconds: oracle.max_age_seconds,
+                max_deviation_bps: oracle.max_deviation_bps,
+            };
+            PRICE_ORACLE.save(deps.storage, &config)?;
+            config.feed.to_string()
+        }
+        None => {
+            PRICE_ORACLE.remove(deps.storage);
+            String::new()
+        }
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "set_price_oracle")
+        .add_attribute("feed", feed))
+}
+
+/// 设置配方美元费用
+/// 
+/// 为目标类型的配方设置以美元计价的费用（6 位小数，即微美元），None 表示移除。
+/// 设置后合成时优先使用美元费用，配方中的原生费用不再生效
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `target`: 目标 NFT 类型
+/// - `usd_cost`: 美元费用（微美元）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_recipe_usd_cost(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    target: NftKind,
+    usd_cost: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    if !RECIPES.has(deps.storage, target.to_key()) {
+        return Err(ContractError::RecipeNotFound {});
+    }
+
+    match usd_cost {
+        Some(cost) if !cost.is_zero() => RECIPE_USD_COSTS.save(deps.storage, target.to_key(), &cost)?,
+        _ => RECIPE_USD_COSTS.remove(deps.storage, target.to_key()),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_recipe_usd_cost")
+        .add_attribute("target", alloc::format!("{:?}", target))
+        .add_attribute("usd_cost", usd_cost.unwrap_or_default().to_string()))
+}
+
+// ========== 费用换算函数 ==========
+
+/// 报价配方费用
+/// 
+/// 若配方设置了美元费用，则查询喂价合约并换算为原生代币（向上取整），
+/// 同时检查价格是否过期以及相对上次使用价格的偏离是否超出容忍度
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `querier`: 查询接口
+/// - `env`: 环境信息，用于检查价格时效
+/// - `target`: 目标 NFT 类型
+/// 
+/// # 返回值
+/// - `Result<Option<(Coin, Decimal)>, ContractError>`: 换算后的费用和使用的价格，未设置美元费用时为 None
+pub fn quote_usd_fee(
+    storage: &dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    target: &NftKind,
+) -> Result<Option<(Coin, Decimal)>, ContractError> {
+    let usd_cost = match RECIPE_USD_COSTS.may_load(storage, target.to_key())? {
+        Some(cost) => cost,
+        None => return Ok(None),
+    };
+    let oracle = PRICE_ORACLE.may_load(storage)?
+        .ok_or(ContractError::PriceOracleNotConfigured {})?;
+
+    // 查询喂价：价格为每个最小单位原生代币对应的微美元数量
+    let feed: PriceFeedResponse = querier.query_wasm_smart(
+        oracle.feed.to_string(),
+        &PriceFeedQueryMsg::Price { denom: oracle.denom.clone() },
+    )?;
+    if feed.price.is_zero() {
+        return Err(ContractError::InvalidOraclePrice { reason: "zero price".to_string() });
+    }
+
+    // 检查价格时效
+    let now = env.block.time.seconds();
+    if now > feed.updated_at.saturating_add(oracle.max_age_seconds) {
+        return Err(ContractError::StaleOraclePrice {
+            updated_at: feed.updated_at,
+            max_age_seconds: oracle.max_age_seconds,
+        });
+    }
+
+    // 检查相对上次使用价格的偏离（容忍度为 0 时不检查）
+    if oracle.max_deviation_bps > 0 {
+        if let Some(last) = LAST_ORACLE_PRICE.may_load(storage)? {
+            let deviation = feed.price.abs_diff(last) / last;
+            if deviation > Decimal::from_ratio(oracle.max_deviation_bps, MAX_DEVIATION_BPS) {
+                return Err(ContractError::OraclePriceDeviation {
+                    last: last.to_string(),
+                    current: feed.price.to_string(),
+                });
+            }
+        }
+    }
+
+    // 换算原生代币数量（向上取整，避免少收费用）
+    let amount = Decimal::from_atomics(usd_cost, 0)
+        .ok()
+        .and_then(|cost| cost.checked_div(feed.price).ok())
+        .ok_or(ContractError::Overflow {})?
+        .to_uint_ceil();
+
+    Ok(Some((Coin { denom: oracle.denom, amount }, feed.price)))
+}
+
+/// 确定合成费用
+/// 
+/// 优先使用美元费用换算的原生代币，并记录本次使用的价格；否则使用配方中的原生费用
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `querier`: 查询接口
+/// - `env`: 环境信息
+/// - `target`: 目标 NFT 类型
+/// - `recipe`: 合成配方
+/// 
+/// # 返回值
+/// - `Result<Option<Coin>, ContractError>`: 需要收取的费用
+pub fn resolve_recipe_fee(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    target: &NftKind,
+    recipe: &Recipe,
+) -> Result<Option<Coin>, ContractError> {
+    match quote_usd_fee(storage, querier, env, target)? {
+        Some((fee, price)) => {
+            LAST_ORACLE_PRICE.save(storage, &price)?;
+            Ok(Some(fee))
+        }
+        None => Ok(recipe.cost.clone()),
+    }
+}
+
+// ========== 定价查询接口 ==========
+
+/// 查询价格预言机配置
+pub fn query_price_oracle(deps: Deps) -> StdResult<Binary> {
+    to_json_binary(&PriceOracleResponse {
+        oracle: PRICE_ORACLE.may_load(deps.storage)?,
+        last_price: LAST_ORACLE_PRICE.may_load(deps.storage)?,
+    })
+}
+
+/// 查询配方当前费用报价
+/// 
+/// 返回按当前喂价换算的费用；预言机不可用或价格过期时查询失败
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和查询访问
+/// - `env`: 环境信息
+/// - `target`: 目标 NFT 类型
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 费用报价响应
+pub fn query_recipe_fee(deps: Deps, env: Env, target: NftKind) -> StdResult<Binary> {
+    let (recipe, _) = lookup_recipe(deps, &env, &target)?;
+    let recipe = recipe.ok_or_else(|| cosmwasm_std::StdError::not_found("recipe"))?;
+    let usd_cost = RECIPE_USD_COSTS.may_load(deps.storage, target.to_key())?;
+    let fee = match quote_usd_fee(deps.storage, &deps.querier, &env, &target)
+        .map_err(|err| cosmwasm_std::StdError::generic_err(err.to_string()))?
+    {
+        Some((fee, _)) => Some(fee),
+        None => recipe.cost,
+    };
+
+    to_json_binary(&RecipeFeeResponse { target, usd_cost, fee })
+}
@@ -2,10 +2,16 @@
 //! 
 //! 此模块包含合成配方的初始化和相关辅助函数
 //! 定义了从基础 NFT 到高级 NFT 的合成路径
+//! 
+//! 多集合部署中可指定共享配方注册表：合成时优先使用注册表的配方并在本地缓存，
+//! 注册表没有该配方时使用本地配方
 
-use cosmwasm_std::Storage;
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage};
+use crate::context::ExecuteContext;
 use crate::error::ContractError;
-use crate::state::RECIPES;
+use crate::helpers::{storage_counter, track_storage_entry};
+use crate::msg::{CachedRecipeEntry, QueryMsg, RecipeRegistryResponse, RecipeResponse};
+use crate::state::{CachedRecipe, RecipeRegistryConfig, RECIPES, RECIPE_CACHE, RECIPE_REGISTRY, STORAGE_COUNTERS};
 use crate::types::{NftKind, Recipe, RecipeInput};
 
 // ========== 配方初始化函数 ==========
@@ -92,5 +98,210 @@ pub fn initialize_default_recipes(storage: &mut dyn Storage) -> Result<(), Contr
     };
     RECIPES.save(storage, NftKind::Genesis.to_key(), &genesis_recipe)?;
 
+    // 同步配方计数器
+    let recipe_count = RECIPES.keys(storage, None, None, Order::Ascending).count() as u64;
+    STORAGE_COUNTERS.save(storage, storage_counter::RECIPES.to_string(), &recipe_count)?;
+
     Ok(())
 }
+
+// ========== 配方重置 ==========
+
+/// 单次重置最多删除的配方数量
+const MAX_RECIPE_RESET_BATCH: usize = 30;
+
+/// 紧急清空合成配方
+/// 
+/// 分批删除全部配方，全部删除后可选择重新写入默认配方，用于快速回滚配置错误的配方。
+/// 配方较多时需重复调用直到响应中 `complete` 为 true；暂停期间也可执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `ctx`: 执行上下文，包含配置
+/// - `info`: 消息信息，包含发送者
+/// - `install_defaults`: 清空完成后是否重新写入默认配方
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 本批删除数量和是否完成
+pub fn execute_reset_recipes(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    install_defaults: bool,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    // 每批删除最前面的配方，无需记录位置
+    let keys = RECIPES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(MAX_RECIPE_RESET_BATCH)
+        .collect::<StdResult<Vec<_>>>()?;
+    for key in &keys {
+        RECIPES.remove(deps.storage, key.clone());
+        track_storage_entry(deps.storage, storage_counter::RECIPES, true, false)?;
+    }
+
+    // 全部清空后再写入默认配方，避免与未删除的旧配方混合
+    let complete = RECIPES.keys(deps.storage, None, None, Order::Ascending).next().is_none();
+    let defaults_installed = complete && install_defaults;
+    if defaults_installed {
+        initialize_default_recipes(deps.storage)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "reset_recipes")
+        .add_attribute("removed", keys.len().to_string())
+        .add_attribute("complete", complete.to_string())
+        .add_attribute("defaults_installed", defaults_installed.to_string()))
+}
+
+// ========== 共享配方注册表 ==========
+
+/// 配方的实际来源
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecipeSource {
+    /// 本地配方（未设置注册表、注册表没有该配方或注册表不可用且无缓存）
+    Local,
+    /// 缓存的注册表配方（未过期，或注册表不可用时的过期缓存）
+    Cache,
+    /// 本次从注册表读取的配方
+    Registry,
+}
+
+/// 设置共享配方注册表
+/// 
+/// 多集合部署中由一个 Luckee NFT 合约（通常为工厂）维护配方，各子集合按需读取并缓存。
+/// 更换或取消注册表时清空缓存。只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置
+/// - `env`: 环境信息，用于拒绝将本合约设为注册表
+/// - `info`: 消息信息，包含发送者
+/// - `registry`: 注册表合约地址（None 表示只使用本地配方）
+/// - `cache_ttl`: 缓存有效时长（秒，0 表示每次读取注册表）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_recipe_registry(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    registry: Option<String>,
+    cache_ttl: u64,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    // 配方类型数量很少，一次清空缓存
+    let cached = RECIPE_CACHE
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for key in cached {
+        RECIPE_CACHE.remove(deps.storage, key);
+    }
+
+    let response = Response::new().add_attribute("action", "set_recipe_registry");
+    match registry {
+        Some(registry) => {
+            let registry = deps.api.addr_validate(&registry)?;
+            if registry == env.contract.address {
+                return Err(ContractError::InvalidRecipeRegistry { reason: "registry is this contract".to_string() });
+            }
+            RECIPE_REGISTRY.save(deps.storage, &RecipeRegistryConfig { registry: registry.clone(), cache_ttl })?;
+            Ok(response
+                .add_attribute("registry", registry.to_string())
+                .add_attribute("cache_ttl", cache_ttl.to_string()))
+        }
+        None => {
+            RECIPE_REGISTRY.remove(deps.storage);
+            Ok(response.add_attribute("registry", "none"))
+        }
+    }
+}
+
+/// 查找目标类型的实际配方
+/// 
+/// 设置了注册表时依次使用：未过期的缓存、注册表的配方、本地配方；
+/// 注册表查询失败时使用过期缓存，没有缓存时使用本地配方。只读，不更新缓存
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和查询访问
+/// - `env`: 环境信息，用于判断缓存是否过期
+/// - `target`: 目标 NFT 类型
+/// 
+/// # 返回值
+/// - `StdResult<(Option<Recipe>, RecipeSource)>`: 配方（不存在时为 None）及其来源
+pub fn lookup_recipe(deps: Deps, env: &Env, target: &NftKind) -> StdResult<(Option<Recipe>, RecipeSource)> {
+    let key = target.to_key();
+    let config = match RECIPE_REGISTRY.may_load(deps.storage)? {
+        Some(config) => config,
+        None => return Ok((RECIPES.may_load(deps.storage, key)?, RecipeSource::Local)),
+    };
+
+    let now = env.block.time.seconds();
+    let cached = RECIPE_CACHE.may_load(deps.storage, key.clone())?;
+    if let Some(cached) = &cached {
+        if now < cached.fetched_at.saturating_add(config.cache_ttl) {
+            return Ok((Some(cached.recipe.clone()), RecipeSource::Cache));
+        }
+    }
+
+    let fetched: StdResult<RecipeResponse> = deps.querier.query_wasm_smart(
+        config.registry.to_string(),
+        &QueryMsg::Recipe { target: target.clone() },
+    );
+    match (fetched, cached) {
+        (Ok(RecipeResponse { recipe: Some(recipe) }), _) => Ok((Some(recipe), RecipeSource::Registry)),
+        (Err(_), Some(cached)) => Ok((Some(cached.recipe), RecipeSource::Cache)),
+        _ => Ok((RECIPES.may_load(deps.storage, key)?, RecipeSource::Local)),
+    }
+}
+
+/// 解析目标类型的实际配方并更新缓存
+/// 
+/// 执行消息使用：从注册表读取的配方写入缓存；注册表没有该配方时删除缓存，
+/// 避免注册表不可用时恢复已删除的配方
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和查询访问
+/// - `env`: 环境信息
+/// - `target`: 目标 NFT 类型
+/// 
+/// # 返回值
+/// - `StdResult<Option<Recipe>>`: 配方（不存在时为 None）
+pub fn resolve_recipe(deps: DepsMut, env: &Env, target: &NftKind) -> StdResult<Option<Recipe>> {
+    let (recipe, source) = lookup_recipe(deps.as_ref(), env, target)?;
+    match (&recipe, source) {
+        (Some(recipe), RecipeSource::Registry) => RECIPE_CACHE.save(
+            deps.storage,
+            target.to_key(),
+            &CachedRecipe { recipe: recipe.clone(), fetched_at: env.block.time.seconds() },
+        )?,
+        (_, RecipeSource::Local) => RECIPE_CACHE.remove(deps.storage, target.to_key()),
+        _ => {}
+    }
+    Ok(recipe)
+}
+
+/// 查询共享配方注册表配置和缓存
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 注册表配置和缓存的配方
+pub fn query_recipe_registry(deps: Deps) -> StdResult<Binary> {
+    let registry = RECIPE_REGISTRY.may_load(deps.storage)?;
+    let mut cached = vec![];
+    for item in RECIPE_CACHE.range(deps.storage, None, None, Order::Ascending) {
+        let (key, entry) = item?;
+        let Ok(target) = NftKind::from_key(&key) else {
+            continue;
+        };
+        cached.push(CachedRecipeEntry { target, recipe: entry.recipe, fetched_at: entry.fetched_at });
+    }
+    to_json_binary(&RecipeRegistryResponse { registry, cached })
+}
@@ -0,0 +1,240 @@
+//! 中继铸造模块
+//! 
+//! 此模块实现第三方网点（如线下售货亭）代用户提交铸造凭证，包括：
+//! - 铸造凭证签名者配置
+//! - 中继者（`mint_relay` 角色）的铸造额度设置
+//! - 提交签名凭证铸造 NFT（校验签名、有效期和凭证序号）
+//! - 中继者额度与凭证序号使用状态查询
+//! 
+//! 凭证由签名者离线签发，中继者只负责提交，无需持有铸造者权限；
+//! 每个中继者受额度限制，凭证序号只能使用一次
+
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+};
+use sha2::{Digest, Sha256};
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::events::emit_mint_event;
+use crate::helpers::{
+    allocate_token_id, assign_series_serial, authorize_series_mint, ensure_kind_mintable, ensure_minter_set_unlocked,
+    ensure_role, record_activity, record_minter_stats, store_minted_token, validate_mint_owner, validate_series_id,
+};
+use crate::msg::{MintVoucher, MintVoucherStatusResponse, RelayQuotaResponse};
+use crate::state::{MINT_VOUCHER_SIGNER, RELAY_QUOTAS, ROLE_MEMBERS, SPENT_VOUCHER_NONCES};
+use crate::types::{NftMeta, Role, Scale};
+
+// ========== 中继配置接口 ==========
+
+/// 设置铸造凭证签名者公钥
+/// 
+/// 更换或移除公钥不会清除已使用的凭证序号，避免旧凭证被重放。
+/// 铸造者集合锁定后不可再修改，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `pubkey`: 签名者 secp256k1 公钥（None 表示停用中继铸造）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_mint_voucher_signer(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    pubkey: Option<Binary>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+    ensure_minter_set_unlocked(deps.storage)?;
+
+    match pubkey {
+        Some(pubkey) => {
+            // secp256k1 公钥为 33 字节（压缩）或 65 字节（非压缩）
+            if pubkey.len() != 33 && pubkey.len() != 65 {
+                return Err(ContractError::InvalidMintVoucher { reason: "invalid secp256k1 public key length".to_string() });
+            }
+            MINT_VOUCHER_SIGNER.save(deps.storage, &pubkey)?;
+        }
+        None => MINT_VOUCHER_SIGNER.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_mint_voucher_signer")
+        .add_attribute("enabled", MINT_VOUCHER_SIGNER.exists(deps.storage).to_string()))
+}
+
+/// 设置中继者的铸造额度
+/// 
+/// 额度为该中继者还可以提交的凭证数量，每次中继铸造扣减一次，0 表示清除额度。
+/// 中继者还需要通过 `SetRole` 授予 `mint_relay` 角色。铸造者集合锁定后不可再修改，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `relayer`: 中继者地址
+/// - `quota`: 剩余额度
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_relay_quota(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    relayer: String,
+    quota: u32,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+    ensure_minter_set_unlocked(deps.storage)?;
+
+    let relayer = deps.api.addr_validate(&relayer)?;
+    if quota == 0 {
+        RELAY_QUOTAS.remove(deps.storage, relayer.clone());
+    } else {
+        RELAY_QUOTAS.save(deps.storage, relayer.clone(), &quota)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_relay_quota")
+        .add_attribute("relayer", relayer.to_string())
+        .add_attribute("quota", quota.to_string()))
+}
+
+// ========== 中继铸造接口 ==========
+
+/// 提交铸造凭证
+/// 
+/// 签名者对 `MintVoucher` 的 JSON 编码进行 secp256k1 签名，持有 `mint_relay` 角色的中继者代为提交。
+/// NFT ID 自动分配（跳过保留区间），序号由系列计数器分配，系列权限按主要铸造者检查
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于校验合约地址和有效期
+/// - `info`: 消息信息，发送者为中继者
+/// - `payload`: `MintVoucher` 的 JSON 编码
+/// - `signature`: 签名者对 `sha256(payload)` 的签名（64 字节）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 铸造结果
+pub fn execute_relay_mint(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    payload: Binary,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停并验证中继角色
+    ctx.ensure_not_paused()?;
+    ensure_role(deps.storage, &Role::MintRelay, &info.sender)?;
+
+    // 检查中继者额度
+    let quota = RELAY_QUOTAS.may_load(deps.storage, info.sender.clone())?.unwrap_or(0);
+    if quota == 0 {
+        return Err(ContractError::RelayQuotaExhausted { relayer: info.sender.to_string() });
+    }
+
+    // 验证签名
+    let pubkey = MINT_VOUCHER_SIGNER.may_load(deps.storage)?
+        .ok_or_else(|| ContractError::InvalidMintVoucher { reason: "voucher signer not configured".to_string() })?;
+    let hash = Sha256::digest(payload.as_slice());
+    let valid = deps.api.secp256k1_verify(&hash, &signature, &pubkey).unwrap_or(false);
+    if !valid {
+        return Err(ContractError::InvalidMintVoucher { reason: "invalid signature".to_string() });
+    }
+
+    // 解析并校验凭证
+    let voucher: MintVoucher = cosmwasm_std::from_json(&payload)
+        .map_err(|_| ContractError::InvalidMintVoucher { reason: "malformed payload".to_string() })?;
+    if voucher.contract != env.contract.address.as_str() {
+        return Err(ContractError::InvalidMintVoucher { reason: "contract mismatch".to_string() });
+    }
+    if env.block.time.seconds() >= voucher.expires_at {
+        return Err(ContractError::InvalidMintVoucher { reason: "voucher expired".to_string() });
+    }
+
+    // 凭证序号只能使用一次，防止重放
+    if SPENT_VOUCHER_NONCES.has(deps.storage, voucher.nonce) {
+        return Err(ContractError::VoucherNonceSpent { nonce: voucher.nonce });
+    }
+
+    // 与普通铸造相同的接收者、类型和系列校验
+    let owner_addr = deps.api.addr_validate(&voucher.recipient)?;
+    validate_mint_owner(deps.storage, &env.contract.address, &owner_addr)?;
+    ensure_kind_mintable(deps.storage, &voucher.kind)?;
+    validate_series_id(&voucher.series_id)?;
+    authorize_series_mint(deps.storage, &voucher.series_id, &ctx.config.minter)?;
+
+    // 校验全部通过后消耗凭证序号并扣减额度
+    SPENT_VOUCHER_NONCES.save(deps.storage, voucher.nonce, &())?;
+    if quota == 1 {
+        RELAY_QUOTAS.remove(deps.storage, info.sender.clone());
+    } else {
+        RELAY_QUOTAS.save(deps.storage, info.sender.clone(), &(quota - 1))?;
+    }
+
+    // 分配 ID 和序号并保存
+    let serial_in_series = assign_series_serial(deps.storage, &voucher.series_id)?;
+    let token_id = allocate_token_id(deps.storage, &voucher.series_id, serial_in_series)?;
+    let meta = NftMeta {
+        kind: voucher.kind.clone(),
+        scale_origin: Scale::Tiny,
+        physical_sku: None,
+        crafted_from: None,
+        series_id: voucher.series_id,
+        collection_group_id: None,
+        serial_in_series,
+        expires_at: None,
+    };
+    let new_supply = store_minted_token(deps.storage, &env, token_id, &owner_addr, &meta)?;
+
+    // 记录到活动流，铸造者统计计入中继者
+    record_activity(deps.storage, &env, "mint", Some(token_id), &info.sender)?;
+    record_minter_stats(deps.storage, &env, &info.sender, 1)?;
+
+    let kind = alloc::format!("{:?}", meta.kind);
+    Ok(Response::new()
+        .add_attribute("action", "relay_mint")
+        .add_attribute("relayer", info.sender.to_string())
+        .add_attribute("nonce", voucher.nonce.to_string())
+        .add_attribute("token_id", token_id.to_string())
+        .add_attribute("owner", owner_addr.to_string())
+        .add_attribute("kind", kind.clone())
+        .add_attribute("remaining_quota", (quota - 1).to_string())
+        .add_event(emit_mint_event(token_id, owner_addr.as_str(), &kind, new_supply)))
+}
+
+// ========== 中继查询接口 ==========
+
+/// 查询中继者的状态和剩余额度
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `relayer`: 中继者地址
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 是否持有中继角色和剩余额度
+pub fn query_relay_quota(deps: Deps, relayer: String) -> StdResult<Binary> {
+    let relayer = deps.api.addr_validate(&relayer)?;
+    let is_relayer = ROLE_MEMBERS.has(deps.storage, (Role::MintRelay.as_str().to_string(), relayer.clone()));
+    let remaining = RELAY_QUOTAS.may_load(deps.storage, relayer.clone())?.unwrap_or(0);
+    to_json_binary(&RelayQuotaResponse { relayer: relayer.to_string(), is_relayer, remaining })
+}
+
+/// 查询凭证序号是否已使用
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `nonce`: 凭证序号
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 使用状态
+pub fn query_mint_voucher_status(deps: Deps, nonce: u64) -> StdResult<Binary> {
+    let spent = SPENT_VOUCHER_NONCES.has(deps.storage, nonce);
+    to_json_binary(&MintVoucherStatusResponse { nonce, spent })
+}
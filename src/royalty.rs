@@ -0,0 +1,162 @@
+//! 合成版税模块
+//! 
+//! 此模块实现高稀有度合成的创作者版税，包括：
+//! - 版税设置（最低目标稀有度和每次合成收取的原生代币）
+//! - 合成时收取版税，按输入 NFT 分给各自系列的创建者
+//! - 按系列查询累计版税
+//! 
+//! 系列创建者为系列注册表中记录的系列所有者，即首次向该系列铸造的铸造者
+
+use cosmwasm_std::{
+    to_json_binary, Addr, BankMsg, Binary, Coin, Deps, DepsMut, MessageInfo, Response, StdResult, Storage, Uint128,
+};
+
+use crate::burn_tax::add_coin;
+use crate::context::ExecuteContext;
+use crate::deposits::charge_fee;
+use crate::error::ContractError;
+use crate::msg::{CraftingRoyaltyResponse, SeriesRoyaltiesResponse};
+use crate::state::{CraftingRoyalty, CRAFTING_ROYALTY, SERIES_OWNERS, SERIES_ROYALTIES};
+use crate::types::{NftKind, NftMeta};
+
+// ========== 版税执行接口 ==========
+
+/// 设置合成版税
+/// 
+/// None 表示停止收取，只有合约所有者可以执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `royalty`: 版税配置
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 设置结果
+pub fn execute_set_crafting_royalty(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    royalty: Option<CraftingRoyalty>,
+) -> Result<Response, ContractError> {
+    // 验证所有者权限
+    ctx.ensure_owner(&info.sender)?;
+
+    let response = Response::new().add_attribute("action", "set_crafting_royalty");
+    match royalty {
+        Some(royalty) => {
+            if royalty.amount.amount.is_zero() {
+                return Err(ContractError::InvalidCraftingRoyalty { reason: "zero royalty".to_string() });
+            }
+            if royalty.min_rarity > NftKind::Genesis.rarity_level() {
+                return Err(ContractError::InvalidCraftingRoyalty { reason: "rarity level out of range".to_string() });
+            }
+            CRAFTING_ROYALTY.save(deps.storage, &royalty)?;
+            Ok(response
+                .add_attribute("min_rarity", royalty.min_rarity.to_string())
+                .add_attribute("amount", royalty.amount.to_string()))
+        }
+        None => {
+            CRAFTING_ROYALTY.remove(deps.storage);
+            Ok(response.add_attribute("amount", "none"))
+        }
+    }
+}
+
+// ========== 合成时的版税处理 ==========
+
+/// 收取合成版税并分给系列创建者
+/// 
+/// 目标稀有度达到门槛时从付费者存款中扣除版税，按输入 NFT 数量均分，
+/// 每份归该输入所属系列的创建者，余数归第一个有创建者的输入。
+/// 输入所属系列均未登记创建者（如合成产出的系列）时不收取
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `payer`: 付费地址
+/// - `target`: 合成目标类型
+/// - `input_metas`: 输入 NFT 的元数据
+/// 
+/// # 返回值
+/// - `Result<(Vec<BankMsg>, String), ContractError>`: 发送给各创建者的银行消息和用于事件属性的描述
+pub fn collect_crafting_royalty(
+    storage: &mut dyn Storage,
+    payer: &Addr,
+    target: &NftKind,
+    input_metas: &[NftMeta],
+) -> Result<(Vec<BankMsg>, String), ContractError> {
+    let royalty = match CRAFTING_ROYALTY.may_load(storage)? {
+        Some(royalty) if target.rarity_level() >= royalty.min_rarity => royalty,
+        _ => return Ok((vec![], String::new())),
+    };
+
+    // 找出有登记创建者的输入
+    let mut creators: Vec<(String, Addr)> = vec![];
+    for meta in input_metas {
+        if let Some(creator) = SERIES_OWNERS.may_load(storage, meta.series_id.clone())? {
+            creators.push((meta.series_id.clone(), creator));
+        }
+    }
+    if creators.is_empty() {
+        return Ok((vec![], String::new()));
+    }
+
+    charge_fee(storage, payer, &[], Some(&royalty.amount))?;
+
+    // 按输入均分，余数归第一个输入
+    let count = Uint128::from(creators.len() as u128);
+    let share = royalty.amount.amount.checked_div(count).map_err(|_| ContractError::Overflow {})?;
+    let remainder = royalty.amount.amount - share * count;
+    let mut payouts: Vec<(Addr, Uint128)> = vec![];
+    for (index, (series_id, creator)) in creators.into_iter().enumerate() {
+        let amount = if index == 0 { share + remainder } else { share };
+        if amount.is_zero() {
+            continue;
+        }
+
+        // 累计系列版税
+        let mut earned = SERIES_ROYALTIES.may_load(storage, series_id.clone())?.unwrap_or_default();
+        add_coin(&mut earned, &Coin { denom: royalty.amount.denom.clone(), amount })?;
+        SERIES_ROYALTIES.save(storage, series_id, &earned)?;
+
+        // 同一创建者的多份版税合并发送
+        match payouts.iter_mut().find(|(addr, _)| *addr == creator) {
+            Some((_, total)) => *total += amount,
+            None => payouts.push((creator, amount)),
+        }
+    }
+
+    let msgs = payouts
+        .into_iter()
+        .map(|(creator, amount)| BankMsg::Send {
+            to_address: creator.to_string(),
+            amount: vec![Coin { denom: royalty.amount.denom.clone(), amount }],
+        })
+        .collect();
+    Ok((msgs, royalty.amount.to_string()))
+}
+
+// ========== 版税查询接口 ==========
+
+/// 查询合成版税配置
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 版税配置（未设置时为 None）
+pub fn query_crafting_royalty(deps: Deps) -> StdResult<Binary> {
+    let royalty = CRAFTING_ROYALTY.may_load(deps.storage)?;
+    to_json_binary(&CraftingRoyaltyResponse { royalty })
+}
+
+/// 查询系列创建者累计获得的合成版税
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `series_id`: 系列 ID
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 系列创建者和累计版税
+pub fn query_series_royalties(deps: Deps, series_id: String) -> StdResult<Binary> {
+    let creator = SERIES_OWNERS.may_load(deps.storage, series_id.clone())?.map(|addr| addr.to_string());
+    let earned = SERIES_ROYALTIES.may_load(deps.storage, series_id.clone())?.unwrap_or_default();
+    to_json_binary(&SeriesRoyaltiesResponse { series_id, creator, earned })
+}
@@ -0,0 +1,128 @@
+//! 会话密钥模块
+//! 
+//! 此模块实现所有者授予的短期会话密钥，包括：
+//! - 授予：所有者为一个地址授予限定操作范围和时长的会话密钥
+//! - 撤销：所有者随时撤销会话密钥，暂停期间也可执行
+//! - 查询：查询所有者授予某地址的生效中的会话密钥
+//! 
+//! 游戏过程中由前端持有的临时密钥代所有者签名，减少钱包弹窗；
+//! 各处理函数在常规权限之外检查会话密钥授权，密钥不能授予或撤销其他会话密钥
+
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::msg::SessionKeyResponse;
+use crate::state::{SessionKeyGrant, SESSION_KEYS};
+use crate::types::SessionKeyScope;
+
+/// 会话密钥的最长有效时长（秒）
+pub const MAX_SESSION_KEY_SECONDS: u64 = 24 * 60 * 60;
+
+// ========== 会话密钥执行接口 ==========
+
+/// 授予会话密钥
+/// 
+/// 同一密钥重复授予时覆盖原有的操作范围和失效时间
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于计算失效时间
+/// - `info`: 消息信息，发送者为授予会话密钥的所有者
+/// - `key`: 会话密钥地址
+/// - `scopes`: 允许代所有者执行的操作
+/// - `duration`: 有效时长（秒，不超过 24 小时）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 授予结果
+pub fn execute_grant_session_key(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    key: String,
+    scopes: Vec<SessionKeyScope>,
+    duration: u64,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    let key_addr = deps.api.addr_validate(&key)?;
+    if key_addr == info.sender {
+        return Err(ContractError::InvalidSessionKey { reason: "key is the owner".to_string() });
+    }
+    if scopes.is_empty() {
+        return Err(ContractError::InvalidSessionKey { reason: "empty scopes".to_string() });
+    }
+    for (index, scope) in scopes.iter().enumerate() {
+        if scopes[..index].contains(scope) {
+            return Err(ContractError::InvalidSessionKey {
+                reason: alloc::format!("duplicate scope {}", scope.as_str()),
+            });
+        }
+    }
+    if duration == 0 || duration > MAX_SESSION_KEY_SECONDS {
+        return Err(ContractError::InvalidSessionKey { reason: "duration out of range".to_string() });
+    }
+
+    let expires_at = env.block.time.seconds() + duration;
+    let scope_names = scopes.iter().map(|scope| scope.as_str()).collect::<Vec<_>>().join(",");
+    SESSION_KEYS.save(deps.storage, (info.sender.clone(), key_addr.clone()), &SessionKeyGrant { scopes, expires_at })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "grant_session_key")
+        .add_attribute("owner", info.sender.to_string())
+        .add_attribute("key", key_addr.to_string())
+        .add_attribute("scopes", scope_names)
+        .add_attribute("expires_at", expires_at.to_string()))
+}
+
+/// 撤销会话密钥
+/// 
+/// 只有授予者可以撤销，暂停期间也可执行
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `info`: 消息信息，发送者为授予会话密钥的所有者
+/// - `key`: 会话密钥地址
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 撤销结果
+pub fn execute_revoke_session_key(
+    deps: DepsMut,
+    info: MessageInfo,
+    key: String,
+) -> Result<Response, ContractError> {
+    let storage_key = (info.sender.clone(), deps.api.addr_validate(&key)?);
+    if !SESSION_KEYS.has(deps.storage, storage_key.clone()) {
+        return Err(ContractError::InvalidSessionKey { reason: "not found".to_string() });
+    }
+    SESSION_KEYS.remove(deps.storage, storage_key);
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_session_key")
+        .add_attribute("owner", info.sender.to_string())
+        .add_attribute("key", key))
+}
+
+// ========== 会话密钥查询接口 ==========
+
+/// 查询会话密钥
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，用于过滤已过期的授权
+/// - `owner`: 所有者地址
+/// - `key`: 会话密钥地址
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 生效中的授权（不存在或已过期时为 None）
+pub fn query_session_key(deps: Deps, env: Env, owner: String, key: String) -> StdResult<Binary> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let key_addr = deps.api.addr_validate(&key)?;
+    let grant = SESSION_KEYS
+        .may_load(deps.storage, (owner_addr, key_addr))?
+        .filter(|grant| env.block.time.seconds() < grant.expires_at);
+    to_json_binary(&SessionKeyResponse { owner, key, grant })
+}
@@ -0,0 +1,180 @@
+//! 游戏会话锁定模块
+//! 
+//! 此模块实现游戏服务器在对局期间临时锁定 NFT，包括：
+//! - 锁定：游戏服务器角色将一批 NFT 锁定到会话，直至截止时间
+//! - 解锁：对局结束后由锁定者（或合约所有者）提前解锁整个会话
+//! - 查询单个 NFT 的锁定状态和会话内的 NFT
+//! 
+//! 锁定期间 NFT 不可转移、销毁、合成、挂单互换或赠送；截止时间到达后自动失效
+
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult};
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::helpers::{active_session_lock, ensure_role};
+use crate::msg::{SessionLockResponse, SessionTokensResponse};
+use crate::state::{SessionLock, SESSION_LOCKS, SESSION_TOKENS, TOKEN_META};
+use crate::types::Role;
+
+/// 单次锁定的最大 NFT 数量
+pub const MAX_SESSION_LOCK_TOKENS: usize = 50;
+
+/// 会话锁定的最长时长（秒）
+pub const MAX_SESSION_LOCK_SECONDS: u64 = 24 * 60 * 60;
+
+/// 会话 ID 最大长度
+pub const MAX_SESSION_ID_LENGTH: usize = 64;
+
+// ========== 会话执行接口 ==========
+
+/// 锁定 NFT 到游戏会话
+/// 
+/// 只有游戏服务器角色可以执行；已被其他会话锁定且未到期的 NFT 不能重复锁定
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，包含当前时间
+/// - `info`: 消息信息，包含发送者
+/// - `token_ids`: 要锁定的 NFT ID 列表
+/// - `session_id`: 会话 ID
+/// - `until`: 锁定截止时间（秒）
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 锁定结果
+pub fn execute_lock_for_session(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    token_ids: Vec<u64>,
+    session_id: String,
+    until: u64,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停并验证游戏服务器角色
+    ctx.ensure_not_paused()?;
+    ensure_role(deps.storage, &Role::GameServer, &info.sender)?;
+
+    // 验证参数
+    if session_id.is_empty() || session_id.len() > MAX_SESSION_ID_LENGTH {
+        return Err(ContractError::InvalidSessionLock { reason: "invalid session id".to_string() });
+    }
+    if token_ids.is_empty() || token_ids.len() > MAX_SESSION_LOCK_TOKENS {
+        return Err(ContractError::InvalidSessionLock {
+            reason: alloc::format!("between 1 and {} tokens per lock", MAX_SESSION_LOCK_TOKENS),
+        });
+    }
+    let now = env.block.time.seconds();
+    if until <= now || until - now > MAX_SESSION_LOCK_SECONDS {
+        return Err(ContractError::InvalidSessionLock { reason: "lock deadline out of range".to_string() });
+    }
+
+    for (index, token_id) in token_ids.iter().enumerate() {
+        // 错误附带出错条目的位置
+        if !TOKEN_META.has(deps.storage, *token_id) {
+            return Err(ContractError::TokenNotFound {}.at_batch_item(index, *token_id));
+        }
+        if let Some(lock) = active_session_lock(deps.storage, &env, *token_id)? {
+            return Err(ContractError::TokenSessionLocked { token_id: *token_id, until: lock.until }
+                .at_batch_item(index, *token_id));
+        }
+        // 清理已到期锁定的会话索引
+        if let Some(stale) = SESSION_LOCKS.may_load(deps.storage, *token_id)? {
+            SESSION_TOKENS.remove(deps.storage, (stale.session_id, *token_id));
+        }
+
+        SESSION_LOCKS.save(deps.storage, *token_id, &SessionLock {
+            session_id: session_id.clone(),
+            locked_by: info.sender.clone(),
+            until,
+        })?;
+        SESSION_TOKENS.save(deps.storage, (session_id.clone(), *token_id), &())?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "lock_for_session")
+        .add_attribute("session_id", session_id)
+        .add_attribute("locked_by", info.sender.to_string())
+        .add_attribute("count", token_ids.len().to_string())
+        .add_attribute("until", until.to_string()))
+}
+
+/// 解锁游戏会话
+/// 
+/// 提前释放会话内的全部 NFT，只有锁定者或合约所有者可以执行，合约暂停期间也可解锁
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `info`: 消息信息，包含发送者
+/// - `session_id`: 会话 ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 解锁结果
+pub fn execute_unlock_session(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    info: MessageInfo,
+    session_id: String,
+) -> Result<Response, ContractError> {
+    let token_ids = SESSION_TOKENS
+        .prefix(session_id.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<u64>>>()?;
+    if token_ids.is_empty() {
+        return Err(ContractError::InvalidSessionLock { reason: "session not found".to_string() });
+    }
+
+    for token_id in &token_ids {
+        let lock = SESSION_LOCKS.load(deps.storage, *token_id)?;
+        if lock.locked_by != info.sender {
+            ctx.ensure_owner(&info.sender)?;
+        }
+        SESSION_LOCKS.remove(deps.storage, *token_id);
+        SESSION_TOKENS.remove(deps.storage, (session_id.clone(), *token_id));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "unlock_session")
+        .add_attribute("session_id", session_id)
+        .add_attribute("count", token_ids.len().to_string()))
+}
+
+// ========== 会话查询接口 ==========
+
+/// 查询 NFT 的会话锁定状态
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `env`: 环境信息，用于判断锁定是否到期
+/// - `token_id`: NFT ID
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 生效中的锁定（未锁定或已到期时为 None）
+pub fn query_session_lock(deps: Deps, env: Env, token_id: u64) -> StdResult<Binary> {
+    let lock = active_session_lock(deps.storage, &env, token_id)?;
+    to_json_binary(&SessionLockResponse { lock })
+}
+
+/// 查询会话内锁定的 NFT
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `env`: 环境信息，用于过滤已到期的锁定
+/// - `session_id`: 会话 ID
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 锁定中的 NFT ID 列表
+pub fn query_session_tokens(deps: Deps, env: Env, session_id: String) -> StdResult<Binary> {
+    let mut token_ids = vec![];
+    for token_id in SESSION_TOKENS
+        .prefix(session_id.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+    {
+        let token_id = token_id?;
+        if active_session_lock(deps.storage, &env, token_id)?.is_some() {
+            token_ids.push(token_id);
+        }
+    }
+    to_json_binary(&SessionTokensResponse { session_id, token_ids })
+}
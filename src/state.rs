@@ -6,13 +6,13 @@
 #[cfg(feature = "cosmwasm")]
 use cosmwasm_std::Addr;
 #[cfg(feature = "cosmwasm")]
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Item, Map, SnapshotMap, Strategy};
 
 #[cfg(feature = "std")]
 use schemars::JsonSchema;
 
 use serde::{Deserialize, Serialize};
-use crate::types::{NftMeta, Recipe};
+use crate::types::{AcceptedDenom, NftKind, NftMeta, Recipe, KindMintStatus};
 
 // ========== 数据结构定义 ==========
 
@@ -32,6 +32,9 @@ pub struct Config {
     pub base_uri: Option<String>,
     /// 合约所有者地址
     pub owner: Addr,
+    /// 接受的支付币种（为空时不限制币种，旧部署的配置中没有此字段）
+    #[serde(default)]
+    pub accepted_denoms: Vec<AcceptedDenom>,
 }
 
 // ========== 存储项定义 ==========
@@ -48,6 +51,11 @@ pub const TOKEN_META: Map<u64, NftMeta> = Map::new("token_meta");
 #[cfg(feature = "cosmwasm")]
 pub const SERIES_NEXT_SERIAL: Map<String, u64> = Map::new("series_next_serial");
 
+/// 系列中现存 NFT 数量
+/// 键: 系列 ID，值: 现存数量（用于判断系列计数器是否仍被引用）
+#[cfg(feature = "cosmwasm")]
+pub const SERIES_TOKEN_COUNT: Map<String, u64> = Map::new("series_token_count");
+
 /// 总供应量存储
 #[cfg(feature = "cosmwasm")]
 pub const TOTAL_SUPPLY: Item<u64> = Item::new("total_supply");
@@ -105,6 +113,483 @@ pub struct SynthesisRecord {
     pub output: u64,
     /// 合成时间戳
     pub timestamp: u64,
+    /// 产出所在的关联集合（在本集合铸造时为 None，旧记录中没有此字段）
+    #[serde(default)]
+    pub output_collection: Option<Addr>,
+}
+
+/// 跨集合合成的来源集合（来源集合地址 -> 产出所在系列 ID）
+/// 
+/// 由合约所有者登记，只有登记的来源集合可以请求铸造合成产出，与铸造者列表相互独立
+#[cfg(feature = "cosmwasm")]
+pub const SYNTHESIS_SOURCES: Map<Addr, String> = Map::new("synthesis_sources");
+
+/// 等待关联集合回复的合成
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct PendingRemoteSynthesis {
+    /// 合成记录（产出 ID 在回复中填入）
+    pub record: SynthesisRecord,
+    /// 合成目标类型
+    pub target: NftKind,
+}
+
+/// 合约活动记录结构
+/// 
+/// 活动流环形缓冲区中的一条记录，概括一次铸造、转移、销毁或合成操作
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct ActivityEntry {
+    /// 全局递增序号
+    pub seq: u64,
+    /// 操作类型（mint、batch_mint、transfer、burn、expire、synthesize）
+    pub action: String,
+    /// 相关的 NFT ID（批量操作为 None）
+    pub token_id: Option<u64>,
+    /// 执行操作的地址
+    pub actor: Addr,
+    /// 区块高度
+    pub height: u64,
+    /// 区块时间戳
+    pub timestamp: u64,
+}
+
+/// 铸造者统计结构
+/// 
+/// 累计记录某个铸造者的铸造数量，便于按合作方配额监控
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct MinterStats {
+    /// 累计铸造数量（包括单个铸造和批量铸造）
+    pub total_minted: u64,
+    /// 最近一次铸造的区块高度
+    pub last_mint_height: u64,
+    /// 最近一次铸造的区块时间戳（秒）
+    pub last_mint_time: u64,
+}
+
+/// 铸造幂等记录结构
+/// 
+/// 记录带幂等键的铸造结果，相同铸造者重复提交同一幂等键时直接返回该结果
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct MintReceipt {
+    /// 首次铸造的 NFT ID 列表
+    pub token_ids: Vec<u64>,
+    /// 首次铸造的区块高度
+    pub height: u64,
+    /// 首次铸造的操作类型（"mint" 或 "batch_mint"，旧记录中没有此字段）
+    #[serde(default)]
+    pub action: String,
+    /// 首次铸造的所有者（仅单个铸造）
+    #[serde(default)]
+    pub owner: Option<Addr>,
+    /// 首次铸造的类型（仅单个铸造）
+    #[serde(default)]
+    pub kind: Option<String>,
+    /// 首次铸造后的总供应量
+    #[serde(default)]
+    pub total_supply: u64,
+}
+
+/// 待销毁记录结构
+/// 
+/// 宽限期内保存被销毁 NFT 的原所有者和元数据，用于恢复
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct PendingBurn {
+    /// 销毁前的所有者地址
+    pub owner: Addr,
+    /// 销毁前的 NFT 元数据
+    pub meta: NftMeta,
+    /// 可恢复的截止区块高度（不含）
+    pub restore_until_height: u64,
+}
+
+/// 销毁冷却配置结构
+/// 
+/// 稀有度不低于门槛的 NFT 须先申请销毁，冷却期结束后才能确认
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct BurnCooldown {
+    /// 需要申请销毁的最低稀有度等级
+    pub min_rarity_level: u8,
+    /// 申请后到可确认的冷却时间（秒）
+    pub delay: u64,
+}
+
+/// 销毁申请结构
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct BurnRequest {
+    /// 申请时的 NFT 所有者
+    pub owner: Addr,
+    /// 发起申请的地址（所有者或获授权的操作员）
+    pub requested_by: Addr,
+    /// 可确认销毁的最早时间（秒）
+    pub confirm_after: u64,
+}
+
+/// 会话密钥授权结构
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct SessionKeyGrant {
+    /// 允许代所有者执行的操作
+    pub scopes: Vec<crate::types::SessionKeyScope>,
+    /// 失效时间（秒）
+    pub expires_at: u64,
+}
+
+/// 旧版合约导入配置结构
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct LegacyImportConfig {
+    /// 旧版 CW721 合约地址
+    pub contract: Addr,
+    /// 导入的 NFT 所属系列
+    pub series_id: String,
+    /// 旧版 `kind` 属性取值到 NFT 类型的映射
+    pub kind_map: Vec<crate::types::LegacyKindMapping>,
+}
+
+/// 紧急提取守护者配置结构
+/// 
+/// 定义 m-of-n 审批规则
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct GuardianConfig {
+    /// 守护者地址列表
+    pub guardians: Vec<Addr>,
+    /// 执行所需的最少审批数
+    pub required: u32,
+    /// 单个币种金额超过此阈值时需要审批（None 表示所有提取都需要审批）
+    pub threshold: Option<cosmwasm_std::Uint128>,
+}
+
+/// 守护者配置变更提案结构
+/// 
+/// 已配置守护者时，配置变更须经当前守护者按 m-of-n 规则审批后生效
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct PendingGuardianChange {
+    /// 提议的新配置（None 表示关闭审批）
+    pub config: Option<GuardianConfig>,
+    /// 提议时间（秒）
+    pub proposed_at: u64,
+    /// 已审批的守护者地址
+    pub approvals: Vec<Addr>,
+}
+
+/// 免审批提取窗口结构
+/// 
+/// 记录当前窗口内未经守护者审批直接执行的提取累计金额
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct UnapprovedWithdrawWindow {
+    /// 窗口开始时间（秒）
+    pub window_start: u64,
+    /// 窗口内已直接提取的金额（按币种）
+    pub withdrawn: Vec<cosmwasm_std::Coin>,
+}
+
+/// 待审批提取记录结构
+/// 
+/// 记录一次超过阈值的紧急提取请求及其审批进度
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct PendingWithdrawal {
+    /// 提取 ID
+    pub id: u64,
+    /// 提取资金列表
+    pub amount: Vec<cosmwasm_std::Coin>,
+    /// 资金接收者地址
+    pub recipient: Addr,
+    /// 已审批的守护者地址
+    pub approvals: Vec<Addr>,
+    /// 提议时间（秒，旧记录中没有此字段，视为已过期）
+    #[serde(default)]
+    pub proposed_at: u64,
+}
+
+/// 类型互换报价结构
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct SwapOffer {
+    /// 报价 ID
+    pub id: u64,
+    /// 报价创建者
+    pub maker: Addr,
+    /// 提供的 NFT ID
+    pub offered_token_id: u64,
+    /// 提供的 NFT 类型
+    pub offered_kind: NftKind,
+    /// 期望换取的 NFT 类型
+    pub wanted_kind: NftKind,
+}
+
+/// 维护公告结构
+/// 
+/// 管理员发布的只读维护提示，前端据此展示计划中的停机时间
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct MaintenanceNotice {
+    /// 展示给用户的公告内容
+    pub message: String,
+    /// 预计结束时间（Unix 秒，可选）
+    pub ends_at: Option<u64>,
+}
+
+/// 合成价值上限配置
+/// 
+/// 限制每个所有者在时间窗口内通过合成产出的兑换价值总和
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct CraftingLimit {
+    /// 窗口内最多产出的兑换价值（四叶草数量）
+    pub max_value: u64,
+    /// 窗口长度（秒）
+    pub window_seconds: u64,
+}
+
+/// 合成版税配置结构
+/// 
+/// 合成目标稀有度达到门槛时额外收取原生代币，按输入 NFT 分给各自系列的创建者
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct CraftingRoyalty {
+    /// 收取版税的最低目标稀有度等级（NftKind::rarity_level）
+    pub min_rarity: u8,
+    /// 每次合成收取的版税
+    pub amount: cosmwasm_std::Coin,
+}
+
+/// 托管标记结构
+/// 
+/// 托管方（如交易所）为其持有的 NFT 标注链下账户引用的哈希，用于对账
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct CustodyTag {
+    /// 托管方地址（标记时的 NFT 所有者）
+    pub custodian: Addr,
+    /// 链下账户引用的 SHA-256 哈希（小写十六进制）
+    pub account_hash: String,
+    /// 标记时的区块时间（秒）
+    pub tagged_at: u64,
+}
+
+/// 所有者取得 NFT 的区块高度和时间
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct Acquisition {
+    /// 取得时的区块高度
+    pub height: u64,
+    /// 取得时的区块时间（Unix 秒）
+    pub time: u64,
+}
+
+/// 保留 NFT ID 区间（含两端）
+/// 
+/// 区间内的 ID 只能由合约所有者铸造（用于博物馆、展览等特殊藏品），
+/// 自动分配 ID 时跳过该区间
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct ReservedIdRange {
+    /// 区间起始 ID
+    pub start: u64,
+    /// 区间结束 ID
+    pub end: u64,
+}
+
+/// 合成销毁税累计统计
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct BurnTaxStats {
+    /// 作为销毁税额外消耗的 NFT 数量
+    pub tokens_burned: u64,
+    /// 额外消耗的 NFT 的兑换价值总和（四叶草数量）
+    pub value_burned: u64,
+    /// 作为销毁税收取的原生代币（按代币累计）
+    pub fees_burned: Vec<cosmwasm_std::Coin>,
+}
+
+/// 所有者当前窗口的合成产出
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct CraftedValueWindow {
+    /// 窗口开始时间（秒）
+    pub window_start: u64,
+    /// 窗口内已产出的兑换价值
+    pub value: u64,
+}
+
+/// 游戏会话锁定结构
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct SessionLock {
+    /// 会话 ID
+    pub session_id: String,
+    /// 执行锁定的游戏服务器
+    pub locked_by: Addr,
+    /// 锁定截止时间（秒），到达后自动失效
+    pub until: u64,
+}
+
+/// 礼物结构
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct Gift {
+    /// 赠送者
+    pub sender: Addr,
+    /// 托管的 NFT ID
+    pub token_id: u64,
+    /// 过期时间（秒）
+    pub expires: u64,
+}
+
+/// 条件转移结构
+/// 
+/// NFT 托管在合约中，接收者在超时前提交哈希锁原文即可领取，超时后由发送者取回
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct ConditionalTransfer {
+    /// 发送者
+    pub sender: Addr,
+    /// 接收者
+    pub recipient: Addr,
+    /// 原文的 SHA-256 哈希（小写十六进制）
+    pub hashlock: String,
+    /// 超时时间（秒）
+    pub timeout: u64,
+}
+
+/// NFT 组合包结构
+/// 
+/// 组合包中的 NFT 托管在合约中，组合包作为整体转移，拆包后归还持有者
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct Bundle {
+    /// 持有者
+    pub owner: Addr,
+    /// 托管的 NFT ID
+    pub token_ids: Vec<u64>,
+    /// 创建时的区块时间（秒）
+    pub created_at: u64,
+}
+
+/// 价格预言机配置结构
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct PriceOracleConfig {
+    /// 喂价合约地址
+    pub feed: Addr,
+    /// 结算使用的原生代币
+    pub denom: String,
+    /// 价格最长有效时间（秒）
+    pub max_age_seconds: u64,
+    /// 相对上次使用价格允许的最大偏离（基点，0 表示不检查）
+    pub max_deviation_bps: u16,
+}
+
+/// 集合社交链接
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct SocialLink {
+    /// 平台名称（如 twitter、discord）
+    pub platform: String,
+    /// 链接地址
+    pub url: String,
+}
+
+/// 集合展示信息结构
+/// 
+/// 与 sg721 的 CollectionInfo 对应，供前端渲染集合页面
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct CollectionInfo {
+    /// 集合描述
+    pub description: String,
+    /// 集合封面图片 URI
+    pub image: String,
+    /// 外部链接（可选）
+    pub external_link: Option<String>,
+    /// 社交链接列表
+    pub socials: Vec<SocialLink>,
+}
+
+/// 子集合记录结构
+/// 
+/// 工厂模式下由本合约实例化的子集合
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct ChildCollection {
+    /// 集合名称
+    pub name: String,
+    /// 集合符号
+    pub symbol: String,
+    /// 实例化所用的代码 ID
+    pub code_id: u64,
+    /// 创建时间（秒）
+    pub created_at: u64,
+}
+
+/// 共享配方注册表配置结构
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct RecipeRegistryConfig {
+    /// 注册表合约地址（提供 Luckee NFT 的 `recipe` 查询）
+    pub registry: Addr,
+    /// 缓存的注册表配方在此时长内直接使用（秒）
+    pub cache_ttl: u64,
+}
+
+/// 缓存的注册表配方结构
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct CachedRecipe {
+    /// 注册表返回的配方
+    pub recipe: Recipe,
+    /// 读取时间（秒）
+    pub fetched_at: u64,
+}
+
+/// 奖池中单个类型的剩余数量
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct PoolSlot {
+    /// NFT 类型
+    pub kind: NftKind,
+    /// 权重
+    pub weight: u32,
+    /// 剩余数量
+    pub remaining: u64,
+}
+
+/// 盲盒奖池结构
+/// 
+/// 奖池总量按权重分配给各类型，每次抽取按剩余数量加权，抽完即止
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct MintPool {
+    /// 铸造的 NFT 所属系列
+    pub series_id: String,
+    /// 各类型的权重和剩余数量
+    pub slots: Vec<PoolSlot>,
+    /// 奖池总量
+    pub size: u64,
+    /// 剩余总量
+    pub remaining: u64,
+}
+
+/// 铸造者轮换结构
+/// 
+/// 记录已提议但尚未生效的主要铸造者变更
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct MinterRotation {
+    /// 新铸造者地址
+    pub new_minter: Addr,
+    /// 生效区块高度（此前原铸造者仍然有效）
+    pub activation_height: u64,
 }
 
 // ========== 权限和状态存储 ==========
@@ -127,6 +612,447 @@ pub const CONTRACT_PAUSED: Item<bool> = Item::new("contract_paused");
 pub const STORAGE_VERSION: Item<String> = Item::new("storage_version");
 
 
+/// 销毁宽限期（区块数）
+/// 大于 0 时，销毁的 NFT 先进入待销毁状态，宽限期内可恢复
+#[cfg(feature = "cosmwasm")]
+pub const BURN_GRACE_PERIOD: Item<u64> = Item::new("burn_grace_period");
+
+/// 事件详细程度（未设置时为 Verbose）
+#[cfg(feature = "cosmwasm")]
+pub const EVENT_VERBOSITY: Item<crate::types::EventVerbosity> = Item::new("event_verbosity");
+
+/// 待销毁 NFT 映射
+/// 键: NFT ID，值: 待销毁记录
+#[cfg(feature = "cosmwasm")]
+pub const PENDING_BURNS: Map<u64, PendingBurn> = Map::new("pending_burns");
+
+/// 销毁冷却配置
+/// 未设置时所有 NFT 均可直接销毁
+#[cfg(feature = "cosmwasm")]
+pub const BURN_COOLDOWN: Item<BurnCooldown> = Item::new("burn_cooldown");
+
+/// 待确认的销毁申请
+/// 键: NFT ID，值: 销毁申请
+#[cfg(feature = "cosmwasm")]
+pub const BURN_REQUESTS: Map<u64, BurnRequest> = Map::new("burn_requests");
+
+/// 旧版合约导入配置
+/// 未设置时不接受旧版合约的 NFT
+#[cfg(feature = "cosmwasm")]
+pub const LEGACY_IMPORT: Item<LegacyImportConfig> = Item::new("legacy_import");
+
+/// 已导入的旧版 NFT
+/// 键: (旧版合约地址, 旧版合约中的 token ID)，值: 导入后的 NFT ID
+/// 
+/// 按旧版合约区分，更换导入来源后不同合约的相同 token ID 互不影响
+#[cfg(feature = "cosmwasm")]
+pub const LEGACY_IDS: Map<(Addr, String), u64> = Map::new("legacy_token_ids");
+
+/// 会话密钥授权
+/// 键: (所有者, 会话密钥地址)，值: 授权范围和失效时间
+#[cfg(feature = "cosmwasm")]
+pub const SESSION_KEYS: Map<(Addr, Addr), SessionKeyGrant> = Map::new("session_keys");
+
+/// 紧急提取守护者配置
+/// 未设置或守护者列表为空时，紧急提取由合约所有者单独执行
+#[cfg(feature = "cosmwasm")]
+pub const WITHDRAW_GUARDIANS: Item<GuardianConfig> = Item::new("withdraw_guardians");
+
+/// 待审批的紧急提取
+/// 键: 提取 ID，值: 待审批提取记录
+#[cfg(feature = "cosmwasm")]
+pub const PENDING_WITHDRAWALS: Map<u64, PendingWithdrawal> = Map::new("pending_withdrawals");
+
+/// 下一个紧急提取 ID 计数器
+#[cfg(feature = "cosmwasm")]
+pub const NEXT_WITHDRAWAL_ID: Item<u64> = Item::new("next_withdrawal_id");
+
+/// 待审批的守护者配置变更
+#[cfg(feature = "cosmwasm")]
+pub const PENDING_GUARDIAN_CHANGE: Item<PendingGuardianChange> = Item::new("pending_guardian_change");
+
+/// 当前窗口内免审批的紧急提取累计金额
+#[cfg(feature = "cosmwasm")]
+pub const UNAPPROVED_WITHDRAW_WINDOW: Item<UnapprovedWithdrawWindow> = Item::new("unapproved_withdraw_window");
+
+/// token URI 模板
+/// 键: 基础 URI 协议前缀（如 `ipfs://`），值: URI 模板
+#[cfg(feature = "cosmwasm")]
+pub const URI_TEMPLATES: Map<String, String> = Map::new("uri_templates");
+
+/// 系列所有者
+/// 键: 系列 ID，值: 系列所有者（首个铸造者或显式注册者）
+#[cfg(feature = "cosmwasm")]
+pub const SERIES_OWNERS: Map<String, Addr> = Map::new("series_owners");
+
+/// 系列铸造授权
+/// 键: (系列 ID, 被授权的铸造者)
+#[cfg(feature = "cosmwasm")]
+pub const SERIES_GRANTS: Map<(String, Addr), ()> = Map::new("series_grants");
+
+/// 最近活动环形缓冲区
+/// 键: 序号 % ACTIVITY_LOG_SIZE，值: 活动记录
+#[cfg(feature = "cosmwasm")]
+pub const ACTIVITY_LOG: Map<u64, ActivityEntry> = Map::new("activity_log");
+
+/// 下一条活动记录的序号
+#[cfg(feature = "cosmwasm")]
+pub const NEXT_ACTIVITY_SEQ: Item<u64> = Item::new("next_activity_seq");
+
+/// 铸造者统计
+/// 键: 铸造者地址，值: 累计铸造数量和最近铸造时间
+#[cfg(feature = "cosmwasm")]
+pub const MINTER_STATS: Map<Addr, MinterStats> = Map::new("minter_stats");
+
+/// 铸造幂等记录
+/// 键: (铸造者地址, 幂等键)，值: 首次铸造的结果。幂等键按铸造者隔离，不同合作方的键互不冲突
+#[cfg(feature = "cosmwasm")]
+pub const MINT_RECEIPTS: Map<(Addr, String), MintReceipt> = Map::new("mint_receipts");
+
+/// 元数据预言机 secp256k1 公钥（未设置时不接受签名更新）
+#[cfg(feature = "cosmwasm")]
+pub const META_ORACLE_PUBKEY: Item<cosmwasm_std::Binary> = Item::new("meta_oracle_pubkey");
+
+/// 已使用的预言机更新序号（防止重放，更换或移除公钥时不清除）
+#[cfg(feature = "cosmwasm")]
+pub const SPENT_ORACLE_NONCES: Map<u64, ()> = Map::new("spent_oracle_nonces");
+
+/// 集合来源证明哈希（所有预提交元数据的哈希，设置后不可更改）
+#[cfg(feature = "cosmwasm")]
+pub const PROVENANCE_HASH: Item<String> = Item::new("provenance_hash");
+
+/// 按类型的铸造状态
+/// 键: NFT 类型键 (NftKind::to_key)，未设置时视为允许铸造
+#[cfg(feature = "cosmwasm")]
+pub const KIND_MINT_STATUS: Map<String, KindMintStatus> = Map::new("kind_mint_status");
+
+/// 禁止接收 NFT 的地址列表（如已知的销毁地址）
+/// 键: 被禁止的接收地址
+#[cfg(feature = "cosmwasm")]
+pub const BLOCKED_RECIPIENTS: Map<Addr, ()> = Map::new("blocked_recipients");
+
+/// 类型互换报价（报价 ID -> 报价）
+#[cfg(feature = "cosmwasm")]
+pub const SWAP_OFFERS: Map<u64, SwapOffer> = Map::new("swap_offers");
+
+/// 按期望类型索引的互换报价（(类型键, 报价 ID) -> ()）
+#[cfg(feature = "cosmwasm")]
+pub const SWAP_OFFERS_BY_WANTED_KIND: Map<(String, u64), ()> = Map::new("swap_offers_by_kind");
+
+/// 下一个互换报价 ID
+#[cfg(feature = "cosmwasm")]
+pub const NEXT_SWAP_OFFER_ID: Item<u64> = Item::new("next_swap_offer_id");
+
+/// 运营角色成员（(角色, 地址) -> ()）
+#[cfg(feature = "cosmwasm")]
+pub const ROLE_MEMBERS: Map<(String, Addr), ()> = Map::new("role_members");
+
+/// 铸造凭证签名者 secp256k1 公钥（未设置时不接受中继铸造）
+#[cfg(feature = "cosmwasm")]
+pub const MINT_VOUCHER_SIGNER: Item<cosmwasm_std::Binary> = Item::new("mint_voucher_signer");
+
+/// 中继者剩余的铸造额度
+#[cfg(feature = "cosmwasm")]
+pub const RELAY_QUOTAS: Map<Addr, u32> = Map::new("relay_quotas");
+
+/// 已使用的铸造凭证序号
+#[cfg(feature = "cosmwasm")]
+pub const SPENT_VOUCHER_NONCES: Map<u64, ()> = Map::new("spent_voucher_nonces");
+
+/// 合成价值上限（未设置时不限制）
+#[cfg(feature = "cosmwasm")]
+pub const CRAFTING_LIMIT: Item<CraftingLimit> = Item::new("crafting_limit");
+
+/// 合成版税配置（未设置时不收取）
+#[cfg(feature = "cosmwasm")]
+pub const CRAFTING_ROYALTY: Item<CraftingRoyalty> = Item::new("crafting_royalty");
+
+/// 各系列创建者累计获得的合成版税
+/// 键: 系列 ID，值: 按代币累计的金额
+#[cfg(feature = "cosmwasm")]
+pub const SERIES_ROYALTIES: Map<String, Vec<cosmwasm_std::Coin>> = Map::new("series_royalties");
+
+/// 保留 NFT ID 区间（未设置时不保留）
+#[cfg(feature = "cosmwasm")]
+pub const RESERVED_ID_RANGE: Item<ReservedIdRange> = Item::new("reserved_id_range");
+
+/// 是否启用确定性 NFT ID（未设置时为 false）
+/// 
+/// 启用后铸造、合成、导入和凭证铸造的 ID 由系列 ID 和系列内序号的哈希推导
+#[cfg(feature = "cosmwasm")]
+pub const DETERMINISTIC_TOKEN_IDS: Item<bool> = Item::new("deterministic_token_ids");
+
+/// 各所有者当前窗口的合成产出
+#[cfg(feature = "cosmwasm")]
+pub const CRAFTED_VALUE: Map<Addr, CraftedValueWindow> = Map::new("crafted_value");
+
+/// 游戏会话锁定（token ID -> 锁定信息）
+#[cfg(feature = "cosmwasm")]
+pub const SESSION_LOCKS: Map<u64, SessionLock> = Map::new("session_locks");
+
+/// 会话内锁定的 NFT（(会话 ID, token ID) -> ()）
+#[cfg(feature = "cosmwasm")]
+pub const SESSION_TOKENS: Map<(String, u64), ()> = Map::new("session_tokens");
+
+/// 礼物（领取码哈希 -> 礼物）
+#[cfg(feature = "cosmwasm")]
+pub const GIFTS: Map<String, Gift> = Map::new("gifts");
+
+/// 托管标记（token ID -> 标记），NFT 转出或销毁时自动清除
+#[cfg(feature = "cosmwasm")]
+pub const CUSTODY_TAGS: Map<u64, CustodyTag> = Map::new("custody_tags");
+
+/// 按托管方索引的托管标记（(托管方, token ID) -> ()）
+#[cfg(feature = "cosmwasm")]
+pub const CUSTODY_TOKENS: Map<(Addr, u64), ()> = Map::new("custody_tokens");
+
+/// 作为礼物托管的 NFT（token ID -> 领取码哈希）
+#[cfg(feature = "cosmwasm")]
+pub const GIFTED_TOKENS: Map<u64, String> = Map::new("gifted_tokens");
+
+/// 条件转移（token ID -> 条件转移）
+#[cfg(feature = "cosmwasm")]
+pub const CONDITIONAL_TRANSFERS: Map<u64, ConditionalTransfer> = Map::new("conditional_transfers");
+
+/// 合作集合白名单（外部 CW721 合约地址 -> 该集合 NFT 在配方中视为的类型）
+#[cfg(feature = "cosmwasm")]
+pub const PARTNER_COLLECTIONS: Map<Addr, NftKind> = Map::new("partner_collections");
+
+/// 存入合约的合作集合 NFT（(集合地址, token ID) -> 存入者）
+/// 
+/// 通过外部集合的 SendNft 存入，作为合成输入消耗或由存入者取回时删除
+#[cfg(feature = "cosmwasm")]
+pub const PARTNER_DEPOSITS: Map<(Addr, String), Addr> = Map::new("partner_deposits");
+
+/// NFT 组合包（组合包 ID -> 组合包）
+#[cfg(feature = "cosmwasm")]
+pub const BUNDLES: Map<u64, Bundle> = Map::new("bundles");
+
+/// 按持有者索引的组合包（(持有者, 组合包 ID) -> ()）
+#[cfg(feature = "cosmwasm")]
+pub const BUNDLES_BY_OWNER: Map<(Addr, u64), ()> = Map::new("bundles_by_owner");
+
+/// 托管在组合包中的 NFT（token ID -> 组合包 ID）
+#[cfg(feature = "cosmwasm")]
+pub const BUNDLED_TOKENS: Map<u64, u64> = Map::new("bundled_tokens");
+
+/// 下一个组合包 ID
+#[cfg(feature = "cosmwasm")]
+pub const NEXT_BUNDLE_ID: Item<u64> = Item::new("next_bundle_id");
+
+/// 按类型授权（(所有者, 操作员, 类型键) -> 过期时间）
+/// 
+/// 操作员可转移、销毁和合成所有者持有的该类型 NFT
+#[cfg(feature = "cosmwasm")]
+pub const KIND_APPROVALS: Map<(Addr, Addr, String), Expiration> = Map::new("kind_approvals");
+
+/// 操作员批准的剩余转移次数（(所有者, 操作员) -> 剩余次数）
+/// 
+/// 仅对设置了 `max_transfers` 的操作员批准存在，用尽时自动撤销批准
+#[cfg(feature = "cosmwasm")]
+pub const OPERATOR_BUDGETS: Map<(Addr, Addr), u32> = Map::new("operator_budgets");
+
+/// 受信任操作员注册表（管理员维护）
+#[cfg(feature = "cosmwasm")]
+pub const TRUSTED_OPERATORS: Map<Addr, ()> = Map::new("trusted_operators");
+
+/// 选择信任注册表中所有操作员的用户
+#[cfg(feature = "cosmwasm")]
+pub const TRUSTED_OPERATOR_OPT_INS: Map<Addr, ()> = Map::new("trusted_operator_opt_ins");
+
+/// 被全局停用的操作员（紧急开关，对所有用户的授权同时生效）
+#[cfg(feature = "cosmwasm")]
+pub const SUSPENDED_OPERATORS: Map<Addr, ()> = Map::new("suspended_operators");
+
+/// 每个所有者各类型 NFT 的持有数量（(所有者, 类型键) -> 数量）
+/// 
+/// 在铸造、转移、销毁和合成时同步维护，数量为 0 时删除条目
+#[cfg(feature = "cosmwasm")]
+pub const COUNTS_BY_OWNER_KIND: Map<(Addr, String), u64> = Map::new("counts_by_owner_kind");
+
+/// 持有数量计数是否从部署起完整维护
+/// 
+/// 部署时设置；从旧版本升级的合约没有历史计数，查询时回退到遍历持有的 NFT
+#[cfg(feature = "cosmwasm")]
+pub const OWNER_KIND_COUNTS_READY: Item<bool> = Item::new("owner_kind_counts_ready");
+
+/// 每个所有者的投票权（所有者 -> 持有 NFT 的兑换价值之和）
+/// 
+/// 与持有数量同步维护，按区块高度保存历史，供 DAO 按快照高度查询
+#[cfg(feature = "cosmwasm")]
+pub const VOTING_POWER: SnapshotMap<Addr, u64> = SnapshotMap::new(
+    "voting_power",
+    "voting_power__checkpoints",
+    "voting_power__changelog",
+    Strategy::EveryBlock,
+);
+
+/// 投票权历史完整记录的起始高度
+/// 
+/// 部署或索引升级完成时设置；早于该高度的快照不完整，不可查询
+#[cfg(feature = "cosmwasm")]
+pub const VOTING_POWER_SINCE: Item<u64> = Item::new("voting_power_since");
+
+/// 投票权委托（委托人 -> 受托人）
+#[cfg(feature = "cosmwasm")]
+pub const DELEGATIONS: Map<Addr, Addr> = Map::new("delegations");
+
+/// 受托人获得的委托投票权（受托人 -> 委托人投票权之和）
+/// 
+/// 委托、取消委托以及委托人投票权变化时同步调整，按区块高度保存历史
+#[cfg(feature = "cosmwasm")]
+pub const DELEGATED_POWER: SnapshotMap<Addr, u64> = SnapshotMap::new(
+    "delegated_power",
+    "delegated_power__checkpoints",
+    "delegated_power__changelog",
+    Strategy::EveryBlock,
+);
+
+/// 价格预言机配置
+#[cfg(feature = "cosmwasm")]
+pub const PRICE_ORACLE: Item<PriceOracleConfig> = Item::new("price_oracle");
+
+/// 上一次合成使用的喂价（用于偏离检查）
+#[cfg(feature = "cosmwasm")]
+pub const LAST_ORACLE_PRICE: Item<cosmwasm_std::Decimal> = Item::new("last_oracle_price");
+
+/// 合成销毁税（目标类型键 -> 销毁税规则）
+#[cfg(feature = "cosmwasm")]
+pub const BURN_TAXES: Map<String, crate::types::BurnTax> = Map::new("burn_taxes");
+
+/// 合成销毁税累计统计
+#[cfg(feature = "cosmwasm")]
+pub const BURN_TAX_STATS: Item<BurnTaxStats> = Item::new("burn_tax_stats");
+
+/// 累计收取的费用
+/// 键: (费用来源, 币种)，值: 累计金额
+#[cfg(feature = "cosmwasm")]
+pub const FEES_COLLECTED: Map<(String, String), cosmwasm_std::Uint128> = Map::new("fees_collected");
+
+/// 累计从合约提取的资金（紧急提取）
+/// 键: 币种，值: 累计金额
+#[cfg(feature = "cosmwasm")]
+pub const TREASURY_WITHDRAWN: Map<String, cosmwasm_std::Uint128> = Map::new("treasury_withdrawn");
+
+/// 工厂模式创建的子集合注册表
+/// 键: 子集合合约地址，值: 子集合记录
+#[cfg(feature = "cosmwasm")]
+pub const COLLECTIONS: Map<Addr, ChildCollection> = Map::new("child_collections");
+
+/// 等待实例化回复的子集合
+/// 
+/// 创建时写入，实例化成功的回复中登记到注册表后删除
+#[cfg(feature = "cosmwasm")]
+pub const PENDING_COLLECTION: Item<ChildCollection> = Item::new("pending_collection");
+
+/// 共享配方注册表（未设置时只使用本地配方）
+#[cfg(feature = "cosmwasm")]
+pub const RECIPE_REGISTRY: Item<RecipeRegistryConfig> = Item::new("recipe_registry");
+
+/// 注册表配方缓存
+/// 键: 目标类型键，值: 缓存的配方和读取时间
+#[cfg(feature = "cosmwasm")]
+pub const RECIPE_CACHE: Map<String, CachedRecipe> = Map::new("recipe_cache");
+
+/// 配方的产出集合（目标类型键 -> 关联集合地址）
+/// 
+/// 设置后该配方的产出在关联集合中铸造
+#[cfg(feature = "cosmwasm")]
+pub const RECIPE_OUTPUT_COLLECTIONS: Map<String, Addr> = Map::new("recipe_output_collections");
+
+/// 等待关联集合回复的合成
+/// 
+/// 合成时写入，关联集合铸造成功的回复中记录合成历史后删除
+#[cfg(feature = "cosmwasm")]
+pub const PENDING_REMOTE_SYNTHESIS: Item<PendingRemoteSynthesis> = Item::new("pending_remote_synthesis");
+
+/// 盲盒奖池（奖池 ID -> 奖池）
+#[cfg(feature = "cosmwasm")]
+pub const MINT_POOLS: Map<String, MintPool> = Map::new("mint_pools");
+
+/// 配方美元费用（目标类型键 -> 微美元）
+#[cfg(feature = "cosmwasm")]
+pub const RECIPE_USD_COSTS: Map<String, cosmwasm_std::Uint128> = Map::new("recipe_usd_costs");
+
+/// 执行锁：尚未收到回复的外部子消息数量
+/// 
+/// 大于 0 时拒绝所有执行消息，防止外部合约在回调中重入
+#[cfg(feature = "cosmwasm")]
+pub const EXECUTION_LOCK: Item<u32> = Item::new("execution_lock");
+
+/// 存储条目计数器（计数器名称 -> 条目数量）
+/// 
+/// 在写入和删除时同步维护，避免查询时遍历整个 Map
+#[cfg(feature = "cosmwasm")]
+pub const STORAGE_COUNTERS: Map<String, u64> = Map::new("storage_counters");
+
+/// 集合展示信息
+#[cfg(feature = "cosmwasm")]
+pub const COLLECTION_INFO: Item<CollectionInfo> = Item::new("collection_info");
+
+/// 维护公告（未设置时表示没有计划中的维护）
+#[cfg(feature = "cosmwasm")]
+pub const MAINTENANCE_NOTICE: Item<MaintenanceNotice> = Item::new("maintenance_notice");
+
+/// 待生效的铸造者轮换
+#[cfg(feature = "cosmwasm")]
+pub const PENDING_MINTER: Item<MinterRotation> = Item::new("pending_minter");
+
+/// 铸造者集合锁定的区块高度（设置后不可撤销，未设置时未锁定）
+#[cfg(feature = "cosmwasm")]
+pub const MINTER_SET_LOCKED_AT: Item<u64> = Item::new("minter_set_locked_at");
+
+/// 销毁监听合约列表
+/// 键: 监听合约地址，NFT 最终销毁时向其发送 BurnNotification
+#[cfg(feature = "cosmwasm")]
+pub const BURN_LISTENERS: Map<Addr, ()> = Map::new("burn_listeners");
+
+/// 用户预付费用存款
+/// 键: (用户地址, 币种)，值: 存款余额
+#[cfg(feature = "cosmwasm")]
+pub const DEPOSITS: Map<(Addr, String), cosmwasm_std::Uint128> = Map::new("deposits");
+
+/// 按币种汇总的用户存款（币种 -> 所有用户存款之和）
+/// 紧急提取不能动用这部分资金
+#[cfg(feature = "cosmwasm")]
+pub const TOTAL_DEPOSITS: Map<String, cosmwasm_std::Uint128> = Map::new("total_deposits");
+
+/// 按类型的 NFT 索引（(类型, token ID) -> ()）
+#[cfg(feature = "cosmwasm")]
+pub const TOKENS_BY_KIND: Map<(String, u64), ()> = Map::new("tokens_by_kind");
+
+/// 按系列的 NFT 索引（(系列 ID, token ID) -> ()）
+#[cfg(feature = "cosmwasm")]
+pub const TOKENS_BY_SERIES: Map<(String, u64), ()> = Map::new("tokens_by_series");
+
+/// 按系列序号的 NFT 索引（(系列 ID, 系列内序号) -> token ID）
+#[cfg(feature = "cosmwasm")]
+pub const TOKENS_BY_SERIAL: Map<(String, u64), u64> = Map::new("tokens_by_serial");
+
+/// 按集合组的 NFT 索引（(组 ID, token ID) -> ()）
+#[cfg(feature = "cosmwasm")]
+pub const TOKENS_BY_GROUP: Map<(String, u64), ()> = Map::new("tokens_by_group");
+
+/// 二级索引（类型、系列、集合组和所有者类型持有数量）是否完整
+/// 新部署在实例化时设置；旧部署在 ApplyIndexUpgrade 完成后设置
+#[cfg(feature = "cosmwasm")]
+pub const TOKEN_INDEXES_READY: Item<bool> = Item::new("token_indexes_ready");
+
+/// 索引升级进度游标
+/// 升级完成后删除
+#[cfg(feature = "cosmwasm")]
+pub const INDEX_UPGRADE: Item<IndexUpgradeCursor> = Item::new("index_upgrade");
+
+/// 存储清理进度游标
+/// 记录 GarbageCollect 当前所处阶段和已扫描到的位置
+#[cfg(feature = "cosmwasm")]
+pub const GC_CURSOR: Item<GcCursor> = Item::new("gc_cursor");
+
+/// 过期清扫进度游标
+/// 记录 SweepExpired 当前所处阶段和已扫描到的位置
+#[cfg(feature = "cosmwasm")]
+pub const SWEEP_CURSOR: Item<SweepCursor> = Item::new("sweep_cursor");
+
 /// 本地 NFT 所有权映射（用于 metadata-only 模式）
 /// 键: NFT ID，值: 所有者地址
 #[cfg(feature = "cosmwasm")]
@@ -139,11 +1065,21 @@ pub const LOCAL_OWNERSHIP: Map<u64, Addr> = Map::new("local_ownership");
 #[cfg(feature = "cosmwasm")]
 pub const TOKEN_OWNERSHIP: Map<u64, Addr> = Map::new("token_owners");
 
+/// 当前所有者取得 NFT 的时间
+/// 键: NFT ID，值: 取得时的区块高度和时间（升级前取得的 NFT 没有记录）
+#[cfg(feature = "cosmwasm")]
+pub const TOKEN_ACQUIRED: Map<u64, Acquisition> = Map::new("token_acquired");
+
 /// NFT ID 到批准信息的映射
 /// 键: NFT ID，值: 批准信息列表
 #[cfg(feature = "cosmwasm")]
 pub const TOKEN_APPROVALS: Map<u64, Vec<crate::state::Approval>> = Map::new("token_approvals");
 
+/// 所有者授予过单个 NFT 批准的反向索引
+/// 键: (所有者地址, NFT ID)，用于按所有者列出其授予的批准
+#[cfg(feature = "cosmwasm")]
+pub const APPROVED_TOKENS_BY_OWNER: Map<(Addr, u64), ()> = Map::new("approved_tokens_by_owner");
+
 /// 所有者到操作员批准的映射
 /// 键: (所有者地址, 操作员地址)，值: 批准过期时间
 #[cfg(feature = "cosmwasm")]
@@ -202,8 +1138,147 @@ pub struct ContractInfo {
     pub symbol: String,
 }
 
+/// 存储清理阶段
+/// 
+/// GarbageCollect 按阶段依次扫描各个存储，完成最后一个阶段后重新开始
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub enum GcPhase {
+    /// 清理没有所有者的全局索引条目
+    #[default]
+    AllTokens,
+    /// 清理已不存在 NFT 的批准信息
+    Approvals,
+    /// 清理所有者索引中已不属于该所有者的 NFT
+    OwnerIndex,
+    /// 清理已无现存 NFT 的系列计数
+    Series,
+}
+
+/// 索引升级阶段
+/// 
+/// 先清空升级前不可靠的持有数量，再按 token ID 升序重建全部二级索引
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub enum IndexUpgradePhase {
+    /// 清空所有者类型持有数量
+    #[default]
+    ClearCounts,
+    /// 清空所有者投票权和委托投票权
+    ClearVotingPower,
+    /// 重建二级索引
+    Build,
+}
+
+/// 索引升级游标
+/// 
+/// 重建阶段中 token ID 不大于 `last_token_id` 的 NFT 已纳入索引，之后的变更需要实时维护
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct IndexUpgradeCursor {
+    /// 当前升级阶段
+    pub phase: IndexUpgradePhase,
+    /// 已重建到的最后一个 token ID
+    pub last_token_id: Option<u64>,
+}
+
+/// 存储清理游标
+/// 
+/// 记录当前阶段以及上次扫描到的最后一个键
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct GcCursor {
+    /// 当前清理阶段
+    pub phase: GcPhase,
+    /// 上次扫描到的最后一个键（字符串形式）
+    pub start_after: Option<String>,
+}
+
+/// 过期清扫阶段
+/// 
+/// SweepExpired 依次扫描互换报价和礼物，完成最后一个阶段后重新开始
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub enum SweepPhase {
+    /// 关闭已失效的互换报价
+    #[default]
+    SwapOffers,
+    /// 退回已过期的礼物
+    Gifts,
+}
+
+/// 过期清扫游标
+/// 
+/// 记录当前阶段以及上次扫描到的最后一个键
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+pub struct SweepCursor {
+    /// 当前清扫阶段
+    pub phase: SweepPhase,
+    /// 上次扫描到的最后一个键（字符串形式）
+    pub start_after: Option<String>,
+}
+
 // ========== 实现方法 ==========
 
+impl IndexUpgradePhase {
+    /// 获取阶段名称（用于事件属性）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IndexUpgradePhase::ClearCounts => "clear_counts",
+            IndexUpgradePhase::ClearVotingPower => "clear_voting_power",
+            IndexUpgradePhase::Build => "build",
+        }
+    }
+}
+
+impl GcPhase {
+    /// 获取下一个清理阶段
+    pub fn next(&self) -> Self {
+        match self {
+            GcPhase::AllTokens => GcPhase::Approvals,
+            GcPhase::Approvals => GcPhase::OwnerIndex,
+            GcPhase::OwnerIndex => GcPhase::Series,
+            GcPhase::Series => GcPhase::AllTokens,
+        }
+    }
+
+    /// 获取阶段名称（用于事件属性）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GcPhase::AllTokens => "all_tokens",
+            GcPhase::Approvals => "approvals",
+            GcPhase::OwnerIndex => "owner_index",
+            GcPhase::Series => "series",
+        }
+    }
+}
+
+impl SweepPhase {
+    /// 获取下一个清扫阶段
+    pub fn next(&self) -> Self {
+        match self {
+            SweepPhase::SwapOffers => SweepPhase::Gifts,
+            SweepPhase::Gifts => SweepPhase::SwapOffers,
+        }
+    }
+
+    /// 获取阶段名称（用于事件属性）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SweepPhase::SwapOffers => "swap_offers",
+            SweepPhase::Gifts => "gifts",
+        }
+    }
+}
+
+impl ReservedIdRange {
+    /// 检查 ID 是否位于保留区间内
+    pub fn contains(&self, token_id: u64) -> bool {
+        self.start <= token_id && token_id <= self.end
+    }
+}
+
 impl Expiration {
     /// 检查是否已过期
     /// 
@@ -0,0 +1,230 @@
+//! 类型互换模块
+//! 
+//! 此模块实现无托管的 NFT 类型互换撮合，包括：
+//! - 创建互换报价（以指定 NFT 换取某类型的 NFT）
+//! - 接受报价，原子交换双方 NFT
+//! - 取消报价
+//! - 按期望类型查询开放报价
+//! 
+//! 报价期间 NFT 仍由创建者持有，接受时重新检查双方所有权
+
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult,
+};
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::helpers::{move_token, record_activity, validate_recipient, ensure_not_session_locked};
+use crate::msg::{Cursor, SwapOffersResponse};
+use crate::pagination::{clamp_limit, cursor_scope, decode_cursor, into_page, start_bound, MAX_LIMIT};
+use crate::state::{SwapOffer, NEXT_SWAP_OFFER_ID, SWAP_OFFERS, SWAP_OFFERS_BY_WANTED_KIND, TOKEN_META, TOKEN_OWNERSHIP};
+use crate::types::NftKind;
+
+// ========== 互换执行接口 ==========
+
+/// 创建互换报价
+/// 
+/// 以发送者持有的 NFT 换取任意一个指定类型的 NFT
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于检查过期时间
+/// - `info`: 消息信息，包含发送者
+/// - `offered_token_id`: 提供的 NFT ID
+/// - `wanted_kind`: 期望换取的 NFT 类型
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 创建结果
+pub fn execute_create_swap_offer(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    offered_token_id: u64,
+    wanted_kind: NftKind,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    // 验证所有者身份
+    let owner = TOKEN_OWNERSHIP.may_load(deps.storage, offered_token_id)?
+        .ok_or(ContractError::TokenNotFound {})?;
+    if owner != info.sender {
+        return Err(ContractError::NotOwned {});
+    }
+    let meta = TOKEN_META.load(deps.storage, offered_token_id)?;
+    if meta.is_expired(env.block.time.seconds()) {
+        return Err(ContractError::TokenExpired {});
+    }
+    ensure_not_session_locked(deps.storage, &env, offered_token_id)?;
+
+    // 分配报价 ID
+    let offer_id = NEXT_SWAP_OFFER_ID.may_load(deps.storage)?.unwrap_or(1);
+    NEXT_SWAP_OFFER_ID.save(deps.storage, &offer_id.checked_add(1).ok_or(ContractError::Overflow {})?)?;
+
+    let offer = SwapOffer {
+        id: offer_id,
+        maker: info.sender.clone(),
+        offered_token_id,
+        offered_kind: meta.kind,
+        wanted_kind: wanted_kind.clone(),
+    };
+    SWAP_OFFERS.save(deps.storage, offer_id, &offer)?;
+    SWAP_OFFERS_BY_WANTED_KIND.save(deps.storage, (wanted_kind.to_key(), offer_id), &())?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_swap_offer")
+        .add_attribute("offer_id", offer_id.to_string())
+        .add_attribute("maker", info.sender.to_string())
+        .add_attribute("offered_token_id", offered_token_id.to_string())
+        .add_attribute("wanted_kind", wanted_kind.to_key()))
+}
+
+/// 接受互换报价
+/// 
+/// 发送者以持有的期望类型 NFT 与报价中的 NFT 原子交换
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于检查过期时间和接收地址
+/// - `info`: 消息信息，包含发送者
+/// - `offer_id`: 报价 ID
+/// - `token_id`: 发送者用于交换的 NFT ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 交换结果
+pub fn execute_accept_swap(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    offer_id: u64,
+    token_id: u64,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    let offer = SWAP_OFFERS.may_load(deps.storage, offer_id)?
+        .ok_or(ContractError::SwapOfferNotFound { offer_id })?;
+    if offer.maker == info.sender {
+        return Err(ContractError::InvalidSwap { reason: "cannot accept own offer".to_string() });
+    }
+
+    // 报价中的 NFT 必须仍由创建者持有且未过期
+    let now = env.block.time.seconds();
+    let offered_meta = TOKEN_META.may_load(deps.storage, offer.offered_token_id)?;
+    let offered_owner = TOKEN_OWNERSHIP.may_load(deps.storage, offer.offered_token_id)?;
+    let offered_meta = match (offered_meta, offered_owner) {
+        (Some(meta), Some(owner)) if owner == offer.maker && !meta.is_expired(now) => meta,
+        _ => return Err(ContractError::InvalidSwap { reason: "offered token no longer available".to_string() }),
+    };
+
+    // 发送者的 NFT 必须为期望类型且未过期
+    let owner = TOKEN_OWNERSHIP.may_load(deps.storage, token_id)?
+        .ok_or(ContractError::TokenNotFound {})?;
+    if owner != info.sender {
+        return Err(ContractError::NotOwned {});
+    }
+    let meta = TOKEN_META.load(deps.storage, token_id)?;
+    if meta.kind != offer.wanted_kind {
+        return Err(ContractError::InvalidSwap { reason: "token kind does not match offer".to_string() });
+    }
+    if meta.is_expired(now) {
+        return Err(ContractError::TokenExpired {});
+    }
+
+    // 双方的 NFT 均不能处于对局锁定中
+    ensure_not_session_locked(deps.storage, &env, offer.offered_token_id)?;
+    ensure_not_session_locked(deps.storage, &env, token_id)?;
+
+    // 双方均须为有效接收地址
+    validate_recipient(deps.storage, &env.contract.address, &offer.maker)?;
+    validate_recipient(deps.storage, &env.contract.address, &info.sender)?;
+
+    // 关闭报价并原子交换
+    SWAP_OFFERS.remove(deps.storage, offer_id);
+    SWAP_OFFERS_BY_WANTED_KIND.remove(deps.storage, (offer.wanted_kind.to_key(), offer_id));
+    move_token(deps.storage, &env, offer.offered_token_id, &offer.maker, &info.sender, &offered_meta.kind)?;
+    move_token(deps.storage, &env, token_id, &info.sender, &offer.maker, &meta.kind)?;
+
+    // 记录到活动流
+    record_activity(deps.storage, &env, "swap", Some(offer.offered_token_id), &info.sender)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_swap")
+        .add_attribute("offer_id", offer_id.to_string())
+        .add_attribute("maker", offer.maker.to_string())
+        .add_attribute("taker", info.sender.to_string())
+        .add_attribute("offered_token_id", offer.offered_token_id.to_string())
+        .add_attribute("token_id", token_id.to_string()))
+}
+
+/// 取消互换报价
+/// 
+/// 只有报价创建者可以取消，合约暂停期间也可取消
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `info`: 消息信息，包含发送者
+/// - `offer_id`: 报价 ID
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 取消结果
+pub fn execute_cancel_swap_offer(
+    deps: DepsMut,
+    info: MessageInfo,
+    offer_id: u64,
+) -> Result<Response, ContractError> {
+    let offer = SWAP_OFFERS.may_load(deps.storage, offer_id)?
+        .ok_or(ContractError::SwapOfferNotFound { offer_id })?;
+    if offer.maker != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    SWAP_OFFERS.remove(deps.storage, offer_id);
+    SWAP_OFFERS_BY_WANTED_KIND.remove(deps.storage, (offer.wanted_kind.to_key(), offer_id));
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_swap_offer")
+        .add_attribute("offer_id", offer_id.to_string()))
+}
+
+// ========== 互换查询接口 ==========
+
+/// 按期望类型查询开放的互换报价
+/// 
+/// 报价中的 NFT 可能已被转出，接受时会重新检查
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `wanted_kind`: 期望换取的 NFT 类型
+/// - `start_after`: 上一页返回的 `next_cursor`
+/// - `limit`: 返回数量限制
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 报价列表
+pub fn query_swap_offers(
+    deps: Deps,
+    wanted_kind: NftKind,
+    start_after: Option<Cursor>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = clamp_limit(limit, MAX_LIMIT);
+    let start = start_bound(decode_cursor::<u64>(cursor_scope::SWAP_OFFERS, start_after)?);
+
+    let offers = SWAP_OFFERS_BY_WANTED_KIND
+        .prefix(wanted_kind.to_key())
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit + 1)
+        .map(|offer_id| SWAP_OFFERS.load(deps.storage, offer_id?))
+        .collect::<StdResult<Vec<_>>>()?;
+    let page = into_page(cursor_scope::SWAP_OFFERS, offers, limit, |offer| offer.id);
+
+    to_json_binary(&SwapOffersResponse {
+        offers: page.items,
+        next_cursor: page.next_cursor,
+        has_more: page.has_more,
+    })
+}
@@ -0,0 +1,148 @@
+//! 过期清扫模块
+//! 
+//! 此模块实现无需权限的市场状态清扫，包括：
+//! - 关闭已失效的互换报价（报价中的 NFT 已销毁、已转出或已过期）
+//! - 退回已过期的礼物（托管的 NFT 归还赠送者）
+//! 
+//! 按阶段分批扫描，进度游标保存在状态中，任何人都可以重复调用，
+//! 避免无人处理的报价和礼物无限期占用存储
+
+use cosmwasm_std::{Addr, DepsMut, Env, Order, Response, Storage};
+use cw_storage_plus::Bound;
+
+use crate::error::ContractError;
+use crate::helpers::{escrowed_token_meta, move_token, parse_token_id};
+use crate::state::{
+    SweepCursor, SweepPhase, GIFTS, GIFTED_TOKENS, SWAP_OFFERS, SWAP_OFFERS_BY_WANTED_KIND, SWEEP_CURSOR,
+    TOKEN_META, TOKEN_OWNERSHIP,
+};
+
+/// 单次清扫默认扫描数量
+const DEFAULT_SWEEP_LIMIT: u32 = 30;
+
+/// 单次清扫最大扫描数量
+const MAX_SWEEP_LIMIT: u32 = 100;
+
+// ========== 清扫执行接口 ==========
+
+/// 清扫过期的报价和礼物（无需权限）
+/// 
+/// 每次只扫描当前阶段的一批条目，本阶段扫描完毕后进入下一阶段。
+/// 与取消报价和取消礼物一致，合约暂停期间也可调用
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，包含合约地址和当前时间
+/// - `limit`: 本次最多扫描的条目数量
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 清扫结果
+pub fn execute_sweep_expired(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_SWEEP_LIMIT).clamp(1, MAX_SWEEP_LIMIT) as usize;
+    let cursor = SWEEP_CURSOR.may_load(deps.storage)?.unwrap_or_default();
+    let start_after = cursor.start_after.as_deref();
+
+    // 执行当前阶段的清扫
+    let (scanned, swept, last_key) = match cursor.phase {
+        SweepPhase::SwapOffers => sweep_swap_offers(deps.storage, &env, start_after, limit)?,
+        SweepPhase::Gifts => sweep_gifts(deps.storage, &env, start_after, limit)?,
+    };
+
+    // 本阶段扫描完毕则进入下一阶段，否则记录游标以便下次继续
+    let next_cursor = if scanned < limit {
+        SweepCursor { phase: cursor.phase.next(), start_after: None }
+    } else {
+        SweepCursor { phase: cursor.phase.clone(), start_after: last_key }
+    };
+    SWEEP_CURSOR.save(deps.storage, &next_cursor)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "sweep_expired")
+        .add_attribute("phase", cursor.phase.as_str())
+        .add_attribute("scanned", scanned.to_string())
+        .add_attribute("swept", swept.to_string())
+        .add_attribute("next_phase", next_cursor.phase.as_str()))
+}
+
+// ========== 内部辅助函数 ==========
+
+/// 关闭已失效的互换报价
+/// 
+/// 报价中的 NFT 已销毁、不再由创建者持有或已过期时，报价无法再被接受
+fn sweep_swap_offers(
+    storage: &mut dyn Storage,
+    env: &Env,
+    start_after: Option<&str>,
+    limit: usize,
+) -> Result<(usize, u64, Option<String>), ContractError> {
+    let start = start_after.map(parse_token_id).transpose()?.map(Bound::exclusive);
+    let offers = SWAP_OFFERS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let now = env.block.time.seconds();
+    let mut swept = 0u64;
+    for (offer_id, offer) in &offers {
+        let owner = TOKEN_OWNERSHIP.may_load(storage, offer.offered_token_id)?;
+        let meta = TOKEN_META.may_load(storage, offer.offered_token_id)?;
+        let available = match (owner, meta) {
+            (Some(owner), Some(meta)) => owner == offer.maker && !meta.is_expired(now),
+            _ => false,
+        };
+        if !available {
+            SWAP_OFFERS.remove(storage, *offer_id);
+            SWAP_OFFERS_BY_WANTED_KIND.remove(storage, (offer.wanted_kind.to_key(), *offer_id));
+            swept += 1;
+        }
+    }
+
+    Ok((offers.len(), swept, offers.last().map(|(offer_id, _)| offer_id.to_string())))
+}
+
+/// 退回已过期的礼物
+/// 
+/// 托管的 NFT 仍存在时归还赠送者，与赠送者自行取消礼物的效果相同
+fn sweep_gifts(
+    storage: &mut dyn Storage,
+    env: &Env,
+    start_after: Option<&str>,
+    limit: usize,
+) -> Result<(usize, u64, Option<String>), ContractError> {
+    let start = start_after.map(|key| Bound::exclusive(key.to_string()));
+    let gifts = GIFTS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let now = env.block.time.seconds();
+    let mut swept = 0u64;
+    for (claim_hash, gift) in &gifts {
+        if now < gift.expires {
+            continue;
+        }
+        GIFTS.remove(storage, claim_hash.clone());
+        GIFTED_TOKENS.remove(storage, gift.token_id);
+        return_gift(storage, env, gift.token_id, &gift.sender)?;
+        swept += 1;
+    }
+
+    Ok((gifts.len(), swept, gifts.last().map(|(claim_hash, _)| claim_hash.clone())))
+}
+
+/// 将托管的礼物 NFT 归还赠送者（NFT 已被回收或不再由合约持有时跳过）
+fn return_gift(
+    storage: &mut dyn Storage,
+    env: &Env,
+    token_id: u64,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    if let Some(meta) = escrowed_token_meta(storage, env, token_id)? {
+        move_token(storage, env, token_id, &env.contract.address, sender, &meta.kind)?;
+    }
+    Ok(())
+}
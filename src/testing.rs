@@ -0,0 +1,278 @@
+//! 集成测试工具模块
+//!
+//! 此模块（`test-utils` 特性）为下游合约（盲盒、市场等）提供基于 cw-multi-test 的测试夹具：
+//! - `LuckeeTestAppBuilder`：部署合约并预置配方、铸造者和 NFT
+//! - `LuckeeTestApp`：封装常用执行和查询的类型化辅助方法
+//!
+//! 下游合约可将自身合约存入 `LuckeeTestApp::app` 后与本合约交互
+
+use anyhow::Result as AnyResult;
+use cosmwasm_std::{Addr, Coin, Empty};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use serde::de::DeserializeOwned;
+
+use crate::contract::{execute, instantiate, migrate, query, reply};
+use crate::events::event_attributes;
+use crate::helpers::parse_synthesized_token_id;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, TokenMetaResponse, TokensByKindResponse};
+use crate::types::{NftKind, NftMeta, Recipe, Scale};
+
+/// 默认合约所有者
+pub const OWNER: &str = "luckee_owner";
+/// 默认主要铸造者
+pub const MINTER: &str = "luckee_minter";
+/// 默认系列ID
+pub const SERIES_ID: &str = "test_series";
+
+/// 返回可存入 cw-multi-test `App` 的合约包装
+pub fn luckee_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(execute, instantiate, query)
+        .with_migrate(migrate)
+        .with_reply(reply);
+    Box::new(contract)
+}
+
+/// 构造测试用 NFT 元数据
+///
+/// # 参数
+/// - `kind`: NFT 类型
+/// - `serial`: 系列内序号
+///
+/// # 返回值
+/// - `NftMeta`: 属于默认系列、无过期时间的元数据
+pub fn test_meta(kind: NftKind, serial: u64) -> NftMeta {
+    NftMeta {
+        kind,
+        scale_origin: Scale::Tiny,
+        physical_sku: None,
+        crafted_from: None,
+        series_id: SERIES_ID.into(),
+        collection_group_id: None,
+        serial_in_series: serial,
+        expires_at: None,
+    }
+}
+
+/// 测试应用构建器
+///
+/// 部署时按顺序执行：实例化、设置配方、添加铸造者、铸造预置 NFT
+pub struct LuckeeTestAppBuilder {
+    owner: String,
+    minter: String,
+    name: String,
+    symbol: String,
+    base_uri: Option<String>,
+    recipes: Vec<(NftKind, Recipe)>,
+    minters: Vec<String>,
+    tokens: Vec<(String, NftKind)>,
+}
+
+impl Default for LuckeeTestAppBuilder {
+    fn default() -> Self {
+        Self {
+            owner: OWNER.into(),
+            minter: MINTER.into(),
+            name: "Luckee NFT".into(),
+            symbol: "LUCKEE".into(),
+            base_uri: None,
+            recipes: vec![],
+            minters: vec![],
+            tokens: vec![],
+        }
+    }
+}
+
+impl LuckeeTestAppBuilder {
+    /// 创建使用默认所有者和铸造者的构建器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置合约所有者（实例化发送者）
+    pub fn with_owner(mut self, owner: impl Into<String>) -> Self {
+        self.owner = owner.into();
+        self
+    }
+
+    /// 设置主要铸造者
+    pub fn with_minter(mut self, minter: impl Into<String>) -> Self {
+        self.minter = minter.into();
+        self
+    }
+
+    /// 设置基础 URI
+    pub fn with_base_uri(mut self, base_uri: impl Into<String>) -> Self {
+        self.base_uri = Some(base_uri.into());
+        self
+    }
+
+    /// 设置（覆盖）合成配方，默认配方在实例化时已写入
+    pub fn with_recipe(mut self, target: NftKind, recipe: Recipe) -> Self {
+        self.recipes.push((target, recipe));
+        self
+    }
+
+    /// 添加额外的铸造者
+    pub fn with_extra_minter(mut self, minter: impl Into<String>) -> Self {
+        self.minters.push(minter.into());
+        self
+    }
+
+    /// 预置 NFT，部署后由主要铸造者按顺序铸造（ID 从 1 开始）
+    pub fn with_tokens(mut self, owner: impl Into<String>, kind: NftKind, count: u32) -> Self {
+        let owner = owner.into();
+        for _ in 0..count {
+            self.tokens.push((owner.clone(), kind.clone()));
+        }
+        self
+    }
+
+    /// 部署合约并执行预置操作
+    ///
+    /// # 返回值
+    /// - `AnyResult<LuckeeTestApp>`: 部署完成的测试应用
+    pub fn build(self) -> AnyResult<LuckeeTestApp> {
+        self.build_with_app(App::default())
+    }
+
+    /// 在已有的 `App` 中部署合约并执行预置操作
+    ///
+    /// # 参数
+    /// - `app`: 已存入下游合约的测试应用
+    ///
+    /// # 返回值
+    /// - `AnyResult<LuckeeTestApp>`: 部署完成的测试应用
+    pub fn build_with_app(self, mut app: App) -> AnyResult<LuckeeTestApp> {
+        let owner = Addr::unchecked(&self.owner);
+        let code_id = app.store_code(luckee_contract());
+        let contract = app.instantiate_contract(
+            code_id,
+            owner.clone(),
+            &InstantiateMsg {
+                name: self.name,
+                symbol: self.symbol,
+                minter: self.minter.clone(),
+                base_uri: self.base_uri,
+            },
+            &[],
+            "Luckee NFT",
+            Some(self.owner.clone()),
+        )?;
+
+        let mut test_app = LuckeeTestApp {
+            app,
+            contract,
+            owner,
+            minter: Addr::unchecked(&self.minter),
+            next_token_id: 1,
+        };
+        for (target, recipe) in self.recipes {
+            test_app.execute_as(&self.owner, &ExecuteMsg::SetRecipe { target, recipe })?;
+        }
+        for minter in self.minters {
+            test_app.execute_as(&self.owner, &ExecuteMsg::SetMinter { minter, allowed: true })?;
+        }
+        for (owner, kind) in self.tokens {
+            test_app.mint(&owner, kind)?;
+        }
+        Ok(test_app)
+    }
+}
+
+/// 已部署 Luckee NFT 合约的测试应用
+pub struct LuckeeTestApp {
+    /// cw-multi-test 应用，可继续存入和调用其他合约
+    pub app: App,
+    /// Luckee NFT 合约地址
+    pub contract: Addr,
+    /// 合约所有者
+    pub owner: Addr,
+    /// 主要铸造者
+    pub minter: Addr,
+    next_token_id: u64,
+}
+
+impl LuckeeTestApp {
+    /// 以指定发送者执行合约消息（不附带资金）
+    pub fn execute_as(&mut self, sender: &str, msg: &ExecuteMsg) -> AnyResult<AppResponse> {
+        self.execute_with_funds(sender, msg, &[])
+    }
+
+    /// 以指定发送者执行合约消息并附带资金
+    pub fn execute_with_funds(&mut self, sender: &str, msg: &ExecuteMsg, funds: &[Coin]) -> AnyResult<AppResponse> {
+        self.app.execute_contract(Addr::unchecked(sender), self.contract.clone(), msg, funds)
+    }
+
+    /// 查询合约并解析为指定类型
+    pub fn query<T: DeserializeOwned>(&self, msg: &QueryMsg) -> AnyResult<T> {
+        Ok(self.app.wrap().query_wasm_smart(self.contract.clone(), msg)?)
+    }
+
+    /// 由主要铸造者铸造默认系列的 NFT
+    ///
+    /// # 返回值
+    /// - `AnyResult<u64>`: 新 NFT 的 ID
+    pub fn mint(&mut self, owner: &str, kind: NftKind) -> AnyResult<u64> {
+        let meta = test_meta(kind, self.next_token_id);
+        let minter = self.minter.to_string();
+        self.mint_as(&minter, owner, meta)
+    }
+
+    /// 由指定铸造者铸造 NFT，使用下一个未占用的 ID
+    ///
+    /// # 返回值
+    /// - `AnyResult<u64>`: 新 NFT 的 ID
+    pub fn mint_as(&mut self, minter: &str, owner: &str, extension: NftMeta) -> AnyResult<u64> {
+        let token_id = self.next_token_id;
+        self.execute_as(minter, &ExecuteMsg::Mint { token_id, owner: owner.into(), extension, idempotency_key: None })?;
+        self.next_token_id += 1;
+        Ok(token_id)
+    }
+
+    /// 转移 NFT
+    pub fn transfer(&mut self, sender: &str, recipient: &str, token_id: u64) -> AnyResult<AppResponse> {
+        self.execute_as(sender, &ExecuteMsg::TransferNft { recipient: recipient.into(), token_id })
+    }
+
+    /// 销毁 NFT
+    pub fn burn(&mut self, sender: &str, token_id: u64) -> AnyResult<AppResponse> {
+        self.execute_as(sender, &ExecuteMsg::Burn { token_id })
+    }
+
+    /// 执行合成
+    ///
+    /// # 返回值
+    /// - `AnyResult<u64>`: 合成产出的 NFT ID
+    pub fn synthesize(&mut self, sender: &str, inputs: Vec<u64>, target: NftKind) -> AnyResult<u64> {
+        let res = self.execute_as(sender, &ExecuteMsg::Synthesize { inputs, target })?;
+        let output_token_id = parse_synthesized_token_id(&res.events)
+            .ok_or_else(|| anyhow::anyhow!("missing {} attribute", event_attributes::OUTPUT_TOKEN_ID))?;
+        self.next_token_id = self.next_token_id.max(output_token_id + 1);
+        Ok(output_token_id)
+    }
+
+    /// 查询 NFT 所有者
+    pub fn owner_of(&self, token_id: u64) -> AnyResult<String> {
+        let res: cw721::OwnerOfResponse = self.query(&QueryMsg::OwnerOf { token_id, include_expired: None })?;
+        Ok(res.owner)
+    }
+
+    /// 查询 NFT 元数据
+    pub fn token_meta(&self, token_id: u64) -> AnyResult<NftMeta> {
+        let res: TokenMetaResponse = self.query(&QueryMsg::TokenMeta { token_id })?;
+        Ok(res.meta)
+    }
+
+    /// 查询所有者持有的 NFT（最多一页）
+    pub fn tokens_of(&self, owner: &str) -> AnyResult<Vec<u64>> {
+        let res: cw721::TokensResponse =
+            self.query(&QueryMsg::Tokens { owner: owner.into(), start_after: None, limit: None })?;
+        Ok(res.tokens.iter().map(|id| id.parse()).collect::<Result<_, _>>()?)
+    }
+
+    /// 查询指定类型的 NFT（最多一页）
+    pub fn tokens_by_kind(&self, kind: NftKind) -> AnyResult<Vec<u64>> {
+        let res: TokensByKindResponse = self.query(&QueryMsg::TokensByKind { kind, start_after: None, limit: None })?;
+        Ok(res.tokens)
+    }
+}
@@ -0,0 +1,108 @@
+//! 资金统计模块
+//! 
+//! 此模块记录合约留存的收入和提取，供 DAO 对账，包括：
+//! - 收入统计：按来源和币种累计收取的费用
+//! - 提取统计：按币种累计通过紧急提取转出的资金
+//! - 资金查询：当前余额、累计收入和累计提取
+//! 
+//! 只统计留存在合约中的费用；销毁税（见 BurnTaxStats）和合成版税在收取时即转出，不计入收入
+
+use cosmwasm_std::{to_json_binary, Binary, Coin, Deps, Env, Order, StdResult, Storage};
+
+use crate::error::ContractError;
+use crate::msg::{FeeSourceTotal, TreasuryResponse};
+use crate::state::{CONFIG, FEES_COLLECTED, TOTAL_DEPOSITS, TREASURY_WITHDRAWN};
+
+/// 费用来源
+pub mod fee_source {
+    /// 合成费用（配方费用或按喂价换算的美元费用）
+    pub const SYNTHESIS: &str = "synthesis";
+}
+
+// ========== 统计记录 ==========
+
+/// 累计收取的费用
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `source`: 费用来源（见 `fee_source`）
+/// - `fee`: 收取的费用
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 记录结果
+pub fn record_fee_collected(storage: &mut dyn Storage, source: &str, fee: &Coin) -> Result<(), ContractError> {
+    if fee.amount.is_zero() {
+        return Ok(());
+    }
+    let key = (source.to_string(), fee.denom.clone());
+    let total = FEES_COLLECTED.may_load(storage, key.clone())?.unwrap_or_default();
+    let total = total.checked_add(fee.amount).map_err(|_| ContractError::Overflow {})?;
+    FEES_COLLECTED.save(storage, key, &total)?;
+    Ok(())
+}
+
+/// 累计从合约提取的资金
+/// 
+/// # 参数
+/// - `storage`: 存储接口
+/// - `amount`: 提取的资金
+/// 
+/// # 返回值
+/// - `Result<(), ContractError>`: 记录结果
+pub fn record_treasury_withdrawal(storage: &mut dyn Storage, amount: &[Coin]) -> Result<(), ContractError> {
+    for coin in amount.iter().filter(|coin| !coin.amount.is_zero()) {
+        let total = TREASURY_WITHDRAWN.may_load(storage, coin.denom.clone())?.unwrap_or_default();
+        let total = total.checked_add(coin.amount).map_err(|_| ContractError::Overflow {})?;
+        TREASURY_WITHDRAWN.save(storage, coin.denom.clone(), &total)?;
+    }
+    Ok(())
+}
+
+// ========== 资金查询接口 ==========
+
+/// 查询合约资金
+/// 
+/// 余额按接受的支付币种以及出现过收入、提取或存款的币种查询；
+/// 余额中包含用户的预付存款，`reserved` 为其中不可紧急提取的存款总额
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和查询访问
+/// - `env`: 环境信息，用于取得合约地址
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 当前余额、存款总额、按来源的累计收入和累计提取
+pub fn query_treasury(deps: Deps, env: Env) -> StdResult<Binary> {
+    let mut fees_collected: Vec<FeeSourceTotal> = vec![];
+    let mut denoms = alloc::collections::BTreeSet::new();
+    for item in FEES_COLLECTED.range(deps.storage, None, None, Order::Ascending) {
+        let ((source, denom), amount) = item?;
+        denoms.insert(denom.clone());
+        let coin = Coin { denom, amount };
+        match fees_collected.iter_mut().find(|total| total.source == source) {
+            Some(total) => total.amount.push(coin),
+            None => fees_collected.push(FeeSourceTotal { source, amount: vec![coin] }),
+        }
+    }
+
+    let withdrawn = TREASURY_WITHDRAWN
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+        .collect::<StdResult<Vec<_>>>()?;
+    denoms.extend(withdrawn.iter().map(|coin| coin.denom.clone()));
+    let reserved = TOTAL_DEPOSITS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+        .collect::<StdResult<Vec<_>>>()?;
+    denoms.extend(reserved.iter().map(|coin| coin.denom.clone()));
+    denoms.extend(CONFIG.load(deps.storage)?.accepted_denoms.into_iter().map(|accepted| accepted.denom));
+
+    let mut balances = vec![];
+    for denom in denoms {
+        let balance = deps.querier.query_balance(env.contract.address.clone(), denom)?;
+        if !balance.amount.is_zero() {
+            balances.push(balance);
+        }
+    }
+
+    to_json_binary(&TreasuryResponse { balances, reserved, fees_collected, withdrawn })
+}
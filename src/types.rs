@@ -3,8 +3,10 @@
 //! 此模块定义了合约中使用的所有核心数据类型，包括：
 //! - NFT 类型枚举 (NftKind)
 //! - 盲盒规模枚举 (Scale)
+//! - 按类型的铸造状态 (KindMintStatus)
+//! - 运营角色 (Role)
 //! - NFT 元数据结构 (NftMeta)
-//! - 合成配方相关结构
+//! - 合成配方相关结构（含合成销毁税）
 //! - 各种请求和响应结构
 
 #[cfg(feature = "std")]
@@ -54,6 +56,19 @@ pub enum NftKind {
 // ========== NftKind 实现方法 ==========
 
 impl NftKind {
+    /// 全部 NFT 类型，按稀有度从低到高排列
+    pub const ALL: [NftKind; 9] = [
+        NftKind::Clover,
+        NftKind::Firefly,
+        NftKind::CrimsonKoi,
+        NftKind::MagicalLamp,
+        NftKind::FatesSpindle,
+        NftKind::Sage,
+        NftKind::Polaris,
+        NftKind::WheelOfDestiny,
+        NftKind::Genesis,
+    ];
+
     /// 获取 NFT 的稀有度等级
     /// 
     /// 返回 0-8 的数字，数字越大表示越稀有
@@ -135,6 +150,7 @@ impl NftKind {
     /// 
     /// # 返回值
     /// - `Result<Self, cosmwasm_std::StdError>`: 转换结果
+    #[cfg(feature = "cosmwasm")]
     pub fn from_key(key: &str) -> Result<Self, cosmwasm_std::StdError> {
         match key {
             "Clover" => Ok(NftKind::Clover),
@@ -173,6 +189,9 @@ pub enum Scale {
 // ========== Scale 实现方法 ==========
 
 impl Scale {
+    /// 全部盲盒规模，按从小到大排列
+    pub const ALL: [Scale; 5] = [Scale::Tiny, Scale::Small, Scale::Medium, Scale::Large, Scale::Huge];
+
     /// 获取规模对应的头奖 NFT 类型
     /// 
     /// 根据盲盒规模返回对应的头奖 NFT 类型
@@ -190,6 +209,110 @@ impl Scale {
     }
 }
 
+// ========== 铸造控制 ==========
+
+/// 按类型的铸造状态
+/// 
+/// 控制某一 NFT 类型能否通过铸造、批量铸造或合成产生
+#[cw_serde]
+pub enum KindMintStatus {
+    /// 允许铸造（默认）
+    Enabled,
+    /// 暂停铸造，可重新启用
+    Paused,
+    /// 永久冻结铸造，设置后不可更改
+    Frozen,
+}
+
+impl KindMintStatus {
+    /// 是否允许铸造
+    pub fn is_mintable(&self) -> bool {
+        matches!(self, KindMintStatus::Enabled)
+    }
+}
+
+// ========== 事件详细程度 ==========
+
+/// 事件详细程度
+/// 
+/// 控制是否发出逐条明细事件。部分链按事件字节收取较高的 gas，
+/// 运营方可在成本与可索引性之间取舍
+#[cw_serde]
+pub enum EventVerbosity {
+    /// 发出全部事件（默认）：批量铸造的逐个铸造事件、合成输入列表和元数据变更明细
+    Verbose,
+    /// 只发出汇总事件和属性，省略逐条明细
+    Minimal,
+}
+
+impl EventVerbosity {
+    /// 是否发出逐条明细事件
+    pub fn is_verbose(&self) -> bool {
+        matches!(self, EventVerbosity::Verbose)
+    }
+}
+
+// ========== 会话密钥定义 ==========
+
+/// 会话密钥可执行的操作
+/// 
+/// 所有者授予会话密钥时逐项列出，密钥只能代所有者执行列出的操作
+#[cw_serde]
+pub enum SessionKeyScope {
+    /// 转移 NFT（TransferNft）
+    TransferNft,
+    /// 批准单个 NFT（Approve）
+    Approve,
+    /// 撤销单个 NFT 的批准（Revoke）
+    Revoke,
+    /// 使用所有者的 NFT 合成（Synthesize）
+    Synthesize,
+}
+
+impl SessionKeyScope {
+    /// 获取操作名称（用于事件属性）
+    /// 
+    /// # 返回值
+    /// - `&'static str`: 操作名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionKeyScope::TransferNft => "transfer_nft",
+            SessionKeyScope::Approve => "approve",
+            SessionKeyScope::Revoke => "revoke",
+            SessionKeyScope::Synthesize => "synthesize",
+        }
+    }
+}
+
+// ========== 运营角色定义 ==========
+
+/// 运营角色枚举
+/// 
+/// 由合约所有者授予的受限权限，持有者只能执行对应的运营操作
+#[cw_serde]
+pub enum Role {
+    /// 空投：从合约托管库存中批量分发 NFT
+    Airdrop,
+    /// 游戏服务器：对局期间锁定玩家的 NFT
+    GameServer,
+    /// 中继铸造：代用户提交签名的铸造凭证，受额度限制
+    MintRelay,
+}
+
+impl Role {
+    /// 将角色转换为存储键
+    /// 
+    /// # 返回值
+    /// - `&'static str`: 对应的字符串键
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Airdrop => "airdrop",
+            Role::GameServer => "game_server",
+            Role::MintRelay => "mint_relay",
+        }
+    }
+}
+
 // ========== NFT 元数据结构 ==========
 
 /// NFT 扩展元数据结构
@@ -211,6 +334,21 @@ pub struct NftMeta {
     pub collection_group_id: Option<String>,
     /// 系列内序号
     pub serial_in_series: u64,
+    /// 过期时间（Unix 秒，可选）。过期后不可转移，并可被任何人回收销毁
+    pub expires_at: Option<u64>,
+}
+
+impl NftMeta {
+    /// 判断 NFT 在指定时间是否已过期
+    /// 
+    /// # 参数
+    /// - `now`: 当前时间（Unix 秒）
+    /// 
+    /// # 返回值
+    /// - `bool`: 是否已过期（未设置过期时间时始终为 false）
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.map_or(false, |expires_at| now >= expires_at)
+    }
 }
 
 // ========== 合成相关结构 ==========
@@ -218,6 +356,7 @@ pub struct NftMeta {
 /// 合成配方结构
 /// 
 /// 定义如何将多个 NFT 合成为一个新的 NFT
+#[cfg(feature = "cosmwasm")]
 #[cw_serde]
 pub struct Recipe {
     /// 输入 NFT 列表
@@ -239,6 +378,51 @@ pub struct RecipeInput {
     pub count: u32,
 }
 
+/// 合成销毁税
+/// 
+/// 按配方（目标类型）配置的通缩规则，合成时在配方要求之外额外消耗
+#[cfg(feature = "cosmwasm")]
+#[cw_serde]
+pub enum BurnTax {
+    /// 额外消耗一个配方第一项输入类型的 NFT
+    ExtraInput {},
+    /// 额外收取原生代币，发送至 sink 地址（未设置时直接销毁）
+    NativeFee { amount: cosmwasm_std::Coin, sink: Option<String> },
+}
+
+/// 旧版合约类型映射
+/// 
+/// 将旧版合约元数据中 `kind` 属性的取值映射为本合约的 NFT 类型
+#[cw_serde]
+pub struct LegacyKindMapping {
+    /// 旧版元数据中 `kind` 属性的取值
+    pub legacy_kind: String,
+    /// 导入后的 NFT 类型
+    pub kind: NftKind,
+}
+
+/// 奖池条目
+/// 
+/// 奖池中一种 NFT 类型的权重，权重之比即抽取概率之比
+#[cw_serde]
+pub struct PoolEntry {
+    /// NFT 类型
+    pub kind: NftKind,
+    /// 权重
+    pub weight: u32,
+}
+
+/// 接受的支付币种
+/// 
+/// `min_amount` 为单次附带该币种的最低数量，用于拒绝粉尘支付
+#[cw_serde]
+pub struct AcceptedDenom {
+    /// 币种
+    pub denom: String,
+    /// 单次附带的最低数量
+    pub min_amount: cosmwasm_std::Uint128,
+}
+
 // ========== 请求结构 ==========
 
 /// 合成请求结构
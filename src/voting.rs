@@ -0,0 +1,188 @@
+//! 投票权模块
+//! 
+//! 此模块为 DAO 集成提供按持有价值加权的投票权，包括：
+//! - 查询所有者当前的投票权
+//! - 按快照高度查询历史投票权，防止提案期间转移 NFT 重复投票
+//! - 投票权委托：持有者将投票权委托给其他地址，受托人的委托投票权随委托人持有变化实时调整
+//! 
+//! 投票权为所有者持有 NFT 的兑换价值之和，在铸造、转移、销毁和合成时与持有数量同步维护
+
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
+
+use crate::context::ExecuteContext;
+use crate::error::ContractError;
+use crate::helpers::adjust_delegated_power;
+use crate::msg::{DelegatedPowerResponse, DelegationResponse, VotingPowerResponse};
+use crate::state::{DELEGATED_POWER, DELEGATIONS, TOKENS_BY_OWNER, TOKEN_META, VOTING_POWER, VOTING_POWER_SINCE};
+
+// ========== 委托执行接口 ==========
+
+/// 将投票权委托给其他地址
+/// 
+/// 已有委托时改为委托给新的受托人，委托人当前的投票权从原受托人转到新受托人。
+/// 委托只转移投票权，NFT 的所有权和转移权限不受影响
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `ctx`: 执行上下文，包含配置和暂停状态
+/// - `env`: 环境信息，用于记录委托投票权历史
+/// - `info`: 消息信息，发送者为委托人
+/// - `to`: 受托人地址
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 委托结果
+pub fn execute_delegate(
+    deps: DepsMut,
+    ctx: &ExecuteContext,
+    env: Env,
+    info: MessageInfo,
+    to: String,
+) -> Result<Response, ContractError> {
+    // 检查合约是否暂停
+    ctx.ensure_not_paused()?;
+
+    let delegate = deps.api.addr_validate(&to)?;
+    if delegate == info.sender {
+        return Err(ContractError::InvalidDelegation { reason: "cannot delegate to self".to_string() });
+    }
+
+    // 将委托人当前的投票权从原受托人转到新受托人
+    let power = VOTING_POWER.may_load(deps.storage, info.sender.clone())?.unwrap_or(0);
+    let height = env.block.height;
+    if let Some(previous) = DELEGATIONS.may_load(deps.storage, info.sender.clone())? {
+        adjust_delegated_power(deps.storage, &previous, power, false, height)?;
+    }
+    adjust_delegated_power(deps.storage, &delegate, power, true, height)?;
+    DELEGATIONS.save(deps.storage, info.sender.clone(), &delegate)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "delegate")
+        .add_attribute("delegator", info.sender.to_string())
+        .add_attribute("delegate", delegate.to_string())
+        .add_attribute("power", power.to_string()))
+}
+
+/// 取消投票权委托
+/// 
+/// 与取消报价一致，合约暂停期间也可调用
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储访问
+/// - `env`: 环境信息，用于记录委托投票权历史
+/// - `info`: 消息信息，发送者为委托人
+/// 
+/// # 返回值
+/// - `Result<Response, ContractError>`: 取消结果
+pub fn execute_undelegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let delegate = DELEGATIONS.may_load(deps.storage, info.sender.clone())?
+        .ok_or_else(|| ContractError::InvalidDelegation { reason: "no delegation".to_string() })?;
+
+    let power = VOTING_POWER.may_load(deps.storage, info.sender.clone())?.unwrap_or(0);
+    adjust_delegated_power(deps.storage, &delegate, power, false, env.block.height)?;
+    DELEGATIONS.remove(deps.storage, info.sender.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "undelegate")
+        .add_attribute("delegator", info.sender.to_string())
+        .add_attribute("delegate", delegate.to_string())
+        .add_attribute("power", power.to_string()))
+}
+
+// ========== 投票权查询接口 ==========
+
+/// 查询所有者的投票权
+/// 
+/// 未指定快照高度时返回当前投票权；指定时返回该高度开始时（该高度内的变更之前）的投票权。
+/// 快照高度必须晚于历史记录起始高度且不晚于当前高度，索引升级期间不提供快照。
+/// 投票权不因委托而减少，DAO 按需结合委托关系计票
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，用于检查快照高度
+/// - `owner`: 所有者地址
+/// - `at_snapshot`: 快照区块高度
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 投票权
+pub fn query_voting_power(deps: Deps, env: Env, owner: String, at_snapshot: Option<u64>) -> StdResult<Binary> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let since = VOTING_POWER_SINCE.may_load(deps.storage)?;
+
+    let power = match at_snapshot {
+        Some(height) => {
+            ensure_snapshot_height(deps, &env, height)?;
+            VOTING_POWER.may_load_at_height(deps.storage, owner_addr, height)?.unwrap_or(0)
+        }
+        // 投票权统计完整时直接读取
+        None if since.is_some() => VOTING_POWER.may_load(deps.storage, owner_addr)?.unwrap_or(0),
+        // 索引升级期间统计不完整，遍历用户持有的 NFT 累计兑换价值
+        None => {
+            let mut power = 0u64;
+            for token_id in TOKENS_BY_OWNER.may_load(deps.storage, owner_addr)?.unwrap_or_default() {
+                if let Some(meta) = TOKEN_META.may_load(deps.storage, token_id)? {
+                    power = power.saturating_add(meta.kind.exchange_value() as u64);
+                }
+            }
+            power
+        }
+    };
+
+    to_json_binary(&VotingPowerResponse { owner, power, height: at_snapshot })
+}
+
+/// 查询受托人获得的委托投票权
+/// 
+/// 快照高度的含义和限制与投票权查询相同；索引升级期间当前值可能不完整
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `env`: 环境信息，用于检查快照高度
+/// - `delegate`: 受托人地址
+/// - `at_snapshot`: 快照区块高度
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 委托投票权
+pub fn query_delegated_power(deps: Deps, env: Env, delegate: String, at_snapshot: Option<u64>) -> StdResult<Binary> {
+    let delegate_addr = deps.api.addr_validate(&delegate)?;
+    let power = match at_snapshot {
+        Some(height) => {
+            ensure_snapshot_height(deps, &env, height)?;
+            DELEGATED_POWER.may_load_at_height(deps.storage, delegate_addr, height)?.unwrap_or(0)
+        }
+        None => DELEGATED_POWER.may_load(deps.storage, delegate_addr)?.unwrap_or(0),
+    };
+    to_json_binary(&DelegatedPowerResponse { delegate, power, height: at_snapshot })
+}
+
+/// 查询委托人的受托人
+/// 
+/// # 参数
+/// - `deps`: 依赖对象，包含存储和API访问
+/// - `delegator`: 委托人地址
+/// 
+/// # 返回值
+/// - `StdResult<Binary>`: 受托人（未委托时为 None）
+pub fn query_delegation(deps: Deps, delegator: String) -> StdResult<Binary> {
+    let delegator_addr = deps.api.addr_validate(&delegator)?;
+    let delegate = DELEGATIONS.may_load(deps.storage, delegator_addr)?.map(|addr| addr.to_string());
+    to_json_binary(&DelegationResponse { delegator, delegate })
+}
+
+// ========== 内部辅助函数 ==========
+
+/// 检查快照高度：必须晚于历史记录起始高度且不晚于当前高度
+fn ensure_snapshot_height(deps: Deps, env: &Env, height: u64) -> StdResult<()> {
+    let since = VOTING_POWER_SINCE.may_load(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("voting power history is not available yet"))?;
+    if height <= since {
+        return Err(StdError::generic_err(alloc::format!("snapshot height must be after {}", since)));
+    }
+    if height > env.block.height {
+        return Err(StdError::generic_err("snapshot height is in the future"));
+    }
+    Ok(())
+}
@@ -7,12 +7,15 @@
 //! - pause/unpause 行为测试（在 pause 下禁止操作）
 //! - 数值溢出测试
 //! - 输入验证测试
+//! - 基础 URI 更新与清除测试
+//! - 禁止接收地址测试
+//! - 合成输入重复 token_id 测试
 
 use cosmwasm_std::{Addr, Empty, Uint128};
 use cw_multi_test::{App, ContractWrapper, Executor};
 use luckee_nft::state::Expiration;
 
-use luckee_nft::contract::{execute, instantiate, query, migrate};
+use luckee_nft::contract::{execute, instantiate, query, migrate, reply};
 use luckee_nft::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
 use luckee_nft::types::{NftKind, NftMeta, Scale, Recipe, RecipeInput};
 
@@ -22,7 +25,8 @@ fn mock_app() -> App {
 
 fn contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
     let contract = ContractWrapper::new(execute, instantiate, query)
-        .with_migrate(migrate);
+        .with_migrate(migrate)
+        .with_reply(reply);
     Box::new(contract)
 }
 
@@ -79,7 +83,9 @@ fn test_synthesis_input_limit() {
                 series_id: "test".to_string(),
                 collection_group_id: None,
                 serial_in_series: i as u32,
+                expires_at: None,
             },
+            idempotency_key: None,
         };
 
         app.execute_contract(
@@ -146,11 +152,12 @@ fn test_batch_mint_limit() {
                 series_id: "batch_test".to_string(),
                 collection_group_id: None,
                 serial_in_series: i as u32,
+                expires_at: None,
             },
         });
     }
 
-    let batch_mint_msg = ExecuteMsg::BatchMint { mints };
+    let batch_mint_msg = ExecuteMsg::BatchMint { mints, idempotency_key: None };
 
     let result = app.execute_contract(
         Addr::unchecked("minter"),
@@ -197,6 +204,7 @@ fn test_duplicate_token_id_in_batch() {
                 series_id: "duplicate_test".to_string(),
                 collection_group_id: None,
                 serial_in_series: 1,
+                expires_at: None,
             },
         },
         luckee_nft::msg::BatchMintItem {
@@ -210,11 +218,12 @@ fn test_duplicate_token_id_in_batch() {
                 series_id: "duplicate_test".to_string(),
                 collection_group_id: None,
                 serial_in_series: 2,
+                expires_at: None,
             },
         },
     ];
 
-    let batch_mint_msg = ExecuteMsg::BatchMint { mints };
+    let batch_mint_msg = ExecuteMsg::BatchMint { mints, idempotency_key: None };
 
     let result = app.execute_contract(
         Addr::unchecked("minter"),
@@ -223,9 +232,11 @@ fn test_duplicate_token_id_in_batch() {
         &[],
     );
 
-    // 应该失败，因为存在重复的 token_id
+    // 应该失败，因为存在重复的 token_id，错误指出第二个条目
     assert!(result.is_err());
-    assert!(result.unwrap_err().to_string().contains("Token already exists"));
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("Token already exists"));
+    assert!(err.contains("Batch item 1 (token 1)"));
 }
 
 #[test]
@@ -260,7 +271,9 @@ fn test_unauthorized_minter() {
             series_id: "unauthorized_test".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let result = app.execute_contract(
@@ -316,7 +329,9 @@ fn test_pause_unpause_behavior() {
             series_id: "pause_test".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let result = app.execute_contract(
@@ -384,7 +399,9 @@ fn test_overflow_protection() {
             series_id: "overflow_test".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     // 这应该成功，因为 u64::MAX 是有效的 token_id
@@ -409,7 +426,9 @@ fn test_overflow_protection() {
             series_id: "overflow_test".to_string(),
             collection_group_id: None,
             serial_in_series: 2,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     // 由于 Rust 的溢出检查，这应该失败
@@ -456,7 +475,9 @@ fn test_invalid_address_format() {
             series_id: "invalid_address_test".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let result = app.execute_contract(
@@ -673,7 +694,9 @@ fn test_series_id_validation() {
             series_id: "".to_string(), // 空系列ID
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let result = app.execute_contract(
@@ -720,7 +743,9 @@ fn test_collection_group_id_validation() {
             series_id: "test_series".to_string(),
             collection_group_id: Some("".to_string()), // 空集合组ID
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let result = app.execute_contract(
@@ -734,3 +759,232 @@ fn test_collection_group_id_validation() {
     assert!(result.is_err());
     assert!(result.unwrap_err().to_string().contains("Invalid collection group ID"));
 }
+
+#[test]
+fn test_update_base_uri_validation_and_clear() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: Some("https://luckee.io/metadata/".to_string()),
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    // 不支持的协议应被拒绝
+    let result = app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::UpdateBaseUri { base_uri: Some("ftp://luckee.io/".to_string()) },
+        &[],
+    );
+    assert!(result.unwrap_err().root_cause().to_string().contains("Invalid base URI"));
+
+    // 过长的 URI 应被拒绝
+    let long_uri = format!("https://{}", "a".repeat(300));
+    let result = app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::UpdateBaseUri { base_uri: Some(long_uri) },
+        &[],
+    );
+    assert!(result.unwrap_err().root_cause().to_string().contains("Invalid base URI"));
+
+    // 合法 URI 更新成功，事件中包含新旧值
+    let res = app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::UpdateBaseUri { base_uri: Some("ipfs://bafybeigdyr".to_string()) },
+        &[],
+    ).unwrap();
+    let wasm_event = res.events.iter().find(|e| e.ty == "wasm").unwrap();
+    assert!(wasm_event.attributes.iter().any(|a| a.key == "old_base_uri" && a.value == "https://luckee.io/metadata/"));
+    assert!(wasm_event.attributes.iter().any(|a| a.key == "new_base_uri" && a.value == "ipfs://bafybeigdyr"));
+
+    // 清除基础 URI
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::UpdateBaseUri { base_uri: None },
+        &[],
+    ).unwrap();
+
+    let info: luckee_nft::msg::LuckeeContractInfoResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr, &QueryMsg::LuckeeContractInfo {})
+        .unwrap();
+    assert_eq!(info.base_uri, None);
+}
+
+#[test]
+fn test_reject_contract_and_blocked_recipients() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: Some("https://luckee.io/metadata/".to_string()),
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    let mint_to = |owner: &str, token_id: u64| ExecuteMsg::Mint {
+        token_id,
+        owner: owner.to_string(),
+        extension: NftMeta {
+            kind: NftKind::Clover,
+            scale_origin: Scale::Tiny,
+            physical_sku: None,
+            crafted_from: None,
+            series_id: "test".to_string(),
+            collection_group_id: None,
+            serial_in_series: token_id,
+            expires_at: None,
+        },
+        idempotency_key: None,
+    };
+
+    // 铸造给合约自身应被拒绝
+    let result = app.execute_contract(
+        Addr::unchecked("minter"),
+        nft_contract_addr.clone(),
+        &mint_to(nft_contract_addr.as_str(), 1),
+        &[],
+    );
+    assert!(result.unwrap_err().root_cause().to_string().contains("Invalid recipient"));
+
+    // 非所有者不能设置禁止接收地址
+    let result = app.execute_contract(
+        Addr::unchecked("user"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetBlockedRecipient { address: "burn_address".to_string(), blocked: true },
+        &[],
+    );
+    assert!(result.is_err());
+
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetBlockedRecipient { address: "burn_address".to_string(), blocked: true },
+        &[],
+    ).unwrap();
+
+    // 铸造给被禁止的地址应被拒绝
+    let result = app.execute_contract(
+        Addr::unchecked("minter"),
+        nft_contract_addr.clone(),
+        &mint_to("burn_address", 1),
+        &[],
+    );
+    assert!(result.unwrap_err().root_cause().to_string().contains("Invalid recipient"));
+
+    // 转移给合约自身或被禁止的地址应被拒绝
+    app.execute_contract(
+        Addr::unchecked("minter"),
+        nft_contract_addr.clone(),
+        &mint_to("user", 1),
+        &[],
+    ).unwrap();
+    for recipient in [nft_contract_addr.to_string(), "burn_address".to_string()] {
+        let result = app.execute_contract(
+            Addr::unchecked("user"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::TransferNft { recipient, token_id: 1 },
+            &[],
+        );
+        assert!(result.unwrap_err().root_cause().to_string().contains("Invalid recipient"));
+    }
+
+    // 解除禁止后可以正常转移
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetBlockedRecipient { address: "burn_address".to_string(), blocked: false },
+        &[],
+    ).unwrap();
+    app.execute_contract(
+        Addr::unchecked("user"),
+        nft_contract_addr,
+        &ExecuteMsg::TransferNft { recipient: "burn_address".to_string(), token_id: 1 },
+        &[],
+    ).unwrap();
+}
+
+
+#[test]
+fn test_duplicate_synthesis_inputs() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约（使用默认配方：2 个四叶草合成 1 个流萤）
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    // 只铸造 1 个四叶草
+    app.execute_contract(
+        Addr::unchecked("minter"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Mint {
+            token_id: 1,
+            owner: "user".to_string(),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "series_1".to_string(),
+                collection_group_id: None,
+                serial_in_series: 1,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        },
+        &[],
+    ).unwrap();
+
+    // 重复提交同一个 NFT 不能凑够配方数量
+    let result = app.execute_contract(
+        Addr::unchecked("user"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Synthesize {
+            inputs: vec![1, 1],
+            target: NftKind::Firefly,
+        },
+        &[],
+    );
+    assert!(result.is_err());
+    assert!(result.unwrap_err().root_cause().to_string().contains("Duplicate input token: 1"));
+
+    // 输入 NFT 仍然存在
+    let owner: cw721::OwnerOfResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr, &QueryMsg::OwnerOf { token_id: 1, include_expired: None })
+        .unwrap();
+    assert_eq!(owner.owner, "user");
+}
@@ -7,14 +7,38 @@
 //! - batch_mint limits
 //! - query: OwnerOf, Approvals, ApprovalsAll, Tokens (分页), AllTokens
 //! - 事件与 attributes 统一性
-
-use cosmwasm_std::{Addr, Empty};
+//! - 限时 NFT 过期与回收
+//! - 存储使用统计计数器
+//! - 按持有数量查询可合成目标
+//! - 有次数限制的操作员批准
+//! - 礼物领取码的领取与过期取回
+//! - 托管的 NFT 过期回收时礼物随之删除，重新铸造的同 ID NFT 不受影响
+//! - 失效互换报价和过期礼物的清扫
+//! - 从合约托管库存空投分发
+//! - NFT 组合包的创建、整体转移与拆包
+//! - 组合包中的 NFT 过期回收后移出组合包，组合包变空时删除
+//! - 条件转移的领取与超时取回
+//! - 工厂模式创建子集合与转发管理消息
+//! - 从共享配方注册表读取并缓存配方
+//! - 在关联集合中为登记的来源集合铸造合成产出
+//! - 盲盒奖池的加权抽取与数量扣减
+//! - 过滤扫描查询在扫描上限处返回部分结果
+//! - 按被批准者查询已批准的 NFT
+
+use cosmwasm_std::{from_json, to_json_binary, Addr, Empty};
 use cw_multi_test::{App, ContractWrapper, Executor};
-use luckee_nft::state::Expiration;
-
-use luckee_nft::contract::{execute, instantiate, query, migrate};
-use luckee_nft::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use luckee_nft::types::{NftKind, NftMeta, Scale};
+use luckee_nft::state::{CraftingLimit, Expiration};
+
+use luckee_nft::contract::{execute, instantiate, query, migrate, reply};
+use luckee_nft::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, StorageStatsResponse, SynthesizableTargetsResponse,
+                      OperatorBudgetResponse, GiftResponse, DistributeItem, CraftingAllowanceResponse,
+                      GrantsByOwnerResponse, SwapOffersResponse, BundleResponse, BundlesResponse,
+                      TokenBundleResponse, ConditionalTransferResponse, CollectionsResponse,
+                      CollectionResponse, RecipeResponse, RecipeRegistryResponse, SynthesizeData,
+                      RecipeOutputCollectionResponse, MintPoolResponse, ApprovedTokensResponse,
+                      SynthesisSourceResponse, TokenMetaResponse};
+use luckee_nft::types::{NftKind, NftMeta, PoolEntry, Recipe, RecipeInput, Role, Scale};
+use sha2::{Digest, Sha256};
 
 fn mock_app() -> App {
     App::default()
@@ -22,7 +46,8 @@ fn mock_app() -> App {
 
 fn contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
     let contract = ContractWrapper::new(execute, instantiate, query)
-        .with_migrate(migrate);
+        .with_migrate(migrate)
+        .with_reply(reply);
     Box::new(contract)
 }
 
@@ -58,7 +83,9 @@ fn test_comprehensive_mint_and_supply_management() {
             series_id: "series_1".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let res = app.execute_contract(
@@ -89,7 +116,9 @@ fn test_comprehensive_mint_and_supply_management() {
             series_id: "series_2".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let res = app.execute_contract(
@@ -120,7 +149,9 @@ fn test_comprehensive_mint_and_supply_management() {
             series_id: "series_1".to_string(),
             collection_group_id: None,
             serial_in_series: 2,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let res = app.execute_contract(
@@ -172,7 +203,9 @@ fn test_comprehensive_transfer_and_approvals_cleanup() {
             series_id: "series_1".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     app.execute_contract(
@@ -187,6 +220,7 @@ fn test_comprehensive_transfer_and_approvals_cleanup() {
         spender: "user2".to_string(),
         token_id: 1,
         expires: Some(Expiration { at_height: None, at_time: None }),
+        max_transfers: None,
     };
 
     let res = app.execute_contract(
@@ -258,7 +292,9 @@ fn test_comprehensive_transfer_and_approvals_cleanup() {
             series_id: "series_1".to_string(),
             collection_group_id: None,
             serial_in_series: 2,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     app.execute_contract(
@@ -340,7 +376,9 @@ fn test_comprehensive_burn_and_cleanup() {
             series_id: "series_1".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     app.execute_contract(
@@ -446,10 +484,11 @@ fn test_comprehensive_batch_mint_limits() {
             series_id: format!("series_{}", i),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
     }).collect();
 
-    let batch_msg = ExecuteMsg::BatchMint { mints: batch_items };
+    let batch_msg = ExecuteMsg::BatchMint { mints: batch_items, idempotency_key: None };
 
     let res = app.execute_contract(
         Addr::unchecked("blind_box_contract"),
@@ -479,10 +518,11 @@ fn test_comprehensive_batch_mint_limits() {
             series_id: format!("series_{}", i),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
     }).collect();
 
-    let large_batch_msg = ExecuteMsg::BatchMint { mints: large_batch };
+    let large_batch_msg = ExecuteMsg::BatchMint { mints: large_batch, idempotency_key: None };
 
     let res = app.execute_contract(
         Addr::unchecked("blind_box_contract"),
@@ -534,7 +574,9 @@ fn test_comprehensive_query_pagination() {
                 series_id: "series_1".to_string(),
                 collection_group_id: None,
                 serial_in_series: i,
+                expires_at: None,
             },
+            idempotency_key: None,
         };
 
         app.execute_contract(
@@ -628,7 +670,9 @@ fn test_comprehensive_events_and_attributes() {
             series_id: "series_1".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let res = app.execute_contract(
@@ -659,6 +703,13 @@ fn test_comprehensive_events_and_attributes() {
         .unwrap();
     assert_eq!(owner_attr.value, "user1");
 
+    // 铸造事件附带铸造后的总供应量
+    let supply_attr = res.events.iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "total_supply")
+        .unwrap();
+    assert_eq!(supply_attr.value, "1");
+
     // 测试转移事件
     let transfer_msg = ExecuteMsg::TransferNft {
         recipient: "user2".to_string(),
@@ -716,6 +767,13 @@ fn test_comprehensive_events_and_attributes() {
         .find(|attr| attr.key == "owner")
         .unwrap();
     assert_eq!(owner_attr.value, "user2");
+
+    // 销毁事件附带销毁后的总供应量
+    let supply_attr = res.events.iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "total_supply")
+        .unwrap();
+    assert_eq!(supply_attr.value, "0");
 }
 
 #[test]
@@ -750,7 +808,9 @@ fn test_comprehensive_checked_arithmetic() {
             series_id: "series_1".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     app.execute_contract(
@@ -839,6 +899,7 @@ fn test_batch_mint_comprehensive() {
                 series_id: "test_series".to_string(),
                 collection_group_id: None,
                 serial_in_series: 1,
+                expires_at: None,
             },
         },
         luckee_nft::msg::BatchMintItem {
@@ -852,11 +913,12 @@ fn test_batch_mint_comprehensive() {
                 series_id: "test_series".to_string(),
                 collection_group_id: None,
                 serial_in_series: 2,
+                expires_at: None,
             },
         },
     ];
 
-    let batch_mint_msg = ExecuteMsg::BatchMint { mints: batch_mints };
+    let batch_mint_msg = ExecuteMsg::BatchMint { mints: batch_mints, idempotency_key: None };
     let res = app.execute_contract(
         Addr::unchecked("minter"),
         nft_contract_addr.clone(),
@@ -888,11 +950,12 @@ fn test_batch_mint_comprehensive() {
                 series_id: "test_series2".to_string(),
                 collection_group_id: None,
                 serial_in_series: 1,
+                expires_at: None,
             },
         },
     ];
 
-    let duplicate_msg = ExecuteMsg::BatchMint { mints: duplicate_batch };
+    let duplicate_msg = ExecuteMsg::BatchMint { mints: duplicate_batch, idempotency_key: None };
     let res = app.execute_contract(
         Addr::unchecked("minter"),
         nft_contract_addr.clone(),
@@ -914,11 +977,12 @@ fn test_batch_mint_comprehensive() {
                 series_id: format!("series{}", i),
                 collection_group_id: None,
                 serial_in_series: 1,
+                expires_at: None,
             },
         })
         .collect();
 
-    let large_msg = ExecuteMsg::BatchMint { mints: large_batch };
+    let large_msg = ExecuteMsg::BatchMint { mints: large_batch, idempotency_key: None };
     let res = app.execute_contract(
         Addr::unchecked("minter"),
         nft_contract_addr.clone(),
@@ -940,11 +1004,12 @@ fn test_batch_mint_comprehensive() {
                 series_id: "".to_string(), // 空系列ID
                 collection_group_id: None,
                 serial_in_series: 1,
+                expires_at: None,
             },
         },
     ];
 
-    let invalid_msg = ExecuteMsg::BatchMint { mints: invalid_series_batch };
+    let invalid_msg = ExecuteMsg::BatchMint { mints: invalid_series_batch, idempotency_key: None };
     let res = app.execute_contract(
         Addr::unchecked("minter"),
         nft_contract_addr.clone(),
@@ -1009,7 +1074,9 @@ fn test_synthesize_comprehensive() {
             series_id: "input_series".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
     let res = app.execute_contract(
         Addr::unchecked("minter"),
@@ -1030,7 +1097,9 @@ fn test_synthesize_comprehensive() {
             series_id: "input_series".to_string(),
             collection_group_id: None,
             serial_in_series: 2,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
     let res = app.execute_contract(
         Addr::unchecked("minter"),
@@ -1084,7 +1153,9 @@ fn test_synthesize_comprehensive() {
             series_id: "input_series2".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
     let res = app.execute_contract(
         Addr::unchecked("minter"),
@@ -1152,7 +1223,9 @@ fn test_paused_state_operations() {
             series_id: "test_series".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
     let res = app.execute_contract(
         Addr::unchecked("minter"),
@@ -1184,7 +1257,9 @@ fn test_paused_state_operations() {
             series_id: "test_series2".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
     let res = app.execute_contract(
         Addr::unchecked("minter"),
@@ -1237,7 +1312,9 @@ fn test_paused_state_operations() {
             series_id: "test_series3".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
     let res = app.execute_contract(
         Addr::unchecked("minter"),
@@ -1247,3 +1324,2280 @@ fn test_paused_state_operations() {
     );
     assert!(res.is_ok());
 }
+
+#[test]
+fn test_batch_mint_supply_consistency() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: Some("https://luckee.io/metadata/".to_string()),
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    // 批量铸造 5 个 NFT（ID 不连续，验证计数器取最大值）
+    let mints: Vec<luckee_nft::msg::BatchMintItem> = [3u64, 1, 10, 4, 7]
+        .iter()
+        .map(|&token_id| luckee_nft::msg::BatchMintItem {
+            token_id,
+            owner: "user1".to_string(),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "series_1".to_string(),
+                collection_group_id: None,
+                serial_in_series: token_id,
+                expires_at: None,
+            },
+        })
+        .collect();
+
+    app.execute_contract(
+        Addr::unchecked("minter"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::BatchMint { mints, idempotency_key: None },
+        &[],
+    ).unwrap();
+
+    // 总供应量必须等于实际铸造数量（不得重复计数）
+    let info: luckee_nft::msg::LuckeeContractInfoResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::LuckeeContractInfo {})
+        .unwrap();
+    assert_eq!(info.total_supply, 5);
+
+    // 再铸造一个 NFT，供应量应继续与实际数量一致
+    app.execute_contract(
+        Addr::unchecked("minter"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Mint {
+            token_id: 11,
+            owner: "user1".to_string(),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "series_1".to_string(),
+                collection_group_id: None,
+                serial_in_series: 6,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        },
+        &[],
+    ).unwrap();
+
+    let info: luckee_nft::msg::LuckeeContractInfoResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::LuckeeContractInfo {})
+        .unwrap();
+    assert_eq!(info.total_supply, 6);
+
+    // 所有 NFT 都可被枚举
+    let tokens: cw721::TokensResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr, &QueryMsg::AllTokens { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(tokens.tokens.len() as u64, info.total_supply);
+}
+
+#[test]
+fn test_burn_grace_period_restore_and_finalize() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: Some("https://luckee.io/metadata/".to_string()),
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    // 开启 10 个区块的销毁宽限期
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetBurnGracePeriod { blocks: 10 },
+        &[],
+    ).unwrap();
+
+    // 铸造两个 NFT
+    for token_id in 1..=2u64 {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "user1".to_string(),
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+
+    // 销毁 NFT 1，进入待销毁状态
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Burn { token_id: 1 },
+        &[],
+    ).unwrap();
+
+    let owner_query = QueryMsg::OwnerOf { token_id: 1, include_expired: None };
+    let result: Result<cw721::OwnerOfResponse, _> = app.wrap().query_wasm_smart(nft_contract_addr.clone(), &owner_query);
+    assert!(result.is_err());
+
+    // 宽限期内不能完成销毁，也不能重新铸造相同 ID
+    let result = app.execute_contract(
+        Addr::unchecked("anyone"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::FinalizeBurn { token_id: 1 },
+        &[],
+    );
+    assert!(result.unwrap_err().root_cause().to_string().contains("Burn grace period has not ended"));
+
+    // 其他用户不能恢复
+    let result = app.execute_contract(
+        Addr::unchecked("user2"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::RestoreBurned { token_id: 1 },
+        &[],
+    );
+    assert!(result.unwrap_err().root_cause().to_string().contains("Unauthorized"));
+
+    // 原所有者恢复成功，发出带总供应量的恢复事件并记录到活动流
+    let res = app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::RestoreBurned { token_id: 1 },
+        &[],
+    ).unwrap();
+    let owner: cw721::OwnerOfResponse = app.wrap().query_wasm_smart(nft_contract_addr.clone(), &owner_query).unwrap();
+    assert_eq!(owner.owner, "user1");
+    let restore_event = res.events.iter()
+        .find(|event| event.attributes.iter().any(|attr| attr.key == "action" && attr.value == "restore"))
+        .unwrap();
+    assert!(restore_event.attributes.iter().any(|attr| attr.key == "total_supply" && attr.value == "2"));
+    let activity: luckee_nft::msg::RecentActivityResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::RecentActivity { limit: Some(1) })
+        .unwrap();
+    assert_eq!(activity.activities[0].action, "restore");
+    assert_eq!(activity.activities[0].token_id, Some(1));
+
+    // 销毁 NFT 2，宽限期结束后任何人都可以完成销毁
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Burn { token_id: 2 },
+        &[],
+    ).unwrap();
+    app.update_block(|block| block.height += 10);
+
+    let result = app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::RestoreBurned { token_id: 2 },
+        &[],
+    );
+    assert!(result.unwrap_err().root_cause().to_string().contains("Burn grace period has expired"));
+
+    app.execute_contract(
+        Addr::unchecked("anyone"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::FinalizeBurn { token_id: 2 },
+        &[],
+    ).unwrap();
+
+    let info: luckee_nft::msg::LuckeeContractInfoResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr, &QueryMsg::LuckeeContractInfo {})
+        .unwrap();
+    assert_eq!(info.total_supply, 1);
+}
+
+#[test]
+fn test_token_expiration_and_reaping() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: Some("https://luckee.io/metadata/".to_string()),
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    // 铸造一个 1 小时后过期的兑换券和一个永久 NFT
+    let now = app.block_info().time.seconds();
+    for (token_id, expires_at) in [(1u64, Some(now + 3600)), (2u64, None)] {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "user1".to_string(),
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "voucher".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+
+    // 未过期时不能回收
+    let result = app.execute_contract(
+        Addr::unchecked("anyone"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Expire { token_id: 1 },
+        &[],
+    );
+    assert!(result.unwrap_err().root_cause().to_string().contains("Token has not expired"));
+
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+
+    // 过期后不可转移
+    let result = app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::TransferNft { recipient: "user2".to_string(), token_id: 1 },
+        &[],
+    );
+    assert!(result.unwrap_err().root_cause().to_string().contains("Token has expired"));
+
+    // 按过期状态查询
+    let expired: luckee_nft::msg::TokensByExpiryResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::TokensByExpiry { expired: true, start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(expired.tokens, vec![1]);
+    let active: luckee_nft::msg::TokensByExpiryResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::TokensByExpiry { expired: false, start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(active.tokens, vec![2]);
+
+    // 任何人都可以回收已过期的 NFT
+    app.execute_contract(
+        Addr::unchecked("anyone"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Expire { token_id: 1 },
+        &[],
+    ).unwrap();
+
+    let info: luckee_nft::msg::LuckeeContractInfoResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr, &QueryMsg::LuckeeContractInfo {})
+        .unwrap();
+    assert_eq!(info.total_supply, 1);
+}
+
+#[test]
+fn test_burn_all_of_kind() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: Some("https://luckee.io/metadata/".to_string()),
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    // 铸造 5 个四叶草和 1 个流萤
+    for token_id in 1..=6u64 {
+        let kind = if token_id == 6 { NftKind::Firefly } else { NftKind::Clover };
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "user1".to_string(),
+                extension: NftMeta {
+                    kind,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+
+    // 最多销毁 3 个四叶草
+    let res = app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::BurnAllOfKind { kind: NftKind::Clover, max: 3 },
+        &[],
+    ).unwrap();
+    let wasm_event = res.events.iter().find(|e| e.ty == "wasm").unwrap();
+    assert!(wasm_event.attributes.iter().any(|a| a.key == "count" && a.value == "3"));
+
+    // 销毁剩余的四叶草，流萤不受影响
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::BurnAllOfKind { kind: NftKind::Clover, max: 100 },
+        &[],
+    ).unwrap();
+
+    let tokens: cw721::TokensResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr, &QueryMsg::Tokens { owner: "user1".to_string(), start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["6".to_string()]);
+}
+
+
+#[test]
+fn test_storage_stats_counters() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    // 部署后只有默认配方
+    let stats: StorageStatsResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::StorageStats {})
+        .unwrap();
+    assert_eq!(stats.recipes, 8);
+    assert_eq!(stats.tokens, 0);
+    assert_eq!(stats.owner_index, 0);
+
+    // 为 user1 铸造 2 个四叶草
+    for token_id in 1..=2u64 {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "user1".to_string(),
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+
+    // 批准、重复批准和操作员批准
+    for _ in 0..2 {
+        app.execute_contract(
+            Addr::unchecked("user1"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Approve { spender: "spender".to_string(), token_id: 1, expires: None },
+            &[],
+        ).unwrap();
+    }
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ApproveAll { operator: "operator".to_string(), expires: None, max_transfers: None },
+        &[],
+    ).unwrap();
+
+    let stats: StorageStatsResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::StorageStats {})
+        .unwrap();
+    assert_eq!(stats.tokens, 2);
+    assert_eq!(stats.owner_index, 1);
+    assert_eq!(stats.token_approvals, 1);
+    assert_eq!(stats.operator_approvals, 1);
+
+    // 转移会清理批准并新增一个持有者
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::TransferNft { recipient: "user2".to_string(), token_id: 1 },
+        &[],
+    ).unwrap();
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::RevokeAll { operator: "operator".to_string() },
+        &[],
+    ).unwrap();
+
+    let stats: StorageStatsResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr, &QueryMsg::StorageStats {})
+        .unwrap();
+    assert_eq!(stats.owner_index, 2);
+    assert_eq!(stats.token_approvals, 0);
+    assert_eq!(stats.operator_approvals, 0);
+}
+
+#[test]
+fn test_synthesizable_targets_follow_owner_kind_counts() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约（默认配方：2 个四叶草合成 1 个流萤）
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    // 为 user1 铸造 5 个四叶草
+    for token_id in 1..=5u64 {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "user1".to_string(),
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+
+    let targets: SynthesizableTargetsResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::SynthesizableTargets { owner: "user1".to_string() })
+        .unwrap();
+    assert_eq!(targets.targets.len(), 1);
+    assert_eq!(targets.targets[0].target, NftKind::Firefly);
+    assert_eq!(targets.targets[0].max_times, 2);
+
+    // 转出 4 个后 user1 只剩 1 个四叶草，不足以合成
+    for token_id in 1..=4u64 {
+        app.execute_contract(
+            Addr::unchecked("user1"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::TransferNft { recipient: "user2".to_string(), token_id },
+            &[],
+        ).unwrap();
+    }
+    let targets: SynthesizableTargetsResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::SynthesizableTargets { owner: "user1".to_string() })
+        .unwrap();
+    assert!(targets.targets.is_empty());
+
+    // user2 合成一次后剩 2 个四叶草和 1 个流萤
+    app.execute_contract(
+        Addr::unchecked("user2"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Synthesize { inputs: vec![1, 2], target: NftKind::Firefly },
+        &[],
+    ).unwrap();
+    let targets: SynthesizableTargetsResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr, &QueryMsg::SynthesizableTargets { owner: "user2".to_string() })
+        .unwrap();
+    assert_eq!(targets.targets.len(), 1);
+    assert_eq!(targets.targets[0].max_times, 1);
+}
+
+#[test]
+fn test_operator_transfer_budget_auto_revokes() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    for token_id in 1..=3u64 {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "user1".to_string(),
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+
+    // 批准市场机器人最多转移 2 次
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ApproveAll { operator: "bot".to_string(), expires: None, max_transfers: Some(2) },
+        &[],
+    ).unwrap();
+
+    for token_id in 1..=2u64 {
+        app.execute_contract(
+            Addr::unchecked("bot"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::TransferNft { recipient: "buyer".to_string(), token_id },
+            &[],
+        ).unwrap();
+    }
+
+    // 次数用尽后批准被自动撤销
+    let result = app.execute_contract(
+        Addr::unchecked("bot"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::TransferNft { recipient: "buyer".to_string(), token_id: 3 },
+        &[],
+    );
+    assert!(result.is_err());
+
+    let budget: OperatorBudgetResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr, &QueryMsg::OperatorBudget {
+            owner: "user1".to_string(),
+            operator: "bot".to_string(),
+        })
+        .unwrap();
+    assert_eq!(budget.remaining, None);
+}
+
+#[test]
+fn test_gift_claim_and_cancel_after_expiry() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    for token_id in 1..=2u64 {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "user1".to_string(),
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+
+    let hash_of = |code: &str| -> String {
+        Sha256::digest(code.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+    };
+    let expires = app.block_info().time.seconds() + 3600;
+
+    // 创建两个礼物，NFT 托管到合约
+    for (token_id, code) in [(1u64, "lucky-code-1"), (2u64, "lucky-code-2")] {
+        app.execute_contract(
+            Addr::unchecked("user1"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::CreateGift { token_id, claim_hash: hash_of(code), expires },
+            &[],
+        ).unwrap();
+    }
+    let res: cw721::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::OwnerOf { token_id: 1, include_expired: None })
+        .unwrap();
+    assert_eq!(res.owner, nft_contract_addr.to_string());
+
+    // 错误的领取码被拒绝
+    let result = app.execute_contract(
+        Addr::unchecked("friend"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ClaimGift { preimage: "wrong-code".to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // 正确的领取码可领取
+    app.execute_contract(
+        Addr::unchecked("friend"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ClaimGift { preimage: "lucky-code-1".to_string() },
+        &[],
+    ).unwrap();
+    let res: cw721::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::OwnerOf { token_id: 1, include_expired: None })
+        .unwrap();
+    assert_eq!(res.owner, "friend");
+
+    // 过期前赠送者不能取消
+    let result = app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::CancelGift { claim_hash: hash_of("lucky-code-2") },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // 过期后不可领取，但赠送者可以取回
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+    let result = app.execute_contract(
+        Addr::unchecked("friend"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ClaimGift { preimage: "lucky-code-2".to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::CancelGift { claim_hash: hash_of("lucky-code-2") },
+        &[],
+    ).unwrap();
+    let res: cw721::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::OwnerOf { token_id: 2, include_expired: None })
+        .unwrap();
+    assert_eq!(res.owner, "user1");
+
+    let gift: GiftResponse = app
+        .wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::Gift { claim_hash: hash_of("lucky-code-2") })
+        .unwrap();
+    assert!(gift.gift.is_none());
+}
+
+#[test]
+fn test_expire_removes_gift_of_escrowed_token() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    let now = app.block_info().time.seconds();
+    let mint = |owner: &str, expires_at: Option<u64>| ExecuteMsg::Mint {
+        token_id: 1,
+        owner: owner.to_string(),
+        extension: NftMeta {
+            kind: NftKind::Clover,
+            scale_origin: Scale::Tiny,
+            physical_sku: None,
+            crafted_from: None,
+            series_id: "series_1".to_string(),
+            collection_group_id: None,
+            serial_in_series: 1,
+            expires_at,
+        },
+        idempotency_key: None,
+    };
+    app.execute_contract(Addr::unchecked("minter"), nft_contract_addr.clone(), &mint("user1", Some(now + 100)), &[]).unwrap();
+
+    // 限时 NFT 作为礼物托管期间过期并被回收，同一 ID 重新铸造给他人
+    let claim_hash: String = Sha256::digest(b"lucky-code").iter().map(|b| format!("{:02x}", b)).collect();
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::CreateGift { token_id: 1, claim_hash: claim_hash.clone(), expires: now + 3600 },
+        &[],
+    ).unwrap();
+    app.update_block(|block| block.time = block.time.plus_seconds(200));
+    app.execute_contract(Addr::unchecked("anyone"), nft_contract_addr.clone(), &ExecuteMsg::Expire { token_id: 1 }, &[]).unwrap();
+    app.execute_contract(Addr::unchecked("minter"), nft_contract_addr.clone(), &mint("user2", None), &[]).unwrap();
+
+    // 回收时礼物已删除，取消礼物失败，新所有者的 NFT 不在托管中
+    let gift: GiftResponse = app
+        .wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::Gift { claim_hash: claim_hash.clone() })
+        .unwrap();
+    assert!(gift.gift.is_none());
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+    assert!(app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::CancelGift { claim_hash },
+        &[],
+    ).is_err());
+
+    // 新所有者可以正常转移重新铸造的 NFT
+    app.execute_contract(
+        Addr::unchecked("user2"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::TransferNft { recipient: "user3".to_string(), token_id: 1 },
+        &[],
+    ).unwrap();
+    let res: cw721::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(nft_contract_addr, &QueryMsg::OwnerOf { token_id: 1, include_expired: None })
+        .unwrap();
+    assert_eq!(res.owner, "user3");
+}
+
+#[test]
+fn test_sweep_expired_offers_and_gifts() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    for token_id in 1..=3u64 {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "user1".to_string(),
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+
+    // 两个互换报价，其中 NFT 2 随后被转出，报价失效
+    for token_id in 1..=2u64 {
+        app.execute_contract(
+            Addr::unchecked("user1"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::CreateSwapOffer { offered_token_id: token_id, wanted_kind: NftKind::Firefly },
+            &[],
+        ).unwrap();
+    }
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::TransferNft { recipient: "user2".to_string(), token_id: 2 },
+        &[],
+    ).unwrap();
+
+    // NFT 3 作为礼物托管，一小时后过期
+    let claim_hash: String = Sha256::digest(b"sweep-code").iter().map(|b| format!("{:02x}", b)).collect();
+    let expires = app.block_info().time.seconds() + 3600;
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::CreateGift { token_id: 3, claim_hash: claim_hash.clone(), expires },
+        &[],
+    ).unwrap();
+
+    // 第一次清扫关闭失效报价，有效报价保留
+    let res = app.execute_contract(
+        Addr::unchecked("anyone"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SweepExpired { limit: Some(10) },
+        &[],
+    ).unwrap();
+    let wasm = res.events.iter().find(|e| e.ty == "wasm").unwrap();
+    assert!(wasm.attributes.iter().any(|a| a.key == "swept" && a.value == "1"));
+    assert!(wasm.attributes.iter().any(|a| a.key == "next_phase" && a.value == "gifts"));
+    let offers: SwapOffersResponse = app
+        .wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::SwapOffers {
+            wanted_kind: NftKind::Firefly,
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(offers.offers.len(), 1);
+    assert_eq!(offers.offers[0].offered_token_id, 1);
+
+    // 礼物未过期时不被清扫
+    app.execute_contract(
+        Addr::unchecked("anyone"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SweepExpired { limit: Some(10) },
+        &[],
+    ).unwrap();
+    let gift: GiftResponse = app
+        .wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::Gift { claim_hash: claim_hash.clone() })
+        .unwrap();
+    assert!(gift.gift.is_some());
+
+    // 过期后再次进入礼物阶段，NFT 归还赠送者
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+    for _ in 0..2 {
+        app.execute_contract(
+            Addr::unchecked("anyone"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::SweepExpired { limit: Some(10) },
+            &[],
+        ).unwrap();
+    }
+    let gift: GiftResponse = app
+        .wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::Gift { claim_hash })
+        .unwrap();
+    assert!(gift.gift.is_none());
+    let res: cw721::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::OwnerOf { token_id: 3, include_expired: None })
+        .unwrap();
+    assert_eq!(res.owner, "user1");
+}
+
+#[test]
+fn test_distribute_from_contract_inventory() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    // 预先铸造到合约地址作为库存，另铸造两个给普通用户
+    for (token_id, owner) in [
+        (1u64, nft_contract_addr.to_string()),
+        (2, nft_contract_addr.to_string()),
+        (3, "user1".to_string()),
+        (4, "user1".to_string()),
+    ] {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner,
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+
+    let items = vec![
+        DistributeItem { recipient: "winner1".to_string(), token_id: 1 },
+        DistributeItem { recipient: "winner2".to_string(), token_id: 2 },
+    ];
+
+    // 未授予空投角色时不能分发
+    let result = app.execute_contract(
+        Addr::unchecked("airdropper"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Distribute { items: items.clone() },
+        &[],
+    );
+    assert!(result.unwrap_err().root_cause().to_string().contains("Missing role"));
+
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetRole { role: Role::Airdrop, address: "airdropper".to_string(), granted: true },
+        &[],
+    ).unwrap();
+
+    // 不能分发用户持有的 NFT
+    let result = app.execute_contract(
+        Addr::unchecked("airdropper"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Distribute { items: vec![DistributeItem { recipient: "winner1".to_string(), token_id: 3 }] },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // 打包托管到合约的 NFT 不属于库存
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::CreateBundle { token_ids: vec![3, 4] },
+        &[],
+    ).unwrap();
+    let result = app.execute_contract(
+        Addr::unchecked("airdropper"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Distribute { items: vec![DistributeItem { recipient: "winner1".to_string(), token_id: 3 }] },
+        &[],
+    );
+    assert!(result.unwrap_err().root_cause().to_string().contains("escrow"));
+
+    // 批量分发，每个 NFT 一个分发事件
+    let res = app.execute_contract(
+        Addr::unchecked("airdropper"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Distribute { items },
+        &[],
+    ).unwrap();
+    let distribute_events = res.events.iter()
+        .filter(|event| event.attributes.iter().any(|attr| attr.key == "action" && attr.value == "distribute"))
+        .filter(|event| event.attributes.iter().any(|attr| attr.key == "recipient"))
+        .count();
+    assert_eq!(distribute_events, 2);
+
+    for (token_id, winner) in [(1u64, "winner1"), (2, "winner2")] {
+        let res: cw721::OwnerOfResponse = app
+            .wrap()
+            .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::OwnerOf { token_id, include_expired: None })
+            .unwrap();
+        assert_eq!(res.owner, winner);
+    }
+}
+
+#[test]
+fn test_crafting_limit_caps_synthesized_value() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    for token_id in 1..=6u64 {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "user1".to_string(),
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+
+    // 非所有者不能设置上限，窗口为 0 的配置被拒绝
+    let limit = CraftingLimit { max_value: 2, window_seconds: 3600 };
+    let result = app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetCraftingLimit { limit: Some(limit.clone()) },
+        &[],
+    );
+    assert!(result.is_err());
+    let result = app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetCraftingLimit { limit: Some(CraftingLimit { max_value: 2, window_seconds: 0 }) },
+        &[],
+    );
+    assert!(result.is_err());
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetCraftingLimit { limit: Some(limit.clone()) },
+        &[],
+    ).unwrap();
+
+    // 第一次合成流萤（价值 2）用完额度
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Synthesize { inputs: vec![1, 2], target: NftKind::Firefly },
+        &[],
+    ).unwrap();
+    let allowance: CraftingAllowanceResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::CraftingAllowance { owner: "user1".to_string() })
+        .unwrap();
+    assert_eq!(allowance.limit, Some(limit));
+    assert_eq!(allowance.used, 2);
+    assert_eq!(allowance.remaining, Some(0));
+
+    // 同一窗口内再次合成被拒绝，输入保持不变
+    let err = app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Synthesize { inputs: vec![3, 4], target: NftKind::Firefly },
+        &[],
+    ).unwrap_err();
+    assert!(err.root_cause().to_string().contains("Crafting limit exceeded"));
+    let res: cw721::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::OwnerOf { token_id: 3, include_expired: None })
+        .unwrap();
+    assert_eq!(res.owner, "user1");
+
+    // 窗口结束后额度恢复
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Synthesize { inputs: vec![3, 4], target: NftKind::Firefly },
+        &[],
+    ).unwrap();
+
+    // 取消上限后不再限制
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetCraftingLimit { limit: None },
+        &[],
+    ).unwrap();
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Synthesize { inputs: vec![5, 6], target: NftKind::Firefly },
+        &[],
+    ).unwrap();
+    let allowance: CraftingAllowanceResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr, &QueryMsg::CraftingAllowance { owner: "user1".to_string() })
+        .unwrap();
+    assert_eq!(allowance.limit, None);
+    assert_eq!(allowance.remaining, None);
+}
+
+#[test]
+fn test_grants_by_owner() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    for token_id in 1..=2u64 {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "user1".to_string(),
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+
+    // 批准 1 号给 market，2 号批准后撤销，并设置限次操作员
+    let user1 = Addr::unchecked("user1");
+    app.execute_contract(user1.clone(), nft_contract_addr.clone(),
+        &ExecuteMsg::Approve { spender: "market".to_string(), token_id: 1, expires: None }, &[]).unwrap();
+    app.execute_contract(user1.clone(), nft_contract_addr.clone(),
+        &ExecuteMsg::Approve { spender: "friend".to_string(), token_id: 2, expires: None }, &[]).unwrap();
+    app.execute_contract(user1.clone(), nft_contract_addr.clone(),
+        &ExecuteMsg::Revoke { spender: "friend".to_string(), token_id: 2 }, &[]).unwrap();
+    app.execute_contract(user1.clone(), nft_contract_addr.clone(),
+        &ExecuteMsg::ApproveAll { operator: "bot".to_string(), expires: None, max_transfers: Some(3) }, &[]).unwrap();
+
+    let query_msg = QueryMsg::GrantsByOwner { owner: "user1".to_string(), start_after: None, limit: None };
+    let grants: GrantsByOwnerResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &query_msg)
+        .unwrap();
+    assert_eq!(grants.token_approvals.len(), 1);
+    assert_eq!(grants.token_approvals[0].token_id, 1);
+    assert_eq!(grants.token_approvals[0].spender, "market");
+    assert_eq!(grants.operators.len(), 1);
+    assert_eq!(grants.operators[0].operator, "bot");
+    assert_eq!(grants.operators[0].remaining, Some(3));
+    assert_eq!(grants.next_cursor, None);
+    assert!(!grants.has_more);
+
+    // 转移后原所有者的批准被清除
+    app.execute_contract(user1, nft_contract_addr.clone(),
+        &ExecuteMsg::TransferNft { recipient: "user2".to_string(), token_id: 1 }, &[]).unwrap();
+    let grants: GrantsByOwnerResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr, &query_msg)
+        .unwrap();
+    assert!(grants.token_approvals.is_empty());
+}
+
+#[test]
+fn test_bundle_create_transfer_and_unbundle() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    for token_id in 1..=3u64 {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "user1".to_string(),
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+
+    // 少于两个、重复或不属于发送者的 NFT 不能打包
+    for token_ids in [vec![1u64], vec![1, 1], vec![1, 4]] {
+        assert!(app.execute_contract(
+            Addr::unchecked("user1"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::CreateBundle { token_ids },
+            &[],
+        ).is_err());
+    }
+
+    // 打包后 NFT 托管到合约
+    let res = app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::CreateBundle { token_ids: vec![1, 2] },
+        &[],
+    ).unwrap();
+    assert!(res.events.iter().any(|event| event.attributes.iter()
+        .any(|attr| attr.key == "bundle_id" && attr.value == "1")));
+    let res: cw721::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::OwnerOf { token_id: 1, include_expired: None })
+        .unwrap();
+    assert_eq!(res.owner, nft_contract_addr.to_string());
+    let res: TokenBundleResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::TokenBundle { token_id: 2 })
+        .unwrap();
+    assert_eq!(res.bundle_id, Some(1));
+
+    // 只有持有者可以转移组合包，转移后由接收者持有
+    assert!(app.execute_contract(
+        Addr::unchecked("user2"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::TransferBundle { bundle_id: 1, recipient: "user2".to_string() },
+        &[],
+    ).is_err());
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::TransferBundle { bundle_id: 1, recipient: "user2".to_string() },
+        &[],
+    ).unwrap();
+    let res: BundlesResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::Bundles {
+            owner: "user2".to_string(),
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(res.bundles.len(), 1);
+    assert_eq!(res.bundles[0].token_ids, vec![1, 2]);
+    let res: BundlesResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::Bundles {
+            owner: "user1".to_string(),
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert!(res.bundles.is_empty());
+
+    // 拆包后 NFT 归还当前持有者，组合包删除
+    assert!(app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Unbundle { bundle_id: 1 },
+        &[],
+    ).is_err());
+    app.execute_contract(
+        Addr::unchecked("user2"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Unbundle { bundle_id: 1 },
+        &[],
+    ).unwrap();
+    for token_id in [1u64, 2] {
+        let res: cw721::OwnerOfResponse = app
+            .wrap()
+            .query_wasm_smart(&nft_contract_addr, &QueryMsg::OwnerOf { token_id, include_expired: None })
+            .unwrap();
+        assert_eq!(res.owner, "user2");
+    }
+    let res: BundleResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::Bundle { bundle_id: 1 })
+        .unwrap();
+    assert!(res.bundle.is_none());
+    let res: TokenBundleResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::TokenBundle { token_id: 1 })
+        .unwrap();
+    assert_eq!(res.bundle_id, None);
+}
+
+#[test]
+fn test_expire_removes_token_from_bundle() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    // 3 号永不过期，其余为限时 NFT
+    let now = app.block_info().time.seconds();
+    for (token_id, expires_at) in [(1u64, Some(now + 100)), (2, Some(now + 100)), (3, None), (4, Some(now + 100))] {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "user1".to_string(),
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::CreateBundle { token_ids: vec![1, 3] },
+        &[],
+    ).unwrap();
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::CreateBundle { token_ids: vec![2, 4] },
+        &[],
+    ).unwrap();
+
+    // 回收 1 号后组合包只剩 3 号
+    app.update_block(|block| block.time = block.time.plus_seconds(200));
+    app.execute_contract(Addr::unchecked("anyone"), nft_contract_addr.clone(), &ExecuteMsg::Expire { token_id: 1 }, &[]).unwrap();
+    let res: BundleResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::Bundle { bundle_id: 1 })
+        .unwrap();
+    assert_eq!(res.bundle.unwrap().token_ids, vec![3]);
+    let res: TokenBundleResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::TokenBundle { token_id: 1 })
+        .unwrap();
+    assert_eq!(res.bundle_id, None);
+
+    // 只含限时 NFT 的组合包在最后一个 NFT 被回收后删除
+    for token_id in [2u64, 4] {
+        app.execute_contract(Addr::unchecked("anyone"), nft_contract_addr.clone(), &ExecuteMsg::Expire { token_id }, &[]).unwrap();
+    }
+    let res: BundleResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::Bundle { bundle_id: 2 })
+        .unwrap();
+    assert!(res.bundle.is_none());
+
+    // 拆包归还剩余的 NFT
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Unbundle { bundle_id: 1 },
+        &[],
+    ).unwrap();
+    let res: cw721::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::OwnerOf { token_id: 3, include_expired: None })
+        .unwrap();
+    assert_eq!(res.owner, "user1");
+    let res: BundlesResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::Bundles {
+            owner: "user1".to_string(),
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert!(res.bundles.is_empty());
+}
+
+#[test]
+fn test_conditional_transfer_claim_and_reclaim() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    for token_id in 1..=2u64 {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "seller".to_string(),
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+
+    let hashlock: String = Sha256::digest(b"atomic-secret").iter().map(|b| format!("{:02x}", b)).collect();
+    let timeout = app.block_info().time.seconds() + 3600;
+    for token_id in 1..=2u64 {
+        app.execute_contract(
+            Addr::unchecked("seller"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::TransferWithCondition {
+                recipient: "buyer".to_string(),
+                token_id,
+                hashlock: hashlock.clone(),
+                timeout,
+            },
+            &[],
+        ).unwrap();
+    }
+    let res: ConditionalTransferResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::ConditionalTransfer { token_id: 1 })
+        .unwrap();
+    assert_eq!(res.transfer.unwrap().recipient, Addr::unchecked("buyer"));
+
+    // 托管中的 NFT 不能作为合约库存空投分发
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetRole { role: Role::Airdrop, address: "airdropper".to_string(), granted: true },
+        &[],
+    ).unwrap();
+    let result = app.execute_contract(
+        Addr::unchecked("airdropper"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Distribute { items: vec![DistributeItem { recipient: "thief".to_string(), token_id: 1 }] },
+        &[],
+    );
+    assert!(result.unwrap_err().root_cause().to_string().contains("escrow"));
+
+    // 只有接收者可以领取，原文必须匹配；超时前发送者不能取回
+    assert!(app.execute_contract(
+        Addr::unchecked("other"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ClaimConditionalTransfer { token_id: 1, preimage: "atomic-secret".to_string() },
+        &[],
+    ).is_err());
+    assert!(app.execute_contract(
+        Addr::unchecked("buyer"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ClaimConditionalTransfer { token_id: 1, preimage: "wrong".to_string() },
+        &[],
+    ).is_err());
+    assert!(app.execute_contract(
+        Addr::unchecked("seller"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ReclaimConditionalTransfer { token_id: 1 },
+        &[],
+    ).is_err());
+
+    // 领取成功，原文公开在事件属性中
+    let res = app.execute_contract(
+        Addr::unchecked("buyer"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ClaimConditionalTransfer { token_id: 1, preimage: "atomic-secret".to_string() },
+        &[],
+    ).unwrap();
+    assert!(res.events.iter().any(|event| event.attributes.iter()
+        .any(|attr| attr.key == "preimage" && attr.value == "atomic-secret")));
+    let res: cw721::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::OwnerOf { token_id: 1, include_expired: None })
+        .unwrap();
+    assert_eq!(res.owner, "buyer");
+
+    // 超时后不能领取，发送者取回
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+    assert!(app.execute_contract(
+        Addr::unchecked("buyer"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ClaimConditionalTransfer { token_id: 2, preimage: "atomic-secret".to_string() },
+        &[],
+    ).is_err());
+    app.execute_contract(
+        Addr::unchecked("seller"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ReclaimConditionalTransfer { token_id: 2 },
+        &[],
+    ).unwrap();
+    let res: cw721::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::OwnerOf { token_id: 2, include_expired: None })
+        .unwrap();
+    assert_eq!(res.owner, "seller");
+    let res: ConditionalTransferResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::ConditionalTransfer { token_id: 2 })
+        .unwrap();
+    assert!(res.transfer.is_none());
+}
+
+#[test]
+fn test_factory_creates_child_collections() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约（工厂）
+    let factory_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: Some("https://luckee.io/metadata/".to_string()),
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    // 只有合约所有者可以创建子集合
+    let create_msg = ExecuteMsg::CreateCollection {
+        name: "Luckee Season 2".to_string(),
+        symbol: "LS2".to_string(),
+        base_uri: None,
+    };
+    assert!(app.execute_contract(Addr::unchecked("other"), factory_addr.clone(), &create_msg, &[]).is_err());
+    app.execute_contract(Addr::unchecked("creator"), factory_addr.clone(), &create_msg, &[]).unwrap();
+
+    // 子集合登记到注册表
+    let res: CollectionsResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::Collections { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(res.collections.len(), 1);
+    assert!(!res.has_more);
+    let entry = &res.collections[0];
+    assert_eq!(entry.collection.name, "Luckee Season 2");
+    assert_eq!(entry.collection.symbol, "LS2");
+    assert_eq!(entry.collection.code_id, contract_id);
+    let child_addr = Addr::unchecked(entry.address.clone());
+    let res: CollectionResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::Collection { address: child_addr.to_string() })
+        .unwrap();
+    assert!(res.collection.is_some());
+    let res: CollectionResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::Collection { address: "unknown".to_string() })
+        .unwrap();
+    assert!(res.collection.is_none());
+
+    let res: cw721::ContractInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&child_addr, &QueryMsg::ContractInfo {})
+        .unwrap();
+    assert_eq!(res.symbol, "LS2");
+
+    // 子集合继承铸造者
+    let mint_msg = |token_id: u64| ExecuteMsg::Mint {
+        token_id,
+        owner: "user1".to_string(),
+        extension: NftMeta {
+            kind: NftKind::Clover,
+            scale_origin: Scale::Tiny,
+            physical_sku: None,
+            crafted_from: None,
+            series_id: "season_2".to_string(),
+            collection_group_id: None,
+            serial_in_series: token_id,
+            expires_at: None,
+        },
+        idempotency_key: None,
+    };
+    app.execute_contract(Addr::unchecked("minter"), child_addr.clone(), &mint_msg(1), &[]).unwrap();
+
+    // 子集合的合约所有者是工厂：直接暂停被拒，经工厂转发可以暂停
+    let pause = to_json_binary(&ExecuteMsg::Pause {}).unwrap();
+    assert!(app.execute_contract(Addr::unchecked("creator"), child_addr.clone(), &ExecuteMsg::Pause {}, &[]).is_err());
+    assert!(app.execute_contract(
+        Addr::unchecked("other"),
+        factory_addr.clone(),
+        &ExecuteMsg::ExecuteOnCollection { collection: child_addr.to_string(), msg: pause.clone() },
+        &[],
+    ).is_err());
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        factory_addr.clone(),
+        &ExecuteMsg::ExecuteOnCollection { collection: child_addr.to_string(), msg: pause.clone() },
+        &[],
+    ).unwrap();
+    assert!(app.execute_contract(Addr::unchecked("minter"), child_addr.clone(), &mint_msg(2), &[]).is_err());
+
+    // 只转发给注册表中的子集合
+    assert!(app.execute_contract(
+        Addr::unchecked("creator"),
+        factory_addr.clone(),
+        &ExecuteMsg::ExecuteOnCollection { collection: factory_addr.to_string(), msg: pause },
+        &[],
+    ).is_err());
+}
+
+#[test]
+fn test_shared_recipe_registry() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    let instantiate_msg = InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "minter".to_string(),
+        base_uri: None,
+    };
+    let registry_addr = app.instantiate_contract(
+        contract_id, Addr::unchecked("creator"), &instantiate_msg, &[], "Registry", None,
+    ).unwrap();
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id, Addr::unchecked("creator"), &instantiate_msg, &[], "Season 2", None,
+    ).unwrap();
+
+    let clover_recipe = |count: u32| Recipe {
+        inputs: vec![RecipeInput { nft_kind: NftKind::Clover, count }],
+        output: NftKind::Firefly,
+        cost: None,
+    };
+    let firefly_inputs = |app: &App| -> u32 {
+        let res: RecipeResponse = app
+            .wrap()
+            .query_wasm_smart(&nft_contract_addr, &QueryMsg::Recipe { target: NftKind::Firefly })
+            .unwrap();
+        res.recipe.unwrap().inputs[0].count
+    };
+
+    // 注册表将流萤配方改为 3 个四叶草，子集合设置注册表后使用注册表的配方
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        registry_addr.clone(),
+        &ExecuteMsg::SetRecipe { target: NftKind::Firefly, recipe: clover_recipe(3) },
+        &[],
+    ).unwrap();
+    let set_registry = ExecuteMsg::SetRecipeRegistry { registry: Some(registry_addr.to_string()), cache_ttl: 3600 };
+    assert!(app.execute_contract(Addr::unchecked("other"), nft_contract_addr.clone(), &set_registry, &[]).is_err());
+    assert!(app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetRecipeRegistry { registry: Some(nft_contract_addr.to_string()), cache_ttl: 3600 },
+        &[],
+    ).is_err());
+    app.execute_contract(Addr::unchecked("creator"), nft_contract_addr.clone(), &set_registry, &[]).unwrap();
+    assert_eq!(firefly_inputs(&app), 3);
+
+    for token_id in 1..=3u64 {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "user1".to_string(),
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+    assert!(app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Synthesize { inputs: vec![1, 2], target: NftKind::Firefly },
+        &[],
+    ).is_err());
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Synthesize { inputs: vec![1, 2, 3], target: NftKind::Firefly },
+        &[],
+    ).unwrap();
+
+    // 合成时缓存注册表的配方，缓存有效期内注册表的修改不生效
+    let res: RecipeRegistryResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::RecipeRegistry {})
+        .unwrap();
+    assert_eq!(res.registry.unwrap().registry, registry_addr);
+    assert_eq!(res.cached.len(), 1);
+    assert_eq!(res.cached[0].target, NftKind::Firefly);
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        registry_addr.clone(),
+        &ExecuteMsg::SetRecipe { target: NftKind::Firefly, recipe: clover_recipe(4) },
+        &[],
+    ).unwrap();
+    assert_eq!(firefly_inputs(&app), 3);
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+    assert_eq!(firefly_inputs(&app), 4);
+
+    // 注册表没有该配方时使用本地配方
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        registry_addr.clone(),
+        &ExecuteMsg::RemoveRecipe { target: NftKind::Firefly },
+        &[],
+    ).unwrap();
+    assert_eq!(firefly_inputs(&app), 2);
+
+    // 取消注册表时清空缓存
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetRecipeRegistry { registry: None, cache_ttl: 0 },
+        &[],
+    ).unwrap();
+    let res: RecipeRegistryResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::RecipeRegistry {})
+        .unwrap();
+    assert!(res.registry.is_none());
+    assert!(res.cached.is_empty());
+}
+
+#[test]
+fn test_cross_collection_synthesis_output() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    let instantiate_msg = |symbol: &str| InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: symbol.to_string(),
+        minter: "minter".to_string(),
+        base_uri: None,
+    };
+    let season_1 = app.instantiate_contract(
+        contract_id, Addr::unchecked("creator"), &instantiate_msg("LS1"), &[], "Season 1", None,
+    ).unwrap();
+    let season_2 = app.instantiate_contract(
+        contract_id, Addr::unchecked("creator"), &instantiate_msg("LS2"), &[], "Season 2", None,
+    ).unwrap();
+
+    // 第一季的流萤配方产出在第二季铸造
+    let set_output = ExecuteMsg::SetRecipeOutputCollection {
+        target: NftKind::Firefly,
+        collection: Some(season_2.to_string()),
+    };
+    assert!(app.execute_contract(Addr::unchecked("other"), season_1.clone(), &set_output, &[]).is_err());
+    app.execute_contract(Addr::unchecked("creator"), season_1.clone(), &set_output, &[]).unwrap();
+    let res: RecipeOutputCollectionResponse = app
+        .wrap()
+        .query_wasm_smart(&season_1, &QueryMsg::RecipeOutputCollection { target: NftKind::Firefly })
+        .unwrap();
+    assert_eq!(res.collection, Some(season_2.to_string()));
+
+    for token_id in 1..=4u64 {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            season_1.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "user1".to_string(),
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "season_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+    let synthesize = |inputs: Vec<u64>| ExecuteMsg::Synthesize { inputs, target: NftKind::Firefly };
+
+    // 第一季未在第二季登记为来源集合时合成整体回滚，输入保留；铸造者身份不能代替登记
+    assert!(app.execute_contract(Addr::unchecked("user1"), season_1.clone(), &synthesize(vec![1, 2]), &[]).is_err());
+    let res: cw721::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(&season_1, &QueryMsg::OwnerOf { token_id: 1, include_expired: None })
+        .unwrap();
+    assert_eq!(res.owner, "user1");
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        season_2.clone(),
+        &ExecuteMsg::SetMinter { minter: season_1.to_string(), allowed: true },
+        &[],
+    ).unwrap();
+    assert!(app.execute_contract(Addr::unchecked("user1"), season_1.clone(), &synthesize(vec![1, 2]), &[]).is_err());
+
+    // 第一季登记为第二季的来源集合后，输入在第一季销毁，产出在登记的系列中铸造
+    let set_source = ExecuteMsg::SetSynthesisSource {
+        collection: season_1.to_string(),
+        series_id: Some("season_1_crafts".to_string()),
+    };
+    assert!(app.execute_contract(Addr::unchecked("other"), season_2.clone(), &set_source, &[]).is_err());
+    app.execute_contract(Addr::unchecked("creator"), season_2.clone(), &set_source, &[]).unwrap();
+    let res: SynthesisSourceResponse = app
+        .wrap()
+        .query_wasm_smart(&season_2, &QueryMsg::SynthesisSource { collection: season_1.to_string() })
+        .unwrap();
+    assert_eq!(res.series_id, Some("season_1_crafts".to_string()));
+    let res = app.execute_contract(Addr::unchecked("user1"), season_1.clone(), &synthesize(vec![1, 2]), &[]).unwrap();
+    let synthesize_event = res.events.iter()
+        .find(|event| event.ty.starts_with("wasm")
+            && event.attributes.iter().any(|attr| attr.key == "_contract_address" && attr.value == season_1.as_str())
+            && event.attributes.iter().any(|attr| attr.key == "action" && attr.value == "synthesize")
+            && event.attributes.iter().any(|attr| attr.key == "total_supply"))
+        .expect("synthesize event");
+    let data: SynthesizeData = from_json(res.data.unwrap()).unwrap();
+    let attr = |key: &str| synthesize_event.attributes.iter().find(|attr| attr.key == key).map(|attr| attr.value.clone());
+    assert_eq!(attr("output_token_id"), Some(data.output_token_id.to_string()));
+    assert_eq!(attr("total_supply"), Some("2".to_string()));
+    assert_eq!(data.output_collection, Some(season_2.to_string()));
+    assert_eq!(data.burned_inputs, vec![1, 2]);
+    assert_eq!(data.target, NftKind::Firefly);
+    let res: cw721::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(&season_2, &QueryMsg::OwnerOf { token_id: data.output_token_id, include_expired: None })
+        .unwrap();
+    assert_eq!(res.owner, "user1");
+    let res: TokenMetaResponse = app
+        .wrap()
+        .query_wasm_smart(&season_2, &QueryMsg::TokenMeta { token_id: data.output_token_id })
+        .unwrap();
+    assert_eq!(res.meta.series_id, "season_1_crafts");
+    assert!(app
+        .wrap()
+        .query_wasm_smart::<cw721::OwnerOfResponse>(&season_1, &QueryMsg::OwnerOf { token_id: 1, include_expired: None })
+        .is_err());
+
+    // 只有登记的来源集合可以请求铸造产出
+    assert!(app.execute_contract(
+        Addr::unchecked("user1"),
+        season_2.clone(),
+        &ExecuteMsg::MintSynthesisOutput { owner: "user1".to_string(), kind: NftKind::Genesis },
+        &[],
+    ).is_err());
+
+    // 取消产出集合后恢复在本集合铸造
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        season_1.clone(),
+        &ExecuteMsg::SetRecipeOutputCollection { target: NftKind::Firefly, collection: None },
+        &[],
+    ).unwrap();
+    let res = app.execute_contract(Addr::unchecked("user1"), season_1.clone(), &synthesize(vec![3, 4]), &[]).unwrap();
+    let data: SynthesizeData = from_json(res.data.unwrap()).unwrap();
+    assert!(data.output_collection.is_none());
+    let res: cw721::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(&season_1, &QueryMsg::OwnerOf { token_id: data.output_token_id, include_expired: None })
+        .unwrap();
+    assert_eq!(res.owner, "user1");
+}
+
+#[test]
+fn test_pool_mint_draws_by_remaining_counts() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    let create_pool = |size: u64| ExecuteMsg::CreateMintPool {
+        pool_id: "box_1".to_string(),
+        series_id: "blind_box_1".to_string(),
+        entries: vec![
+            PoolEntry { kind: NftKind::Clover, weight: 3 },
+            PoolEntry { kind: NftKind::Firefly, weight: 1 },
+        ],
+        size,
+    };
+    // 只有合约所有者可以创建；总量须按权重整除
+    assert!(app.execute_contract(Addr::unchecked("minter"), nft_contract_addr.clone(), &create_pool(4), &[]).is_err());
+    assert!(app.execute_contract(Addr::unchecked("creator"), nft_contract_addr.clone(), &create_pool(5), &[]).is_err());
+    app.execute_contract(Addr::unchecked("creator"), nft_contract_addr.clone(), &create_pool(4), &[]).unwrap();
+    assert!(app.execute_contract(Addr::unchecked("creator"), nft_contract_addr.clone(), &create_pool(4), &[]).is_err());
+
+    let res: MintPoolResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::MintPool { pool_id: "box_1".to_string() })
+        .unwrap();
+    let pool = res.pool.unwrap();
+    assert_eq!(pool.remaining, 4);
+    assert_eq!(pool.slots[0].remaining, 3);
+    assert_eq!(pool.slots[1].remaining, 1);
+
+    // 只有铸造者可以抽取
+    let pool_mint = ExecuteMsg::PoolMint { pool_id: "box_1".to_string(), owner: "user1".to_string() };
+    assert!(app.execute_contract(Addr::unchecked("user1"), nft_contract_addr.clone(), &pool_mint, &[]).is_err());
+
+    // 抽完时产出与权重完全一致
+    let mut kinds = vec![];
+    for _ in 0..4 {
+        let res = app.execute_contract(Addr::unchecked("minter"), nft_contract_addr.clone(), &pool_mint, &[]).unwrap();
+        let kind = res.events.iter()
+            .flat_map(|event| event.attributes.iter())
+            .find(|attr| attr.key == "kind")
+            .map(|attr| attr.value.clone())
+            .unwrap();
+        // 响应注明抽取结果可预测
+        assert!(res.events.iter()
+            .flat_map(|event| event.attributes.iter())
+            .any(|attr| attr.key == "draw_predictable" && attr.value == "true"));
+        kinds.push(kind);
+        app.update_block(|block| block.height += 1);
+    }
+    assert_eq!(kinds.iter().filter(|kind| *kind == "Clover").count(), 3);
+    assert_eq!(kinds.iter().filter(|kind| *kind == "Firefly").count(), 1);
+    assert!(app.execute_contract(Addr::unchecked("minter"), nft_contract_addr.clone(), &pool_mint, &[]).is_err());
+
+    let res: cw721::TokensResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::Tokens { owner: "user1".to_string(), start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(res.tokens.len(), 4);
+
+    // 关闭奖池
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::CloseMintPool { pool_id: "box_1".to_string() },
+        &[],
+    ).unwrap();
+    let res: MintPoolResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::MintPool { pool_id: "box_1".to_string() })
+        .unwrap();
+    assert!(res.pool.is_none());
+}
+
+#[test]
+fn test_expiry_scan_stops_at_scan_cap() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    // 铸造超过扫描上限数量的未过期 NFT
+    for batch in 0..4u64 {
+        let mints: Vec<_> = (1..=100).map(|i| luckee_nft::msg::BatchMintItem {
+            token_id: batch * 100 + i,
+            owner: format!("user{}", batch * 100 + i),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "scan_series".to_string(),
+                collection_group_id: None,
+                serial_in_series: batch * 100 + i,
+                expires_at: None,
+            },
+        }).collect();
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::BatchMint { mints, idempotency_key: None },
+            &[],
+        ).unwrap();
+    }
+
+    // 没有命中的条目：第一次查询在扫描上限处停止，返回部分结果和续扫游标
+    let res: luckee_nft::msg::TokensByExpiryResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::TokensByExpiry { expired: true, start_after: None, limit: None })
+        .unwrap();
+    assert!(res.tokens.is_empty());
+    assert!(res.scan_truncated);
+    assert!(res.has_more);
+    assert!(res.next_cursor.is_some());
+
+    // 续扫剩余的键后到达末尾
+    let res: luckee_nft::msg::TokensByExpiryResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::TokensByExpiry {
+            expired: true,
+            start_after: res.next_cursor,
+            limit: None,
+        })
+        .unwrap();
+    assert!(res.tokens.is_empty());
+    assert!(!res.scan_truncated);
+    assert!(!res.has_more);
+    assert!(res.next_cursor.is_none());
+
+    // 命中足够条目时不受影响
+    let res: luckee_nft::msg::TokensByExpiryResponse = app
+        .wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::TokensByExpiry { expired: false, start_after: None, limit: Some(10) })
+        .unwrap();
+    assert_eq!(res.tokens.len(), 10);
+    assert!(!res.scan_truncated);
+    assert!(res.has_more);
+}
+
+#[test]
+fn test_approved_tokens_by_spender() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    for token_id in 1..=4u64 {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "user1".to_string(),
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+
+    // 1、3、4 号批准给 market（4 号稍后过期），2 号批准给 friend
+    let user1 = Addr::unchecked("user1");
+    let expires_at = app.block_info().time.seconds() + 100;
+    app.execute_contract(user1.clone(), nft_contract_addr.clone(),
+        &ExecuteMsg::Approve { spender: "market".to_string(), token_id: 1, expires: None }, &[]).unwrap();
+    app.execute_contract(user1.clone(), nft_contract_addr.clone(),
+        &ExecuteMsg::Approve { spender: "friend".to_string(), token_id: 2, expires: None }, &[]).unwrap();
+    app.execute_contract(user1.clone(), nft_contract_addr.clone(),
+        &ExecuteMsg::Approve { spender: "market".to_string(), token_id: 3, expires: None }, &[]).unwrap();
+    app.execute_contract(user1.clone(), nft_contract_addr.clone(),
+        &ExecuteMsg::Approve {
+            spender: "market".to_string(),
+            token_id: 4,
+            expires: Some(Expiration { at_height: None, at_time: Some(expires_at) }),
+        }, &[]).unwrap();
+
+    // 分页查询
+    let res: ApprovedTokensResponse = app.wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::ApprovedTokens {
+            owner: "user1".to_string(),
+            spender: "market".to_string(),
+            start_after: None,
+            limit: Some(2),
+        })
+        .unwrap();
+    assert_eq!(res.tokens.iter().map(|grant| grant.token_id).collect::<Vec<_>>(), vec![1, 3]);
+    assert!(res.has_more);
+    assert!(!res.scan_truncated);
+    let res: ApprovedTokensResponse = app.wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::ApprovedTokens {
+            owner: "user1".to_string(),
+            spender: "market".to_string(),
+            start_after: res.next_cursor,
+            limit: Some(2),
+        })
+        .unwrap();
+    assert_eq!(res.tokens.len(), 1);
+    assert_eq!(res.tokens[0].token_id, 4);
+    assert_eq!(res.tokens[0].expires, Some(Expiration { at_height: None, at_time: Some(expires_at) }));
+    assert!(!res.has_more);
+
+    // 过期和转出的 NFT 不再返回
+    app.update_block(|block| block.time = block.time.plus_seconds(200));
+    app.execute_contract(user1, nft_contract_addr.clone(),
+        &ExecuteMsg::TransferNft { recipient: "user2".to_string(), token_id: 1 }, &[]).unwrap();
+    let res: ApprovedTokensResponse = app.wrap()
+        .query_wasm_smart(&nft_contract_addr, &QueryMsg::ApprovedTokens {
+            owner: "user1".to_string(),
+            spender: "market".to_string(),
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(res.tokens.iter().map(|grant| grant.token_id).collect::<Vec<_>>(), vec![3]);
+    assert!(!res.has_more);
+}
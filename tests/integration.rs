@@ -2,10 +2,10 @@ use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 use cosmwasm_std::{coins, Addr, from_json};
 use cw_multi_test::{App, Contract, ContractWrapper, Executor};
 
-use luckee_nft::contract::{execute, instantiate, query};
-use luckee_nft::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, TokenMetaResponse};
-use luckee_nft::state::CONFIG;
-use luckee_nft::types::{NftKind, NftMeta, Scale};
+use luckee_nft::contract::{execute, instantiate, query, migrate};
+use luckee_nft::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, TokenMetaResponse};
+use luckee_nft::state::{CONFIG, COUNTS_BY_OWNER_KIND, TOKENS_BY_KIND, TOKEN_INDEXES_READY};
+use luckee_nft::types::{NftKind, NftMeta, Recipe, RecipeInput, Scale};
 
 fn mock_app() -> App {
     App::default()
@@ -39,6 +39,38 @@ fn test_instantiate() {
     assert_eq!(config.minter, Addr::unchecked("blind_box_contract"));
 }
 
+#[test]
+fn test_instantiate_validation() {
+    use luckee_nft::ContractError;
+
+    let init = |name: &str, symbol: &str, base_uri: Option<&str>| {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), InstantiateMsg {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            minter: "blind_box_contract".to_string(),
+            base_uri: base_uri.map(|uri| uri.to_string()),
+        })
+    };
+    let field_of = |err: ContractError| match err {
+        ContractError::InvalidInstantiateField { field, .. } => field,
+        err => panic!("unexpected error: {}", err),
+    };
+
+    // 名称和符号不能为空或过长，符号不能含空白
+    assert_eq!(field_of(init("  ", "LUCKEE", None).unwrap_err()), "name");
+    assert_eq!(field_of(init(&"n".repeat(65), "LUCKEE", None).unwrap_err()), "name");
+    assert_eq!(field_of(init("Luckee NFT", "", None).unwrap_err()), "symbol");
+    assert_eq!(field_of(init("Luckee NFT", "LUCK EE", None).unwrap_err()), "symbol");
+    assert_eq!(field_of(init("Luckee NFT", &"S".repeat(17), None).unwrap_err()), "symbol");
+
+    // 基础 URI 须使用支持的协议
+    assert_eq!(field_of(init("Luckee NFT", "LUCKEE", Some("ftp://luckee.io/")).unwrap_err()), "base_uri");
+
+    // 边界长度可以通过
+    init(&"n".repeat(64), &"S".repeat(16), Some("ipfs://bafy/")).unwrap();
+}
+
 #[test]
 fn test_mint_nft() {
     let mut deps = mock_dependencies();
@@ -66,7 +98,9 @@ fn test_mint_nft() {
             series_id: "series_1".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let mint_info = mock_info("blind_box_contract", &[]);
@@ -109,7 +143,9 @@ fn test_synthesis() {
                 series_id: "series_1".to_string(),
                 collection_group_id: None,
                 serial_in_series: i,
+                expires_at: None,
             },
+            idempotency_key: None,
         };
         execute(deps.as_mut(), env.clone(), mint_info.clone(), mint_msg).unwrap();
     }
@@ -124,6 +160,13 @@ fn test_synthesis() {
     let res = execute(deps.as_mut(), env.clone(), user_info, synthesis_msg).unwrap();
     assert_eq!(res.messages.len(), 0);
 
+    // 合成结果写入 Response.data
+    let data: luckee_nft::msg::SynthesizeData = from_json(&res.data.unwrap()).unwrap();
+    assert_eq!(data.output_token_id, 3);
+    assert_eq!(data.owner, "user1");
+    assert_eq!(data.target, NftKind::Firefly);
+    assert_eq!(data.burned_inputs, vec![1, 2]);
+
     // 验证合成结果
     let query_msg = QueryMsg::TokenMeta { token_id: 3 };
     let res: TokenMetaResponse = from_json(&query(deps.as_ref(), env, query_msg).unwrap()).unwrap();
@@ -160,6 +203,7 @@ fn test_batch_mint() {
                     series_id: "series_1".to_string(),
                     collection_group_id: None,
                     serial_in_series: 1,
+                    expires_at: None,
                 },
             },
             luckee_nft::msg::BatchMintItem {
@@ -173,9 +217,11 @@ fn test_batch_mint() {
                     series_id: "series_1".to_string(),
                     collection_group_id: None,
                     serial_in_series: 2,
+                    expires_at: None,
                 },
             },
         ],
+        idempotency_key: None,
     };
 
     let mint_info = mock_info("blind_box_contract", &[]);
@@ -215,8 +261,17 @@ fn test_recipe_management() {
 
     // 查询所有配方
     let query_msg = QueryMsg::AllRecipes { start_after: None, limit: Some(10) };
-    let res: luckee_nft::msg::AllRecipesResponse = from_json(&query(deps.as_ref(), env, query_msg).unwrap()).unwrap();
+    let res: luckee_nft::msg::AllRecipesResponse = from_json(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
     assert_eq!(res.recipes.len(), 8); // 8个合成配方
+    assert!(!res.has_more);
+    assert_eq!(res.total, Some(8));
+
+    // 分页时总数不变
+    let query_msg = QueryMsg::AllRecipes { start_after: None, limit: Some(3) };
+    let res: luckee_nft::msg::AllRecipesResponse = from_json(&query(deps.as_ref(), env, query_msg).unwrap()).unwrap();
+    assert_eq!(res.recipes.len(), 3);
+    assert!(res.has_more);
+    assert_eq!(res.total, Some(8));
 }
 
 #[test]
@@ -246,7 +301,9 @@ fn test_unauthorized_mint() {
             series_id: "series_1".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let unauthorized_info = mock_info("unauthorized_user", &[]);
@@ -321,7 +378,9 @@ fn test_cw721_integration_mint_transfer() {
             series_id: "series_1".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let _mint_info = mock_info("blind_box_contract", &[]);
@@ -381,7 +440,9 @@ fn test_synthesize_owner_check() {
                 series_id: "series_1".to_string(),
                 collection_group_id: None,
                 serial_in_series: i,
+                expires_at: None,
             },
+            idempotency_key: None,
         };
         app.execute_contract(
             Addr::unchecked("blind_box_contract"),
@@ -462,11 +523,12 @@ fn test_batch_mint_gas_and_split() {
                 series_id: "batch_series".to_string(),
                 collection_group_id: None,
                 serial_in_series: i,
+                expires_at: None,
             },
         });
     }
 
-    let batch_mint_msg = ExecuteMsg::BatchMint { mints };
+    let batch_mint_msg = ExecuteMsg::BatchMint { mints, idempotency_key: None };
     let res = app.execute_contract(
         Addr::unchecked("blind_box_contract"),
         nft_contract_addr.clone(),
@@ -521,7 +583,9 @@ fn test_replay_duplicate_token_id() {
             series_id: "series_1".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let res = app.execute_contract(
@@ -586,7 +650,9 @@ fn test_pause_unpause_mechanism() {
             series_id: "series_1".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let res = app.execute_contract(
@@ -698,11 +764,12 @@ fn test_batch_mint_size_limit() {
                 series_id: "batch_series".to_string(),
                 collection_group_id: None,
                 serial_in_series: i,
+                expires_at: None,
             },
         });
     }
 
-    let batch_mint_msg = ExecuteMsg::BatchMint { mints };
+    let batch_mint_msg = ExecuteMsg::BatchMint { mints, idempotency_key: None };
     let res = app.execute_contract(
         Addr::unchecked("blind_box_contract"),
         nft_contract_addr,
@@ -748,7 +815,9 @@ fn test_cw721_ownership_verification() {
             series_id: "series_1".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     app.execute_contract(
@@ -911,3 +980,3230 @@ fn test_synthesis_edge_cases() {
     assert!(res.is_err());
 }
 
+
+#[test]
+fn test_emergency_withdraw_guardian_approval() {
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked("creator"), coins(10_000, "uluckee"))
+            .unwrap();
+    });
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约并注入资金
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "blind_box_contract".to_string(),
+            base_uri: Some("https://luckee.io/metadata/".to_string()),
+        },
+        &coins(10_000, "uluckee"),
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    // 配置 2-of-3 守护者，阈值 1000
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetWithdrawGuardians {
+            guardians: vec!["guardian1".to_string(), "guardian2".to_string(), "guardian3".to_string()],
+            required: 2,
+            threshold: Some(cosmwasm_std::Uint128::new(1000)),
+        },
+        &[],
+    ).unwrap();
+
+    // 阈值以内的提取直接执行
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::EmergencyWithdraw { amount: coins(500, "uluckee") },
+        &[],
+    ).unwrap();
+    let balance = app.wrap().query_balance("creator", "uluckee").unwrap();
+    assert_eq!(balance.amount.u128(), 500);
+
+    // 超过阈值的提取需要审批
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::EmergencyWithdraw { amount: coins(5000, "uluckee") },
+        &[],
+    ).unwrap();
+    let balance = app.wrap().query_balance("creator", "uluckee").unwrap();
+    assert_eq!(balance.amount.u128(), 500);
+
+    // 非守护者不能审批
+    let res = app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ApproveWithdrawal { id: 1 },
+        &[],
+    );
+    assert!(res.is_err());
+
+    // 第一个守护者审批，不能重复审批
+    app.execute_contract(
+        Addr::unchecked("guardian1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ApproveWithdrawal { id: 1 },
+        &[],
+    ).unwrap();
+    let res = app.execute_contract(
+        Addr::unchecked("guardian1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ApproveWithdrawal { id: 1 },
+        &[],
+    );
+    assert!(res.is_err());
+
+    // 第二个守护者审批后执行转账
+    app.execute_contract(
+        Addr::unchecked("guardian2"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ApproveWithdrawal { id: 1 },
+        &[],
+    ).unwrap();
+    let balance = app.wrap().query_balance("creator", "uluckee").unwrap();
+    assert_eq!(balance.amount.u128(), 5500);
+
+    let pending: luckee_nft::msg::PendingWithdrawalResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::PendingWithdrawal { id: 1 })
+        .unwrap();
+    assert!(pending.withdrawal.is_none());
+
+    // 拆分成多笔小额提取时按窗口累计：500 + 600 超过阈值，需要审批
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::EmergencyWithdraw { amount: coins(600, "uluckee") },
+        &[],
+    ).unwrap();
+    let balance = app.wrap().query_balance("creator", "uluckee").unwrap();
+    assert_eq!(balance.amount.u128(), 5500);
+    let pending: luckee_nft::msg::PendingWithdrawalResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::PendingWithdrawal { id: 2 })
+        .unwrap();
+    assert!(pending.withdrawal.is_some());
+
+    // 超过有效期的提取不能再审批，由所有者取消
+    app.update_block(|block| block.time = block.time.plus_seconds(luckee_nft::admin::GUARDIAN_PROPOSAL_TTL_SECONDS));
+    let err = app.execute_contract(
+        Addr::unchecked("guardian1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ApproveWithdrawal { id: 2 },
+        &[],
+    ).unwrap_err();
+    assert!(err.root_cause().to_string().contains("Proposal expired"));
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::CancelWithdrawal { id: 2 },
+        &[],
+    ).unwrap();
+
+    // 所有者不能担任守护者
+    let res = app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetWithdrawGuardians { guardians: vec!["creator".to_string()], required: 1, threshold: None },
+        &[],
+    );
+    assert!(res.is_err());
+
+    // 已配置守护者时，关闭审批只登记为提案，需当前守护者审批后生效
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetWithdrawGuardians { guardians: vec![], required: 0, threshold: None },
+        &[],
+    ).unwrap();
+    let guardians: luckee_nft::msg::WithdrawGuardiansResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::WithdrawGuardians {})
+        .unwrap();
+    assert!(guardians.config.is_some());
+    assert!(guardians.pending_change.unwrap().config.is_none());
+
+    // 只有所有者可以取消配置变更，取消后重新提议
+    assert!(app.execute_contract(
+        Addr::unchecked("guardian1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::CancelGuardianChange {},
+        &[],
+    ).is_err());
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::CancelGuardianChange {},
+        &[],
+    ).unwrap();
+    let guardians: luckee_nft::msg::WithdrawGuardiansResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::WithdrawGuardians {})
+        .unwrap();
+    assert!(guardians.pending_change.is_none());
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetWithdrawGuardians { guardians: vec![], required: 0, threshold: None },
+        &[],
+    ).unwrap();
+
+    let res = app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ApproveGuardianChange {},
+        &[],
+    );
+    assert!(res.is_err());
+    for guardian in ["guardian1", "guardian3"] {
+        app.execute_contract(
+            Addr::unchecked(guardian),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::ApproveGuardianChange {},
+            &[],
+        ).unwrap();
+    }
+    let guardians: luckee_nft::msg::WithdrawGuardiansResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr, &QueryMsg::WithdrawGuardians {})
+        .unwrap();
+    assert!(guardians.config.is_none());
+    assert!(guardians.pending_change.is_none());
+}
+
+#[test]
+fn test_cw721_string_token_ids() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let info = mock_info("creator", &[]);
+
+    let init_msg = InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    };
+    instantiate(deps.as_mut(), env.clone(), info, init_msg).unwrap();
+
+    let mint_msg = ExecuteMsg::Mint {
+        token_id: 7,
+        owner: "user1".to_string(),
+        extension: NftMeta {
+            kind: NftKind::Clover,
+            scale_origin: Scale::Tiny,
+            physical_sku: None,
+            crafted_from: None,
+            series_id: "series_1".to_string(),
+            collection_group_id: None,
+            serial_in_series: 1,
+            expires_at: None,
+        },
+        idempotency_key: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), mint_msg).unwrap();
+
+    // 通用 CW721 工具以字符串发送 token_id，与数字形式等价
+    let string_msg: ExecuteMsg = from_json(br#"{"transfer_nft":{"recipient":"user2","token_id":"7"}}"#).unwrap();
+    let number_msg: ExecuteMsg = from_json(br#"{"transfer_nft":{"recipient":"user2","token_id":7}}"#).unwrap();
+    assert_eq!(string_msg, number_msg);
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), string_msg).unwrap();
+
+    let query_msg: QueryMsg = from_json(br#"{"owner_of":{"token_id":"7"}}"#).unwrap();
+    let res: cw721::OwnerOfResponse = from_json(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+    assert_eq!(res.owner, "user2");
+
+    // 分页参数同样接受字符串，响应中的 token_id 为字符串
+    let query_msg: QueryMsg = from_json(br#"{"all_tokens":{"start_after":"6"}}"#).unwrap();
+    let res: cw721::TokensResponse = from_json(&query(deps.as_ref(), env, query_msg).unwrap()).unwrap();
+    assert_eq!(res.tokens, vec!["7".to_string()]);
+
+    // 非数字的 token_id 被拒绝
+    assert!(from_json::<ExecuteMsg>(br#"{"burn":{"token_id":"abc"}}"#).is_err());
+}
+
+#[test]
+fn test_index_upgrade_for_legacy_deployment() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    let init_msg = InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    };
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), init_msg).unwrap();
+
+    for (token_id, kind) in [(1u64, NftKind::Clover), (2, NftKind::Clover), (3, NftKind::Firefly)] {
+        let mint_msg = ExecuteMsg::Mint {
+            token_id,
+            owner: "user1".to_string(),
+            extension: NftMeta {
+                kind,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "series_1".to_string(),
+                collection_group_id: None,
+                serial_in_series: token_id,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), mint_msg).unwrap();
+    }
+
+    // 模拟尚无二级索引的旧部署
+    TOKEN_INDEXES_READY.remove(&mut deps.storage);
+    for token_id in [1u64, 2] {
+        TOKENS_BY_KIND.remove(&mut deps.storage, ("Clover".to_string(), token_id));
+    }
+    TOKENS_BY_KIND.remove(&mut deps.storage, ("Firefly".to_string(), 3));
+
+    // 迁移推进第一批：清空旧的持有数量
+    let res = migrate(deps.as_mut(), env.clone(), MigrateMsg { allow_downgrade: false }).unwrap();
+    let phase = res.events[0].attributes.iter().find(|attr| attr.key == "phase").unwrap();
+    assert_eq!(phase.value, "clear_counts");
+
+    // 迁移事件报告执行的转换和批次结束后的进度
+    let attr = |key: &str| res.events[1].attributes.iter().find(|attr| attr.key == key).unwrap().value.clone();
+    assert_eq!(attr("action"), "migrate");
+    assert_eq!(attr("transforms"), "index_upgrade_batch");
+    assert_eq!(attr("index_phase"), "clear_voting_power");
+    assert_eq!(attr("index_last_token_id"), "none");
+    assert_eq!(attr("index_ready"), "false");
+
+    // 重建一批后转移一个尚未扫描到的 NFT，升级结果仍然正确
+    let upgrade = ExecuteMsg::ApplyIndexUpgrade { limit: Some(1) };
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), upgrade.clone()).unwrap();
+    let transfer = ExecuteMsg::TransferNft { recipient: "user2".to_string(), token_id: 3 };
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), transfer).unwrap();
+
+    // 非所有者不能推进升级
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), upgrade.clone()).is_err());
+
+    let mut batches = 0;
+    loop {
+        let res = execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), upgrade.clone()).unwrap();
+        batches += 1;
+        let complete = res.events.iter()
+            .flat_map(|event| event.attributes.iter())
+            .any(|attr| attr.key == "complete" && attr.value == "true");
+        if complete {
+            break;
+        }
+        assert!(batches < 10);
+    }
+
+    assert!(TOKEN_INDEXES_READY.load(&deps.storage).unwrap());
+    assert_eq!(COUNTS_BY_OWNER_KIND.load(&deps.storage, (Addr::unchecked("user1"), "Clover".to_string())).unwrap(), 2);
+    assert_eq!(COUNTS_BY_OWNER_KIND.load(&deps.storage, (Addr::unchecked("user2"), "Firefly".to_string())).unwrap(), 1);
+    assert!(!COUNTS_BY_OWNER_KIND.has(&deps.storage, (Addr::unchecked("user1"), "Firefly".to_string())));
+
+    // 投票权随索引一同重建
+    for (owner, power) in [("user1", 2u64), ("user2", 2)] {
+        let query_msg = QueryMsg::VotingPower { owner: owner.to_string(), at_snapshot: None };
+        let res: luckee_nft::msg::VotingPowerResponse = from_json(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.power, power);
+    }
+
+    let query_msg = QueryMsg::TokensByKind { kind: NftKind::Clover, start_after: None, limit: None };
+    let res: luckee_nft::msg::TokensByKindResponse = from_json(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+    assert_eq!(res.tokens, vec![1, 2]);
+    assert!(!res.has_more);
+    assert_eq!(res.next_cursor, None);
+
+    // 按游标逐页读取
+    let query_msg = QueryMsg::TokensByKind { kind: NftKind::Clover, start_after: None, limit: Some(1) };
+    let res: luckee_nft::msg::TokensByKindResponse = from_json(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+    assert_eq!(res.tokens, vec![1]);
+    assert!(res.has_more);
+    let cursor = res.next_cursor.unwrap();
+
+    let query_msg = QueryMsg::TokensByKind { kind: NftKind::Clover, start_after: Some(cursor.clone()), limit: Some(1) };
+    let res: luckee_nft::msg::TokensByKindResponse = from_json(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+    assert_eq!(res.tokens, vec![2]);
+    assert!(!res.has_more);
+    assert_eq!(res.next_cursor, None);
+
+    // 系列响应附带现存总数
+    let query_msg = QueryMsg::TokensBySeries { series_id: "series_1".to_string(), start_after: None, limit: Some(2) };
+    let res: luckee_nft::msg::TokensBySeriesResponse = from_json(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+    assert_eq!(res.tokens, vec![1, 2]);
+    assert!(res.has_more);
+    assert_eq!(res.total, Some(3));
+
+    // 游标不能用于其他查询
+    let query_msg = QueryMsg::TokensBySeries { series_id: "S1".to_string(), start_after: Some(cursor), limit: None };
+    assert!(query(deps.as_ref(), env, query_msg).is_err());
+}
+
+#[test]
+fn test_simulate_recipe() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    let init_msg = InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    };
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), init_msg).unwrap();
+
+    // 3 个四叶草 + 1 个流萤 合成 1 个赤色锦鲤（消耗价值 5，产出价值 4）
+    let recipe = Recipe {
+        inputs: vec![
+            RecipeInput { nft_kind: NftKind::Clover, count: 3 },
+            RecipeInput { nft_kind: NftKind::Firefly, count: 1 },
+        ],
+        output: NftKind::CrimsonKoi,
+        cost: None,
+    };
+
+    // 重复类型的库存累加
+    let query_msg = QueryMsg::SimulateRecipe {
+        recipe: recipe.clone(),
+        inventory: vec![(NftKind::Clover, 4), (NftKind::Clover, 3), (NftKind::Firefly, 2)],
+    };
+    let res: luckee_nft::msg::SimulateRecipeResponse = from_json(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+    assert!(res.matches);
+    assert_eq!(res.max_times, 2);
+    assert!(res.missing.is_empty());
+    assert_eq!(res.input_value, 5);
+    assert_eq!(res.output_value, 4);
+    assert_eq!(res.value_delta, -1);
+
+    // 库存不足时列出缺少的输入
+    let query_msg = QueryMsg::SimulateRecipe { recipe, inventory: vec![(NftKind::Clover, 1)] };
+    let res: luckee_nft::msg::SimulateRecipeResponse = from_json(&query(deps.as_ref(), env, query_msg).unwrap()).unwrap();
+    assert!(!res.matches);
+    assert_eq!(res.max_times, 0);
+    assert_eq!(res.missing, vec![
+        RecipeInput { nft_kind: NftKind::Clover, count: 2 },
+        RecipeInput { nft_kind: NftKind::Firefly, count: 1 },
+    ]);
+}
+
+#[test]
+fn test_minter_stats() {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+
+    let init_msg = InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    };
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), init_msg).unwrap();
+
+    let meta = |serial: u64| NftMeta {
+        kind: NftKind::Clover,
+        scale_origin: Scale::Tiny,
+        physical_sku: None,
+        crafted_from: None,
+        series_id: "series_1".to_string(),
+        collection_group_id: None,
+        serial_in_series: serial,
+        expires_at: None,
+    };
+
+    // 从未铸造的地址统计为空
+    let query_msg = QueryMsg::MinterStats { minter: "blind_box_contract".to_string() };
+    let res: luckee_nft::msg::MinterStatsResponse = from_json(&query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap()).unwrap();
+    assert_eq!(res.total_minted, 0);
+    assert_eq!(res.last_mint_time, None);
+
+    // 单个铸造和批量铸造都计入统计
+    let mint_msg = ExecuteMsg::Mint { token_id: 1, owner: "user1".to_string(), extension: meta(1), idempotency_key: None };
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), mint_msg).unwrap();
+
+    env.block.height += 5;
+    env.block.time = env.block.time.plus_seconds(30);
+    let batch_mint_msg = ExecuteMsg::BatchMint {
+        mints: (2..=3u64)
+            .map(|token_id| luckee_nft::msg::BatchMintItem {
+                token_id,
+                owner: "user2".to_string(),
+                extension: meta(token_id),
+            })
+            .collect(),
+            idempotency_key: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), batch_mint_msg).unwrap();
+
+    // 销毁不减少累计铸造数量
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::Burn { token_id: 1 }).unwrap();
+
+    let res: luckee_nft::msg::MinterStatsResponse = from_json(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+    assert_eq!(res.minter, "blind_box_contract");
+    assert_eq!(res.total_minted, 3);
+    assert_eq!(res.last_mint_height, Some(env.block.height));
+    assert_eq!(res.last_mint_time, Some(env.block.time.seconds()));
+}
+
+#[test]
+fn test_nonpayable_messages_reject_funds() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    let init_msg = InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    };
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), init_msg).unwrap();
+
+    // 铸造不使用资金，附带资金被拒绝
+    let mint_msg = ExecuteMsg::Mint {
+        token_id: 1,
+        owner: "user1".to_string(),
+        extension: NftMeta {
+            kind: NftKind::Clover,
+            scale_origin: Scale::Tiny,
+            physical_sku: None,
+            crafted_from: None,
+            series_id: "series_1".to_string(),
+            collection_group_id: None,
+            serial_in_series: 1,
+            expires_at: None,
+        },
+        idempotency_key: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &coins(100, "uluckee")), mint_msg.clone())
+        .unwrap_err();
+    assert!(err.to_string().contains("does no accept funds"));
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), mint_msg).unwrap();
+
+    // 管理操作同样拒绝资金
+    let res = execute(deps.as_mut(), env.clone(), mock_info("creator", &coins(1, "uluckee")), ExecuteMsg::Pause {});
+    assert!(res.is_err());
+
+    // 存款接受资金
+    execute(deps.as_mut(), env, mock_info("user1", &coins(100, "uluckee")), ExecuteMsg::Deposit {}).unwrap();
+}
+
+#[test]
+fn test_retag_series_emits_meta_diff() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    let init_msg = InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    };
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), init_msg).unwrap();
+
+    let mint_msg = ExecuteMsg::Mint {
+        token_id: 1,
+        owner: "user1".to_string(),
+        extension: NftMeta {
+            kind: NftKind::Clover,
+            scale_origin: Scale::Tiny,
+            physical_sku: None,
+            crafted_from: None,
+            series_id: "series_old".to_string(),
+            collection_group_id: None,
+            serial_in_series: 1,
+            expires_at: None,
+        },
+        idempotency_key: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), mint_msg).unwrap();
+
+    // 系列重命名为每个 NFT 发出只包含变更字段的元数据变更事件
+    let retag_msg = ExecuteMsg::RetagSeries {
+        from_series: "series_old".to_string(),
+        to_series: "series_new".to_string(),
+        limit: None,
+        cursor: None,
+    };
+    let res = execute(deps.as_mut(), env, mock_info("creator", &[]), retag_msg).unwrap();
+    let event = res.events.iter()
+        .find(|event| event.attributes.iter().any(|attr| attr.key == "action" && attr.value == "meta_update"))
+        .unwrap();
+    let attr = |key: &str| event.attributes.iter().find(|attr| attr.key == key).map(|attr| attr.value.clone());
+    assert_eq!(attr("token_id"), Some("1".to_string()));
+    assert_eq!(attr("changed_fields"), Some("series_id".to_string()));
+    assert_eq!(attr("series_id_before"), Some("series_old".to_string()));
+    assert_eq!(attr("series_id_after"), Some("series_new".to_string()));
+    assert_eq!(attr("kind_before"), None);
+}
+
+#[test]
+fn test_maintenance_notice() {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+
+    let init_msg = InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    };
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), init_msg).unwrap();
+
+    // 未设置公告
+    let res: luckee_nft::msg::MaintenanceInfoResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::MaintenanceInfo {}).unwrap()).unwrap();
+    assert!(!res.active);
+    assert!(!res.paused);
+
+    // 只有合约所有者可以发布公告
+    let ends_at = env.block.time.seconds() + 3600;
+    let notice = luckee_nft::state::MaintenanceNotice {
+        message: "scheduled upgrade".to_string(),
+        ends_at: Some(ends_at),
+    };
+    let msg = ExecuteMsg::SetMaintenanceNotice { notice: Some(notice) };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), msg.clone()).is_err());
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+    let res: luckee_nft::msg::MaintenanceInfoResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::MaintenanceInfo {}).unwrap()).unwrap();
+    assert!(res.active);
+    assert_eq!(res.message, Some("scheduled upgrade".to_string()));
+    assert_eq!(res.ends_at, Some(ends_at));
+
+    // 已结束的公告自动失效
+    env.block.time = env.block.time.plus_seconds(3600);
+    let res: luckee_nft::msg::MaintenanceInfoResponse =
+        from_json(&query(deps.as_ref(), env, QueryMsg::MaintenanceInfo {}).unwrap()).unwrap();
+    assert!(!res.active);
+    assert_eq!(res.message, None);
+}
+
+#[test]
+fn test_logic_matches_on_chain_checks() {
+    use luckee_nft::logic::{self, SynthesisError};
+
+    let inputs = vec![RecipeInput { nft_kind: NftKind::Clover, count: 2 }];
+
+    // 模拟结果与链上价值计算一致
+    assert_eq!(logic::inputs_value(&inputs), 2);
+    assert_eq!(logic::value_delta(&inputs, &NftKind::Firefly), 0);
+    assert_eq!(logic::max_synthesis_times(&inputs, |_| 5), 2);
+    assert_eq!(logic::max_synthesis_times(&[], |_| 5), 0);
+    assert_eq!(
+        logic::missing_inputs(&inputs, |_| 1),
+        vec![RecipeInput { nft_kind: NftKind::Clover, count: 1 }]
+    );
+
+    // 输入校验
+    assert_eq!(logic::check_synthesis_inputs(&[], &inputs), Err(SynthesisError::NoInputs));
+    assert_eq!(
+        logic::check_synthesis_inputs(&[(1, NftKind::Clover), (1, NftKind::Clover)], &inputs),
+        Err(SynthesisError::DuplicateInput { token_id: 1 })
+    );
+    assert_eq!(
+        logic::check_synthesis_inputs(&[(1, NftKind::Clover), (2, NftKind::Firefly)], &inputs),
+        Err(SynthesisError::InsufficientInputs)
+    );
+    assert_eq!(logic::check_synthesis_inputs(&[(1, NftKind::Clover), (2, NftKind::Clover)], &inputs), Ok(()));
+
+    // 链上错误映射
+    assert!(matches!(
+        luckee_nft::ContractError::from(SynthesisError::DuplicateInput { token_id: 7 }),
+        luckee_nft::ContractError::DuplicateInputToken { token_id: 7 }
+    ));
+}
+
+#[test]
+fn test_message_builders_and_event_parsers() {
+    use cosmwasm_std::{CosmosMsg, WasmMsg};
+    use luckee_nft::helpers::{build_mint_msg, build_synthesize_msg, parse_burned_token_id, parse_minted_token_id,
+                              parse_synthesized_token_id};
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+
+    let meta = |serial| NftMeta {
+        kind: NftKind::Clover,
+        scale_origin: Scale::Tiny,
+        physical_sku: None,
+        crafted_from: None,
+        series_id: "series_1".to_string(),
+        collection_group_id: None,
+        serial_in_series: serial,
+        expires_at: None,
+    };
+
+    // 构造的消息与手写的执行消息一致
+    let CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, funds }) =
+        build_mint_msg("luckee", 1, "user1", meta(1)).unwrap()
+    else {
+        panic!("expected wasm execute message");
+    };
+    assert_eq!(contract_addr, "luckee");
+    assert!(funds.is_empty());
+    let mint_msg: ExecuteMsg = from_json(&msg).unwrap();
+
+    // 从响应事件中解析 NFT ID
+    let res = execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), mint_msg).unwrap();
+    assert_eq!(parse_minted_token_id(&res.events), Some(1));
+    assert_eq!(parse_synthesized_token_id(&res.events), None);
+
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+        token_id: 2,
+        owner: "user1".to_string(),
+        extension: meta(2),
+        idempotency_key: None,
+    }).unwrap();
+    let CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) =
+        build_synthesize_msg("luckee", vec![1, 2], NftKind::Firefly, vec![]).unwrap()
+    else {
+        panic!("expected wasm execute message");
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), from_json(&msg).unwrap()).unwrap();
+    assert_eq!(parse_synthesized_token_id(&res.events), Some(3));
+
+    let res = execute(deps.as_mut(), env, mock_info("user1", &[]), ExecuteMsg::Burn { token_id: 3 }).unwrap();
+    assert_eq!(parse_burned_token_id(&res.events), Some(3));
+}
+
+#[test]
+fn test_crafting_cost_follows_recipe_chain() {
+    use luckee_nft::msg::CraftingCostResponse;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+
+    let cost = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, target, from_kind| -> CraftingCostResponse {
+        from_json(&query(deps.as_ref(), mock_env(), QueryMsg::CraftingCost { target, from_kind }).unwrap()).unwrap()
+    };
+
+    // 默认配方：2 → 4 → 20 个四叶草
+    assert_eq!(cost(&deps, NftKind::Firefly, None).required, 2);
+    assert_eq!(cost(&deps, NftKind::CrimsonKoi, None).required, 4);
+    let lamp = cost(&deps, NftKind::MagicalLamp, None);
+    assert_eq!((lamp.required, lamp.syntheses), (20, 16));
+    assert_eq!(cost(&deps, NftKind::MagicalLamp, Some(NftKind::Firefly)).required, 10);
+
+    // 删除流萤配方后，流萤无法由四叶草合成，单独列出
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), ExecuteMsg::RemoveRecipe {
+        target: NftKind::Firefly,
+    }).unwrap();
+    let koi = cost(&deps, NftKind::CrimsonKoi, None);
+    assert_eq!(koi.required, 0);
+    assert_eq!(koi.other_inputs, vec![(NftKind::Firefly, 2)]);
+
+    // 配方循环（赤色锦鲤依赖紫薇帝星，紫薇帝星最终依赖赤色锦鲤）返回错误
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), ExecuteMsg::SetRecipe {
+        target: NftKind::CrimsonKoi,
+        recipe: Recipe {
+            inputs: vec![RecipeInput { nft_kind: NftKind::Polaris, count: 1 }],
+            output: NftKind::CrimsonKoi,
+            cost: None,
+        },
+    }).unwrap();
+    let res = query(deps.as_ref(), env, QueryMsg::CraftingCost { target: NftKind::CrimsonKoi, from_kind: None });
+    assert!(res.unwrap_err().to_string().contains("cycle"));
+}
+
+#[test]
+fn test_recipe_graph() {
+    use luckee_nft::msg::RecipeGraphResponse;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+
+    // 默认配方为单链：每个输入类型只有一个输出
+    let graph: RecipeGraphResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::RecipeGraph {}).unwrap()).unwrap();
+    assert_eq!(graph.recipe_count, 8);
+    assert_eq!(graph.nodes.len(), 8);
+    let clover = graph.nodes.iter().find(|node| node.input == NftKind::Clover).unwrap();
+    assert_eq!(clover.outputs.len(), 1);
+    assert_eq!((clover.outputs[0].output.clone(), clover.outputs[0].count), (NftKind::Firefly, 2));
+
+    // 多输入配方出现在每个输入节点下
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), ExecuteMsg::SetRecipe {
+        target: NftKind::MagicalLamp,
+        recipe: Recipe {
+            inputs: vec![
+                RecipeInput { nft_kind: NftKind::Clover, count: 10 },
+                RecipeInput { nft_kind: NftKind::CrimsonKoi, count: 3 },
+            ],
+            output: NftKind::MagicalLamp,
+            cost: Some(cosmwasm_std::coin(100, "uluckee")),
+        },
+    }).unwrap();
+    let graph: RecipeGraphResponse =
+        from_json(&query(deps.as_ref(), env, QueryMsg::RecipeGraph {}).unwrap()).unwrap();
+    assert_eq!(graph.recipe_count, 8);
+    let clover = graph.nodes.iter().find(|node| node.input == NftKind::Clover).unwrap();
+    let lamp_edge = clover.outputs.iter().find(|edge| edge.output == NftKind::MagicalLamp).unwrap();
+    assert_eq!(lamp_edge.count, 10);
+    assert_eq!(lamp_edge.cost, Some(cosmwasm_std::coin(100, "uluckee")));
+}
+
+#[test]
+fn test_reset_recipes() {
+    use luckee_nft::msg::{AllRecipesResponse, StorageStatsResponse};
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+
+    // 修改一个配方
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), ExecuteMsg::SetRecipe {
+        target: NftKind::Firefly,
+        recipe: Recipe {
+            inputs: vec![RecipeInput { nft_kind: NftKind::Clover, count: 1 }],
+            output: NftKind::Firefly,
+            cost: None,
+        },
+    }).unwrap();
+
+    // 仅合约所有者可重置
+    let reset = |install_defaults| ExecuteMsg::ResetRecipes { install_defaults };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), reset(true)).is_err());
+
+    // 暂停期间也可清空配方
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), ExecuteMsg::Pause {}).unwrap();
+    let res = execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), reset(false)).unwrap();
+    assert!(res.attributes.iter().any(|attr| attr.key == "removed" && attr.value == "8"));
+    assert!(res.attributes.iter().any(|attr| attr.key == "complete" && attr.value == "true"));
+    let recipes: AllRecipesResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::AllRecipes {
+        start_after: None,
+        limit: None,
+    }).unwrap()).unwrap();
+    assert!(recipes.recipes.is_empty());
+    let stats: StorageStatsResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::StorageStats {}).unwrap()).unwrap();
+    assert_eq!(stats.recipes, 0);
+
+    // 重新写入默认配方
+    let res = execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), reset(true)).unwrap();
+    assert!(res.attributes.iter().any(|attr| attr.key == "defaults_installed" && attr.value == "true"));
+    let recipe: luckee_nft::msg::RecipeResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::Recipe {
+        target: NftKind::Firefly,
+    }).unwrap()).unwrap();
+    assert_eq!(recipe.recipe.unwrap().inputs[0].count, 2);
+    let stats: StorageStatsResponse =
+        from_json(&query(deps.as_ref(), env, QueryMsg::StorageStats {}).unwrap()).unwrap();
+    assert_eq!(stats.recipes, 8);
+}
+
+#[test]
+fn test_serial_assigned_from_series_counter() {
+    use luckee_nft::msg::{BatchMintItem, SerialOwnerResponse};
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+
+    // 调用方传入重复的序号
+    let meta = |series: &str| NftMeta {
+        kind: NftKind::Clover,
+        scale_origin: Scale::Tiny,
+        physical_sku: None,
+        crafted_from: None,
+        series_id: series.to_string(),
+        collection_group_id: None,
+        serial_in_series: 1,
+        expires_at: None,
+    };
+    let minter = mock_info("blind_box_contract", &[]);
+    execute(deps.as_mut(), env.clone(), minter.clone(), ExecuteMsg::Mint {
+        token_id: 1,
+        owner: "user1".to_string(),
+        extension: meta("spring2025"),
+        idempotency_key: None,
+    }).unwrap();
+    execute(deps.as_mut(), env.clone(), minter.clone(), ExecuteMsg::BatchMint {
+        mints: vec![
+            BatchMintItem { token_id: 2, owner: "user2".to_string(), extension: meta("spring2025") },
+            BatchMintItem { token_id: 3, owner: "user3".to_string(), extension: meta("summer2025") },
+            BatchMintItem { token_id: 4, owner: "user3".to_string(), extension: meta("spring2025") },
+        ],
+        idempotency_key: None,
+    }).unwrap();
+
+    // 序号由系列计数器依次分配
+    let serial_of = |token_id| -> u64 {
+        let res: TokenMetaResponse =
+            from_json(&query(deps.as_ref(), env.clone(), QueryMsg::TokenMeta { token_id }).unwrap()).unwrap();
+        res.meta.serial_in_series
+    };
+    assert_eq!((serial_of(1), serial_of(2), serial_of(3), serial_of(4)), (1, 2, 1, 3));
+
+    let serial_owner = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, series_id: &str, serial| -> SerialOwnerResponse {
+        from_json(&query(deps.as_ref(), mock_env(), QueryMsg::SerialOwner {
+            series_id: series_id.to_string(),
+            serial,
+        }).unwrap()).unwrap()
+    };
+    let res = serial_owner(&deps, "spring2025", 3);
+    assert_eq!((res.token_id, res.owner.as_deref()), (Some(4), Some("user3")));
+    assert_eq!(serial_owner(&deps, "spring2025", 9).token_id, None);
+
+    // 销毁后序号不再指向该 NFT，序号也不会被复用
+    execute(deps.as_mut(), env.clone(), mock_info("user3", &[]), ExecuteMsg::Burn { token_id: 4 }).unwrap();
+    assert_eq!(serial_owner(&deps, "spring2025", 3).owner, None);
+    execute(deps.as_mut(), env.clone(), minter, ExecuteMsg::Mint {
+        token_id: 5,
+        owner: "user1".to_string(),
+        extension: meta("spring2025"),
+        idempotency_key: None,
+    }).unwrap();
+    assert_eq!(serial_owner(&deps, "spring2025", 4).token_id, Some(5));
+}
+
+#[test]
+fn test_token_by_serial() {
+    use luckee_nft::msg::TokenBySerialResponse;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+
+    for (token_id, owner) in [(7, "user1"), (8, "user2")] {
+        execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+            token_id,
+            owner: owner.to_string(),
+            extension: NftMeta {
+                kind: NftKind::Firefly,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "spring2025".to_string(),
+                collection_group_id: None,
+                serial_in_series: 0,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        }).unwrap();
+    }
+
+    let res: TokenBySerialResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::TokenBySerial {
+        series_id: "spring2025".to_string(),
+        serial: 2,
+    }).unwrap()).unwrap();
+    assert_eq!(res.token_id, 8);
+    assert_eq!(res.owner, "user2");
+    assert_eq!(res.meta.kind, NftKind::Firefly);
+    assert_eq!(res.meta.serial_in_series, 2);
+
+    // 不存在的序号或系列返回错误
+    assert!(query(deps.as_ref(), env.clone(), QueryMsg::TokenBySerial {
+        series_id: "spring2025".to_string(),
+        serial: 3,
+    }).is_err());
+    assert!(query(deps.as_ref(), env, QueryMsg::TokenBySerial {
+        series_id: "winter2025".to_string(),
+        serial: 1,
+    }).is_err());
+}
+
+#[test]
+fn test_reserved_id_range() {
+    use luckee_nft::helpers::parse_synthesized_token_id;
+    use luckee_nft::msg::{BatchMintItem, ReservedIdRangeResponse};
+    use luckee_nft::state::ReservedIdRange;
+    use luckee_nft::ContractError;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+
+    let meta = NftMeta {
+        kind: NftKind::Clover,
+        scale_origin: Scale::Tiny,
+        physical_sku: None,
+        crafted_from: None,
+        series_id: "spring2025".to_string(),
+        collection_group_id: None,
+        serial_in_series: 0,
+        expires_at: None,
+    };
+    // 系列由首个铸造者认领，合约所有者铸造的展品使用单独的系列
+    let mint = |token_id: u64, series_id: &str| ExecuteMsg::Mint {
+        token_id,
+        owner: "user1".to_string(),
+        extension: NftMeta { series_id: series_id.to_string(), ..meta.clone() },
+        idempotency_key: None,
+    };
+    let minter = mock_info("blind_box_contract", &[]);
+    let owner = mock_info("creator", &[]);
+
+    // 设置区间前铸造的 NFT 不受影响
+    execute(deps.as_mut(), env.clone(), minter.clone(), mint(1, "spring2025")).unwrap();
+    execute(deps.as_mut(), env.clone(), minter.clone(), mint(2, "spring2025")).unwrap();
+
+    // 仅合约所有者可以设置，且区间必须有效
+    let set_range = |start, end| ExecuteMsg::SetReservedIdRange { range: Some(ReservedIdRange { start, end }) };
+    assert!(execute(deps.as_mut(), env.clone(), minter.clone(), set_range(1, 50)).is_err());
+    let err = execute(deps.as_mut(), env.clone(), owner.clone(), set_range(50, 1)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidReservedIdRange { .. }));
+    execute(deps.as_mut(), env.clone(), owner.clone(), set_range(1, 50)).unwrap();
+
+    let reserved: ReservedIdRangeResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::ReservedIdRange {}).unwrap()).unwrap();
+    assert_eq!(reserved.range, Some(ReservedIdRange { start: 1, end: 50 }));
+    assert_eq!(reserved.next_auto_token_id, 51);
+
+    // 铸造者不能铸造保留 ID，合约所有者可以（无需铸造者权限）
+    let err = execute(deps.as_mut(), env.clone(), minter.clone(), mint(7, "spring2025")).unwrap_err();
+    assert!(matches!(err, ContractError::ReservedTokenId { token_id: 7, start: 1, end: 50 }));
+    execute(deps.as_mut(), env.clone(), owner.clone(), mint(7, "museum")).unwrap();
+
+    // 合约所有者铸造区间外的 ID 仍需铸造者权限
+    let err = execute(deps.as_mut(), env.clone(), owner.clone(), mint(100, "museum")).unwrap_err();
+    assert!(matches!(err, ContractError::MinterNotAuthorized {}));
+
+    // 批量铸造逐条检查
+    let err = execute(deps.as_mut(), env.clone(), minter.clone(), ExecuteMsg::BatchMint {
+        mints: vec![
+            BatchMintItem { token_id: 60, owner: "user1".to_string(), extension: meta.clone() },
+            BatchMintItem { token_id: 8, owner: "user1".to_string(), extension: meta.clone() },
+        ],
+        idempotency_key: None,
+    }).unwrap_err();
+    assert!(err.to_string().contains("Batch item 1 (token 8)"));
+
+    // 合成产出跳过保留区间
+    let res = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::Synthesize {
+        inputs: vec![1, 2],
+        target: NftKind::Firefly,
+    }).unwrap();
+    assert_eq!(parse_synthesized_token_id(&res.events), Some(51));
+
+    // 取消保留后恢复正常铸造
+    execute(deps.as_mut(), env.clone(), owner, ExecuteMsg::SetReservedIdRange { range: None }).unwrap();
+    execute(deps.as_mut(), env, minter, mint(8, "spring2025")).unwrap();
+}
+
+#[test]
+fn test_synthesis_burn_tax() {
+    use cosmwasm_std::{BankMsg, Coin, CosmosMsg};
+    use luckee_nft::msg::{BurnTaxResponse, BurnTaxStatsResponse, SynthesisPreviewResponse};
+    use luckee_nft::types::BurnTax;
+    use luckee_nft::ContractError;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    for token_id in 1..=5 {
+        execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+            token_id,
+            owner: "user1".to_string(),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "spring2025".to_string(),
+                collection_group_id: None,
+                serial_in_series: 0,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        }).unwrap();
+    }
+    let owner = mock_info("creator", &[]);
+    let set_tax = |tax| ExecuteMsg::SetBurnTax { target: NftKind::Firefly, tax };
+
+    // 仅合约所有者可以设置，零金额无效
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), set_tax(Some(BurnTax::ExtraInput {}))).is_err());
+    let err = execute(deps.as_mut(), env.clone(), owner.clone(), set_tax(Some(BurnTax::NativeFee {
+        amount: Coin::new(0, "uluckee"),
+        sink: None,
+    }))).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidBurnTax { .. }));
+
+    // 额外输入：流萤需要 3 个四叶草
+    execute(deps.as_mut(), env.clone(), owner.clone(), set_tax(Some(BurnTax::ExtraInput {}))).unwrap();
+    let preview: SynthesisPreviewResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::SynthesisPreview {
+        inputs: vec![],
+        target: NftKind::Firefly,
+    }).unwrap()).unwrap();
+    assert_eq!(preview.required_inputs[0].count, 3);
+
+    let synthesize = |inputs: Vec<u64>| ExecuteMsg::Synthesize { inputs, target: NftKind::Firefly };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), synthesize(vec![1, 2])).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientInputTokens {}));
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), synthesize(vec![1, 2, 3])).unwrap();
+
+    // 原生代币：发送至 sink 地址
+    execute(deps.as_mut(), env.clone(), owner, set_tax(Some(BurnTax::NativeFee {
+        amount: Coin::new(500, "uluckee"),
+        sink: Some("community_pool".to_string()),
+    }))).unwrap();
+    let tax: BurnTaxResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::BurnTax { target: NftKind::Firefly }).unwrap()).unwrap();
+    assert!(matches!(tax.tax, Some(BurnTax::NativeFee { .. })));
+
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), synthesize(vec![4, 5])).is_err());
+    let res = execute(deps.as_mut(), env.clone(), mock_info("user1", &coins(500, "uluckee")), synthesize(vec![4, 5])).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+        to_address: "community_pool".to_string(),
+        amount: coins(500, "uluckee"),
+    }));
+
+    // 累计统计
+    let stats: BurnTaxStatsResponse =
+        from_json(&query(deps.as_ref(), env, QueryMsg::BurnTaxStats {}).unwrap()).unwrap();
+    assert_eq!((stats.tokens_burned, stats.value_burned), (1, 1));
+    assert_eq!(stats.fees_burned, coins(500, "uluckee"));
+}
+
+#[test]
+fn test_owned_at() {
+    use luckee_nft::msg::OwnedAtResponse;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+        token_id: 1,
+        owner: "user1".to_string(),
+        extension: NftMeta {
+            kind: NftKind::Clover,
+            scale_origin: Scale::Tiny,
+            physical_sku: None,
+            crafted_from: None,
+            series_id: "spring2025".to_string(),
+            collection_group_id: None,
+            serial_in_series: 0,
+            expires_at: None,
+        },
+        idempotency_key: None,
+    }).unwrap();
+
+    // 铸造时记录取得高度
+    let res: OwnedAtResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::OwnedAt { token_id: 1 }).unwrap()).unwrap();
+    assert_eq!(res.owner, "user1");
+    assert_eq!(res.acquired.unwrap().height, env.block.height);
+
+    // 转移后更新为新所有者的取得高度
+    let mut later = env.clone();
+    later.block.height += 100;
+    later.block.time = later.block.time.plus_seconds(600);
+    execute(deps.as_mut(), later.clone(), mock_info("user1", &[]), ExecuteMsg::TransferNft {
+        recipient: "user2".to_string(),
+        token_id: 1,
+    }).unwrap();
+    let res: OwnedAtResponse =
+        from_json(&query(deps.as_ref(), later.clone(), QueryMsg::OwnedAt { token_id: 1 }).unwrap()).unwrap();
+    assert_eq!(res.owner, "user2");
+    let acquired = res.acquired.unwrap();
+    assert_eq!(acquired.height, later.block.height);
+    assert_eq!(acquired.time, later.block.time.seconds());
+
+    // 不存在的 NFT 返回错误
+    assert!(query(deps.as_ref(), later, QueryMsg::OwnedAt { token_id: 2 }).is_err());
+}
+
+#[test]
+fn test_crafting_royalty() {
+    use cosmwasm_std::{BankMsg, Coin, CosmosMsg};
+    use luckee_nft::msg::SeriesRoyaltiesResponse;
+    use luckee_nft::state::CraftingRoyalty;
+    use luckee_nft::ContractError;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+
+    // 铸造者创建 spring2025 系列，合约所有者创建 museum 系列
+    for (token_id, minter, series_id) in [
+        (1, "blind_box_contract", "spring2025"),
+        (2, "blind_box_contract", "spring2025"),
+        (3, "creator", "museum"),
+        (4, "creator", "museum"),
+    ] {
+        execute(deps.as_mut(), env.clone(), mock_info(minter, &[]), ExecuteMsg::Mint {
+            token_id,
+            owner: "user1".to_string(),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: series_id.to_string(),
+                collection_group_id: None,
+                serial_in_series: 0,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        }).unwrap();
+    }
+    let owner = mock_info("creator", &[]);
+    let set_royalty = |min_rarity, amount| ExecuteMsg::SetCraftingRoyalty {
+        royalty: Some(CraftingRoyalty { min_rarity, amount: Coin::new(amount, "uluckee") }),
+    };
+
+    // 仅合约所有者可以设置，零金额和超出范围的稀有度无效
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), set_royalty(1, 301)).is_err());
+    let err = execute(deps.as_mut(), env.clone(), owner.clone(), set_royalty(1, 0)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidCraftingRoyalty { .. }));
+    let err = execute(deps.as_mut(), env.clone(), owner.clone(), set_royalty(9, 301)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidCraftingRoyalty { .. }));
+
+    // 流萤达到门槛：版税按输入均分给两个系列的创建者，余数归第一个输入
+    execute(deps.as_mut(), env.clone(), owner.clone(), set_royalty(1, 301)).unwrap();
+    let synthesize = |inputs: Vec<u64>| ExecuteMsg::Synthesize { inputs, target: NftKind::Firefly };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), synthesize(vec![1, 3])).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientDeposit { .. }));
+    let res = execute(deps.as_mut(), env.clone(), mock_info("user1", &coins(301, "uluckee")), synthesize(vec![1, 3])).unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+        to_address: "blind_box_contract".to_string(),
+        amount: coins(151, "uluckee"),
+    }));
+    assert_eq!(res.messages[1].msg, CosmosMsg::Bank(BankMsg::Send {
+        to_address: "creator".to_string(),
+        amount: coins(150, "uluckee"),
+    }));
+
+    let earned: SeriesRoyaltiesResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::SeriesRoyalties {
+        series_id: "spring2025".to_string(),
+    }).unwrap()).unwrap();
+    assert_eq!(earned.creator.as_deref(), Some("blind_box_contract"));
+    assert_eq!(earned.earned, coins(151, "uluckee"));
+
+    // 低于门槛的合成不收取版税
+    execute(deps.as_mut(), env.clone(), owner, set_royalty(2, 301)).unwrap();
+    let res = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), synthesize(vec![2, 4])).unwrap();
+    assert!(res.messages.is_empty());
+    let earned: SeriesRoyaltiesResponse = from_json(&query(deps.as_ref(), env, QueryMsg::SeriesRoyalties {
+        series_id: "museum".to_string(),
+    }).unwrap()).unwrap();
+    assert_eq!(earned.earned, coins(150, "uluckee"));
+}
+
+#[test]
+fn test_verify_ownership() {
+    use luckee_nft::msg::VerifyOwnershipResponse;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    for (token_id, owner) in [(1, "user1"), (2, "user1"), (3, "user2")] {
+        execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+            token_id,
+            owner: owner.to_string(),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "spring2025".to_string(),
+                collection_group_id: None,
+                serial_in_series: 0,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        }).unwrap();
+    }
+    let verify = |token_ids: Vec<u64>| -> VerifyOwnershipResponse {
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::VerifyOwnership {
+            owner: "user1".to_string(),
+            token_ids,
+        }).unwrap()).unwrap()
+    };
+
+    let res = verify(vec![1, 2]);
+    assert_eq!(res.owned, vec![true, true]);
+    assert!(res.all_owned);
+
+    // 他人持有和不存在的 NFT 视为未持有
+    let res = verify(vec![2, 3, 99]);
+    assert_eq!(res.owned, vec![true, false, false]);
+    assert!(!res.all_owned);
+
+    // 超出单次上限返回错误
+    assert!(query(deps.as_ref(), env.clone(), QueryMsg::VerifyOwnership {
+        owner: "user1".to_string(),
+        token_ids: (1..=101).collect(),
+    }).is_err());
+}
+
+#[test]
+fn test_custody_tags() {
+    use luckee_nft::msg::{CustodyTagItem, CustodyTagResponse, CustodyTokensResponse};
+    use luckee_nft::ContractError;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    for token_id in 1..=3 {
+        execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+            token_id,
+            owner: "exchange".to_string(),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "spring2025".to_string(),
+                collection_group_id: None,
+                serial_in_series: 0,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        }).unwrap();
+    }
+    let hash = "AB".repeat(32);
+    let tag = |token_id, account_hash: Option<&str>| CustodyTagItem {
+        token_id,
+        account_hash: account_hash.map(|hash| hash.to_string()),
+    };
+
+    // 只能标记自己持有的 NFT，哈希须为 64 位十六进制
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::TagCustody {
+        tags: vec![tag(1, Some(&hash))],
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::BatchItemFailed { index: 0, token_id: 1, .. }));
+    let err = execute(deps.as_mut(), env.clone(), mock_info("exchange", &[]), ExecuteMsg::TagCustody {
+        tags: vec![tag(1, Some("not-a-hash"))],
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::BatchItemFailed { index: 0, .. }));
+
+    execute(deps.as_mut(), env.clone(), mock_info("exchange", &[]), ExecuteMsg::TagCustody {
+        tags: vec![tag(1, Some(&hash)), tag(2, Some(&hash)), tag(3, Some(&hash))],
+    }).unwrap();
+    let res: CustodyTagResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::CustodyTag { token_id: 1 }).unwrap()).unwrap();
+    let stored = res.tag.unwrap();
+    assert_eq!(stored.custodian.as_str(), "exchange");
+    assert_eq!(stored.account_hash, hash.to_ascii_lowercase());
+
+    // 清除标记，转出的 NFT 自动清除标记
+    execute(deps.as_mut(), env.clone(), mock_info("exchange", &[]), ExecuteMsg::TagCustody {
+        tags: vec![tag(2, None)],
+    }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("exchange", &[]), ExecuteMsg::TransferNft {
+        recipient: "user1".to_string(),
+        token_id: 3,
+    }).unwrap();
+    let res: CustodyTagResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::CustodyTag { token_id: 3 }).unwrap()).unwrap();
+    assert!(res.tag.is_none());
+
+    let res: CustodyTokensResponse = from_json(&query(deps.as_ref(), env, QueryMsg::CustodyTokens {
+        custodian: "exchange".to_string(),
+        start_after: None,
+        limit: None,
+    }).unwrap()).unwrap();
+    assert_eq!(res.tokens.len(), 1);
+    assert_eq!(res.tokens[0].token_id, 1);
+}
+
+#[test]
+fn test_voting_power() {
+    use luckee_nft::msg::VotingPowerResponse;
+
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let start = env.block.height;
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    for (token_id, kind) in [(1u64, NftKind::Clover), (2, NftKind::Firefly)] {
+        execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+            token_id,
+            owner: "user1".to_string(),
+            extension: NftMeta {
+                kind,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "spring2025".to_string(),
+                collection_group_id: None,
+                serial_in_series: 0,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        }).unwrap();
+    }
+
+    // 在之后的高度转出流萤
+    env.block.height = start + 5;
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::TransferNft {
+        recipient: "user2".to_string(),
+        token_id: 2,
+    }).unwrap();
+    env.block.height = start + 10;
+
+    let power = |env: &cosmwasm_std::Env, owner: &str, at_snapshot: Option<u64>| -> VotingPowerResponse {
+        let msg = QueryMsg::VotingPower { owner: owner.to_string(), at_snapshot };
+        from_json(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap()
+    };
+
+    // 当前投票权为持有 NFT 的兑换价值之和
+    let res = power(&env, "user1", None);
+    assert_eq!((res.power, res.height), (1, None));
+    assert_eq!(power(&env, "user2", None).power, 2);
+
+    // 快照取该高度开始时的投票权，转移所在高度之前的投票权不受影响
+    assert_eq!(power(&env, "user1", Some(start + 5)).power, 3);
+    assert_eq!(power(&env, "user2", Some(start + 5)).power, 0);
+    let res = power(&env, "user1", Some(start + 6));
+    assert_eq!((res.power, res.height), (1, Some(start + 6)));
+    assert_eq!(power(&env, "user2", Some(start + 6)).power, 2);
+
+    // 不早于记录起始高度或晚于当前高度的快照不可查询
+    let msg = QueryMsg::VotingPower { owner: "user1".to_string(), at_snapshot: Some(start) };
+    assert!(query(deps.as_ref(), env.clone(), msg).is_err());
+    let msg = QueryMsg::VotingPower { owner: "user1".to_string(), at_snapshot: Some(start + 11) };
+    assert!(query(deps.as_ref(), env, msg).is_err());
+}
+
+#[test]
+fn test_delegated_voting_power() {
+    use luckee_nft::msg::{DelegatedPowerResponse, DelegationResponse};
+    use luckee_nft::ContractError;
+
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let start = env.block.height;
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    let mint = |token_id: u64, kind: NftKind| ExecuteMsg::Mint {
+        token_id,
+        owner: "user1".to_string(),
+        extension: NftMeta {
+            kind,
+            scale_origin: Scale::Tiny,
+            physical_sku: None,
+            crafted_from: None,
+            series_id: "spring2025".to_string(),
+            collection_group_id: None,
+            serial_in_series: 0,
+            expires_at: None,
+        },
+        idempotency_key: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), mint(1, NftKind::Firefly)).unwrap();
+
+    // 不能委托给自己，未委托时不能取消
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::Delegate {
+        to: "user1".to_string(),
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidDelegation { .. }));
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::Undelegate {}).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidDelegation { .. }));
+
+    // 委托后受托人获得当前投票权，之后铸造和转出实时调整
+    env.block.height = start + 1;
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::Delegate {
+        to: "dao_delegate".to_string(),
+    }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), mint(2, NftKind::CrimsonKoi)).unwrap();
+    env.block.height = start + 2;
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::TransferNft {
+        recipient: "user2".to_string(),
+        token_id: 1,
+    }).unwrap();
+    env.block.height = start + 3;
+
+    let delegated = |deps: cosmwasm_std::Deps, env: &cosmwasm_std::Env, delegate: &str, at_snapshot: Option<u64>| -> u64 {
+        let msg = QueryMsg::DelegatedPower { delegate: delegate.to_string(), at_snapshot };
+        let res: DelegatedPowerResponse = from_json(&query(deps, env.clone(), msg).unwrap()).unwrap();
+        res.power
+    };
+    assert_eq!(delegated(deps.as_ref(), &env, "dao_delegate", None), 4);
+    assert_eq!(delegated(deps.as_ref(), &env, "dao_delegate", Some(start + 1)), 0);
+    assert_eq!(delegated(deps.as_ref(), &env, "dao_delegate", Some(start + 2)), 6);
+    let res: DelegationResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::Delegation {
+        delegator: "user1".to_string(),
+    }).unwrap()).unwrap();
+    assert_eq!(res.delegate.as_deref(), Some("dao_delegate"));
+
+    // 改为委托给新的受托人，投票权随之转移
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::Delegate {
+        to: "other_delegate".to_string(),
+    }).unwrap();
+    assert_eq!(delegated(deps.as_ref(), &env, "dao_delegate", None), 0);
+    assert_eq!(delegated(deps.as_ref(), &env, "other_delegate", None), 4);
+
+    // 取消委托后受托人不再获得投票权，委托人自身投票权不变
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::Undelegate {}).unwrap();
+    assert_eq!(delegated(deps.as_ref(), &env, "other_delegate", None), 0);
+    let res: luckee_nft::msg::VotingPowerResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::VotingPower {
+        owner: "user1".to_string(),
+        at_snapshot: None,
+    }).unwrap()).unwrap();
+    assert_eq!(res.power, 4);
+}
+
+#[test]
+fn test_partner_collection_synthesis() {
+    use cosmwasm_std::{Binary, CosmosMsg, WasmMsg};
+    use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+    use luckee_nft::msg::{PartnerCollectionsResponse, PartnerDepositResponse, PartnerInput};
+    use luckee_nft::ContractError;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    for token_id in 1..=2 {
+        execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+            token_id,
+            owner: "user1".to_string(),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "spring2025".to_string(),
+                collection_group_id: None,
+                serial_in_series: 0,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        }).unwrap();
+    }
+    let receive = |token_id: &str| ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+        sender: "user1".to_string(),
+        token_id: token_id.to_string(),
+        msg: Binary::default(),
+    });
+    let partner_input = |token_id: &str| PartnerInput {
+        collection: "partner_collection".to_string(),
+        token_id: token_id.to_string(),
+    };
+
+    // 仅合约所有者可以登记合作集合，未登记的集合不能存入
+    let set_partner = ExecuteMsg::SetPartnerCollection {
+        collection: "partner_collection".to_string(),
+        kind: Some(NftKind::Clover),
+    };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), set_partner.clone()).is_err());
+    let err = execute(deps.as_mut(), env.clone(), mock_info("partner_collection", &[]), receive("7")).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidPartnerNft { .. }));
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), set_partner).unwrap();
+    let collections: PartnerCollectionsResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::PartnerCollections {}).unwrap()).unwrap();
+    assert_eq!(collections.collections.len(), 1);
+    assert_eq!(collections.collections[0].kind, NftKind::Clover);
+
+    // 存入两个合作集合 NFT，只有合作集合合约的回调有效
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), receive("7")).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidPartnerNft { .. }));
+    execute(deps.as_mut(), env.clone(), mock_info("partner_collection", &[]), receive("7")).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("partner_collection", &[]), receive("8")).unwrap();
+
+    // 配方只需要 2 个四叶草，多余的合作集合输入被拒绝；他人存入的 NFT 不能使用
+    let synthesize = |partner_inputs: Vec<PartnerInput>| ExecuteMsg::SynthesizeWithPartners {
+        inputs: vec![1],
+        partner_inputs,
+        target: NftKind::Firefly,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]),
+        synthesize(vec![partner_input("7"), partner_input("8")])).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidPartnerNft { .. }));
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user2", &[]),
+        synthesize(vec![partner_input("7")])).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidPartnerNft { .. }));
+
+    // 1 个本合约四叶草 + 1 个合作集合 NFT 合成流萤，外部 NFT 被销毁
+    let res = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]),
+        synthesize(vec![partner_input("7")])).unwrap();
+    assert!(res.messages.iter().any(|sub| sub.msg == CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "partner_collection".to_string(),
+        msg: cosmwasm_std::to_json_binary(&Cw721ExecuteMsg::Burn { token_id: "7".to_string() }).unwrap(),
+        funds: vec![],
+    })));
+    let deposit: PartnerDepositResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::PartnerDeposit {
+        collection: "partner_collection".to_string(),
+        token_id: "7".to_string(),
+    }).unwrap()).unwrap();
+    assert_eq!(deposit.depositor, None);
+
+    // 未使用的 NFT 只能由存入者取回
+    let withdraw = ExecuteMsg::WithdrawPartnerNft {
+        collection: "partner_collection".to_string(),
+        token_id: "8".to_string(),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user2", &[]), withdraw.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    let res = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), withdraw).unwrap();
+    assert_eq!(res.messages.len(), 1);
+}
+
+#[test]
+fn test_validate_mint() {
+    use luckee_nft::msg::MintValidationResponse;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    let extension = NftMeta {
+        kind: NftKind::Clover,
+        scale_origin: Scale::Tiny,
+        physical_sku: None,
+        crafted_from: None,
+        series_id: "spring2025".to_string(),
+        collection_group_id: None,
+        serial_in_series: 0,
+        expires_at: None,
+    };
+    let validate = |deps: cosmwasm_std::Deps, token_id: u64, owner: &str, extension: NftMeta| -> MintValidationResponse {
+        from_json(&query(deps, mock_env(), QueryMsg::ValidateMint {
+            token_id,
+            owner: owner.to_string(),
+            extension,
+        }).unwrap()).unwrap()
+    };
+
+    // 通过校验且不写入任何状态
+    let res = validate(deps.as_ref(), 1, "user1", extension.clone());
+    assert!(res.valid);
+    assert_eq!(res.error, None);
+    let res = validate(deps.as_ref(), 1, "user1", extension.clone());
+    assert!(res.valid);
+
+    // ID 已存在
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+        token_id: 1,
+        owner: "user1".to_string(),
+        extension: extension.clone(),
+        idempotency_key: None,
+    }).unwrap();
+    let res = validate(deps.as_ref(), 1, "user1", extension.clone());
+    assert!(!res.valid);
+    assert_eq!(res.error.as_deref(), Some("Token already exists"));
+
+    // 地址格式和系列ID格式
+    assert!(!validate(deps.as_ref(), 2, "", extension.clone()).valid);
+    let mut bad_series = extension.clone();
+    bad_series.series_id = "".to_string();
+    assert!(!validate(deps.as_ref(), 2, "user1", bad_series).valid);
+
+    // 已过期
+    let mut expired = extension.clone();
+    expired.expires_at = Some(env.block.time.seconds());
+    assert!(!validate(deps.as_ref(), 2, "user1", expired).valid);
+
+    // 合约暂停
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), ExecuteMsg::Pause {}).unwrap();
+    let res = validate(deps.as_ref(), 2, "user1", extension);
+    assert!(!res.valid);
+}
+
+#[test]
+fn test_validate_batch_mint() {
+    use luckee_nft::msg::{BatchMintItem, ValidateBatchMintResponse};
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    let item = |token_id: u64, owner: &str, series_id: &str| BatchMintItem {
+        token_id,
+        owner: owner.to_string(),
+        extension: NftMeta {
+            kind: NftKind::Clover,
+            scale_origin: Scale::Tiny,
+            physical_sku: None,
+            crafted_from: None,
+            series_id: series_id.to_string(),
+            collection_group_id: None,
+            serial_in_series: 0,
+            expires_at: None,
+        },
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::BatchMint {
+        mints: vec![item(1, "user1", "spring2025")],
+        idempotency_key: None,
+    }).unwrap();
+
+    // 已存在的 ID、批内重复的 ID、无效地址和无效系列ID逐条报告
+    let res: ValidateBatchMintResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::ValidateBatchMint {
+        mints: vec![
+            item(1, "user1", "spring2025"),
+            item(2, "user2", "spring2025"),
+            item(2, "user3", "spring2025"),
+            item(3, "", "spring2025"),
+            item(4, "user4", "bad series!"),
+            item(5, "user5", "spring2025"),
+        ],
+    }).unwrap()).unwrap();
+    let valid: Vec<bool> = res.results.iter().map(|result| result.valid).collect();
+    assert_eq!(valid, vec![false, true, false, false, false, true]);
+    assert!(!res.all_valid);
+    assert_eq!(res.results[2].token_id, 2);
+    assert_eq!(res.results[2].error.as_deref(), Some("Token already exists"));
+    assert!(res.results[1].error.is_none());
+
+    // 全部通过
+    let res: ValidateBatchMintResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::ValidateBatchMint {
+        mints: vec![item(2, "user2", "spring2025"), item(3, "user3", "summer2025")],
+    }).unwrap()).unwrap();
+    assert!(res.all_valid);
+
+    // 超过批量上限
+    let mints = (10..=110).map(|token_id| item(token_id, "user1", "spring2025")).collect();
+    assert!(query(deps.as_ref(), env, QueryMsg::ValidateBatchMint { mints }).is_err());
+}
+
+#[test]
+fn test_mint_idempotency_key() {
+    use luckee_nft::msg::BatchMintItem;
+    use luckee_nft::state::TOTAL_SUPPLY;
+    use luckee_nft::ContractError;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    let meta = NftMeta {
+        kind: NftKind::Clover,
+        scale_origin: Scale::Tiny,
+        physical_sku: None,
+        crafted_from: None,
+        series_id: "spring2025".to_string(),
+        collection_group_id: None,
+        serial_in_series: 0,
+        expires_at: None,
+    };
+    let mint = |token_id: u64, key: Option<&str>| ExecuteMsg::Mint {
+        token_id,
+        owner: "user1".to_string(),
+        extension: meta.clone(),
+        idempotency_key: key.map(|key| key.to_string()),
+    };
+    let minter = mock_info("blind_box_contract", &[]);
+
+    // 重试时换用了下一个空闲 ID，仍返回首次铸造的结果而不再铸造
+    execute(deps.as_mut(), env.clone(), minter.clone(), mint(1, Some("order-1"))).unwrap();
+    let res = execute(deps.as_mut(), env.clone(), minter.clone(), mint(2, Some("order-1"))).unwrap();
+    assert!(res.attributes.iter().any(|attr| attr.key == "replayed" && attr.value == "true"));
+    assert!(res.attributes.iter().any(|attr| attr.key == "token_ids" && attr.value == "1"));
+    assert_eq!(TOTAL_SUPPLY.load(&deps.storage).unwrap(), 1);
+    // 重放的响应与首次铸造形式相同，铸造事件附带重放标记
+    assert!(res.attributes.iter().any(|attr| attr.key == "token_id" && attr.value == "1"));
+    let event = res.events.iter().find(|event| event.attributes.iter().any(|attr| attr.key == "action" && attr.value == "mint")).unwrap();
+    assert!(event.attributes.iter().any(|attr| attr.key == "token_id" && attr.value == "1"));
+    assert!(event.attributes.iter().any(|attr| attr.key == "total_supply" && attr.value == "1"));
+    assert!(event.attributes.iter().any(|attr| attr.key == "replayed" && attr.value == "true"));
+
+    // 未提供幂等键时照常铸造，新的幂等键照常铸造
+    execute(deps.as_mut(), env.clone(), minter.clone(), mint(2, None)).unwrap();
+    execute(deps.as_mut(), env.clone(), minter.clone(), mint(3, Some("order-2"))).unwrap();
+    assert_eq!(TOTAL_SUPPLY.load(&deps.storage).unwrap(), 3);
+
+    // 空幂等键无效
+    let err = execute(deps.as_mut(), env.clone(), minter.clone(), mint(4, Some(""))).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidIdempotencyKey { .. }));
+
+    // 批量铸造
+    let batch = |token_ids: Vec<u64>| ExecuteMsg::BatchMint {
+        mints: token_ids.into_iter().map(|token_id| BatchMintItem {
+            token_id,
+            owner: "user2".to_string(),
+            extension: meta.clone(),
+        }).collect(),
+        idempotency_key: Some("airdrop-1".to_string()),
+    };
+    execute(deps.as_mut(), env.clone(), minter.clone(), batch(vec![10, 11])).unwrap();
+    let res = execute(deps.as_mut(), env.clone(), minter.clone(), batch(vec![12, 13])).unwrap();
+    assert!(res.attributes.iter().any(|attr| attr.key == "token_ids" && attr.value == "10,11"));
+    assert!(res.attributes.iter().any(|attr| attr.key == "count" && attr.value == "2"));
+    assert_eq!(TOTAL_SUPPLY.load(&deps.storage).unwrap(), 5);
+
+    // 幂等键不能在单个铸造与批量铸造之间互相重放
+    let err = execute(deps.as_mut(), env.clone(), minter.clone(), mint(14, Some("airdrop-1"))).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidIdempotencyKey { .. }));
+    let err = execute(deps.as_mut(), env.clone(), minter, ExecuteMsg::BatchMint {
+        mints: vec![BatchMintItem { token_id: 14, owner: "user2".to_string(), extension: meta.clone() }],
+        idempotency_key: Some("order-1".to_string()),
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidIdempotencyKey { .. }));
+
+    // 幂等键按铸造者隔离
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), ExecuteMsg::SetMinter {
+        minter: "partner_minter".to_string(),
+        allowed: true,
+    }).unwrap();
+    execute(deps.as_mut(), env, mock_info("partner_minter", &[]), mint(20, Some("order-1"))).unwrap();
+    assert_eq!(TOTAL_SUPPLY.load(&deps.storage).unwrap(), 6);
+}
+
+#[test]
+fn test_event_verbosity() {
+    use luckee_nft::msg::{BatchMintItem, EventVerbosityResponse};
+    use luckee_nft::types::EventVerbosity;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    let batch = |token_ids: Vec<u64>| ExecuteMsg::BatchMint {
+        mints: token_ids.into_iter().map(|token_id| BatchMintItem {
+            token_id,
+            owner: "user1".to_string(),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "spring2025".to_string(),
+                collection_group_id: None,
+                serial_in_series: 0,
+                expires_at: None,
+            },
+        }).collect(),
+        idempotency_key: None,
+    };
+    let synthesize = |inputs: Vec<u64>| ExecuteMsg::Synthesize { inputs, target: NftKind::Firefly };
+    let minter = mock_info("blind_box_contract", &[]);
+
+    // 默认发出逐个铸造事件和合成输入列表
+    let res = execute(deps.as_mut(), env.clone(), minter.clone(), batch(vec![1, 2, 3, 4])).unwrap();
+    assert_eq!(res.events.len(), 5);
+    let res = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), synthesize(vec![1, 2])).unwrap();
+    assert!(res.events[0].attributes.iter().any(|attr| attr.key == "inputs" && attr.value == "1,2"));
+
+    // 仅合约所有者可以设置
+    let set_minimal = ExecuteMsg::SetEventVerbosity { verbosity: EventVerbosity::Minimal };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), set_minimal.clone()).is_err());
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), set_minimal).unwrap();
+    let res: EventVerbosityResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::EventVerbosity {}).unwrap()).unwrap();
+    assert_eq!(res.verbosity, EventVerbosity::Minimal);
+
+    // Minimal 时只保留汇总事件
+    let res = execute(deps.as_mut(), env.clone(), minter, batch(vec![5, 6, 7])).unwrap();
+    assert_eq!(res.events.len(), 1);
+    let res = execute(deps.as_mut(), env, mock_info("user1", &[]), synthesize(vec![3, 4])).unwrap();
+    assert!(!res.events[0].attributes.iter().any(|attr| attr.key == "inputs"));
+}
+
+#[test]
+fn test_kind_info_queries() {
+    use luckee_nft::msg::{KindInfoResponse, KindListResponse};
+
+    let deps = mock_dependencies();
+    let info: KindInfoResponse =
+        from_json(&query(deps.as_ref(), mock_env(), QueryMsg::KindInfo { kind: NftKind::MagicalLamp }).unwrap()).unwrap();
+    assert_eq!(info.rarity_level, 3);
+    assert_eq!(info.rarity_name, "Epic");
+    assert_eq!(info.exchange_value, 20);
+
+    let list: KindListResponse = from_json(&query(deps.as_ref(), mock_env(), QueryMsg::KindList {}).unwrap()).unwrap();
+    assert_eq!(list.kinds.len(), 9);
+    assert_eq!(list.kinds[0].kind, NftKind::Clover);
+    assert_eq!(list.kinds[8].kind, NftKind::Genesis);
+    for (index, info) in list.kinds.iter().enumerate() {
+        assert_eq!(info.rarity_level as usize, index);
+        assert_eq!(info.exchange_value, info.kind.exchange_value());
+    }
+}
+
+#[test]
+fn test_scale_info_query() {
+    use luckee_nft::msg::ScaleInfoResponse;
+
+    let deps = mock_dependencies();
+    let res: ScaleInfoResponse = from_json(&query(deps.as_ref(), mock_env(), QueryMsg::ScaleInfo {}).unwrap()).unwrap();
+    assert_eq!(res.scales.len(), 5);
+    assert_eq!(res.scales[0].scale, Scale::Tiny);
+    assert_eq!(res.scales[0].first_prize, NftKind::CrimsonKoi);
+    assert_eq!(res.scales[4].first_prize, NftKind::Polaris);
+    assert!(res.scales.iter().all(|info| info.first_prize == info.scale.first_prize_nft()));
+}
+
+#[test]
+fn test_migrate_guard() {
+    use luckee_nft::ContractError;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    let msg = |allow_downgrade| MigrateMsg { allow_downgrade };
+
+    // 同版本重复迁移允许，迁移事件报告版本和索引状态
+    let res = migrate(deps.as_mut(), env.clone(), msg(false)).unwrap();
+    let event = res.events.iter()
+        .find(|event| event.attributes.iter().any(|attr| attr.key == "action" && attr.value == "migrate"))
+        .unwrap();
+    let attr = |key: &str| event.attributes.iter().find(|attr| attr.key == key).unwrap().value.clone();
+    assert_eq!(attr("previous_version"), env!("CARGO_PKG_VERSION"));
+    assert_eq!(attr("new_version"), env!("CARGO_PKG_VERSION"));
+    assert_eq!(attr("transforms"), "none");
+    assert_eq!(attr("index_phase"), "done");
+    assert_eq!(attr("index_ready"), "true");
+
+    // 已存储的版本更新时需要显式允许降级
+    cw2::set_contract_version(&mut deps.storage, "crates.io:luckee_nft", "99.0.0").unwrap();
+    let err = migrate(deps.as_mut(), env.clone(), msg(false)).unwrap_err();
+    assert!(matches!(err, ContractError::MigrationDowngrade { .. }));
+    migrate(deps.as_mut(), env.clone(), msg(true)).unwrap();
+
+    // 其他合约不能迁移到本合约
+    cw2::set_contract_version(&mut deps.storage, "crates.io:other_contract", "0.1.0").unwrap();
+    let err = migrate(deps.as_mut(), env.clone(), msg(true)).unwrap_err();
+    assert!(matches!(err, ContractError::MigrationContractMismatch { .. }));
+
+    // 无法解析的版本号
+    cw2::set_contract_version(&mut deps.storage, "crates.io:luckee_nft", "latest").unwrap();
+    let err = migrate(deps.as_mut(), env, msg(false)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidContractVersion { .. }));
+}
+
+#[test]
+fn test_burn_cooldown() {
+    use luckee_nft::msg::{BatchMintItem, BurnRequestResponse};
+    use luckee_nft::ContractError;
+
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    let mints = [(1, NftKind::Genesis), (2, NftKind::Genesis), (3, NftKind::Clover)];
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::BatchMint {
+        mints: mints.iter().map(|(token_id, kind)| BatchMintItem {
+            token_id: *token_id,
+            owner: "user1".to_string(),
+            extension: NftMeta {
+                kind: kind.clone(),
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "spring2025".to_string(),
+                collection_group_id: None,
+                serial_in_series: 0,
+                expires_at: None,
+            },
+        }).collect(),
+        idempotency_key: None,
+    }).unwrap();
+    let user = mock_info("user1", &[]);
+
+    // 仅合约所有者可以设置，保护 Sage 及以上的类型
+    let set_cooldown = ExecuteMsg::SetBurnCooldown { min_rarity_level: Some(5), delay: 3600 };
+    assert!(execute(deps.as_mut(), env.clone(), user.clone(), set_cooldown.clone()).is_err());
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), set_cooldown).unwrap();
+
+    // 受保护类型不能直接销毁，低稀有度类型不受影响
+    let err = execute(deps.as_mut(), env.clone(), user.clone(), ExecuteMsg::Burn { token_id: 1 }).unwrap_err();
+    assert!(matches!(err, ContractError::BurnRequestRequired { .. }));
+    let err = execute(deps.as_mut(), env.clone(), user.clone(), ExecuteMsg::RequestBurn { token_id: 3 }).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidBurnRequest { .. }));
+    execute(deps.as_mut(), env.clone(), user.clone(), ExecuteMsg::Burn { token_id: 3 }).unwrap();
+
+    // 冷却期结束前不能确认
+    execute(deps.as_mut(), env.clone(), user.clone(), ExecuteMsg::RequestBurn { token_id: 1 }).unwrap();
+    let res: BurnRequestResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::BurnRequest { token_id: 1 }).unwrap()).unwrap();
+    assert_eq!(res.request.unwrap().confirm_after, env.block.time.seconds() + 3600);
+    let err = execute(deps.as_mut(), env.clone(), user.clone(), ExecuteMsg::ConfirmBurn { token_id: 1 }).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidBurnRequest { .. }));
+
+    // 所有者可以取消申请
+    execute(deps.as_mut(), env.clone(), user.clone(), ExecuteMsg::RequestBurn { token_id: 2 }).unwrap();
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("attacker", &[]), ExecuteMsg::CancelBurnRequest { token_id: 2 }).is_err());
+    execute(deps.as_mut(), env.clone(), user.clone(), ExecuteMsg::CancelBurnRequest { token_id: 2 }).unwrap();
+    env.block.time = env.block.time.plus_seconds(3600);
+    let err = execute(deps.as_mut(), env.clone(), user.clone(), ExecuteMsg::ConfirmBurn { token_id: 2 }).unwrap_err();
+    assert!(matches!(err, ContractError::BurnRequestNotFound {}));
+
+    // 转移后申请随之删除，新所有者须重新申请
+    execute(deps.as_mut(), env.clone(), user.clone(), ExecuteMsg::RequestBurn { token_id: 2 }).unwrap();
+    execute(deps.as_mut(), env.clone(), user.clone(), ExecuteMsg::TransferNft {
+        recipient: "user2".to_string(),
+        token_id: 2,
+    }).unwrap();
+    let res: BurnRequestResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::BurnRequest { token_id: 2 }).unwrap()).unwrap();
+    assert!(res.request.is_none());
+    execute(deps.as_mut(), env.clone(), mock_info("user2", &[]), ExecuteMsg::RequestBurn { token_id: 2 }).unwrap();
+
+    // 冷却期结束后确认销毁
+    let res = execute(deps.as_mut(), env.clone(), user, ExecuteMsg::ConfirmBurn { token_id: 1 }).unwrap();
+    assert!(res.attributes.iter().any(|attr| attr.key == "action" && attr.value == "burn"));
+    assert!(query(deps.as_ref(), env.clone(), QueryMsg::TokenMeta { token_id: 1 }).is_err());
+    let res: BurnRequestResponse =
+        from_json(&query(deps.as_ref(), env, QueryMsg::BurnRequest { token_id: 1 }).unwrap()).unwrap();
+    assert!(res.request.is_none());
+}
+
+#[test]
+fn test_burn_cooldown_guards_synthesis_inputs() {
+    use luckee_nft::msg::BurnRequestResponse;
+    use luckee_nft::ContractError;
+
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    for token_id in 1..=2 {
+        execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+            token_id,
+            owner: "user1".to_string(),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "series_1".to_string(),
+                collection_group_id: None,
+                serial_in_series: token_id,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        }).unwrap();
+    }
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), ExecuteMsg::SetBurnCooldown {
+        min_rarity_level: Some(0),
+        delay: 3600,
+    }).unwrap();
+    let user = mock_info("user1", &[]);
+    let synthesize = ExecuteMsg::Synthesize { inputs: vec![1, 2], target: NftKind::Firefly };
+
+    // 受保护的输入不能直接被合成消耗
+    let err = execute(deps.as_mut(), env.clone(), user.clone(), synthesize.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::BurnRequestRequired { .. }));
+
+    // 只有部分输入完成申请，或冷却期未结束时仍被拒绝
+    execute(deps.as_mut(), env.clone(), user.clone(), ExecuteMsg::RequestBurn { token_id: 1 }).unwrap();
+    env.block.time = env.block.time.plus_seconds(3600);
+    let err = execute(deps.as_mut(), env.clone(), user.clone(), synthesize.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::BurnRequestRequired { .. }));
+    execute(deps.as_mut(), env.clone(), user.clone(), ExecuteMsg::RequestBurn { token_id: 2 }).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), user.clone(), synthesize.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::BurnRequestRequired { .. }));
+
+    // 全部申请到期后可以合成，申请随输入一并清除
+    env.block.time = env.block.time.plus_seconds(3600);
+    execute(deps.as_mut(), env.clone(), user, synthesize).unwrap();
+    let res: BurnRequestResponse =
+        from_json(&query(deps.as_ref(), env, QueryMsg::BurnRequest { token_id: 1 }).unwrap()).unwrap();
+    assert!(res.request.is_none());
+}
+
+#[test]
+fn test_import_legacy() {
+    use cosmwasm_std::{Binary, ContractResult, SystemResult, WasmQuery};
+    use cw721::{Cw721QueryMsg, Cw721ReceiveMsg};
+    use luckee_nft::msg::{LegacyImportMsg, LegacyTokenResponse};
+    use luckee_nft::types::LegacyKindMapping;
+    use luckee_nft::ContractError;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    // 旧版合约的 nft_info：token "7" 的类型无法映射，其余为 gold 类型的大型盲盒 NFT
+    deps.querier.update_wasm(|query| {
+        let token_id = match query {
+            WasmQuery::Smart { msg, .. } => match from_json(msg).unwrap() {
+                Cw721QueryMsg::NftInfo { token_id } => token_id,
+                _ => panic!("unexpected query"),
+            },
+            _ => panic!("unexpected query"),
+        };
+        let kind = if token_id == "7" { "silver" } else { "gold" };
+        let info = format!(
+            r#"{{"token_uri":null,"extension":{{"name":"Legacy Luckee","attributes":[{{"trait_type":"kind","value":"{}"}},{{"trait_type":"scale","value":"large"}},{{"trait_type":"physical_sku","value":"SKU-1"}}]}}}}"#,
+            kind
+        );
+        SystemResult::Ok(ContractResult::Ok(Binary::from(info.into_bytes())))
+    });
+    let receive = |token_id: &str| ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+        sender: "user1".to_string(),
+        token_id: token_id.to_string(),
+        msg: Binary::default(),
+    });
+
+    // 仅合约所有者可以设置
+    let set_import = ExecuteMsg::SetLegacyImport {
+        import: Some(LegacyImportMsg {
+            contract: "legacy_contract".to_string(),
+            series_id: "legacy".to_string(),
+            kind_map: vec![LegacyKindMapping { legacy_kind: "gold".to_string(), kind: NftKind::Polaris }],
+        }),
+    };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), set_import.clone()).is_err());
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), set_import).unwrap();
+
+    // 导入后按映射的元数据铸造给原所有者
+    let res = execute(deps.as_mut(), env.clone(), mock_info("legacy_contract", &[]), receive("42")).unwrap();
+    let token_id: u64 = res.attributes.iter().find(|attr| attr.key == "token_id").unwrap().value.parse().unwrap();
+    let meta: TokenMetaResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::TokenMeta { token_id }).unwrap()).unwrap();
+    assert_eq!(meta.meta.kind, NftKind::Polaris);
+    assert_eq!(meta.meta.scale_origin, Scale::Large);
+    assert_eq!(meta.meta.physical_sku, Some("SKU-1".to_string()));
+    assert_eq!(meta.meta.series_id, "legacy");
+    let res: LegacyTokenResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::LegacyToken {
+        legacy_contract: "legacy_contract".to_string(),
+        legacy_token_id: "42".to_string(),
+    }).unwrap()).unwrap();
+    assert_eq!(res.token_id, Some(token_id));
+    // 其他旧版合约的相同 token ID 不受影响
+    let res: LegacyTokenResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::LegacyToken {
+        legacy_contract: "other_legacy".to_string(),
+        legacy_token_id: "42".to_string(),
+    }).unwrap()).unwrap();
+    assert_eq!(res.token_id, None);
+
+    // 重复导入和无法映射的类型被拒绝
+    let err = execute(deps.as_mut(), env.clone(), mock_info("legacy_contract", &[]), receive("42")).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidLegacyImport { .. }));
+    let err = execute(deps.as_mut(), env.clone(), mock_info("legacy_contract", &[]), receive("7")).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidLegacyImport { .. }));
+
+    // 映射后的类型暂停铸造时不能导入
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), ExecuteMsg::SetKindMintStatus {
+        kind: NftKind::Polaris,
+        status: luckee_nft::types::KindMintStatus::Paused,
+    }).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info("legacy_contract", &[]), receive("44")).unwrap_err();
+    assert!(matches!(err, ContractError::KindMintDisabled { .. }));
+
+    // 其他合约的回调仍按合作集合处理
+    let err = execute(deps.as_mut(), env, mock_info("other_contract", &[]), receive("43")).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidPartnerNft { .. }));
+}
+
+#[test]
+fn test_pagination_helpers() {
+    use luckee_nft::pagination::{
+        clamp_limit, cursor_scope, decode_cursor, encode_cursor, into_page, page_after, MAX_LIMIT, MAX_WIDE_LIMIT,
+    };
+
+    assert_eq!(clamp_limit(None, MAX_LIMIT), 30);
+    assert_eq!(clamp_limit(Some(500), MAX_LIMIT), 30);
+    assert_eq!(clamp_limit(Some(500), MAX_WIDE_LIMIT), 100);
+    assert_eq!(clamp_limit(Some(5), MAX_LIMIT), 5);
+    // 至少为 1，空页无法给出下一页游标
+    assert_eq!(clamp_limit(Some(0), MAX_LIMIT), 1);
+
+    // 内存列表跳过起点后截取
+    assert_eq!(page_after(vec![1, 3, 5, 7, 9], Some(3), 2), vec![5, 7]);
+    assert_eq!(page_after(vec![1, 3, 5], None, 10), vec![1, 3, 5]);
+
+    // 多读取的一项说明还有下一页，游标指向本页最后一项
+    let page = into_page(cursor_scope::TOKENS_BY_KIND, vec![5u64, 7, 9], 2, |id| *id);
+    assert_eq!(page.items, vec![5, 7]);
+    assert!(page.has_more);
+    let cursor = page.next_cursor.unwrap();
+    assert_eq!(cursor, encode_cursor(cursor_scope::TOKENS_BY_KIND, &7u64));
+    assert_eq!(decode_cursor::<u64>(cursor_scope::TOKENS_BY_KIND, Some(cursor.clone())).unwrap(), Some(7));
+
+    // 不足一页说明已到末尾
+    let page = into_page(cursor_scope::TOKENS_BY_KIND, vec![5u64], 2, |id| *id);
+    assert!(!page.has_more);
+    assert_eq!(page.next_cursor, None);
+    assert_eq!(decode_cursor::<u64>(cursor_scope::TOKENS_BY_KIND, None).unwrap(), None);
+
+    // 其他查询的游标和格式错误的游标被拒绝
+    assert!(decode_cursor::<u64>(cursor_scope::TOKENS_BY_SERIES, Some(cursor)).is_err());
+    let garbage = luckee_nft::msg::Cursor(cosmwasm_std::Binary::from(b"tokens_by_kind".to_vec()));
+    assert!(decode_cursor::<u64>(cursor_scope::TOKENS_BY_KIND, Some(garbage)).is_err());
+
+    // 字符串键
+    let cursor = encode_cursor(cursor_scope::ALL_RECIPES, &"rare".to_string());
+    assert_eq!(decode_cursor::<String>(cursor_scope::ALL_RECIPES, Some(cursor)).unwrap(), Some("rare".to_string()));
+}
+
+#[test]
+fn test_accepted_denoms() {
+    use cosmwasm_std::{Coin, Uint128};
+    use luckee_nft::msg::AcceptedDenomsResponse;
+    use luckee_nft::types::AcceptedDenom;
+    use luckee_nft::ContractError;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    let accepted = |denom: &str, min_amount: u128| AcceptedDenom {
+        denom: denom.to_string(),
+        min_amount: Uint128::new(min_amount),
+    };
+
+    // 默认不限制币种
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &coins(1, "uother")), ExecuteMsg::Deposit {}).unwrap();
+
+    // 只有所有者可以设置，币种不能重复
+    let set = |denoms| ExecuteMsg::SetAcceptedDenoms { denoms };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), set(vec![accepted("uluckee", 10)])).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    let err = execute(deps.as_mut(), env.clone(), mock_info("creator", &[]),
+        set(vec![accepted("uluckee", 10), accepted("uluckee", 1)])).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFunds { .. }));
+
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]),
+        set(vec![accepted("uluckee", 10), accepted("uatom", 1)])).unwrap();
+    let res: AcceptedDenomsResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::AcceptedDenoms {}).unwrap()).unwrap();
+    assert_eq!(res.denoms, vec![accepted("uluckee", 10), accepted("uatom", 1)]);
+
+    // 不接受的币种和低于最低数量的支付被拒绝
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user1", &coins(100, "uother")), ExecuteMsg::Deposit {}).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFunds { .. }));
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user1", &coins(5, "uluckee")), ExecuteMsg::Deposit {}).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFunds { .. }));
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user1", &coins(5, "uluckee")), ExecuteMsg::Synthesize {
+        inputs: vec![1, 2],
+        target: NftKind::Firefly,
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFunds { .. }));
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &coins(10, "uluckee")), ExecuteMsg::Deposit {}).unwrap();
+
+    // 之前存入的其他币种仍可提取
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::Withdraw {
+        amount: Coin::new(1, "uother"),
+    }).unwrap();
+
+    // 清空列表后恢复不限制
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), set(vec![])).unwrap();
+    execute(deps.as_mut(), env, mock_info("user1", &coins(1, "uother")), ExecuteMsg::Deposit {}).unwrap();
+}
+
+#[test]
+fn test_session_keys() {
+    use luckee_nft::msg::SessionKeyResponse;
+    use luckee_nft::types::SessionKeyScope;
+    use luckee_nft::ContractError;
+
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    for token_id in 1..=3u64 {
+        execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+            token_id,
+            owner: "user1".to_string(),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "series_1".to_string(),
+                collection_group_id: None,
+                serial_in_series: token_id,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        }).unwrap();
+    }
+    let grant = |scopes, duration| ExecuteMsg::GrantSessionKey { key: "game_key".to_string(), scopes, duration };
+
+    // 授权范围不能为空或重复，时长不超过 24 小时
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), grant(vec![], 600)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidSessionKey { .. }));
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]),
+        grant(vec![SessionKeyScope::Approve, SessionKeyScope::Approve], 600)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidSessionKey { .. }));
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]),
+        grant(vec![SessionKeyScope::Approve], 24 * 60 * 60 + 1)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidSessionKey { .. }));
+
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]),
+        grant(vec![SessionKeyScope::Approve, SessionKeyScope::Synthesize], 600)).unwrap();
+    let res: SessionKeyResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::SessionKey {
+        owner: "user1".to_string(),
+        key: "game_key".to_string(),
+    }).unwrap()).unwrap();
+    assert_eq!(res.grant.unwrap().scopes, vec![SessionKeyScope::Approve, SessionKeyScope::Synthesize]);
+
+    // 会话密钥可执行授权范围内的操作
+    execute(deps.as_mut(), env.clone(), mock_info("game_key", &[]), ExecuteMsg::Approve {
+        spender: "market".to_string(),
+        token_id: 3,
+        expires: None,
+    }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("game_key", &[]), ExecuteMsg::Synthesize {
+        inputs: vec![1, 2],
+        target: NftKind::Firefly,
+    }).unwrap();
+
+    // 范围之外的操作仍被拒绝
+    let err = execute(deps.as_mut(), env.clone(), mock_info("game_key", &[]), ExecuteMsg::TransferNft {
+        recipient: "game_key".to_string(),
+        token_id: 3,
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::NotOwned {}));
+    let err = execute(deps.as_mut(), env.clone(), mock_info("game_key", &[]), ExecuteMsg::Revoke {
+        spender: "market".to_string(),
+        token_id: 3,
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::NotOwned {}));
+
+    // 到期后失效
+    env.block.time = env.block.time.plus_seconds(600);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("game_key", &[]), ExecuteMsg::Approve {
+        spender: "market".to_string(),
+        token_id: 3,
+        expires: None,
+    }).unwrap_err();
+    assert!(matches!(err, ContractError::NotOwned {}));
+    let res: SessionKeyResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::SessionKey {
+        owner: "user1".to_string(),
+        key: "game_key".to_string(),
+    }).unwrap()).unwrap();
+    assert_eq!(res.grant, None);
+
+    // 所有者可撤销，撤销不存在的密钥返回错误
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::RevokeSessionKey { key: "game_key".to_string() }).unwrap();
+    let err = execute(deps.as_mut(), env, mock_info("user1", &[]), ExecuteMsg::RevokeSessionKey { key: "game_key".to_string() }).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidSessionKey { .. }));
+}
+
+#[test]
+fn test_revoke_all_my_approvals() {
+    use luckee_nft::msg::{GrantsByOwnerResponse, KindApprovalResponse, SessionKeyResponse};
+    use luckee_nft::types::SessionKeyScope;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    for token_id in 1..=101u64 {
+        execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+            token_id,
+            owner: "user1".to_string(),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "series_1".to_string(),
+                collection_group_id: None,
+                serial_in_series: token_id,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        }).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::Approve {
+            spender: "market".to_string(),
+            token_id,
+            expires: None,
+        }).unwrap();
+    }
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]),
+        ExecuteMsg::ApproveAll { operator: "bot".to_string(), expires: None, max_transfers: Some(3) }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]),
+        ExecuteMsg::ApproveKind { operator: "crafter".to_string(), kind: NftKind::Clover, expires: None }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::GrantSessionKey {
+        key: "game_key".to_string(),
+        scopes: vec![SessionKeyScope::Approve],
+        duration: 600,
+    }).unwrap();
+    let attr = |res: &cosmwasm_std::Response, key: &str| {
+        res.attributes.iter().find(|attr| attr.key == key).unwrap().value.clone()
+    };
+
+    // 第一批只处理 100 个单个 NFT 批准
+    let res = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::RevokeAllMyApprovals {}).unwrap();
+    assert_eq!(attr(&res, "token_approvals"), "100");
+    assert_eq!(attr(&res, "operators"), "0");
+    assert_eq!(attr(&res, "has_more"), "true");
+
+    // 第二批清除剩余条目
+    let res = execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::RevokeAllMyApprovals {}).unwrap();
+    assert_eq!(attr(&res, "token_approvals"), "1");
+    assert_eq!(attr(&res, "operators"), "1");
+    assert_eq!(attr(&res, "kind_approvals"), "1");
+    assert_eq!(attr(&res, "session_keys"), "1");
+    assert_eq!(attr(&res, "has_more"), "false");
+
+    let grants: GrantsByOwnerResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::GrantsByOwner {
+        owner: "user1".to_string(),
+        start_after: None,
+        limit: None,
+    }).unwrap()).unwrap();
+    assert!(grants.token_approvals.is_empty());
+    assert!(grants.operators.is_empty());
+    let kind: KindApprovalResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::KindApproval {
+        owner: "user1".to_string(),
+        operator: "crafter".to_string(),
+        kind: NftKind::Clover,
+    }).unwrap()).unwrap();
+    assert!(!kind.approved);
+    let session: SessionKeyResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::SessionKey {
+        owner: "user1".to_string(),
+        key: "game_key".to_string(),
+    }).unwrap()).unwrap();
+    assert_eq!(session.grant, None);
+
+    // 已撤销的地址不能再转移
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("market", &[]), ExecuteMsg::TransferNft {
+        recipient: "market".to_string(),
+        token_id: 1,
+    }).is_err());
+    assert!(execute(deps.as_mut(), env, mock_info("bot", &[]), ExecuteMsg::TransferNft {
+        recipient: "bot".to_string(),
+        token_id: 2,
+    }).is_err());
+}
+
+#[test]
+fn test_token_debug() {
+    use luckee_nft::msg::TokenDebugResponse;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+        token_id: 1,
+        owner: "user1".to_string(),
+        extension: NftMeta {
+            kind: NftKind::Clover,
+            scale_origin: Scale::Tiny,
+            physical_sku: None,
+            crafted_from: None,
+            series_id: "series_1".to_string(),
+            collection_group_id: None,
+            serial_in_series: 1,
+            expires_at: None,
+        },
+        idempotency_key: None,
+    }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::Approve {
+        spender: "market".to_string(),
+        token_id: 1,
+        expires: None,
+    }).unwrap();
+
+    let res: TokenDebugResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::TokenDebug { token_id: 1 }).unwrap()).unwrap();
+    assert_eq!(res.owner, Some("user1".to_string()));
+    assert_eq!(res.meta.unwrap().kind, NftKind::Clover);
+    assert!(res.acquired.is_some());
+    assert_eq!(res.approvals.len(), 1);
+    assert_eq!(res.approvals[0].spender.as_str(), "market");
+    assert_eq!(res.session_lock, None);
+    assert_eq!(res.bundle_id, None);
+    assert!(res.indexes.all_tokens && res.indexes.owner_tokens);
+    assert!(res.indexes.by_kind && res.indexes.by_series && res.indexes.by_serial);
+    assert_eq!(res.indexes.by_group, None);
+
+    // 不存在的 NFT 返回空记录而不是错误
+    let res: TokenDebugResponse = from_json(&query(deps.as_ref(), env, QueryMsg::TokenDebug { token_id: 2 }).unwrap()).unwrap();
+    assert_eq!(res.owner, None);
+    assert_eq!(res.meta, None);
+    assert!(!res.indexes.all_tokens);
+}
+
+#[test]
+fn test_deterministic_token_ids() {
+    use luckee_nft::helpers::derive_token_id;
+    use luckee_nft::msg::{BatchMintItem, DeterministicTokenIdResponse};
+    use luckee_nft::ContractError;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    let meta = || NftMeta {
+        kind: NftKind::Clover,
+        scale_origin: Scale::Tiny,
+        physical_sku: None,
+        crafted_from: None,
+        series_id: "series_1".to_string(),
+        collection_group_id: None,
+        serial_in_series: 0,
+        expires_at: None,
+    };
+    let mint = |token_id| ExecuteMsg::Mint { token_id, owner: "user1".to_string(), extension: meta(), idempotency_key: None };
+
+    // 只有合约所有者可以切换
+    let err = execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]),
+        ExecuteMsg::SetDeterministicTokenIds { enabled: true }).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]),
+        ExecuteMsg::SetDeterministicTokenIds { enabled: true }).unwrap();
+
+    // 传入 0 时按系列和序号推导
+    let first = derive_token_id("series_1", 1);
+    let res = execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), mint(0)).unwrap();
+    assert_eq!(res.attributes.iter().find(|attr| attr.key == "token_id").unwrap().value, first.to_string());
+    let owner: cw721::OwnerOfResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::OwnerOf {
+        token_id: first,
+        include_expired: None,
+    }).unwrap()).unwrap();
+    assert_eq!(owner.owner, "user1");
+
+    // 查询下一个序号对应的 ID；传入的 ID 须与推导结果一致
+    let next: DeterministicTokenIdResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::DeterministicTokenId {
+        series_id: "series_1".to_string(),
+        serial: None,
+    }).unwrap()).unwrap();
+    assert!(next.enabled);
+    assert_eq!(next.serial, 2);
+    assert_eq!(next.token_id, derive_token_id("series_1", 2));
+    assert!(!next.taken);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), mint(7)).unwrap_err();
+    assert!(matches!(err, ContractError::DeterministicTokenIdMismatch { .. }));
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), mint(next.token_id)).unwrap();
+
+    // 批量铸造按出现顺序依次推导
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::BatchMint {
+        mints: vec![
+            BatchMintItem { token_id: 0, owner: "user1".to_string(), extension: meta() },
+            BatchMintItem { token_id: 0, owner: "user2".to_string(), extension: meta() },
+        ],
+        idempotency_key: None,
+    }).unwrap();
+    let owner: cw721::OwnerOfResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::OwnerOf {
+        token_id: derive_token_id("series_1", 4),
+        include_expired: None,
+    }).unwrap()).unwrap();
+    assert_eq!(owner.owner, "user2");
+
+    // 推导出的 ID 不占用自动分配计数器
+    let reserved: luckee_nft::msg::ReservedIdRangeResponse = from_json(&query(deps.as_ref(), env, QueryMsg::ReservedIdRange {}).unwrap()).unwrap();
+    assert_eq!(reserved.next_auto_token_id, 1);
+}
+
+#[test]
+fn test_treasury() {
+    use cosmwasm_std::testing::mock_dependencies_with_balance;
+    use luckee_nft::msg::TreasuryResponse;
+
+    let mut deps = mock_dependencies_with_balance(&coins(1000, "uluckee"));
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), ExecuteMsg::SetRecipe {
+        target: NftKind::Firefly,
+        recipe: Recipe {
+            inputs: vec![RecipeInput { nft_kind: NftKind::Clover, count: 2 }],
+            output: NftKind::Firefly,
+            cost: Some(cosmwasm_std::coin(100, "uluckee")),
+        },
+    }).unwrap();
+    for token_id in 1..=2u64 {
+        execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+            token_id,
+            owner: "user1".to_string(),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "series_1".to_string(),
+                collection_group_id: None,
+                serial_in_series: token_id,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        }).unwrap();
+    }
+
+    // 合成费用计入收入，紧急提取计入提取
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &coins(100, "uluckee")), ExecuteMsg::Synthesize {
+        inputs: vec![1, 2],
+        target: NftKind::Firefly,
+    }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]),
+        ExecuteMsg::EmergencyWithdraw { amount: coins(300, "uluckee") }).unwrap();
+
+    let res: TreasuryResponse = from_json(&query(deps.as_ref(), env, QueryMsg::Treasury {}).unwrap()).unwrap();
+    assert_eq!(res.balances, coins(1000, "uluckee"));
+    assert!(res.reserved.is_empty());
+    assert_eq!(res.fees_collected.len(), 1);
+    assert_eq!(res.fees_collected[0].source, "synthesis");
+    assert_eq!(res.fees_collected[0].amount, coins(100, "uluckee"));
+    assert_eq!(res.withdrawn, coins(300, "uluckee"));
+}
+
+#[test]
+fn test_update_meta_signed_rejects_replay_and_bad_signature() {
+    use cosmwasm_std::{to_json_binary, Binary};
+    use luckee_nft::msg::{MetaOracleNonceResponse, OracleMetaPayload};
+    use luckee_nft::ContractError;
+
+    // 测试用预言机密钥签发的固定签名，负载绑定合约地址 luckee_contract，
+    // 过期时间为 mock_env 区块时间之后 100 秒
+    const ORACLE_PUBKEY: &str = "Ax4h5P3fqMLGKUZMgJhZGbVVGsgZj2ZnoQE7VZ9EfTXd";
+    const SHIPPED_SIGNATURE: &str =
+        "HSPF1bHbpB84/DOIMIc//wAMxMfYOrdAnqh5orDR4PFkSof7PnBeFJQm+YZY2HDWMRopuohFYgRO8pHNk9r9zA==";
+    const DELIVERED_SIGNATURE: &str =
+        "2mF8KMh3WNYYSUYWNtuFfDiqoS3B4oGAfvtmdIgoQ55cdib+5MDcf1G44fb2ugNH5cjPqgJIuhuaK1i2C7t2rQ==";
+    const RETURNED_SIGNATURE: &str =
+        "bX5vlFvwfGktWC9PoJ/9tAqbYB2W4r34p9oR41n6MLRZMRuOcE2XWyhRaeEGOysmHgFd3v6GJGFatNEKMb4pWA==";
+
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked("luckee_contract");
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+        token_id: 1,
+        owner: "user1".to_string(),
+        extension: NftMeta {
+            kind: NftKind::Clover,
+            scale_origin: Scale::Tiny,
+            physical_sku: None,
+            crafted_from: None,
+            series_id: "series_1".to_string(),
+            collection_group_id: None,
+            serial_in_series: 1,
+            expires_at: None,
+        },
+        idempotency_key: None,
+    }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), ExecuteMsg::SetMetaOracle {
+        pubkey: Some(Binary::from_base64(ORACLE_PUBKEY).unwrap()),
+    }).unwrap();
+
+    let expires_at = env.block.time.seconds() + 100;
+    let payload = |nonce: u64, physical_sku: &str| to_json_binary(&OracleMetaPayload {
+        contract: "luckee_contract".to_string(),
+        token_id: 1,
+        nonce,
+        expires_at,
+        physical_sku: Some(physical_sku.to_string()),
+    }).unwrap();
+    let update = |payload: Binary, signature: &str| ExecuteMsg::UpdateMetaSigned {
+        token_id: 1,
+        payload,
+        signature: Binary::from_base64(signature).unwrap(),
+    };
+    let relayer = mock_info("anyone", &[]);
+
+    // 签名与负载不匹配时拒绝，且不消耗序号
+    let err = execute(deps.as_mut(), env.clone(), relayer.clone(), update(payload(7, "SKU-SHIPPED"), DELIVERED_SIGNATURE)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidOracleSignature {}));
+    let err = execute(deps.as_mut(), env.clone(), relayer.clone(), update(payload(7, "SKU-FORGED"), SHIPPED_SIGNATURE)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidOracleSignature {}));
+
+    // 有效签名由任何人提交均可更新
+    let shipped = update(payload(7, "SKU-SHIPPED"), SHIPPED_SIGNATURE);
+    execute(deps.as_mut(), env.clone(), relayer.clone(), shipped.clone()).unwrap();
+    let res: TokenMetaResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::TokenMeta { token_id: 1 }).unwrap()).unwrap();
+    assert_eq!(res.meta.physical_sku, Some("SKU-SHIPPED".to_string()));
+
+    // 重放已使用的序号被拒绝
+    let err = execute(deps.as_mut(), env.clone(), relayer.clone(), shipped).unwrap_err();
+    assert!(matches!(err, ContractError::OracleNonceSpent { nonce: 7 }));
+
+    // 序号不要求连续，较小的未使用序号正常生效
+    execute(deps.as_mut(), env.clone(), relayer.clone(), update(payload(3, "SKU-DELIVERED"), DELIVERED_SIGNATURE)).unwrap();
+    let res: TokenMetaResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::TokenMeta { token_id: 1 }).unwrap()).unwrap();
+    assert_eq!(res.meta.physical_sku, Some("SKU-DELIVERED".to_string()));
+    let status: MetaOracleNonceResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::MetaOracleNonce { nonce: 7 }).unwrap()).unwrap();
+    assert!(status.spent);
+
+    // 过期的签名被拒绝，且不消耗序号
+    env.block.time = env.block.time.plus_seconds(100);
+    let err = execute(deps.as_mut(), env.clone(), relayer, update(payload(4, "SKU-RETURNED"), RETURNED_SIGNATURE)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidOraclePayload { .. }));
+    let status: MetaOracleNonceResponse =
+        from_json(&query(deps.as_ref(), env, QueryMsg::MetaOracleNonce { nonce: 4 }).unwrap()).unwrap();
+    assert!(!status.spent);
+}
+
+#[test]
+fn test_swap_exchanges_tokens_and_updates_indexes() {
+    use cw721::{OwnerOfResponse, TokensResponse};
+    use luckee_nft::msg::SwapOffersResponse;
+    use luckee_nft::ContractError;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    for (token_id, owner, kind) in [(1, "alice", NftKind::Clover), (2, "bob", NftKind::Firefly), (3, "bob", NftKind::Clover)] {
+        execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+            token_id,
+            owner: owner.to_string(),
+            extension: NftMeta {
+                kind,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "series_1".to_string(),
+                collection_group_id: None,
+                serial_in_series: token_id,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        }).unwrap();
+    }
+
+    // alice 用四叶草换流萤
+    execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::CreateSwapOffer {
+        offered_token_id: 1,
+        wanted_kind: NftKind::Firefly,
+    }).unwrap();
+    let offers = |deps: &cosmwasm_std::OwnedDeps<_, _, _>| -> SwapOffersResponse {
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::SwapOffers {
+            wanted_kind: NftKind::Firefly,
+            start_after: None,
+            limit: None,
+        }).unwrap()).unwrap()
+    };
+    assert_eq!(offers(&deps).offers.len(), 1);
+
+    // 类型不符、非所有者和创建者本人都不能接受
+    let err = execute(deps.as_mut(), env.clone(), mock_info("bob", &[]), ExecuteMsg::AcceptSwap { offer_id: 1, token_id: 3 }).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidSwap { .. }));
+    let err = execute(deps.as_mut(), env.clone(), mock_info("carol", &[]), ExecuteMsg::AcceptSwap { offer_id: 1, token_id: 2 }).unwrap_err();
+    assert!(matches!(err, ContractError::NotOwned {}));
+    let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::AcceptSwap { offer_id: 1, token_id: 2 }).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidSwap { .. }));
+
+    execute(deps.as_mut(), env.clone(), mock_info("bob", &[]), ExecuteMsg::AcceptSwap { offer_id: 1, token_id: 2 }).unwrap();
+
+    // 双方 NFT 均已交换
+    let owner_of = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, token_id: u64| -> String {
+        let res: OwnerOfResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::OwnerOf {
+            token_id,
+            include_expired: None,
+        }).unwrap()).unwrap();
+        res.owner
+    };
+    assert_eq!(owner_of(&deps, 1), "bob");
+    assert_eq!(owner_of(&deps, 2), "alice");
+
+    // 所有者索引和按类型持有数量随之更新
+    let tokens = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, owner: &str| -> Vec<String> {
+        let res: TokensResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::Tokens {
+            owner: owner.to_string(),
+            start_after: None,
+            limit: None,
+        }).unwrap()).unwrap();
+        res.tokens
+    };
+    assert_eq!(tokens(&deps, "alice"), vec!["2".to_string()]);
+    assert_eq!(tokens(&deps, "bob"), vec!["1".to_string(), "3".to_string()]);
+    let count = |owner: &str, kind: &str| {
+        COUNTS_BY_OWNER_KIND.may_load(&deps.storage, (Addr::unchecked(owner), kind.to_string())).unwrap().unwrap_or(0)
+    };
+    assert_eq!(count("alice", "Clover"), 0);
+    assert_eq!(count("alice", "Firefly"), 1);
+    assert_eq!(count("bob", "Clover"), 2);
+    assert_eq!(count("bob", "Firefly"), 0);
+
+    // 报价已关闭，不能再次接受
+    assert!(offers(&deps).offers.is_empty());
+    let err = execute(deps.as_mut(), env.clone(), mock_info("bob", &[]), ExecuteMsg::AcceptSwap { offer_id: 1, token_id: 3 }).unwrap_err();
+    assert!(matches!(err, ContractError::SwapOfferNotFound { offer_id: 1 }));
+}
+
+#[test]
+fn test_trusted_operator_opt_in_and_suspension() {
+    use cw721::OwnerOfResponse;
+    use luckee_nft::msg::TrustedOperatorsResponse;
+    use luckee_nft::ContractError;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    for token_id in 1..=3 {
+        execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+            token_id,
+            owner: "alice".to_string(),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "series_1".to_string(),
+                collection_group_id: None,
+                serial_in_series: token_id,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        }).unwrap();
+    }
+    let marketplace = mock_info("marketplace", &[]);
+    let transfer = |token_id: u64| ExecuteMsg::TransferNft { recipient: "bob".to_string(), token_id };
+
+    // 仅合约所有者可以登记受信任操作员
+    let trust = ExecuteMsg::SetTrustedOperator { operator: "marketplace".to_string(), trusted: true };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), trust.clone()).is_err());
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), trust).unwrap();
+
+    // 用户未选择信任注册表时，受信任操作员无权转移
+    let err = execute(deps.as_mut(), env.clone(), marketplace.clone(), transfer(1)).unwrap_err();
+    assert!(matches!(err, ContractError::NotOwned {}));
+
+    // 选择信任后可以代为转移
+    execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::OptInTrustedOperators {}).unwrap();
+    execute(deps.as_mut(), env.clone(), marketplace.clone(), transfer(1)).unwrap();
+    let res: OwnerOfResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::OwnerOf {
+        token_id: 1,
+        include_expired: None,
+    }).unwrap()).unwrap();
+    assert_eq!(res.owner, "bob");
+
+    // 全局停用后立即失效，即使用户已选择信任
+    let suspend = |suspended: bool| ExecuteMsg::SuspendOperator { operator: "marketplace".to_string(), suspended };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), suspend(true)).is_err());
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), suspend(true)).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), marketplace.clone(), transfer(2)).unwrap_err();
+    assert!(matches!(err, ContractError::NotOwned {}));
+    let res: TrustedOperatorsResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::TrustedOperators {
+        owner: Some("alice".to_string()),
+    }).unwrap()).unwrap();
+    assert_eq!(res.operators, vec!["marketplace".to_string()]);
+    assert_eq!(res.suspended, vec!["marketplace".to_string()]);
+    assert_eq!(res.opted_in, Some(true));
+
+    // 恢复后原有信任重新生效
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), suspend(false)).unwrap();
+    execute(deps.as_mut(), env.clone(), marketplace.clone(), transfer(2)).unwrap();
+
+    // 用户退出后不再信任注册表
+    execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::OptOutTrustedOperators {}).unwrap();
+    let err = execute(deps.as_mut(), env, marketplace, transfer(3)).unwrap_err();
+    assert!(matches!(err, ContractError::NotOwned {}));
+}
+
+#[test]
+fn test_usd_fee_rejects_stale_and_deviating_prices() {
+    use cosmwasm_std::testing::MockQuerier;
+    use cosmwasm_std::{to_json_binary, ContractResult, Decimal, SystemResult, Uint128};
+    use luckee_nft::msg::{PriceFeedResponse, PriceOracleMsg, PriceOracleResponse, RecipeFeeResponse};
+    use luckee_nft::ContractError;
+
+    fn set_price_feed(querier: &mut MockQuerier, price: Decimal, updated_at: u64) {
+        querier.update_wasm(move |_| {
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&PriceFeedResponse { price, updated_at }).unwrap()))
+        });
+    }
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let now = env.block.time.seconds();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    for token_id in 1..=6 {
+        execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+            token_id,
+            owner: "user1".to_string(),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "series_1".to_string(),
+                collection_group_id: None,
+                serial_in_series: token_id,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        }).unwrap();
+    }
+
+    // 合成流萤收取 2 美元，价格最长有效 600 秒，相对上次价格最多偏离 10%
+    let creator = mock_info("creator", &[]);
+    execute(deps.as_mut(), env.clone(), creator.clone(), ExecuteMsg::SetPriceOracle {
+        oracle: Some(PriceOracleMsg {
+            feed: "price_feed".to_string(),
+            denom: "uluckee".to_string(),
+            max_age_seconds: 600,
+            max_deviation_bps: 1000,
+        }),
+    }).unwrap();
+    execute(deps.as_mut(), env.clone(), creator, ExecuteMsg::SetRecipeUsdCost {
+        target: NftKind::Firefly,
+        usd_cost: Some(Uint128::new(2_000_000)),
+    }).unwrap();
+    let user = mock_info("user1", &coins(1_000_000, "uluckee"));
+    let synthesize = |inputs: Vec<u64>| ExecuteMsg::Synthesize { inputs, target: NftKind::Firefly };
+
+    // 超过最长有效时间的价格返回 StaleOraclePrice，查询报价同样失败
+    set_price_feed(&mut deps.querier, Decimal::percent(200), now - 601);
+    let err = execute(deps.as_mut(), env.clone(), user.clone(), synthesize(vec![1, 2])).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::StaleOraclePrice { updated_at, max_age_seconds: 600 } if updated_at == now - 601
+    ));
+    assert!(query(deps.as_ref(), env.clone(), QueryMsg::RecipeFee { target: NftKind::Firefly }).is_err());
+
+    // 有效期内的价格按向上取整换算：2 美元 / 每单位 2 微美元 = 1,000,000 uluckee
+    set_price_feed(&mut deps.querier, Decimal::percent(200), now - 600);
+    let quote: RecipeFeeResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::RecipeFee { target: NftKind::Firefly }).unwrap()).unwrap();
+    assert_eq!(quote.fee, Some(cosmwasm_std::coin(1_000_000, "uluckee")));
+    execute(deps.as_mut(), env.clone(), user.clone(), synthesize(vec![1, 2])).unwrap();
+    let oracle: PriceOracleResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::PriceOracle {}).unwrap()).unwrap();
+    assert_eq!(oracle.last_price, Some(Decimal::percent(200)));
+
+    // 偏离恰好等于容忍度时接受，并作为新的基准价格
+    set_price_feed(&mut deps.querier, Decimal::percent(220), now);
+    let quote: RecipeFeeResponse =
+        from_json(&query(deps.as_ref(), env.clone(), QueryMsg::RecipeFee { target: NftKind::Firefly }).unwrap()).unwrap();
+    assert_eq!(quote.fee, Some(cosmwasm_std::coin(909_091, "uluckee")));
+    execute(deps.as_mut(), env.clone(), user.clone(), synthesize(vec![3, 4])).unwrap();
+
+    // 相对新基准偏离超过 10% 时返回 OraclePriceDeviation
+    set_price_feed(&mut deps.querier, Decimal::percent(250), now);
+    let err = execute(deps.as_mut(), env.clone(), user, synthesize(vec![5, 6])).unwrap_err();
+    assert!(matches!(err, ContractError::OraclePriceDeviation { .. }));
+    let oracle: PriceOracleResponse = from_json(&query(deps.as_ref(), env, QueryMsg::PriceOracle {}).unwrap()).unwrap();
+    assert_eq!(oracle.last_price, Some(Decimal::percent(220)));
+}
+
+#[test]
+fn test_version_info_capabilities() {
+    use luckee_nft::msg::VersionInfoResponse;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+
+    let res: VersionInfoResponse = from_json(&query(deps.as_ref(), env, QueryMsg::VersionInfo {}).unwrap()).unwrap();
+    assert_eq!(res.contract_name, "crates.io:luckee_nft");
+    let enabled = [
+        "synthesis",
+        "batch_mint",
+        "pausable",
+        "burn_grace_period",
+        "withdraw_guardians",
+        "deposits",
+        "burn_hooks",
+        "kind_mint_status",
+        "provenance",
+        "meta_oracle",
+        "expiration",
+        "series_registry",
+        "minter_rotation",
+        "usd_pricing",
+        "kind_approvals",
+        "trusted_operators",
+        "operator_budgets",
+        "swaps",
+        "gifts",
+        "airdrop",
+        "game_sessions",
+        "crafting_limits",
+        "reserved_ids",
+        "burn_tax",
+        "relay_mint",
+        "crafting_royalty",
+        "minter_set_lock",
+        "custody",
+        "voting",
+        "delegation",
+        "bundles",
+        "conditional_transfers",
+        "partners",
+        "idempotency",
+        "event_verbosity",
+        "burn_cooldown",
+        "legacy_import",
+        "payment_denoms",
+        "session_keys",
+        "deterministic_ids",
+        "factory",
+        "shared_recipes",
+        "cross_collection_output",
+        "mint_pools",
+    ];
+    for capability in enabled {
+        assert!(res.capabilities.iter().any(|c| c == capability), "missing {}", capability);
+    }
+
+    // 未实现的约定能力不会列出
+    for capability in ["marketplace", "ibc", "randomness", "soulbound"] {
+        assert!(!res.capabilities.iter().any(|c| c == capability), "unexpected {}", capability);
+    }
+}
+
+#[test]
+fn test_emergency_withdraw_excludes_deposits() {
+    use cosmwasm_std::testing::mock_dependencies_with_balance;
+    use luckee_nft::msg::TreasuryResponse;
+
+    // 合约余额 1000，其中 800 为用户存款
+    let mut deps = mock_dependencies_with_balance(&coins(1000, "uluckee"));
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &coins(800, "uluckee")), ExecuteMsg::Deposit {}).unwrap();
+
+    let res: TreasuryResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::Treasury {}).unwrap()).unwrap();
+    assert_eq!(res.reserved, coins(800, "uluckee"));
+
+    // 超出余额减去存款的部分被拒绝
+    let err = execute(deps.as_mut(), env.clone(), mock_info("creator", &[]),
+        ExecuteMsg::EmergencyWithdraw { amount: coins(300, "uluckee") }).unwrap_err();
+    assert!(matches!(err, luckee_nft::ContractError::WithdrawExceedsAvailable { available, .. } if available.u128() == 200));
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]),
+        ExecuteMsg::EmergencyWithdraw { amount: coins(200, "uluckee") }).unwrap();
+
+    // 用户提取后存款总额随之减少
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]),
+        ExecuteMsg::Withdraw { amount: cosmwasm_std::coin(500, "uluckee") }).unwrap();
+    let res: TreasuryResponse = from_json(&query(deps.as_ref(), env, QueryMsg::Treasury {}).unwrap()).unwrap();
+    assert_eq!(res.reserved, coins(300, "uluckee"));
+}
+
+#[test]
+fn test_expire_removes_conditional_transfer() {
+    use luckee_nft::msg::ConditionalTransferResponse;
+
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    let now = env.block.time.seconds();
+    execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), ExecuteMsg::Mint {
+        token_id: 1,
+        owner: "seller".to_string(),
+        extension: NftMeta {
+            kind: NftKind::Clover,
+            scale_origin: Scale::Tiny,
+            physical_sku: None,
+            crafted_from: None,
+            series_id: "series_1".to_string(),
+            collection_group_id: None,
+            serial_in_series: 1,
+            expires_at: Some(now + 100),
+        },
+        idempotency_key: None,
+    }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("seller", &[]), ExecuteMsg::TransferWithCondition {
+        recipient: "buyer".to_string(),
+        token_id: 1,
+        hashlock: "ab".repeat(32),
+        timeout: now + 3600,
+    }).unwrap();
+
+    // 托管期间过期被回收，条件转移一并删除
+    env.block.time = env.block.time.plus_seconds(200);
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::Expire { token_id: 1 }).unwrap();
+    let res: ConditionalTransferResponse = from_json(
+        &query(deps.as_ref(), env.clone(), QueryMsg::ConditionalTransfer { token_id: 1 }).unwrap(),
+    ).unwrap();
+    assert!(res.transfer.is_none());
+    assert!(execute(deps.as_mut(), env, mock_info("seller", &[]),
+        ExecuteMsg::ReclaimConditionalTransfer { token_id: 1 }).is_err());
+}
+
+#[test]
+fn test_garbage_collect_series_counters() {
+    use luckee_nft::state::{SERIES_NEXT_SERIAL, SERIES_TOKEN_COUNT};
+
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg {
+        name: "Luckee NFT".to_string(),
+        symbol: "LUCKEE".to_string(),
+        minter: "blind_box_contract".to_string(),
+        base_uri: None,
+    }).unwrap();
+    let mint = |token_id: u64, series_id: &str| ExecuteMsg::Mint {
+        token_id,
+        owner: "user1".to_string(),
+        extension: NftMeta {
+            kind: NftKind::Clover,
+            scale_origin: Scale::Tiny,
+            physical_sku: None,
+            crafted_from: None,
+            series_id: series_id.to_string(),
+            collection_group_id: None,
+            serial_in_series: 0,
+            expires_at: None,
+        },
+        idempotency_key: None,
+    };
+
+    // 合成系列前缀保留给合约
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), mint(1, "synthesis_1")).is_err());
+
+    // 合成后销毁产出，两个系列的现存数量均归零
+    for token_id in 1..=2 {
+        execute(deps.as_mut(), env.clone(), mock_info("blind_box_contract", &[]), mint(token_id, "series_1")).unwrap();
+    }
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::Synthesize {
+        inputs: vec![1, 2],
+        target: NftKind::Firefly,
+    }).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), ExecuteMsg::Burn { token_id: 3 }).unwrap();
+    let synthesis_series = format!("synthesis_{}", env.block.time.seconds());
+
+    // 同一区块内合成系列仍可能继续分配序号，保留计数器（依次执行四个阶段，完成一轮清理）
+    for _ in 0..4 {
+        execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::GarbageCollect { limit: None }).unwrap();
+    }
+    assert!(!SERIES_TOKEN_COUNT.has(&deps.storage, "series_1".to_string()));
+    assert!(SERIES_NEXT_SERIAL.has(&deps.storage, synthesis_series.clone()));
+
+    // 之后的区块删除合成系列的计数器，普通系列始终保留序号计数器
+    env.block.height += 1;
+    env.block.time = env.block.time.plus_seconds(5);
+    for _ in 0..4 {
+        execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::GarbageCollect { limit: None }).unwrap();
+    }
+    assert!(!SERIES_TOKEN_COUNT.has(&deps.storage, synthesis_series.clone()));
+    assert!(!SERIES_NEXT_SERIAL.has(&deps.storage, synthesis_series));
+    assert_eq!(SERIES_NEXT_SERIAL.load(&deps.storage, "series_1".to_string()).unwrap(), 2);
+}
@@ -0,0 +1,288 @@
+//! 基于模型的随机测试
+//!
+//! 以简单的内存模型（token_id → 所有者、类型）镜像预期状态，
+//! 对合约与模型同时执行随机的铸造、转移、销毁和合成序列，
+//! 每一步之后检查总供应量、所有者索引和类型索引与模型一致。
+//!
+//! 随机序列由固定种子生成，失败时输出种子和步骤便于复现
+
+#![cfg(feature = "cosmwasm")]
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use cosmwasm_std::{Addr, Empty};
+use cw_multi_test::{App, ContractWrapper, Executor};
+
+use luckee_nft::contract::{execute, instantiate, query};
+use luckee_nft::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, StorageStatsResponse, TokensByKindResponse};
+use luckee_nft::types::{NftKind, NftMeta, Scale};
+
+const MINTER: &str = "minter";
+const USERS: [&str; 4] = ["user1", "user2", "user3", "user4"];
+const KINDS: [NftKind; 3] = [NftKind::Clover, NftKind::Firefly, NftKind::CrimsonKoi];
+
+/// 测试用确定性随机数生成器（xorshift64）
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// 预期状态模型
+#[derive(Default)]
+struct Model {
+    tokens: BTreeMap<u64, (String, NftKind)>,
+    next_token_id: u64,
+}
+
+impl Model {
+    fn tokens_of(&self, owner: &str, kind: Option<&NftKind>) -> Vec<u64> {
+        self.tokens
+            .iter()
+            .filter(|(_, (o, k))| o == owner && kind.map_or(true, |kind| k == kind))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+}
+
+/// 随机操作
+#[derive(Debug)]
+enum Action {
+    Mint { owner: String, kind: NftKind },
+    Transfer { sender: String, token_id: u64, recipient: String },
+    Burn { sender: String, token_id: u64 },
+    Synthesize { sender: String, inputs: Vec<u64>, target: NftKind },
+}
+
+fn setup() -> (App, Addr) {
+    let mut app = App::default();
+    let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)) as Box<dyn cw_multi_test::Contract<Empty>>);
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("creator"),
+            &InstantiateMsg {
+                name: "Luckee NFT".to_string(),
+                symbol: "LUCKEE".to_string(),
+                minter: MINTER.to_string(),
+                base_uri: None,
+            },
+            &[],
+            "Luckee NFT",
+            None,
+        )
+        .unwrap();
+    (app, addr)
+}
+
+/// 根据模型当前状态生成一个可执行的随机操作
+fn random_action(rng: &mut Rng, model: &Model) -> Action {
+    let user = |rng: &mut Rng| USERS[rng.below(USERS.len())].to_string();
+    let random_token = |rng: &mut Rng| {
+        let ids: Vec<u64> = model.tokens.keys().copied().collect();
+        ids[rng.below(ids.len())]
+    };
+
+    match rng.below(4) {
+        1 if !model.tokens.is_empty() => {
+            let token_id = random_token(rng);
+            Action::Transfer { sender: model.tokens[&token_id].0.clone(), token_id, recipient: user(rng) }
+        }
+        2 if !model.tokens.is_empty() => {
+            let token_id = random_token(rng);
+            Action::Burn { sender: model.tokens[&token_id].0.clone(), token_id }
+        }
+        3 => {
+            // 2 个四叶草合成流萤，2 个流萤合成赤色锦鲤
+            let owner = user(rng);
+            let (input_kind, target) = if rng.below(2) == 0 {
+                (NftKind::Clover, NftKind::Firefly)
+            } else {
+                (NftKind::Firefly, NftKind::CrimsonKoi)
+            };
+            let held = model.tokens_of(&owner, Some(&input_kind));
+            if held.len() < 2 {
+                return Action::Mint { owner, kind: input_kind };
+            }
+            Action::Synthesize { sender: owner, inputs: held[..2].to_vec(), target }
+        }
+        _ => Action::Mint { owner: user(rng), kind: KINDS[rng.below(2)].clone() },
+    }
+}
+
+/// 对合约执行操作，并在成功后同步更新模型
+fn apply(app: &mut App, contract: &Addr, model: &mut Model, action: &Action) {
+    match action {
+        Action::Mint { owner, kind } => {
+            let token_id = model.next_token_id;
+            app.execute_contract(
+                Addr::unchecked(MINTER),
+                contract.clone(),
+                &ExecuteMsg::Mint {
+                    token_id,
+                    owner: owner.clone(),
+                    extension: NftMeta {
+                        kind: kind.clone(),
+                        scale_origin: Scale::Tiny,
+                        physical_sku: None,
+                        crafted_from: None,
+                        series_id: "series_1".to_string(),
+                        collection_group_id: None,
+                        serial_in_series: token_id,
+                        expires_at: None,
+                    },
+                    idempotency_key: None,
+                },
+                &[],
+            )
+            .unwrap();
+            model.tokens.insert(token_id, (owner.clone(), kind.clone()));
+            model.next_token_id += 1;
+        }
+        Action::Transfer { sender, token_id, recipient } => {
+            app.execute_contract(
+                Addr::unchecked(sender),
+                contract.clone(),
+                &ExecuteMsg::TransferNft { recipient: recipient.clone(), token_id: *token_id },
+                &[],
+            )
+            .unwrap();
+            model.tokens.get_mut(token_id).unwrap().0 = recipient.clone();
+        }
+        Action::Burn { sender, token_id } => {
+            app.execute_contract(Addr::unchecked(sender), contract.clone(), &ExecuteMsg::Burn { token_id: *token_id }, &[])
+                .unwrap();
+            model.tokens.remove(token_id);
+        }
+        Action::Synthesize { sender, inputs, target } => {
+            app.execute_contract(
+                Addr::unchecked(sender),
+                contract.clone(),
+                &ExecuteMsg::Synthesize { inputs: inputs.clone(), target: target.clone() },
+                &[],
+            )
+            .unwrap();
+            for token_id in inputs {
+                model.tokens.remove(token_id);
+            }
+            // 合成产出使用下一个 NFT ID
+            model.tokens.insert(model.next_token_id, (sender.clone(), target.clone()));
+            model.next_token_id += 1;
+        }
+    }
+}
+
+/// 分页读取全部结果
+fn collect_pages(mut page: impl FnMut(Option<u64>) -> Vec<u64>) -> Vec<u64> {
+    let mut all = vec![];
+    let mut start_after = None;
+    loop {
+        let tokens = page(start_after);
+        if tokens.is_empty() {
+            return all;
+        }
+        start_after = tokens.last().copied();
+        all.extend(tokens);
+    }
+}
+
+/// 检查合约状态与模型一致
+fn assert_consistent(app: &App, contract: &Addr, model: &Model) {
+    let wrap = app.wrap();
+
+    // 总供应量
+    let stats: StorageStatsResponse = wrap.query_wasm_smart(contract, &QueryMsg::StorageStats {}).unwrap();
+    assert_eq!(stats.tokens, model.tokens.len() as u64, "total supply");
+
+    // 全部 NFT 列表
+    let all = collect_pages(|start_after| {
+        let res: cw721::TokensResponse =
+            wrap.query_wasm_smart(contract, &QueryMsg::AllTokens { start_after, limit: None }).unwrap();
+        res.tokens.iter().map(|id| id.parse().unwrap()).collect()
+    });
+    assert_eq!(all, model.tokens.keys().copied().collect::<Vec<_>>(), "all tokens");
+
+    // 每个 NFT 的所有者
+    for (token_id, (owner, _)) in &model.tokens {
+        let res: cw721::OwnerOfResponse = wrap
+            .query_wasm_smart(contract, &QueryMsg::OwnerOf { token_id: *token_id, include_expired: None })
+            .unwrap();
+        assert_eq!(&res.owner, owner, "owner of {}", token_id);
+    }
+
+    // 所有者索引
+    for user in USERS {
+        let owned = collect_pages(|start_after| {
+            let res: cw721::TokensResponse = wrap
+                .query_wasm_smart(contract, &QueryMsg::Tokens { owner: user.to_string(), start_after, limit: None })
+                .unwrap();
+            res.tokens.iter().map(|id| id.parse().unwrap()).collect()
+        });
+        assert_eq!(owned, model.tokens_of(user, None), "tokens of {}", user);
+    }
+
+    // 类型索引
+    for kind in &KINDS {
+        let mut by_kind = vec![];
+        let mut start_after = None;
+        loop {
+            let res: TokensByKindResponse = wrap
+                .query_wasm_smart(contract, &QueryMsg::TokensByKind { kind: kind.clone(), start_after, limit: None })
+                .unwrap();
+            by_kind.extend(res.tokens);
+            if !res.has_more {
+                break;
+            }
+            start_after = res.next_cursor;
+        }
+        let expected: BTreeSet<u64> =
+            model.tokens.iter().filter(|(_, (_, k))| k == kind).map(|(id, _)| *id).collect();
+        assert_eq!(by_kind.into_iter().collect::<BTreeSet<_>>(), expected, "tokens of kind {:?}", kind);
+    }
+}
+
+fn run_sequence(seed: u64, steps: usize) {
+    let (mut app, contract) = setup();
+    let mut model = Model { next_token_id: 1, ..Model::default() };
+    let mut rng = Rng(seed);
+
+    for step in 0..steps {
+        let action = random_action(&mut rng, &model);
+        println!("seed {} step {}: {:?}", seed, step, action);
+        apply(&mut app, &contract, &mut model, &action);
+        assert_consistent(&app, &contract, &model);
+    }
+}
+
+#[test]
+fn test_model_random_sequences() {
+    for seed in [0x9e37_79b9_7f4a_7c15, 0x1234_5678, 0xdead_beef, 42] {
+        run_sequence(seed, 150);
+    }
+}
+
+#[test]
+fn test_model_rejects_non_owner_transfer() {
+    let (mut app, contract) = setup();
+    let mut model = Model { next_token_id: 1, ..Model::default() };
+    apply(&mut app, &contract, &mut model, &Action::Mint { owner: "user1".to_string(), kind: NftKind::Clover });
+
+    // 失败的操作不改变任何状态
+    let res = app.execute_contract(
+        Addr::unchecked("user2"),
+        contract.clone(),
+        &ExecuteMsg::TransferNft { recipient: "user2".to_string(), token_id: 1 },
+        &[],
+    );
+    assert!(res.is_err());
+    assert_consistent(&app, &contract, &model);
+}
@@ -11,7 +11,7 @@ use cosmwasm_std::{Addr, Empty, Uint128};
 use cw_multi_test::{App, ContractWrapper, Executor};
 use luckee_nft::state::Expiration;
 
-use luckee_nft::contract::{execute, instantiate, query, migrate};
+use luckee_nft::contract::{execute, instantiate, query, migrate, reply};
 use luckee_nft::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
 use luckee_nft::types::{NftKind, NftMeta, Scale, Recipe, RecipeInput};
 
@@ -21,7 +21,8 @@ fn mock_app() -> App {
 
 fn contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
     let contract = ContractWrapper::new(execute, instantiate, query)
-        .with_migrate(migrate);
+        .with_migrate(migrate)
+        .with_reply(reply);
     Box::new(contract)
 }
 
@@ -57,7 +58,9 @@ fn test_no_std_basic_functionality() {
             series_id: "no_std_test".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let result = app.execute_contract(
@@ -128,10 +131,12 @@ fn test_no_std_synthesis_functionality() {
                 scale_origin: Scale::Tiny,
                 physical_sku: None,
                 crafted_from: None,
-                series_id: "synthesis_input".to_string(),
+                series_id: "synth_input".to_string(),
                 collection_group_id: None,
                 serial_in_series: i as u32,
+                expires_at: None,
             },
+            idempotency_key: None,
         };
 
         app.execute_contract(
@@ -201,6 +206,7 @@ fn test_no_std_batch_operations() {
                 series_id: "batch_test".to_string(),
                 collection_group_id: None,
                 serial_in_series: 1,
+                expires_at: None,
             },
         },
         luckee_nft::msg::BatchMintItem {
@@ -214,11 +220,12 @@ fn test_no_std_batch_operations() {
                 series_id: "batch_test".to_string(),
                 collection_group_id: None,
                 serial_in_series: 2,
+                expires_at: None,
             },
         },
     ];
 
-    let batch_mint_msg = ExecuteMsg::BatchMint { mints };
+    let batch_mint_msg = ExecuteMsg::BatchMint { mints, idempotency_key: None };
 
     let result = app.execute_contract(
         Addr::unchecked("minter"),
@@ -273,7 +280,9 @@ fn test_no_std_memory_management() {
                 series_id: "memory_test".to_string(),
                 collection_group_id: None,
                 serial_in_series: i as u32,
+                expires_at: None,
             },
+            idempotency_key: None,
         };
 
         let result = app.execute_contract(
@@ -380,7 +389,9 @@ fn test_no_std_performance() {
                 series_id: "performance_test".to_string(),
                 collection_group_id: None,
                 serial_in_series: i as u32,
+                expires_at: None,
             },
+            idempotency_key: None,
         };
 
         let result = app.execute_contract(
@@ -456,7 +467,9 @@ fn test_no_std_compatibility() {
             series_id: "compatibility_test".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     app.execute_contract(
@@ -527,7 +540,9 @@ fn test_no_std_migration() {
                 series_id: "migration_test".to_string(),
                 collection_group_id: None,
                 serial_in_series: i as u32,
+                expires_at: None,
             },
+            idempotency_key: None,
         };
 
         app.execute_contract(
@@ -548,7 +563,7 @@ fn test_no_std_migration() {
     assert_eq!(query_result.tokens.len(), 10);
 
     // 执行迁移（这里我们使用相同的合约代码，实际迁移会使用新版本）
-    let migrate_msg = luckee_nft::msg::MigrateMsg {};
+    let migrate_msg = luckee_nft::msg::MigrateMsg { allow_downgrade: false };
     
     let result = app.migrate_contract(
         Addr::unchecked("creator"),
@@ -610,7 +625,9 @@ fn test_no_std_edge_cases() {
                 series_id: "edge_case_test".to_string(),
                 collection_group_id: None,
                 serial_in_series: 1,
+                expires_at: None,
             },
+            idempotency_key: None,
         };
 
         let result = app.execute_contract(
@@ -1,10 +1,10 @@
-use cosmwasm_std::{Addr, Empty};
+use cosmwasm_std::{to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult, WasmMsg};
 use cw_multi_test::{App, ContractWrapper, Executor};
-use luckee_nft::msg::{ExecuteMsg, InstantiateMsg};
-use luckee_nft::types::{NftKind, NftMeta, Scale};
+use luckee_nft::msg::{BurnHookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, SessionLockResponse};
+use luckee_nft::types::{NftKind, NftMeta, Role, Scale};
 
 // 导入合约代码
-use luckee_nft::contract::{execute, instantiate, query, migrate};
+use luckee_nft::contract::{execute, instantiate, query, migrate, reply};
 
 fn mock_app() -> App {
     App::default()
@@ -12,10 +12,29 @@ fn mock_app() -> App {
 
 fn contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
     let contract = ContractWrapper::new(execute, instantiate, query)
-        .with_migrate(migrate);
+        .with_migrate(migrate)
+        .with_reply(reply);
     Box::new(contract)
 }
 
+/// 销毁监听合约：收到通知后回调 NFT 合约（重入）
+fn reentrant_listener() -> Box<dyn cw_multi_test::Contract<Empty>> {
+    fn execute(_deps: DepsMut, _env: Env, info: MessageInfo, _msg: BurnHookMsg) -> StdResult<Response> {
+        Ok(Response::new().add_message(WasmMsg::Execute {
+            contract_addr: info.sender.to_string(),
+            msg: to_json_binary(&ExecuteMsg::RevokeAll { operator: "operator".to_string() })?,
+            funds: vec![],
+        }))
+    }
+    fn instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+    fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        to_json_binary(&())
+    }
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
 #[test]
 fn test_enforce_external_config() {
     let mut app = mock_app();
@@ -48,7 +67,9 @@ fn test_enforce_external_config() {
             series_id: "series_1".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let res = app.execute_contract(
@@ -74,7 +95,9 @@ fn test_enforce_external_config() {
             series_id: "series_2".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
 
     let res = app.execute_contract(
@@ -102,7 +125,9 @@ fn test_enforce_external_config() {
             series_id: "series_3".to_string(),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
+        idempotency_key: None,
     };
     let res = app.execute_contract(
         Addr::unchecked("blind_box_contract"),
@@ -161,10 +186,11 @@ fn test_batch_limits() {
             series_id: format!("series_{}", i),
             collection_group_id: None,
             serial_in_series: 1,
+            expires_at: None,
         },
     }).collect();
 
-    let batch_mint_msg = ExecuteMsg::BatchMint { mints };
+    let batch_mint_msg = ExecuteMsg::BatchMint { mints, idempotency_key: None };
     let res = app.execute_contract(
         Addr::unchecked("blind_box_contract"),
         nft_contract_addr,
@@ -195,3 +221,445 @@ fn test_admin_cleanup_functions() {
 
     // 清理功能已被移除，所有操作都改为本地 CW721 模式
 }
+
+#[test]
+fn test_reentrant_burn_listener_rejected() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    let listener_id = app.store_code(reentrant_listener());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    // 部署会重入的监听合约并注册
+    let listener_addr = app.instantiate_contract(
+        listener_id,
+        Addr::unchecked("creator"),
+        &Empty {},
+        &[],
+        "Listener",
+        None,
+    ).unwrap();
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::AddBurnListener { address: listener_addr.to_string() },
+        &[],
+    ).unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("minter"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Mint {
+            token_id: 1,
+            owner: "user1".to_string(),
+            extension: NftMeta {
+                kind: NftKind::Clover,
+                scale_origin: Scale::Tiny,
+                physical_sku: None,
+                crafted_from: None,
+                series_id: "series_1".to_string(),
+                collection_group_id: None,
+                serial_in_series: 1,
+                expires_at: None,
+            },
+            idempotency_key: None,
+        },
+        &[],
+    ).unwrap();
+
+    // 监听合约在回调中重入，整个销毁交易被拒绝
+    let err = app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Burn { token_id: 1 },
+        &[],
+    ).unwrap_err();
+    assert!(err.root_cause().to_string().contains("Reentrant call rejected"));
+
+    // 移除监听合约后销毁成功，执行锁不会残留
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::RemoveBurnListener { address: listener_addr.to_string() },
+        &[],
+    ).unwrap();
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr,
+        &ExecuteMsg::Burn { token_id: 1 },
+        &[],
+    ).unwrap();
+}
+
+#[test]
+fn test_approve_kind_limits_operator_to_kind() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约（默认配方：2 个四叶草合成 1 个流萤）
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    // 为 user1 铸造 3 个四叶草和 1 个流萤
+    for token_id in 1..=4u64 {
+        let kind = if token_id == 4 { NftKind::Firefly } else { NftKind::Clover };
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "user1".to_string(),
+                extension: NftMeta {
+                    kind,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+
+    // 只授权合成合约操作四叶草
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::ApproveKind { operator: "crafter".to_string(), kind: NftKind::Clover, expires: None },
+        &[],
+    ).unwrap();
+
+    // 操作员不能转移流萤
+    let result = app.execute_contract(
+        Addr::unchecked("crafter"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::TransferNft { recipient: "crafter".to_string(), token_id: 4 },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // 操作员可代为合成，产出归所有者
+    let res = app.execute_contract(
+        Addr::unchecked("crafter"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::Synthesize { inputs: vec![1, 2], target: NftKind::Firefly },
+        &[],
+    ).unwrap();
+    let wasm_event = res.events.iter().find(|e| e.ty == "wasm").unwrap();
+    let output_id: u64 = wasm_event.attributes.iter()
+        .find(|a| a.key == "output_token_id").unwrap().value.parse().unwrap();
+    let owner: cw721::OwnerOfResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &luckee_nft::msg::QueryMsg::OwnerOf { token_id: output_id, include_expired: None })
+        .unwrap();
+    assert_eq!(owner.owner, "user1");
+
+    // 撤销后操作员不能再销毁四叶草
+    app.execute_contract(
+        Addr::unchecked("user1"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::RevokeKind { operator: "crafter".to_string(), kind: NftKind::Clover },
+        &[],
+    ).unwrap();
+    let result = app.execute_contract(
+        Addr::unchecked("crafter"),
+        nft_contract_addr,
+        &ExecuteMsg::Burn { token_id: 3 },
+        &[],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_session_lock_blocks_transfer_until_deadline() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    
+    // 部署NFT合约
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "minter".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    for token_id in 1..=2u64 {
+        app.execute_contract(
+            Addr::unchecked("minter"),
+            nft_contract_addr.clone(),
+            &ExecuteMsg::Mint {
+                token_id,
+                owner: "player".to_string(),
+                extension: NftMeta {
+                    kind: NftKind::Clover,
+                    scale_origin: Scale::Tiny,
+                    physical_sku: None,
+                    crafted_from: None,
+                    series_id: "series_1".to_string(),
+                    collection_group_id: None,
+                    serial_in_series: token_id,
+                    expires_at: None,
+                },
+                idempotency_key: None,
+            },
+            &[],
+        ).unwrap();
+    }
+
+    let until = app.block_info().time.seconds() + 600;
+    let lock_msg = ExecuteMsg::LockForSession { token_ids: vec![1, 2], session_id: "match-1".to_string(), until };
+
+    // 没有游戏服务器角色不能锁定
+    let result = app.execute_contract(Addr::unchecked("game"), nft_contract_addr.clone(), &lock_msg, &[]);
+    assert!(result.is_err());
+
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetRole { role: Role::GameServer, address: "game".to_string(), granted: true },
+        &[],
+    ).unwrap();
+    app.execute_contract(Addr::unchecked("game"), nft_contract_addr.clone(), &lock_msg, &[]).unwrap();
+
+    // 锁定期间不能转移
+    let result = app.execute_contract(
+        Addr::unchecked("player"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::TransferNft { recipient: "buyer".to_string(), token_id: 1 },
+        &[],
+    );
+    assert!(result.unwrap_err().root_cause().to_string().contains("locked for a game session"));
+
+    // 提前解锁后 token 1 可转移
+    app.execute_contract(
+        Addr::unchecked("game"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::UnlockSession { session_id: "match-1".to_string() },
+        &[],
+    ).unwrap();
+    app.execute_contract(
+        Addr::unchecked("player"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::TransferNft { recipient: "buyer".to_string(), token_id: 1 },
+        &[],
+    ).unwrap();
+
+    // 重新锁定 token 2，到期后自动解锁
+    let until = app.block_info().time.seconds() + 600;
+    app.execute_contract(
+        Addr::unchecked("game"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::LockForSession { token_ids: vec![2], session_id: "match-2".to_string(), until },
+        &[],
+    ).unwrap();
+    let res: SessionLockResponse = app
+        .wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::SessionLock { token_id: 2 })
+        .unwrap();
+    assert_eq!(res.lock.unwrap().session_id, "match-2");
+
+    app.update_block(|block| block.time = block.time.plus_seconds(600));
+    let res: SessionLockResponse = app
+        .wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::SessionLock { token_id: 2 })
+        .unwrap();
+    assert!(res.lock.is_none());
+    app.execute_contract(
+        Addr::unchecked("player"),
+        nft_contract_addr,
+        &ExecuteMsg::TransferNft { recipient: "buyer".to_string(), token_id: 2 },
+        &[],
+    ).unwrap();
+}
+
+#[test]
+fn test_relay_mint_requires_role_quota_and_valid_voucher() {
+    use luckee_nft::msg::{MintVoucher, MintVoucherStatusResponse, RelayQuotaResponse};
+
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "blind_box_contract".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+
+    let voucher = MintVoucher {
+        contract: nft_contract_addr.to_string(),
+        recipient: "player".to_string(),
+        kind: NftKind::Clover,
+        series_id: "kiosk_2025".to_string(),
+        nonce: 7,
+        expires_at: app.block_info().time.seconds() + 600,
+    };
+    let relay_msg = ExecuteMsg::RelayMint {
+        payload: to_json_binary(&voucher).unwrap(),
+        signature: Binary::from(vec![1u8; 64]),
+    };
+    let relay = |app: &mut App| {
+        app.execute_contract(Addr::unchecked("kiosk"), nft_contract_addr.clone(), &relay_msg, &[])
+            .unwrap_err()
+            .root_cause()
+            .to_string()
+    };
+
+    // 没有中继角色不能提交
+    assert!(relay(&mut app).contains("mint_relay"));
+    app.execute_contract(
+        Addr::unchecked("creator"),
+        nft_contract_addr.clone(),
+        &ExecuteMsg::SetRole { role: Role::MintRelay, address: "kiosk".to_string(), granted: true },
+        &[],
+    ).unwrap();
+
+    // 没有额度不能提交
+    assert!(relay(&mut app).contains("Relay quota exhausted"));
+    let set_quota = ExecuteMsg::SetRelayQuota { relayer: "kiosk".to_string(), quota: 2 };
+    assert!(app.execute_contract(Addr::unchecked("kiosk"), nft_contract_addr.clone(), &set_quota, &[]).is_err());
+    app.execute_contract(Addr::unchecked("creator"), nft_contract_addr.clone(), &set_quota, &[]).unwrap();
+
+    // 未配置签名者、签名无效时拒绝
+    assert!(relay(&mut app).contains("voucher signer not configured"));
+    let set_signer = |pubkey: Vec<u8>| ExecuteMsg::SetMintVoucherSigner { pubkey: Some(Binary::from(pubkey)) };
+    assert!(app.execute_contract(Addr::unchecked("creator"), nft_contract_addr.clone(), &set_signer(vec![2u8; 20]), &[]).is_err());
+    app.execute_contract(Addr::unchecked("creator"), nft_contract_addr.clone(), &set_signer(vec![2u8; 33]), &[]).unwrap();
+    assert!(relay(&mut app).contains("invalid signature"));
+
+    // 失败的提交不消耗额度和凭证序号
+    let quota: RelayQuotaResponse = app
+        .wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::RelayQuota { relayer: "kiosk".to_string() })
+        .unwrap();
+    assert!(quota.is_relayer);
+    assert_eq!(quota.remaining, 2);
+    let status: MintVoucherStatusResponse = app
+        .wrap()
+        .query_wasm_smart(nft_contract_addr, &QueryMsg::MintVoucherStatus { nonce: 7 })
+        .unwrap();
+    assert!(!status.spent);
+}
+
+#[test]
+fn test_lock_minter_set_is_permanent() {
+    use luckee_nft::msg::{MinterSetLockResponse, PendingMinterResponse};
+
+    let mut app = mock_app();
+    let contract_id = app.store_code(contract());
+    let nft_contract_addr = app.instantiate_contract(
+        contract_id,
+        Addr::unchecked("creator"),
+        &InstantiateMsg {
+            name: "Luckee NFT".to_string(),
+            symbol: "LUCKEE".to_string(),
+            minter: "blind_box_contract".to_string(),
+            base_uri: None,
+        },
+        &[],
+        "Luckee NFT",
+        None,
+    ).unwrap();
+    let execute_as = |app: &mut App, sender: &str, msg: &ExecuteMsg| {
+        app.execute_contract(Addr::unchecked(sender), nft_contract_addr.clone(), msg, &[])
+    };
+
+    // 锁定前可以正常调整铸造者，并提议轮换
+    execute_as(&mut app, "creator", &ExecuteMsg::SetMinter { minter: "partner".to_string(), allowed: true }).unwrap();
+    let activation_height = app.block_info().height + 100;
+    execute_as(&mut app, "creator", &ExecuteMsg::ProposeMinter {
+        new_minter: "new_minter".to_string(),
+        activation_height,
+    }).unwrap();
+
+    // 仅合约所有者可以锁定，锁定同时取消待生效的轮换
+    assert!(execute_as(&mut app, "blind_box_contract", &ExecuteMsg::LockMinterSet {}).is_err());
+    execute_as(&mut app, "creator", &ExecuteMsg::LockMinterSet {}).unwrap();
+    let lock: MinterSetLockResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::MinterSetLock {})
+        .unwrap();
+    assert!(lock.locked);
+    assert_eq!(lock.locked_at_height, Some(app.block_info().height));
+    let pending: PendingMinterResponse = app.wrap()
+        .query_wasm_smart(nft_contract_addr.clone(), &QueryMsg::PendingMinter {})
+        .unwrap();
+    assert!(pending.rotation.is_none());
+
+    // 锁定后所有铸造者变更均被拒绝
+    for msg in [
+        ExecuteMsg::SetMinter { minter: "intruder".to_string(), allowed: true },
+        ExecuteMsg::UpdateMinter { new_minter: "intruder".to_string() },
+        ExecuteMsg::ProposeMinter { new_minter: "intruder".to_string(), activation_height: activation_height + 1 },
+        ExecuteMsg::LockMinterSet {},
+        ExecuteMsg::SetMintVoucherSigner { pubkey: Some(Binary::from(vec![2u8; 33])) },
+        ExecuteMsg::SetRelayQuota { relayer: "intruder".to_string(), quota: 10 },
+        ExecuteMsg::SetRole { role: Role::MintRelay, address: "intruder".to_string(), granted: true },
+    ] {
+        let err = execute_as(&mut app, "creator", &msg).unwrap_err();
+        assert_eq!(err.root_cause().to_string(), "Minter set is locked");
+    }
+
+    // 其他运营角色不受锁定影响
+    execute_as(&mut app, "creator", &ExecuteMsg::SetRole {
+        role: Role::Airdrop,
+        address: "ops".to_string(),
+        granted: true,
+    }).unwrap();
+
+    // 已有铸造者仍可继续铸造
+    execute_as(&mut app, "partner", &ExecuteMsg::Mint {
+        token_id: 1,
+        owner: "user1".to_string(),
+        extension: NftMeta {
+            kind: NftKind::Clover,
+            scale_origin: Scale::Tiny,
+            physical_sku: None,
+            crafted_from: None,
+            series_id: "partner_series".to_string(),
+            collection_group_id: None,
+            serial_in_series: 0,
+            expires_at: None,
+        },
+        idempotency_key: None,
+    }).unwrap();
+}
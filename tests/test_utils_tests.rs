@@ -0,0 +1,49 @@
+//! 集成测试夹具（test-utils 特性）测试
+
+#![cfg(feature = "test-utils")]
+
+use luckee_nft::testing::LuckeeTestAppBuilder;
+use luckee_nft::types::{NftKind, Recipe, RecipeInput};
+
+#[test]
+fn test_builder_seeds_tokens_and_minters() {
+    let mut test_app = LuckeeTestAppBuilder::new()
+        .with_extra_minter("blind_box")
+        .with_tokens("alice", NftKind::Clover, 3)
+        .with_tokens("bob", NftKind::Firefly, 1)
+        .build()
+        .unwrap();
+
+    assert_eq!(test_app.tokens_of("alice").unwrap(), vec![1, 2, 3]);
+    assert_eq!(test_app.owner_of(4).unwrap(), "bob");
+    assert_eq!(test_app.tokens_by_kind(NftKind::Clover).unwrap(), vec![1, 2, 3]);
+
+    // 合成产出后继续铸造不会与产出 ID 冲突
+    let output = test_app.synthesize("alice", vec![1, 2], NftKind::Firefly).unwrap();
+    assert_eq!(output, 5);
+    assert_eq!(test_app.token_meta(output).unwrap().kind, NftKind::Firefly);
+    assert_eq!(test_app.mint("carol", NftKind::Clover).unwrap(), 6);
+
+    test_app.transfer("alice", "bob", 3).unwrap();
+    assert_eq!(test_app.owner_of(3).unwrap(), "bob");
+    test_app.burn("bob", 3).unwrap();
+    assert!(test_app.owner_of(3).is_err());
+}
+
+#[test]
+fn test_builder_overrides_recipe() {
+    let mut test_app = LuckeeTestAppBuilder::new()
+        .with_recipe(
+            NftKind::Firefly,
+            Recipe {
+                inputs: vec![RecipeInput { nft_kind: NftKind::Clover, count: 3 }],
+                output: NftKind::Firefly,
+                cost: None,
+            },
+        )
+        .with_tokens("alice", NftKind::Clover, 2)
+        .build()
+        .unwrap();
+
+    assert!(test_app.synthesize("alice", vec![1, 2], NftKind::Firefly).is_err());
+}